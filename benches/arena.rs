@@ -0,0 +1,99 @@
+//! 比较 Box 表达式树与 arena 表达式的 clone、遍历和释放开销
+//!
+//! 运行：cargo bench --features arena
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use sql_parser_lib::ast::arena::{ArenaExpr, ExprArena, ExprId};
+use sql_parser_lib::ast::expr::Expr;
+use sql_parser_lib::Parser;
+
+const ROUNDS: usize = 200;
+
+// 生成一个较大的 WHERE 条件：c0 = 0 AND (c1 + 1) * 2 > LOWER(c1) OR ...
+fn build_sql(terms: usize) -> String {
+    (0..terms)
+        .map(|i| format!("(c{i} + {i}) * 2 > LOWER(c{i}, 'v{i}') AND NOT c{i} = -{i}"))
+        .collect::<Vec<_>>()
+        .join(" OR ")
+}
+
+fn count_boxed(expr: &Expr) -> usize {
+    1 + match expr {
+        Expr::BinaryOp { left, right, .. } => count_boxed(left) + count_boxed(right),
+        Expr::FunctionCall { args, .. } => args.iter().map(count_boxed).sum(),
+        Expr::LogicalOp { expressions, .. } => expressions.iter().map(count_boxed).sum(),
+        Expr::UnaryOp { expr, .. }
+        | Expr::Collate { expr, .. }
+        | Expr::Introducer { expr, .. }
+        | Expr::Interval { value: expr, .. } => count_boxed(expr),
+        _ => 0,
+    }
+}
+
+fn count_arena(arena: &ExprArena, id: ExprId) -> usize {
+    1 + match arena.get(id) {
+        ArenaExpr::BinaryOp { left, right, .. } => count_arena(arena, *left) + count_arena(arena, *right),
+        ArenaExpr::FunctionCall { args: list, .. } | ArenaExpr::LogicalOp { expressions: list, .. } => {
+            arena.list(*list).iter().map(|id| count_arena(arena, *id)).sum()
+        }
+        ArenaExpr::UnaryOp { expr, .. }
+        | ArenaExpr::Collate { expr, .. }
+        | ArenaExpr::Introducer { expr, .. }
+        | ArenaExpr::Interval { value: expr, .. } => count_arena(arena, *expr),
+        _ => 0,
+    }
+}
+
+fn measure(name: &str, mut f: impl FnMut()) -> Duration {
+    // 预热
+    f();
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        f();
+    }
+    let elapsed = start.elapsed() / ROUNDS as u32;
+    println!("{:<28} {:>12?}", name, elapsed);
+    elapsed
+}
+
+fn main() {
+    let sql = build_sql(150);
+    let expr = Parser::new_from_sql(&sql).parse_expr(0).expect("benchmark SQL should parse");
+    let mut arena = ExprArena::new();
+    let root = arena.alloc(&expr);
+    assert_eq!(count_boxed(&expr), count_arena(&arena, root));
+    println!("expression nodes: {}", arena.len());
+
+    let boxed_clone = measure("boxed clone + drop", || {
+        black_box(black_box(&expr).clone());
+    });
+    let arena_clone = measure("arena clone + drop", || {
+        black_box(black_box(&arena).clone());
+    });
+
+    let boxed_walk = measure("boxed traversal", || {
+        black_box(count_boxed(black_box(&expr)));
+    });
+    let arena_walk = measure("arena traversal", || {
+        black_box(count_arena(black_box(&arena), root));
+    });
+    // 后序存放的节点可以直接线性扫描
+    let arena_scan = measure("arena linear scan", || {
+        black_box(black_box(&arena).nodes().iter().filter(|n| matches!(n, ArenaExpr::Literal(_))).count());
+    });
+
+    // 复用同一个arena反复放入表达式，不再为每个节点单独分配
+    let mut reused = ExprArena::with_capacity(arena.len());
+    let arena_reuse = measure("arena reuse (clear + alloc)", || {
+        reused.clear();
+        black_box(reused.alloc(black_box(&expr)));
+    });
+
+    println!();
+    println!("clone speedup:     {:.2}x", boxed_clone.as_secs_f64() / arena_clone.as_secs_f64());
+    println!("traversal speedup: {:.2}x", boxed_walk.as_secs_f64() / arena_walk.as_secs_f64());
+    println!("scan vs traversal: {:.2}x", boxed_walk.as_secs_f64() / arena_scan.as_secs_f64());
+    println!("reuse vs clone:    {:.2}x", boxed_clone.as_secs_f64() / arena_reuse.as_secs_f64());
+}
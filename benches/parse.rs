@@ -0,0 +1,95 @@
+//! 分别统计词法分析和解析时的内存分配次数和耗时
+//!
+//! 运行：cargo bench --bench parse
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use sql_parser_lib::token::tokenize;
+use sql_parser_lib::{Parser, StatementParser};
+
+const ROUNDS: usize = 200;
+
+// 统计分配次数的全局分配器
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+// 生成一个较大的 WHERE 条件：(c0 + 0) * 2 > LOWER(c0, 'v0') AND NOT c0 = -0 OR ...
+fn build_where(terms: usize) -> String {
+    (0..terms)
+        .map(|i| format!("(c{i} + {i}) * 2 > LOWER(c{i}, 'v{i}') AND NOT c{i} = -{i}"))
+        .collect::<Vec<_>>()
+        .join(" OR ")
+}
+
+fn main() {
+    let cases = [
+        ("point select", "SELECT id, name FROM users WHERE id = 1".to_string()),
+        (
+            "join + group by",
+            "SELECT u.id, COUNT(*) FROM users u JOIN orders o ON u.id = o.user_id \
+             WHERE o.status IN ('paid', 'shipped') GROUP BY u.id HAVING COUNT(*) > 1 ORDER BY u.id LIMIT 10"
+                .to_string(),
+        ),
+        ("insert", "INSERT INTO t (a, b, c) VALUES (1, 'x', NULL), (2, 'y', DEFAULT)".to_string()),
+        ("large where", format!("SELECT * FROM t WHERE {}", build_where(150))),
+    ];
+    println!(
+        "{:<20} {:>8} {:>15} {:>14} {:>14} {:>12}",
+        "case", "tokens", "allocs/tokenize", "time/tokenize", "allocs/parse", "time/parse"
+    );
+    for (name, sql) in &cases {
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        let start = Instant::now();
+        for _ in 0..ROUNDS {
+            black_box(tokenize(black_box(sql)));
+        }
+        let tokenize_elapsed = start.elapsed() / ROUNDS as u32;
+        let tokenize_allocations = (ALLOCATIONS.load(Ordering::Relaxed) - before) / ROUNDS;
+
+        let tokens = tokenize(sql);
+        let mut allocations = 0;
+        let mut elapsed = std::time::Duration::ZERO;
+        for _ in 0..ROUNDS {
+            // 只统计解析本身，token的复制不计入
+            let mut parser = Parser::new(tokens.clone());
+            let before = ALLOCATIONS.load(Ordering::Relaxed);
+            let start = Instant::now();
+            let stmt = parser.parse().expect("benchmark SQL should parse");
+            elapsed += start.elapsed();
+            allocations += ALLOCATIONS.load(Ordering::Relaxed) - before;
+            black_box(stmt);
+        }
+        println!(
+            "{:<20} {:>8} {:>15} {:>14?} {:>14} {:>12?}",
+            name,
+            tokens.len(),
+            tokenize_allocations,
+            tokenize_elapsed,
+            allocations / ROUNDS,
+            elapsed / ROUNDS as u32
+        );
+    }
+}
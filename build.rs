@@ -0,0 +1,152 @@
+//! 根据 keywords.json 和 types.json 生成关键字、类型名的查找表
+//!
+//! 生成 Keyword 枚举（包括各方言的保留字判断）和静态的 PHF 表，解析器按枚举值比较关键字，拼错的关键字在编译时就会报错；
+//! JSON 格式错误也在构建时报告，运行时不再解析 JSON。
+
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+// 关键字和类型名必须是大写的ASCII单词，查找时只把输入转换成大写
+fn check_word(file: &str, word: &str) {
+    assert!(
+        !word.is_empty() && word.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'_'),
+        "{}: {:?} must be an uppercase ASCII word",
+        file,
+        word
+    );
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(file: &str) -> T {
+    println!("cargo:rerun-if-changed={}", file);
+    let json = fs::read_to_string(file).unwrap_or_else(|e| panic!("failed to read {}: {}", file, e));
+    serde_json::from_str(&json).unwrap_or_else(|e| panic!("failed to parse {}: {}", file, e))
+}
+
+// 生成 phf::Set<&'static str>
+fn phf_set<'a>(out: &mut String, doc: &str, name: &str, words: impl Iterator<Item = &'a str>) {
+    let mut set = phf_codegen::Set::new();
+    for word in words {
+        set.entry(word);
+    }
+    writeln!(out, "/// {}", doc).unwrap();
+    writeln!(out, "pub static {}: phf::Set<&'static str> = {};\n", name, set.build()).unwrap();
+}
+
+// 读取 keywords.json 中的一组单词，例如 reserved 或 dialects.Postgres.reserved
+fn word_list<'a>(value: &'a Value, path: &str, set: &str) -> Vec<&'a str> {
+    let list = value.get(set).and_then(Value::as_array).unwrap_or_else(|| panic!("keywords.json is missing \"{}{}\"", path, set));
+    list.iter()
+        .map(|word| {
+            let word = word.as_str().unwrap_or_else(|| panic!("keywords.json: {}{} must be a list of strings", path, set));
+            check_word("keywords.json", word);
+            word
+        })
+        .collect()
+}
+
+fn main() {
+    let sets: Value = read_json("keywords.json");
+    let mut types: Vec<String> = read_json("types.json");
+
+    let mut keywords: Vec<(&str, bool)> = Vec::new();
+    for (set, reserved) in [("reserved", true), ("non_reserved", false)] {
+        keywords.extend(word_list(&sets, "", set).into_iter().map(|word| (word, reserved)));
+    }
+    keywords.sort();
+    for pair in keywords.windows(2) {
+        assert_ne!(pair[0].0, pair[1].0, "keyword {} is listed twice", pair[0].0);
+    }
+    let base: HashMap<&str, bool> = keywords.iter().copied().collect();
+
+    // 顶层的两个集合按 MySQL 的规则划分，其他方言在 dialects 中列出与 MySQL 不同的单词：
+    // reserved 是在该方言中保留的非保留字，non_reserved 是在该方言中可以作为标识符的保留字
+    let mut dialects: Vec<(&str, Vec<&str>)> = Vec::new();
+    let overrides = sets.get("dialects").and_then(Value::as_object).expect("keywords.json is missing \"dialects\"");
+    for (dialect, sets) in overrides {
+        assert_ne!(dialect, "MySql", "keywords.json: MySQL uses the top-level sets");
+        let path = format!("dialects.{}.", dialect);
+        let mut reserved: Vec<&str> = keywords.iter().filter(|(_, r)| *r).map(|(w, _)| *w).collect();
+        for (set, was_reserved) in [("reserved", false), ("non_reserved", true)] {
+            for word in word_list(sets, &path, set) {
+                assert_eq!(
+                    base.get(word),
+                    Some(&was_reserved),
+                    "keywords.json: {}{} lists {}, which must be a {} keyword",
+                    path,
+                    set,
+                    word,
+                    if was_reserved { "reserved" } else { "non-reserved" }
+                );
+                if was_reserved {
+                    reserved.retain(|w| *w != word);
+                } else {
+                    reserved.push(word);
+                }
+            }
+        }
+        reserved.sort();
+        dialects.push((dialect.as_str(), reserved));
+    }
+    for name in &types {
+        check_word("types.json", name);
+    }
+    // types.json 中允许重复的类型名
+    types.sort();
+    types.dedup();
+
+    let mut out = String::new();
+    out.push_str("/// 关键字，由 build.rs 根据 keywords.json 生成，按字母顺序排列\n");
+    out.push_str("#[allow(non_camel_case_types, clippy::upper_case_acronyms)]\n");
+    out.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]\n");
+    out.push_str("pub enum Keyword {\n");
+    for (word, _) in &keywords {
+        writeln!(out, "    {},", word).unwrap();
+    }
+    out.push_str("}\n\n");
+
+    out.push_str("/// 全部关键字，按字母顺序排列\n");
+    out.push_str("pub const ALL_KEYWORDS: &[Keyword] = &[\n");
+    for (word, _) in &keywords {
+        writeln!(out, "    Keyword::{},", word).unwrap();
+    }
+    out.push_str("];\n\n");
+
+    out.push_str("impl Keyword {\n");
+    out.push_str("    /// 关键字的大写形式\n");
+    out.push_str("    pub fn as_str(self) -> &'static str {\n        match self {\n");
+    for (word, _) in &keywords {
+        writeln!(out, "            Keyword::{0} => \"{0}\",", word).unwrap();
+    }
+    out.push_str("        }\n    }\n\n");
+
+    out.push_str("    /// 在指定方言中是否为保留字，保留字不能直接作为标识符使用\n");
+    out.push_str("    pub fn is_reserved(self, dialect: Dialect) -> bool {\n        match dialect {\n");
+    let mysql = keywords.iter().filter(|(_, r)| *r).map(|(w, _)| *w).collect();
+    for (dialect, reserved) in dialects.iter().chain([("MySql", mysql)].iter()) {
+        let variants: Vec<String> = reserved.iter().map(|w| format!("Keyword::{}", w)).collect();
+        writeln!(out, "            Dialect::{} => matches!(\n                self,\n                {}\n            ),", dialect, variants.join("\n                    | ")).unwrap();
+    }
+    out.push_str("        }\n    }\n}\n\n");
+
+    // 大写形式到关键字的映射
+    let mut map = phf_codegen::Map::new();
+    let values: Vec<String> = keywords.iter().map(|(word, _)| format!("Keyword::{}", word)).collect();
+    for ((word, _), value) in keywords.iter().zip(&values) {
+        map.entry(*word, value);
+    }
+    writeln!(out, "static KEYWORD_MAP: phf::Map<&'static str, Keyword> = {};\n", map.build()).unwrap();
+
+    let words = |reserved: bool| keywords.iter().filter(move |(_, r)| *r == reserved).map(|(w, _)| *w);
+    phf_set(&mut out, "MySQL 的保留字，不能直接作为标识符使用，必须加反引号", "RESERVED_KEYWORDS", words(true));
+    phf_set(&mut out, "MySQL 的非保留字，在标识符位置可以直接作为列名、表名等使用", "NON_RESERVED_KEYWORDS", words(false));
+    phf_set(&mut out, "全部关键字，词法分析使用", "KEYWORDS", keywords.iter().map(|(w, _)| *w));
+    phf_set(&mut out, "不带参数的类型名，例如 INT、VARCHAR", "TYPES", types.iter().map(String::as_str));
+
+    let path = Path::new(&env::var("OUT_DIR").unwrap()).join("keywords.rs");
+    fs::write(path, out).expect("failed to write generated keyword tables");
+}
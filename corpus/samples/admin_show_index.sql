@@ -0,0 +1 @@
+SHOW INDEX FROM users FROM app WHERE Key_name = 'PRIMARY'
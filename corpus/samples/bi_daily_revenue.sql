@@ -0,0 +1,7 @@
+SELECT LOWER(region) AS region, SUM(amount * (1 - discount)) AS revenue
+FROM orders
+WHERE created_at >= '2024-01-01' AND status != 'cancelled'
+GROUP BY 1
+HAVING SUM(amount) > 1000
+ORDER BY 2 DESC
+LIMIT 50
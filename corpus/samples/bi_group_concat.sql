@@ -0,0 +1,5 @@
+SELECT customer_id, GROUP_CONCAT(DISTINCT sku ORDER BY sku SEPARATOR ', ') AS skus, COUNT(id) AS orders
+FROM orders
+WHERE status = 'paid'
+GROUP BY customer_id
+ORDER BY 3 DESC
@@ -0,0 +1 @@
+SELECT `order report`.`total.amount`, `a``b` AS `x y` FROM `order report` WHERE `it's` > 0 ORDER BY `x y` DESC
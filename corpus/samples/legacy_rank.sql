@@ -0,0 +1,3 @@
+SELECT @pos := @pos + 1 AS pos, name, score
+FROM players
+ORDER BY score DESC
@@ -0,0 +1 @@
+INSERT INTO `users` VALUES (1,'alice@example.com','Alice','active','2024-01-02 03:04:05'),(2,'bob@example.com',NULL,'disabled','2024-02-03 04:05:06');
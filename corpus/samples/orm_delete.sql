@@ -0,0 +1 @@
+DELETE FROM `sessions` WHERE `sessions`.`expires_at` < NOW() ORDER BY `sessions`.`expires_at` LIMIT 1000
@@ -0,0 +1 @@
+SELECT `users`.`id`, `users`.`email`, `users`.`name` FROM `users` WHERE `users`.`status` = 'active' AND `users`.`id` > 100 ORDER BY `users`.`id` ASC LIMIT 20 OFFSET 40
@@ -0,0 +1 @@
+UPDATE `users` SET `name` = 'Alice B', `status` = 'active' WHERE `users`.`id` = 1 LIMIT 1
@@ -0,0 +1 @@
+INSERT INTO `sessions` (`id`, `user_id`, `expires_at`) VALUES ('s1', 1, NOW()) ON DUPLICATE KEY UPDATE `expires_at` = NOW()
@@ -0,0 +1 @@
+INSERT INTO `stock` (`sku`, `qty`) VALUES ('a1', 5), ('b2', 2) AS new ON DUPLICATE KEY UPDATE `qty` = `stock`.`qty` + new.qty, `updated` = VALUES(`updated`)
@@ -0,0 +1,114 @@
+//! 编译时检查的SQL
+//!
+//! `sql!("SELECT ...")` 在编译时解析SQL，有语法错误时编译失败并指出出错的位置；
+//! 通过检查后展开为解析这条SQL得到的 `SQLStatement`。可以在SQL之前指定方言：
+//!
+//! ```text
+//! let stmt = sql!("SELECT id FROM users WHERE id = 1");
+//! let stmt = sql!(dialect = Postgres, "SELECT DISTINCT ON (a) a FROM t");
+//! ```
+//!
+//! AST 中包含 String、Vec，不能作为常量，展开后的代码在运行时按相同的选项重新解析，
+//! 因此使用方启用的 sql_parser_lib 特性（例如 ddl、routines）需要与编译宏时相同，否则运行时可能解析失败。
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use sql_parser_lib::{Dialect, Parser, ParserOptions, StatementParser};
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, LitStr, Token};
+
+/// 编译时检查SQL，展开为 `sql_parser_lib::ast::SQLStatement`，语法见 crate 文档
+#[proc_macro]
+pub fn sql(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand(input.into()).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+// 宏的参数：[dialect = 方言,] "SQL"
+struct SqlInput {
+    dialect: Option<Ident>,
+    sql: LitStr,
+}
+
+impl Parse for SqlInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut dialect = None;
+        if input.peek(Ident) {
+            let key: Ident = input.parse()?;
+            if key != "dialect" {
+                return Err(syn::Error::new(key.span(), "expected `dialect = ...` or a string literal"));
+            }
+            input.parse::<Token![=]>()?;
+            dialect = Some(input.parse()?);
+            input.parse::<Token![,]>()?;
+        }
+        let sql = input.parse()?;
+        // 允许末尾的逗号
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+        Ok(SqlInput { dialect, sql })
+    }
+}
+
+fn dialect(name: &Ident) -> syn::Result<Dialect> {
+    match name.to_string().as_str() {
+        "MySql" => Ok(Dialect::MySql),
+        "Postgres" => Ok(Dialect::Postgres),
+        "MsSql" => Ok(Dialect::MsSql),
+        "BigQuery" => Ok(Dialect::BigQuery),
+        "Snowflake" => Ok(Dialect::Snowflake),
+        _ => Err(syn::Error::new(
+            name.span(),
+            "unknown dialect, expected one of MySql, Postgres, MsSql, BigQuery, Snowflake",
+        )),
+    }
+}
+
+fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let SqlInput { dialect: name, sql } = syn::parse2(input)?;
+    let options = ParserOptions {
+        dialect: name.as_ref().map(dialect).transpose()?.unwrap_or_default(),
+        ..ParserOptions::default()
+    };
+    let text = sql.value();
+    let mut parser = Parser::new_from_sql(&text).with_options(options);
+    if let Err(err) = parser.parse() {
+        return Err(syn::Error::new(sql.span(), err.to_string()));
+    }
+    let dialect = match name {
+        Some(name) => quote!(::sql_parser_lib::Dialect::#name),
+        None => quote!(::sql_parser_lib::Dialect::default()),
+    };
+    Ok(quote! {{
+        let options = ::sql_parser_lib::ParserOptions {
+            dialect: #dialect,
+            ..::sql_parser_lib::ParserOptions::default()
+        };
+        let mut parser = ::sql_parser_lib::Parser::new_from_sql(#sql).with_options(options);
+        ::sql_parser_lib::StatementParser::parse(&mut parser).expect("SQL checked by sql! at compile time")
+    }})
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn error(input: TokenStream) -> String {
+        expand(input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_expand() {
+        assert!(expand(quote!("SELECT id FROM users WHERE id = 1")).is_ok());
+        assert!(expand(quote!(dialect = Postgres, "SELECT DISTINCT ON (a) a FROM t",)).is_ok());
+        // MySQL 不支持 DISTINCT ON
+        assert!(expand(quote!("SELECT DISTINCT ON (a) a FROM t")).is_err());
+
+        let message = error(quote!("SELECT id FROM WHERE id = 1"));
+        assert!(message.contains("line 1, column 16"), "{}", message);
+        assert!(error(quote!("UPDATE t SET a = 'x")).contains("Unterminated string literal"));
+        assert!(error(quote!(dialect = Oracle, "SELECT 1")).starts_with("unknown dialect"));
+        assert!(error(quote!(options = MySql, "SELECT 1")).starts_with("expected `dialect"));
+        assert!(expand(quote!(1)).is_err());
+    }
+}
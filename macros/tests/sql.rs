@@ -0,0 +1,15 @@
+use sql_parser_lib::ast::SQLStatement;
+use sql_parser_lib::{Parser, StatementParser};
+use sql_parser_macros::sql;
+
+#[test]
+fn test_sql_macro() {
+    let stmt = sql!("SELECT id, name FROM users WHERE id = 1");
+    assert_eq!(stmt, Parser::new_from_sql("SELECT id, name FROM users WHERE id = 1").parse().unwrap());
+
+    let stmt = sql!(dialect = Postgres, "SELECT DISTINCT ON (a) a, b FROM t");
+    let SQLStatement::Select(select) = stmt else {
+        panic!("expected SELECT");
+    };
+    assert_eq!(select.distinct_on.map(|exprs| exprs.len()), Some(1));
+}
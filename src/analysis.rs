@@ -0,0 +1,1134 @@
+//! 基于AST的静态分析
+//!
+//! 只读取AST，不修改语句，例如统计 IN 列表的大小，供指纹归一化时折叠超长的列表，
+//! 提取对象之间的依赖关系，用于评估删除或修改一张表的影响，计算语句的复杂度，
+//! 在执行之前拒绝过于复杂的查询，比较语句的相似度，把同一条查询的不同变体聚在一起，
+//! 以及按表结构推断表达式的类型，发现字符串与整数比较之类的隐式转换。
+
+use crate::ast::common::{DataType, Ident, ObjectName, TableFunction, TableReference};
+#[cfg(feature = "ddl")]
+use crate::ast::alter::{AlterStatement, AlterTableOperation};
+#[cfg(feature = "ddl")]
+use crate::ast::create::{ColumnOption, ConstraintKind, CreateStatement, ForeignKeyReference};
+#[cfg(feature = "ddl")]
+use crate::ast::expr::AggregateFunction;
+use crate::ast::expr::{BinaryOperator, Expr, LogicalOperator, OrderByExpr, SortKey, Value};
+use crate::ast::select::{Cte, JoinKind, SelectColumn, SelectStatement};
+use crate::ast::SQLStatement;
+use crate::debug::expr_label;
+#[cfg(feature = "ddl")]
+use crate::schema::{SchemaProvider, TableSchema};
+use std::collections::HashMap;
+
+/// 一个 IN 值列表的大小
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InListSize {
+    /// 列表中的项数
+    pub len: usize,
+    /// 是否为 NOT IN
+    pub negated: bool,
+}
+
+/// 按前序收集表达式中所有 IN 值列表的大小
+///
+/// IN (SELECT ...) 不是值列表，不会被统计，子查询内部的表达式也不会被访问。
+pub fn in_list_sizes(expr: &Expr) -> Vec<InListSize> {
+    expr.iter()
+        .filter_map(|node| match node {
+            Expr::In { list, .. } if matches!(list.as_slice(), [Expr::Subquery(_)]) => None,
+            Expr::In { list, negated, .. } => Some(InListSize { len: list.len(), negated: *negated }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// 把 AND 连接的条件拆分为各个合取项，嵌套的 AND 同样展开
+///
+/// 不是 AND 的表达式本身就是唯一的一项。
+pub fn split_conjunction(expr: &Expr) -> Vec<&Expr> {
+    match expr {
+        Expr::LogicalOp { op: LogicalOperator::And, expressions } => {
+            expressions.iter().flat_map(split_conjunction).collect()
+        }
+        expr => vec![expr],
+    }
+}
+
+/// 提取只涉及某一张表、可以下推到这张表上执行的条件，table 为别名，没有别名时为表名
+///
+/// 候选的条件是 WHERE 以及内连接 ON 条件中的合取项，其中引用的列必须都属于这张表，
+/// 只有一张表时不带限定符的列也属于它。包含子查询、不引用任何列的条件不会返回。
+/// 外连接中可能补 NULL 的一侧（LEFT JOIN 的右表、RIGHT JOIN 的左侧）不能下推条件，返回空列表；
+/// 找不到这张表时同样返回空列表。
+pub fn predicates_for_table<'a>(select: &'a SelectStatement, table: &str) -> Vec<&'a Expr> {
+    let tables: Vec<&TableReference> = select.from.iter().chain(select.joins.iter().map(|join| &join.table)).collect();
+    let is_target = |reference: &TableReference| match &reference.alias {
+        Some(alias) => alias.eq_ignore_ascii_case(table),
+        None => reference.name.name().value.eq_ignore_ascii_case(table),
+    };
+    let Some(target) = tables.iter().position(|reference| is_target(reference)) else {
+        return Vec::new();
+    };
+    // 第 i 个连接的表是 tables[i + 1]
+    for (i, join) in select.joins.iter().enumerate() {
+        match join.kind {
+            JoinKind::Left if target == i + 1 => return Vec::new(),
+            JoinKind::Right if target <= i => return Vec::new(),
+            _ => {}
+        }
+    }
+
+    let reference = tables[target];
+    let single = tables.len() == 1;
+    let belongs = |qualifier: &[Ident]| match &reference.alias {
+        _ if qualifier.is_empty() => single,
+        Some(alias) => qualifier.len() == 1 && qualifier[0].value.eq_ignore_ascii_case(alias),
+        None => ObjectName(qualifier.to_vec()).matches(&reference.name),
+    };
+    let pushable = |conjunct: &Expr| {
+        let mut columns = 0;
+        let mut ok = true;
+        conjunct.walk(|node| match node {
+            Expr::Subquery(_) | Expr::Exists(_) => ok = false,
+            Expr::Identifier(name) if !name.name().value.starts_with('@') => {
+                columns += 1;
+                ok &= belongs(name.qualifier());
+            }
+            Expr::Match { columns: matched, .. } => {
+                columns += matched.len();
+                ok &= matched.iter().all(|name| belongs(name.qualifier()));
+            }
+            _ => {}
+        });
+        ok && columns > 0
+    };
+
+    let inner_on = select
+        .joins
+        .iter()
+        .filter(|join| !matches!(join.kind, JoinKind::Left | JoinKind::Right))
+        .filter_map(|join| join.on.as_ref());
+    select.where_clause.iter().chain(inner_on).flat_map(split_conjunction).filter(|conjunct| pushable(conjunct)).collect()
+}
+
+/// 依赖的来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DependencyKind {
+    /// 外键引用，包括列定义中的 REFERENCES 和 ALTER TABLE ADD FOREIGN KEY
+    ForeignKey,
+    /// INSERT ... SELECT 从其它表读取数据
+    InsertSelect,
+    /// 视图的查询读取的表、视图或 CTE
+    View,
+    /// CTE 的查询读取的表、视图或其它 CTE
+    Cte,
+}
+
+/// 一条依赖：from 引用了 to
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Dependency {
+    pub from: ObjectName,
+    pub to: ObjectName,
+    pub kind: DependencyKind,
+}
+
+/// 对象之间的依赖图，对象名的比较规则与 [`ObjectName::matches`] 相同
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Graph {
+    /// 按出现顺序排列的对象，包括定义的和被引用的
+    pub objects: Vec<ObjectName>,
+    /// 按出现顺序排列的依赖，相同的依赖只保留一条
+    pub edges: Vec<Dependency>,
+}
+
+impl Graph {
+    /// name 直接引用的对象
+    pub fn dependencies_of(&self, name: &ObjectName) -> Vec<&ObjectName> {
+        self.edges.iter().filter(|edge| edge.from.matches(name)).map(|edge| &edge.to).collect()
+    }
+
+    /// 直接引用 name 的对象
+    pub fn dependents_of(&self, name: &ObjectName) -> Vec<&ObjectName> {
+        self.edges.iter().filter(|edge| edge.to.matches(name)).map(|edge| &edge.from).collect()
+    }
+
+    /// 直接或间接引用 name 的对象，即删除 name 后会受影响的对象，按广度优先的顺序排列，不包括 name 本身
+    pub fn impacted_by(&self, name: &ObjectName) -> Vec<&ObjectName> {
+        let mut impacted: Vec<&ObjectName> = Vec::new();
+        let mut i = 0;
+        let mut current = name;
+        loop {
+            for dependent in self.dependents_of(current) {
+                if !dependent.matches(name) && !impacted.iter().any(|seen| seen.matches(dependent)) {
+                    impacted.push(dependent);
+                }
+            }
+            match impacted.get(i) {
+                Some(next) => current = next,
+                None => return impacted,
+            }
+            i += 1;
+        }
+    }
+
+    fn object(&mut self, name: &ObjectName) {
+        if !self.objects.iter().any(|object| object.matches(name)) {
+            self.objects.push(name.clone());
+        }
+    }
+
+    fn edge(&mut self, from: &ObjectName, to: &ObjectName, kind: DependencyKind) {
+        self.object(from);
+        self.object(to);
+        let exists = self.edges.iter().any(|edge| edge.kind == kind && edge.from.matches(from) && edge.to.matches(to));
+        if !exists {
+            self.edges.push(Dependency { from: from.clone(), to: to.clone(), kind });
+        }
+    }
+}
+
+/// 提取语句之间的对象依赖
+///
+/// CREATE TABLE 定义表并引用外键指向的表，ALTER TABLE 添加的外键同样计入；CREATE VIEW 定义的视图和
+/// INSERT ... SELECT 的目标表依赖查询中读取的表（包括子查询）。
+///
+/// 查询中的 CTE 也是图中的对象：引用 CTE 的视图或目标表依赖 CTE，CTE 依赖它的查询读取的对象，
+/// 这样删除表时能沿着 CTE 找到受影响的视图。CTE 以名称作为对象，不同语句中同名的 CTE 视为同一个对象。
+/// 其它语句只在图中加入所引用的表和 CTE，不产生依赖。
+pub fn dependencies(statements: &[SQLStatement]) -> Graph {
+    let mut graph = Graph::default();
+    for stmt in statements {
+        match stmt {
+            SQLStatement::Select(select) => query_dependencies(&mut graph, None, select),
+            SQLStatement::Insert(insert) => {
+                graph.object(&insert.table.name);
+                if let Some(select) = &insert.select_clause {
+                    query_dependencies(&mut graph, Some((&insert.table.name, DependencyKind::InsertSelect)), select);
+                }
+            }
+            SQLStatement::Update(update) => graph.object(&update.table.name),
+            SQLStatement::Delete(delete) => graph.object(&delete.table.name),
+            #[cfg(feature = "ddl")]
+            SQLStatement::Create(CreateStatement::Table(create)) => {
+                graph.object(&create.name);
+                let inline = create.columns.iter().flat_map(|column| &column.options).filter_map(|option| match option {
+                    ColumnOption::References(reference) => Some(reference),
+                    _ => None,
+                });
+                let constraints = create.constraints.iter().map(|constraint| &constraint.kind);
+                for reference in inline.chain(foreign_keys(constraints)) {
+                    graph.edge(&create.name, &reference.table, DependencyKind::ForeignKey);
+                }
+            }
+            #[cfg(feature = "ddl")]
+            SQLStatement::Create(CreateStatement::View(view)) => {
+                graph.object(&view.name);
+                query_dependencies(&mut graph, Some((&view.name, DependencyKind::View)), &view.query);
+            }
+            #[cfg(feature = "ddl")]
+            SQLStatement::Create(CreateStatement::Routine(_)) => {}
+            #[cfg(feature = "ddl")]
+            SQLStatement::Alter(AlterStatement::Table(alter)) => {
+                graph.object(&alter.name);
+                let constraints = alter.operations.iter().filter_map(|operation| match operation {
+                    AlterTableOperation::AddConstraint(constraint) => Some(&constraint.kind),
+                    _ => None,
+                });
+                for reference in foreign_keys(constraints) {
+                    graph.edge(&alter.name, &reference.table, DependencyKind::ForeignKey);
+                }
+            }
+            SQLStatement::Show(_) | SQLStatement::Set(_) => {}
+        }
+    }
+    graph
+}
+
+// 查询引用的对象加入图中，有 owner 时 owner 依赖它们；查询中定义的 CTE 依赖各自的查询读取的对象
+fn query_dependencies(graph: &mut Graph, owner: Option<(&ObjectName, DependencyKind)>, select: &SelectStatement) {
+    let mut references = Vec::new();
+    let mut ctes = Vec::new();
+    collect_references(select, &mut references, &mut ctes);
+    for name in references {
+        match owner {
+            // 递归 CTE 对自身的引用不是依赖
+            Some((owner, _)) if owner.matches(name) => {}
+            Some((owner, kind)) => graph.edge(owner, name, kind),
+            None => graph.object(name),
+        }
+    }
+    for cte in ctes {
+        let name = ObjectName::new([cte.name.as_str()]);
+        query_dependencies(graph, Some((&name, DependencyKind::Cte)), &cte.query);
+    }
+}
+
+#[cfg(feature = "ddl")]
+fn foreign_keys<'a>(constraints: impl Iterator<Item = &'a ConstraintKind>) -> impl Iterator<Item = &'a ForeignKeyReference> {
+    constraints.filter_map(|kind| match kind {
+        ConstraintKind::ForeignKey { reference, .. } => Some(reference),
+        _ => None,
+    })
+}
+
+/// 语句的复杂度
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Complexity {
+    /// JOIN 的数量，包括子查询中的 JOIN
+    pub joins: usize,
+    /// 子查询的数量，包括派生表和表达式中的子查询
+    pub subqueries: usize,
+    /// 谓词的数量：比较、LIKE、IN、BETWEEN、IS NULL、MATCH ... AGAINST 和 ANY
+    pub predicates: usize,
+    /// 子查询嵌套的最大层数，没有子查询时为0
+    pub depth: usize,
+}
+
+/// 计算语句的复杂度
+///
+/// INSERT ... SELECT 中的查询是语句本身的一部分，不算作子查询。DDL 和 SHOW 语句的复杂度为0。
+pub fn complexity(stmt: &SQLStatement) -> Complexity {
+    let mut counter = ComplexityCounter::default();
+    match stmt {
+        SQLStatement::Select(select) => counter.select(select),
+        SQLStatement::Insert(insert) => {
+            counter.table(&insert.table);
+            insert.values.iter().flatten().flatten().for_each(|cell| counter.expr(cell));
+            insert.set_clause.iter().flatten().for_each(|(_, value)| counter.expr(value));
+            if let Some(select) = &insert.select_clause {
+                counter.select(select);
+            }
+            for (_, value) in insert.on_duplicate.iter().flat_map(|clause| &clause.updates) {
+                counter.expr(value);
+            }
+        }
+        SQLStatement::Update(update) => {
+            counter.table(&update.table);
+            update.assignments.iter().for_each(|(_, value)| counter.expr(value));
+            update.where_clause.iter().for_each(|expr| counter.expr(expr));
+            sort_exprs(update.order_by.iter().flatten()).for_each(|expr| counter.expr(expr));
+        }
+        SQLStatement::Delete(delete) => {
+            counter.table(&delete.table);
+            delete.where_clause.iter().for_each(|expr| counter.expr(expr));
+            sort_exprs(delete.order_by.iter().flatten()).for_each(|expr| counter.expr(expr));
+        }
+        _ => {}
+    }
+    counter.complexity
+}
+
+#[derive(Default)]
+struct ComplexityCounter {
+    complexity: Complexity,
+    // 当前所在子查询的层数
+    level: usize,
+}
+
+impl ComplexityCounter {
+    fn select(&mut self, select: &SelectStatement) {
+        for cte in select.with.iter().flat_map(|with| &with.ctes) {
+            self.subquery(&cte.query);
+        }
+        self.complexity.joins += select.joins.len();
+        for table in select.from.iter().chain(select.joins.iter().map(|join| &join.table)) {
+            self.table(table);
+        }
+        select_exprs(select).for_each(|expr| self.expr(expr));
+        // 集合运算的各个查询与第一个查询处于同一层
+        for operation in &select.set_operations {
+            self.select(&operation.select);
+        }
+    }
+
+    fn table(&mut self, table: &TableReference) {
+        if let Some(subquery) = &table.subquery {
+            self.subquery(subquery);
+        }
+        match table.function.as_deref() {
+            Some(TableFunction::JsonTable { expr, .. }) => self.expr(expr),
+            Some(TableFunction::Call { args, .. }) => args.iter().for_each(|arg| self.expr(arg)),
+            None => {}
+        }
+    }
+
+    fn subquery(&mut self, select: &SelectStatement) {
+        self.complexity.subqueries += 1;
+        self.level += 1;
+        self.complexity.depth = self.complexity.depth.max(self.level);
+        self.select(select);
+        self.level -= 1;
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        expr.walk(|node| match node {
+            Expr::BinaryOp { op, .. } => {
+                let comparison = matches!(
+                    op,
+                    BinaryOperator::Eq
+                        | BinaryOperator::NotEq
+                        | BinaryOperator::Lt
+                        | BinaryOperator::LtEq
+                        | BinaryOperator::Gt
+                        | BinaryOperator::GtEq
+                        | BinaryOperator::Like
+                );
+                self.complexity.predicates += comparison as usize;
+            }
+            Expr::In { .. } | Expr::Between { .. } | Expr::IsNull { .. } | Expr::Match { .. } | Expr::Any { .. } => {
+                self.complexity.predicates += 1;
+            }
+            Expr::Subquery(select) | Expr::Exists(select) => self.subquery(select),
+            _ => {}
+        });
+    }
+}
+
+/// 复杂度的上限，None 表示不限制
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ComplexityLimits {
+    /// SQL 文本的最大字节数，由 [`ComplexityLimits::check_length`] 在解析之前检查
+    pub length: Option<usize>,
+    pub joins: Option<usize>,
+    pub subqueries: Option<usize>,
+    pub predicates: Option<usize>,
+    pub depth: Option<usize>,
+}
+
+/// 复杂度的指标
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ComplexityMetric {
+    Length,
+    Joins,
+    Subqueries,
+    Predicates,
+    Depth,
+}
+
+/// 超出上限的指标
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ComplexityViolation {
+    pub metric: ComplexityMetric,
+    pub actual: usize,
+    pub limit: usize,
+}
+
+impl ComplexityLimits {
+    /// 检查SQL文本的长度，可以在解析之前调用，避免解析过长的输入
+    pub fn check_length(&self, sql: &str) -> Option<ComplexityViolation> {
+        let limit = self.length?;
+        (sql.len() > limit).then_some(ComplexityViolation { metric: ComplexityMetric::Length, actual: sql.len(), limit })
+    }
+
+    /// 检查语句的复杂度，按 joins、subqueries、predicates、depth 的顺序返回超出上限的指标
+    pub fn check(&self, complexity: &Complexity) -> Vec<ComplexityViolation> {
+        [
+            (ComplexityMetric::Joins, complexity.joins, self.joins),
+            (ComplexityMetric::Subqueries, complexity.subqueries, self.subqueries),
+            (ComplexityMetric::Predicates, complexity.predicates, self.predicates),
+            (ComplexityMetric::Depth, complexity.depth, self.depth),
+        ]
+        .into_iter()
+        .filter_map(|(metric, actual, limit)| limit.filter(|limit| actual > *limit).map(|limit| ComplexityViolation { metric, actual, limit }))
+        .collect()
+    }
+}
+
+/// 两条语句的相似度，范围为0到1，1 表示两条语句的特征完全相同
+///
+/// 把语句拆分为特征的多重集合：语句类型、从句、连接方式、表名、列名、函数、运算符以及字面量的类型，
+/// 按加权的 Jaccard 系数（各特征较小次数之和除以较大次数之和）比较。名称不区分大小写，
+/// 列名只取最后一段，字面量的值、别名和书写格式都不影响结果；DDL 和 SHOW 语句只比较语句类型。
+pub fn similarity(a: &SQLStatement, b: &SQLStatement) -> f64 {
+    let (a, b) = (Features::of(a), Features::of(b));
+    let mut shared = 0;
+    let mut total = 0;
+    for (feature, &count) in &a.0 {
+        let other = b.0.get(feature).copied().unwrap_or(0);
+        shared += count.min(other);
+        total += count.max(other);
+    }
+    total += b.0.iter().filter(|(feature, _)| !a.0.contains_key(*feature)).map(|(_, count)| count).sum::<usize>();
+    if total == 0 {
+        return 1.0;
+    }
+    shared as f64 / total as f64
+}
+
+// 语句特征及其出现次数
+#[derive(Default)]
+struct Features(HashMap<String, usize>);
+
+impl Features {
+    fn of(stmt: &SQLStatement) -> Self {
+        let mut features = Features::default();
+        match stmt {
+            SQLStatement::Select(select) => features.select(select),
+            SQLStatement::Insert(insert) => {
+                features.add("INSERT");
+                features.table(&insert.table);
+                for column in insert.columns.iter().flatten() {
+                    features.add(format!("column {}", column.to_lowercase()));
+                }
+                insert.values.iter().flatten().flatten().for_each(|cell| features.expr(cell));
+                for (column, value) in insert.set_clause.iter().flatten() {
+                    features.assignment(column, value);
+                }
+                if let Some(select) = &insert.select_clause {
+                    features.select(select);
+                }
+                for (column, value) in insert.on_duplicate.iter().flat_map(|clause| &clause.updates) {
+                    features.add("ON DUPLICATE KEY UPDATE");
+                    features.assignment(column, value);
+                }
+            }
+            SQLStatement::Update(update) => {
+                features.add("UPDATE");
+                features.table(&update.table);
+                for (column, value) in &update.assignments {
+                    features.assignment(column, value);
+                }
+                features.filter(update.where_clause.as_ref(), update.order_by.as_deref(), update.limit.is_some());
+            }
+            SQLStatement::Delete(delete) => {
+                features.add("DELETE");
+                features.table(&delete.table);
+                features.filter(delete.where_clause.as_ref(), delete.order_by.as_deref(), delete.limit.is_some());
+            }
+            #[cfg(feature = "ddl")]
+            SQLStatement::Create(_) => features.add("CREATE"),
+            #[cfg(feature = "ddl")]
+            SQLStatement::Alter(_) => features.add("ALTER"),
+            SQLStatement::Show(_) => features.add("SHOW"),
+            SQLStatement::Set(_) => features.add("SET"),
+        }
+        features
+    }
+
+    fn add(&mut self, feature: impl Into<String>) {
+        *self.0.entry(feature.into()).or_default() += 1;
+    }
+
+    fn select(&mut self, select: &SelectStatement) {
+        if let Some(with) = &select.with {
+            self.add(if with.recursive { "WITH RECURSIVE" } else { "WITH" });
+            with.ctes.iter().for_each(|cte| self.select(&cte.query));
+        }
+        self.add("SELECT");
+        let clauses = [
+            ("DISTINCT", select.distinct),
+            ("WHERE", select.where_clause.is_some()),
+            ("GROUP BY", select.group_by.is_some()),
+            ("HAVING", select.having.is_some()),
+            ("QUALIFY", select.qualify.is_some()),
+            ("WINDOW", !select.windows.is_empty()),
+            ("ORDER BY", select.order_by.is_some()),
+            ("LIMIT", select.limit.is_some()),
+        ];
+        for (clause, present) in clauses {
+            if present {
+                self.add(clause);
+            }
+        }
+        for join in &select.joins {
+            self.add(format!("JOIN {:?}", join.kind));
+        }
+        for table in select.from.iter().chain(select.joins.iter().map(|join| &join.table)) {
+            self.table(table);
+        }
+        select_exprs(select).for_each(|expr| self.expr(expr));
+        for operation in &select.set_operations {
+            self.add(if operation.all { format!("{} ALL", operation.op.name()) } else { operation.op.name().to_string() });
+            self.select(&operation.select);
+        }
+    }
+
+    // UPDATE、DELETE 的 WHERE、ORDER BY、LIMIT 从句
+    fn filter(&mut self, where_clause: Option<&Expr>, order_by: Option<&[OrderByExpr]>, limit: bool) {
+        if let Some(expr) = where_clause {
+            self.add("WHERE");
+            self.expr(expr);
+        }
+        if let Some(order_by) = order_by {
+            self.add("ORDER BY");
+            sort_exprs(order_by.iter()).for_each(|expr| self.expr(expr));
+        }
+        if limit {
+            self.add("LIMIT");
+        }
+    }
+
+    fn assignment(&mut self, column: &ObjectName, value: &Expr) {
+        self.add(format!("set {}", column.name().value.to_lowercase()));
+        self.expr(value);
+    }
+
+    fn table(&mut self, table: &TableReference) {
+        if let Some(subquery) = &table.subquery {
+            self.add("derived table");
+            self.select(subquery);
+        } else if let Some(function) = &table.function {
+            match &**function {
+                TableFunction::JsonTable { expr, .. } => {
+                    self.add("function JSON_TABLE");
+                    self.expr(expr);
+                }
+                TableFunction::Call { name, args, .. } => {
+                    self.add(format!("function {}", name.to_uppercase()));
+                    args.iter().for_each(|arg| self.expr(arg));
+                }
+            }
+        } else {
+            self.add(format!("table {}", table.name.to_string().to_lowercase()));
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        expr.walk(|node| match node {
+            Expr::Identifier(name) => self.add(format!("column {}", name.name().value.to_lowercase())),
+            Expr::Literal(value) => {
+                let kind = match value {
+                    Value::String(_) => "string",
+                    Value::Integer(_) | Value::Float(_) | Value::Decimal(_) => "number",
+                    Value::Boolean(_) => "boolean",
+                    Value::Null => "null",
+                    Value::DEFAULT => "default",
+                };
+                self.add(format!("literal {}", kind));
+            }
+            Expr::Subquery(select) => {
+                self.add("subquery");
+                self.select(select);
+            }
+            Expr::Exists(select) => {
+                self.add("EXISTS");
+                self.select(select);
+            }
+            node => self.add(expr_label(node).to_uppercase()),
+        });
+    }
+}
+
+/// 表达式的类型，按 MySQL 的类型分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SqlType {
+    Integer,
+    Decimal,
+    Float,
+    String,
+    Boolean,
+    Date,
+    Time,
+    DateTime,
+    Binary,
+    Json,
+    /// NULL 字面量
+    Null,
+    /// 无法确定，例如未知的列或函数
+    Unknown,
+}
+
+impl SqlType {
+    /// 列定义或 CAST 中的类型对应的分类，不认识的类型为 Unknown
+    pub fn from_data_type(data_type: &DataType) -> SqlType {
+        match data_type.name.to_uppercase().as_str() {
+            "TINYINT" | "SMALLINT" | "MEDIUMINT" | "INT" | "INTEGER" | "BIGINT" | "SERIAL" | "YEAR" | "BIT" | "SIGNED"
+            | "UNSIGNED" => SqlType::Integer,
+            "DECIMAL" | "DEC" | "NUMERIC" | "FIXED" => SqlType::Decimal,
+            "FLOAT" | "DOUBLE" | "REAL" => SqlType::Float,
+            "CHAR" | "VARCHAR" | "NCHAR" | "NVARCHAR" | "TINYTEXT" | "TEXT" | "MEDIUMTEXT" | "LONGTEXT" | "ENUM" | "SET" => {
+                SqlType::String
+            }
+            "BOOL" | "BOOLEAN" => SqlType::Boolean,
+            "DATE" => SqlType::Date,
+            "TIME" => SqlType::Time,
+            "DATETIME" | "TIMESTAMP" => SqlType::DateTime,
+            "BINARY" | "VARBINARY" | "TINYBLOB" | "BLOB" | "MEDIUMBLOB" | "LONGBLOB" => SqlType::Binary,
+            "JSON" => SqlType::Json,
+            _ => SqlType::Unknown,
+        }
+    }
+
+    /// 是否为数值类型，MySQL 中布尔值就是整数
+    pub fn is_numeric(self) -> bool {
+        matches!(self, SqlType::Integer | SqlType::Decimal | SqlType::Float | SqlType::Boolean)
+    }
+
+    /// 是否为日期时间类型
+    pub fn is_temporal(self) -> bool {
+        matches!(self, SqlType::Date | SqlType::Time | SqlType::DateTime)
+    }
+
+    /// 两种类型的值能否直接比较，不能比较时 MySQL 会做隐式转换，结果往往出乎意料，索引也无法使用
+    ///
+    /// 只有数值与字符串不能比较；日期时间与数值、字符串比较时 MySQL 会把常量转换为日期时间，
+    /// 例如 `created > 20240101`，视为兼容。NULL 和 Unknown 与任何类型都能比较。
+    pub fn is_comparable(self, other: SqlType) -> bool {
+        let numeric_vs_string = |a: SqlType, b: SqlType| a.is_numeric() && b == SqlType::String;
+        !numeric_vs_string(self, other) && !numeric_vs_string(other, self)
+    }
+
+    // 算术运算的结果类型
+    #[cfg(feature = "ddl")]
+    fn arithmetic(self, op: &BinaryOperator, other: SqlType) -> SqlType {
+        use SqlType::*;
+        match (self, other) {
+            (Null, _) | (_, Null) => Null,
+            // 日期加减 INTERVAL 仍是日期
+            (left, Unknown) if left.is_temporal() => left,
+            (Unknown, _) | (_, Unknown) => Unknown,
+            (Float, _) | (_, Float) | (String, _) | (_, String) => Float,
+            _ if *op == BinaryOperator::Divide => Decimal,
+            (Decimal, _) | (_, Decimal) => Decimal,
+            (left, right) if left.is_numeric() && right.is_numeric() => Integer,
+            _ => Unknown,
+        }
+    }
+}
+
+/// 推断类型时可见的表，按别名或表名查找列
+#[cfg(feature = "ddl")]
+#[derive(Debug, Clone, Default)]
+pub struct TypeScope<'a> {
+    tables: Vec<(Option<String>, &'a TableSchema)>,
+}
+
+#[cfg(feature = "ddl")]
+impl<'a> TypeScope<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// SELECT 语句 FROM 和 JOIN 中的表，不包括子查询
+    pub fn for_select<P: SchemaProvider + ?Sized>(schema: &'a P, select: &SelectStatement) -> Self {
+        let mut scope = TypeScope::new();
+        for table in select.from.iter().chain(select.joins.iter().map(|join| &join.table)) {
+            scope.add_table(schema, table);
+        }
+        scope
+    }
+
+    /// 加入一张表，目录中没有的表和派生表被忽略
+    pub fn add_table<P: SchemaProvider + ?Sized>(&mut self, schema: &'a P, table: &TableReference) {
+        if table.subquery.is_some() || table.function.is_some() {
+            return;
+        }
+        if let Some(found) = schema.table(&table.name) {
+            self.tables.push((table.alias.clone(), found));
+        }
+    }
+
+    /// 列的类型，t.c 中的 t 匹配别名或表名，不带限定符的列名只在一张表中存在时才能确定
+    pub fn column_type(&self, name: &ObjectName) -> SqlType {
+        let column = name.name().value.as_str();
+        let qualifier = name.qualifier();
+        let mut types = self
+            .tables
+            .iter()
+            .filter(|(alias, table)| match (qualifier, alias) {
+            ([], _) => true,
+            ([q], Some(alias)) => alias.eq_ignore_ascii_case(&q.value),
+            (_, Some(_)) => false,
+            (_, None) => {
+                let parts = &table.name.0;
+                parts.len() >= qualifier.len()
+                    && parts[parts.len() - qualifier.len()..].iter().zip(qualifier).all(|(a, b)| a.value.eq_ignore_ascii_case(&b.value))
+            }
+            })
+            .filter_map(|(_, table)| table.column(column));
+        match (types.next(), types.next()) {
+            (Some(column), None) => SqlType::from_data_type(&column.data_type),
+            _ => SqlType::Unknown,
+        }
+    }
+}
+
+/// 推断表达式的类型
+///
+/// 覆盖字面量、列、运算符、CAST 和常用函数；子查询、用户变量和不认识的函数为 Unknown。
+/// 比较、IN、LIKE 等谓词的结果为 Boolean，整数相除的结果为 Decimal。
+#[cfg(feature = "ddl")]
+pub fn infer_type(expr: &Expr, scope: &TypeScope) -> SqlType {
+    let infer = |expr: &Expr| infer_type(expr, scope);
+    match expr {
+        Expr::Literal(value) => match value {
+            Value::Integer(_) => SqlType::Integer,
+            Value::Float(_) => SqlType::Float,
+            Value::Decimal(_) => SqlType::Decimal,
+            Value::String(_) => SqlType::String,
+            Value::Boolean(_) => SqlType::Boolean,
+            Value::Null => SqlType::Null,
+            Value::DEFAULT => SqlType::Unknown,
+        },
+        Expr::Identifier(name) if name.name().value.starts_with('@') => SqlType::Unknown,
+        Expr::Identifier(name) => scope.column_type(name),
+        Expr::BinaryOp { left, op, right } => match op {
+            BinaryOperator::Plus | BinaryOperator::Minus | BinaryOperator::Multiply | BinaryOperator::Divide => {
+                infer(left).arithmetic(op, infer(right))
+            }
+            _ => SqlType::Boolean,
+        },
+        Expr::In { .. }
+        | Expr::Between { .. }
+        | Expr::IsNull { .. }
+        | Expr::LogicalOp { .. }
+        | Expr::Match { .. }
+        | Expr::Any { .. }
+        | Expr::Exists(_) => SqlType::Boolean,
+        Expr::Extract { .. } => SqlType::Integer,
+        // 第一个能确定类型的分支
+        Expr::Case { results, else_result, .. } => results
+            .iter()
+            .chain(else_result.as_deref())
+            .map(infer)
+            .find(|t| !matches!(t, SqlType::Null | SqlType::Unknown))
+            .unwrap_or(SqlType::Unknown),
+        Expr::UnaryOp { expr, .. } => infer(expr),
+        Expr::Cast { data_type, .. } => SqlType::from_data_type(data_type),
+        Expr::Introducer { .. } | Expr::Collate { .. } | Expr::GroupConcat(_) => SqlType::String,
+        Expr::Aggregate { func, args, .. } => {
+            let arg = args.first().map_or(SqlType::Unknown, infer);
+            match func {
+                AggregateFunction::Count => SqlType::Integer,
+                AggregateFunction::Sum | AggregateFunction::Avg if arg == SqlType::Float => SqlType::Float,
+                AggregateFunction::Sum | AggregateFunction::Avg if arg.is_numeric() => SqlType::Decimal,
+                AggregateFunction::Sum | AggregateFunction::Avg => SqlType::Unknown,
+                AggregateFunction::Min | AggregateFunction::Max => arg,
+            }
+        }
+        Expr::FunctionCall { name, args } => function_type(name, args, scope),
+        Expr::Window { func, .. } => infer(func),
+        Expr::Assignment { value, .. } => infer(value),
+        Expr::Wildcard | Expr::Interval { .. } | Expr::Subquery(_) | Expr::Array(_) | Expr::Subscript { .. } => {
+            SqlType::Unknown
+        }
+    }
+}
+
+// 常用函数的返回类型
+#[cfg(feature = "ddl")]
+fn function_type(name: &str, args: &[Expr], scope: &TypeScope) -> SqlType {
+    let arg = |i: usize| args.get(i).map_or(SqlType::Unknown, |arg| infer_type(arg, scope));
+    match name.to_uppercase().as_str() {
+        "CONCAT" | "CONCAT_WS" | "LOWER" | "UPPER" | "LCASE" | "UCASE" | "SUBSTRING" | "SUBSTR" | "TRIM" | "LTRIM"
+        | "RTRIM" | "REPLACE" | "LEFT" | "RIGHT" | "LPAD" | "RPAD" | "REVERSE" | "REPEAT" | "FORMAT" | "DATE_FORMAT"
+        | "HEX" | "MD5" | "SHA1" | "SHA2" | "UUID" => SqlType::String,
+        "LENGTH" | "CHAR_LENGTH" | "CHARACTER_LENGTH" | "LOCATE" | "INSTR" | "YEAR" | "MONTH" | "DAY" | "DAYOFMONTH"
+        | "DAYOFWEEK" | "HOUR" | "MINUTE" | "SECOND" | "DATEDIFF" | "FLOOR" | "CEIL" | "CEILING" | "SIGN"
+        | "UNIX_TIMESTAMP" | "LAST_INSERT_ID" | "FOUND_ROWS" | "ROW_NUMBER" | "RANK" | "DENSE_RANK" => SqlType::Integer,
+        "RAND" | "SQRT" | "POW" | "POWER" | "EXP" | "LN" | "LOG" | "LOG10" | "PI" | "SIN" | "COS" | "TAN" => SqlType::Float,
+        "NOW" | "CURRENT_TIMESTAMP" | "SYSDATE" | "LOCALTIME" | "LOCALTIMESTAMP" | "UTC_TIMESTAMP" | "FROM_UNIXTIME"
+        | "TIMESTAMP" => SqlType::DateTime,
+        "CURDATE" | "CURRENT_DATE" | "UTC_DATE" | "DATE" | "LAST_DAY" => SqlType::Date,
+        "CURTIME" | "CURRENT_TIME" | "UTC_TIME" | "TIME" => SqlType::Time,
+        "JSON_OBJECT" | "JSON_ARRAY" | "JSON_EXTRACT" | "JSON_SET" | "JSON_REMOVE" | "JSON_MERGE_PATCH" => SqlType::Json,
+        "ABS" | "ROUND" | "TRUNCATE" | "DATE_ADD" | "DATE_SUB" | "ADDDATE" | "SUBDATE" => arg(0),
+        // 第一个能确定类型的参数
+        "COALESCE" | "IFNULL" | "NULLIF" | "GREATEST" | "LEAST" => {
+            (0..args.len()).map(arg).find(|t| !matches!(t, SqlType::Null | SqlType::Unknown)).unwrap_or(SqlType::Unknown)
+        }
+        "IF" => [arg(1), arg(2)].into_iter().find(|t| !matches!(t, SqlType::Null | SqlType::Unknown)).unwrap_or(SqlType::Unknown),
+        _ => SqlType::Unknown,
+    }
+}
+
+/// 两侧类型不能直接比较的比较，见 [`SqlType::is_comparable`]
+#[cfg(feature = "ddl")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypeMismatch<'a> {
+    /// 比较表达式，IN 和 BETWEEN 为整个表达式
+    pub expr: &'a Expr,
+    pub left: SqlType,
+    pub right: SqlType,
+}
+
+/// 查找表达式中类型不兼容的比较，例如字符串列与整数比较，不包括子查询
+#[cfg(feature = "ddl")]
+pub fn type_mismatches<'a>(expr: &'a Expr, scope: &TypeScope) -> Vec<TypeMismatch<'a>> {
+    let mut mismatches = Vec::new();
+    for node in expr.iter() {
+        let (left, others): (&Expr, Vec<&Expr>) = match node {
+            Expr::BinaryOp { left, op, right } if is_comparison(op) => (left, vec![right]),
+            Expr::In { expr, list, .. } => (expr, list.iter().collect()),
+            Expr::Between { expr, low, high, .. } => (expr, vec![low, high]),
+            _ => continue,
+        };
+        let left = infer_type(left, scope);
+        let mismatch = others.into_iter().map(|other| infer_type(other, scope)).find(|right| !left.is_comparable(*right));
+        if let Some(right) = mismatch {
+            mismatches.push(TypeMismatch { expr: node, left, right });
+        }
+    }
+    mismatches
+}
+
+#[cfg(feature = "ddl")]
+fn is_comparison(op: &BinaryOperator) -> bool {
+    matches!(
+        op,
+        BinaryOperator::Eq
+            | BinaryOperator::NotEq
+            | BinaryOperator::Lt
+            | BinaryOperator::LtEq
+            | BinaryOperator::Gt
+            | BinaryOperator::GtEq
+    )
+}
+
+// 排序项中的表达式，按列序号排序的项没有表达式
+fn sort_exprs<'a>(items: impl Iterator<Item = &'a OrderByExpr>) -> impl Iterator<Item = &'a Expr> {
+    items.filter_map(|item| match &item.expr {
+        SortKey::Expr(expr) => Some(expr),
+        _ => None,
+    })
+}
+
+// 查询本身的表达式，不包括派生表和表函数中的表达式
+fn select_exprs(select: &SelectStatement) -> impl Iterator<Item = &Expr> {
+    let columns = select.columns.iter().filter_map(|column| match column {
+        SelectColumn::Expression { expr, .. } => Some(expr),
+        _ => None,
+    });
+    let group_by = select.group_by.iter().flatten().filter_map(|key| match key {
+        SortKey::Expr(expr) => Some(expr),
+        _ => None,
+    });
+    columns
+        .chain(select.distinct_on.iter().flatten())
+        .chain(select.top.iter().map(|top| &top.quantity.value))
+        .chain(select.joins.iter().filter_map(|join| join.on.as_ref()))
+        .chain(&select.where_clause)
+        .chain(group_by)
+        .chain(&select.having)
+        .chain(&select.qualify)
+        .chain(select.windows.iter().flat_map(|(_, spec)| spec.exprs()))
+        .chain(sort_exprs(select.order_by.iter().flatten()))
+}
+
+// 查询读取的表和 CTE，包括派生表、表达式中的子查询和集合运算中的查询；CTE 的定义单独收集，
+// 它们的查询读取的对象不属于外层查询
+fn collect_references<'a>(select: &'a SelectStatement, references: &mut Vec<&'a ObjectName>, ctes: &mut Vec<&'a Cte>) {
+    ctes.extend(select.with.iter().flat_map(|with| &with.ctes));
+    for table in select.from.iter().chain(select.joins.iter().map(|join| &join.table)) {
+        match &table.subquery {
+            Some(subquery) => collect_references(subquery, references, ctes),
+            None => references.push(&table.name),
+        }
+    }
+    for expr in select_exprs(select) {
+        for node in expr.iter() {
+            if let Expr::Subquery(subquery) | Expr::Exists(subquery) = node {
+                collect_references(subquery, references, ctes);
+            }
+        }
+    }
+    for operation in &select.set_operations {
+        collect_references(&operation.select, references, ctes);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::select::SelectStatementParser;
+    use crate::parser::{Parser, StatementParser};
+
+    #[test]
+    fn test_in_list_sizes() {
+        let ids: Vec<String> = (0..5000).map(|i| i.to_string()).collect();
+        let sql = format!(
+            "status NOT IN ('a', 'b') AND (id IN ({}) OR owner IN (SELECT id FROM users WHERE role IN (1, 2, 3)))",
+            ids.join(",")
+        );
+        let expr = Parser::new_from_sql(&sql).parse_expr(0).unwrap();
+        assert_eq!(
+            in_list_sizes(&expr),
+            vec![InListSize { len: 2, negated: true }, InListSize { len: 5000, negated: false }]
+        );
+    }
+
+    #[cfg(feature = "ddl")]
+    #[test]
+    fn test_dependencies() {
+        let sql = "CREATE TABLE users (id INT);
+                   CREATE TABLE orders (id INT, user_id INT REFERENCES users (id));
+                   CREATE TABLE items (order_id INT, CONSTRAINT fk FOREIGN KEY (order_id) REFERENCES orders (id));
+                   CREATE TABLE report (n INT);
+                   INSERT INTO report SELECT COUNT(*) FROM ORDERS o WHERE o.user_id IN (SELECT id FROM users);
+                   ALTER TABLE report ADD CONSTRAINT fk_user FOREIGN KEY (n) REFERENCES users (id);
+                   SELECT * FROM logs";
+        let graph = dependencies(&Parser::new_from_sql(sql).parse_statements().unwrap());
+        let name = |name: &str| ObjectName::new([name]);
+        let names = |objects: Vec<&ObjectName>| objects.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+        assert_eq!(names(graph.objects.iter().collect()), ["users", "orders", "items", "report", "logs"]);
+        assert_eq!(graph.edges.len(), 5);
+        assert_eq!(graph.edges[2], Dependency { from: name("report"), to: name("ORDERS"), kind: DependencyKind::InsertSelect });
+        assert_eq!(names(graph.dependencies_of(&name("report"))), ["ORDERS", "users", "users"]);
+        assert_eq!(names(graph.dependents_of(&name("orders"))), ["items", "report"]);
+        assert_eq!(names(graph.impacted_by(&name("users"))), ["orders", "report", "items"]);
+        assert!(graph.impacted_by(&name("logs")).is_empty());
+
+
+        // 视图依赖查询读取的表和 CTE，CTE 依赖它的查询读取的表
+        let sql = "CREATE TABLE orders (id INT);
+                   CREATE VIEW recent AS WITH r AS (SELECT id FROM orders WHERE id > 100) SELECT id FROM r UNION SELECT id FROM archive;
+                   CREATE OR REPLACE VIEW report AS SELECT * FROM recent WHERE EXISTS (SELECT 1 FROM users);
+                   WITH RECURSIVE n (i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 3) SELECT i FROM n";
+        let graph = dependencies(&Parser::new_from_sql(sql).parse_statements().unwrap());
+        assert_eq!(names(graph.objects.iter().collect()), ["orders", "recent", "r", "archive", "report", "users", "n"]);
+        assert_eq!(names(graph.dependencies_of(&name("recent"))), ["r", "archive"]);
+        assert_eq!(graph.edges[0], Dependency { from: name("recent"), to: name("r"), kind: DependencyKind::View });
+        assert_eq!(graph.edges[2], Dependency { from: name("r"), to: name("orders"), kind: DependencyKind::Cte });
+        assert_eq!(names(graph.impacted_by(&name("orders"))), ["r", "recent", "report"]);
+        // 递归 CTE 对自身的引用不是依赖
+        assert!(graph.dependencies_of(&name("n")).is_empty());
+    }
+
+    #[test]
+    fn test_complexity() {
+        let sql = "SELECT u.id, (SELECT COUNT(*) FROM orders o WHERE o.uid = u.id) FROM users u
+                   JOIN (SELECT uid FROM vip v JOIN tiers t ON t.id = v.tier WHERE v.until > NOW()) x ON x.uid = u.id
+                   LEFT JOIN logs l ON l.uid = u.id
+                   WHERE u.age >= 18 AND u.name != 'a' AND u.score < 100
+                   AND u.id IN (SELECT uid FROM bans WHERE reason IN (SELECT code FROM reasons WHERE id > 1))";
+        let stmt = Parser::new_from_sql(sql).parse().unwrap();
+        let complexity = complexity(&stmt);
+        assert_eq!(complexity, Complexity { joins: 3, subqueries: 4, predicates: 11, depth: 2 });
+
+        let limits = ComplexityLimits { length: Some(100), joins: Some(3), subqueries: Some(2), depth: Some(1), ..Default::default() };
+        assert_eq!(
+            limits.check(&complexity),
+            [
+                ComplexityViolation { metric: ComplexityMetric::Subqueries, actual: 4, limit: 2 },
+                ComplexityViolation { metric: ComplexityMetric::Depth, actual: 2, limit: 1 },
+            ]
+        );
+        assert_eq!(limits.check_length(sql).map(|violation| violation.metric), Some(ComplexityMetric::Length));
+        assert_eq!(limits.check_length("SELECT 1"), None);
+        assert!(ComplexityLimits::default().check(&complexity).is_empty());
+
+        // INSERT ... SELECT 的查询不是子查询
+        let stmt = Parser::new_from_sql("INSERT INTO t SELECT a FROM s WHERE a = 1 AND b IN (SELECT b FROM r)").parse().unwrap();
+        assert_eq!(super::complexity(&stmt), Complexity { joins: 0, subqueries: 1, predicates: 2, depth: 1 });
+        let stmt = Parser::new_from_sql("DELETE FROM t WHERE a = 1 ORDER BY (b > 2)").parse().unwrap();
+        assert_eq!(super::complexity(&stmt).predicates, 2);
+        // CTE 算作子查询，集合运算中的查询与第一个查询同层
+        let sql = "WITH c AS (SELECT id FROM a) SELECT id FROM c UNION SELECT b.id FROM b JOIN d ON d.id = b.id";
+        let stmt = Parser::new_from_sql(sql).parse().unwrap();
+        assert_eq!(super::complexity(&stmt), Complexity { joins: 1, subqueries: 1, predicates: 1, depth: 1 });
+    }
+
+    #[test]
+    fn test_similarity() {
+        let parse = |sql: &str| Parser::new_from_sql(sql).parse().unwrap();
+        let base = parse("SELECT o.id, o.total FROM orders o WHERE o.user_id = 42 AND o.status IN ('paid', 'sent') ORDER BY o.id LIMIT 10");
+        // 字面量、别名和大小写不同
+        let same = parse("select ID, TOTAL from ORDERS where user_id = 7 and status in ('new', 'x') order by id limit 50");
+        assert_eq!(similarity(&base, &same), 1.0);
+        // 多一个条件的变体比换了表和列的查询更相似
+        let variant = parse("SELECT id, total FROM orders WHERE user_id = 1 AND status IN ('paid') AND total > 100 ORDER BY id LIMIT 10");
+        let other = parse("SELECT name FROM users WHERE email = 'a' LIMIT 1");
+        let (near, far) = (similarity(&base, &variant), similarity(&base, &other));
+        assert!(near > 0.7 && near < 1.0, "{}", near);
+        assert!(far < 0.4, "{}", far);
+        assert_eq!(similarity(&variant, &base), near);
+        // 不同类型的语句
+        let delete = parse("DELETE FROM orders WHERE user_id = 42");
+        assert!(similarity(&base, &delete) < near);
+        assert_eq!(similarity(&delete, &parse("DELETE FROM ORDERS WHERE USER_ID = 1")), 1.0);
+    }
+
+    #[test]
+    fn test_predicates_for_table() {
+        let expr = Parser::new_from_sql("a = 1 AND (b = 2 AND c = 3) AND (d = 4 OR e = 5)").parse_expr(0).unwrap();
+        let conjuncts: Vec<String> = split_conjunction(&expr).iter().map(|e| e.to_string()).collect();
+        assert_eq!(conjuncts, ["a = 1", "b = 2", "c = 3", "d = 4 OR e = 5"]);
+        assert_eq!(split_conjunction(&Expr::Identifier(ObjectName::new(["x"]))).len(), 1);
+
+        let sql = "SELECT * FROM users u JOIN orders o ON o.user_id = u.id AND o.status = 'paid' LEFT JOIN logs l ON l.uid = u.id
+                   WHERE u.age > 18 AND o.total > 100 AND u.id = o.user_id AND LOWER(u.name) = 'a'
+                   AND l.level = 1 AND u.id IN (SELECT uid FROM vip) AND @flag = 1 AND (u.vip OR o.rush)";
+        let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+        let predicates = |table: &str| -> Vec<String> {
+            predicates_for_table(&select, table).iter().map(|e| e.to_string()).collect()
+        };
+        assert_eq!(predicates("u"), ["u.age > 18", "LOWER(u.name) = 'a'"]);
+        assert_eq!(predicates("O"), ["o.total > 100", "o.status = 'paid'"]);
+        // LEFT JOIN 的右表、未知的表
+        assert!(predicates("l").is_empty());
+        assert!(predicates("users").is_empty());
+
+        // 只有一张表时不带限定符的列也属于它
+        let select = Parser::new_from_sql("SELECT * FROM mydb.users WHERE age > 1 AND users.id = 2 AND 1 = 1")
+            .parse_select_statement()
+            .unwrap();
+        let predicates: Vec<String> = predicates_for_table(&select, "users").iter().map(|e| e.to_string()).collect();
+        assert_eq!(predicates, ["age > 1", "users.id = 2"]);
+    }
+
+    #[test]
+    fn test_is_comparable() {
+        use SqlType::*;
+        // 数值与字符串比较会隐式转换
+        assert!(!Integer.is_comparable(String));
+        assert!(!String.is_comparable(Decimal));
+        // 日期时间与数值、字符串比较时常量会转换为日期时间
+        assert!(DateTime.is_comparable(Integer));
+        assert!(Date.is_comparable(String));
+        assert!(Float.is_comparable(Time));
+        assert!(Integer.is_comparable(Decimal));
+        assert!(Null.is_comparable(String) && Unknown.is_comparable(Integer));
+    }
+
+    #[cfg(feature = "ddl")]
+    #[test]
+    fn test_infer_type() {
+        use crate::schema::Catalog;
+
+        let catalog = Catalog::from_script(
+            "CREATE TABLE users (id INT, name VARCHAR(20), created DATETIME, score DECIMAL(5,2));
+             CREATE TABLE orders (id BIGINT, user_id INT, total DOUBLE)",
+        )
+        .unwrap();
+        let select = Parser::new_from_sql("SELECT * FROM users u JOIN orders o ON o.user_id = u.id").parse_select_statement().unwrap();
+        let scope = TypeScope::for_select(&catalog, &select);
+        let infer = |sql: &str| infer_type(&Parser::new_from_sql(sql).parse_expr(0).unwrap(), &scope);
+
+        assert_eq!(infer("1"), SqlType::Integer);
+        assert_eq!(infer("'a'"), SqlType::String);
+        assert_eq!(infer("NULL"), SqlType::Null);
+        assert_eq!(infer("name"), SqlType::String);
+        assert_eq!(infer("u.created"), SqlType::DateTime);
+        assert_eq!(infer("u.score"), SqlType::Decimal);
+        // 两张表都有 id
+        assert_eq!(infer("id"), SqlType::Unknown);
+        assert_eq!(infer("o.id + 1"), SqlType::Integer);
+        assert_eq!(infer("u.id / 2"), SqlType::Decimal);
+        assert_eq!(infer("score * total"), SqlType::Float);
+        assert_eq!(infer("-score"), SqlType::Decimal);
+        assert_eq!(infer("CAST(name AS SIGNED)"), SqlType::Integer);
+        assert_eq!(infer("CAST(u.id AS CHAR(10))"), SqlType::String);
+        assert_eq!(infer("COUNT(*)"), SqlType::Integer);
+        assert_eq!(infer("SUM(user_id)"), SqlType::Decimal);
+        assert_eq!(infer("MAX(created)"), SqlType::DateTime);
+        assert_eq!(infer("CONCAT(name, u.id)"), SqlType::String);
+        assert_eq!(infer("COALESCE(NULL, total, 0)"), SqlType::Float);
+        assert_eq!(infer("NOW()"), SqlType::DateTime);
+        assert_eq!(infer("unknown_fn(1)"), SqlType::Unknown);
+        assert_eq!(infer("name = 'a' AND u.id > 1"), SqlType::Boolean);
+
+        let mismatches = |sql: &str| -> Vec<(String, SqlType, SqlType)> {
+            let expr = Parser::new_from_sql(sql).parse_expr(0).unwrap();
+            type_mismatches(&expr, &scope).iter().map(|m| (m.expr.to_string(), m.left, m.right)).collect()
+        };
+        assert_eq!(
+            mismatches("name = 1 AND u.id = '2' AND o.total > 0 AND created > '2024-01-01' AND created < 20250101"),
+            [
+                ("name = 1".to_string(), SqlType::String, SqlType::Integer),
+                ("u.id = '2'".to_string(), SqlType::Integer, SqlType::String),
+            ]
+        );
+        assert_eq!(mismatches("u.id IN (1, 'x')"), [("u.id IN (1, 'x')".to_string(), SqlType::Integer, SqlType::String)]);
+        assert!(mismatches("name = NULL OR unknown_col = 1 OR id = 'a'").is_empty());
+    }
+}
@@ -0,0 +1,237 @@
+//! 列别名的作用域解析
+//!
+//! GROUP BY、HAVING、QUALIFY 和 ORDER BY 中可以用别名或序号引用选择列表中的列，例如
+//! `SELECT price * qty AS total FROM items ORDER BY total`。解析把这些引用链接到选择列表中的定义，
+//! 血缘分析和静态检查可以据此找到引用的真正表达式，同时报告无法解析的序号和有歧义的别名。
+//!
+//! 只有不带限定符的名称才可能是别名。没有匹配别名的名称通常是FROM中的表的列，只有语句没有FROM时才报告为未知。
+//! 子查询有自己的作用域，需要单独解析。
+
+use std::ptr;
+
+use super::expr::{Expr, SortKey};
+use super::select::{SelectColumn, SelectStatement};
+
+/// 引用出现的从句
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AliasClause {
+    GroupBy,
+    Having,
+    Qualify,
+    OrderBy,
+}
+
+/// 引用选择列表中的列的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AliasReference<'a> {
+    /// 别名，例如 ORDER BY total 中的 total
+    Name(&'a Expr),
+    /// 序号，例如 ORDER BY 1
+    Ordinal(u32),
+}
+
+/// 解析得到的一个引用
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResolvedAlias<'a> {
+    pub clause: AliasClause,
+    pub reference: AliasReference<'a>,
+    /// 被引用的列在选择列表中的下标，从0开始
+    pub index: usize,
+    pub column: &'a SelectColumn,
+}
+
+/// 无法解析的引用
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AliasIssue {
+    /// 既不是别名也不是表中的列，只在语句没有FROM时报告
+    Unknown { clause: AliasClause, name: String },
+    /// 序号为0或超过了选择列表的列数
+    UnknownOrdinal { clause: AliasClause, ordinal: u32 },
+    /// 多个不同的列使用了同一个名称，candidates 为这些列在选择列表中的下标
+    Ambiguous { clause: AliasClause, name: String, candidates: Vec<usize> },
+}
+
+/// 一条 SELECT 语句的别名解析结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AliasResolution<'a> {
+    /// 按在语句中出现的顺序排列
+    pub resolved: Vec<ResolvedAlias<'a>>,
+    pub issues: Vec<AliasIssue>,
+}
+
+impl<'a> AliasResolution<'a> {
+    /// 别名引用对应的列定义，expr 必须是解析的语句中的节点（按地址比较）
+    pub fn definition(&self, expr: &Expr) -> Option<&'a SelectColumn> {
+        self.resolved.iter().find_map(|resolved| match resolved.reference {
+            AliasReference::Name(name) if ptr::eq(name, expr) => Some(resolved.column),
+            _ => None,
+        })
+    }
+}
+
+impl SelectStatement {
+    /// 解析 GROUP BY、HAVING、QUALIFY 和 ORDER BY 中的别名和序号，不包括子查询
+    pub fn resolve_aliases(&self) -> AliasResolution<'_> {
+        let mut resolver = Resolver { select: self, resolution: AliasResolution::default() };
+        if let Some(keys) = &self.group_by {
+            for key in keys {
+                resolver.key(AliasClause::GroupBy, key);
+            }
+        }
+        if let Some(having) = &self.having {
+            resolver.expr(AliasClause::Having, having);
+        }
+        if let Some(qualify) = &self.qualify {
+            resolver.expr(AliasClause::Qualify, qualify);
+        }
+        for item in self.order_by.iter().flatten() {
+            resolver.key(AliasClause::OrderBy, &item.expr);
+        }
+        resolver.resolution
+    }
+}
+
+struct Resolver<'a> {
+    select: &'a SelectStatement,
+    resolution: AliasResolution<'a>,
+}
+
+impl<'a> Resolver<'a> {
+    fn key(&mut self, clause: AliasClause, key: &'a SortKey) {
+        match key {
+            SortKey::Expr(expr) => self.expr(clause, expr),
+            SortKey::Ordinal(ordinal) => self.ordinal(clause, *ordinal),
+        }
+    }
+
+    fn ordinal(&mut self, clause: AliasClause, ordinal: u32) {
+        if let Some(column) = self.select.resolve_ordinal(ordinal) {
+            let index = ordinal as usize - 1;
+            let reference = AliasReference::Ordinal(ordinal);
+            self.resolution.resolved.push(ResolvedAlias { clause, reference, index, column });
+            return;
+        }
+        // 序号之前有 * 时无法确定列数，不报告
+        let wildcard = self.select.columns.iter().any(|column| matches!(column, SelectColumn::Wildcard | SelectColumn::QualifiedWildcard(_)));
+        if ordinal == 0 || !wildcard {
+            self.resolution.issues.push(AliasIssue::UnknownOrdinal { clause, ordinal });
+        }
+    }
+
+    fn expr(&mut self, clause: AliasClause, expr: &'a Expr) {
+        for node in expr.iter() {
+            let Expr::Identifier(name) = node else { continue };
+            let [ident] = name.0.as_slice() else { continue };
+            if ident.value.starts_with('@') {
+                continue;
+            }
+            self.name(clause, node, &ident.value);
+        }
+    }
+
+    fn name(&mut self, clause: AliasClause, node: &'a Expr, name: &str) {
+        let candidates: Vec<(usize, &SelectColumn, bool)> = self
+            .select
+            .columns
+            .iter()
+            .enumerate()
+            .filter_map(|(index, column)| {
+                let (output, alias) = output_name(column)?;
+                output.eq_ignore_ascii_case(name).then_some((index, column, alias))
+            })
+            .collect();
+        let Some(&(index, column, _)) = candidates.iter().find(|(_, _, alias)| *alias) else {
+            // 没有别名匹配时是表中的列
+            if candidates.is_empty() && self.select.from.is_none() {
+                self.resolution.issues.push(AliasIssue::Unknown { clause, name: name.to_string() });
+            }
+            return;
+        };
+        // 同名的列定义相同时不算歧义，例如 SELECT a, a AS a
+        if candidates.iter().any(|(_, other, _)| definition(other) != definition(column)) {
+            let candidates = candidates.iter().map(|(index, _, _)| *index).collect();
+            self.resolution.issues.push(AliasIssue::Ambiguous { clause, name: name.to_string(), candidates });
+            return;
+        }
+        let reference = AliasReference::Name(node);
+        self.resolution.resolved.push(ResolvedAlias { clause, reference, index, column });
+    }
+}
+
+// 列在结果中的名称，以及这个名称是否为别名
+fn output_name(column: &SelectColumn) -> Option<(&str, bool)> {
+    match column {
+        SelectColumn::Column { alias: Some(alias), .. } | SelectColumn::Expression { alias: Some(alias), .. } => {
+            Some((alias, true))
+        }
+        SelectColumn::Column { name, alias: None } => Some((&name.name().value, false)),
+        _ => None,
+    }
+}
+
+// 列引用的值，用于判断同名的列是否相同
+fn definition(column: &SelectColumn) -> Option<Expr> {
+    match column {
+        SelectColumn::Column { name, .. } => Some(Expr::Identifier(name.clone())),
+        SelectColumn::Expression { expr, .. } => Some(expr.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::select::SelectStatementParser;
+    use crate::parser::Parser;
+
+    fn parse(sql: &str) -> SelectStatement {
+        Parser::new_from_sql(sql).parse_select_statement().unwrap()
+    }
+
+    // (从句, 下标) 形式的解析结果
+    fn resolved(select: &SelectStatement) -> Vec<(AliasClause, usize)> {
+        select.resolve_aliases().resolved.iter().map(|resolved| (resolved.clause, resolved.index)).collect()
+    }
+
+    #[test]
+    fn test_resolve_aliases() {
+        use AliasClause::*;
+
+        let select = parse(
+            "SELECT dept AS d, COUNT(*) AS cnt, price * qty AS total FROM items
+             GROUP BY d HAVING cnt > 1 AND SUM(price) > 10 ORDER BY total + 1 DESC, 2, items.total",
+        );
+        assert_eq!(resolved(&select), [(GroupBy, 0), (Having, 1), (OrderBy, 2), (OrderBy, 1)]);
+        let resolution = select.resolve_aliases();
+        assert!(resolution.issues.is_empty());
+        // 引用链接到选择列表中的表达式
+        let having = select.having.as_ref().unwrap();
+        let cnt = having.iter().find(|expr| matches!(expr, Expr::Identifier(name) if name.name().value == "cnt")).unwrap();
+        assert_eq!(resolution.definition(cnt), Some(&select.columns[1]));
+        assert_eq!(resolution.definition(having), None);
+
+        // 名称不匹配任何别名时是表中的列
+        assert_eq!(resolved(&parse("SELECT a AS x FROM t ORDER BY b, X")), [(OrderBy, 0)]);
+        assert!(parse("SELECT a, b FROM t ORDER BY a").resolve_aliases().resolved.is_empty());
+        assert_eq!(resolved(&parse("SELECT a, a AS a FROM t ORDER BY a")), [(OrderBy, 1)]);
+
+        let issues = |sql| parse(sql).resolve_aliases().issues;
+        assert_eq!(
+            issues("SELECT a, b AS a FROM t ORDER BY a"),
+            [AliasIssue::Ambiguous { clause: OrderBy, name: "a".to_string(), candidates: vec![0, 1] }]
+        );
+        assert_eq!(
+            issues("SELECT a AS x, b AS x FROM t GROUP BY x"),
+            [AliasIssue::Ambiguous { clause: GroupBy, name: "x".to_string(), candidates: vec![0, 1] }]
+        );
+        assert_eq!(
+            issues("SELECT a, b FROM t ORDER BY 3"),
+            [AliasIssue::UnknownOrdinal { clause: OrderBy, ordinal: 3 }]
+        );
+        // * 展开后的列数未知
+        assert!(issues("SELECT *, a FROM t ORDER BY 5").is_empty());
+        assert_eq!(issues("SELECT 1 AS one ORDER BY two"), [AliasIssue::Unknown { clause: OrderBy, name: "two".to_string() }]);
+        // 子查询有自己的作用域
+        assert_eq!(resolved(&parse("SELECT a AS x FROM t HAVING x IN (SELECT x FROM s ORDER BY x)")), [(Having, 0)]);
+    }
+}
@@ -0,0 +1,42 @@
+use super::common::ObjectName;
+use super::create::{ColumnDef, IndexDef, TableConstraint};
+
+/// ALTER 语句，按修改的对象类型区分
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AlterStatement {
+    /// ALTER TABLE
+    Table(AlterTableStatement),
+}
+
+/// ALTER TABLE 语句
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterTableStatement {
+    pub name: ObjectName,
+    /// 以逗号分隔的修改操作
+    pub operations: Vec<AlterTableOperation>,
+}
+
+/// ALTER TABLE 的单个修改操作
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AlterTableOperation {
+    /// ADD [COLUMN] column_definition
+    AddColumn(ColumnDef),
+    /// ADD [CONSTRAINT [name]] {PRIMARY KEY | UNIQUE | FOREIGN KEY | CHECK} ...
+    AddConstraint(TableConstraint),
+    /// ADD {INDEX | KEY} [name] (columns)
+    AddIndex(IndexDef),
+    /// MODIFY [COLUMN] column_definition
+    ModifyColumn(ColumnDef),
+    /// DROP [COLUMN] name
+    DropColumn(String),
+    /// DROP PRIMARY KEY
+    DropPrimaryKey,
+    /// DROP FOREIGN KEY name
+    DropForeignKey(String),
+    /// DROP CHECK name
+    DropCheck(String),
+    /// DROP CONSTRAINT name
+    DropConstraint(String),
+    /// DROP {INDEX | KEY} name
+    DropIndex(String),
+}
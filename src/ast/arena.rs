@@ -0,0 +1,507 @@
+//! 基于索引的表达式arena
+//!
+//! 普通的 [`Expr`] 每个子节点都单独 `Box` 分配。这里把整棵表达式树放进一块连续的
+//! `Vec` 中，子节点用 [`ExprId`] 索引引用，列表（函数参数、IN 列表等）也存放在同一个
+//! 索引池中。这样：
+//! - 一棵树只占用少量几次分配，clone 和 drop 都是按块完成
+//! - 节点按后序连续存放，不关心树结构的分析可以直接线性扫描 [`ExprArena::nodes`]
+//! - 调用 [`ExprArena::clear`] 后可以复用已分配的容量，适合高吞吐场景反复解析
+//!
+//! 解析器仍然先生成 [`Expr`]，再由 [`ExprArena::alloc`] 放入arena。
+//! 性能对比见 `benches/arena.rs`（`cargo bench --features arena`）。
+
+use super::common::{DataType, ObjectName};
+use super::select::SelectStatement;
+use super::expr::{
+    AggregateFunction, BinaryOperator, Expr, GroupConcat, IntervalUnit, LogicalOperator, OrderByExpr, SearchMode,
+    SortKey, UnaryOperator, Value, WindowFrame, WindowSpec,
+};
+
+/// arena 中表达式节点的索引
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExprId(u32);
+
+/// arena 中一段连续的子节点列表
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExprList {
+    start: u32,
+    len: u32,
+}
+
+impl ExprList {
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// 与 [`Expr`] 一一对应，子节点改为 arena 内的索引
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ArenaExpr {
+    Identifier(ObjectName),
+    Wildcard,
+    Literal(Value),
+    BinaryOp {
+        left: ExprId,
+        op: BinaryOperator,
+        right: ExprId,
+    },
+    In {
+        expr: ExprId,
+        list: ExprList,
+        negated: bool,
+    },
+    Between {
+        expr: ExprId,
+        low: ExprId,
+        high: ExprId,
+        negated: bool,
+    },
+    IsNull {
+        expr: ExprId,
+        negated: bool,
+    },
+    FunctionCall {
+        name: String,
+        args: ExprList,
+    },
+    Aggregate {
+        func: AggregateFunction,
+        distinct: bool,
+        args: ExprList,
+    },
+    GroupConcat {
+        distinct: bool,
+        args: ExprList,
+        order_by: Vec<ArenaOrderBy>,
+        separator: Option<String>,
+    },
+    LogicalOp {
+        op: LogicalOperator,
+        expressions: ExprList,
+    },
+    UnaryOp {
+        op: UnaryOperator,
+        expr: ExprId,
+    },
+    Introducer {
+        charset: String,
+        expr: ExprId,
+    },
+    Collate {
+        expr: ExprId,
+        collation: String,
+    },
+    Cast {
+        expr: ExprId,
+        data_type: DataType,
+    },
+    Interval {
+        value: ExprId,
+        unit: IntervalUnit,
+    },
+    Match {
+        columns: Vec<ObjectName>,
+        against: ExprId,
+        mode: SearchMode,
+    },
+    Assignment {
+        variable: String,
+        value: ExprId,
+    },
+    Subquery(Box<SelectStatement>),
+    Window {
+        func: ExprId,
+        name: Option<String>,
+        partition_by: ExprList,
+        order_by: Vec<ArenaOrderBy>,
+        frame: Option<WindowFrame>,
+    },
+    Array(ExprList),
+    Subscript {
+        expr: ExprId,
+        index: ExprId,
+    },
+    Any {
+        expr: ExprId,
+        op: BinaryOperator,
+        array: ExprId,
+    },
+    Case {
+        operand: Option<ExprId>,
+        conditions: ExprList,
+        results: ExprList,
+        else_result: Option<ExprId>,
+    },
+    Exists(Box<SelectStatement>),
+    Extract {
+        unit: IntervalUnit,
+        expr: ExprId,
+    },
+}
+
+/// 与 [`SortKey`] 对应
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArenaSortKey {
+    Expr(ExprId),
+    Ordinal(u32),
+}
+
+/// 与 [`OrderByExpr`] 对应
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArenaOrderBy {
+    pub key: ArenaSortKey,
+    pub asc: bool,
+}
+
+/// 存放表达式节点的arena
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ExprArena {
+    nodes: Vec<ArenaExpr>,
+    lists: Vec<ExprId>,
+}
+
+impl ExprArena {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 预先分配节点容量
+    pub fn with_capacity(nodes: usize) -> Self {
+        ExprArena {
+            nodes: Vec::with_capacity(nodes),
+            lists: Vec::with_capacity(nodes / 2),
+        }
+    }
+
+    /// 节点数量
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// 清空所有节点，保留已分配的容量以便复用
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.lists.clear();
+    }
+
+    /// 直接添加一个节点，返回它的索引
+    pub fn push(&mut self, node: ArenaExpr) -> ExprId {
+        let id = ExprId(self.nodes.len() as u32);
+        self.nodes.push(node);
+        id
+    }
+
+    /// 添加一段子节点列表
+    pub fn push_list(&mut self, ids: &[ExprId]) -> ExprList {
+        let start = self.lists.len() as u32;
+        self.lists.extend_from_slice(ids);
+        ExprList { start, len: ids.len() as u32 }
+    }
+
+    /// 获取节点
+    pub fn get(&self, id: ExprId) -> &ArenaExpr {
+        &self.nodes[id.0 as usize]
+    }
+
+    /// 获取子节点列表
+    pub fn list(&self, list: ExprList) -> &[ExprId] {
+        &self.lists[list.start as usize..(list.start + list.len) as usize]
+    }
+
+    /// 所有节点，按后序排列，子节点总是在父节点之前
+    pub fn nodes(&self) -> &[ArenaExpr] {
+        &self.nodes
+    }
+
+    /// 把一棵 Expr 树放入arena，返回根节点的索引
+    pub fn alloc(&mut self, expr: &Expr) -> ExprId {
+        let node = match expr {
+            Expr::Identifier(name) => ArenaExpr::Identifier(name.clone()),
+            Expr::Wildcard => ArenaExpr::Wildcard,
+            Expr::Literal(value) => ArenaExpr::Literal(value.clone()),
+            Expr::BinaryOp { left, op, right } => ArenaExpr::BinaryOp {
+                left: self.alloc(left),
+                op: op.clone(),
+                right: self.alloc(right),
+            },
+            Expr::In { expr, list, negated } => ArenaExpr::In {
+                expr: self.alloc(expr),
+                list: self.alloc_list(list),
+                negated: *negated,
+            },
+            Expr::Between { expr, low, high, negated } => ArenaExpr::Between {
+                expr: self.alloc(expr),
+                low: self.alloc(low),
+                high: self.alloc(high),
+                negated: *negated,
+            },
+            Expr::IsNull { expr, negated } => ArenaExpr::IsNull {
+                expr: self.alloc(expr),
+                negated: *negated,
+            },
+            Expr::FunctionCall { name, args } => ArenaExpr::FunctionCall {
+                name: name.clone(),
+                args: self.alloc_list(args),
+            },
+            Expr::Aggregate { func, distinct, args } => ArenaExpr::Aggregate {
+                func: *func,
+                distinct: *distinct,
+                args: self.alloc_list(args),
+            },
+            Expr::GroupConcat(group_concat) => ArenaExpr::GroupConcat {
+                distinct: group_concat.distinct,
+                args: self.alloc_list(&group_concat.args),
+                order_by: self.alloc_order_by(&group_concat.order_by),
+                separator: group_concat.separator.clone(),
+            },
+            Expr::LogicalOp { op, expressions } => ArenaExpr::LogicalOp {
+                op: op.clone(),
+                expressions: self.alloc_list(expressions),
+            },
+            Expr::UnaryOp { op, expr } => ArenaExpr::UnaryOp {
+                op: op.clone(),
+                expr: self.alloc(expr),
+            },
+            Expr::Introducer { charset, expr } => ArenaExpr::Introducer {
+                charset: charset.clone(),
+                expr: self.alloc(expr),
+            },
+            Expr::Collate { expr, collation } => ArenaExpr::Collate {
+                expr: self.alloc(expr),
+                collation: collation.clone(),
+            },
+            Expr::Cast { expr, data_type } => ArenaExpr::Cast {
+                expr: self.alloc(expr),
+                data_type: data_type.clone(),
+            },
+            Expr::Interval { value, unit } => ArenaExpr::Interval {
+                value: self.alloc(value),
+                unit: *unit,
+            },
+            Expr::Match { columns, against, mode } => ArenaExpr::Match {
+                columns: columns.clone(),
+                against: self.alloc(against),
+                mode: *mode,
+            },
+            Expr::Assignment { variable, value } => ArenaExpr::Assignment {
+                variable: variable.clone(),
+                value: self.alloc(value),
+            },
+            Expr::Subquery(select) => ArenaExpr::Subquery(select.clone()),
+            Expr::Window { func, over } => ArenaExpr::Window {
+                func: self.alloc(func),
+                name: over.name.clone(),
+                partition_by: self.alloc_list(&over.partition_by),
+                order_by: self.alloc_order_by(&over.order_by),
+                frame: over.frame.clone(),
+            },
+            Expr::Array(items) => ArenaExpr::Array(self.alloc_list(items)),
+            Expr::Subscript { expr, index } => ArenaExpr::Subscript {
+                expr: self.alloc(expr),
+                index: self.alloc(index),
+            },
+            Expr::Any { expr, op, array } => ArenaExpr::Any {
+                expr: self.alloc(expr),
+                op: op.clone(),
+                array: self.alloc(array),
+            },
+            Expr::Case { operand, conditions, results, else_result } => ArenaExpr::Case {
+                operand: operand.as_deref().map(|operand| self.alloc(operand)),
+                conditions: self.alloc_list(conditions),
+                results: self.alloc_list(results),
+                else_result: else_result.as_deref().map(|else_result| self.alloc(else_result)),
+            },
+            Expr::Exists(select) => ArenaExpr::Exists(select.clone()),
+            Expr::Extract { unit, expr } => ArenaExpr::Extract {
+                unit: *unit,
+                expr: self.alloc(expr),
+            },
+        };
+        self.push(node)
+    }
+
+    fn alloc_list(&mut self, exprs: &[Expr]) -> ExprList {
+        let ids: Vec<ExprId> = exprs.iter().map(|e| self.alloc(e)).collect();
+        self.push_list(&ids)
+    }
+
+    fn alloc_order_by(&mut self, order_by: &[OrderByExpr]) -> Vec<ArenaOrderBy> {
+        order_by
+            .iter()
+            .map(|item| ArenaOrderBy {
+                key: match &item.expr {
+                    SortKey::Expr(expr) => ArenaSortKey::Expr(self.alloc(expr)),
+                    SortKey::Ordinal(n) => ArenaSortKey::Ordinal(*n),
+                },
+                asc: item.asc,
+            })
+            .collect()
+    }
+
+    fn to_order_by(&self, order_by: &[ArenaOrderBy]) -> Vec<OrderByExpr> {
+        order_by
+            .iter()
+            .map(|item| OrderByExpr {
+                expr: match item.key {
+                    ArenaSortKey::Expr(id) => SortKey::Expr(self.to_expr(id)),
+                    ArenaSortKey::Ordinal(n) => SortKey::Ordinal(n),
+                },
+                asc: item.asc,
+            })
+            .collect()
+    }
+
+    /// 从arena中还原出 Expr 树
+    pub fn to_expr(&self, id: ExprId) -> Expr {
+        let boxed = |id: ExprId| Box::new(self.to_expr(id));
+        let list = |list: ExprList| -> Vec<Expr> {
+            self.list(list).iter().map(|id| self.to_expr(*id)).collect()
+        };
+        match self.get(id) {
+            ArenaExpr::Identifier(name) => Expr::Identifier(name.clone()),
+            ArenaExpr::Wildcard => Expr::Wildcard,
+            ArenaExpr::Literal(value) => Expr::Literal(value.clone()),
+            ArenaExpr::BinaryOp { left, op, right } => Expr::BinaryOp {
+                left: boxed(*left),
+                op: op.clone(),
+                right: boxed(*right),
+            },
+            ArenaExpr::In { expr, list: items, negated } => Expr::In {
+                expr: boxed(*expr),
+                list: list(*items),
+                negated: *negated,
+            },
+            ArenaExpr::Between { expr, low, high, negated } => Expr::Between {
+                expr: boxed(*expr),
+                low: boxed(*low),
+                high: boxed(*high),
+                negated: *negated,
+            },
+            ArenaExpr::IsNull { expr, negated } => Expr::IsNull {
+                expr: boxed(*expr),
+                negated: *negated,
+            },
+            ArenaExpr::FunctionCall { name, args } => Expr::FunctionCall {
+                name: name.clone(),
+                args: list(*args),
+            },
+            ArenaExpr::Aggregate { func, distinct, args } => Expr::Aggregate {
+                func: *func,
+                distinct: *distinct,
+                args: list(*args),
+            },
+            ArenaExpr::GroupConcat { distinct, args, order_by, separator } => Expr::GroupConcat(Box::new(GroupConcat {
+                distinct: *distinct,
+                args: list(*args),
+                order_by: self.to_order_by(order_by),
+                separator: separator.clone(),
+            })),
+            ArenaExpr::LogicalOp { op, expressions } => Expr::LogicalOp {
+                op: op.clone(),
+                expressions: list(*expressions),
+            },
+            ArenaExpr::UnaryOp { op, expr } => Expr::UnaryOp {
+                op: op.clone(),
+                expr: boxed(*expr),
+            },
+            ArenaExpr::Introducer { charset, expr } => Expr::Introducer {
+                charset: charset.clone(),
+                expr: boxed(*expr),
+            },
+            ArenaExpr::Collate { expr, collation } => Expr::Collate {
+                expr: boxed(*expr),
+                collation: collation.clone(),
+            },
+            ArenaExpr::Cast { expr, data_type } => Expr::Cast {
+                expr: boxed(*expr),
+                data_type: data_type.clone(),
+            },
+            ArenaExpr::Interval { value, unit } => Expr::Interval {
+                value: boxed(*value),
+                unit: *unit,
+            },
+            ArenaExpr::Match { columns, against, mode } => Expr::Match {
+                columns: columns.clone(),
+                against: boxed(*against),
+                mode: *mode,
+            },
+            ArenaExpr::Assignment { variable, value } => Expr::Assignment {
+                variable: variable.clone(),
+                value: boxed(*value),
+            },
+            ArenaExpr::Subquery(select) => Expr::Subquery(select.clone()),
+            ArenaExpr::Window { func, name, partition_by, order_by, frame } => Expr::Window {
+                func: boxed(*func),
+                over: Box::new(WindowSpec {
+                    name: name.clone(),
+                    partition_by: list(*partition_by),
+                    order_by: self.to_order_by(order_by),
+                    frame: frame.clone(),
+                }),
+            },
+            ArenaExpr::Array(items) => Expr::Array(list(*items)),
+            ArenaExpr::Subscript { expr, index } => Expr::Subscript {
+                expr: boxed(*expr),
+                index: boxed(*index),
+            },
+            ArenaExpr::Any { expr, op, array } => Expr::Any {
+                expr: boxed(*expr),
+                op: op.clone(),
+                array: boxed(*array),
+            },
+            ArenaExpr::Case { operand, conditions, results, else_result } => Expr::Case {
+                operand: operand.map(boxed),
+                conditions: list(*conditions),
+                results: list(*results),
+                else_result: else_result.map(boxed),
+            },
+            ArenaExpr::Exists(select) => Expr::Exists(select.clone()),
+            ArenaExpr::Extract { unit, expr } => Expr::Extract {
+                unit: *unit,
+                expr: boxed(*expr),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_arena_round_trip() {
+        let sql = "(a + 1) * 2 > LOWER(b, 'x') AND NOT c = _utf8mb4'y' COLLATE utf8mb4_bin OR d = -3 \
+                   OR GROUP_CONCAT(DISTINCT e ORDER BY 2, f DESC SEPARATOR ' | ') = 'z'";
+        let mut parser = Parser::new_from_sql(sql);
+        let expr = parser.parse_expr(0).unwrap();
+
+        let mut arena = ExprArena::new();
+        let root = arena.alloc(&expr);
+        assert_eq!(arena.to_expr(root), expr);
+        // 后序存放，根节点是最后一个
+        assert_eq!(root, ExprId(arena.len() as u32 - 1));
+
+        // 解析后放入复用的arena
+        let mut parser = Parser::new_from_sql(sql);
+        let mut reused = ExprArena::new();
+        let root = parser.parse_expr_in(&mut reused).unwrap();
+        assert_eq!(reused, arena);
+        let ArenaExpr::LogicalOp { op: LogicalOperator::Or, expressions } = reused.get(root) else {
+            panic!("Expected OR at root");
+        };
+        assert_eq!(expressions.len(), 3);
+
+        // 清空后复用容量
+        reused.clear();
+        assert!(reused.is_empty());
+    }
+}
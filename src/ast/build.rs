@@ -0,0 +1,301 @@
+//! 构造AST的辅助函数和宏
+//!
+//! 直接写 Expr 的结构体字面量非常冗长，测试和程序化构造查询时可以使用 [`expr!`](crate::expr) 和
+//! [`select!`](crate::select)：
+//!
+//! ```text
+//! expr!(col("e.salary") > 100000 && col("e.status") != "inactive")
+//! select!("id", "e.name" from "employees" as "e" where col("e.salary") > 100000)
+//! ```
+//!
+//! expr! 中的运算符和优先级与解析器一致：`||`（OR）低于 `&&`（AND），低于前缀的 `!`（NOT，作用于整个比较），
+//! 低于比较运算符 `=`/`==`、`!=`、`<`、`<=`、`>`、`>=`、`in [...]`、`not in [...]`，低于 `+`、`-`，低于 `*`、`/`。
+//! AND、OR 的多个操作数合并为一个 LogicalOp，与解析结果相同。操作数可以是：
+//! - 括号中的表达式
+//! - `col("t.c")` 列名、`lit(v)` 字面量、`func("NAME", [args])` 函数调用，以及 `null`
+//! - Rust 字面量，例如 `1`、`2.5`、`"text"`、`true`，等价于 `lit(...)`
+//! - 其他结果为 Expr 的 Rust 表达式，例如变量
+//!
+//! 负号只能出现在操作数开头，前面是 `*`、`/` 时需要加括号，例如 `col("a") * (-col("b"))`。
+//! func 的参数是普通的 Rust 表达式，其中使用 col、lit 需要导入本模块。
+
+use super::common::{ObjectName, TableReference};
+use super::expr::{BinaryOperator, Expr, LogicalOperator, UnaryOperator, Value};
+use super::select::{SelectColumn, SelectModifiers, SelectStatement};
+
+/// 列名，按 . 分隔限定符，例如 col("e.salary")
+pub fn col(name: &str) -> Expr {
+    Expr::Identifier(ObjectName::new(name.split('.')))
+}
+
+/// 字面量，例如 lit(1)、lit("IT")、lit(true)
+pub fn lit(value: impl Into<Value>) -> Expr {
+    Expr::Literal(value.into())
+}
+
+/// 函数调用，例如 func("LOWER", [col("name")])
+pub fn func(name: &str, args: impl IntoIterator<Item = Expr>) -> Expr {
+    Expr::FunctionCall { name: name.to_string(), args: args.into_iter().collect() }
+}
+
+/// 二元操作
+pub fn binary(left: Expr, op: BinaryOperator, right: Expr) -> Expr {
+    Expr::BinaryOp { left: Box::new(left), op, right: Box::new(right) }
+}
+
+/// AND、OR 连接的表达式，只有一项时直接返回这一项
+pub fn logical(op: LogicalOperator, mut expressions: Vec<Expr>) -> Expr {
+    if expressions.len() == 1 {
+        expressions.pop().unwrap()
+    } else {
+        Expr::LogicalOp { op, expressions }
+    }
+}
+
+/// NOT
+pub fn not(expr: Expr) -> Expr {
+    Expr::LogicalOp { op: LogicalOperator::Not, expressions: vec![expr] }
+}
+
+/// 取负，数值字面量直接得到负数，与解析 -1 的结果相同
+pub fn neg(expr: Expr) -> Expr {
+    if let Expr::Literal(value) = &expr {
+        if let Some(negated) = value.negate() {
+            return Expr::Literal(negated);
+        }
+    }
+    Expr::UnaryOp { op: UnaryOperator::Minus, expr: Box::new(expr) }
+}
+
+/// IN 列表
+pub fn in_list(expr: Expr, list: Vec<Expr>, negated: bool) -> Expr {
+    Expr::In { expr: Box::new(expr), list, negated }
+}
+
+/// 表名，按 . 分隔数据库名，例如 table("mydb.users")
+pub fn table(name: &str) -> TableReference {
+    TableReference {
+        name: ObjectName::new(name.split('.')),
+        partitions: None,
+        alias: None,
+        index_hints: Vec::new(),
+        sample: None,
+        subquery: None,
+        function: None,
+        lateral: false,
+    }
+}
+
+/// 查询列表中的列名，例如 column("e.name")
+pub fn column(name: &str) -> SelectColumn {
+    SelectColumn::Column { name: ObjectName::new(name.split('.')), alias: None }
+}
+
+/// 只有查询列表的 SELECT 语句，其他从句都为空
+pub fn select(columns: impl IntoIterator<Item = SelectColumn>) -> SelectStatement {
+    SelectStatement {
+        with: None,
+        columns: columns.into_iter().collect(),
+        distinct: false,
+        distinct_on: None,
+        modifiers: SelectModifiers::default(),
+        top: None,
+        from: None,
+        joins: Vec::new(),
+        where_clause: None,
+        group_by: None,
+        with_rollup: false,
+        having: None,
+        qualify: None,
+        windows: Vec::new(),
+        set_operations: Vec::new(),
+        order_by: None,
+        limit: None,
+        into: None,
+        lock: None,
+    }
+}
+
+/// 构造表达式，语法见 [`ast::build`](crate::ast::build)
+///
+/// 按优先级从低到高逐层切分：先按 `||` 分成若干项，每项再按 `&&` 分，依次类推，最后是操作数。
+#[macro_export]
+macro_rules! expr {
+    // OR
+    (@or [$($done:tt)*] [$($cur:tt)+] || $($rest:tt)+) => {
+        $crate::expr!(@or [$($done)* [$($cur)+]] [] $($rest)+)
+    };
+    (@or [$($done:tt)*] [$($cur:tt)*] $t:tt $($rest:tt)*) => {
+        $crate::expr!(@or [$($done)*] [$($cur)* $t] $($rest)*)
+    };
+    (@or [$([$($item:tt)+])*] [$($cur:tt)+]) => {
+        $crate::ast::build::logical(
+            $crate::ast::expr::LogicalOperator::Or,
+            vec![$($crate::expr!(@and [] [] $($item)+),)* $crate::expr!(@and [] [] $($cur)+)],
+        )
+    };
+
+    // AND
+    (@and [$($done:tt)*] [$($cur:tt)+] && $($rest:tt)+) => {
+        $crate::expr!(@and [$($done)* [$($cur)+]] [] $($rest)+)
+    };
+    (@and [$($done:tt)*] [$($cur:tt)*] $t:tt $($rest:tt)*) => {
+        $crate::expr!(@and [$($done)*] [$($cur)* $t] $($rest)*)
+    };
+    (@and [$([$($item:tt)+])*] [$($cur:tt)+]) => {
+        $crate::ast::build::logical(
+            $crate::ast::expr::LogicalOperator::And,
+            vec![$($crate::expr!(@not $($item)+),)* $crate::expr!(@not $($cur)+)],
+        )
+    };
+
+    // NOT，作用于整个比较
+    (@not ! $($e:tt)+) => { $crate::ast::build::not($crate::expr!(@cmp [] $($e)+)) };
+    (@not $($e:tt)+) => { $crate::expr!(@cmp [] $($e)+) };
+
+    // 比较，不能连续使用
+    (@cmp [$($l:tt)+] == $($r:tt)+) => { $crate::expr!(@binary Eq [$($l)+] [$($r)+]) };
+    (@cmp [$($l:tt)+] = $($r:tt)+) => { $crate::expr!(@binary Eq [$($l)+] [$($r)+]) };
+    (@cmp [$($l:tt)+] != $($r:tt)+) => { $crate::expr!(@binary NotEq [$($l)+] [$($r)+]) };
+    (@cmp [$($l:tt)+] < $($r:tt)+) => { $crate::expr!(@binary Lt [$($l)+] [$($r)+]) };
+    (@cmp [$($l:tt)+] <= $($r:tt)+) => { $crate::expr!(@binary LtEq [$($l)+] [$($r)+]) };
+    (@cmp [$($l:tt)+] > $($r:tt)+) => { $crate::expr!(@binary Gt [$($l)+] [$($r)+]) };
+    (@cmp [$($l:tt)+] >= $($r:tt)+) => { $crate::expr!(@binary GtEq [$($l)+] [$($r)+]) };
+    (@cmp [$($l:tt)+] in [$($v:literal),* $(,)?]) => {
+        $crate::ast::build::in_list($crate::expr!(@add [] [] $($l)+), vec![$($crate::ast::build::lit($v)),*], false)
+    };
+    (@cmp [$($l:tt)+] not in [$($v:literal),* $(,)?]) => {
+        $crate::ast::build::in_list($crate::expr!(@add [] [] $($l)+), vec![$($crate::ast::build::lit($v)),*], true)
+    };
+    (@cmp [$($l:tt)*] $t:tt $($rest:tt)*) => { $crate::expr!(@cmp [$($l)* $t] $($rest)*) };
+    (@cmp [$($l:tt)+]) => { $crate::expr!(@add [] [] $($l)+) };
+    (@binary $op:ident [$($l:tt)+] [$($r:tt)+]) => {
+        $crate::ast::build::binary(
+            $crate::expr!(@add [] [] $($l)+),
+            $crate::ast::expr::BinaryOperator::$op,
+            $crate::expr!(@add [] [] $($r)+),
+        )
+    };
+
+    // 加减，左结合
+    (@add [$($done:tt)*] [$($cur:tt)+] + $($rest:tt)+) => {
+        $crate::expr!(@add [$($done)* [$($cur)+] Plus] [] $($rest)+)
+    };
+    (@add [$($done:tt)*] [$($cur:tt)+] - $($rest:tt)+) => {
+        $crate::expr!(@add [$($done)* [$($cur)+] Minus] [] $($rest)+)
+    };
+    (@add [$($done:tt)*] [$($cur:tt)*] $t:tt $($rest:tt)*) => {
+        $crate::expr!(@add [$($done)*] [$($cur)* $t] $($rest)*)
+    };
+    (@add [] [$($cur:tt)+]) => { $crate::expr!(@mul [] [] $($cur)+) };
+    (@add [[$($first:tt)+] $($more:tt)*] [$($cur:tt)+]) => {
+        $crate::expr!(@fold_add ($crate::expr!(@mul [] [] $($first)+)) $($more)* [$($cur)+])
+    };
+    (@fold_add ($acc:expr)) => { $acc };
+    (@fold_add ($acc:expr) $op:ident [$($r:tt)+] $($rest:tt)*) => {
+        $crate::expr!(@fold_add ($crate::ast::build::binary(
+            $acc,
+            $crate::ast::expr::BinaryOperator::$op,
+            $crate::expr!(@mul [] [] $($r)+),
+        )) $($rest)*)
+    };
+
+    // 乘除，左结合
+    (@mul [$($done:tt)*] [$($cur:tt)+] * $($rest:tt)+) => {
+        $crate::expr!(@mul [$($done)* [$($cur)+] Multiply] [] $($rest)+)
+    };
+    (@mul [$($done:tt)*] [$($cur:tt)+] / $($rest:tt)+) => {
+        $crate::expr!(@mul [$($done)* [$($cur)+] Divide] [] $($rest)+)
+    };
+    (@mul [$($done:tt)*] [$($cur:tt)*] $t:tt $($rest:tt)*) => {
+        $crate::expr!(@mul [$($done)*] [$($cur)* $t] $($rest)*)
+    };
+    (@mul [] [$($cur:tt)+]) => { $crate::expr!(@atom $($cur)+) };
+    (@mul [[$($first:tt)+] $($more:tt)*] [$($cur:tt)+]) => {
+        $crate::expr!(@fold_mul ($crate::expr!(@atom $($first)+)) $($more)* [$($cur)+])
+    };
+    (@fold_mul ($acc:expr)) => { $acc };
+    (@fold_mul ($acc:expr) $op:ident [$($r:tt)+] $($rest:tt)*) => {
+        $crate::expr!(@fold_mul ($crate::ast::build::binary(
+            $acc,
+            $crate::ast::expr::BinaryOperator::$op,
+            $crate::expr!(@atom $($r)+),
+        )) $($rest)*)
+    };
+
+    // 操作数
+    (@atom ($($inner:tt)+)) => { $crate::expr!(@or [] [] $($inner)+) };
+    (@atom - $($e:tt)+) => { $crate::ast::build::neg($crate::expr!(@atom $($e)+)) };
+    (@atom null) => { $crate::ast::expr::Expr::Literal($crate::ast::expr::Value::Null) };
+    (@atom col($($arg:tt)*)) => { $crate::ast::build::col($($arg)*) };
+    (@atom lit($($arg:tt)*)) => { $crate::ast::build::lit($($arg)*) };
+    (@atom func($($arg:tt)*)) => { $crate::ast::build::func($($arg)*) };
+    (@atom $v:literal) => { $crate::ast::build::lit($v) };
+    (@atom $e:expr) => { $e };
+
+    ($($t:tt)+) => { $crate::expr!(@or [] [] $($t)+) };
+}
+
+/// 构造 SELECT 语句，语法为 `select!(列 from 表 [as 别名] [where 条件])`
+///
+/// 列是 `*` 或逗号分隔的列名字符串，表名和别名是字符串，条件的语法与 [`expr!`](crate::expr) 相同。
+#[macro_export]
+macro_rules! select {
+    (@from [$($columns:tt)*] $table:literal $(as $alias:literal)? $(where $($cond:tt)+)?) => {{
+        let mut select = $crate::ast::build::select([$($columns)*]);
+        select.from = Some($crate::ast::build::table($table));
+        $(if let Some(table) = &mut select.from {
+            table.alias = Some($alias.to_string());
+        })?
+        $(select.where_clause = Some($crate::expr!($($cond)+));)?
+        select
+    }};
+    (* from $($rest:tt)+) => {
+        $crate::select!(@from [$crate::ast::select::SelectColumn::Wildcard] $($rest)+)
+    };
+    ($($column:literal),+ from $($rest:tt)+) => {
+        $crate::select!(@from [$($crate::ast::build::column($column)),+] $($rest)+)
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::select::SelectStatementParser;
+    use crate::parser::Parser;
+
+    fn parse(sql: &str) -> Expr {
+        Parser::new_from_sql(sql).parse_expr(0).unwrap()
+    }
+
+    #[test]
+    fn test_expr_macro() {
+        // 与解析结果相同
+        let cases = [
+            (expr!(col("e.salary") > lit(100000)), "e.salary > 100000"),
+            (expr!(col("a") = 1 && col("b") != "x" || col("c") <= 2), "a = 1 AND b != 'x' OR c <= 2"),
+            (expr!(col("a") == 1 && (col("b") >= 2 || !col("c") < 3)), "a = 1 AND (b >= 2 OR NOT c < 3)"),
+            (expr!(col("a") - 1 - col("b") * 2 / 3 + -4), "a - 1 - b * 2 / 3 + -4"),
+            (expr!(-col("a") + (col("b") - 1) * 2), "-a + (b - 1) * 2"),
+            (expr!(col("id") not in [1, 2, 3]), "id NOT IN (1, 2, 3)"),
+            (expr!(func("LOWER", [col("name")]) = "bob" && col("d") = null), "LOWER(name) = 'bob' AND d = NULL"),
+            (expr!(func("NOW", [])), "NOW()"),
+        ];
+        for (built, sql) in cases {
+            assert_eq!(built, parse(sql), "{}", sql);
+        }
+        // 操作数可以是其他 Expr
+        let left = expr!(col("a") = 1);
+        assert_eq!(expr!(left && col("b") > col("a")), parse("a = 1 AND b > a"));
+    }
+
+    #[test]
+    fn test_select_macro() {
+        let parse = |sql: &str| Parser::new_from_sql(sql).parse_select_statement().unwrap();
+        assert_eq!(select!(* from "users"), parse("SELECT * FROM users"));
+        assert_eq!(
+            select!("id", "e.name" from "mydb.employees" as "e" where col("e.salary") > 100000 && col("e.dept") = "IT"),
+            parse("SELECT id, e.name FROM mydb.employees e WHERE e.salary > 100000 AND e.dept = 'IT'")
+        );
+    }
+}
@@ -1,9 +1,206 @@
+use std::fmt;
 
+use super::expr::Expr;
+use super::select::SelectStatement;
+use super::symbol::Symbol;
 
+/// 标识符，例如列名、表名中的一段
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Ident {
+    pub value: Symbol,
+    /// 包裹标识符的引号，例如 `users` 为 Some('`')，未加引号时为None
+    pub quote_style: Option<char>,
+}
 
-/// 表示选择的表,暂时不考虑多个表
-#[derive(Debug, Clone,PartialEq)]
+impl Ident {
+    pub fn new(value: impl Into<Symbol>) -> Self {
+        Ident { value: value.into(), quote_style: None }
+    }
+
+    /// 带引号的标识符，例如 Ident::with_quote('`', "order")
+    pub fn with_quote(quote: char, value: impl Into<Symbol>) -> Self {
+        Ident { value: value.into(), quote_style: Some(quote) }
+    }
+}
+
+impl fmt::Display for Ident {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.quote_style {
+            // 标识符内的引号需要成对转义
+            Some(q) => {
+                let escaped = self.value.replace(q, &format!("{}{}", q, q));
+                write!(f, "{}{}{}", q, escaped, q)
+            }
+            None => write!(f, "{}", self.value),
+        }
+    }
+}
+
+/// 由点号分隔的对象名，例如 users、mydb.users、catalog.schema.table
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ObjectName(pub Vec<Ident>);
+
+impl ObjectName {
+    /// 由各段未加引号的名称构造对象名
+    pub fn new<S: Into<Symbol>>(parts: impl IntoIterator<Item = S>) -> Self {
+        ObjectName(parts.into_iter().map(Ident::new).collect())
+    }
+
+    /// 最后一段，即对象本身的名字
+    pub fn name(&self) -> &Ident {
+        self.0.last().expect("ObjectName must not be empty")
+    }
+
+    /// 除最后一段之外的限定部分，例如 mydb.users 中的 mydb
+    pub fn qualifier(&self) -> &[Ident] {
+        &self.0[..self.0.len() - 1]
+    }
+
+    /// 是否指同一个对象，不区分大小写，未限定库名的一方可以匹配任意库，例如 users 匹配 mydb.users
+    pub fn matches(&self, other: &ObjectName) -> bool {
+        let (a, b) = (self.qualifier(), other.qualifier());
+        let same = |a: &Ident, b: &Ident| a.value.eq_ignore_ascii_case(&b.value);
+        same(self.name(), other.name())
+            && (a.is_empty() || b.is_empty() || (a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same(a, b))))
+    }
+}
+
+impl fmt::Display for ObjectName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self.0.iter().map(|ident| ident.to_string()).collect();
+        write!(f, "{}", parts.join("."))
+    }
+}
+
+/// 表示选择的表，多表连接见 [`Join`](super::select::Join)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TableReference {
-    pub name: String,
+    /// 表名，派生表为它的别名
+    pub name: ObjectName,
+    /// PARTITION (p0, p1) 指定的分区
+    pub partitions: Option<Vec<String>>,
     pub alias: Option<String>,
-}
\ No newline at end of file
+    /// 别名之后的索引提示，按出现顺序保存
+    pub index_hints: Vec<IndexHint>,
+    /// PostgreSQL 的 TABLESAMPLE 抽样
+    pub sample: Option<TableSample>,
+    /// 派生表 (SELECT ...) AS t 的子查询，此时 name 为别名，alias 为None
+    pub subquery: Option<Box<SelectStatement>>,
+    /// 表函数，例如 JSON_TABLE(...) AS jt，此时 name 为别名，alias 为None
+    pub function: Option<Box<TableFunction>>,
+    /// LATERAL 派生表，子查询中可以引用它之前的表
+    pub lateral: bool,
+}
+
+/// FROM 中返回一组行的函数
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TableFunction {
+    /// MySQL 的 JSON_TABLE(expr, path COLUMNS (...))
+    JsonTable { expr: Expr, path: String, columns: Vec<JsonTableColumn> },
+    /// PostgreSQL 的集合返回函数，例如 UNNEST(arr) WITH ORDINALITY AS t(elem, n)
+    Call {
+        name: String,
+        args: Vec<Expr>,
+        /// WITH ORDINALITY，在结果末尾追加从1开始的行号列
+        with_ordinality: bool,
+        /// 别名后面括号中的列名
+        column_aliases: Vec<String>,
+    },
+}
+
+/// JSON_TABLE 的 COLUMNS 中的一项
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum JsonTableColumn {
+    /// name FOR ORDINALITY，从1开始的行号
+    Ordinality(String),
+    /// name type [EXISTS] PATH path [on_empty ON EMPTY] [on_error ON ERROR]
+    Path {
+        name: String,
+        data_type: DataType,
+        /// EXISTS PATH 只判断路径是否存在
+        exists: bool,
+        path: String,
+        on_empty: Option<JsonTableFallback>,
+        on_error: Option<JsonTableFallback>,
+    },
+    /// NESTED [PATH] path COLUMNS (...)
+    Nested { path: String, columns: Vec<JsonTableColumn> },
+}
+
+/// 路径不存在或取值出错时的处理方式
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum JsonTableFallback {
+    Null,
+    /// DEFAULT 'json'
+    Default(String),
+    Error,
+}
+
+/// 按行数或百分比表示的数量，TOP 和 TABLESAMPLE 共用
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Quantity {
+    pub value: Expr,
+    pub unit: QuantityUnit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuantityUnit {
+    Rows,
+    Percent,
+}
+
+/// 表抽样：TABLESAMPLE method (percent) [REPEATABLE (seed)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TableSample {
+    pub method: SampleMethod,
+    /// 抽样比例，单位总是百分比
+    pub size: Quantity,
+    /// REPEATABLE 指定的随机种子
+    pub seed: Option<Expr>,
+}
+
+/// 抽样方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SampleMethod {
+    /// 按数据页抽样
+    System,
+    /// 按行抽样
+    Bernoulli,
+}
+
+/// 索引提示：{USE | FORCE | IGNORE} {INDEX | KEY} [FOR {JOIN | ORDER BY | GROUP BY}] (index_list)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IndexHint {
+    pub kind: IndexHintKind,
+    /// FOR 指定的作用范围，None表示作用于所有阶段
+    pub scope: Option<IndexHintScope>,
+    /// 索引名，USE INDEX () 的列表可以为空
+    pub indexes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndexHintKind {
+    Use,
+    Force,
+    Ignore,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndexHintScope {
+    Join,
+    OrderBy,
+    GroupBy,
+}
+
+/// 数据类型，例如 VARCHAR(36)、DECIMAL(10,2)、ENUM('a','b')
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DataType {
+    /// 类型名称
+    pub name: String,
+    /// 可选的长度/精度参数，多个参数以逗号连接，例如 10,2
+    pub length: Option<String>,
+    /// ENUM/SET 类型的可选值列表
+    pub values: Option<Vec<String>>,
+    /// 是否为 UNSIGNED
+    pub unsigned: bool,
+}
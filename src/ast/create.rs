@@ -0,0 +1,276 @@
+use super::common::{DataType, ObjectName};
+use super::expr::Expr;
+use super::select::SelectStatement;
+use crate::token::{Span, Token};
+
+/// CREATE 语句，按创建的对象类型区分
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CreateStatement {
+    /// CREATE PROCEDURE / CREATE FUNCTION
+    Routine(CreateRoutineStatement),
+    /// CREATE TABLE
+    Table(CreateTableStatement),
+    /// CREATE VIEW
+    View(CreateViewStatement),
+}
+
+/// 存储过程或函数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoutineKind {
+    Procedure,
+    Function,
+}
+
+/// 存储过程/函数的定义，函数体保留为token和原始SQL，不做解析
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CreateRoutineStatement {
+    pub kind: RoutineKind,
+    /// DEFINER = user 中的用户
+    pub definer: Option<String>,
+    pub if_not_exists: bool,
+    pub name: ObjectName,
+    pub params: Vec<RoutineParameter>,
+    /// 函数的返回类型，存储过程为 None
+    pub returns: Option<DataType>,
+    pub characteristics: Vec<RoutineCharacteristic>,
+    /// 函数体的token，不包括末尾的分号
+    pub body: Vec<Token>,
+    /// 函数体的原始SQL，解析器不是由SQL创建时为None
+    pub body_sql: Option<String>,
+    /// 函数体在原始SQL中的字节范围，解析器不是由SQL创建时为None
+    pub body_span: Option<Span>,
+}
+
+/// 存储过程/函数的参数
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RoutineParameter {
+    /// 参数方向，未指定时为 None（存储过程默认为 IN，函数不允许指定）
+    pub mode: Option<ParameterMode>,
+    pub name: String,
+    pub data_type: DataType,
+}
+
+/// 参数方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParameterMode {
+    In,
+    Out,
+    InOut,
+}
+
+/// 存储过程/函数的特性
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RoutineCharacteristic {
+    /// COMMENT 'string'
+    Comment(String),
+    /// LANGUAGE SQL
+    LanguageSql,
+    /// [NOT] DETERMINISTIC，false 表示 NOT DETERMINISTIC
+    Deterministic(bool),
+    /// CONTAINS SQL | NO SQL | READS SQL DATA | MODIFIES SQL DATA
+    DataAccess(SqlDataAccess),
+    /// SQL SECURITY { DEFINER | INVOKER }
+    SqlSecurity(SqlSecurity),
+}
+
+/// 数据访问特性
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SqlDataAccess {
+    ContainsSql,
+    NoSql,
+    ReadsSqlData,
+    ModifiesSqlData,
+}
+
+/// SQL SECURITY 特性
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SqlSecurity {
+    Definer,
+    Invoker,
+}
+
+/// CREATE VIEW 语句
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CreateViewStatement {
+    pub or_replace: bool,
+    pub algorithm: Option<ViewAlgorithm>,
+    /// DEFINER = user 中的用户
+    pub definer: Option<String>,
+    /// SQL SECURITY { DEFINER | INVOKER }
+    pub security: Option<SqlSecurity>,
+    pub name: ObjectName,
+    /// 视图的列名，未指定时为空
+    pub columns: Vec<String>,
+    pub query: Box<SelectStatement>,
+    /// WITH [CASCADED | LOCAL] CHECK OPTION，省略 CASCADED、LOCAL 时为 CASCADED
+    pub check_option: Option<ViewCheckOption>,
+}
+
+/// ALGORITHM = { UNDEFINED | MERGE | TEMPTABLE }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ViewAlgorithm {
+    Undefined,
+    Merge,
+    TempTable,
+}
+
+/// 可更新视图的检查范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ViewCheckOption {
+    Cascaded,
+    Local,
+}
+
+/// CREATE TABLE 语句
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CreateTableStatement {
+    pub if_not_exists: bool,
+    pub name: ObjectName,
+    pub columns: Vec<ColumnDef>,
+    /// 表级约束：PRIMARY KEY、UNIQUE、FOREIGN KEY、CHECK
+    pub constraints: Vec<TableConstraint>,
+    /// 普通索引：KEY/INDEX
+    pub indexes: Vec<IndexDef>,
+    /// 表选项，例如 ENGINE=InnoDB，名称统一为大写
+    pub options: Vec<TableOption>,
+    /// PARTITION BY 分区定义
+    pub partition_by: Option<PartitionBy>,
+}
+
+/// 列定义
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ColumnDef {
+    pub name: String,
+    pub data_type: DataType,
+    pub options: Vec<ColumnOption>,
+}
+
+/// 列属性
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ColumnOption {
+    Null,
+    NotNull,
+    Default(Expr),
+    /// ON UPDATE expr
+    OnUpdate(Expr),
+    AutoIncrement,
+    PrimaryKey,
+    Unique,
+    Comment(String),
+    /// CHARACTER SET charset_name / CHARSET charset_name
+    CharacterSet(String),
+    /// COLLATE collation_name
+    Collate(String),
+    /// [CONSTRAINT [name]] CHECK (expr)
+    Check {
+        name: Option<String>,
+        expr: Expr,
+    },
+    /// REFERENCES table (columns) ...
+    References(ForeignKeyReference),
+}
+
+/// 表级约束
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TableConstraint {
+    /// CONSTRAINT 后指定的约束名
+    pub name: Option<String>,
+    pub kind: ConstraintKind,
+}
+
+/// 约束类型
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ConstraintKind {
+    PrimaryKey {
+        columns: Vec<String>,
+    },
+    Unique {
+        /// UNIQUE [KEY] 后的索引名
+        index_name: Option<String>,
+        columns: Vec<String>,
+    },
+    ForeignKey {
+        columns: Vec<String>,
+        reference: ForeignKeyReference,
+    },
+    Check {
+        expr: Expr,
+        /// [NOT] ENFORCED，未指定时为 None
+        enforced: Option<bool>,
+    },
+}
+
+/// 外键引用：REFERENCES table (columns) [ON DELETE action] [ON UPDATE action]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ForeignKeyReference {
+    pub table: ObjectName,
+    pub columns: Vec<String>,
+    pub on_delete: Option<ReferentialAction>,
+    pub on_update: Option<ReferentialAction>,
+}
+
+/// 外键的级联动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReferentialAction {
+    Restrict,
+    Cascade,
+    SetNull,
+    NoAction,
+    SetDefault,
+}
+
+/// 普通索引：KEY/INDEX [name] (columns)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IndexDef {
+    pub name: Option<String>,
+    pub columns: Vec<String>,
+}
+
+/// 表选项
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TableOption {
+    pub name: String,
+    pub value: String,
+}
+
+/// 分区定义：PARTITION BY method (...) [PARTITIONS n] [(partition_definition, ...)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PartitionBy {
+    pub method: PartitionMethod,
+    /// LINEAR HASH / LINEAR KEY
+    pub linear: bool,
+    /// RANGE/LIST/HASH 使用的分区表达式
+    pub expr: Option<Expr>,
+    /// RANGE COLUMNS/LIST COLUMNS/KEY 使用的列
+    pub columns: Vec<String>,
+    /// PARTITIONS n
+    pub count: Option<u64>,
+    pub partitions: Vec<PartitionDef>,
+}
+
+/// 分区方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PartitionMethod {
+    Range,
+    RangeColumns,
+    List,
+    ListColumns,
+    Hash,
+    Key,
+}
+
+/// 单个分区：PARTITION name [VALUES {LESS THAN (...) | IN (...)}]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PartitionDef {
+    pub name: String,
+    pub values: Option<PartitionValues>,
+}
+
+/// 分区的取值范围，MAXVALUE 表示为 Expr::Identifier("MAXVALUE")
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PartitionValues {
+    /// VALUES LESS THAN (expr, ...) 或 VALUES LESS THAN MAXVALUE
+    LessThan(Vec<Expr>),
+    /// VALUES IN (expr, ...)
+    In(Vec<Expr>),
+}
@@ -2,11 +2,20 @@ use super::expr::{Expr,OrderByExpr,LimitClause};
 use super::common::TableReference;
 
 /// delete 语句结构
-#[derive(Debug, Clone,PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DeleteStatement {
+    pub modifiers: DeleteModifiers,
     pub table: TableReference,
     pub where_clause: Option<Expr>,
     pub order_by: Option<Vec<OrderByExpr>>,
     pub limit: Option<LimitClause>,
     pub is_return_count:bool,
-}
\ No newline at end of file
+}
+
+/// DELETE 修饰符：[LOW_PRIORITY] [QUICK] [IGNORE]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DeleteModifiers {
+    pub low_priority: bool,
+    pub quick: bool,
+    pub ignore: bool,
+}
@@ -0,0 +1,775 @@
+//! 把AST重新输出为SQL
+//!
+//! 各节点的 Display 输出可以被重新解析的SQL，表达式只在必要时添加括号。
+
+use std::fmt;
+
+use super::common::{
+    DataType, IndexHint, IndexHintKind, IndexHintScope, JsonTableColumn, JsonTableFallback, ObjectName, QuantityUnit,
+    SampleMethod, TableFunction, TableReference, TableSample,
+};
+use super::expr::{
+    BinaryOperator, Expr, FrameBound, FrameUnits, GroupConcat, LimitClause, LogicalOperator, OrderByExpr, SearchMode,
+    SortKey, UnaryOperator, Value, WindowFrame, WindowSpec,
+};
+use super::insert::InsertStatement;
+use super::select::{
+    IntoClause, Join, JoinKind, LockMode, LockWait, SelectColumn, SelectStatement, SetOperation, Top, With,
+};
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::String(s) => write!(f, "{}", quote_string(s)),
+            Value::Integer(i) => write!(f, "{}", i),
+            // Debug 形式保留小数点，例如 1.0，重新解析后仍是浮点数
+            Value::Float(v) => write!(f, "{:?}", v),
+            Value::Decimal(d) => f.write_str(d),
+            Value::Boolean(true) => f.write_str("TRUE"),
+            Value::Boolean(false) => f.write_str("FALSE"),
+            Value::Null => f.write_str("NULL"),
+            Value::DEFAULT => f.write_str("DEFAULT"),
+        }
+    }
+}
+
+/// 输出为单引号字符串，内部的单引号成对转义，反斜杠在 MySQL 中是转义字符，输出为两个反斜杠
+fn quote_string(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''"))
+}
+
+impl fmt::Display for BinaryOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BinaryOperator::Eq => "=",
+            BinaryOperator::NotEq => "!=",
+            BinaryOperator::Lt => "<",
+            BinaryOperator::LtEq => "<=",
+            BinaryOperator::Gt => ">",
+            BinaryOperator::GtEq => ">=",
+            BinaryOperator::Plus => "+",
+            BinaryOperator::Minus => "-",
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::Like => "LIKE",
+        })
+    }
+}
+
+impl fmt::Display for SortKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SortKey::Expr(expr) => write!(f, "{}", expr),
+            SortKey::Ordinal(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+impl fmt::Display for OrderByExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.expr)?;
+        if !self.asc {
+            f.write_str(" DESC")?;
+        }
+        Ok(())
+    }
+}
+
+/// 括号中的窗口定义，不包括括号本身
+impl fmt::Display for WindowSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(name) = &self.name {
+            parts.push(name.clone());
+        }
+        if !self.partition_by.is_empty() {
+            parts.push(format!("PARTITION BY {}", join(&self.partition_by)));
+        }
+        if !self.order_by.is_empty() {
+            parts.push(format!("ORDER BY {}", join(&self.order_by)));
+        }
+        if let Some(frame) = &self.frame {
+            parts.push(frame.to_string());
+        }
+        f.write_str(&parts.join(" "))
+    }
+}
+
+impl fmt::Display for WindowFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self.units {
+            FrameUnits::Rows => "ROWS ",
+            FrameUnits::Range => "RANGE ",
+        })?;
+        match &self.end {
+            Some(end) => write!(f, "BETWEEN {} AND {}", self.start, end),
+            None => write!(f, "{}", self.start),
+        }
+    }
+}
+
+impl fmt::Display for FrameBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameBound::UnboundedPreceding => f.write_str("UNBOUNDED PRECEDING"),
+            FrameBound::Preceding(offset) => write!(f, "{} PRECEDING", offset),
+            FrameBound::CurrentRow => f.write_str("CURRENT ROW"),
+            FrameBound::Following(offset) => write!(f, "{} FOLLOWING", offset),
+            FrameBound::UnboundedFollowing => f.write_str("UNBOUNDED FOLLOWING"),
+        }
+    }
+}
+
+impl fmt::Display for LimitClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LIMIT {}", self.limit)?;
+        if let Some(offset) = self.offset {
+            write!(f, " OFFSET {}", offset)?;
+        }
+        Ok(())
+    }
+}
+
+// 与解析器的优先级层次对应，数值越大绑定越紧
+mod precedence {
+    pub const ASSIGNMENT: u8 = 0;
+    pub const OR: u8 = 1;
+    pub const AND: u8 = 2;
+    pub const NOT: u8 = 3;
+    pub const COMPARISON: u8 = 4;
+    pub const ADDITIVE: u8 = 5;
+    pub const MULTIPLICATIVE: u8 = 6;
+    pub const UNARY: u8 = 7;
+    pub const POSTFIX: u8 = 8;
+    pub const PRIMARY: u8 = 9;
+}
+
+impl Expr {
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Assignment { .. } => precedence::ASSIGNMENT,
+            Expr::LogicalOp { op: LogicalOperator::Or, .. } => precedence::OR,
+            Expr::LogicalOp { op: LogicalOperator::And, .. } => precedence::AND,
+            Expr::LogicalOp { op: LogicalOperator::Not, .. } => precedence::NOT,
+            Expr::BinaryOp { op: BinaryOperator::Plus | BinaryOperator::Minus, .. } => precedence::ADDITIVE,
+            Expr::BinaryOp { op: BinaryOperator::Multiply | BinaryOperator::Divide, .. } => precedence::MULTIPLICATIVE,
+            Expr::BinaryOp { .. } | Expr::In { .. } | Expr::Between { .. } | Expr::IsNull { .. } | Expr::Any { .. } => {
+                precedence::COMPARISON
+            }
+            Expr::UnaryOp { .. } => precedence::UNARY,
+            // 负数字面量带有负号，与一元运算同级，例如 -(-5)
+            Expr::Literal(Value::Integer(i)) if *i < 0 => precedence::UNARY,
+            Expr::Literal(Value::Float(v)) if *v < 0.0 => precedence::UNARY,
+            Expr::Literal(Value::Decimal(d)) if d.starts_with('-') => precedence::UNARY,
+            Expr::Collate { .. } => precedence::POSTFIX,
+            _ => precedence::PRIMARY,
+        }
+    }
+
+    // 输出子表达式，优先级低于 min 时加括号
+    fn fmt_operand(&self, f: &mut fmt::Formatter<'_>, min: u8) -> fmt::Result {
+        if self.precedence() < min {
+            write!(f, "({})", self)
+        } else {
+            write!(f, "{}", self)
+        }
+    }
+}
+
+fn fmt_list(f: &mut fmt::Formatter<'_>, exprs: &[Expr]) -> fmt::Result {
+    for (i, expr) in exprs.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{}", expr)?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use precedence::*;
+        match self {
+            Expr::Identifier(name) => write!(f, "{}", name),
+            Expr::Wildcard => f.write_str("*"),
+            Expr::Literal(value) => write!(f, "{}", value),
+            Expr::BinaryOp { left, op, right } => {
+                let p = self.precedence();
+                // 比较运算不能连写，两侧都需要更高的优先级；加减乘除为左结合
+                let (left_min, right_min) = if p == COMPARISON { (p + 1, p + 1) } else { (p, p + 1) };
+                left.fmt_operand(f, left_min)?;
+                write!(f, " {} ", op)?;
+                right.fmt_operand(f, right_min)
+            }
+            Expr::In { expr, list, negated } => {
+                expr.fmt_operand(f, ADDITIVE)?;
+                f.write_str(if *negated { " NOT IN (" } else { " IN (" })?;
+                match list.as_slice() {
+                    // IN (SELECT ...) 不需要再加一层括号
+                    [Expr::Subquery(select)] => write!(f, "{}", select)?,
+                    _ => fmt_list(f, list)?,
+                }
+                f.write_str(")")
+            }
+            Expr::Between { expr, low, high, negated } => {
+                expr.fmt_operand(f, ADDITIVE)?;
+                f.write_str(if *negated { " NOT BETWEEN " } else { " BETWEEN " })?;
+                low.fmt_operand(f, ADDITIVE)?;
+                f.write_str(" AND ")?;
+                high.fmt_operand(f, ADDITIVE)
+            }
+            Expr::IsNull { expr, negated } => {
+                expr.fmt_operand(f, ADDITIVE)?;
+                f.write_str(if *negated { " IS NOT NULL" } else { " IS NULL" })
+            }
+            Expr::FunctionCall { name, args } => {
+                write!(f, "{}(", name)?;
+                fmt_list(f, args)?;
+                f.write_str(")")
+            }
+            Expr::Aggregate { func, distinct, args } => {
+                write!(f, "{}(", func.name())?;
+                if *distinct {
+                    f.write_str("DISTINCT ")?;
+                }
+                fmt_list(f, args)?;
+                f.write_str(")")
+            }
+            Expr::GroupConcat(group_concat) => {
+                let GroupConcat { distinct, args, order_by, separator } = &**group_concat;
+                f.write_str("GROUP_CONCAT(")?;
+                if *distinct {
+                    f.write_str("DISTINCT ")?;
+                }
+                fmt_list(f, args)?;
+                for (i, item) in order_by.iter().enumerate() {
+                    f.write_str(if i == 0 { " ORDER BY " } else { ", " })?;
+                    write!(f, "{}", item)?;
+                }
+                if let Some(separator) = separator {
+                    write!(f, " SEPARATOR {}", quote_string(separator))?;
+                }
+                f.write_str(")")
+            }
+            Expr::LogicalOp { op: LogicalOperator::Not, expressions } => {
+                f.write_str("NOT ")?;
+                match expressions.first() {
+                    Some(expr) => expr.fmt_operand(f, COMPARISON),
+                    None => Ok(()),
+                }
+            }
+            Expr::LogicalOp { op, expressions } => {
+                let p = self.precedence();
+                let keyword = if *op == LogicalOperator::And { " AND " } else { " OR " };
+                for (i, expr) in expressions.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(keyword)?;
+                    }
+                    expr.fmt_operand(f, if i == 0 { p } else { p + 1 })?;
+                }
+                Ok(())
+            }
+            Expr::UnaryOp { op, expr } => {
+                f.write_str(match op {
+                    UnaryOperator::Plus => "+",
+                    UnaryOperator::Minus => "-",
+                })?;
+                // 连续的一元运算加括号，避免输出 -- 被当作注释
+                expr.fmt_operand(f, POSTFIX)
+            }
+            Expr::Introducer { charset, expr } => write!(f, "_{}{}", charset, expr),
+            Expr::Collate { expr, collation } => {
+                expr.fmt_operand(f, POSTFIX)?;
+                write!(f, " COLLATE {}", collation)
+            }
+            Expr::Cast { expr, data_type } => write!(f, "CAST({} AS {})", expr, data_type),
+            Expr::Interval { value, unit } => {
+                f.write_str("INTERVAL ")?;
+                value.fmt_operand(f, ADDITIVE)?;
+                write!(f, " {}", unit.name())
+            }
+            Expr::Match { columns, against, mode } => {
+                f.write_str("MATCH(")?;
+                for (i, column) in columns.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", column)?;
+                }
+                f.write_str(") AGAINST (")?;
+                against.fmt_operand(f, ADDITIVE)?;
+                f.write_str(match mode {
+                    SearchMode::NaturalLanguage => "",
+                    SearchMode::NaturalLanguageWithQueryExpansion => " IN NATURAL LANGUAGE MODE WITH QUERY EXPANSION",
+                    SearchMode::Boolean => " IN BOOLEAN MODE",
+                    SearchMode::QueryExpansion => " WITH QUERY EXPANSION",
+                })?;
+                f.write_str(")")
+            }
+            Expr::Assignment { variable, value } => write!(f, "{} := {}", variable, value),
+            Expr::Subquery(select) => write!(f, "({})", select),
+            Expr::Window { func, over } => match over.name.as_deref() {
+                // 只引用命名窗口时不需要括号
+                Some(name) if over.partition_by.is_empty() && over.order_by.is_empty() && over.frame.is_none() => {
+                    write!(f, "{} OVER {}", func, name)
+                }
+                _ => write!(f, "{} OVER ({})", func, over),
+            },
+            Expr::Array(items) => {
+                f.write_str("ARRAY[")?;
+                fmt_list(f, items)?;
+                f.write_str("]")
+            }
+            Expr::Subscript { expr, index } => {
+                expr.fmt_operand(f, PRIMARY)?;
+                write!(f, "[{}]", index)
+            }
+            Expr::Any { expr, op, array } => {
+                expr.fmt_operand(f, ADDITIVE)?;
+                match &**array {
+                    // ANY (SELECT ...) 不需要再加一层括号
+                    Expr::Subquery(select) => write!(f, " {} ANY({})", op, select),
+                    array => write!(f, " {} ANY({})", op, array),
+                }
+            }
+            Expr::Case { operand, conditions, results, else_result } => {
+                f.write_str("CASE")?;
+                if let Some(operand) = operand {
+                    write!(f, " {}", operand)?;
+                }
+                for (condition, result) in conditions.iter().zip(results) {
+                    write!(f, " WHEN {} THEN {}", condition, result)?;
+                }
+                if let Some(else_result) = else_result {
+                    write!(f, " ELSE {}", else_result)?;
+                }
+                f.write_str(" END")
+            }
+            Expr::Exists(select) => write!(f, "EXISTS ({})", select),
+            Expr::Extract { unit, expr } => write!(f, "EXTRACT({} FROM {})", unit.name(), expr),
+        }
+    }
+}
+
+impl fmt::Display for TableReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(subquery) = &self.subquery {
+            if self.lateral {
+                f.write_str("LATERAL ")?;
+            }
+            return write!(f, "({}) AS {}", subquery, self.name);
+        }
+        if let Some(function) = &self.function {
+            write!(f, "{} AS {}", function, self.name)?;
+            if let TableFunction::Call { column_aliases, .. } = &**function {
+                if !column_aliases.is_empty() {
+                    write!(f, "({})", column_aliases.join(", "))?;
+                }
+            }
+            return Ok(());
+        }
+        write!(f, "{}", self.name)?;
+        if let Some(partitions) = &self.partitions {
+            write!(f, " PARTITION ({})", partitions.join(", "))?;
+        }
+        if let Some(alias) = &self.alias {
+            write!(f, " AS {}", alias)?;
+        }
+        if let Some(sample) = &self.sample {
+            write!(f, " {}", sample)?;
+        }
+        for hint in &self.index_hints {
+            write!(f, " {}", hint)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TableFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TableFunction::JsonTable { expr, path, columns } => {
+                write!(f, "JSON_TABLE({}, {} COLUMNS ({}))", expr, quote_string(path), join(columns))
+            }
+            TableFunction::Call { name, args, with_ordinality, .. } => {
+                write!(f, "{}({})", name, join(args))?;
+                if *with_ordinality {
+                    f.write_str(" WITH ORDINALITY")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for JsonTableColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonTableColumn::Ordinality(name) => write!(f, "{} FOR ORDINALITY", name),
+            JsonTableColumn::Path { name, data_type, exists, path, on_empty, on_error } => {
+                write!(f, "{} {}", name, data_type)?;
+                if *exists {
+                    f.write_str(" EXISTS")?;
+                }
+                write!(f, " PATH {}", quote_string(path))?;
+                if let Some(fallback) = on_empty {
+                    write!(f, " {} ON EMPTY", fallback)?;
+                }
+                if let Some(fallback) = on_error {
+                    write!(f, " {} ON ERROR", fallback)?;
+                }
+                Ok(())
+            }
+            JsonTableColumn::Nested { path, columns } => {
+                write!(f, "NESTED PATH {} COLUMNS ({})", quote_string(path), join(columns))
+            }
+        }
+    }
+}
+
+impl fmt::Display for JsonTableFallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonTableFallback::Null => f.write_str("NULL"),
+            JsonTableFallback::Default(value) => write!(f, "DEFAULT {}", quote_string(value)),
+            JsonTableFallback::Error => f.write_str("ERROR"),
+        }
+    }
+}
+
+/// 类型名和括号内的参数，ENUM/SET 输出值列表
+impl fmt::Display for DataType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)?;
+        if let Some(length) = &self.length {
+            write!(f, "({})", length)?;
+        }
+        if let Some(values) = &self.values {
+            let values: Vec<String> = values.iter().map(|value| quote_string(value)).collect();
+            write!(f, "({})", values.join(", "))?;
+        }
+        if self.unsigned {
+            f.write_str(" UNSIGNED")?;
+        }
+        Ok(())
+    }
+}
+
+/// TABLESAMPLE 的参数本身就是百分比，不输出 PERCENT
+impl fmt::Display for TableSample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let method = match self.method {
+            SampleMethod::System => "SYSTEM",
+            SampleMethod::Bernoulli => "BERNOULLI",
+        };
+        write!(f, "TABLESAMPLE {} ({})", method, self.size.value)?;
+        if let Some(seed) = &self.seed {
+            write!(f, " REPEATABLE ({})", seed)?;
+        }
+        Ok(())
+    }
+}
+
+/// 数量总是放在括号中，TOP (n) 可以跟任意表达式
+impl fmt::Display for Top {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TOP ({})", self.quantity.value)?;
+        if self.quantity.unit == QuantityUnit::Percent {
+            f.write_str(" PERCENT")?;
+        }
+        if self.with_ties {
+            f.write_str(" WITH TIES")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for IndexHint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self.kind {
+            IndexHintKind::Use => "USE INDEX",
+            IndexHintKind::Force => "FORCE INDEX",
+            IndexHintKind::Ignore => "IGNORE INDEX",
+        })?;
+        f.write_str(match self.scope {
+            None => "",
+            Some(IndexHintScope::Join) => " FOR JOIN",
+            Some(IndexHintScope::OrderBy) => " FOR ORDER BY",
+            Some(IndexHintScope::GroupBy) => " FOR GROUP BY",
+        })?;
+        write!(f, " ({})", self.indexes.join(", "))
+    }
+}
+
+impl fmt::Display for Join {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.natural {
+            f.write_str("NATURAL ")?;
+        }
+        f.write_str(match self.kind {
+            JoinKind::Inner => "JOIN",
+            JoinKind::Cross => "CROSS JOIN",
+            JoinKind::Left => "LEFT JOIN",
+            JoinKind::Right => "RIGHT JOIN",
+            JoinKind::Straight => "STRAIGHT_JOIN",
+            JoinKind::Comma => ",",
+        })?;
+        write!(f, " {}", self.table)?;
+        if let Some(on) = &self.on {
+            write!(f, " ON {}", on)?;
+        }
+        if let Some(using) = &self.using {
+            write!(f, " USING ({})", using.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+// 用逗号连接各项
+fn join<T: fmt::Display>(items: &[T]) -> String {
+    items.iter().map(|item| item.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+/// 重新输出为 SQL，INTO 子句统一放在末尾
+impl fmt::Display for With {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.recursive { "WITH RECURSIVE " } else { "WITH " })?;
+        for (i, cte) in self.ctes.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(&cte.name)?;
+            if !cte.columns.is_empty() {
+                write!(f, " ({})", cte.columns.join(", "))?;
+            }
+            write!(f, " AS ({})", cte.query)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SetOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.op.name())?;
+        if self.all {
+            f.write_str(" ALL")?;
+        }
+        // 带有作用于整个查询的子句时，操作数需要加括号
+        let select = &self.select;
+        if select.with.is_some()
+            || !select.set_operations.is_empty()
+            || select.order_by.is_some()
+            || select.limit.is_some()
+            || select.lock.is_some()
+            || select.into.is_some()
+        {
+            write!(f, " ({})", select)
+        } else {
+            write!(f, " {}", select)
+        }
+    }
+}
+
+impl fmt::Display for SelectStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(with) = &self.with {
+            write!(f, "{} ", with)?;
+        }
+        f.write_str("SELECT ")?;
+        if let Some(exprs) = &self.distinct_on {
+            write!(f, "DISTINCT ON ({}) ", join(exprs))?;
+        } else if self.distinct {
+            f.write_str("DISTINCT ")?;
+        }
+        if let Some(top) = &self.top {
+            write!(f, "{} ", top)?;
+        }
+        let modifiers = &self.modifiers;
+        for (set, keyword) in [
+            (modifiers.high_priority, "HIGH_PRIORITY "),
+            (modifiers.straight_join, "STRAIGHT_JOIN "),
+            (modifiers.sql_small_result, "SQL_SMALL_RESULT "),
+            (modifiers.sql_big_result, "SQL_BIG_RESULT "),
+            (modifiers.sql_buffer_result, "SQL_BUFFER_RESULT "),
+            (modifiers.sql_no_cache, "SQL_NO_CACHE "),
+            (modifiers.sql_calc_found_rows, "SQL_CALC_FOUND_ROWS "),
+        ] {
+            if set {
+                f.write_str(keyword)?;
+            }
+        }
+        f.write_str(&join(&self.columns))?;
+        if let Some(from) = &self.from {
+            write!(f, " FROM {}", from)?;
+        }
+        for join in &self.joins {
+            // 逗号紧跟在前一个表之后
+            if join.kind != JoinKind::Comma {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}", join)?;
+        }
+        if let Some(condition) = &self.where_clause {
+            write!(f, " WHERE {}", condition)?;
+        }
+        if let Some(group_by) = &self.group_by {
+            write!(f, " GROUP BY {}", join(group_by))?;
+            if self.with_rollup {
+                f.write_str(" WITH ROLLUP")?;
+            }
+        }
+        if let Some(having) = &self.having {
+            write!(f, " HAVING {}", having)?;
+        }
+        if let Some(qualify) = &self.qualify {
+            write!(f, " QUALIFY {}", qualify)?;
+        }
+        for (i, (name, spec)) in self.windows.iter().enumerate() {
+            write!(f, "{}{} AS ({})", if i == 0 { " WINDOW " } else { ", " }, name, spec)?;
+        }
+        for operation in &self.set_operations {
+            write!(f, " {}", operation)?;
+        }
+        if let Some(order_by) = &self.order_by {
+            write!(f, " ORDER BY {}", join(order_by))?;
+        }
+        if let Some(limit) = &self.limit {
+            write!(f, " {}", limit)?;
+        }
+        if let Some(lock) = &self.lock {
+            f.write_str(match lock.mode {
+                LockMode::Update => " FOR UPDATE",
+                LockMode::Share => " FOR SHARE",
+            })?;
+            f.write_str(match lock.wait {
+                LockWait::Wait => "",
+                LockWait::Nowait => " NOWAIT",
+                LockWait::SkipLocked => " SKIP LOCKED",
+            })?;
+        }
+        if let Some(into) = &self.into {
+            write!(f, " {}", into)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SelectColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let alias = match self {
+            SelectColumn::Wildcard => return f.write_str("*"),
+            SelectColumn::QualifiedWildcard(table) => return write!(f, "{}.*", table),
+            SelectColumn::Column { name, alias } => {
+                write!(f, "{}", name)?;
+                alias
+            }
+            SelectColumn::Expression { expr, alias } => {
+                write!(f, "{}", expr)?;
+                alias
+            }
+        };
+        if let Some(alias) = alias {
+            write!(f, " AS {}", alias)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for IntoClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntoClause::Outfile { path, charset, fields, lines } => {
+                write!(f, "INTO OUTFILE {}", quote_string(path))?;
+                if let Some(charset) = charset {
+                    write!(f, " CHARACTER SET {}", charset)?;
+                }
+                if let Some(fields) = fields {
+                    f.write_str(" FIELDS")?;
+                    if let Some(s) = &fields.terminated_by {
+                        write!(f, " TERMINATED BY {}", quote_string(s))?;
+                    }
+                    if let Some(s) = &fields.enclosed_by {
+                        let optionally = if fields.optionally_enclosed { " OPTIONALLY" } else { "" };
+                        write!(f, "{} ENCLOSED BY {}", optionally, quote_string(s))?;
+                    }
+                    if let Some(s) = &fields.escaped_by {
+                        write!(f, " ESCAPED BY {}", quote_string(s))?;
+                    }
+                }
+                if let Some(lines) = lines {
+                    f.write_str(" LINES")?;
+                    if let Some(s) = &lines.starting_by {
+                        write!(f, " STARTING BY {}", quote_string(s))?;
+                    }
+                    if let Some(s) = &lines.terminated_by {
+                        write!(f, " TERMINATED BY {}", quote_string(s))?;
+                    }
+                }
+                Ok(())
+            }
+            IntoClause::Dumpfile(path) => write!(f, "INTO DUMPFILE {}", quote_string(path)),
+            IntoClause::Variables(variables) => write!(f, "INTO {}", variables.join(", ")),
+        }
+    }
+}
+
+// 输出 col = expr, ... 形式的赋值列表
+fn fmt_assignments(f: &mut fmt::Formatter<'_>, assignments: &[(ObjectName, Expr)]) -> fmt::Result {
+    for (i, (column, value)) in assignments.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{} = {}", column, value)?;
+    }
+    Ok(())
+}
+
+/// 重新输出为 SQL
+impl fmt::Display for InsertStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("INSERT")?;
+        let modifiers = &self.modifiers;
+        for (set, keyword) in [
+            (modifiers.low_priority, " LOW_PRIORITY"),
+            (modifiers.delayed, " DELAYED"),
+            (modifiers.high_priority, " HIGH_PRIORITY"),
+            (modifiers.ignore, " IGNORE"),
+        ] {
+            if set {
+                f.write_str(keyword)?;
+            }
+        }
+        write!(f, " INTO {}", self.table)?;
+        if let Some(columns) = &self.columns {
+            write!(f, " ({})", columns.join(", "))?;
+        }
+        if self.is_default_values {
+            f.write_str(" DEFAULT VALUES")?;
+        } else if let Some(rows) = &self.values {
+            f.write_str(" VALUES ")?;
+            for (i, row) in rows.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                let cells: Vec<String> = row.iter().map(|cell| cell.to_string()).collect();
+                write!(f, "({})", cells.join(", "))?;
+            }
+        } else if let Some(assignments) = &self.set_clause {
+            f.write_str(" SET ")?;
+            fmt_assignments(f, assignments)?;
+        } else if let Some(select) = &self.select_clause {
+            write!(f, " {}", select)?;
+        }
+        if let Some(alias) = &self.row_alias {
+            write!(f, " AS {}", alias.name)?;
+            if let Some(columns) = &alias.columns {
+                write!(f, "({})", columns.join(", "))?;
+            }
+        }
+        if let Some(on_duplicate) = &self.on_duplicate {
+            f.write_str(" ON DUPLICATE KEY UPDATE ")?;
+            fmt_assignments(f, &on_duplicate.updates)?;
+        }
+        Ok(())
+    }
+}
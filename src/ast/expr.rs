@@ -1,8 +1,13 @@
+use super::common::{DataType, ObjectName};
+use super::select::SelectStatement;
+use std::hash::{Hash, Hasher};
+
 /// 表示SQL表达式
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Expr {
-    /// 标识符（列名）
-    Identifier(String),
+    /// 标识符（列名），保留限定符和引号，例如 id、e.department、mydb.users.id
+    /// 通过 Display 可以得到兼容以前的拼接形式 "e.department"
+    Identifier(ObjectName),
 
     Wildcard,  // * 通配符
     
@@ -17,6 +22,7 @@ pub enum Expr {
     },
     
     /// IN 表达式（如 id IN (1, 2, 3)）
+    /// IN (SELECT ...) 的 list 只有一个 Expr::Subquery 元素
     In {
         expr: Box<Expr>,
         list: Vec<Expr>,
@@ -37,12 +43,23 @@ pub enum Expr {
         negated: bool,  // 表示 IS NULL 或 IS NOT NULL
     },
     
-    /// 函数调用（如 COUNT(*), SUM(price)）
+    /// 函数调用（如 LOWER(name), CONCAT(a, b)）
     FunctionCall {
         name: String,
         args: Vec<Expr>,
     },
     
+    /// 聚合函数（如 COUNT(*)、COUNT(DISTINCT a)、SUM(price)）
+    /// COUNT(*) 的 args 只有一个 Expr::Wildcard 元素
+    Aggregate {
+        func: AggregateFunction,
+        distinct: bool,
+        args: Vec<Expr>,
+    },
+
+    /// GROUP_CONCAT([DISTINCT] expr, ... [ORDER BY ...] [SEPARATOR 'str'])
+    GroupConcat(Box<GroupConcat>),
+
     /// 逻辑操作符表达式
     LogicalOp {
         op: LogicalOperator,
@@ -53,11 +70,94 @@ pub enum Expr {
     UnaryOp {
         op: UnaryOperator,
         expr: Box<Expr>,
-    }
+    },
+
+    /// 字符集引导符（如 _utf8mb4'text'）
+    Introducer {
+        charset: String,
+        expr: Box<Expr>,
+    },
+
+    /// 指定排序规则（如 name COLLATE utf8mb4_bin）
+    Collate {
+        expr: Box<Expr>,
+        collation: String,
+    },
+
+    /// 类型转换（如 CAST(price AS DECIMAL(10, 2))）
+    /// MySQL 的 CAST(x AS SIGNED [INTEGER]) 和 CAST(x AS UNSIGNED [INTEGER]) 的类型名为 SIGNED、UNSIGNED
+    Cast {
+        expr: Box<Expr>,
+        data_type: DataType,
+    },
+
+    /// 时间间隔（如 INTERVAL 7 DAY、INTERVAL '1:30' HOUR_MINUTE）
+    Interval {
+        value: Box<Expr>,
+        unit: IntervalUnit,
+    },
+
+    /// 全文检索（如 MATCH(title, body) AGAINST ('rust' IN BOOLEAN MODE)）
+    Match {
+        columns: Vec<ObjectName>,
+        against: Box<Expr>,
+        mode: SearchMode,
+    },
+
+    /// 用户变量赋值（如 @row := @row + 1），variable 保留 @ 前缀
+    Assignment {
+        variable: String,
+        value: Box<Expr>,
+    },
+
+    /// 括号中的子查询（如 (SELECT MAX(id) FROM users)）
+    Subquery(Box<SelectStatement>),
+
+    /// PostgreSQL 的数组字面量（如 ARRAY[1, 2, 3]）
+    Array(Vec<Expr>),
+
+    /// PostgreSQL 的数组下标（如 tags[1]），下标从1开始
+    Subscript {
+        expr: Box<Expr>,
+        index: Box<Expr>,
+    },
+
+    /// 窗口函数（如 ROW_NUMBER() OVER (PARTITION BY a ORDER BY b)、SUM(x) OVER w）
+    /// func 为 FunctionCall 或 Aggregate
+    Window {
+        func: Box<Expr>,
+        over: Box<WindowSpec>,
+    },
+
+    /// PostgreSQL 与数组中任一元素比较（如 'beta' = ANY(flags)）
+    /// array 也可以是 Expr::Subquery
+    Any {
+        expr: Box<Expr>,
+        op: BinaryOperator,
+        array: Box<Expr>,
+    },
+
+    /// CASE 表达式（如 CASE WHEN a > 0 THEN 'y' ELSE 'n' END）
+    /// 简单形式 CASE x WHEN 1 THEN ... 的 operand 为 x；conditions 与 results 一一对应
+    Case {
+        operand: Option<Box<Expr>>,
+        conditions: Vec<Expr>,
+        results: Vec<Expr>,
+        else_result: Option<Box<Expr>>,
+    },
+
+    /// EXISTS (SELECT ...)，NOT EXISTS 为外层的 NOT
+    Exists(Box<SelectStatement>),
+
+    /// 提取日期时间的一部分（如 EXTRACT(YEAR FROM o_orderdate)）
+    Extract {
+        unit: IntervalUnit,
+        expr: Box<Expr>,
+    },
 }
 
 /// 二元操作符
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum BinaryOperator {
     Eq,      // =
     NotEq,   // !=, <>
@@ -73,14 +173,145 @@ pub enum BinaryOperator {
 }
 
 /// 一元操作符
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum UnaryOperator {
     Plus,    // +
     Minus,   // -
 }
 
+/// INTERVAL 的时间单位
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntervalUnit {
+    Microsecond,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+    SecondMicrosecond,
+    MinuteMicrosecond,
+    MinuteSecond,
+    HourMicrosecond,
+    HourSecond,
+    HourMinute,
+    DayMicrosecond,
+    DaySecond,
+    DayMinute,
+    DayHour,
+    YearMonth,
+}
+
+impl IntervalUnit {
+    const ALL: [IntervalUnit; 20] = [
+        IntervalUnit::Microsecond,
+        IntervalUnit::Second,
+        IntervalUnit::Minute,
+        IntervalUnit::Hour,
+        IntervalUnit::Day,
+        IntervalUnit::Week,
+        IntervalUnit::Month,
+        IntervalUnit::Quarter,
+        IntervalUnit::Year,
+        IntervalUnit::SecondMicrosecond,
+        IntervalUnit::MinuteMicrosecond,
+        IntervalUnit::MinuteSecond,
+        IntervalUnit::HourMicrosecond,
+        IntervalUnit::HourSecond,
+        IntervalUnit::HourMinute,
+        IntervalUnit::DayMicrosecond,
+        IntervalUnit::DaySecond,
+        IntervalUnit::DayMinute,
+        IntervalUnit::DayHour,
+        IntervalUnit::YearMonth,
+    ];
+
+    /// SQL中的单位名称，例如 DAY、HOUR_MINUTE
+    pub fn name(&self) -> &'static str {
+        match self {
+            IntervalUnit::Microsecond => "MICROSECOND",
+            IntervalUnit::Second => "SECOND",
+            IntervalUnit::Minute => "MINUTE",
+            IntervalUnit::Hour => "HOUR",
+            IntervalUnit::Day => "DAY",
+            IntervalUnit::Week => "WEEK",
+            IntervalUnit::Month => "MONTH",
+            IntervalUnit::Quarter => "QUARTER",
+            IntervalUnit::Year => "YEAR",
+            IntervalUnit::SecondMicrosecond => "SECOND_MICROSECOND",
+            IntervalUnit::MinuteMicrosecond => "MINUTE_MICROSECOND",
+            IntervalUnit::MinuteSecond => "MINUTE_SECOND",
+            IntervalUnit::HourMicrosecond => "HOUR_MICROSECOND",
+            IntervalUnit::HourSecond => "HOUR_SECOND",
+            IntervalUnit::HourMinute => "HOUR_MINUTE",
+            IntervalUnit::DayMicrosecond => "DAY_MICROSECOND",
+            IntervalUnit::DaySecond => "DAY_SECOND",
+            IntervalUnit::DayMinute => "DAY_MINUTE",
+            IntervalUnit::DayHour => "DAY_HOUR",
+            IntervalUnit::YearMonth => "YEAR_MONTH",
+        }
+    }
+
+    /// 按名称查找单位，忽略大小写
+    pub fn from_name(name: &str) -> Option<IntervalUnit> {
+        IntervalUnit::ALL.into_iter().find(|unit| unit.name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// 聚合函数的种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AggregateFunction {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+impl AggregateFunction {
+    const ALL: [AggregateFunction; 5] = [
+        AggregateFunction::Count,
+        AggregateFunction::Sum,
+        AggregateFunction::Avg,
+        AggregateFunction::Min,
+        AggregateFunction::Max,
+    ];
+
+    /// SQL中的函数名，例如 COUNT
+    pub fn name(&self) -> &'static str {
+        match self {
+            AggregateFunction::Count => "COUNT",
+            AggregateFunction::Sum => "SUM",
+            AggregateFunction::Avg => "AVG",
+            AggregateFunction::Min => "MIN",
+            AggregateFunction::Max => "MAX",
+        }
+    }
+
+    /// 按函数名查找，忽略大小写
+    pub fn from_name(name: &str) -> Option<AggregateFunction> {
+        AggregateFunction::ALL.into_iter().find(|func| func.name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// MATCH ... AGAINST 的检索模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SearchMode {
+    /// IN NATURAL LANGUAGE MODE，未指定时的默认模式
+    #[default]
+    NaturalLanguage,
+    /// IN NATURAL LANGUAGE MODE WITH QUERY EXPANSION
+    NaturalLanguageWithQueryExpansion,
+    /// IN BOOLEAN MODE
+    Boolean,
+    /// WITH QUERY EXPANSION
+    QueryExpansion,
+}
+
 /// 逻辑操作符
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum LogicalOperator {
     And,
     Or,
@@ -93,24 +324,188 @@ pub enum Value {
     String(String),
     Integer(i64),
     Float(f64),
+    /// 定点小数，保留字面量原文（例如 999.99），避免 f64 的精度损失
+    Decimal(String),
     Boolean(bool),
     Null,
     DEFAULT, // 用于DEFAULT关键字
 }
 
 
+// 解析得到的浮点字面量不会是 NaN，因此可以认为 Value 满足 Eq
+impl Eq for Value {}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Value::String(s) => s.hash(state),
+            Value::Integer(i) => i.hash(state),
+            // 按位哈希，与 PartialEq 保持一致（0.0 与 -0.0 除外，字面量不会出现 -0.0）
+            Value::Float(f) => f.to_bits().hash(state),
+            Value::Decimal(d) => d.hash(state),
+            Value::Boolean(b) => b.hash(state),
+            Value::Null | Value::DEFAULT => {}
+        }
+    }
+}
+
+impl Value {
+    /// 数值取负，非数值或溢出时返回None
+    pub fn negate(&self) -> Option<Value> {
+        match self {
+            Value::Integer(i) => i.checked_neg().map(Value::Integer),
+            // 0.0 取负后仍为 0.0，避免出现 -0.0
+            Value::Float(f) => Some(Value::Float(if *f == 0.0 { 0.0 } else { -f })),
+            Value::Decimal(d) => Some(Value::Decimal(match d.strip_prefix('-') {
+                Some(abs) => abs.to_string(),
+                None => format!("-{}", d),
+            })),
+            _ => None,
+        }
+    }
+}
+
+impl From<i64> for Value {
+    fn from(i: i64) -> Self {
+        Value::Integer(i)
+    }
+}
+
+impl From<i32> for Value {
+    fn from(i: i32) -> Self {
+        Value::Integer(i.into())
+    }
+}
+
+impl From<f64> for Value {
+    fn from(f: f64) -> Self {
+        Value::Float(f)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Boolean(b)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl Expr {
+    /// 是否为聚合函数，包括 GROUP_CONCAT
+    pub fn is_aggregate(&self) -> bool {
+        matches!(self, Expr::Aggregate { .. } | Expr::GroupConcat(_))
+    }
+
+    /// 是否为 COUNT(*)
+    pub fn is_count_star(&self) -> bool {
+        matches!(
+            self,
+            Expr::Aggregate { func: AggregateFunction::Count, distinct: false, args } if args == &[Expr::Wildcard]
+        )
+    }
+}
+
+/// GROUP_CONCAT 聚合函数
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GroupConcat {
+    pub distinct: bool,
+    pub args: Vec<Expr>,
+    pub order_by: Vec<OrderByExpr>, // 为空表示没有 ORDER BY
+    pub separator: Option<String>,
+}
+
+/// 窗口定义：OVER (...) 或 WINDOW w AS (...) 括号中的内容，OVER w 只有 name
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct WindowSpec {
+    /// 引用的命名窗口，例如 OVER w、OVER (w ORDER BY b)
+    pub name: Option<String>,
+    pub partition_by: Vec<Expr>,
+    pub order_by: Vec<OrderByExpr>,
+    pub frame: Option<WindowFrame>,
+}
+
+impl WindowSpec {
+    /// PARTITION BY 和 ORDER BY 中的表达式，按出现顺序
+    pub fn exprs(&self) -> impl Iterator<Item = &Expr> {
+        self.partition_by.iter().chain(self.order_by.iter().filter_map(|item| match &item.expr {
+            SortKey::Expr(expr) => Some(expr),
+            SortKey::Ordinal(_) => None,
+        }))
+    }
+
+    /// 与 [`WindowSpec::exprs`] 相同，返回可变引用
+    pub fn exprs_mut(&mut self) -> impl Iterator<Item = &mut Expr> {
+        self.partition_by.iter_mut().chain(self.order_by.iter_mut().filter_map(|item| match &mut item.expr {
+            SortKey::Expr(expr) => Some(expr),
+            SortKey::Ordinal(_) => None,
+        }))
+    }
+}
+
+/// 窗口框架 {ROWS | RANGE} {start | BETWEEN start AND end}
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WindowFrame {
+    pub units: FrameUnits,
+    pub start: FrameBound,
+    /// 只有起点时终点为 CURRENT ROW
+    pub end: Option<FrameBound>,
+}
+
+/// 窗口框架的单位
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrameUnits {
+    Rows,
+    Range,
+}
+
+/// 窗口框架的边界，偏移量不作为子表达式遍历
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FrameBound {
+    UnboundedPreceding,
+    Preceding(Box<Expr>),
+    CurrentRow,
+    Following(Box<Expr>),
+    UnboundedFollowing,
+}
+
 /// 表示ORDER BY子句中的表达式
-#[derive(Debug, Clone,PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct OrderByExpr {
-    pub expr: Expr,    // 允许任何表达式类型
+    pub expr: SortKey, // 允许任何表达式类型，SELECT中还可以是列序号
     pub asc: bool,     // true表示ASC，false表示DESC
 }
 
+/// ORDER BY、GROUP BY 的一项
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SortKey {
+    Expr(Expr),
+    /// select列表中的列序号，从1开始，例如 ORDER BY 1 DESC、GROUP BY 2
+    Ordinal(u32),
+}
+
+impl From<Expr> for SortKey {
+    fn from(expr: Expr) -> Self {
+        SortKey::Expr(expr)
+    }
+}
+
 /// 表示LIMIT子句
-#[derive(Debug, Clone,PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct LimitClause {
     /// 要返回的最大行数
     pub limit: u64,
     /// 要跳过的行数（用于分页）
     pub offset: Option<u64>,
-}
\ No newline at end of file
+}
@@ -1,15 +1,17 @@
-use super::expr::{Expr,OrderByExpr,LimitClause};
-use super::common::TableReference;
+use super::expr::Expr;
+use super::common::{ObjectName, TableReference};
 use super::select::SelectStatement;
 
 /// insert 语句结构
-#[derive(Debug, Clone,PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct InsertStatement {
-    pub table: TableReference,  // 表名
+    pub modifiers: InsertModifiers, // INSERT 之后的修饰符
+    pub table: TableReference,  // 表名（含 PARTITION 分区列表）
     pub columns: Option<Vec<String>>,  // 可选列名
     pub values: Option<Vec<Vec<Expr>>>, // 插入的值(可以插入多个记录)
     pub select_clause: Option<SelectStatement>, // 当没有values时，使用select语句插入
-    pub set_clause: Option<Vec<(String, Expr)>>, // 当没有values时，使用set语句插入
+    pub set_clause: Option<Vec<(ObjectName, Expr)>>, // 当没有values时，使用set语句插入
+    pub row_alias: Option<RowAlias>, // VALUES 或 SET 之后的行别名，供 ON DUPLICATE KEY UPDATE 引用
     pub on_duplicate: Option<OnDuplicateClause>, // 冲突处理
     pub is_default_values: bool,  // 是否为 INSERT ... DEFAULT VALUES
     pub is_return_count:bool,
@@ -17,7 +19,24 @@ pub struct InsertStatement {
 
 
 // 冲突处理子句
-#[derive(Debug, Clone,PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct OnDuplicateClause {
-    pub updates: Vec<(String, Expr)>,  // 列名和新值对
+    pub updates: Vec<(ObjectName, Expr)>,  // 列名（可以带限定符）和新值对
+}
+
+/// 新插入行的别名（MySQL 8.0.19+），例如 VALUES (...) AS new(a, b)
+/// 之后 ON DUPLICATE KEY UPDATE 中可以用 new.a 或 a 代替 VALUES(a)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RowAlias {
+    pub name: String,
+    pub columns: Option<Vec<String>>, // 可选的列别名
+}
+
+/// INSERT 修饰符：[LOW_PRIORITY | DELAYED | HIGH_PRIORITY] [IGNORE]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct InsertModifiers {
+    pub low_priority: bool,
+    pub delayed: bool,
+    pub high_priority: bool,
+    pub ignore: bool,
 }
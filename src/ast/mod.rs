@@ -4,22 +4,53 @@ pub mod select;
 pub mod insert;
 
 pub mod delete;
+pub mod update;
+#[cfg(feature = "ddl")]
+pub mod create;
+#[cfg(feature = "ddl")]
+pub mod alter;
+pub mod show;
+pub mod set;
+pub mod semantic;
+pub mod symbol;
+pub mod visit;
+pub mod query;
+pub mod build;
+pub mod alias;
+#[cfg(feature = "routines")]
+pub mod routine;
+#[cfg(feature = "arena")]
+pub mod arena;
+#[cfg(feature = "formatter")]
+mod display;
 
 pub use select::{SelectStatement, SelectColumn};
+use insert::InsertStatement;
 use delete::DeleteStatement;
+use update::UpdateStatement;
+#[cfg(feature = "ddl")]
+use create::CreateStatement;
+#[cfg(feature = "ddl")]
+use alter::AlterStatement;
+use show::ShowStatement;
+use set::SetStatement;
 
 #[non_exhaustive]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SQLStatement {
-    Select(SelectStatement),
+    Select(Box<SelectStatement>),
+    Insert(Box<InsertStatement>),
     // Insert(InsertStatement),
-    // Update(UpdateStatement),
+    Update(UpdateStatement),
     Delete(DeleteStatement),
-    // Create(CreateStatement),
+    #[cfg(feature = "ddl")]
+    Create(CreateStatement),
     // Drop(DropStatement),
-    // Alter(AlterStatement),
+    #[cfg(feature = "ddl")]
+    Alter(AlterStatement),
     // Use(UseStatement),
-    // Show(ShowStatement),
+    Show(ShowStatement),
+    Set(SetStatement),
     // Explain(ExplainStatement),
     // BeginTransaction(BeginTransactionStatement),
     // Commit(CommitStatement),
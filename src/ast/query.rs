@@ -0,0 +1,654 @@
+//! 类似 XPath 的节点选择
+//!
+//! 把语句看作一棵节点树，用路径表达式查找节点，例如 `Select/Where//FunctionCall[name='NOW']`
+//! 查找 WHERE 条件中所有的 NOW() 调用。lint 规则和临时脚本可以直接写路径，不必为每个检查实现遍历。
+//!
+//! 路径由若干步组成，`/` 选择子节点，`//` 选择所有后代节点，开头的一步匹配语句本身。每一步是节点名或
+//! 匹配任意节点的 `*`，后面可以跟若干条件：`[attr]` 要求节点有这个属性，`[attr='v']`、`[attr!='v']`
+//! 比较属性值，值不区分大小写，可以用单引号或双引号包裹，引号内两个连续的引号表示一个引号。
+//!
+//! 节点名和属性：
+//! - 语句：`Select`、`Insert`、`Update`、`Delete`，DDL 和 SHOW 语句没有节点
+//! - 从句：`Columns`、`DistinctOn`、`From`、`Where`、`GroupBy`、`Having`、`Qualify`、`Window`、`OrderBy`、
+//!   `Limit`、`Values`、`Set`、`OnDuplicate`、`On`，子节点是从句中的表、表达式或赋值；`With` 的子节点是
+//!   各个 CTE 的 `Select`，`Union`、`Except`、`Intersect` 的子节点是参与运算的 `Select`
+//! - `Table`（name、alias），派生表的子节点是 `Select`；`Join`（kind，例如 LEFT）；`SetItem`（column）
+//! - 表达式按 [`Expr`] 的变体命名，例如 `Identifier`（name、column）、`Literal`（value、type）、
+//!   `BinaryOp`（op）、`Cast`（type）、`FunctionCall`（name）、`Aggregate`（name）、`Subquery`（子节点是 `Select`）。
+//!   查询列表中的列名同样是 `Identifier`，`*` 和 `t.*` 是 `Wildcard`（table）
+
+use std::error::Error;
+use std::fmt;
+
+use super::common::{ObjectName, TableFunction, TableReference};
+use super::delete::DeleteStatement;
+use super::expr::{BinaryOperator, Expr, LogicalOperator, OrderByExpr, SortKey, UnaryOperator, Value};
+use super::insert::InsertStatement;
+use super::select::{Join, JoinKind, SelectColumn, SelectStatement, SetOperator};
+use super::update::UpdateStatement;
+use super::SQLStatement;
+
+/// 语句树中的一个节点
+#[derive(Debug, Clone)]
+pub struct Node<'a> {
+    name: &'static str,
+    // 节点在AST中的地址，与名称一起标识节点，用于结果去重
+    addr: usize,
+    value: NodeValue<'a>,
+}
+
+#[derive(Debug, Clone)]
+enum NodeValue<'a> {
+    Select(&'a SelectStatement),
+    Insert(&'a InsertStatement),
+    Update(&'a UpdateStatement),
+    Delete(&'a DeleteStatement),
+    Table(&'a TableReference),
+    Join(&'a Join),
+    SetItem(&'a (ObjectName, Expr)),
+    Column(&'a SelectColumn),
+    Expr(&'a Expr),
+    // 从句本身没有对应的AST节点，直接保存子节点
+    Clause(Vec<Node<'a>>),
+}
+
+impl<'a> Node<'a> {
+    /// 语句的根节点，DDL 和 SHOW 语句返回None
+    pub fn root(stmt: &'a SQLStatement) -> Option<Self> {
+        match stmt {
+            SQLStatement::Select(select) => Some(Node::select(select)),
+            SQLStatement::Insert(insert) => Some(Node::new("Insert", &**insert, NodeValue::Insert(insert))),
+            SQLStatement::Update(update) => Some(Node::new("Update", update, NodeValue::Update(update))),
+            SQLStatement::Delete(delete) => Some(Node::new("Delete", delete, NodeValue::Delete(delete))),
+            _ => None,
+        }
+    }
+
+    fn new<T>(name: &'static str, target: &T, value: NodeValue<'a>) -> Self {
+        Node { name, addr: target as *const T as usize, value }
+    }
+
+    fn select(select: &'a SelectStatement) -> Self {
+        Node::new("Select", select, NodeValue::Select(select))
+    }
+
+    fn table(table: &'a TableReference) -> Self {
+        Node::new("Table", table, NodeValue::Table(table))
+    }
+
+    fn expr(expr: &'a Expr) -> Self {
+        Node::new(expr_name(expr), expr, NodeValue::Expr(expr))
+    }
+
+    // 从句节点以所属的节点作为地址
+    fn clause<T>(name: &'static str, owner: &T, children: Vec<Node<'a>>) -> Self {
+        Node::new(name, owner, NodeValue::Clause(children))
+    }
+
+    fn set_items<T>(name: &'static str, owner: &T, items: &'a [(ObjectName, Expr)]) -> Self {
+        let children = items.iter().map(|item| Node::new("SetItem", item, NodeValue::SetItem(item))).collect();
+        Node::clause(name, owner, children)
+    }
+
+    /// 节点名，例如 Select、Where、FunctionCall
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// 表达式节点对应的表达式
+    pub fn as_expr(&self) -> Option<&'a Expr> {
+        match self.value {
+            NodeValue::Expr(expr) => Some(expr),
+            NodeValue::Column(SelectColumn::Expression { expr, .. }) => Some(expr),
+            _ => None,
+        }
+    }
+
+    /// Select 节点对应的查询
+    pub fn as_select(&self) -> Option<&'a SelectStatement> {
+        match self.value {
+            NodeValue::Select(select) => Some(select),
+            _ => None,
+        }
+    }
+
+    /// Table 节点对应的表
+    pub fn as_table(&self) -> Option<&'a TableReference> {
+        match self.value {
+            NodeValue::Table(table) => Some(table),
+            _ => None,
+        }
+    }
+
+    /// 节点的属性值，没有这个属性时返回None
+    pub fn attr(&self, name: &str) -> Option<String> {
+        match (&self.value, name) {
+            (NodeValue::Table(table), "name") => Some(object_name(&table.name)),
+            (NodeValue::Table(table), "alias") => table.alias.clone(),
+            (NodeValue::Join(join), "kind") => Some(join_kind(join.kind).to_string()),
+            (NodeValue::SetItem((column, _)), "column") => Some(object_name(column)),
+            (NodeValue::Column(SelectColumn::Column { name: column, .. }), "name") => Some(object_name(column)),
+            (NodeValue::Column(SelectColumn::Column { name: column, .. }), "column") => Some(column.name().value.to_string()),
+            (NodeValue::Column(SelectColumn::Column { alias, .. }), "alias") => alias.clone(),
+            (NodeValue::Column(SelectColumn::QualifiedWildcard(table)), "table") => Some(object_name(table)),
+            (NodeValue::Expr(expr), _) => expr_attr(expr, name),
+            _ => None,
+        }
+    }
+
+    /// 子节点，按在语句中出现的顺序排列
+    pub fn children(&self) -> Vec<Node<'a>> {
+        match &self.value {
+            NodeValue::Select(select) => select_children(select),
+            NodeValue::Insert(insert) => {
+                let mut children = vec![Node::table(&insert.table)];
+                if let Some(rows) = &insert.values {
+                    children.push(Node::clause("Values", *insert, rows.iter().flatten().map(Node::expr).collect()));
+                }
+                if let Some(items) = &insert.set_clause {
+                    children.push(Node::set_items("Set", *insert, items));
+                }
+                children.extend(insert.select_clause.as_ref().map(Node::select));
+                if let Some(clause) = &insert.on_duplicate {
+                    children.push(Node::set_items("OnDuplicate", *insert, &clause.updates));
+                }
+                children
+            }
+            NodeValue::Update(update) => {
+                let mut children = vec![Node::table(&update.table), Node::set_items("Set", *update, &update.assignments)];
+                filter_children(&mut children, *update, &update.where_clause, &update.order_by, update.limit.is_some());
+                children
+            }
+            NodeValue::Delete(delete) => {
+                let mut children = vec![Node::table(&delete.table)];
+                filter_children(&mut children, *delete, &delete.where_clause, &delete.order_by, delete.limit.is_some());
+                children
+            }
+            NodeValue::Table(table) => match (&table.subquery, table.function.as_deref()) {
+                (Some(subquery), _) => vec![Node::select(subquery)],
+                (None, Some(TableFunction::JsonTable { expr, .. })) => vec![Node::expr(expr)],
+                (None, Some(TableFunction::Call { args, .. })) => args.iter().map(Node::expr).collect(),
+                (None, None) => Vec::new(),
+            },
+            NodeValue::Join(join) => {
+                let mut children = vec![Node::table(&join.table)];
+                children.extend(join.on.as_ref().map(|on| Node::clause("On", *join, vec![Node::expr(on)])));
+                children
+            }
+            NodeValue::SetItem((_, value)) => vec![Node::expr(value)],
+            NodeValue::Column(_) => Vec::new(),
+            NodeValue::Expr(Expr::Subquery(select) | Expr::Exists(select)) => vec![Node::select(select)],
+            NodeValue::Expr(expr) => expr.children().into_iter().map(Node::expr).collect(),
+            NodeValue::Clause(children) => children.clone(),
+        }
+    }
+
+    // 前序收集所有后代节点，不包括自身
+    fn descendants(&self, out: &mut Vec<Node<'a>>) {
+        for child in self.children() {
+            out.push(child.clone());
+            child.descendants(out);
+        }
+    }
+
+    fn same(&self, other: &Node) -> bool {
+        self.name == other.name && self.addr == other.addr
+    }
+}
+
+fn select_children(select: &SelectStatement) -> Vec<Node<'_>> {
+    let columns = select
+        .columns
+        .iter()
+        .map(|column| match column {
+            SelectColumn::Expression { expr, .. } => Node::expr(expr),
+            SelectColumn::Column { .. } => Node::new("Identifier", column, NodeValue::Column(column)),
+            SelectColumn::Wildcard | SelectColumn::QualifiedWildcard(_) => {
+                Node::new("Wildcard", column, NodeValue::Column(column))
+            }
+        })
+        .collect();
+    let mut children = Vec::new();
+    if let Some(with) = &select.with {
+        children.push(Node::clause("With", with, with.ctes.iter().map(|cte| Node::select(&cte.query)).collect()));
+    }
+    children.push(Node::clause("Columns", select, columns));
+    if let Some(exprs) = &select.distinct_on {
+        children.push(Node::clause("DistinctOn", select, exprs.iter().map(Node::expr).collect()));
+    }
+    if let Some(from) = &select.from {
+        let mut tables = vec![Node::table(from)];
+        tables.extend(select.joins.iter().map(|join| Node::new("Join", join, NodeValue::Join(join))));
+        children.push(Node::clause("From", select, tables));
+    }
+    children.extend(expr_clause("Where", select, &select.where_clause));
+    if let Some(keys) = &select.group_by {
+        let keys = keys.iter().filter_map(|key| match key {
+            SortKey::Expr(expr) => Some(Node::expr(expr)),
+            SortKey::Ordinal(_) => None,
+        });
+        children.push(Node::clause("GroupBy", select, keys.collect()));
+    }
+    children.extend(expr_clause("Having", select, &select.having));
+    children.extend(expr_clause("Qualify", select, &select.qualify));
+    if !select.windows.is_empty() {
+        let exprs = select.windows.iter().flat_map(|(_, spec)| spec.exprs()).map(Node::expr);
+        children.push(Node::clause("Window", select, exprs.collect()));
+    }
+    for operation in &select.set_operations {
+        let name = match operation.op {
+            SetOperator::Union => "Union",
+            SetOperator::Except => "Except",
+            SetOperator::Intersect => "Intersect",
+        };
+        children.push(Node::clause(name, operation, vec![Node::select(&operation.select)]));
+    }
+    filter_children(&mut children, select, &None, &select.order_by, select.limit.is_some());
+    children
+}
+
+// 只包含一个表达式的从句，例如 WHERE、HAVING
+fn expr_clause<'a, T>(name: &'static str, owner: &T, expr: &'a Option<Expr>) -> Option<Node<'a>> {
+    expr.as_ref().map(|expr| Node::clause(name, owner, vec![Node::expr(expr)]))
+}
+
+// WHERE、ORDER BY、LIMIT 从句
+fn filter_children<'a, T>(
+    children: &mut Vec<Node<'a>>,
+    owner: &T,
+    where_clause: &'a Option<Expr>,
+    order_by: &'a Option<Vec<OrderByExpr>>,
+    limit: bool,
+) {
+    children.extend(expr_clause("Where", owner, where_clause));
+    if let Some(order_by) = order_by {
+        let keys = order_by.iter().filter_map(|item| match &item.expr {
+            SortKey::Expr(expr) => Some(Node::expr(expr)),
+            SortKey::Ordinal(_) => None,
+        });
+        children.push(Node::clause("OrderBy", owner, keys.collect()));
+    }
+    if limit {
+        children.push(Node::clause("Limit", owner, Vec::new()));
+    }
+}
+
+fn expr_name(expr: &Expr) -> &'static str {
+    match expr {
+        Expr::Identifier(_) => "Identifier",
+        Expr::Wildcard => "Wildcard",
+        Expr::Literal(_) => "Literal",
+        Expr::BinaryOp { .. } => "BinaryOp",
+        Expr::In { .. } => "In",
+        Expr::Between { .. } => "Between",
+        Expr::IsNull { .. } => "IsNull",
+        Expr::FunctionCall { .. } => "FunctionCall",
+        Expr::Aggregate { .. } => "Aggregate",
+        Expr::GroupConcat(_) => "GroupConcat",
+        Expr::LogicalOp { .. } => "LogicalOp",
+        Expr::UnaryOp { .. } => "UnaryOp",
+        Expr::Introducer { .. } => "Introducer",
+        Expr::Collate { .. } => "Collate",
+        Expr::Cast { .. } => "Cast",
+        Expr::Interval { .. } => "Interval",
+        Expr::Match { .. } => "Match",
+        Expr::Assignment { .. } => "Assignment",
+        Expr::Subquery(_) => "Subquery",
+        Expr::Array(_) => "Array",
+        Expr::Subscript { .. } => "Subscript",
+        Expr::Window { .. } => "Window",
+        Expr::Any { .. } => "Any",
+        Expr::Case { .. } => "Case",
+        Expr::Exists(_) => "Exists",
+        Expr::Extract { .. } => "Extract",
+    }
+}
+
+fn expr_attr(expr: &Expr, name: &str) -> Option<String> {
+    let negated = |negated: bool| Some(if negated { "true" } else { "false" }.to_string());
+    match (expr, name) {
+        (Expr::Identifier(object), "name") => Some(object_name(object)),
+        (Expr::Identifier(object), "column") => Some(object.name().value.to_string()),
+        (Expr::Literal(value), "value") => Some(match value {
+            Value::String(s) | Value::Decimal(s) => s.clone(),
+            Value::Integer(i) => i.to_string(),
+            Value::Float(f) => f.to_string(),
+            Value::Boolean(b) => if *b { "TRUE" } else { "FALSE" }.to_string(),
+            Value::Null => "NULL".to_string(),
+            Value::DEFAULT => "DEFAULT".to_string(),
+        }),
+        (Expr::Literal(value), "type") => Some(
+            match value {
+                Value::String(_) => "string",
+                Value::Integer(_) | Value::Float(_) | Value::Decimal(_) => "number",
+                Value::Boolean(_) => "boolean",
+                Value::Null => "null",
+                Value::DEFAULT => "default",
+            }
+            .to_string(),
+        ),
+        (Expr::BinaryOp { op, .. } | Expr::Any { op, .. }, "op") => Some(binary_operator(op).to_string()),
+        (Expr::LogicalOp { op, .. }, "op") => Some(
+            match op {
+                LogicalOperator::And => "AND",
+                LogicalOperator::Or => "OR",
+                LogicalOperator::Not => "NOT",
+            }
+            .to_string(),
+        ),
+        (Expr::UnaryOp { op, .. }, "op") => Some(
+            match op {
+                UnaryOperator::Plus => "+",
+                UnaryOperator::Minus => "-",
+            }
+            .to_string(),
+        ),
+        (Expr::FunctionCall { name: function, .. }, "name") => Some(function.clone()),
+        (Expr::Aggregate { func, .. }, "name") => Some(func.name().to_string()),
+        (Expr::Aggregate { distinct, .. }, "distinct") => negated(*distinct),
+        (Expr::GroupConcat(_), "name") => Some("GROUP_CONCAT".to_string()),
+        (Expr::In { negated: n, .. } | Expr::Between { negated: n, .. } | Expr::IsNull { negated: n, .. }, "negated") => {
+            negated(*n)
+        }
+        (Expr::Introducer { charset, .. }, "charset") => Some(charset.clone()),
+        (Expr::Collate { collation, .. }, "collation") => Some(collation.clone()),
+        (Expr::Cast { data_type, .. }, "type") => Some(data_type.name.clone()),
+        (Expr::Interval { unit, .. } | Expr::Extract { unit, .. }, "unit") => Some(unit.name().to_string()),
+        (Expr::Assignment { variable, .. }, "variable") => Some(variable.clone()),
+        (Expr::Window { over, .. }, "name") => over.name.clone(),
+        _ => None,
+    }
+}
+
+// 不带引号的对象名，例如 mydb.users
+fn object_name(name: &ObjectName) -> String {
+    name.0.iter().map(|ident| ident.value.as_str()).collect::<Vec<_>>().join(".")
+}
+
+fn join_kind(kind: JoinKind) -> &'static str {
+    match kind {
+        JoinKind::Inner => "INNER",
+        JoinKind::Cross => "CROSS",
+        JoinKind::Left => "LEFT",
+        JoinKind::Right => "RIGHT",
+        JoinKind::Straight => "STRAIGHT_JOIN",
+        JoinKind::Comma => "COMMA",
+    }
+}
+
+fn binary_operator(op: &BinaryOperator) -> &'static str {
+    match op {
+        BinaryOperator::Eq => "=",
+        BinaryOperator::NotEq => "!=",
+        BinaryOperator::Lt => "<",
+        BinaryOperator::LtEq => "<=",
+        BinaryOperator::Gt => ">",
+        BinaryOperator::GtEq => ">=",
+        BinaryOperator::Plus => "+",
+        BinaryOperator::Minus => "-",
+        BinaryOperator::Multiply => "*",
+        BinaryOperator::Divide => "/",
+        BinaryOperator::Like => "LIKE",
+    }
+}
+
+/// 路径表达式的语法错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathError {
+    pub message: String,
+    /// 出错位置在路径中的字节偏移
+    pub position: usize,
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid path at position {}: {}", self.position, self.message)
+    }
+}
+
+impl Error for PathError {}
+
+/// 解析后的路径表达式，可以重复用于多条语句
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path {
+    steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Step {
+    // 是否选择所有后代节点（//），否则只选择子节点
+    descendant: bool,
+    // 节点名，None 表示 *
+    name: Option<String>,
+    predicates: Vec<Predicate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Predicate {
+    attr: String,
+    // 比较的值以及是否为 !=，None 表示只要求有这个属性
+    test: Option<(bool, String)>,
+}
+
+impl Predicate {
+    fn matches(&self, node: &Node) -> bool {
+        match (node.attr(&self.attr), &self.test) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(actual), Some((negated, expected))) => actual.eq_ignore_ascii_case(expected) != *negated,
+        }
+    }
+}
+
+impl Step {
+    fn matches(&self, node: &Node) -> bool {
+        self.name.as_ref().is_none_or(|name| name.eq_ignore_ascii_case(node.name()))
+            && self.predicates.iter().all(|predicate| predicate.matches(node))
+    }
+}
+
+impl Path {
+    /// 解析路径表达式
+    pub fn parse(path: &str) -> Result<Self, PathError> {
+        PathParser { path, pos: 0 }.parse()
+    }
+
+    /// 按路径选择语句中的节点，结果按在语句中出现的顺序排列，同一个节点只出现一次
+    pub fn select<'a>(&self, stmt: &'a SQLStatement) -> Vec<Node<'a>> {
+        let Some(root) = Node::root(stmt) else {
+            return Vec::new();
+        };
+        // 开头的一步从虚拟的文档节点出发，它唯一的子节点是语句本身
+        let mut context: Option<Vec<Node<'a>>> = None;
+        for step in &self.steps {
+            let mut candidates = Vec::new();
+            match context.take() {
+                None => {
+                    candidates.push(root.clone());
+                    if step.descendant {
+                        root.descendants(&mut candidates);
+                    }
+                }
+                Some(nodes) => {
+                    for node in nodes {
+                        if step.descendant {
+                            node.descendants(&mut candidates);
+                        } else {
+                            candidates.extend(node.children());
+                        }
+                    }
+                }
+            }
+            let mut selected: Vec<Node<'a>> = Vec::new();
+            for node in candidates.into_iter().filter(|node| step.matches(node)) {
+                if !selected.iter().any(|other| other.same(&node)) {
+                    selected.push(node);
+                }
+            }
+            context = Some(selected);
+        }
+        context.unwrap_or_default()
+    }
+}
+
+/// 按路径选择语句中的节点，例如 `select(&stmt, "Select/Where//FunctionCall[name='NOW']")`
+pub fn select<'a>(stmt: &'a SQLStatement, path: &str) -> Result<Vec<Node<'a>>, PathError> {
+    Ok(Path::parse(path)?.select(stmt))
+}
+
+struct PathParser<'p> {
+    path: &'p str,
+    pos: usize,
+}
+
+impl PathParser<'_> {
+    fn error(&self, message: &str) -> PathError {
+        PathError { message: message.to_string(), position: self.pos }
+    }
+
+    fn rest(&self) -> &str {
+        &self.path[self.pos..]
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        if self.rest().starts_with(text) {
+            self.pos += text.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse(mut self) -> Result<Path, PathError> {
+        let mut steps = Vec::new();
+        loop {
+            let descendant = if self.eat("//") {
+                true
+            } else {
+                // 第一步前面的 / 可以省略
+                if !self.eat("/") && !steps.is_empty() {
+                    return Err(self.error("expected '/' or '//'"));
+                }
+                false
+            };
+            let name = if self.eat("*") { None } else { Some(self.word("node name")?) };
+            let mut predicates = Vec::new();
+            while self.eat("[") {
+                predicates.push(self.predicate()?);
+            }
+            steps.push(Step { descendant, name, predicates });
+            if self.rest().is_empty() {
+                return Ok(Path { steps });
+            }
+        }
+    }
+
+    fn word(&mut self, what: &str) -> Result<String, PathError> {
+        let len = self.rest().find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(self.rest().len());
+        if len == 0 {
+            return Err(self.error(&format!("expected {}", what)));
+        }
+        let word = self.rest()[..len].to_string();
+        self.pos += len;
+        Ok(word)
+    }
+
+    fn predicate(&mut self) -> Result<Predicate, PathError> {
+        let attr = self.word("attribute name")?;
+        let negated = if self.eat("!=") {
+            true
+        } else if self.eat("=") {
+            false
+        } else if self.eat("]") {
+            return Ok(Predicate { attr, test: None });
+        } else {
+            return Err(self.error("expected '=', '!=' or ']'"));
+        };
+        let value = self.quoted()?;
+        if !self.eat("]") {
+            return Err(self.error("expected ']'"));
+        }
+        Ok(Predicate { attr, test: Some((negated, value)) })
+    }
+
+    fn quoted(&mut self) -> Result<String, PathError> {
+        let quote = match self.rest().chars().next() {
+            Some(quote @ ('\'' | '"')) => quote,
+            _ => return Err(self.error("expected quoted value")),
+        };
+        let start = self.pos;
+        self.pos += 1;
+        let mut value = String::new();
+        let mut chars = self.rest().char_indices().peekable();
+        while let Some((i, ch)) = chars.next() {
+            if ch != quote {
+                value.push(ch);
+                continue;
+            }
+            // 两个连续的引号表示一个引号
+            if chars.next_if(|(_, next)| *next == quote).is_some() {
+                value.push(quote);
+                continue;
+            }
+            self.pos += i + 1;
+            return Ok(value);
+        }
+        self.pos = start;
+        Err(self.error("unterminated quoted value"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::{Parser, StatementParser};
+
+    fn parse(sql: &str) -> SQLStatement {
+        Parser::new_from_sql(sql).parse().unwrap()
+    }
+
+    fn names(nodes: &[Node]) -> Vec<String> {
+        nodes.iter().map(|node| node.attr("name").unwrap_or_else(|| node.name().to_string())).collect()
+    }
+
+    #[test]
+    fn test_select_nodes() {
+        let stmt = parse(
+            "SELECT id, now() AS t, COUNT(*) FROM users u LEFT JOIN orders o ON o.uid = u.id AND o.at < NOW()
+             WHERE u.created < NOW() AND u.id IN (SELECT uid FROM bans WHERE expires > NOW()) GROUP BY id",
+        );
+        let nodes = select(&stmt, "Select/Where//FunctionCall[name='NOW']").unwrap();
+        assert_eq!(nodes.len(), 2);
+        assert!(matches!(nodes[0].as_expr(), Some(Expr::FunctionCall { args, .. }) if args.is_empty()));
+        #[cfg(feature = "formatter")]
+        assert_eq!(nodes[0].as_expr().map(|expr| expr.to_string()), Some("NOW()".to_string()));
+        // 所有的 NOW()，包括查询列表、ON 条件和子查询中的
+        assert_eq!(select(&stmt, "//FunctionCall[name='now']").unwrap().len(), 4);
+        assert_eq!(names(&select(&stmt, "Select/Columns/*").unwrap()), ["id", "now", "COUNT"]);
+        assert_eq!(names(&select(&stmt, "//Table").unwrap()), ["users", "orders", "bans"]);
+        assert_eq!(names(&select(&stmt, "/Select/From/Join[kind='left']/Table").unwrap()), ["orders"]);
+        assert_eq!(names(&select(&stmt, "//Join/On//Identifier").unwrap()), ["o.uid", "u.id", "o.at"]);
+        assert_eq!(names(&select(&stmt, "//Subquery/Select/Where//Identifier").unwrap()), ["expires"]);
+        assert_eq!(names(&select(&stmt, "//BinaryOp[op!='=']/Identifier").unwrap()), ["o.at", "u.created", "expires"]);
+        assert_eq!(select(&stmt, "//Table[alias]").unwrap().len(), 2);
+        assert!(select(&stmt, "Update//*").unwrap().is_empty());
+        // 同一个节点只出现一次
+        assert_eq!(select(&stmt, "//Where//FunctionCall").unwrap().len(), 2);
+        let table = select(&stmt, "Select/From/Table").unwrap();
+        assert_eq!(table[0].as_table().map(|table| table.alias.clone()), Some(Some("u".to_string())));
+
+        let stmt = parse("UPDATE t SET a = 'x', b = b + 1 WHERE id = 1");
+        assert_eq!(select(&stmt, "Update/Set/SetItem[column=\"b\"]//Identifier").unwrap().len(), 1);
+        assert_eq!(names(&select(&stmt, "//Literal[type='string']").unwrap()), ["Literal"]);
+    }
+
+    #[test]
+    fn test_path_errors() {
+        let stmt = parse("SELECT 1");
+        assert_eq!(select(&stmt, "Select/").unwrap_err(), PathError { message: "expected node name".to_string(), position: 7 });
+        assert_eq!(select(&stmt, "Select[name=x]").unwrap_err().position, 12);
+        assert_eq!(select(&stmt, "Select[name='x").unwrap_err().message, "unterminated quoted value");
+        assert_eq!(select(&stmt, "Select Where").unwrap_err().message, "expected '/' or '//'");
+        assert!(Path::parse("//Literal[value='it''s']").is_ok());
+    }
+}
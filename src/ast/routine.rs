@@ -0,0 +1,112 @@
+use super::common::DataType;
+use super::expr::Expr;
+use super::select::SelectStatement;
+use super::SQLStatement;
+use crate::token::Token;
+
+/// 存储过程/函数体中的语句
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RoutineStatement {
+    /// [label:] BEGIN ... END [label]
+    Compound {
+        label: Option<String>,
+        statements: Vec<RoutineStatement>,
+    },
+    /// DECLARE var [, var] ... type [DEFAULT expr]
+    DeclareVariable {
+        names: Vec<String>,
+        data_type: DataType,
+        default: Option<Expr>,
+    },
+    /// DECLARE cursor_name CURSOR FOR select_statement
+    DeclareCursor {
+        name: String,
+        query: Box<SelectStatement>,
+    },
+    /// DECLARE {CONTINUE | EXIT | UNDO} HANDLER FOR condition [, condition] ... statement
+    DeclareHandler {
+        action: HandlerAction,
+        conditions: Vec<HandlerCondition>,
+        statement: Box<RoutineStatement>,
+    },
+    /// IF ... THEN ... [ELSEIF ... THEN ...] [ELSE ...] END IF
+    If {
+        /// 按顺序排列的 IF/ELSEIF 分支
+        branches: Vec<(Expr, Vec<RoutineStatement>)>,
+        else_branch: Option<Vec<RoutineStatement>>,
+    },
+    /// [label:] WHILE condition DO ... END WHILE [label]
+    While {
+        label: Option<String>,
+        condition: Expr,
+        body: Vec<RoutineStatement>,
+    },
+    /// [label:] LOOP ... END LOOP [label]
+    Loop {
+        label: Option<String>,
+        body: Vec<RoutineStatement>,
+    },
+    /// [label:] REPEAT ... UNTIL condition END REPEAT [label]
+    Repeat {
+        label: Option<String>,
+        body: Vec<RoutineStatement>,
+        condition: Expr,
+    },
+    /// LEAVE label
+    Leave(String),
+    /// ITERATE label
+    Iterate(String),
+    /// OPEN cursor_name
+    Open(String),
+    /// FETCH [[NEXT] FROM] cursor_name INTO var [, var] ...
+    Fetch {
+        cursor: String,
+        into: Vec<String>,
+    },
+    /// CLOSE cursor_name
+    Close(String),
+    /// SIGNAL condition [SET item = value [, item = value] ...]
+    Signal {
+        condition: SignalCondition,
+        set: Vec<(String, Expr)>,
+    },
+    /// RETURN expr
+    Return(Expr),
+    /// SET var = expr [, var = expr] ...
+    Set(Vec<(String, Expr)>),
+    /// 已支持解析的普通SQL语句
+    Sql(Box<SQLStatement>),
+    /// 暂不支持解析的语句，保留原始token（不包含结尾的分号）
+    Raw(Vec<Token>),
+}
+
+/// 异常处理器的动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HandlerAction {
+    Continue,
+    Exit,
+    Undo,
+}
+
+/// 异常处理器捕获的条件
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HandlerCondition {
+    /// MySQL 错误码，例如 1062
+    ErrorCode(u32),
+    /// SQLSTATE [VALUE] 'sqlstate_value'
+    SqlState(String),
+    /// 命名的条件
+    Name(String),
+    SqlWarning,
+    NotFound,
+    SqlException,
+}
+
+/// SIGNAL 抛出的条件
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SignalCondition {
+    /// SQLSTATE [VALUE] 'sqlstate_value'
+    SqlState(String),
+    /// 命名的条件
+    Name(String),
+}
@@ -1,35 +1,298 @@
-use super::expr::{Expr,OrderByExpr,LimitClause};
-use super::common::TableReference;
+use super::expr::{BinaryOperator, Expr, LimitClause, LogicalOperator, OrderByExpr, SortKey, Value, WindowSpec};
+use super::common::{Ident, ObjectName, Quantity, QuantityUnit, TableReference};
 
 /// SELECT语句结构
-#[derive(Debug, Clone,PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SelectStatement {
+    /// WITH 子句定义的公用表表达式
+    pub with: Option<With>,
     /// 选择的列
     pub columns: Vec<SelectColumn>,
     pub distinct: bool, // false表示ALL，true表示DISTINCT
-    /// FROM子句中的表
-    pub from: TableReference,
+    /// PostgreSQL 的 DISTINCT ON (expr, ...)，此时 distinct 为 true
+    pub distinct_on: Option<Vec<Expr>>,
+    /// DISTINCT 之后的 MySQL 修饰符
+    pub modifiers: SelectModifiers,
+    /// SQL Server 的 TOP n [PERCENT] [WITH TIES]
+    pub top: Option<Top>,
+    /// FROM子句中的表，SELECT 1 之类的语句没有FROM子句
+    pub from: Option<TableReference>,
+    /// FROM 中第一个表之后依次连接的表，包括逗号分隔的表
+    pub joins: Vec<Join>,
     /// WHERE子句
     pub where_clause: Option<Expr>,
     /// GROUP BY子句
-    pub group_by: Option<Vec<Expr>>,
+    pub group_by: Option<Vec<SortKey>>,
+    /// GROUP BY ... WITH ROLLUP，额外输出各级小计和总计行
+    pub with_rollup: bool,
     /// HAVING子句
     pub having: Option<Expr>,
+    /// QUALIFY子句，按窗口函数的结果过滤，只有部分方言支持
+    pub qualify: Option<Expr>,
+    /// WINDOW子句定义的命名窗口，按出现顺序保存
+    pub windows: Vec<(String, WindowSpec)>,
+    /// UNION、EXCEPT、INTERSECT 依次连接的查询，之后的 ORDER BY、LIMIT 作用于整个组合的结果
+    pub set_operations: Vec<SetOperation>,
     /// ORDER BY子句
     pub order_by: Option<Vec<OrderByExpr>>,
     /// LIMIT子句
     pub limit: Option<LimitClause>,
+    /// INTO子句，可以出现在FROM之前或LIMIT之后
+    pub into: Option<IntoClause>,
+    /// FOR UPDATE、FOR SHARE、LOCK IN SHARE MODE 加锁子句
+    pub lock: Option<LockClause>,
 }
 
+impl SelectStatement {
+    /// 按序号（从1开始）获取select列表中的列，用于解析 ORDER BY 1、GROUP BY 2 之类的引用
+    ///
+    /// 序号越界，或者序号之前出现了 * 而无法确定位置时返回None
+    pub fn resolve_ordinal(&self, ordinal: u32) -> Option<&SelectColumn> {
+        let index = (ordinal as usize).checked_sub(1)?;
+        let columns = self.columns.get(..=index)?;
+        if columns.iter().any(|column| matches!(column, SelectColumn::Wildcard | SelectColumn::QualifiedWildcard(_))) {
+            return None;
+        }
+        columns.last()
+    }
+
+    /// 按名称查找 WINDOW 子句中的窗口定义，忽略大小写
+    pub fn named_window(&self, name: &str) -> Option<&WindowSpec> {
+        self.windows.iter().find(|(window, _)| window.eq_ignore_ascii_case(name)).map(|(_, spec)| spec)
+    }
+
+    /// 展开窗口定义引用的命名窗口，得到完整的 PARTITION BY、ORDER BY 和框架
+    ///
+    /// 引用的窗口不存在时返回None
+    pub fn resolve_window(&self, spec: &WindowSpec) -> Option<WindowSpec> {
+        resolve_window(&self.windows, spec)
+    }
+
+    /// 结果的最大行数，统一 LIMIT 和按行数的 TOP，不能静态确定时返回None
+    pub fn row_limit(&self) -> Option<u64> {
+        if let Some(limit) = &self.limit {
+            return Some(limit.limit);
+        }
+        match self.top.as_ref()?.quantity {
+            Quantity { value: Expr::Literal(Value::Integer(rows)), unit: QuantityUnit::Rows } => u64::try_from(rows).ok(),
+            _ => None,
+        }
+    }
+}
+
+// 命名窗口只能引用在它之前定义的窗口，因此不会出现循环引用
+fn resolve_window(windows: &[(String, WindowSpec)], spec: &WindowSpec) -> Option<WindowSpec> {
+    let Some(name) = &spec.name else {
+        return Some(spec.clone());
+    };
+    let index = windows.iter().position(|(window, _)| window.eq_ignore_ascii_case(name))?;
+    let base = resolve_window(&windows[..index], &windows[index].1)?;
+    Some(WindowSpec {
+        name: None,
+        partition_by: base.partition_by,
+        order_by: if spec.order_by.is_empty() { base.order_by } else { spec.order_by.clone() },
+        frame: spec.frame.clone().or(base.frame),
+    })
+}
+
+/// WITH [RECURSIVE] 子句
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct With {
+    pub recursive: bool,
+    pub ctes: Vec<Cte>,
+}
+
+/// 公用表表达式，例如 revenue (supplier_no, total) AS (SELECT ...)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cte {
+    pub name: String,
+    /// 名称后面括号中的列名，没有时为空
+    pub columns: Vec<String>,
+    pub query: SelectStatement,
+}
+
+/// 集合运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SetOperator {
+    Union,
+    Except,
+    Intersect,
+}
+
+impl SetOperator {
+    /// SQL中的运算符名称，例如 UNION
+    pub fn name(&self) -> &'static str {
+        match self {
+            SetOperator::Union => "UNION",
+            SetOperator::Except => "EXCEPT",
+            SetOperator::Intersect => "INTERSECT",
+        }
+    }
+}
+
+/// 集合运算连接的一个查询，例如 UNION ALL SELECT ...
+///
+/// 不带括号的查询没有 ORDER BY 和 LIMIT，括号中的查询可以有自己的 ORDER BY、LIMIT
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SetOperation {
+    pub op: SetOperator,
+    /// ALL 保留重复的行，DISTINCT 或者省略时去重
+    pub all: bool,
+    pub select: SelectStatement,
+}
 
 /// 表示选择的列
-#[derive(Debug, Clone,PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SelectColumn {
     /// 所有列 (*)
     Wildcard,
+    /// 某个表的所有列，例如 t.*、mydb.users.*，名称为表名或别名
+    QualifiedWildcard(ObjectName),
     /// 指定列，可能包含别名
     Column {
-        name: String,
+        name: ObjectName,
         alias: Option<String>,
     },
+    /// 表达式列，例如 1、NOW()、@@version
+    Expression {
+        expr: Expr,
+        alias: Option<String>,
+    },
+}
+
+/// 连接，例如 LEFT JOIN orders o ON o.user_id = u.id
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Join {
+    pub kind: JoinKind,
+    pub table: TableReference,
+    /// ON 条件，LEFT/RIGHT JOIN 必须有 ON、USING 之一或者是 NATURAL JOIN，其它连接可以省略
+    pub on: Option<Expr>,
+    /// USING (a, b) 中的列，与 ON 互斥
+    pub using: Option<Vec<String>>,
+    /// NATURAL [LEFT | RIGHT] JOIN，按两边同名的列连接，没有 ON 和 USING
+    pub natural: bool,
+}
+
+impl Join {
+    /// USING 隐含的等值连接条件 left.a = right.a AND left.b = right.b
+    ///
+    /// left 为左侧表的名称或别名，右侧使用被连接的表的别名，没有别名时为表名。没有 USING 时返回None。
+    pub fn using_condition(&self, left: &ObjectName) -> Option<Expr> {
+        let right = match &self.table.alias {
+            Some(alias) => ObjectName::new([alias.as_str()]),
+            None => self.table.name.clone(),
+        };
+        let column = |table: &ObjectName, column: &str| {
+            let mut parts = table.0.clone();
+            parts.push(Ident::new(column));
+            Box::new(Expr::Identifier(ObjectName(parts)))
+        };
+        let mut expressions: Vec<Expr> = self
+            .using
+            .as_ref()?
+            .iter()
+            .map(|name| Expr::BinaryOp { left: column(left, name), op: BinaryOperator::Eq, right: column(&right, name) })
+            .collect();
+        Some(if expressions.len() == 1 {
+            expressions.pop().unwrap()
+        } else {
+            Expr::LogicalOp { op: LogicalOperator::And, expressions }
+        })
+    }
+}
+
+/// 连接方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JoinKind {
+    /// [INNER] JOIN
+    Inner,
+    /// CROSS JOIN，MySQL 中与 INNER JOIN 等价，但保留原来的写法
+    Cross,
+    /// LEFT [OUTER] JOIN
+    Left,
+    /// RIGHT [OUTER] JOIN
+    Right,
+    /// STRAIGHT_JOIN，强制优化器先读左边的表
+    Straight,
+    /// FROM a, b 中逗号分隔的表，相当于没有连接条件的 CROSS JOIN
+    Comma,
+}
+
+/// SELECT TOP 子句
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Top {
+    pub quantity: Quantity,
+    /// WITH TIES，保留与最后一行排序值相同的行
+    pub with_ties: bool,
+}
+
+/// SELECT 修饰符：[HIGH_PRIORITY] [STRAIGHT_JOIN] [SQL_SMALL_RESULT] [SQL_BIG_RESULT]
+/// [SQL_BUFFER_RESULT] [SQL_NO_CACHE] [SQL_CALC_FOUND_ROWS]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SelectModifiers {
+    pub high_priority: bool,
+    pub straight_join: bool,
+    pub sql_small_result: bool,
+    pub sql_big_result: bool,
+    pub sql_buffer_result: bool,
+    pub sql_no_cache: bool,
+    pub sql_calc_found_rows: bool,
+}
+
+/// 加锁读取的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LockMode {
+    /// FOR UPDATE
+    Update,
+    /// FOR SHARE 或 LOCK IN SHARE MODE
+    Share,
+}
+
+/// 遇到已被锁定的行时的处理方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LockWait {
+    #[default]
+    Wait,
+    Nowait,
+    SkipLocked,
+}
+
+/// 加锁子句
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LockClause {
+    pub mode: LockMode,
+    pub wait: LockWait,
+}
+
+/// SELECT ... INTO 的目标
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IntoClause {
+    /// INTO OUTFILE 'path' [CHARACTER SET charset] [FIELDS ...] [LINES ...]
+    Outfile {
+        path: String,
+        charset: Option<String>,
+        fields: Option<FieldsOptions>,
+        lines: Option<LinesOptions>,
+    },
+    /// INTO DUMPFILE 'path'
+    Dumpfile(String),
+    /// INTO var [, var] ...，用户变量保留 @ 前缀
+    Variables(Vec<String>),
+}
+
+/// FIELDS/COLUMNS 导出选项
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FieldsOptions {
+    pub terminated_by: Option<String>,
+    pub enclosed_by: Option<String>,
+    /// OPTIONALLY ENCLOSED BY
+    pub optionally_enclosed: bool,
+    pub escaped_by: Option<String>,
+}
+
+/// LINES 导出选项
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct LinesOptions {
+    pub starting_by: Option<String>,
+    pub terminated_by: Option<String>,
 }
@@ -0,0 +1,594 @@
+use super::common::{Ident, IndexHint, ObjectName, Quantity, TableFunction, TableReference, TableSample};
+use super::delete::DeleteStatement;
+use super::expr::{Expr, LogicalOperator, OrderByExpr, SortKey, UnaryOperator, Value, WindowSpec};
+use super::insert::{InsertStatement, OnDuplicateClause, RowAlias};
+use super::select::{Cte, Join, SelectColumn, SelectStatement, SetOperation, Top, With};
+use super::update::UpdateStatement;
+use super::SQLStatement;
+use std::hash::{Hash, Hasher};
+
+/// 语义比较接口
+///
+/// 与 `==` 不同，语义比较忽略以下差异：
+/// - 别名，以及单表语句中列的表名/表别名限定符（`u.id`、`users.id` 与 `id` 相同）
+/// - 标识符、函数名的字母大小写和反引号
+/// - 括号造成的 AND/OR 嵌套（`(a AND b) AND c` 与 `a AND (b AND c)` 相同）
+pub trait SemanticEq {
+    fn semantic_eq(&self, other: &Self) -> bool;
+}
+
+// 规范形式，语义相同的节点转换后结构完全相等
+trait Canonical {
+    fn canonical(&self) -> Self;
+}
+
+macro_rules! impl_semantic_eq {
+    ($($ty:ty),*) => {
+        $(
+            impl SemanticEq for $ty {
+                fn semantic_eq(&self, other: &Self) -> bool {
+                    self.canonical() == other.canonical()
+                }
+            }
+        )*
+    };
+}
+
+impl_semantic_eq!(
+    Ident,
+    ObjectName,
+    Expr,
+    TableReference,
+    SelectStatement,
+    InsertStatement,
+    DeleteStatement,
+    UpdateStatement,
+    SQLStatement
+);
+
+impl Canonical for Ident {
+    fn canonical(&self) -> Self {
+        Ident::new(self.value.to_lowercase())
+    }
+}
+
+impl Canonical for ObjectName {
+    fn canonical(&self) -> Self {
+        ObjectName(self.0.iter().map(Canonical::canonical).collect())
+    }
+}
+
+impl Canonical for Expr {
+    fn canonical(&self) -> Self {
+        canonical_expr(self, None)
+    }
+}
+
+impl Canonical for TableReference {
+    fn canonical(&self) -> Self {
+        TableReference {
+            name: self.name.canonical(),
+            partitions: self
+                .partitions
+                .as_ref()
+                .map(|partitions| partitions.iter().map(|p| p.to_lowercase()).collect()),
+            alias: None,
+            index_hints: self
+                .index_hints
+                .iter()
+                .map(|hint| IndexHint {
+                    indexes: hint.indexes.iter().map(|index| index.to_lowercase()).collect(),
+                    ..hint.clone()
+                })
+                .collect(),
+            sample: self.sample.as_ref().map(|sample| TableSample {
+                size: Quantity { value: canonical_expr(&sample.size.value, None), unit: sample.size.unit },
+                seed: sample.seed.as_ref().map(|seed| canonical_expr(seed, None)),
+                ..*sample
+            }),
+            subquery: self.subquery.as_ref().map(|subquery| Box::new(subquery.canonical())),
+            function: self.function.as_ref().map(|function| Box::new(function.canonical())),
+            lateral: self.lateral,
+        }
+    }
+}
+
+impl Canonical for TableFunction {
+    fn canonical(&self) -> Self {
+        match self {
+            TableFunction::JsonTable { expr, path, columns } => {
+                TableFunction::JsonTable { expr: canonical_expr(expr, None), path: path.clone(), columns: columns.clone() }
+            }
+            TableFunction::Call { name, args, with_ordinality, column_aliases } => TableFunction::Call {
+                name: name.clone(),
+                args: args.iter().map(|arg| canonical_expr(arg, None)).collect(),
+                with_ordinality: *with_ordinality,
+                column_aliases: column_aliases.clone(),
+            },
+        }
+    }
+}
+
+impl Canonical for SelectStatement {
+    fn canonical(&self) -> Self {
+        // 有连接时限定符用来区分不同的表，不能去掉
+        let table = self.from.as_ref().filter(|_| self.joins.is_empty());
+        let expr = |e: &Expr| canonical_expr(e, table);
+        SelectStatement {
+            with: self.with.as_ref().map(|with| With {
+                recursive: with.recursive,
+                ctes: with
+                    .ctes
+                    .iter()
+                    .map(|cte| Cte {
+                        name: cte.name.to_lowercase(),
+                        columns: cte.columns.iter().map(|c| c.to_lowercase()).collect(),
+                        query: cte.query.canonical(),
+                    })
+                    .collect(),
+            }),
+            columns: self
+                .columns
+                .iter()
+                .map(|column| match column {
+                    SelectColumn::Wildcard => SelectColumn::Wildcard,
+                    SelectColumn::QualifiedWildcard(name) => SelectColumn::QualifiedWildcard(name.clone()),
+                    SelectColumn::Column { name, .. } => SelectColumn::Column {
+                        name: resolve_name(name, table),
+                        alias: None,
+                    },
+                    SelectColumn::Expression { expr: e, .. } => SelectColumn::Expression {
+                        expr: expr(e),
+                        alias: None,
+                    },
+                })
+                .collect(),
+            distinct: self.distinct,
+            distinct_on: self.distinct_on.as_ref().map(|exprs| exprs.iter().map(expr).collect()),
+            modifiers: self.modifiers,
+            top: self.top.as_ref().map(|top| Top {
+                quantity: Quantity { value: expr(&top.quantity.value), unit: top.quantity.unit },
+                with_ties: top.with_ties,
+            }),
+            from: self.from.as_ref().map(Canonical::canonical),
+            joins: self
+                .joins
+                .iter()
+                .map(|join| Join {
+                    kind: join.kind,
+                    table: join.table.canonical(),
+                    on: join.on.as_ref().map(expr),
+                    using: join.using.as_ref().map(|columns| columns.iter().map(|c| c.to_lowercase()).collect()),
+                    natural: join.natural,
+                })
+                .collect(),
+            where_clause: self.where_clause.as_ref().map(expr),
+            group_by: self
+                .group_by
+                .as_ref()
+                .map(|keys| keys.iter().map(|key| canonical_sort_key(key, table)).collect()),
+            with_rollup: self.with_rollup,
+            having: self.having.as_ref().map(expr),
+            qualify: self.qualify.as_ref().map(expr),
+            windows: self
+                .windows
+                .iter()
+                .map(|(name, spec)| (name.to_lowercase(), canonical_window(spec, table)))
+                .collect(),
+            set_operations: self
+                .set_operations
+                .iter()
+                .map(|operation| SetOperation { select: operation.select.canonical(), ..*operation })
+                .collect(),
+            order_by: self.order_by.as_ref().map(|order_by| canonical_order_by(order_by, table)),
+            limit: self.limit.clone(),
+            into: self.into.clone(),
+            lock: self.lock,
+        }
+    }
+}
+
+impl Canonical for InsertStatement {
+    fn canonical(&self) -> Self {
+        let table = Some(&self.table);
+        let expr = |e: &Expr| canonical_expr(e, table);
+        let assignments = |list: &Vec<(ObjectName, Expr)>| -> Vec<(ObjectName, Expr)> {
+            list.iter().map(|(column, e)| (resolve_name(column, table), expr(e))).collect()
+        };
+        InsertStatement {
+            modifiers: self.modifiers,
+            table: self.table.canonical(),
+            columns: self
+                .columns
+                .as_ref()
+                .map(|columns| columns.iter().map(|c| c.to_lowercase()).collect()),
+            values: self
+                .values
+                .as_ref()
+                .map(|rows| rows.iter().map(|row| row.iter().map(expr).collect()).collect()),
+            select_clause: self.select_clause.as_ref().map(Canonical::canonical),
+            set_clause: self.set_clause.as_ref().map(assignments),
+            row_alias: self.row_alias.as_ref().map(|alias| RowAlias {
+                name: alias.name.to_lowercase(),
+                columns: alias.columns.as_ref().map(|columns| columns.iter().map(|c| c.to_lowercase()).collect()),
+            }),
+            on_duplicate: self.on_duplicate.as_ref().map(|clause| OnDuplicateClause {
+                updates: assignments(&clause.updates),
+            }),
+            is_default_values: self.is_default_values,
+            is_return_count: self.is_return_count,
+        }
+    }
+}
+
+impl Canonical for DeleteStatement {
+    fn canonical(&self) -> Self {
+        let table = Some(&self.table);
+        DeleteStatement {
+            modifiers: self.modifiers,
+            table: self.table.canonical(),
+            where_clause: self.where_clause.as_ref().map(|e| canonical_expr(e, table)),
+            order_by: self.order_by.as_ref().map(|order_by| canonical_order_by(order_by, table)),
+            limit: self.limit.clone(),
+            is_return_count: self.is_return_count,
+        }
+    }
+}
+
+impl Canonical for UpdateStatement {
+    fn canonical(&self) -> Self {
+        let table = Some(&self.table);
+        UpdateStatement {
+            modifiers: self.modifiers,
+            table: self.table.canonical(),
+            assignments: self
+                .assignments
+                .iter()
+                .map(|(column, e)| (resolve_name(column, table), canonical_expr(e, table)))
+                .collect(),
+            where_clause: self.where_clause.as_ref().map(|e| canonical_expr(e, table)),
+            order_by: self.order_by.as_ref().map(|order_by| canonical_order_by(order_by, table)),
+            limit: self.limit.clone(),
+            is_return_count: self.is_return_count,
+        }
+    }
+}
+
+impl Canonical for SQLStatement {
+    fn canonical(&self) -> Self {
+        match self {
+            SQLStatement::Select(select) => SQLStatement::Select(Box::new(select.canonical())),
+            SQLStatement::Insert(insert) => SQLStatement::Insert(Box::new(insert.canonical())),
+            SQLStatement::Update(update) => SQLStatement::Update(update.canonical()),
+            SQLStatement::Delete(delete) => SQLStatement::Delete(delete.canonical()),
+            // 其它语句按结构比较
+            other => other.clone(),
+        }
+    }
+}
+
+// 单表语句中，通过表名或表别名限定的列等价于不带限定符的列，去掉限定符后转换为规范形式
+fn resolve_name(name: &ObjectName, table: Option<&TableReference>) -> ObjectName {
+    if let (Some(table), [qualifier @ .., column]) = (table, name.0.as_slice()) {
+        let same = |a: &[Ident], b: &[Ident]| {
+            a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.value.eq_ignore_ascii_case(&y.value))
+        };
+        let by_alias = matches!((&table.alias, qualifier), (Some(alias), [q]) if q.value.eq_ignore_ascii_case(alias));
+        // 限定符可以是完整表名，也可以只是表名的最后一段
+        let by_name = !qualifier.is_empty()
+            && (same(qualifier, &table.name.0) || same(qualifier, std::slice::from_ref(table.name.name())));
+        if by_alias || by_name {
+            return ObjectName(vec![column.canonical()]);
+        }
+    }
+    name.canonical()
+}
+
+fn canonical_order_by(order_by: &[OrderByExpr], table: Option<&TableReference>) -> Vec<OrderByExpr> {
+    order_by
+        .iter()
+        .map(|item| OrderByExpr {
+            expr: canonical_sort_key(&item.expr, table),
+            asc: item.asc,
+        })
+        .collect()
+}
+
+// 窗口名与 MySQL 一致，不区分大小写
+fn canonical_window(spec: &WindowSpec, table: Option<&TableReference>) -> WindowSpec {
+    WindowSpec {
+        name: spec.name.as_ref().map(|name| name.to_lowercase()),
+        partition_by: spec.partition_by.iter().map(|e| canonical_expr(e, table)).collect(),
+        order_by: canonical_order_by(&spec.order_by, table),
+        frame: spec.frame.clone(),
+    }
+}
+
+fn canonical_sort_key(key: &SortKey, table: Option<&TableReference>) -> SortKey {
+    match key {
+        SortKey::Expr(expr) => SortKey::Expr(canonical_expr(expr, table)),
+        SortKey::Ordinal(n) => SortKey::Ordinal(*n),
+    }
+}
+
+// 转换表达式的规范形式，table 用于解析表别名
+fn canonical_expr(expr: &Expr, table: Option<&TableReference>) -> Expr {
+    expr.fold(|node, children| {
+        let mut expr = node.with_children(children);
+        match &mut expr {
+            Expr::Identifier(name) => *name = resolve_name(name, table),
+            Expr::FunctionCall { name, .. } => *name = name.to_uppercase(),
+            // 默认的分隔符是 ','
+            Expr::GroupConcat(group_concat) if group_concat.separator.as_deref() == Some(",") => {
+                group_concat.separator = None
+            }
+            // 子节点已经是规范形式，合并同类的 AND/OR 嵌套只需要展开一层
+            Expr::LogicalOp { op, expressions } if *op != LogicalOperator::Not => {
+                *expressions = std::mem::take(expressions)
+                    .into_iter()
+                    .flat_map(|e| match e {
+                        Expr::LogicalOp { op: inner_op, expressions: inner } if inner_op == *op => inner,
+                        e => vec![e],
+                    })
+                    .collect();
+            }
+            // 与解析时一致，数字字面量的负号并入字面量
+            Expr::UnaryOp { op: UnaryOperator::Minus, expr: inner } => {
+                if let Expr::Literal(value) = &**inner {
+                    if let Some(negated) = value.negate() {
+                        return Expr::Literal(negated);
+                    }
+                }
+            }
+            Expr::Window { over, .. } => over.name = over.name.as_ref().map(|name| name.to_lowercase()),
+            Expr::Introducer { charset, .. } => *charset = charset.to_lowercase(),
+            Expr::Collate { collation, .. } => *collation = collation.to_lowercase(),
+            Expr::Cast { data_type, .. } => data_type.name = data_type.name.to_uppercase(),
+            Expr::Match { columns, .. } => {
+                for column in columns {
+                    *column = resolve_name(column, table);
+                }
+            }
+            // 子查询中的列按子查询自己的表解析
+            Expr::Subquery(select) | Expr::Exists(select) => **select = select.canonical(),
+            _ => {}
+        }
+        expr
+    })
+}
+
+/// 结构哈希的选项
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StableHashOptions {
+    /// 是否忽略字符串和数字字面量的值，只保留字面量的类型，默认开启
+    /// NULL、TRUE/FALSE、DEFAULT 以及 LIMIT、TOP 中的数字属于语句结构，始终参与哈希
+    pub ignore_literals: bool,
+}
+
+impl Default for StableHashOptions {
+    fn default() -> Self {
+        StableHashOptions { ignore_literals: true }
+    }
+}
+
+impl SQLStatement {
+    /// 语句的结构哈希，用于执行计划缓存的键和语句去重
+    ///
+    /// 按语义比较的规范形式计算，[`SemanticEq`] 相同的语句哈希相同，空白、注释和大小写等书写差异不影响结果；
+    /// 默认忽略字面量的值，`id = 1` 与 `id = 2` 哈希相同。结果与进程、平台和编译器版本无关，可以持久化。
+    pub fn stable_hash(&self) -> u64 {
+        self.stable_hash_with(StableHashOptions::default())
+    }
+
+    /// 按指定选项计算结构哈希
+    pub fn stable_hash_with(&self, options: StableHashOptions) -> u64 {
+        let mut stmt = self.canonical();
+        if options.ignore_literals {
+            erase_statement_literals(&mut stmt);
+        }
+        let mut hasher = StableHasher::default();
+        stmt.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+// 64 位 FNV-1a，整数按小端序写入，usize/isize 按 64 位写入，结果与平台无关
+struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        StableHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_u64(i as i64 as u64);
+    }
+}
+
+fn erase_statement_literals(stmt: &mut SQLStatement) {
+    match stmt {
+        SQLStatement::Select(select) => erase_select_literals(select),
+        SQLStatement::Insert(insert) => {
+            insert.values.iter_mut().flatten().flatten().for_each(erase_literals);
+            insert.set_clause.iter_mut().flatten().for_each(|(_, value)| erase_literals(value));
+            if let Some(select) = &mut insert.select_clause {
+                erase_select_literals(select);
+            }
+            for (_, value) in insert.on_duplicate.iter_mut().flat_map(|clause| &mut clause.updates) {
+                erase_literals(value);
+            }
+        }
+        SQLStatement::Update(update) => {
+            update.assignments.iter_mut().for_each(|(_, value)| erase_literals(value));
+            update.where_clause.iter_mut().for_each(erase_literals);
+            erase_order_by_literals(&mut update.order_by);
+        }
+        SQLStatement::Delete(delete) => {
+            delete.where_clause.iter_mut().for_each(erase_literals);
+            erase_order_by_literals(&mut delete.order_by);
+        }
+        // DDL 和 SHOW 中的字面量属于语句结构
+        _ => {}
+    }
+}
+
+fn erase_select_literals(select: &mut SelectStatement) {
+    for column in &mut select.columns {
+        if let SelectColumn::Expression { expr, .. } = column {
+            erase_literals(expr);
+        }
+    }
+    select.distinct_on.iter_mut().flatten().for_each(erase_literals);
+    for table in select.from.iter_mut().chain(select.joins.iter_mut().map(|join| &mut join.table)) {
+        if let Some(subquery) = &mut table.subquery {
+            erase_select_literals(subquery);
+        }
+        match table.function.as_deref_mut() {
+            Some(TableFunction::JsonTable { expr, .. }) => erase_literals(expr),
+            Some(TableFunction::Call { args, .. }) => args.iter_mut().for_each(erase_literals),
+            None => {}
+        }
+    }
+    select.joins.iter_mut().filter_map(|join| join.on.as_mut()).for_each(erase_literals);
+    select.where_clause.iter_mut().chain(select.having.iter_mut()).chain(select.qualify.iter_mut()).for_each(erase_literals);
+    select.windows.iter_mut().flat_map(|(_, spec)| spec.exprs_mut()).for_each(erase_literals);
+    for key in select.group_by.iter_mut().flatten() {
+        if let SortKey::Expr(expr) = key {
+            erase_literals(expr);
+        }
+    }
+    for cte in select.with.iter_mut().flat_map(|with| &mut with.ctes) {
+        erase_select_literals(&mut cte.query);
+    }
+    for operation in &mut select.set_operations {
+        erase_select_literals(&mut operation.select);
+    }
+    erase_order_by_literals(&mut select.order_by);
+}
+
+fn erase_order_by_literals(order_by: &mut Option<Vec<OrderByExpr>>) {
+    for item in order_by.iter_mut().flatten() {
+        if let SortKey::Expr(expr) = &mut item.expr {
+            erase_literals(expr);
+        }
+    }
+}
+
+// 把字符串和数字字面量替换为同类型的固定值
+fn erase_literals(expr: &mut Expr) {
+    expr.walk_mut(|node| match node {
+        Expr::Literal(value) => match value {
+            Value::String(s) | Value::Decimal(s) => s.clear(),
+            Value::Integer(i) => *i = 0,
+            Value::Float(f) => *f = 0.0,
+            Value::Boolean(_) | Value::Null | Value::DEFAULT => {}
+        },
+        Expr::Subquery(select) | Expr::Exists(select) => erase_select_literals(select),
+        _ => {}
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::select::SelectStatementParser;
+    use crate::parser::Parser;
+    use std::collections::HashSet;
+
+    fn parse_select(sql: &str) -> SelectStatement {
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_select_statement();
+        assert!(result.is_ok(), "解析失败: {} {:?}", sql, result.err());
+        result.unwrap()
+    }
+
+    #[test]
+    fn test_semantic_eq() {
+        let a = parse_select("SELECT u.id AS uid, LOWER(u.name) FROM users AS u WHERE (u.age > 18 AND u.status = 1) AND u.id < 10");
+        let b = parse_select("select `ID`, lower(NAME) from USERS where users.age > 18 and (users.STATUS = 1 and users.id < 10)");
+        assert_ne!(a, b);
+        assert!(a.semantic_eq(&b));
+
+        // 字面量和运算符不同则语义不同
+        let c = parse_select("SELECT id FROM users WHERE age > 19");
+        let d = parse_select("SELECT id FROM users WHERE age > 18");
+        assert!(!c.semantic_eq(&d));
+        // AND 与 OR 不会互相合并
+        let e = parse_select("SELECT id FROM users WHERE (a = 1 OR b = 1) AND c = 1");
+        let f = parse_select("SELECT id FROM users WHERE a = 1 OR (b = 1 AND c = 1)");
+        assert!(!e.semantic_eq(&f));
+    }
+
+    #[test]
+    fn test_stable_hash() {
+        use crate::parser::StatementParser;
+        let hash = |sql: &str| Parser::new_from_sql(sql).parse().unwrap().stable_hash();
+        let a = hash("SELECT u.id FROM users u WHERE u.age > 18 AND u.name IN ('a', 'b') LIMIT 10");
+        // 书写差异和字面量的值不影响结果
+        assert_eq!(a, hash("select ID from USERS where age > 30 -- x\n and `name` in ('c', 'd') limit 10"));
+        assert_eq!(a, hash("SELECT id FROM users WHERE age > 18 AND name IN ('a', 'b') LIMIT 10;"));
+        // 结构、字面量的类型和 LIMIT 不同则哈希不同
+        assert_ne!(a, hash("SELECT id FROM users WHERE age >= 18 AND name IN ('a', 'b') LIMIT 10"));
+        assert_ne!(a, hash("SELECT id FROM users WHERE age > 18 AND name IN ('a', 'b', 'c') LIMIT 10"));
+        assert_ne!(a, hash("SELECT id FROM users WHERE age > '18' AND name IN ('a', 'b') LIMIT 10"));
+        assert_ne!(a, hash("SELECT id FROM users WHERE age > 18 AND name IN ('a', 'b') LIMIT 20"));
+        // 子查询和 INSERT 中的字面量同样被忽略
+        assert_eq!(
+            hash("INSERT INTO t (a) VALUES (1) ON DUPLICATE KEY UPDATE a = (SELECT MAX(x) FROM s WHERE y = 'p')"),
+            hash("INSERT INTO t (a) VALUES (2) ON DUPLICATE KEY UPDATE a = (SELECT MAX(x) FROM s WHERE y = 'q')")
+        );
+
+        // 保留字面量的值
+        let options = StableHashOptions { ignore_literals: false };
+        let exact = |sql: &str| Parser::new_from_sql(sql).parse().unwrap().stable_hash_with(options);
+        assert_eq!(exact("SELECT id FROM users WHERE age > 18"), exact("select `ID` from users where AGE > 18"));
+        assert_ne!(exact("SELECT id FROM users WHERE age > 18"), exact("SELECT id FROM users WHERE age > 30"));
+
+        // 结果是固定的，不随进程和平台变化
+        assert_eq!(hash("SELECT 1"), hash("SELECT 1"));
+        let mut hasher = StableHasher::default();
+        hasher.write(b"a");
+        assert_eq!(hasher.finish(), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_hash_dedup() {
+        let mut seen = HashSet::new();
+        assert!(seen.insert(parse_select("SELECT id FROM users WHERE price > 1.5")));
+        assert!(!seen.insert(parse_select("SELECT id  FROM users  WHERE price > 1.5")));
+        assert!(seen.insert(parse_select("SELECT id FROM users WHERE price > 2.5")));
+        assert_eq!(seen.len(), 2);
+    }
+}
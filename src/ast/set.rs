@@ -0,0 +1,18 @@
+use super::expr::Expr;
+use super::show::VariableScope;
+
+/// SET 语句，给系统变量或用户变量赋值
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SetStatement {
+    pub assignments: Vec<SetAssignment>,
+}
+
+/// SET 中的一个赋值，= 与 := 等价
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SetAssignment {
+    /// GLOBAL、SESSION 或 LOCAL 指定的作用域，@@global.name 中的作用域保留在变量名中
+    pub scope: Option<VariableScope>,
+    /// 变量名，用户变量以 @ 开头，系统变量可以以 @@ 开头
+    pub variable: String,
+    pub value: Expr,
+}
@@ -0,0 +1,59 @@
+use super::common::ObjectName;
+use super::expr::Expr;
+
+/// SHOW 语句
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ShowStatement {
+    pub target: ShowTarget,
+}
+
+/// SHOW 的查看对象
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ShowTarget {
+    /// SHOW CREATE TABLE name
+    CreateTable(ObjectName),
+    /// SHOW [GLOBAL | SESSION] VARIABLES [LIKE 'pattern' | WHERE expr]
+    Variables {
+        scope: Option<VariableScope>,
+        filter: Option<ShowFilter>,
+    },
+    /// SHOW [GLOBAL | SESSION] STATUS [LIKE 'pattern' | WHERE expr]
+    Status {
+        scope: Option<VariableScope>,
+        filter: Option<ShowFilter>,
+    },
+    /// SHOW [FULL] PROCESSLIST
+    ProcessList {
+        full: bool,
+    },
+    /// SHOW {INDEX | INDEXES | KEYS} {FROM | IN} table [{FROM | IN} db] [WHERE expr]
+    Index {
+        table: ObjectName,
+        database: Option<String>,
+        filter: Option<ShowFilter>,
+    },
+    /// SHOW [FULL] TABLES [{FROM | IN} db] [LIKE 'pattern' | WHERE expr]
+    Tables {
+        full: bool,
+        database: Option<String>,
+        filter: Option<ShowFilter>,
+    },
+    /// SHOW DATABASES [LIKE 'pattern' | WHERE expr]
+    Databases {
+        filter: Option<ShowFilter>,
+    },
+}
+
+/// 变量作用域，LOCAL 等同于 SESSION
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VariableScope {
+    Global,
+    Session,
+}
+
+/// SHOW 语句的过滤条件
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ShowFilter {
+    Like(String),
+    Where(Expr),
+}
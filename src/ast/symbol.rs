@@ -0,0 +1,177 @@
+use std::borrow::Borrow;
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// 不可变的共享字符串，clone 只增加引用计数
+///
+/// 由同一个 [`Interner`] 得到的相同字符串共享同一块内存。
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(Arc<str>);
+
+impl Symbol {
+    pub fn new(value: &str) -> Self {
+        Symbol(Arc::from(value))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// 两个 Symbol 是否共享同一块内存
+    pub fn ptr_eq(&self, other: &Symbol) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Deref for Symbol {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Symbol {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(value: &str) -> Self {
+        Symbol::new(value)
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(value: String) -> Self {
+        Symbol(Arc::from(value))
+    }
+}
+
+impl From<Symbol> for String {
+    fn from(value: Symbol) -> Self {
+        value.0.to_string()
+    }
+}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+impl PartialEq<String> for Symbol {
+    fn eq(&self, other: &String) -> bool {
+        &*self.0 == other.as_str()
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// 字符串驻留池，相同的字符串只分配一次
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    symbols: HashSet<Symbol>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 返回与 value 相同的 Symbol，已存在时直接共享
+    pub fn intern(&mut self, value: &str) -> Symbol {
+        if let Some(symbol) = self.symbols.get(value) {
+            return symbol.clone();
+        }
+        let symbol = Symbol::new(value);
+        self.symbols.insert(symbol.clone());
+        symbol
+    }
+
+    /// 已驻留的不同字符串数量
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ast::expr::Expr;
+    use crate::ast::select::SelectColumn;
+    use crate::parser::select::SelectStatementParser;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_interned_identifiers() {
+        let sql = "SELECT price, u.price FROM users AS u WHERE price > 1 AND users.price < 10";
+        let mut parser = Parser::new_from_sql(sql).with_interner(Interner::new());
+        let result = parser.parse_select_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let select = result.unwrap();
+
+        let SelectColumn::Column { name: first, .. } = &select.columns[0] else {
+            panic!("Expected column");
+        };
+        let SelectColumn::Column { name: second, .. } = &select.columns[1] else {
+            panic!("Expected column");
+        };
+        // 重复出现的标识符共享同一块内存
+        assert!(first.name().value.ptr_eq(&second.name().value));
+        let Some(Expr::LogicalOp { expressions, .. }) = &select.where_clause else {
+            panic!("Expected AND");
+        };
+        let Expr::BinaryOp { left, .. } = &expressions[1] else {
+            panic!("Expected comparison");
+        };
+        let Expr::Identifier(qualified) = left.as_ref() else {
+            panic!("Expected identifier");
+        };
+        assert!(qualified.0[0].value.ptr_eq(&select.from.as_ref().unwrap().name.name().value));
+        assert!(qualified.name().value.ptr_eq(&first.name().value));
+
+        // 驻留池可以取回并用于下一次解析
+        let interner = parser.take_interner().unwrap();
+        assert_eq!(interner.len(), 3);
+        let mut parser = Parser::new_from_sql("SELECT price FROM users").with_interner(interner);
+        let select = parser.parse_select_statement().unwrap();
+        assert_eq!(parser.interner().unwrap().len(), 3);
+        assert_eq!(select.from.unwrap().name.name().value, "users");
+
+        // 不使用驻留池时内容相同，但不共享内存
+        let a = Symbol::from("price");
+        let b = Symbol::from(String::from("price"));
+        assert_eq!(a, b);
+        assert!(!a.ptr_eq(&b));
+    }
+}
@@ -0,0 +1,21 @@
+use super::expr::{Expr,OrderByExpr,LimitClause};
+use super::common::{ObjectName, TableReference};
+
+/// update 语句结构
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UpdateStatement {
+    pub modifiers: UpdateModifiers,
+    pub table: TableReference,
+    pub assignments: Vec<(ObjectName, Expr)>, // 列名（可以带限定符，例如 t.col）和新值对
+    pub where_clause: Option<Expr>,
+    pub order_by: Option<Vec<OrderByExpr>>,
+    pub limit: Option<LimitClause>,
+    pub is_return_count:bool,
+}
+
+/// UPDATE 修饰符：[LOW_PRIORITY] [IGNORE]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct UpdateModifiers {
+    pub low_priority: bool,
+    pub ignore: bool,
+}
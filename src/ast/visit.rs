@@ -0,0 +1,344 @@
+//! 表达式树的非递归遍历
+//!
+//! 自动生成的过滤条件可能嵌套很深，即使解析成功，递归的访问函数也可能耗尽栈空间。
+//! 这里的遍历都使用显式的栈，栈深度只受堆内存限制。子查询是独立的语句，
+//! [`Expr::Subquery`] 和 [`Expr::Exists`] 被当作叶子节点，不会进入其中的表达式。
+
+use super::expr::{Expr, GroupConcat, OrderByExpr, SortKey, WindowSpec};
+
+/// 前序遍历表达式树的迭代器，由 [`Expr::iter`] 创建
+pub struct Iter<'a> {
+    stack: Vec<&'a Expr>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a Expr;
+
+    fn next(&mut self) -> Option<&'a Expr> {
+        let expr = self.stack.pop()?;
+        expr.push_children(&mut self.stack);
+        Some(expr)
+    }
+}
+
+// 后序折叠时栈中的帧：进入节点时展开子节点，离开节点时合并子节点的结果
+enum Frame<'a> {
+    Enter(&'a Expr),
+    Exit(&'a Expr, usize),
+}
+
+impl Expr {
+    /// 直接子表达式，按在SQL中出现的顺序
+    pub fn children(&self) -> Vec<&Expr> {
+        let mut children = Vec::new();
+        self.push_children(&mut children);
+        children.reverse();
+        children
+    }
+
+    /// 可修改的直接子表达式，顺序与 [`Expr::children`] 一致
+    pub fn children_mut(&mut self) -> Vec<&mut Expr> {
+        let mut children = Vec::new();
+        self.push_children_mut(&mut children);
+        children.reverse();
+        children
+    }
+
+    /// 前序遍历所有节点（包括自身）
+    pub fn iter(&self) -> Iter<'_> {
+        Iter { stack: vec![self] }
+    }
+
+    /// 按前序对每个节点调用 f
+    pub fn walk<'a>(&'a self, f: impl FnMut(&'a Expr)) {
+        self.iter().for_each(f)
+    }
+
+    /// 按前序对每个节点调用 f，f 替换掉的节点会继续遍历替换后的子节点
+    pub fn walk_mut(&mut self, mut f: impl FnMut(&mut Expr)) {
+        let mut stack = vec![self];
+        while let Some(expr) = stack.pop() {
+            f(expr);
+            expr.push_children_mut(&mut stack);
+        }
+    }
+
+    /// 后序折叠：先得到所有子节点的结果，再和节点本身一起计算出节点的结果
+    ///
+    /// 传给 f 的子节点结果与 [`Expr::children`] 的顺序一致。
+    pub fn fold<T>(&self, mut f: impl FnMut(&Expr, Vec<T>) -> T) -> T {
+        let mut frames = vec![Frame::Enter(self)];
+        let mut results: Vec<T> = Vec::new();
+        let mut children = Vec::new();
+        while let Some(frame) = frames.pop() {
+            match frame {
+                Frame::Enter(expr) => {
+                    expr.push_children(&mut children);
+                    frames.push(Frame::Exit(expr, children.len()));
+                    frames.extend(children.drain(..).map(Frame::Enter));
+                }
+                Frame::Exit(expr, count) => {
+                    let children = results.split_off(results.len() - count);
+                    results.push(f(expr, children));
+                }
+            }
+        }
+        results.pop().expect("fold always produces a result for the root")
+    }
+
+    /// 用新的子表达式替换直接子表达式，其它部分保持不变
+    ///
+    /// children 的数量和顺序必须与 [`Expr::children`] 一致，通常与 [`Expr::fold`] 配合使用。
+    ///
+    /// # Panics
+    ///
+    /// children 数量不足时 panic
+    pub fn with_children(&self, children: impl IntoIterator<Item = Expr>) -> Expr {
+        let mut children = children.into_iter();
+        let mut next = move || Box::new(children.next().expect("not enough children for expression"));
+        match self {
+            Expr::Identifier(_) | Expr::Wildcard | Expr::Literal(_) | Expr::Subquery(_) | Expr::Exists(_) => {
+                self.clone()
+            }
+            Expr::BinaryOp { op, .. } => Expr::BinaryOp { left: next(), op: op.clone(), right: next() },
+            Expr::In { list, negated, .. } => Expr::In {
+                expr: next(),
+                list: (0..list.len()).map(|_| *next()).collect(),
+                negated: *negated,
+            },
+            Expr::Between { negated, .. } => Expr::Between { expr: next(), low: next(), high: next(), negated: *negated },
+            Expr::IsNull { negated, .. } => Expr::IsNull { expr: next(), negated: *negated },
+            Expr::FunctionCall { name, args } => Expr::FunctionCall {
+                name: name.clone(),
+                args: (0..args.len()).map(|_| *next()).collect(),
+            },
+            Expr::Aggregate { func, distinct, args } => Expr::Aggregate {
+                func: *func,
+                distinct: *distinct,
+                args: (0..args.len()).map(|_| *next()).collect(),
+            },
+            Expr::GroupConcat(group_concat) => Expr::GroupConcat(Box::new(GroupConcat {
+                distinct: group_concat.distinct,
+                args: (0..group_concat.args.len()).map(|_| *next()).collect(),
+                order_by: group_concat
+                    .order_by
+                    .iter()
+                    .map(|item| OrderByExpr {
+                        expr: match &item.expr {
+                            SortKey::Expr(_) => SortKey::Expr(*next()),
+                            ordinal => ordinal.clone(),
+                        },
+                        asc: item.asc,
+                    })
+                    .collect(),
+                separator: group_concat.separator.clone(),
+            })),
+            Expr::LogicalOp { op, expressions } => Expr::LogicalOp {
+                op: op.clone(),
+                expressions: (0..expressions.len()).map(|_| *next()).collect(),
+            },
+            Expr::UnaryOp { op, .. } => Expr::UnaryOp { op: op.clone(), expr: next() },
+            Expr::Introducer { charset, .. } => Expr::Introducer { charset: charset.clone(), expr: next() },
+            Expr::Collate { collation, .. } => Expr::Collate { expr: next(), collation: collation.clone() },
+            Expr::Cast { data_type, .. } => Expr::Cast { expr: next(), data_type: data_type.clone() },
+            Expr::Interval { unit, .. } => Expr::Interval { value: next(), unit: *unit },
+            Expr::Match { columns, mode, .. } => Expr::Match { columns: columns.clone(), against: next(), mode: *mode },
+            Expr::Assignment { variable, .. } => Expr::Assignment { variable: variable.clone(), value: next() },
+            Expr::Window { over, .. } => Expr::Window {
+                func: next(),
+                over: Box::new(WindowSpec {
+                    name: over.name.clone(),
+                    partition_by: (0..over.partition_by.len()).map(|_| *next()).collect(),
+                    order_by: over
+                        .order_by
+                        .iter()
+                        .map(|item| OrderByExpr {
+                            expr: match &item.expr {
+                                SortKey::Expr(_) => SortKey::Expr(*next()),
+                                ordinal => ordinal.clone(),
+                            },
+                            asc: item.asc,
+                        })
+                        .collect(),
+                    frame: over.frame.clone(),
+                }),
+            },
+            Expr::Array(items) => Expr::Array((0..items.len()).map(|_| *next()).collect()),
+            Expr::Subscript { .. } => Expr::Subscript { expr: next(), index: next() },
+            Expr::Any { op, .. } => Expr::Any { expr: next(), op: op.clone(), array: next() },
+            Expr::Case { operand, conditions, else_result, .. } => {
+                let operand = operand.as_ref().map(|_| next());
+                let (conditions, results) = (0..conditions.len()).map(|_| (*next(), *next())).unzip();
+                Expr::Case { operand, conditions, results, else_result: else_result.as_ref().map(|_| next()) }
+            }
+            Expr::Extract { unit, .. } => Expr::Extract { unit: *unit, expr: next() },
+        }
+    }
+
+    // 把直接子表达式按相反的顺序压栈，出栈时即为原顺序
+    fn push_children<'a>(&'a self, stack: &mut Vec<&'a Expr>) {
+        match self {
+            Expr::Identifier(_) | Expr::Wildcard | Expr::Literal(_) | Expr::Subquery(_) | Expr::Exists(_) => {}
+            Expr::BinaryOp { left, right, .. }
+            | Expr::Subscript { expr: left, index: right }
+            | Expr::Any { expr: left, array: right, .. } => stack.extend([&**right, &**left]),
+            Expr::In { expr, list, .. } => {
+                stack.extend(list.iter().rev());
+                stack.push(expr);
+            }
+            Expr::Between { expr, low, high, .. } => stack.extend([&**high, &**low, &**expr]),
+            Expr::FunctionCall { args: exprs, .. }
+            | Expr::Aggregate { args: exprs, .. }
+            | Expr::LogicalOp { expressions: exprs, .. }
+            | Expr::Array(exprs) => stack.extend(exprs.iter().rev()),
+            // 先是参数，然后是 ORDER BY 中的表达式
+            Expr::GroupConcat(group_concat) => {
+                stack.extend(group_concat.order_by.iter().rev().filter_map(|item| match &item.expr {
+                    SortKey::Expr(expr) => Some(expr),
+                    SortKey::Ordinal(_) => None,
+                }));
+                stack.extend(group_concat.args.iter().rev());
+            }
+            // 先是 CASE 之后的表达式，然后依次是每个 WHEN 和 THEN，最后是 ELSE
+            Expr::Case { operand, conditions, results, else_result } => {
+                stack.extend(else_result.as_deref());
+                for (condition, result) in conditions.iter().zip(results).rev() {
+                    stack.extend([result, condition]);
+                }
+                stack.extend(operand.as_deref());
+            }
+            // 先是函数，然后是 PARTITION BY 和 ORDER BY 中的表达式
+            Expr::Window { func, over } => {
+                stack.extend(over.order_by.iter().rev().filter_map(|item| match &item.expr {
+                    SortKey::Expr(expr) => Some(expr),
+                    SortKey::Ordinal(_) => None,
+                }));
+                stack.extend(over.partition_by.iter().rev());
+                stack.push(func);
+            }
+            Expr::IsNull { expr, .. }
+            | Expr::UnaryOp { expr, .. }
+            | Expr::Introducer { expr, .. }
+            | Expr::Collate { expr, .. }
+            | Expr::Cast { expr, .. }
+            | Expr::Interval { value: expr, .. }
+            | Expr::Extract { expr, .. }
+            | Expr::Match { against: expr, .. }
+            | Expr::Assignment { value: expr, .. } => stack.push(expr),
+        }
+    }
+
+    fn push_children_mut<'a>(&'a mut self, stack: &mut Vec<&'a mut Expr>) {
+        match self {
+            Expr::Identifier(_) | Expr::Wildcard | Expr::Literal(_) | Expr::Subquery(_) | Expr::Exists(_) => {}
+            Expr::BinaryOp { left, right, .. }
+            | Expr::Subscript { expr: left, index: right }
+            | Expr::Any { expr: left, array: right, .. } => stack.extend([&mut **right, &mut **left]),
+            Expr::In { expr, list, .. } => {
+                stack.extend(list.iter_mut().rev());
+                stack.push(expr);
+            }
+            Expr::Between { expr, low, high, .. } => stack.extend([&mut **high, &mut **low, &mut **expr]),
+            Expr::FunctionCall { args: exprs, .. }
+            | Expr::Aggregate { args: exprs, .. }
+            | Expr::LogicalOp { expressions: exprs, .. }
+            | Expr::Array(exprs) => stack.extend(exprs.iter_mut().rev()),
+            Expr::GroupConcat(group_concat) => {
+                let GroupConcat { args, order_by, .. } = &mut **group_concat;
+                stack.extend(order_by.iter_mut().rev().filter_map(|item| match &mut item.expr {
+                    SortKey::Expr(expr) => Some(expr),
+                    SortKey::Ordinal(_) => None,
+                }));
+                stack.extend(args.iter_mut().rev());
+            }
+            Expr::Case { operand, conditions, results, else_result } => {
+                stack.extend(else_result.as_deref_mut());
+                for (condition, result) in conditions.iter_mut().zip(results).rev() {
+                    stack.extend([result, condition]);
+                }
+                stack.extend(operand.as_deref_mut());
+            }
+            Expr::Window { func, over } => {
+                let WindowSpec { partition_by, order_by, .. } = &mut **over;
+                stack.extend(order_by.iter_mut().rev().filter_map(|item| match &mut item.expr {
+                    SortKey::Expr(expr) => Some(expr),
+                    SortKey::Ordinal(_) => None,
+                }));
+                stack.extend(partition_by.iter_mut().rev());
+                stack.push(func);
+            }
+            Expr::IsNull { expr, .. }
+            | Expr::UnaryOp { expr, .. }
+            | Expr::Introducer { expr, .. }
+            | Expr::Collate { expr, .. }
+            | Expr::Cast { expr, .. }
+            | Expr::Interval { value: expr, .. }
+            | Expr::Extract { expr, .. }
+            | Expr::Match { against: expr, .. }
+            | Expr::Assignment { value: expr, .. } => stack.push(expr),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ast::common::ObjectName;
+    use crate::ast::expr::{BinaryOperator, Value};
+    use crate::parser::Parser;
+
+    fn parse(sql: &str) -> Expr {
+        Parser::new_from_sql(sql).parse_expr(0).unwrap()
+    }
+
+    #[test]
+    fn test_walk_and_fold() {
+        let expr = parse("a + 1 > LOWER(b) AND c IN (2, 3)");
+        let names: Vec<String> = expr
+            .iter()
+            .filter_map(|e| match e {
+                Expr::Identifier(name) => Some(name.to_string()),
+                Expr::Literal(value) => Some(format!("{:?}", value)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(names, ["a", "Integer(1)", "b", "c", "Integer(2)", "Integer(3)"]);
+        assert_eq!(expr.children().len(), 2);
+
+        // 用 fold 计算树的高度
+        let height = expr.fold(|_, children: Vec<usize>| 1 + children.into_iter().max().unwrap_or(0));
+        assert_eq!(height, 4);
+        // 不做修改的 fold 得到原来的树
+        assert_eq!(expr.fold(|node, children| node.with_children(children)), expr);
+
+        let mut masked = expr.clone();
+        masked.walk_mut(|e| {
+            if let Expr::Literal(_) = e {
+                *e = Expr::Identifier(ObjectName::new(["?"]));
+            }
+        });
+        assert!(masked.iter().all(|e| !matches!(e, Expr::Literal(_))));
+    }
+
+    #[test]
+    fn test_deep_tree() {
+        // 手工构造很深的树，递归遍历会耗尽栈空间
+        let mut expr = Expr::Literal(Value::Integer(0));
+        for i in 1..50_000 {
+            expr = Expr::BinaryOp {
+                left: Box::new(expr),
+                op: BinaryOperator::Plus,
+                right: Box::new(Expr::Literal(Value::Integer(i))),
+            };
+        }
+        assert_eq!(expr.iter().count(), 99_999);
+        let sum = expr.fold(|node, children: Vec<i64>| match node {
+            Expr::Literal(Value::Integer(i)) => *i,
+            _ => children.into_iter().sum(),
+        });
+        assert_eq!(sum, (0..50_000).sum::<i64>());
+        expr.walk_mut(|_| {});
+        // 自动生成的 Drop 是递归的，这里不释放
+        std::mem::forget(expr);
+    }
+}
@@ -0,0 +1,301 @@
+//! 编辑器补全支持
+//!
+//! [`context`] 根据光标之前的token判断光标处期望的内容（语句关键字、表名、列名等），
+//! 并通过对整条语句的部分解析收集其中引用的表，编辑器插件不需要自己实现语法。
+
+use crate::ast::common::TableReference;
+use crate::parser::Parser;
+use crate::token::{tokenize, Token};
+
+/// 光标处期望的内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompletionKind {
+    /// 语句开头，期望 SELECT、INSERT 等语句关键字
+    Statement,
+    /// 期望列名。qualifier 为点号前的表名或别名，例如 u.| 中的 u
+    Column { qualifier: Option<String> },
+    /// 期望表名
+    Table,
+    /// 只期望关键字，例如表名之后的 WHERE
+    Keyword,
+    /// 光标位于字符串或注释中，不需要补全
+    None,
+}
+
+/// 光标处的补全上下文
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompletionContext {
+    pub kind: CompletionKind,
+    /// 光标前已经输入的部分单词，用于过滤候选项
+    pub prefix: String,
+    /// 当前位置可以出现的关键字，已按 prefix 过滤
+    pub keywords: Vec<&'static str>,
+    /// 语句中引用的表，用于补全列名
+    pub tables: Vec<TableReference>,
+}
+
+impl CompletionContext {
+    /// 列名的限定符对应的表，按别名或表名查找
+    pub fn qualified_table(&self) -> Option<&TableReference> {
+        let CompletionKind::Column { qualifier: Some(qualifier) } = &self.kind else {
+            return None;
+        };
+        self.tables.iter().find(|table| match &table.alias {
+            Some(alias) => alias.eq_ignore_ascii_case(qualifier),
+            None => table.name.name().value.eq_ignore_ascii_case(qualifier),
+        })
+    }
+}
+
+const STATEMENT_KEYWORDS: &[&str] = &["SELECT", "INSERT", "UPDATE", "DELETE", "CREATE", "ALTER", "SHOW"];
+const AFTER_SELECT_ITEM: &[&str] = &["AS", "FROM", "INTO"];
+const AFTER_TABLE: &[&str] = &["AS", "PARTITION", "WHERE", "GROUP", "HAVING", "ORDER", "LIMIT"];
+const AFTER_CONDITION: &[&str] = &["AND", "OR", "IS", "IN", "BETWEEN", "GROUP", "HAVING", "ORDER", "LIMIT"];
+const AFTER_GROUP_ITEM: &[&str] = &["HAVING", "ORDER", "LIMIT"];
+const AFTER_ORDER_ITEM: &[&str] = &["ASC", "DESC", "LIMIT"];
+const AFTER_UPDATE_TABLE: &[&str] = &["SET"];
+const AFTER_INSERT_TABLE: &[&str] = &["VALUES", "SET", "SELECT"];
+const AFTER_ASSIGNMENT: &[&str] = &["WHERE", "ORDER", "LIMIT"];
+const BY: &[&str] = &["BY"];
+
+// 决定后续内容的从句关键字
+const CLAUSES: &[&str] = &[
+    "SELECT", "FROM", "WHERE", "HAVING", "ON", "GROUP", "ORDER", "LIMIT", "SET", "UPDATE", "INTO", "VALUES",
+    "DELETE", "INSERT", "CREATE", "ALTER", "SHOW",
+];
+// 后面紧跟表名的关键字
+const TABLE_KEYWORDS: &[&str] = &["FROM", "JOIN", "STRAIGHT_JOIN", "UPDATE", "INTO", "TABLE"];
+
+// 光标前文本的词法状态
+#[derive(PartialEq)]
+enum ScanState {
+    Normal,
+    String,
+    Backtick(usize),
+    Comment,
+}
+
+// 扫描光标前的文本，判断光标是否位于字符串、反引号标识符或注释中
+fn scan(before: &str) -> ScanState {
+    let bytes = before.as_bytes();
+    let mut state = ScanState::Normal;
+    let mut block_comment = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        match state {
+            ScanState::Normal => match b {
+                b'\'' => state = ScanState::String,
+                b'`' => state = ScanState::Backtick(i + 1),
+                b'-' if bytes.get(i + 1) == Some(&b'-') => state = ScanState::Comment,
+                b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                    state = ScanState::Comment;
+                    block_comment = true;
+                    i += 1;
+                }
+                _ => {}
+            },
+            // MySQL 字符串中的反斜杠转义下一个字符
+            ScanState::String if b == b'\\' => i += 1,
+            ScanState::String if b == b'\'' => state = ScanState::Normal,
+            ScanState::Backtick(_) if b == b'`' => state = ScanState::Normal,
+            ScanState::Comment if block_comment && b == b'*' && bytes.get(i + 1) == Some(&b'/') => {
+                state = ScanState::Normal;
+                block_comment = false;
+                i += 1;
+            }
+            ScanState::Comment if !block_comment && b == b'\n' => state = ScanState::Normal,
+            _ => {}
+        }
+        i += 1;
+    }
+    state
+}
+
+// 去掉反引号后的名称，用于限定符
+fn unquote(word: &str) -> String {
+    word.trim_matches('`').to_string()
+}
+
+fn keyword_upper(token: &Token) -> Option<&'static str> {
+    match token {
+        Token::Keyword { keyword, .. } => Some(keyword.as_str()),
+        _ => None,
+    }
+}
+
+// 光标之前最近的从句关键字
+fn current_clause(tokens: &[Token]) -> Option<&'static str> {
+    tokens
+        .iter()
+        .rev()
+        .filter_map(keyword_upper)
+        .find(|k| CLAUSES.contains(k) || *k == "JOIN" || *k == "TABLE")
+}
+
+// 根据光标前的token判断期望的内容
+fn classify(tokens: &[Token]) -> (CompletionKind, &'static [&'static str]) {
+    let column = CompletionKind::Column { qualifier: None };
+    let Some(last) = tokens.last() else {
+        return (CompletionKind::Statement, STATEMENT_KEYWORDS);
+    };
+    let clause = current_clause(tokens);
+    match last {
+        Token::Punctuator(';') => (CompletionKind::Statement, STATEMENT_KEYWORDS),
+        Token::Keyword { keyword, .. } => match keyword.as_str() {
+            k if TABLE_KEYWORDS.contains(&k) => (CompletionKind::Table, &[]),
+            "SELECT" => (column, &["DISTINCT"]),
+            "DISTINCT" | "WHERE" | "AND" | "OR" | "NOT" | "ON" | "HAVING" | "SET" | "BY" => (column, &[]),
+            "GROUP" | "ORDER" => (CompletionKind::Keyword, BY),
+            "DELETE" => (CompletionKind::Keyword, &["FROM"]),
+            "INSERT" => (CompletionKind::Keyword, &["INTO"]),
+            "CREATE" | "ALTER" => (CompletionKind::Keyword, &["TABLE"]),
+            _ => (CompletionKind::Keyword, &[]),
+        },
+        // 逗号、左括号和运算符之后是新的一项
+        Token::Punctuator(',') | Token::Punctuator('(') | Token::Operator(_) => match clause {
+            Some("FROM") | Some("JOIN") => (CompletionKind::Table, &[]),
+            _ => (column, &[]),
+        },
+        // 一个完整的项之后期望从句中的关键字
+        _ => {
+            let keywords = match clause {
+                Some("SELECT") => AFTER_SELECT_ITEM,
+                Some("FROM") | Some("JOIN") => AFTER_TABLE,
+                Some("WHERE") | Some("HAVING") | Some("ON") => AFTER_CONDITION,
+                Some("GROUP") => AFTER_GROUP_ITEM,
+                Some("ORDER") => AFTER_ORDER_ITEM,
+                Some("UPDATE") => AFTER_UPDATE_TABLE,
+                Some("INTO") => AFTER_INSERT_TABLE,
+                Some("SET") => AFTER_ASSIGNMENT,
+                _ => &[],
+            };
+            (CompletionKind::Keyword, keywords)
+        }
+    }
+}
+
+// 解析整条语句中出现在 FROM、UPDATE、INTO 等关键字之后的表
+fn collect_tables(sql: &str) -> Vec<TableReference> {
+    let tokens = tokenize(sql);
+    tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| keyword_upper(token).is_some_and(|k| TABLE_KEYWORDS.contains(&k)))
+        .filter_map(|(i, _)| Parser::new(tokens[i + 1..].to_vec()).parse_table_reference(true).ok())
+        .collect()
+}
+
+/// 计算 sql 中 cursor_offset（字节偏移）处的补全上下文
+pub fn context(sql: &str, cursor_offset: usize) -> CompletionContext {
+    let mut cursor = cursor_offset.min(sql.len());
+    while !sql.is_char_boundary(cursor) {
+        cursor -= 1;
+    }
+    let before = &sql[..cursor];
+    let tables = collect_tables(sql);
+
+    let (prefix_start, prefix) = match scan(before) {
+        ScanState::String | ScanState::Comment => {
+            return CompletionContext {
+                kind: CompletionKind::None,
+                prefix: String::new(),
+                keywords: Vec::new(),
+                tables,
+            };
+        }
+        // 光标位于未闭合的反引号中，反引号之后的内容都是前缀
+        ScanState::Backtick(start) => (start - 1, &before[start..]),
+        ScanState::Normal => {
+            let start = before
+                .char_indices()
+                .rev()
+                .take_while(|(_, c)| c.is_alphanumeric() || *c == '_')
+                .last()
+                .map_or(before.len(), |(i, _)| i);
+            (start, &before[start..])
+        }
+    };
+
+    // 点号之前的单词是列名的限定符，例如 u.na|
+    let head = &before[..prefix_start];
+    let (kind, keywords) = match head.strip_suffix('.') {
+        Some(rest) => {
+            let qualifier_start = rest
+                .char_indices()
+                .rev()
+                .take_while(|(_, c)| c.is_alphanumeric() || *c == '_' || *c == '`')
+                .last()
+                .map_or(rest.len(), |(i, _)| i);
+            let qualifier = unquote(&rest[qualifier_start..]);
+            (CompletionKind::Column { qualifier: Some(qualifier) }, &[][..])
+        }
+        None => classify(&tokenize(head)),
+    };
+
+    let keywords = keywords
+        .iter()
+        .copied()
+        .filter(|k| k.len() >= prefix.len() && k[..prefix.len()].eq_ignore_ascii_case(prefix))
+        .collect();
+    CompletionContext { kind, prefix: prefix.to_string(), keywords, tables }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // 用 | 标记光标位置
+    fn at(sql: &str) -> CompletionContext {
+        let cursor = sql.find('|').expect("missing cursor");
+        context(&sql.replace('|', ""), cursor)
+    }
+
+    #[test]
+    fn test_completion_context() {
+        let ctx = at("|");
+        assert_eq!(ctx.kind, CompletionKind::Statement);
+        assert!(ctx.keywords.contains(&"SELECT"));
+
+        let ctx = at("SEL|");
+        assert_eq!(ctx.prefix, "SEL");
+        assert_eq!(ctx.keywords, vec!["SELECT"]);
+
+        let ctx = at("SELECT | FROM users");
+        assert_eq!(ctx.kind, CompletionKind::Column { qualifier: None });
+        assert_eq!(ctx.tables.len(), 1);
+        assert_eq!(ctx.tables[0].name.name().value, "users");
+
+        let ctx = at("SELECT id, na| FROM users");
+        assert_eq!(ctx.kind, CompletionKind::Column { qualifier: None });
+        assert_eq!(ctx.prefix, "na");
+
+        let ctx = at("SELECT id FROM |");
+        assert_eq!(ctx.kind, CompletionKind::Table);
+
+        let ctx = at("SELECT id FROM users W|");
+        assert_eq!(ctx.kind, CompletionKind::Keyword);
+        assert_eq!(ctx.keywords, vec!["WHERE"]);
+
+        let ctx = at("SELECT id FROM users AS u WHERE u.|");
+        assert_eq!(ctx.kind, CompletionKind::Column { qualifier: Some("u".to_string()) });
+        assert_eq!(ctx.qualified_table().unwrap().name.name().value, "users");
+
+        let ctx = at("SELECT id FROM users WHERE age > 1 |");
+        assert_eq!(ctx.kind, CompletionKind::Keyword);
+        assert!(ctx.keywords.contains(&"AND"));
+
+        let ctx = at("SELECT id FROM users ORDER |");
+        assert_eq!(ctx.keywords, vec!["BY"]);
+
+        let ctx = at("UPDATE `ord|");
+        assert_eq!(ctx.kind, CompletionKind::Table);
+        assert_eq!(ctx.prefix, "ord");
+
+        // 字符串和注释中不补全
+        assert_eq!(at("SELECT id FROM users WHERE name = 'Al|").kind, CompletionKind::None);
+        assert_eq!(at("SELECT id -- fr|\nFROM users").kind, CompletionKind::None);
+        // 注释结束后恢复正常
+        assert_eq!(at("SELECT /* x */ |").kind, CompletionKind::Column { qualifier: None });
+    }
+}
@@ -0,0 +1,500 @@
+//! 调试输出
+//!
+//! - [`to_dot`] 把语句的AST输出为 Graphviz 的 DOT 格式，[`join_graph`] 输出 SELECT 中各表的连接关系。
+//!   可以用 `dot -Tsvg ast.dot -o ast.svg` 渲染为图片。
+//! - [`TreeDisplay`] 把AST输出为缩进的树，每行一个节点，类似 `EXPLAIN FORMAT=TREE`，适合在测试失败时对比。
+//!
+//! 两者都比嵌套的 Debug 输出更容易看清多表连接和复杂表达式的结构。
+
+use std::fmt::{self, Write};
+
+use crate::ast::common::TableReference;
+use crate::ast::expr::{Expr, LimitClause, LogicalOperator, OrderByExpr, SortKey, UnaryOperator};
+use crate::ast::select::{Join, JoinKind, SelectColumn, SelectStatement};
+use crate::ast::SQLStatement;
+
+/// 把语句的AST输出为 DOT 格式的有向图，每个节点是一个子句或表达式
+pub fn to_dot(stmt: &SQLStatement) -> String {
+    let mut graph = Graph::new("digraph", "ast");
+    // 各层最近的节点，即下一个更深节点的父节点
+    let mut parents: Vec<usize> = Vec::new();
+    for (id, node) in Outline::of(stmt).nodes.iter().enumerate() {
+        graph.node(id, &node.label);
+        parents.truncate(node.depth);
+        if let Some(parent) = parents.last() {
+            graph.edge(*parent, id, node.edge.as_deref());
+        }
+        parents.push(id);
+    }
+    graph.finish()
+}
+
+/// 把 SELECT 中的表及其连接输出为 DOT 格式的无向图
+///
+/// 节点是表（有别名时显示为 `name AS alias`），边是连接，标签为连接方式和 ON 条件。
+/// ON 条件中通过限定符引用的表与被连接的表之间各有一条边，没有引用其它表时连接到 FROM 中的第一个表。
+pub fn join_graph(select: &SelectStatement) -> String {
+    let mut graph = Graph::new("graph", "joins");
+    let Some(from) = &select.from else {
+        return graph.finish();
+    };
+    graph.node(0, &from.to_string());
+    let mut tables = vec![from];
+    for join in &select.joins {
+        let id = tables.len();
+        graph.node(id, &join.table.to_string());
+        let kind = join_keyword(join);
+        let label = match (&join.on, &join.using) {
+            (Some(on), _) => format!("{} ON {}", kind, on),
+            (None, Some(using)) => format!("{} USING ({})", kind, using.join(", ")),
+            (None, None) => kind,
+        };
+        let mut referenced: Vec<usize> = Vec::new();
+        for expr in join.on.iter().flat_map(Expr::iter) {
+            let Expr::Identifier(name) = expr else {
+                continue;
+            };
+            let Some(qualifier) = name.qualifier().last() else {
+                continue;
+            };
+            if let Some(other) = tables.iter().position(|table| table_matches(table, &qualifier.value)) {
+                if !referenced.contains(&other) {
+                    referenced.push(other);
+                }
+            }
+        }
+        if referenced.is_empty() {
+            referenced.push(0);
+        }
+        for other in referenced {
+            graph.edge(other, id, Some(&label));
+        }
+        tables.push(&join.table);
+    }
+    graph.finish()
+}
+
+/// 把AST输出为缩进的树，每行一个节点
+///
+/// 子句和表达式各占一行，节点与父节点的关系（例如 WHERE、ON、别名）写在节点前面：
+///
+/// ```text
+/// SELECT
+/// ├─ id
+/// ├─ FROM: users
+/// └─ WHERE: >
+///    ├─ age
+///    └─ 18
+/// ```
+pub struct TreeDisplay {
+    outline: Outline,
+}
+
+impl TreeDisplay {
+    pub fn new(stmt: &SQLStatement) -> Self {
+        TreeDisplay { outline: Outline::of(stmt) }
+    }
+
+    /// 单独的 SELECT 语句，例如子查询
+    pub fn select(select: &SelectStatement) -> Self {
+        let mut outline = Outline::default();
+        outline.select(0, select, None);
+        TreeDisplay { outline }
+    }
+}
+
+impl fmt::Display for TreeDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nodes = &self.outline.nodes;
+        // 从后往前确定每个节点是否为最后一个子节点：later[d] 表示深度 d 上之后还有兄弟节点
+        let mut is_last = vec![true; nodes.len()];
+        let mut later: Vec<bool> = Vec::new();
+        for (i, node) in nodes.iter().enumerate().rev() {
+            later.resize(node.depth + 1, false);
+            is_last[i] = !later[node.depth];
+            later[node.depth] = true;
+        }
+        // 各层祖先是否为最后一个子节点，决定是否画竖线
+        let mut ancestors: Vec<bool> = Vec::new();
+        for (node, last) in nodes.iter().zip(is_last) {
+            ancestors.truncate(node.depth);
+            if node.depth > 0 {
+                for ancestor_last in &ancestors[1..] {
+                    f.write_str(if *ancestor_last { "   " } else { "│  " })?;
+                }
+                f.write_str(if last { "└─ " } else { "├─ " })?;
+            }
+            if let Some(edge) = &node.edge {
+                write!(f, "{}: ", edge)?;
+            }
+            writeln!(f, "{}", node.label)?;
+            ancestors.push(last);
+        }
+        Ok(())
+    }
+}
+
+fn join_keyword(join: &Join) -> String {
+    let keyword = match join.kind {
+        JoinKind::Inner => "JOIN",
+        JoinKind::Cross => "CROSS JOIN",
+        JoinKind::Left => "LEFT JOIN",
+        JoinKind::Right => "RIGHT JOIN",
+        JoinKind::Straight => "STRAIGHT_JOIN",
+        JoinKind::Comma => ",",
+    };
+    if join.natural {
+        format!("NATURAL {}", keyword)
+    } else {
+        keyword.to_string()
+    }
+}
+
+// 限定符是否指向这个表：有别名时只能用别名，否则用表名
+fn table_matches(table: &TableReference, qualifier: &str) -> bool {
+    match &table.alias {
+        Some(alias) => alias.eq_ignore_ascii_case(qualifier),
+        None => table.name.name().value.eq_ignore_ascii_case(qualifier),
+    }
+}
+
+// DOT 输出
+struct Graph {
+    out: String,
+    edge_op: &'static str,
+}
+
+impl Graph {
+    fn new(kind: &str, name: &str) -> Self {
+        let edge_op = if kind == "digraph" { "->" } else { "--" };
+        Graph { out: format!("{} {} {{\n  node [shape=box];\n", kind, name), edge_op }
+    }
+
+    fn finish(mut self) -> String {
+        self.out.push_str("}\n");
+        self.out
+    }
+
+    fn node(&mut self, id: usize, label: &str) {
+        let _ = writeln!(self.out, "  n{} [label=\"{}\"];", id, escape(label));
+    }
+
+    fn edge(&mut self, from: usize, to: usize, label: Option<&str>) {
+        let _ = write!(self.out, "  n{} {} n{}", from, self.edge_op, to);
+        if let Some(label) = label {
+            let _ = write!(self.out, " [label=\"{}\"]", escape(label));
+        }
+        self.out.push_str(";\n");
+    }
+}
+
+// 前序排列的节点，深度决定父子关系，DOT 和树形输出共用
+#[derive(Default)]
+struct Outline {
+    nodes: Vec<OutlineNode>,
+}
+
+struct OutlineNode {
+    depth: usize,
+    label: String,
+    // 与父节点的关系
+    edge: Option<String>,
+}
+
+impl Outline {
+    fn of(stmt: &SQLStatement) -> Self {
+        let mut outline = Outline::default();
+        match stmt {
+            SQLStatement::Select(select) => outline.select(0, select, None),
+            SQLStatement::Insert(insert) => {
+                let columns = insert.columns.as_ref().map(|columns| format!(" ({})", columns.join(", ")));
+                outline.push(0, format!("INSERT INTO {}{}", insert.table, columns.unwrap_or_default()), None);
+                for row in insert.values.iter().flatten() {
+                    outline.push(1, "ROW".to_string(), Some("VALUES".to_string()));
+                    row.iter().for_each(|value| outline.expr(2, value, None));
+                }
+                if let Some(select) = &insert.select_clause {
+                    outline.select(1, select, None);
+                }
+                for (column, value) in insert.set_clause.iter().flatten() {
+                    outline.expr(1, value, Some(format!("SET {}", column)));
+                }
+                for (column, value) in insert.on_duplicate.iter().flat_map(|clause| &clause.updates) {
+                    outline.expr(1, value, Some(format!("ON DUPLICATE KEY UPDATE {}", column)));
+                }
+            }
+            SQLStatement::Update(update) => {
+                outline.push(0, format!("UPDATE {}", update.table), None);
+                for (column, value) in &update.assignments {
+                    outline.expr(1, value, Some(format!("SET {}", column)));
+                }
+                outline.clause(1, "WHERE", update.where_clause.as_ref());
+                outline.order_by(1, &update.order_by);
+                outline.limit(1, &update.limit);
+            }
+            SQLStatement::Delete(delete) => {
+                outline.push(0, format!("DELETE FROM {}", delete.table), None);
+                outline.clause(1, "WHERE", delete.where_clause.as_ref());
+                outline.order_by(1, &delete.order_by);
+                outline.limit(1, &delete.limit);
+            }
+            #[cfg(feature = "ddl")]
+            SQLStatement::Create(_) => outline.push(0, "CREATE".to_string(), None),
+            #[cfg(feature = "ddl")]
+            SQLStatement::Alter(_) => outline.push(0, "ALTER".to_string(), None),
+            SQLStatement::Show(_) => outline.push(0, "SHOW".to_string(), None),
+            SQLStatement::Set(_) => outline.push(0, "SET".to_string(), None),
+        }
+        outline
+    }
+
+    fn push(&mut self, depth: usize, label: String, edge: Option<String>) {
+        self.nodes.push(OutlineNode { depth, label, edge });
+    }
+
+    fn select(&mut self, depth: usize, select: &SelectStatement, edge: Option<String>) {
+        let label = if select.distinct { "SELECT DISTINCT" } else { "SELECT" };
+        self.push(depth, label.to_string(), edge);
+        let depth = depth + 1;
+        if let Some(with) = &select.with {
+            let keyword = if with.recursive { "WITH RECURSIVE" } else { "WITH" };
+            for cte in &with.ctes {
+                let name = if cte.columns.is_empty() {
+                    cte.name.clone()
+                } else {
+                    format!("{} ({})", cte.name, cte.columns.join(", "))
+                };
+                self.select(depth, &cte.query, Some(format!("{} {}", keyword, name)));
+            }
+        }
+        for column in &select.columns {
+            match column {
+                SelectColumn::Wildcard => self.push(depth, "*".to_string(), None),
+                SelectColumn::QualifiedWildcard(table) => self.push(depth, format!("{}.*", table), None),
+                SelectColumn::Column { name, alias } => {
+                    self.push(depth, name.to_string(), alias.as_ref().map(|alias| format!("AS {}", alias)))
+                }
+                SelectColumn::Expression { expr, alias } => {
+                    self.expr(depth, expr, alias.as_ref().map(|alias| format!("AS {}", alias)))
+                }
+            }
+        }
+        if let Some(from) = &select.from {
+            self.push(depth, from.to_string(), Some("FROM".to_string()));
+        }
+        for join in &select.joins {
+            self.push(depth, join.table.to_string(), Some(join_keyword(join)));
+            if let Some(on) = &join.on {
+                self.expr(depth + 1, on, Some("ON".to_string()));
+            }
+            if let Some(using) = &join.using {
+                self.push(depth + 1, using.join(", "), Some("USING".to_string()));
+            }
+        }
+        self.clause(depth, "WHERE", select.where_clause.as_ref());
+        if let Some(keys) = &select.group_by {
+            let label = if select.with_rollup { "GROUP BY WITH ROLLUP" } else { "GROUP BY" };
+            self.push(depth, label.to_string(), None);
+            for key in keys {
+                self.sort_key(depth + 1, key, None);
+            }
+        }
+        self.clause(depth, "HAVING", select.having.as_ref());
+        self.clause(depth, "QUALIFY", select.qualify.as_ref());
+        for (name, spec) in &select.windows {
+            self.push(depth, format!("{} AS ({})", name, spec), Some("WINDOW".to_string()));
+        }
+        for operation in &select.set_operations {
+            let name = operation.op.name();
+            let keyword = if operation.all { format!("{} ALL", name) } else { name.to_string() };
+            self.select(depth, &operation.select, Some(keyword));
+        }
+        self.order_by(depth, &select.order_by);
+        self.limit(depth, &select.limit);
+    }
+
+    fn clause(&mut self, depth: usize, name: &str, expr: Option<&Expr>) {
+        if let Some(expr) = expr {
+            self.expr(depth, expr, Some(name.to_string()));
+        }
+    }
+
+    fn order_by(&mut self, depth: usize, order_by: &Option<Vec<OrderByExpr>>) {
+        if let Some(items) = order_by {
+            self.push(depth, "ORDER BY".to_string(), None);
+            for item in items {
+                self.sort_key(depth + 1, &item.expr, Some(if item.asc { "ASC" } else { "DESC" }.to_string()));
+            }
+        }
+    }
+
+    fn sort_key(&mut self, depth: usize, key: &SortKey, edge: Option<String>) {
+        match key {
+            SortKey::Expr(expr) => self.expr(depth, expr, edge),
+            SortKey::Ordinal(ordinal) => self.push(depth, format!("#{}", ordinal), edge),
+        }
+    }
+
+    fn limit(&mut self, depth: usize, limit: &Option<LimitClause>) {
+        if let Some(limit) = limit {
+            self.push(depth, limit.to_string(), None);
+        }
+    }
+
+    // 使用显式的栈，很深的表达式也不会耗尽栈空间
+    fn expr(&mut self, depth: usize, expr: &Expr, edge: Option<String>) {
+        let mut stack = vec![(depth, expr, edge)];
+        while let Some((depth, expr, edge)) = stack.pop() {
+            if let Expr::Subquery(select) = expr {
+                self.select(depth, select, edge);
+                continue;
+            }
+            self.push(depth, expr_label(expr), edge);
+            if let Expr::Exists(select) = expr {
+                self.select(depth + 1, select, None);
+                continue;
+            }
+            stack.extend(expr.children().into_iter().rev().map(|child| (depth + 1, child, None)));
+        }
+    }
+}
+
+// 节点只显示自身，子表达式是单独的节点
+pub(crate) fn expr_label(expr: &Expr) -> String {
+    match expr {
+        Expr::Identifier(name) => name.to_string(),
+        Expr::Wildcard => "*".to_string(),
+        Expr::Literal(value) => value.to_string(),
+        Expr::BinaryOp { op, .. } => op.to_string(),
+        Expr::In { negated, .. } => if *negated { "NOT IN" } else { "IN" }.to_string(),
+        Expr::Between { negated, .. } => if *negated { "NOT BETWEEN" } else { "BETWEEN" }.to_string(),
+        Expr::IsNull { negated, .. } => if *negated { "IS NOT NULL" } else { "IS NULL" }.to_string(),
+        Expr::FunctionCall { name, .. } => format!("{}()", name),
+        Expr::Aggregate { func, distinct: true, .. } => format!("{}(DISTINCT)", func.name()),
+        Expr::Aggregate { func, .. } => format!("{}()", func.name()),
+        Expr::GroupConcat(group_concat) if group_concat.distinct => "GROUP_CONCAT(DISTINCT)".to_string(),
+        Expr::GroupConcat(_) => "GROUP_CONCAT()".to_string(),
+        Expr::LogicalOp { op, .. } => match op {
+            LogicalOperator::And => "AND",
+            LogicalOperator::Or => "OR",
+            LogicalOperator::Not => "NOT",
+        }
+        .to_string(),
+        Expr::UnaryOp { op, .. } => match op {
+            UnaryOperator::Plus => "+",
+            UnaryOperator::Minus => "-",
+        }
+        .to_string(),
+        Expr::Introducer { charset, .. } => format!("_{}", charset),
+        Expr::Collate { collation, .. } => format!("COLLATE {}", collation),
+        Expr::Cast { data_type, .. } => format!("CAST AS {}", data_type),
+        Expr::Interval { unit, .. } => format!("INTERVAL {}", unit.name()),
+        Expr::Match { columns, .. } => {
+            let columns: Vec<String> = columns.iter().map(|column| column.to_string()).collect();
+            format!("MATCH ({}) AGAINST", columns.join(", "))
+        }
+        Expr::Assignment { variable, .. } => format!("{} :=", variable),
+        Expr::Subquery(_) => "subquery".to_string(),
+        Expr::Window { over, .. } => match &over.name {
+            Some(name) => format!("OVER {}", name),
+            None => "OVER".to_string(),
+        },
+        Expr::Array(_) => "ARRAY[]".to_string(),
+        Expr::Subscript { .. } => "[]".to_string(),
+        Expr::Any { op, .. } => format!("{} ANY", op),
+        Expr::Case { .. } => "CASE".to_string(),
+        Expr::Exists(_) => "EXISTS".to_string(),
+        Expr::Extract { unit, .. } => format!("EXTRACT {}", unit.name()),
+    }
+}
+
+// DOT 字符串中需要转义双引号和反斜杠
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::select::SelectStatementParser;
+    use crate::parser::Parser;
+
+    fn parse(sql: &str) -> SelectStatement {
+        Parser::new_from_sql(sql).parse_select_statement().unwrap()
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let select = parse("SELECT name AS n FROM users WHERE age > 18 AND note = 'say \"hi\"' OR id IN (SELECT 1)");
+        let dot = to_dot(&SQLStatement::Select(Box::new(select)));
+        let lines: Vec<&str> = dot.lines().collect();
+        assert_eq!(lines[0], "digraph ast {");
+        assert!(lines.contains(&"  n1 [label=\"name\"];"));
+        assert!(lines.contains(&"  n0 -> n1 [label=\"AS n\"];"));
+        assert!(lines.contains(&"  n3 [label=\"OR\"];"));
+        assert!(lines.contains(&"  n0 -> n3 [label=\"WHERE\"];"));
+        assert!(lines.contains(&"  n4 [label=\"AND\"];"));
+        assert!(lines.contains(&"  n3 -> n4;"));
+        assert!(dot.contains("[label=\"'say \\\"hi\\\"'\"]"));
+        // 子查询是一个完整的 SELECT 子图
+        assert_eq!(dot.matches("[label=\"SELECT\"]").count(), 2);
+        assert_eq!(lines.last(), Some(&"}"));
+    }
+
+    #[test]
+    fn test_tree_display() {
+        let select = parse(
+            "SELECT o.id, COUNT(u.id) AS n FROM orders o LEFT JOIN users u ON u.id = o.user_id \
+             WHERE o.total > 10 AND u.id IN (SELECT id FROM vip) GROUP BY o.id ORDER BY 2 DESC LIMIT 5",
+        );
+        let tree = TreeDisplay::new(&SQLStatement::Select(Box::new(select))).to_string();
+        let expected = "\
+SELECT
+├─ o.id
+├─ AS n: COUNT()
+│  └─ u.id
+├─ FROM: orders AS o
+├─ LEFT JOIN: users AS u
+│  └─ ON: =
+│     ├─ u.id
+│     └─ o.user_id
+├─ WHERE: AND
+│  ├─ >
+│  │  ├─ o.total
+│  │  └─ 10
+│  └─ IN
+│     ├─ u.id
+│     └─ SELECT
+│        ├─ id
+│        └─ FROM: vip
+├─ GROUP BY
+│  └─ o.id
+├─ ORDER BY
+│  └─ DESC: #2
+└─ LIMIT 5
+";
+        assert_eq!(tree, expected);
+    }
+
+    #[test]
+    fn test_join_graph() {
+        let select = parse(
+            "SELECT * FROM orders o JOIN users u ON u.id = o.user_id \
+             LEFT JOIN items i ON i.order_id = o.id AND i.owner_id = u.id CROSS JOIN regions",
+        );
+        let dot = join_graph(&select);
+        assert_eq!(
+            dot,
+            "graph joins {\n  node [shape=box];\n\
+             \x20 n0 [label=\"orders AS o\"];\n\
+             \x20 n1 [label=\"users AS u\"];\n\
+             \x20 n0 -- n1 [label=\"JOIN ON u.id = o.user_id\"];\n\
+             \x20 n2 [label=\"items AS i\"];\n\
+             \x20 n0 -- n2 [label=\"LEFT JOIN ON i.order_id = o.id AND i.owner_id = u.id\"];\n\
+             \x20 n1 -- n2 [label=\"LEFT JOIN ON i.order_id = o.id AND i.owner_id = u.id\"];\n\
+             \x20 n3 [label=\"regions\"];\n\
+             \x20 n0 -- n3 [label=\"CROSS JOIN\"];\n\
+             }\n"
+        );
+    }
+}
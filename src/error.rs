@@ -0,0 +1,286 @@
+//! 错误信息目录
+//!
+//! [`ErrorKind`] 是与语言无关、可供程序判断的错误类别，这里按 [`Language`] 把它渲染为
+//! 英文或中文的描述。尚未收录的自由格式错误在两种语言下都显示英文原文。
+
+use crate::ast::common::ObjectName;
+use crate::token::{LexError, Token};
+
+/// 错误信息使用的语言
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Language {
+    #[default]
+    English,
+    Chinese,
+}
+
+/// 机器可读的错误类别，与显示语言无关
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// 期望某个语法成分，但遇到了其它token或输入结束（found 为None）
+    Expected { expected: String, found: Option<Token> },
+    /// 不支持的语句或操作，例如 construct 为 "SHOW statement"
+    Unsupported { construct: String, found: Option<Token> },
+    /// 无法解析的值，例如超出范围的整数
+    InvalidValue { what: String, value: String },
+    /// 从句出现在不允许的位置，例如 WHERE 出现在 ORDER BY 之后
+    ClauseOutOfOrder { clause: String, after: String },
+    /// 完整的语句之后还有其它token，found 为其中的第一个
+    TrailingInput { found: Token },
+    /// 词法错误，例如未闭合的字符串
+    Lex(LexError),
+    /// 其它语法错误，只有英文描述
+    Syntax,
+}
+
+impl ErrorKind {
+    /// 按指定语言生成错误描述，message 为英文原文，Syntax 类错误会用到它
+    pub fn render(&self, message: &str, language: Language) -> String {
+        match language {
+            Language::English => self.render_english(message),
+            Language::Chinese => self.render_chinese(message),
+        }
+    }
+
+    fn render_english(&self, message: &str) -> String {
+        match self {
+            ErrorKind::Expected { expected, found } => {
+                format!("Expected {}, found {}", expected, describe_token(found.as_ref(), Language::English))
+            }
+            ErrorKind::Unsupported { construct, found } => {
+                format!("Unsupported {}, found {}", construct, describe_token(found.as_ref(), Language::English))
+            }
+            ErrorKind::InvalidValue { what, value } => format!("Invalid {}: {}", what, value),
+            ErrorKind::ClauseOutOfOrder { clause, after } => {
+                format!("{} clause out of order, expected after {}", clause, after)
+            }
+            ErrorKind::TrailingInput { found } => {
+                let found = describe_token(Some(found), Language::English);
+                format!("Unexpected trailing input after statement, found {}", found)
+            }
+            ErrorKind::Lex(err) => err.to_string(),
+            ErrorKind::Syntax => message.to_string(),
+        }
+    }
+
+    fn render_chinese(&self, message: &str) -> String {
+        match self {
+            ErrorKind::Expected { expected, found } => {
+                let expected = join(&["期望".to_string(), translate(expected)]);
+                format!("{}，但遇到{}", expected, describe_token(found.as_ref(), Language::Chinese))
+            }
+            ErrorKind::Unsupported { construct, found } => {
+                let construct = join(&["不支持的".to_string(), translate(construct)]);
+                format!("{}：{}", construct, describe_token(found.as_ref(), Language::Chinese))
+            }
+            ErrorKind::InvalidValue { what, value } => {
+                format!("{}：{}", join(&["无效的".to_string(), translate(what)]), value)
+            }
+            ErrorKind::ClauseOutOfOrder { clause, after } => {
+                format!("{} 子句的位置错误，不能出现在 {} 之后", clause, after)
+            }
+            ErrorKind::TrailingInput { found } => {
+                format!("语句之后有多余的输入：{}", describe_token(Some(found), Language::Chinese))
+            }
+            ErrorKind::Lex(LexError::Unterminated { quote, line, column, .. }) => {
+                let what = if *quote == '`' { "反引号标识符" } else { "字符串" };
+                format!("未闭合的{}，开始于第 {} 行第 {} 列", what, line, column)
+            }
+            ErrorKind::Syntax => SYNTAX_MESSAGES
+                .iter()
+                .find(|(en, _)| *en == message)
+                .map_or_else(|| message.to_string(), |(_, zh)| zh.to_string()),
+        }
+    }
+}
+
+/// 描述一个token，用于 "found X" 部分，None 表示输入已经结束
+pub fn describe_token(token: Option<&Token>, language: Language) -> String {
+    let zh = language == Language::Chinese;
+    let (kind, text) = match token {
+        None => return if zh { "输入结尾" } else { "end of input" }.to_string(),
+        Some(Token::Punctuator(c)) => return format!("'{}'", c),
+        Some(Token::Keyword { keyword, .. }) => (if zh { "关键字" } else { "keyword" }, keyword.to_string()),
+        Some(Token::Identifier(id)) => (if zh { "标识符" } else { "identifier" }, id.clone()),
+        Some(Token::QuotedIdentifier(id)) => (if zh { "标识符" } else { "identifier" }, format!("`{}`", id)),
+        Some(Token::QualifiedIdentifier(parts)) => {
+            (if zh { "标识符" } else { "identifier" }, ObjectName(parts.clone()).to_string())
+        }
+        Some(Token::StringLiteral(s)) => (if zh { "字符串" } else { "string" }, format!("'{}'", s)),
+        Some(Token::NumericLiteral(n)) => (if zh { "数字" } else { "number" }, n.clone()),
+        Some(Token::Operator(op)) => (if zh { "运算符" } else { "operator" }, op.clone()),
+        Some(Token::DataType(name)) => (if zh { "类型" } else { "type" }, name.clone()),
+    };
+    format!("{} {}", kind, text)
+}
+
+// 期望内容中常见的词组，按词组长度优先匹配
+const TERMS: &[(&str, &str)] = &[
+    ("comma or closing parenthesis", "逗号或右括号"),
+    ("number of partitions", "分区数量"),
+    ("opening parenthesis", "左括号"),
+    ("closing parenthesis", "右括号"),
+    ("ALTER TABLE operation", "ALTER TABLE 操作"),
+    ("FOREIGN KEY columns", "FOREIGN KEY 列"),
+    ("table definition", "表定义"),
+    ("function argument", "函数参数"),
+    ("column list", "列列表"),
+    ("parameter list", "参数列表"),
+    ("value list", "值列表"),
+    ("data type", "数据类型"),
+    ("file path", "文件路径"),
+    ("referential action", "引用动作"),
+    ("routine body", "例程体"),
+    ("table option", "表选项"),
+    ("partition method", "分区方法"),
+    ("partition expression", "分区表达式"),
+    ("partition definitions", "分区定义"),
+    ("check constraint", "CHECK 约束"),
+    ("foreign key", "外键"),
+    ("collation", "排序规则"),
+    ("statement", "语句"),
+    ("expression", "表达式"),
+    ("condition", "条件"),
+    ("string", "字符串"),
+    ("integer", "整数"),
+    ("number", "数字"),
+    ("float", "浮点数"),
+    ("pattern", "模式"),
+    ("value", "值"),
+    ("user", "用户"),
+    ("name", "名称"),
+    ("column", "列"),
+    ("table", "表"),
+    ("database", "数据库"),
+    ("alias", "别名"),
+    ("index", "索引"),
+    ("constraint", "约束"),
+    ("parameter", "参数"),
+    ("variable", "变量"),
+    ("routine", "例程"),
+    ("partition", "分区"),
+    ("partitions", "分区"),
+    ("cursor", "游标"),
+    ("error code", "错误码"),
+    ("or", "或"),
+];
+
+// 把期望内容翻译为中文，例如 "')' after expression" -> "表达式之后的 ')'"
+// SQL 关键字和符号保持原样
+fn translate(phrase: &str) -> String {
+    if let Some((_, zh)) = TERMS.iter().find(|(en, _)| *en == phrase) {
+        return zh.to_string();
+    }
+    for (sep, template) in [(" after ", "之后的"), (" before ", "之前的"), (" in ", "中的"), (" of ", "的")] {
+        if let Some((head, tail)) = phrase.split_once(sep) {
+            let (head, tail) = (translate(head), translate(tail));
+            return if sep == " of " {
+                format!("{}{}{}", tail, template, head)
+            } else {
+                join(&[tail, template.to_string(), head])
+            };
+        }
+    }
+    let words: Vec<&str> = phrase.split(' ').collect();
+    let mut pieces = Vec::new();
+    let mut i = 0;
+    while i < words.len() {
+        // 尝试最长的词组
+        let matched = (1..=(words.len() - i).min(4)).rev().find_map(|n| {
+            let candidate = words[i..i + n].join(" ");
+            TERMS.iter().find(|(en, _)| *en == candidate).map(|(_, zh)| (n, zh.to_string()))
+        });
+        match matched {
+            Some((n, zh)) => {
+                pieces.push(zh);
+                i += n;
+            }
+            None => {
+                pieces.push(words[i].to_string());
+                i += 1;
+            }
+        }
+    }
+    // "列 名称" 之类的组合合并为 "列名"
+    if pieces.len() == 2 && pieces[1] == "名称" && !pieces[0].is_ascii() {
+        return format!("{}名", pieces[0]);
+    }
+    join(&pieces)
+}
+
+// 中文之间不加空格，与英文单词、符号之间加空格
+fn join(pieces: &[String]) -> String {
+    let mut result = String::new();
+    for piece in pieces.iter().filter(|p| !p.is_empty()) {
+        let ascii_boundary = result.chars().last().is_some_and(|c| c.is_ascii())
+            || piece.chars().next().is_some_and(|c| c.is_ascii());
+        if !result.is_empty() && ascii_boundary {
+            result.push(' ');
+        }
+        result.push_str(piece);
+    }
+    result
+}
+
+// 固定文本的语法错误
+const SYNTAX_MESSAGES: &[(&str, &str)] = &[
+    ("Expression nesting too deep", "表达式嵌套过深"),
+    ("Unexpected trailing comma in function arguments", "函数参数末尾有多余的逗号"),
+    ("Multiple INTO clauses are not allowed", "不允许出现多个 INTO 子句"),
+    ("Cannot specify multiple value sources", "不能同时指定多个值来源"),
+    ("Cannot specify columns with DEFAULT VALUES", "使用 DEFAULT VALUES 时不能指定列"),
+    ("RETURNS is only allowed for functions", "只有函数可以指定 RETURNS"),
+    ("IN/OUT/INOUT is only allowed for procedure parameters", "IN/OUT/INOUT 只能用于存储过程的参数"),
+    ("Routine body requires the original SQL", "函数体需要原始SQL，解析器应由SQL创建"),
+    ("LINEAR is only allowed for HASH or KEY partitioning", "LINEAR 只能用于 HASH 或 KEY 分区"),
+    (
+        "Only one of LOW_PRIORITY, DELAYED and HIGH_PRIORITY is allowed, and it must precede IGNORE",
+        "LOW_PRIORITY、DELAYED 和 HIGH_PRIORITY 只能指定一个，并且必须位于 IGNORE 之前",
+    ),
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::kerwords::Keyword;
+
+    #[test]
+    fn test_error_catalog() {
+        let kind = ErrorKind::Expected {
+            expected: "')' after expression".to_string(),
+            found: None,
+        };
+        assert_eq!(kind.render("", Language::English), "Expected ')' after expression, found end of input");
+        assert_eq!(kind.render("", Language::Chinese), "期望表达式之后的 ')'，但遇到输入结尾");
+
+        let kind = ErrorKind::Expected {
+            expected: "column name".to_string(),
+            found: Some(Token::Keyword { keyword: Keyword::FROM, text: "from".to_string() }),
+        };
+        assert_eq!(kind.render("", Language::Chinese), "期望列名，但遇到关键字 FROM");
+
+        let kind = ErrorKind::Expected {
+            expected: "closing parenthesis after column list".to_string(),
+            found: Some(Token::Punctuator(';')),
+        };
+        assert_eq!(kind.render("", Language::Chinese), "期望列列表之后的右括号，但遇到';'");
+
+        let kind = ErrorKind::TrailingInput { found: Token::Identifier("garbage".to_string()) };
+        assert_eq!(
+            kind.render("", Language::English),
+            "Unexpected trailing input after statement, found identifier garbage"
+        );
+        assert_eq!(kind.render("", Language::Chinese), "语句之后有多余的输入：标识符 garbage");
+
+        let kind = ErrorKind::InvalidValue { what: "number of partitions".to_string(), value: "x".to_string() };
+        assert_eq!(kind.render("", Language::Chinese), "无效的分区数量：x");
+
+        let kind = ErrorKind::Lex(LexError::Unterminated { quote: '`', span: crate::token::Span::new(7, 9), line: 1, column: 8 });
+        assert_eq!(kind.render("", Language::English), "Unterminated quoted identifier starting at line 1 col 8");
+        assert_eq!(kind.render("", Language::Chinese), "未闭合的反引号标识符，开始于第 1 行第 8 列");
+
+        // 未收录的错误保持英文原文
+        assert_eq!(ErrorKind::Syntax.render("Something odd", Language::Chinese), "Something odd");
+        assert_eq!(ErrorKind::Syntax.render("Expression nesting too deep", Language::Chinese), "表达式嵌套过深");
+    }
+}
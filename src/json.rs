@@ -0,0 +1,1211 @@
+//! AST 的 JSON 表示
+//!
+//! 输出格式是公开的、带版本号的约定，供其它语言编写的工具直接解析，与 Rust 类型的字段名和枚举名无关：
+//!
+//! - 顶层是 `{"version": 1, "statement": {...}}`，版本号见 [`SCHEMA_VERSION`]。
+//! - 语句、表达式、选择列等有多种形态的节点都是对象，`type` 字段区分形态，取值为 snake_case，
+//!   例如 `{"type": "binary_op", "op": "eq", "left": ..., "right": ...}`。
+//! - 不带数据的枚举（连接方式、锁模式等）输出为 snake_case 字符串，排序方向等开关输出为布尔值。
+//! - 可选字段缺省时输出 `null`，字段总是存在；对象的键按字母顺序输出，同一个AST的输出总是相同。
+//!
+//! 同一版本内只会增加字段或新的 `type` 取值，不会删除、改名或改变已有字段的含义；
+//! 不兼容的修改会增加版本号。`corpus/json` 中保存了每个版本已发布的输出，测试保证新的输出与之兼容。
+
+use serde_json::{json, Map, Value as Json};
+
+#[cfg(feature = "ddl")]
+use crate::ast::alter::{AlterStatement, AlterTableOperation};
+use crate::ast::common::{
+    DataType, Ident, IndexHint, IndexHintKind, IndexHintScope, JsonTableColumn, JsonTableFallback, ObjectName, Quantity,
+    QuantityUnit, SampleMethod, TableFunction, TableReference, TableSample,
+};
+#[cfg(feature = "ddl")]
+use crate::ast::create::{
+    ColumnDef, ColumnOption, ConstraintKind, CreateStatement, ForeignKeyReference, IndexDef, ParameterMode,
+    PartitionBy, PartitionMethod, PartitionValues, ReferentialAction, RoutineCharacteristic, RoutineKind,
+    SqlDataAccess, SqlSecurity, TableConstraint, ViewAlgorithm, ViewCheckOption,
+};
+use crate::ast::delete::DeleteStatement;
+use crate::ast::expr::{
+    BinaryOperator, Expr, FrameBound, FrameUnits, GroupConcat, LimitClause, LogicalOperator, OrderByExpr, SearchMode,
+    SortKey, UnaryOperator, Value, WindowFrame, WindowSpec,
+};
+use crate::ast::insert::InsertStatement;
+use crate::ast::select::{
+    FieldsOptions, IntoClause, Join, JoinKind, LinesOptions, LockClause, LockMode, LockWait, SelectColumn,
+    SelectStatement, SetOperation, Top, With,
+};
+use crate::ast::set::{SetAssignment, SetStatement};
+use crate::ast::show::{ShowFilter, ShowStatement, ShowTarget, VariableScope};
+use crate::ast::update::UpdateStatement;
+use crate::ast::SQLStatement;
+#[cfg(feature = "ddl")]
+use crate::token::Token;
+
+/// 当前输出格式的版本号
+pub const SCHEMA_VERSION: u64 = 1;
+
+/// 可以输出为 JSON 的AST节点
+pub trait ToJson {
+    /// 节点本身的 JSON 表示，不含版本号
+    fn to_json(&self) -> Json;
+}
+
+/// 带版本号的完整输出：`{"version": 1, "statement": ...}`
+pub fn to_value<T: ToJson + ?Sized>(node: &T) -> Json {
+    object([("version", json!(SCHEMA_VERSION)), ("statement", node.to_json())])
+}
+
+/// [`to_value`] 的紧凑文本形式
+pub fn to_string<T: ToJson + ?Sized>(node: &T) -> String {
+    to_value(node).to_string()
+}
+
+/// [`to_value`] 的缩进文本形式
+pub fn to_string_pretty<T: ToJson + ?Sized>(node: &T) -> String {
+    serde_json::to_string_pretty(&to_value(node)).expect("JSON values always serialize")
+}
+
+// 由字段构造对象。子节点已经是 JSON 值，直接移入而不经过 json! 宏，
+// 否则 json! 会对子节点重新序列化，深层嵌套的表达式会耗尽栈
+fn object<const N: usize>(fields: [(&str, Json); N]) -> Json {
+    Json::Object(
+        fields
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect(),
+    )
+}
+
+// 带 type 标签的对象
+fn tagged<const N: usize>(tag: &str, fields: [(&str, Json); N]) -> Json {
+    let mut object = Map::new();
+    object.insert("type".to_string(), Json::from(tag));
+    object.extend(fields.into_iter().map(|(key, value)| (key.to_string(), value)));
+    Json::Object(object)
+}
+
+fn list<T: ToJson>(items: &[T]) -> Json {
+    Json::Array(items.iter().map(ToJson::to_json).collect())
+}
+
+fn optional<T: ToJson>(item: Option<&T>) -> Json {
+    item.map_or(Json::Null, ToJson::to_json)
+}
+
+// column 只是列名本身，name 包含限定符和引号
+fn assignments(pairs: &[(ObjectName, Expr)]) -> Json {
+    Json::Array(
+        pairs
+            .iter()
+            .map(|(column, value)| {
+                object([
+                    ("column", json!(&*column.name().value)),
+                    ("name", column.to_json()),
+                    ("value", value.to_json()),
+                ])
+            })
+            .collect(),
+    )
+}
+
+impl ToJson for SQLStatement {
+    fn to_json(&self) -> Json {
+        match self {
+            SQLStatement::Select(select) => select.to_json(),
+            SQLStatement::Insert(insert) => insert.to_json(),
+            SQLStatement::Update(update) => update.to_json(),
+            SQLStatement::Delete(delete) => delete.to_json(),
+            #[cfg(feature = "ddl")]
+            SQLStatement::Create(create) => create.to_json(),
+            #[cfg(feature = "ddl")]
+            SQLStatement::Alter(alter) => alter.to_json(),
+            SQLStatement::Show(show) => show.to_json(),
+            SQLStatement::Set(set) => set.to_json(),
+        }
+    }
+}
+
+impl ToJson for Ident {
+    fn to_json(&self) -> Json {
+        object([
+            ("value", json!(&*self.value)),
+            ("quote", json!(self.quote_style.map(String::from))),
+        ])
+    }
+}
+
+impl ToJson for ObjectName {
+    fn to_json(&self) -> Json {
+        list(&self.0)
+    }
+}
+
+impl ToJson for Value {
+    fn to_json(&self) -> Json {
+        match self {
+            Value::String(s) => tagged("string", [("value", json!(s))]),
+            Value::Integer(i) => tagged("integer", [("value", json!(i))]),
+            Value::Float(f) => tagged("float", [("value", json!(f))]),
+            // 保留原文，避免精度损失
+            Value::Decimal(d) => tagged("decimal", [("value", json!(d))]),
+            Value::Boolean(b) => tagged("boolean", [("value", json!(b))]),
+            Value::Null => tagged("null", []),
+            Value::DEFAULT => tagged("default", []),
+        }
+    }
+}
+
+impl ToJson for Expr {
+    // 后序折叠，深层嵌套的表达式不会耗尽栈
+    fn to_json(&self) -> Json {
+        self.fold(|expr, children: Vec<Json>| {
+            let mut children = children.into_iter();
+            let mut next = move || children.next().expect("fold yields one result per child");
+            match expr {
+                Expr::Identifier(name) => tagged("identifier", [("name", name.to_json())]),
+                Expr::Wildcard => tagged("wildcard", []),
+                Expr::Literal(value) => tagged("literal", [("value", value.to_json())]),
+                Expr::BinaryOp { op, .. } => {
+                    let left = next();
+                    tagged(
+                        "binary_op",
+                        [("op", json!(binary_operator(op))), ("left", left), ("right", next())],
+                    )
+                }
+                Expr::In { list, negated, .. } => {
+                    let expr = next();
+                    let list = list.iter().map(|_| next()).collect();
+                    tagged(
+                        "in",
+                        [("expr", expr), ("list", Json::Array(list)), ("negated", json!(negated))],
+                    )
+                }
+                Expr::Between { negated, .. } => {
+                    let (expr, low) = (next(), next());
+                    tagged(
+                        "between",
+                        [
+                            ("expr", expr),
+                            ("low", low),
+                            ("high", next()),
+                            ("negated", json!(negated)),
+                        ],
+                    )
+                }
+                Expr::IsNull { negated, .. } => tagged("is_null", [("expr", next()), ("negated", json!(negated))]),
+                Expr::FunctionCall { name, args } => {
+                    let args = args.iter().map(|_| next()).collect();
+                    tagged(
+                        "function_call",
+                        [
+                            ("name", json!(name)),
+                            ("args", Json::Array(args)),
+                            ("aggregate", json!(false)),
+                            ("distinct", json!(false)),
+                        ],
+                    )
+                }
+                // 与普通函数调用共用 function_call，COUNT(*) 的参数是 wildcard
+                Expr::Aggregate { func, distinct, args } => {
+                    let args = args.iter().map(|_| next()).collect();
+                    tagged(
+                        "function_call",
+                        [
+                            ("name", json!(func.name())),
+                            ("args", Json::Array(args)),
+                            ("aggregate", json!(true)),
+                            ("distinct", json!(distinct)),
+                        ],
+                    )
+                }
+                Expr::GroupConcat(group_concat) => {
+                    let GroupConcat { distinct, args, order_by, separator } = &**group_concat;
+                    let args = args.iter().map(|_| next()).collect();
+                    let order_by = order_by
+                        .iter()
+                        .map(|item| {
+                            let key = match &item.expr {
+                                SortKey::Expr(_) => tagged("expr", [("expr", next())]),
+                                SortKey::Ordinal(n) => tagged("ordinal", [("ordinal", json!(n))]),
+                            };
+                            object([("key", key), ("asc", json!(item.asc))])
+                        })
+                        .collect();
+                    tagged(
+                        "group_concat",
+                        [
+                            ("distinct", json!(distinct)),
+                            ("args", Json::Array(args)),
+                            ("order_by", Json::Array(order_by)),
+                            ("separator", json!(separator)),
+                        ],
+                    )
+                }
+                Expr::LogicalOp { op, expressions } => {
+                    let operands = expressions.iter().map(|_| next()).collect();
+                    tagged(
+                        "logical_op",
+                        [("op", json!(logical_operator(op))), ("operands", Json::Array(operands))],
+                    )
+                }
+                Expr::UnaryOp { op, .. } => {
+                    let op = match op {
+                        UnaryOperator::Plus => "plus",
+                        UnaryOperator::Minus => "minus",
+                    };
+                    tagged("unary_op", [("op", json!(op)), ("expr", next())])
+                }
+                Expr::Introducer { charset, .. } => {
+                    tagged("introducer", [("charset", json!(charset)), ("expr", next())])
+                }
+                Expr::Collate { collation, .. } => {
+                    tagged("collate", [("expr", next()), ("collation", json!(collation))])
+                }
+                Expr::Cast { data_type, .. } => {
+                    tagged("cast", [("expr", next()), ("data_type", data_type.to_json())])
+                }
+                Expr::Interval { unit, .. } => tagged(
+                    "interval",
+                    [("value", next()), ("unit", json!(unit.name().to_lowercase()))],
+                ),
+                Expr::Match { columns, mode, .. } => {
+                    let mode = match mode {
+                        SearchMode::NaturalLanguage => "natural_language",
+                        SearchMode::NaturalLanguageWithQueryExpansion => "natural_language_with_query_expansion",
+                        SearchMode::Boolean => "boolean",
+                        SearchMode::QueryExpansion => "query_expansion",
+                    };
+                    tagged(
+                        "match",
+                        [("columns", list(columns)), ("against", next()), ("mode", json!(mode))],
+                    )
+                }
+                Expr::Assignment { variable, .. } => {
+                    tagged("assignment", [("variable", json!(variable)), ("value", next())])
+                }
+                Expr::Subquery(select) => tagged("subquery", [("query", select.to_json())]),
+                Expr::Window { over, .. } => {
+                    let func = next();
+                    let partition_by = over.partition_by.iter().map(|_| next()).collect();
+                    let order_by = over
+                        .order_by
+                        .iter()
+                        .map(|item| {
+                            let key = match &item.expr {
+                                SortKey::Expr(_) => tagged("expr", [("expr", next())]),
+                                SortKey::Ordinal(n) => tagged("ordinal", [("ordinal", json!(n))]),
+                            };
+                            object([("key", key), ("asc", json!(item.asc))])
+                        })
+                        .collect();
+                    tagged("window", [("func", func), ("over", window_spec(over, partition_by, order_by))])
+                }
+                Expr::Array(items) => {
+                    let items = items.iter().map(|_| next()).collect();
+                    tagged("array", [("items", Json::Array(items))])
+                }
+                Expr::Subscript { .. } => {
+                    let expr = next();
+                    tagged("subscript", [("expr", expr), ("index", next())])
+                }
+                Expr::Any { op, .. } => {
+                    let expr = next();
+                    tagged("any", [("op", json!(binary_operator(op))), ("expr", expr), ("array", next())])
+                }
+                Expr::Case { operand, conditions, else_result, .. } => {
+                    let operand = operand.as_ref().map_or(Json::Null, |_| next());
+                    let branches = conditions
+                        .iter()
+                        .map(|_| {
+                            let condition = next();
+                            object([("when", condition), ("then", next())])
+                        })
+                        .collect();
+                    let else_result = else_result.as_ref().map_or(Json::Null, |_| next());
+                    tagged(
+                        "case",
+                        [("operand", operand), ("branches", Json::Array(branches)), ("else", else_result)],
+                    )
+                }
+                Expr::Exists(select) => tagged("exists", [("query", select.to_json())]),
+                Expr::Extract { unit, .. } => tagged(
+                    "extract",
+                    [("unit", json!(unit.name().to_lowercase())), ("expr", next())],
+                ),
+            }
+        })
+    }
+}
+
+// 子表达式已经转换好，由调用者传入
+fn window_spec(spec: &WindowSpec, partition_by: Vec<Json>, order_by: Vec<Json>) -> Json {
+    object([
+        ("name", json!(spec.name)),
+        ("partition_by", Json::Array(partition_by)),
+        ("order_by", Json::Array(order_by)),
+        ("frame", optional(spec.frame.as_ref())),
+    ])
+}
+
+impl ToJson for WindowSpec {
+    fn to_json(&self) -> Json {
+        let partition_by = self.partition_by.iter().map(ToJson::to_json).collect();
+        let order_by = self.order_by.iter().map(ToJson::to_json).collect();
+        window_spec(self, partition_by, order_by)
+    }
+}
+
+impl ToJson for WindowFrame {
+    fn to_json(&self) -> Json {
+        let units = match self.units {
+            FrameUnits::Rows => "rows",
+            FrameUnits::Range => "range",
+        };
+        object([("units", json!(units)), ("start", self.start.to_json()), ("end", optional(self.end.as_ref()))])
+    }
+}
+
+impl ToJson for FrameBound {
+    fn to_json(&self) -> Json {
+        match self {
+            FrameBound::UnboundedPreceding => tagged("unbounded_preceding", []),
+            FrameBound::Preceding(offset) => tagged("preceding", [("offset", offset.to_json())]),
+            FrameBound::CurrentRow => tagged("current_row", []),
+            FrameBound::Following(offset) => tagged("following", [("offset", offset.to_json())]),
+            FrameBound::UnboundedFollowing => tagged("unbounded_following", []),
+        }
+    }
+}
+
+fn binary_operator(op: &BinaryOperator) -> &'static str {
+    match op {
+        BinaryOperator::Eq => "eq",
+        BinaryOperator::NotEq => "not_eq",
+        BinaryOperator::Lt => "lt",
+        BinaryOperator::LtEq => "lt_eq",
+        BinaryOperator::Gt => "gt",
+        BinaryOperator::GtEq => "gt_eq",
+        BinaryOperator::Plus => "plus",
+        BinaryOperator::Minus => "minus",
+        BinaryOperator::Multiply => "multiply",
+        BinaryOperator::Divide => "divide",
+        BinaryOperator::Like => "like",
+    }
+}
+
+fn logical_operator(op: &LogicalOperator) -> &'static str {
+    match op {
+        LogicalOperator::And => "and",
+        LogicalOperator::Or => "or",
+        LogicalOperator::Not => "not",
+    }
+}
+
+impl ToJson for SortKey {
+    fn to_json(&self) -> Json {
+        match self {
+            SortKey::Expr(expr) => tagged("expr", [("expr", expr.to_json())]),
+            SortKey::Ordinal(n) => tagged("ordinal", [("ordinal", json!(n))]),
+        }
+    }
+}
+
+impl ToJson for OrderByExpr {
+    fn to_json(&self) -> Json {
+        object([("key", self.expr.to_json()), ("asc", json!(self.asc))])
+    }
+}
+
+impl ToJson for LimitClause {
+    fn to_json(&self) -> Json {
+        object([("limit", json!(self.limit)), ("offset", json!(self.offset))])
+    }
+}
+
+impl ToJson for Quantity {
+    fn to_json(&self) -> Json {
+        let unit = match self.unit {
+            QuantityUnit::Rows => "rows",
+            QuantityUnit::Percent => "percent",
+        };
+        object([("value", self.value.to_json()), ("unit", json!(unit))])
+    }
+}
+
+impl ToJson for TableSample {
+    fn to_json(&self) -> Json {
+        let method = match self.method {
+            SampleMethod::System => "system",
+            SampleMethod::Bernoulli => "bernoulli",
+        };
+        object([
+            ("method", json!(method)),
+            ("size", self.size.to_json()),
+            ("seed", optional(self.seed.as_ref())),
+        ])
+    }
+}
+
+impl ToJson for IndexHint {
+    fn to_json(&self) -> Json {
+        let kind = match self.kind {
+            IndexHintKind::Use => "use",
+            IndexHintKind::Force => "force",
+            IndexHintKind::Ignore => "ignore",
+        };
+        let scope = self.scope.map(|scope| match scope {
+            IndexHintScope::Join => "join",
+            IndexHintScope::OrderBy => "order_by",
+            IndexHintScope::GroupBy => "group_by",
+        });
+        object([
+            ("kind", json!(kind)),
+            ("scope", json!(scope)),
+            ("indexes", json!(self.indexes)),
+        ])
+    }
+}
+
+impl ToJson for TableReference {
+    fn to_json(&self) -> Json {
+        object([
+            ("name", self.name.to_json()),
+            ("partitions", json!(self.partitions)),
+            ("alias", json!(self.alias)),
+            ("index_hints", list(&self.index_hints)),
+            ("sample", optional(self.sample.as_ref())),
+            ("subquery", optional(self.subquery.as_deref())),
+            ("function", optional(self.function.as_deref())),
+            ("lateral", json!(self.lateral)),
+        ])
+    }
+}
+
+impl ToJson for TableFunction {
+    fn to_json(&self) -> Json {
+        match self {
+            TableFunction::JsonTable { expr, path, columns } => tagged(
+                "json_table",
+                [("expr", expr.to_json()), ("path", json!(path)), ("columns", list(columns))],
+            ),
+            TableFunction::Call { name, args, with_ordinality, column_aliases } => tagged(
+                "call",
+                [
+                    ("name", json!(name)),
+                    ("args", list(args)),
+                    ("with_ordinality", json!(with_ordinality)),
+                    ("column_aliases", json!(column_aliases)),
+                ],
+            ),
+        }
+    }
+}
+
+impl ToJson for JsonTableColumn {
+    fn to_json(&self) -> Json {
+        match self {
+            JsonTableColumn::Ordinality(name) => tagged("ordinality", [("name", json!(name))]),
+            JsonTableColumn::Path { name, data_type, exists, path, on_empty, on_error } => tagged(
+                "path",
+                [
+                    ("name", json!(name)),
+                    ("data_type", data_type.to_json()),
+                    ("exists", json!(exists)),
+                    ("path", json!(path)),
+                    ("on_empty", optional(on_empty.as_ref())),
+                    ("on_error", optional(on_error.as_ref())),
+                ],
+            ),
+            JsonTableColumn::Nested { path, columns } => {
+                tagged("nested", [("path", json!(path)), ("columns", list(columns))])
+            }
+        }
+    }
+}
+
+impl ToJson for JsonTableFallback {
+    fn to_json(&self) -> Json {
+        match self {
+            JsonTableFallback::Null => tagged("null", []),
+            JsonTableFallback::Default(value) => tagged("default", [("value", json!(value))]),
+            JsonTableFallback::Error => tagged("error", []),
+        }
+    }
+}
+
+impl ToJson for SelectColumn {
+    fn to_json(&self) -> Json {
+        match self {
+            SelectColumn::Wildcard => tagged("wildcard", []),
+            SelectColumn::QualifiedWildcard(table) => tagged("qualified_wildcard", [("table", table.to_json())]),
+            SelectColumn::Column { name, alias } => {
+                tagged("column", [("name", name.to_json()), ("alias", json!(alias))])
+            }
+            SelectColumn::Expression { expr, alias } => {
+                tagged("expression", [("expr", expr.to_json()), ("alias", json!(alias))])
+            }
+        }
+    }
+}
+
+impl ToJson for Join {
+    fn to_json(&self) -> Json {
+        let kind = match self.kind {
+            JoinKind::Inner => "inner",
+            JoinKind::Cross => "cross",
+            JoinKind::Left => "left",
+            JoinKind::Right => "right",
+            JoinKind::Straight => "straight",
+            JoinKind::Comma => "comma",
+        };
+        object([
+            ("kind", json!(kind)),
+            ("table", self.table.to_json()),
+            ("on", optional(self.on.as_ref())),
+            ("using", json!(self.using)),
+            ("natural", json!(self.natural)),
+        ])
+    }
+}
+
+impl ToJson for Top {
+    fn to_json(&self) -> Json {
+        object([
+            ("quantity", self.quantity.to_json()),
+            ("with_ties", json!(self.with_ties)),
+        ])
+    }
+}
+
+impl ToJson for FieldsOptions {
+    fn to_json(&self) -> Json {
+        object([
+            ("terminated_by", json!(self.terminated_by)),
+            ("enclosed_by", json!(self.enclosed_by)),
+            ("optionally_enclosed", json!(self.optionally_enclosed)),
+            ("escaped_by", json!(self.escaped_by)),
+        ])
+    }
+}
+
+impl ToJson for LinesOptions {
+    fn to_json(&self) -> Json {
+        object([
+            ("starting_by", json!(self.starting_by)),
+            ("terminated_by", json!(self.terminated_by)),
+        ])
+    }
+}
+
+impl ToJson for IntoClause {
+    fn to_json(&self) -> Json {
+        match self {
+            IntoClause::Outfile {
+                path,
+                charset,
+                fields,
+                lines,
+            } => tagged(
+                "outfile",
+                [
+                    ("path", json!(path)),
+                    ("charset", json!(charset)),
+                    ("fields", optional(fields.as_ref())),
+                    ("lines", optional(lines.as_ref())),
+                ],
+            ),
+            IntoClause::Dumpfile(path) => tagged("dumpfile", [("path", json!(path))]),
+            IntoClause::Variables(names) => tagged("variables", [("names", json!(names))]),
+        }
+    }
+}
+
+impl ToJson for LockClause {
+    fn to_json(&self) -> Json {
+        let mode = match self.mode {
+            LockMode::Update => "update",
+            LockMode::Share => "share",
+        };
+        let wait = match self.wait {
+            LockWait::Wait => "wait",
+            LockWait::Nowait => "nowait",
+            LockWait::SkipLocked => "skip_locked",
+        };
+        object([("mode", json!(mode)), ("wait", json!(wait))])
+    }
+}
+
+impl ToJson for With {
+    fn to_json(&self) -> Json {
+        let ctes = self
+            .ctes
+            .iter()
+            .map(|cte| {
+                object([
+                    ("name", json!(cte.name)),
+                    ("columns", json!(cte.columns)),
+                    ("query", cte.query.to_json()),
+                ])
+            })
+            .collect();
+        object([("recursive", json!(self.recursive)), ("ctes", Json::Array(ctes))])
+    }
+}
+
+impl ToJson for SetOperation {
+    fn to_json(&self) -> Json {
+        object([
+            ("op", json!(self.op.name().to_ascii_lowercase())),
+            ("all", json!(self.all)),
+            ("query", self.select.to_json()),
+        ])
+    }
+}
+
+impl ToJson for SelectStatement {
+    fn to_json(&self) -> Json {
+        let m = &self.modifiers;
+        tagged(
+            "select",
+            [
+                ("with", optional(self.with.as_ref())),
+                ("distinct", json!(self.distinct)),
+                ("distinct_on", self.distinct_on.as_deref().map_or(Json::Null, list)),
+                (
+                    "modifiers",
+                    object([
+                        ("high_priority", json!(m.high_priority)),
+                        ("straight_join", json!(m.straight_join)),
+                        ("sql_small_result", json!(m.sql_small_result)),
+                        ("sql_big_result", json!(m.sql_big_result)),
+                        ("sql_buffer_result", json!(m.sql_buffer_result)),
+                        ("sql_no_cache", json!(m.sql_no_cache)),
+                        ("sql_calc_found_rows", json!(m.sql_calc_found_rows)),
+                    ]),
+                ),
+                ("top", optional(self.top.as_ref())),
+                ("columns", list(&self.columns)),
+                ("from", optional(self.from.as_ref())),
+                ("joins", list(&self.joins)),
+                ("where", optional(self.where_clause.as_ref())),
+                ("group_by", self.group_by.as_deref().map_or(Json::Null, list)),
+                ("with_rollup", json!(self.with_rollup)),
+                ("having", optional(self.having.as_ref())),
+                ("qualify", optional(self.qualify.as_ref())),
+                (
+                    "windows",
+                    Json::Array(
+                        self.windows
+                            .iter()
+                            .map(|(name, spec)| object([("name", json!(name)), ("spec", spec.to_json())]))
+                            .collect(),
+                    ),
+                ),
+                ("set_operations", list(&self.set_operations)),
+                ("order_by", self.order_by.as_deref().map_or(Json::Null, list)),
+                ("limit", optional(self.limit.as_ref())),
+                ("into", optional(self.into.as_ref())),
+                ("lock", optional(self.lock.as_ref())),
+            ],
+        )
+    }
+}
+
+impl ToJson for InsertStatement {
+    fn to_json(&self) -> Json {
+        let m = &self.modifiers;
+        let values = self
+            .values
+            .as_ref()
+            .map_or(Json::Null, |rows| rows.iter().map(|row| list(row)).collect());
+        tagged(
+            "insert",
+            [
+                (
+                    "modifiers",
+                    object([
+                        ("low_priority", json!(m.low_priority)),
+                        ("delayed", json!(m.delayed)),
+                        ("high_priority", json!(m.high_priority)),
+                        ("ignore", json!(m.ignore)),
+                    ]),
+                ),
+                ("table", self.table.to_json()),
+                ("columns", json!(self.columns)),
+                ("values", values),
+                ("select", optional(self.select_clause.as_ref())),
+                ("set", self.set_clause.as_deref().map_or(Json::Null, assignments)),
+                (
+                    "row_alias",
+                    self.row_alias.as_ref().map_or(Json::Null, |alias| {
+                        object([("name", json!(alias.name)), ("columns", json!(alias.columns))])
+                    }),
+                ),
+                (
+                    "on_duplicate",
+                    self.on_duplicate
+                        .as_ref()
+                        .map_or(Json::Null, |clause| assignments(&clause.updates)),
+                ),
+                ("default_values", json!(self.is_default_values)),
+            ],
+        )
+    }
+}
+
+impl ToJson for UpdateStatement {
+    fn to_json(&self) -> Json {
+        tagged(
+            "update",
+            [
+                (
+                    "modifiers",
+                    object([
+                        ("low_priority", json!(self.modifiers.low_priority)),
+                        ("ignore", json!(self.modifiers.ignore)),
+                    ]),
+                ),
+                ("table", self.table.to_json()),
+                ("assignments", assignments(&self.assignments)),
+                ("where", optional(self.where_clause.as_ref())),
+                ("order_by", self.order_by.as_deref().map_or(Json::Null, list)),
+                ("limit", optional(self.limit.as_ref())),
+            ],
+        )
+    }
+}
+
+impl ToJson for DeleteStatement {
+    fn to_json(&self) -> Json {
+        let m = &self.modifiers;
+        tagged(
+            "delete",
+            [
+                (
+                    "modifiers",
+                    object([
+                        ("low_priority", json!(m.low_priority)),
+                        ("quick", json!(m.quick)),
+                        ("ignore", json!(m.ignore)),
+                    ]),
+                ),
+                ("table", self.table.to_json()),
+                ("where", optional(self.where_clause.as_ref())),
+                ("order_by", self.order_by.as_deref().map_or(Json::Null, list)),
+                ("limit", optional(self.limit.as_ref())),
+            ],
+        )
+    }
+}
+
+impl ToJson for ShowFilter {
+    fn to_json(&self) -> Json {
+        match self {
+            ShowFilter::Like(pattern) => tagged("like", [("pattern", json!(pattern))]),
+            ShowFilter::Where(expr) => tagged("where", [("expr", expr.to_json())]),
+        }
+    }
+}
+
+// SHOW 和 SET 中的变量作用域
+fn scope(scope: &Option<VariableScope>) -> Json {
+    json!(scope.map(|scope| match scope {
+        VariableScope::Global => "global",
+        VariableScope::Session => "session",
+    }))
+}
+
+impl ToJson for ShowStatement {
+    fn to_json(&self) -> Json {
+        let target = match &self.target {
+            ShowTarget::CreateTable(name) => tagged("create_table", [("table", name.to_json())]),
+            ShowTarget::Variables { scope: s, filter } => tagged(
+                "variables",
+                [("scope", scope(s)), ("filter", optional(filter.as_ref()))],
+            ),
+            ShowTarget::Status { scope: s, filter } => {
+                tagged("status", [("scope", scope(s)), ("filter", optional(filter.as_ref()))])
+            }
+            ShowTarget::ProcessList { full } => tagged("process_list", [("full", json!(full))]),
+            ShowTarget::Index {
+                table,
+                database,
+                filter,
+            } => tagged(
+                "index",
+                [
+                    ("table", table.to_json()),
+                    ("database", json!(database)),
+                    ("filter", optional(filter.as_ref())),
+                ],
+            ),
+            ShowTarget::Tables { full, database, filter } => tagged(
+                "tables",
+                [
+                    ("full", json!(full)),
+                    ("database", json!(database)),
+                    ("filter", optional(filter.as_ref())),
+                ],
+            ),
+            ShowTarget::Databases { filter } => tagged("databases", [("filter", optional(filter.as_ref()))]),
+        };
+        tagged("show", [("target", target)])
+    }
+}
+
+impl ToJson for SetStatement {
+    fn to_json(&self) -> Json {
+        tagged("set", [("assignments", list(&self.assignments))])
+    }
+}
+
+impl ToJson for SetAssignment {
+    fn to_json(&self) -> Json {
+        object([
+            ("scope", scope(&self.scope)),
+            ("variable", json!(self.variable)),
+            ("value", self.value.to_json()),
+        ])
+    }
+}
+
+impl ToJson for DataType {
+    fn to_json(&self) -> Json {
+        object([
+            ("name", json!(self.name)),
+            ("length", json!(self.length)),
+            ("values", json!(self.values)),
+            ("unsigned", json!(self.unsigned)),
+        ])
+    }
+}
+
+#[cfg(feature = "ddl")]
+impl ToJson for Token {
+    fn to_json(&self) -> Json {
+        match self {
+            Token::Keyword { text, .. } => tagged("keyword", [("text", json!(text))]),
+            Token::Identifier(text) => tagged("identifier", [("text", json!(text))]),
+            Token::QuotedIdentifier(text) => tagged("quoted_identifier", [("text", json!(text))]),
+            Token::StringLiteral(text) => tagged("string", [("text", json!(text))]),
+            Token::NumericLiteral(text) => tagged("number", [("text", json!(text))]),
+            Token::Operator(text) => tagged("operator", [("text", json!(text))]),
+            Token::Punctuator(c) => tagged("punctuator", [("text", json!(c.to_string()))]),
+            Token::DataType(name) => tagged("data_type", [("text", json!(name))]),
+            Token::QualifiedIdentifier(parts) => tagged("qualified_identifier", [("parts", list(parts))]),
+        }
+    }
+}
+
+#[cfg(feature = "ddl")]
+impl ToJson for ForeignKeyReference {
+    fn to_json(&self) -> Json {
+        fn action(action: Option<ReferentialAction>) -> Json {
+            json!(action.map(|action| match action {
+                ReferentialAction::Restrict => "restrict",
+                ReferentialAction::Cascade => "cascade",
+                ReferentialAction::SetNull => "set_null",
+                ReferentialAction::NoAction => "no_action",
+                ReferentialAction::SetDefault => "set_default",
+            }))
+        }
+        object([
+            ("table", self.table.to_json()),
+            ("columns", json!(self.columns)),
+            ("on_delete", action(self.on_delete)),
+            ("on_update", action(self.on_update)),
+        ])
+    }
+}
+
+#[cfg(feature = "ddl")]
+impl ToJson for ColumnOption {
+    fn to_json(&self) -> Json {
+        match self {
+            ColumnOption::Null => tagged("null", []),
+            ColumnOption::NotNull => tagged("not_null", []),
+            ColumnOption::Default(expr) => tagged("default", [("expr", expr.to_json())]),
+            ColumnOption::OnUpdate(expr) => tagged("on_update", [("expr", expr.to_json())]),
+            ColumnOption::AutoIncrement => tagged("auto_increment", []),
+            ColumnOption::PrimaryKey => tagged("primary_key", []),
+            ColumnOption::Unique => tagged("unique", []),
+            ColumnOption::Comment(text) => tagged("comment", [("text", json!(text))]),
+            ColumnOption::CharacterSet(charset) => tagged("character_set", [("charset", json!(charset))]),
+            ColumnOption::Collate(collation) => tagged("collate", [("collation", json!(collation))]),
+            ColumnOption::Check { name, expr } => tagged("check", [("name", json!(name)), ("expr", expr.to_json())]),
+            ColumnOption::References(reference) => tagged("references", [("reference", reference.to_json())]),
+        }
+    }
+}
+
+#[cfg(feature = "ddl")]
+impl ToJson for ColumnDef {
+    fn to_json(&self) -> Json {
+        object([
+            ("name", json!(self.name)),
+            ("data_type", self.data_type.to_json()),
+            ("options", list(&self.options)),
+        ])
+    }
+}
+
+#[cfg(feature = "ddl")]
+impl ToJson for TableConstraint {
+    fn to_json(&self) -> Json {
+        let kind = match &self.kind {
+            ConstraintKind::PrimaryKey { columns } => tagged("primary_key", [("columns", json!(columns))]),
+            ConstraintKind::Unique { index_name, columns } => tagged(
+                "unique",
+                [("index_name", json!(index_name)), ("columns", json!(columns))],
+            ),
+            ConstraintKind::ForeignKey { columns, reference } => tagged(
+                "foreign_key",
+                [("columns", json!(columns)), ("reference", reference.to_json())],
+            ),
+            ConstraintKind::Check { expr, enforced } => {
+                tagged("check", [("expr", expr.to_json()), ("enforced", json!(enforced))])
+            }
+        };
+        object([("name", json!(self.name)), ("kind", kind)])
+    }
+}
+
+#[cfg(feature = "ddl")]
+impl ToJson for IndexDef {
+    fn to_json(&self) -> Json {
+        object([("name", json!(self.name)), ("columns", json!(self.columns))])
+    }
+}
+
+#[cfg(feature = "ddl")]
+impl ToJson for PartitionBy {
+    fn to_json(&self) -> Json {
+        let method = match self.method {
+            PartitionMethod::Range => "range",
+            PartitionMethod::RangeColumns => "range_columns",
+            PartitionMethod::List => "list",
+            PartitionMethod::ListColumns => "list_columns",
+            PartitionMethod::Hash => "hash",
+            PartitionMethod::Key => "key",
+        };
+        let partitions = self
+            .partitions
+            .iter()
+            .map(|partition| {
+                let values = partition.values.as_ref().map_or(Json::Null, |values| match values {
+                    PartitionValues::LessThan(exprs) => tagged("less_than", [("exprs", list(exprs))]),
+                    PartitionValues::In(exprs) => tagged("in", [("exprs", list(exprs))]),
+                });
+                object([("name", json!(partition.name)), ("values", values)])
+            })
+            .collect();
+        object([
+            ("method", json!(method)),
+            ("linear", json!(self.linear)),
+            ("expr", optional(self.expr.as_ref())),
+            ("columns", json!(self.columns)),
+            ("count", json!(self.count)),
+            ("partitions", partitions),
+        ])
+    }
+}
+
+#[cfg(feature = "ddl")]
+impl ToJson for CreateStatement {
+    fn to_json(&self) -> Json {
+        match self {
+            CreateStatement::Routine(routine) => {
+                let kind = match routine.kind {
+                    RoutineKind::Procedure => "procedure",
+                    RoutineKind::Function => "function",
+                };
+                let params = routine
+                    .params
+                    .iter()
+                    .map(|param| {
+                        let mode = param.mode.map(|mode| match mode {
+                            ParameterMode::In => "in",
+                            ParameterMode::Out => "out",
+                            ParameterMode::InOut => "inout",
+                        });
+                        object([
+                            ("mode", json!(mode)),
+                            ("name", json!(param.name)),
+                            ("data_type", param.data_type.to_json()),
+                        ])
+                    })
+                    .collect();
+                let characteristics = routine
+                    .characteristics
+                    .iter()
+                    .map(|characteristic| match characteristic {
+                        RoutineCharacteristic::Comment(text) => tagged("comment", [("text", json!(text))]),
+                        RoutineCharacteristic::LanguageSql => tagged("language_sql", []),
+                        RoutineCharacteristic::Deterministic(b) => tagged("deterministic", [("value", json!(b))]),
+                        RoutineCharacteristic::DataAccess(access) => {
+                            let access = match access {
+                                SqlDataAccess::ContainsSql => "contains_sql",
+                                SqlDataAccess::NoSql => "no_sql",
+                                SqlDataAccess::ReadsSqlData => "reads_sql_data",
+                                SqlDataAccess::ModifiesSqlData => "modifies_sql_data",
+                            };
+                            tagged("data_access", [("value", json!(access))])
+                        }
+                        RoutineCharacteristic::SqlSecurity(security) => {
+                            tagged("sql_security", [("value", json!(sql_security(*security)))])
+                        }
+                    })
+                    .collect();
+                tagged(
+                    "create_routine",
+                    [
+                        ("kind", json!(kind)),
+                        ("definer", json!(routine.definer)),
+                        ("if_not_exists", json!(routine.if_not_exists)),
+                        ("name", routine.name.to_json()),
+                        ("params", params),
+                        ("returns", optional(routine.returns.as_ref())),
+                        ("characteristics", characteristics),
+                        ("body", list(&routine.body)),
+                        ("body_sql", json!(routine.body_sql)),
+                    ],
+                )
+            }
+            CreateStatement::Table(table) => {
+                let options = table
+                    .options
+                    .iter()
+                    .map(|option| object([("name", json!(option.name)), ("value", json!(option.value))]))
+                    .collect();
+                tagged(
+                    "create_table",
+                    [
+                        ("if_not_exists", json!(table.if_not_exists)),
+                        ("name", table.name.to_json()),
+                        ("columns", list(&table.columns)),
+                        ("constraints", list(&table.constraints)),
+                        ("indexes", list(&table.indexes)),
+                        ("options", options),
+                        ("partition_by", optional(table.partition_by.as_ref())),
+                    ],
+                )
+            }
+            CreateStatement::View(view) => {
+                let algorithm = view.algorithm.map(|algorithm| match algorithm {
+                    ViewAlgorithm::Undefined => "undefined",
+                    ViewAlgorithm::Merge => "merge",
+                    ViewAlgorithm::TempTable => "temptable",
+                });
+                let check_option = view.check_option.map(|option| match option {
+                    ViewCheckOption::Cascaded => "cascaded",
+                    ViewCheckOption::Local => "local",
+                });
+                tagged(
+                    "create_view",
+                    [
+                        ("or_replace", json!(view.or_replace)),
+                        ("algorithm", json!(algorithm)),
+                        ("definer", json!(view.definer)),
+                        ("sql_security", json!(view.security.map(sql_security))),
+                        ("name", view.name.to_json()),
+                        ("columns", json!(view.columns)),
+                        ("query", view.query.to_json()),
+                        ("check_option", json!(check_option)),
+                    ],
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "ddl")]
+fn sql_security(security: SqlSecurity) -> &'static str {
+    match security {
+        SqlSecurity::Definer => "definer",
+        SqlSecurity::Invoker => "invoker",
+    }
+}
+
+#[cfg(feature = "ddl")]
+impl ToJson for AlterTableOperation {
+    fn to_json(&self) -> Json {
+        match self {
+            AlterTableOperation::AddColumn(column) => tagged("add_column", [("column", column.to_json())]),
+            AlterTableOperation::AddConstraint(constraint) => {
+                tagged("add_constraint", [("constraint", constraint.to_json())])
+            }
+            AlterTableOperation::AddIndex(index) => tagged("add_index", [("index", index.to_json())]),
+            AlterTableOperation::ModifyColumn(column) => tagged("modify_column", [("column", column.to_json())]),
+            AlterTableOperation::DropColumn(name) => tagged("drop_column", [("name", json!(name))]),
+            AlterTableOperation::DropPrimaryKey => tagged("drop_primary_key", []),
+            AlterTableOperation::DropForeignKey(name) => tagged("drop_foreign_key", [("name", json!(name))]),
+            AlterTableOperation::DropCheck(name) => tagged("drop_check", [("name", json!(name))]),
+            AlterTableOperation::DropConstraint(name) => tagged("drop_constraint", [("name", json!(name))]),
+            AlterTableOperation::DropIndex(name) => tagged("drop_index", [("name", json!(name))]),
+        }
+    }
+}
+
+#[cfg(feature = "ddl")]
+impl ToJson for AlterStatement {
+    fn to_json(&self) -> Json {
+        match self {
+            AlterStatement::Table(alter) => tagged(
+                "alter_table",
+                [("name", alter.name.to_json()), ("operations", list(&alter.operations))],
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::select::SelectStatementParser;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_select_to_json() {
+        let select = Parser::new_from_sql("SELECT `id`, -1.5 AS x FROM t WHERE a IN (1, 'b') LIMIT 2")
+            .parse_select_statement()
+            .unwrap();
+        let value = to_value(&select);
+        assert_eq!(value["version"], json!(1));
+        let statement = &value["statement"];
+        assert_eq!(statement["type"], "select");
+        assert_eq!(
+            statement["columns"][0]["name"],
+            json!([{ "value": "id", "quote": "`" }])
+        );
+        assert_eq!(statement["columns"][1]["alias"], "x");
+        assert_eq!(statement["where"]["type"], "in");
+        assert_eq!(
+            statement["where"]["list"][1],
+            json!({ "type": "literal", "value": { "type": "string", "value": "b" } })
+        );
+        assert_eq!(statement["limit"], json!({ "limit": 2, "offset": null }));
+        assert_eq!(statement["having"], Json::Null);
+        // 键按字母顺序输出
+        assert!(to_string(&select).starts_with("{\"statement\":{\"columns\":"));
+    }
+
+    #[test]
+    fn test_deep_expression() {
+        let mut expr = Expr::Literal(Value::Integer(0));
+        for _ in 0..50_000 {
+            expr = Expr::UnaryOp {
+                op: UnaryOperator::Minus,
+                expr: Box::new(expr),
+            };
+        }
+        let mut value = expr.to_json();
+        let mut depth = 0;
+        while value["type"] == "unary_op" {
+            value = value["expr"].take();
+            depth += 1;
+        }
+        assert_eq!(depth, 50_000);
+        std::mem::forget(expr);
+    }
+}
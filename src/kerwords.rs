@@ -1,22 +1,95 @@
-use std::collections::HashSet;
-use serde_json;
-use lazy_static::lazy_static;
-
-
-lazy_static! {
-    pub static ref KEYWORDS: HashSet<String> = {
-        // 使用 include_str! 宏在编译期内嵌入 JSON 数据
-        let json_str = include_str!("../keywords.json");
-        let keywords: Vec<String> =
-            serde_json::from_str(json_str).expect("Failed to parse keyword.json");
-        keywords.into_iter().collect()
-    };
-
-    pub static ref TYPES:HashSet<String> = {
-        // 使用 include_str! 宏在编译期内嵌入 JSON 数据
-        let json_str = include_str!("../types.json");
-        let types: Vec<String> =
-            serde_json::from_str(json_str).expect("Failed to parse types.json");
-        types.into_iter().collect()
-    };
-}
\ No newline at end of file
+//! 关键字和类型名
+//!
+//! 查找表由 build.rs 根据 keywords.json（按 MySQL 的规则区分保留字和非保留字，其他方言列出与 MySQL 不同的单词）
+//! 和 types.json 在编译期生成
+
+use crate::parser::Dialect;
+
+include!(concat!(env!("OUT_DIR"), "/keywords.rs"));
+
+impl Keyword {
+    /// 查找单词对应的关键字（忽略大小写）
+    pub fn lookup(word: &str) -> Option<Keyword> {
+        with_ascii_uppercase(word, |upper| KEYWORD_MAP.get(upper).copied()).flatten()
+    }
+}
+
+impl std::fmt::Display for Keyword {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// 关键字和类型名都是不超过 32 字节的大写ASCII单词，在栈上转换成大写后再查找，不需要为每次判断分配String
+// 更长或含有非ASCII字符的单词不可能是关键字，返回 None
+fn with_ascii_uppercase<T>(word: &str, f: impl FnOnce(&str) -> T) -> Option<T> {
+    let mut buf = [0u8; 32];
+    let buf = buf.get_mut(..word.len())?;
+    if !word.is_ascii() {
+        return None;
+    }
+    buf.copy_from_slice(word.as_bytes());
+    buf.make_ascii_uppercase();
+    std::str::from_utf8(buf).ok().map(f)
+}
+
+/// 判断一个单词在指定方言中是否为保留字（忽略大小写）
+pub fn is_reserved_keyword(word: &str, dialect: Dialect) -> bool {
+    Keyword::lookup(word).is_some_and(|keyword| keyword.is_reserved(dialect))
+}
+
+/// 判断一个单词是否为关键字，包括保留字和非保留字（忽略大小写）
+pub fn is_keyword(word: &str) -> bool {
+    Keyword::lookup(word).is_some()
+}
+
+/// 判断一个单词是否为不带参数的类型名（忽略大小写）
+pub fn is_type_name(word: &str) -> bool {
+    with_ascii_uppercase(word, |upper| TYPES.contains(upper)).unwrap_or(false)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_keyword_lookup() {
+        assert!(is_reserved_keyword("select", Dialect::MySql));
+        assert!(is_reserved_keyword("SeLeCt", Dialect::MySql));
+        assert!(!is_reserved_keyword("duplicate", Dialect::MySql));
+        assert!(is_keyword("duplicate"));
+        assert!(!is_keyword("salary"));
+        assert!(is_type_name("varchar"));
+        // 非ASCII字符转换成大写后可能与关键字相同，但不是关键字
+        assert!(!is_keyword("ſelect"));
+        assert!(!is_keyword(&"a".repeat(64)));
+        assert!(!is_keyword(""));
+
+        assert_eq!(Keyword::lookup("Duplicate"), Some(Keyword::DUPLICATE));
+        assert_eq!(Keyword::lookup("salary"), None);
+        assert!(Keyword::SELECT.is_reserved(Dialect::MySql));
+        assert!(!Keyword::KEY.is_reserved(Dialect::MySql));
+        // 生成的枚举与 keywords.json 中的集合一致
+        for keyword in ALL_KEYWORDS {
+            assert_eq!(Keyword::lookup(keyword.as_str()), Some(*keyword));
+            assert_eq!(keyword.is_reserved(Dialect::MySql), RESERVED_KEYWORDS.contains(keyword.as_str()));
+        }
+        assert_eq!(ALL_KEYWORDS.len(), KEYWORDS.len());
+    }
+
+    #[test]
+    fn test_dialect_reserved() {
+        // QUALIFY 在 BigQuery 和 Snowflake 中是保留字，在 MySQL 中不是
+        assert!(!Keyword::QUALIFY.is_reserved(Dialect::MySql));
+        assert!(Keyword::QUALIFY.is_reserved(Dialect::BigQuery));
+        assert!(Keyword::QUALIFY.is_reserved(Dialect::Snowflake));
+        // SIGNAL 只在 MySQL 中保留
+        assert!(is_reserved_keyword("signal", Dialect::MySql));
+        assert!(!is_reserved_keyword("signal", Dialect::Postgres));
+        // 所有方言都保留的关键字
+        for dialect in [Dialect::MySql, Dialect::Postgres, Dialect::MsSql, Dialect::BigQuery, Dialect::Snowflake] {
+            assert!(Keyword::SELECT.is_reserved(dialect));
+            assert!(!Keyword::DUPLICATE.is_reserved(dialect));
+        }
+    }
+}
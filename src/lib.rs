@@ -3,13 +3,49 @@ pub mod error;
 pub mod parser;
 pub mod token;
 pub mod kerwords;
+#[cfg(feature = "formatter")]
+pub mod debug;
+#[cfg(feature = "analysis")]
+pub mod analysis;
+#[cfg(feature = "analysis")]
+pub mod completion;
+#[cfg(feature = "analysis")]
+pub mod range;
+#[cfg(feature = "analysis")]
+pub mod rewrite;
+#[cfg(feature = "analysis")]
+pub mod template;
+#[cfg(feature = "lint")]
+pub mod validate;
+#[cfg(feature = "lint")]
+pub mod policy;
+#[cfg(feature = "ddl")]
+pub mod schema;
+#[cfg(feature = "corpus")]
+pub mod corpus;
+#[cfg(feature = "json")]
+pub mod json;
 
 pub use parser::{
-    ParseError,Parser,
+    ParseError,Parser,ParserOptions,Dialect,
     StatementParser,
+    plugin::{PluginParser, StatementPlugin},
     select::SelectStatementParser,
     delete::DeleteStatementParser,
+    update::UpdateStatementParser,
+    show::ShowStatementParser,
 };
+#[cfg(feature = "ddl")]
+pub use parser::{create::CreateStatementParser, alter::AlterStatementParser};
+#[cfg(feature = "routines")]
+pub use parser::routine::RoutineStatementParser;
+#[cfg(feature = "metrics")]
+pub use parser::metrics::ParseMetrics;
 
-#[cfg(test)]
-pub mod tests;
\ No newline at end of file
+pub use error::{ErrorKind, Language};
+pub use kerwords::Keyword;
+pub use ast::semantic::SemanticEq;
+pub use ast::symbol::{Interner, Symbol};
+
+#[cfg(all(test, feature = "ddl", feature = "formatter"))]
+mod tests;
\ No newline at end of file
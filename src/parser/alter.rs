@@ -0,0 +1,117 @@
+use super::{ParseError, Parser};
+use crate::ast::alter::{AlterStatement, AlterTableOperation, AlterTableStatement};
+use crate::kerwords::Keyword;
+
+/// alter语句解析器接口
+pub trait AlterStatementParser {
+    type Error;
+    // 解析alter语句
+    fn parse_alter_statement(&mut self) -> Result<AlterStatement, Self::Error>;
+}
+
+impl Parser {
+    // 解析单个修改操作
+    fn parse_alter_table_operation(&mut self) -> Result<AlterTableOperation, ParseError> {
+        if self.match_keyword(Keyword::ADD) {
+            if self.is_table_constraint_start() {
+                return Ok(AlterTableOperation::AddConstraint(self.parse_table_constraint()?));
+            }
+            if self.match_keyword(Keyword::INDEX) || self.match_keyword(Keyword::KEY) {
+                return Ok(AlterTableOperation::AddIndex(self.parse_index_def()?));
+            }
+            self.match_keyword(Keyword::COLUMN);
+            return Ok(AlterTableOperation::AddColumn(self.parse_column_def()?));
+        }
+        if self.match_keyword(Keyword::MODIFY) {
+            self.match_keyword(Keyword::COLUMN);
+            return Ok(AlterTableOperation::ModifyColumn(self.parse_column_def()?));
+        }
+        if self.match_keyword(Keyword::DROP) {
+            if self.match_keyword(Keyword::PRIMARY) {
+                if !self.match_keyword(Keyword::KEY) {
+                    return Err(self.expected("KEY after DROP PRIMARY"));
+                }
+                return Ok(AlterTableOperation::DropPrimaryKey);
+            }
+            if self.match_keyword(Keyword::FOREIGN) {
+                if !self.match_keyword(Keyword::KEY) {
+                    return Err(self.expected("KEY after DROP FOREIGN"));
+                }
+                return Ok(AlterTableOperation::DropForeignKey(self.parse_identifier("foreign key")?));
+            }
+            if self.match_keyword(Keyword::CHECK) {
+                return Ok(AlterTableOperation::DropCheck(self.parse_identifier("check constraint")?));
+            }
+            if self.match_keyword(Keyword::CONSTRAINT) {
+                return Ok(AlterTableOperation::DropConstraint(self.parse_identifier("constraint")?));
+            }
+            if self.match_keyword(Keyword::INDEX) || self.match_keyword(Keyword::KEY) {
+                return Ok(AlterTableOperation::DropIndex(self.parse_identifier("index")?));
+            }
+            self.match_keyword(Keyword::COLUMN);
+            return Ok(AlterTableOperation::DropColumn(self.parse_identifier("column")?));
+        }
+        Err(self.unsupported("ALTER TABLE operation"))
+    }
+}
+
+impl AlterStatementParser for Parser {
+    type Error = ParseError;
+    // 解析ALTER语句
+    fn parse_alter_statement(&mut self) -> Result<AlterStatement, Self::Error> {
+        // 期望以ALTER关键字开始
+        if !self.match_keyword(Keyword::ALTER) {
+            return Err(self.expected("ALTER"));
+        }
+        if !self.match_keyword(Keyword::TABLE) {
+            return Err(self.unsupported("ALTER statement"));
+        }
+        let name = self.parse_object_name("table")?;
+        let mut operations = Vec::new();
+        loop {
+            operations.push(self.parse_alter_table_operation()?);
+            if !self.match_punctuator(',') {
+                break;
+            }
+        }
+        Ok(AlterStatement::Table(AlterTableStatement { name, operations }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ast::common::ObjectName;
+    use crate::ast::create::{ConstraintKind, ForeignKeyReference, ReferentialAction, TableConstraint};
+
+    #[test]
+    fn test_alter_table_constraints() {
+        let sql = "ALTER TABLE orders ADD CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE SET NULL,
+                   ADD CHECK (amount > 0), DROP FOREIGN KEY fk_old, DROP CHECK chk_old";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_alter_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let AlterStatement::Table(alter) = result.unwrap();
+
+        assert_eq!(alter.name.to_string(), "orders");
+        assert_eq!(alter.operations.len(), 4);
+        assert_eq!(alter.operations[0], AlterTableOperation::AddConstraint(TableConstraint {
+            name: Some("fk_user".to_string()),
+            kind: ConstraintKind::ForeignKey {
+                columns: vec!["user_id".to_string()],
+                reference: ForeignKeyReference {
+                    table: ObjectName::new(["users"]),
+                    columns: vec!["id".to_string()],
+                    on_delete: Some(ReferentialAction::SetNull),
+                    on_update: None,
+                },
+            },
+        }));
+        assert!(matches!(&alter.operations[1], AlterTableOperation::AddConstraint(TableConstraint {
+            name: None,
+            kind: ConstraintKind::Check { enforced: None, .. },
+        })));
+        assert_eq!(alter.operations[2], AlterTableOperation::DropForeignKey("fk_old".to_string()));
+        assert_eq!(alter.operations[3], AlterTableOperation::DropCheck("chk_old".to_string()));
+    }
+}
@@ -1,9 +1,14 @@
-use super::{ParseError, Parser};
+use super::{Dialect, ParseError, Parser};
 use crate::ast::{
-    expr::{LimitClause, OrderByExpr},
-    common::TableReference,
+    expr::{Expr, LimitClause, OrderByExpr, SortKey, Value},
+    common::{
+        DataType, Ident, IndexHint, IndexHintKind, IndexHintScope, ObjectName, Quantity, QuantityUnit, SampleMethod,
+        TableReference, TableSample,
+    },
 };
+use crate::error::ErrorKind;
 use crate::token::Token;
+use crate::kerwords::Keyword;
 // 实现公共解析功能
 impl Parser {
     /// 移动当前从句索引，返回新的索引或者顺序错误
@@ -14,51 +19,32 @@ impl Parser {
         get_clause_name: fn(u8) -> &'static str,
     ) -> Result<u8, ParseError> {
         if clause_idx > current_idx {
+            #[cfg(feature = "tracing")]
+            tracing::trace!(clause = get_clause_name(clause_idx), position = self.current, "clause");
             Ok(clause_idx)
         } else {
-            // 获取错误上下文信息
-            let context = self.get_error_context();
-
-            Err(ParseError {
-                message: format!(
-                    "{} clause out of order, expected after {}. Near: {}",
-                    get_clause_name(clause_idx),
-                    get_clause_name(current_idx),
-                    context
-                ),
-                token_position: self.current,
-            })
+            Err(self.error(ErrorKind::ClauseOutOfOrder {
+                clause: get_clause_name(clause_idx).to_string(),
+                after: get_clause_name(current_idx).to_string(),
+            }))
         }
     }
     /// 解析表名
     pub fn parse_table_reference(&mut self,allow_as_keyword:bool) -> Result<TableReference, ParseError> {
         // 获取表名
-        let name = match self.peek() {
-            Some(Token::Identifier(ident)) => {
-                let name = ident.to_owned();
-                self.consume_token();
-                name
-            }
-            _ => {
-                return Err(
-                    self.get_parse_error(&format!("Expected table name, found {:?}", self.peek()))
-                );
-            }
+        let name = self.parse_object_name("table")?;
+
+        // 检查是否指定了分区
+        let partitions = if self.match_keyword(Keyword::PARTITION) {
+            Some(self.parse_column_name_list()?)
+        } else {
+            None
         };
 
         // 检查是否有别名
-        let alias = if allow_as_keyword && self.match_keyword("AS") {
-            if let Some(Token::Identifier(ident)) = self.peek() {
-                let alias_name = ident.to_owned();
-                self.consume_token();
-                Some(alias_name)
-            } else {
-                return Err(self.get_parse_error(&format!(
-                    "Expected alias after AS, found {:?}",
-                    self.peek()
-                )));
-            }
-        } else if let Some(Token::Identifier(ident)) = self.peek() {
+        let alias = if allow_as_keyword && self.match_keyword(Keyword::AS) {
+            Some(self.parse_identifier("alias")?)
+        } else if let Some(Token::Identifier(ident)) | Some(Token::QuotedIdentifier(ident)) = self.peek() {
             let alias = ident.clone();
             self.consume_token();
             Some(alias)
@@ -66,29 +52,244 @@ impl Parser {
             None
         };
 
-        Ok(TableReference { name, alias })
+        let sample = if self.options.dialect == Dialect::Postgres && self.match_keyword(Keyword::TABLESAMPLE) {
+            Some(self.parse_table_sample()?)
+        } else {
+            None
+        };
+        let index_hints = self.parse_index_hints()?;
+        #[cfg(feature = "metrics")]
+        {
+            self.collector.tables += 1;
+        }
+        Ok(TableReference { name, partitions, alias, index_hints, sample, subquery: None, function: None, lateral: false })
+    }
+
+    // 解析 TABLESAMPLE 之后的抽样方法、比例和可选的随机种子
+    fn parse_table_sample(&mut self) -> Result<TableSample, ParseError> {
+        let method = if self.match_keyword(Keyword::SYSTEM) {
+            SampleMethod::System
+        } else if self.match_keyword(Keyword::BERNOULLI) {
+            SampleMethod::Bernoulli
+        } else {
+            return Err(self.expected("SYSTEM or BERNOULLI after TABLESAMPLE"));
+        };
+        let value = self.parse_parenthesized_expr("sampling percentage")?;
+        let seed = if self.match_keyword(Keyword::REPEATABLE) {
+            Some(self.parse_parenthesized_expr("REPEATABLE seed")?)
+        } else {
+            None
+        };
+        Ok(TableSample { method, size: Quantity { value, unit: QuantityUnit::Percent }, seed })
+    }
+
+    /// 解析字符串字面量，what 用于错误信息
+    pub fn parse_string_literal(&mut self, what: &str) -> Result<String, ParseError> {
+        match self.peek() {
+            Some(Token::StringLiteral(s)) => {
+                let value = s.to_owned();
+                self.consume_token();
+                Ok(value)
+            }
+            _ => Err(self.expected(what)),
+        }
+    }
+
+    /// 解析括号中的单个表达式，what 用于错误信息
+    pub fn parse_parenthesized_expr(&mut self, what: &str) -> Result<Expr, ParseError> {
+        if !self.match_punctuator('(') {
+            return Err(self.expected(&format!("opening parenthesis before {}", what)));
+        }
+        let expr = self.parse_expr(0)?;
+        if !self.match_punctuator(')') {
+            return Err(self.expected(&format!("closing parenthesis after {}", what)));
+        }
+        Ok(expr)
+    }
+
+    // 解析别名之后的索引提示，可以有多个，例如 USE INDEX (a) IGNORE INDEX FOR ORDER BY (b)
+    fn parse_index_hints(&mut self) -> Result<Vec<IndexHint>, ParseError> {
+        let mut hints = Vec::new();
+        loop {
+            let kind = if self.match_keyword(Keyword::USE) {
+                IndexHintKind::Use
+            } else if self.match_keyword(Keyword::FORCE) {
+                IndexHintKind::Force
+            } else if self.match_keyword(Keyword::IGNORE) {
+                IndexHintKind::Ignore
+            } else {
+                return Ok(hints);
+            };
+            if !self.match_keyword(Keyword::INDEX) && !self.match_keyword(Keyword::KEY) {
+                return Err(self.expected("INDEX or KEY in index hint"));
+            }
+            let scope = if self.match_keyword(Keyword::FOR) {
+                if self.match_keyword(Keyword::JOIN) {
+                    Some(IndexHintScope::Join)
+                } else if self.match_keyword(Keyword::ORDER) && self.match_keyword(Keyword::BY) {
+                    Some(IndexHintScope::OrderBy)
+                } else if self.match_keyword(Keyword::GROUP) && self.match_keyword(Keyword::BY) {
+                    Some(IndexHintScope::GroupBy)
+                } else {
+                    return Err(self.expected("JOIN, ORDER BY or GROUP BY after FOR"));
+                }
+            } else {
+                None
+            };
+            if !self.match_punctuator('(') {
+                return Err(self.expected("opening parenthesis before index list"));
+            }
+            let mut indexes = Vec::new();
+            // 只有 USE INDEX 允许空列表，表示不使用任何索引
+            if kind != IndexHintKind::Use || !self.is_punctuator(')') {
+                loop {
+                    // PRIMARY 是保留字，但可以作为主键索引的名字
+                    if self.match_keyword(Keyword::PRIMARY) {
+                        indexes.push("PRIMARY".to_string());
+                    } else {
+                        indexes.push(self.parse_identifier("index")?);
+                    }
+                    if !self.match_punctuator(',') {
+                        break;
+                    }
+                }
+            }
+            if !self.match_punctuator(')') {
+                return Err(self.expected("closing parenthesis after index list"));
+            }
+            hints.push(IndexHint { kind, scope, indexes });
+        }
+    }
+
+    /// 解析可选的 IF NOT EXISTS
+    pub fn parse_if_not_exists(&mut self) -> Result<bool, ParseError> {
+        if !self.match_keyword(Keyword::IF) {
+            return Ok(false);
+        }
+        if !self.match_keyword(Keyword::NOT) || !self.match_keyword(Keyword::EXISTS) {
+            return Err(self.expected("NOT EXISTS after IF"));
+        }
+        Ok(true)
+    }
+
+    /// 解析对象名，允许任意层级的限定，例如 my_proc、mydb.my_proc、catalog.schema.table
+    pub fn parse_object_name(&mut self, object: &str) -> Result<ObjectName, ParseError> {
+        let name = match self.peek() {
+            Some(Token::QualifiedIdentifier(parts)) => ObjectName(parts.clone()),
+            Some(Token::QuotedIdentifier(ident)) => ObjectName(vec![Ident::with_quote('`', ident.to_owned())]),
+            _ => {
+                let name = ObjectName::new([self.parse_identifier(object)?]);
+                return Ok(self.intern_object_name(name));
+            }
+        };
+        self.consume_token();
+        Ok(self.intern_object_name(name))
+    }
+
+    /// 解析以逗号分隔的 col = value 赋值列表，用于 UPDATE、INSERT 的 SET 和 ON DUPLICATE KEY UPDATE
+    ///
+    /// 列名可以带限定符或使用反引号，例如 t.col、`order`；= 也可以写作 :=；值可以是单独的 DEFAULT
+    pub fn parse_assignments(&mut self) -> Result<Vec<(ObjectName, Expr)>, ParseError> {
+        let mut assignments = Vec::new();
+        loop {
+            let column = self.parse_object_name("column")?;
+            if !self.match_operator("=") && !self.match_operator(":=") {
+                return Err(self.expected("= or := after column name"));
+            }
+            assignments.push((column, self.parse_assigned_value()?));
+            if !self.match_punctuator(',') {
+                break;
+            }
+        }
+        Ok(assignments)
+    }
+
+    /// 解析括号内以逗号分隔的列名列表，例如 (id, `name`)
+    pub fn parse_column_name_list(&mut self) -> Result<Vec<String>, ParseError> {
+        self.parse_column_names(false)
+    }
+
+    /// 解析单个列名，非保留关键字和反引号包裹的关键字都可以作为列名，例如 `order`、status
+    ///
+    /// allow_qualified为true时接受 t.col、db.t.col 这样的限定列名，只保留最后的列名部分
+    pub fn parse_column_name(&mut self, allow_qualified: bool) -> Result<String, ParseError> {
+        if allow_qualified {
+            if let Some(Token::QualifiedIdentifier(parts)) = self.peek() {
+                let column = parts.last().map(|part| part.value.to_string()).unwrap_or_default();
+                self.consume_token();
+                return Ok(column);
+            }
+        }
+        self.parse_identifier("column")
+    }
+
+    /// 解析括号内的列名列表，列名的规则见 parse_column_name
+    pub fn parse_column_names(&mut self, allow_qualified: bool) -> Result<Vec<String>, ParseError> {
+        if !self.match_punctuator('(') {
+            return Err(self.expected("opening parenthesis before column list"));
+        }
+        let mut columns = Vec::new();
+        loop {
+            columns.push(self.parse_column_name(allow_qualified)?);
+            if !self.match_punctuator(',') {
+                break;
+            }
+        }
+        if !self.match_punctuator(')') {
+            return Err(self.expected("closing parenthesis after column list"));
+        }
+        Ok(columns)
+    }
+
+    /// 解析变量名，用户变量和系统变量保留 @ 前缀，例如 total、@total、@@version
+    pub fn parse_variable_name(&mut self) -> Result<String, ParseError> {
+        let mut name = String::new();
+        // 词法分析会把 @ 单独拆成一个标识符
+        while self.peek() == Some(&Token::Identifier("@".to_string())) {
+            name.push('@');
+            self.consume_token();
+        }
+        // @@global.name 形式的系统变量被识别为限定名
+        if name == "@@" {
+            if let Some(Token::QualifiedIdentifier(parts)) = self.peek() {
+                name.push_str(&parts.iter().map(|part| &*part.value).collect::<Vec<_>>().join("."));
+                self.consume_token();
+                return Ok(name);
+            }
+        }
+        name.push_str(&self.parse_identifier("variable")?);
+        Ok(name)
+    }
+
+    /// 解析ORDER BY/GROUP BY中的一项，allow_ordinal为true时单独的正整数表示列序号
+    pub fn parse_sort_key(&mut self, allow_ordinal: bool) -> Result<SortKey, ParseError> {
+        let start = self.current;
+        let expr = self.parse_expr(0)?;
+        // 只有单独一个整数才是序号，1 + 0 之类的表达式仍按常量处理
+        if allow_ordinal && self.current == start + 1 {
+            if let Expr::Literal(Value::Integer(n)) = expr {
+                if let Ok(ordinal @ 1..) = u32::try_from(n) {
+                    return Ok(SortKey::Ordinal(ordinal));
+                }
+            }
+        }
+        Ok(SortKey::Expr(expr))
     }
 
     /// 解析order by子句
-    pub fn parse_order_by(&mut self) -> Result<Vec<OrderByExpr>, ParseError> {
+    pub fn parse_order_by(&mut self, allow_ordinal: bool) -> Result<Vec<OrderByExpr>, ParseError> {
         let mut order_by = Vec::new();
         // 解析列列表
         loop {
             // 解析单个列
-            let expr = self.parse_expr(0)?;
-            let order = if self.match_keyword("DESC") {
-                OrderByExpr {
-                    expr: expr.clone(),
-                    asc: false,
-                }
+            let expr = self.parse_sort_key(allow_ordinal)?;
+            let asc = if self.match_keyword(Keyword::DESC) {
+                false
             } else {
-                self.match_keyword("ASC");
-                OrderByExpr {
-                    expr: expr.clone(),
-                    asc: true,
-                }
+                self.match_keyword(Keyword::ASC);
+                true
             };
-            order_by.push(order);
+            order_by.push(OrderByExpr { expr, asc });
             // 如果后面是逗号，继续解析下一个列
             if !self.match_punctuator(',') {
                 break;
@@ -102,35 +303,32 @@ impl Parser {
         // 解析LIMIT值
         let limit = if let Some(Token::NumericLiteral(value)) = self.peek() {
             let limit_value = value.parse::<u64>().map_err(|_| {
-                self.get_parse_error(&format!(
-                    "Invalid number after LIMIT, found {:?}",
-                    self.peek()
-                ))
+                self.invalid_value("number after LIMIT", value)
             })?;
             self.consume_token(); // 消费LIMIT值
             limit_value
         } else {
-            return Err(self.get_parse_error(&format!(
-                "Expected integer after LIMIT, found {:?}",
-                self.peek()
-            )));
+            return Err(self.expected("integer after LIMIT"));
         };
+        // MySQL 的 LIMIT offset, row_count
+        if self.match_punctuator(',') {
+            let Some(Token::NumericLiteral(value)) = self.peek() else {
+                return Err(self.expected("row count after LIMIT offset"));
+            };
+            let row_count = value.parse::<u64>().map_err(|_| self.invalid_value("row count after LIMIT offset", value))?;
+            self.consume_token();
+            return Ok(LimitClause { limit: row_count, offset: Some(limit) });
+        }
         // 检查是否有OFFSET
-        let offset = if self.match_keyword("OFFSET") {
+        let offset = if self.match_keyword(Keyword::OFFSET) {
             if let Some(Token::NumericLiteral(value)) = self.peek() {
                 let offset_value = value.parse::<u64>().map_err(|_| {
-                    self.get_parse_error(&format!(
-                        "Invalid number after OFFSET, found {:?}",
-                        self.peek()
-                    ))
+                    self.invalid_value("number after OFFSET", value)
                 })?;
                 self.consume_token(); // 消费OFFSET值
                 Some(offset_value)
             } else {
-                return Err(self.get_parse_error(&format!(
-                    "Expected integer after OFFSET, found {:?}",
-                    self.peek()
-                )));
+                return Err(self.expected("integer after OFFSET"));
             }
         } else {
             None
@@ -138,4 +336,62 @@ impl Parser {
 
         Ok(LimitClause { limit, offset })
     }
-}
\ No newline at end of file
+
+    /// 解析数据类型，例如 INT UNSIGNED、VARCHAR(36)、DECIMAL (10, 2)、ENUM('a','b')
+    pub fn parse_data_type(&mut self) -> Result<DataType, ParseError> {
+        let mut data_type = self.parse_data_type_name()?;
+        data_type.unsigned = self.match_keyword(Keyword::UNSIGNED);
+        Ok(data_type)
+    }
+
+    // 解析类型名及括号内的参数，参数是数字列表，ENUM 和 SET 是字符串列表
+    fn parse_data_type_name(&mut self) -> Result<DataType, ParseError> {
+        let name = match self.peek() {
+            Some(Token::DataType(name)) => name.to_owned(),
+            // SET 同时是关键字，作为类型时后面必须紧跟值列表
+            Some(Token::Keyword { keyword: Keyword::SET, text }) if self.peek_n(1) == Some(&Token::Punctuator('(')) => {
+                text.to_owned()
+            }
+            _ => {
+                return Err(self.expected("data type"));
+            }
+        };
+        self.consume_token();
+        // ENUM 和 SET 的括号内是可选值列表，而不是长度
+        let is_value_list = name.eq_ignore_ascii_case("ENUM") || name.eq_ignore_ascii_case("SET");
+
+        if !self.match_punctuator('(') {
+            if is_value_list {
+                return Err(self.expected(&format!("value list after {}", name)));
+            }
+            return Ok(DataType { name, length: None, values: None, unsigned: false });
+        }
+        let mut parts = Vec::new();
+        loop {
+            match self.peek() {
+                Some(Token::NumericLiteral(n)) if !is_value_list => {
+                    parts.push(n.to_owned());
+                    self.consume_token();
+                }
+                Some(Token::StringLiteral(s)) if is_value_list => {
+                    parts.push(s.to_owned());
+                    self.consume_token();
+                }
+                _ => {
+                    return Err(self.expected(&format!("value in data type {}", name)));
+                }
+            }
+            if !self.match_punctuator(',') {
+                break;
+            }
+        }
+        if !self.match_punctuator(')') {
+            return Err(self.expected(&format!("closing parenthesis after data type {}", name)));
+        }
+        if is_value_list {
+            Ok(DataType { name, length: None, values: Some(parts), unsigned: false })
+        } else {
+            Ok(DataType { name, length: Some(parts.join(",")), values: None, unsigned: false })
+        }
+    }
+}
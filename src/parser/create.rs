@@ -0,0 +1,1073 @@
+use super::{ParseError, Parser};
+use crate::ast::create::{
+    ColumnDef, ColumnOption, ConstraintKind, CreateRoutineStatement, CreateStatement,
+    CreateTableStatement, CreateViewStatement, ForeignKeyReference, IndexDef, ParameterMode, PartitionBy, PartitionDef,
+    PartitionMethod, PartitionValues, ReferentialAction,
+    RoutineCharacteristic, RoutineKind, RoutineParameter, SqlDataAccess, SqlSecurity,
+    TableConstraint, TableOption, ViewAlgorithm, ViewCheckOption,
+};
+use crate::ast::expr::Expr;
+use crate::parser::select::SelectStatementParser;
+use crate::token::Token;
+use crate::kerwords::Keyword;
+
+/// create语句解析器接口
+pub trait CreateStatementParser {
+    type Error;
+    // 解析create语句
+    fn parse_create_statement(&mut self) -> Result<CreateStatement, Self::Error>;
+}
+
+impl Parser {
+    // 解析 DEFINER = user，用户部分原样拼接，例如 `root`@`localhost`
+    fn parse_definer(&mut self) -> Result<Option<String>, ParseError> {
+        if !self.match_keyword(Keyword::DEFINER) {
+            return Ok(None);
+        }
+        if !self.match_operator("=") {
+            return Err(self.expected("= after DEFINER"));
+        }
+        let mut parts = Vec::new();
+        let is_end = |parser: &Self| {
+            [Keyword::PROCEDURE, Keyword::FUNCTION, Keyword::VIEW, Keyword::SQL].iter().any(|kw| parser.is_keyword(*kw))
+        };
+        while self.has_more() && !is_end(self) {
+            let part = match self.peek() {
+                Some(Token::QuotedIdentifier(part)) => part.to_owned(),
+                Some(token) => self.format_token(token),
+                None => break,
+            };
+            parts.push(part);
+            self.consume_token();
+        }
+        if parts.is_empty() {
+            return Err(self.expected("user after DEFINER ="));
+        }
+        Ok(Some(parts.concat()))
+    }
+
+    // 解析参数列表，包括两侧的括号
+    fn parse_routine_params(&mut self, kind: RoutineKind) -> Result<Vec<RoutineParameter>, ParseError> {
+        if !self.match_punctuator('(') {
+            return Err(self.expected("opening parenthesis before parameter list"));
+        }
+        let mut params = Vec::new();
+        // 没有参数
+        if self.match_punctuator(')') {
+            return Ok(params);
+        }
+        loop {
+            let mode = if self.match_keyword(Keyword::IN) {
+                Some(ParameterMode::In)
+            } else if self.match_keyword(Keyword::OUT) {
+                Some(ParameterMode::Out)
+            } else if self.match_keyword(Keyword::INOUT) {
+                Some(ParameterMode::InOut)
+            } else {
+                None
+            };
+            if mode.is_some() && kind == RoutineKind::Function {
+                return Err(self.get_parse_error("IN/OUT/INOUT is only allowed for procedure parameters"));
+            }
+            let name = self.parse_identifier("parameter")?;
+            let data_type = self.parse_data_type()?;
+            params.push(RoutineParameter { mode, name, data_type });
+
+            if !self.match_punctuator(',') {
+                break;
+            }
+        }
+        if !self.match_punctuator(')') {
+            return Err(self.expected("closing parenthesis after parameter list"));
+        }
+        Ok(params)
+    }
+
+    // 解析单个特性，不是特性时返回None
+    fn parse_routine_characteristic(&mut self) -> Result<Option<RoutineCharacteristic>, ParseError> {
+        let characteristic = if self.match_keyword(Keyword::COMMENT) {
+            match self.peek() {
+                Some(Token::StringLiteral(s)) => {
+                    let comment = s.to_owned();
+                    self.consume_token();
+                    RoutineCharacteristic::Comment(comment)
+                }
+                _ => {
+                    return Err(self.expected("string after COMMENT"));
+                }
+            }
+        } else if self.match_keyword(Keyword::LANGUAGE) {
+            self.expect_keyword(Keyword::SQL, "LANGUAGE")?;
+            RoutineCharacteristic::LanguageSql
+        } else if self.match_keyword(Keyword::DETERMINISTIC) {
+            RoutineCharacteristic::Deterministic(true)
+        } else if self.is_keyword(Keyword::NOT)
+            && matches!(self.peek_n(1), Some(Token::Keyword { keyword: Keyword::DETERMINISTIC, .. }))
+        {
+            self.skip(2);
+            RoutineCharacteristic::Deterministic(false)
+        } else if self.match_keyword(Keyword::CONTAINS) {
+            self.expect_keyword(Keyword::SQL, "CONTAINS")?;
+            RoutineCharacteristic::DataAccess(SqlDataAccess::ContainsSql)
+        } else if self.match_keyword(Keyword::NO) {
+            self.expect_keyword(Keyword::SQL, "NO")?;
+            RoutineCharacteristic::DataAccess(SqlDataAccess::NoSql)
+        } else if self.match_keyword(Keyword::READS) {
+            self.expect_keyword(Keyword::SQL, "READS")?;
+            self.expect_keyword(Keyword::DATA, "READS SQL")?;
+            RoutineCharacteristic::DataAccess(SqlDataAccess::ReadsSqlData)
+        } else if self.match_keyword(Keyword::MODIFIES) {
+            self.expect_keyword(Keyword::SQL, "MODIFIES")?;
+            self.expect_keyword(Keyword::DATA, "MODIFIES SQL")?;
+            RoutineCharacteristic::DataAccess(SqlDataAccess::ModifiesSqlData)
+        } else if let Some(security) = self.parse_sql_security()? {
+            RoutineCharacteristic::SqlSecurity(security)
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(characteristic))
+    }
+
+    // 可选的 SQL SECURITY { DEFINER | INVOKER }
+    fn parse_sql_security(&mut self) -> Result<Option<SqlSecurity>, ParseError> {
+        if !(self.is_keyword(Keyword::SQL) && self.is_keyword_at(1, Keyword::SECURITY)) {
+            return Ok(None);
+        }
+        self.skip(2);
+        if self.match_keyword(Keyword::DEFINER) {
+            Ok(Some(SqlSecurity::Definer))
+        } else if self.match_keyword(Keyword::INVOKER) {
+            Ok(Some(SqlSecurity::Invoker))
+        } else {
+            Err(self.expected("DEFINER or INVOKER after SQL SECURITY"))
+        }
+    }
+
+    // 解析 PROCEDURE/FUNCTION 之后的部分，函数体只确定范围，保留为token和原始SQL
+    fn parse_create_routine(&mut self, definer: Option<String>) -> Result<CreateRoutineStatement, ParseError> {
+        let kind = if self.match_keyword(Keyword::PROCEDURE) {
+            RoutineKind::Procedure
+        } else if self.match_keyword(Keyword::FUNCTION) {
+            RoutineKind::Function
+        } else {
+            return Err(self.expected("PROCEDURE or FUNCTION"));
+        };
+        let if_not_exists = self.parse_if_not_exists()?;
+        let name = self.parse_object_name("routine")?;
+        let params = self.parse_routine_params(kind)?;
+
+        // 函数必须有RETURNS，存储过程不能有
+        let returns = if self.match_keyword(Keyword::RETURNS) {
+            if kind == RoutineKind::Procedure {
+                return Err(self.get_parse_error("RETURNS is only allowed for functions"));
+            }
+            Some(self.parse_data_type()?)
+        } else if kind == RoutineKind::Function {
+            return Err(self.expected("RETURNS"));
+        } else {
+            None
+        };
+
+        let mut characteristics = Vec::new();
+        while let Some(characteristic) = self.parse_routine_characteristic()? {
+            characteristics.push(characteristic);
+        }
+
+        let start = self.current;
+        let end = self.routine_body_end();
+        if end == start {
+            return Err(self.expected("routine body"));
+        }
+        let (body_span, body_sql) = match self.source_slice(start, end) {
+            Some((span, sql)) => (Some(span), Some(sql.to_string())),
+            None => (None, None),
+        };
+        let body = self.tokens[start..end].to_vec();
+        self.skip(end - start);
+
+        Ok(CreateRoutineStatement {
+            kind,
+            definer,
+            if_not_exists,
+            name,
+            params,
+            returns,
+            characteristics,
+            body,
+            body_sql,
+            body_span,
+        })
+    }
+
+    // 函数体结束的位置：深度为0的分号（语句结束符）之前；以 BEGIN、LOOP 等复合语句开头的函数体
+    // 在匹配的 END [IF|CASE|LOOP|WHILE|REPEAT] [label] 之后结束
+    fn routine_body_end(&self) -> usize {
+        let keyword = |i: usize| match self.tokens.get(i) {
+            Some(Token::Keyword { keyword, .. }) => Some(*keyword),
+            _ => None,
+        };
+        let is_punctuator = |i: usize, c: char| self.tokens.get(i) == Some(&Token::Punctuator(c));
+        // IF(...) 和 REPEAT(...) 是函数，IF [NOT] EXISTS 是DDL的选项，都不是复合语句
+        let opens_block = |i: usize| match keyword(i) {
+            Some(Keyword::BEGIN | Keyword::CASE | Keyword::LOOP | Keyword::WHILE) => true,
+            Some(Keyword::REPEAT) => !is_punctuator(i + 1, '('),
+            Some(Keyword::IF) => !is_punctuator(i + 1, '(') && !matches!(keyword(i + 1), Some(Keyword::NOT | Keyword::EXISTS)),
+            _ => false,
+        };
+
+        let mut i = self.current;
+        let label = matches!(self.tokens.get(i), Some(Token::Identifier(_))) && is_punctuator(i + 1, ':');
+        let compound = opens_block(if label { i + 2 } else { i });
+        let mut depth = 0usize;
+        while i < self.tokens.len() {
+            if keyword(i) == Some(Keyword::END) {
+                i += 1;
+                if matches!(keyword(i), Some(Keyword::IF | Keyword::CASE | Keyword::LOOP | Keyword::WHILE | Keyword::REPEAT)) {
+                    i += 1;
+                }
+                depth = depth.saturating_sub(1);
+                if depth == 0 && compound {
+                    if label && matches!(self.tokens.get(i), Some(Token::Identifier(_))) {
+                        i += 1;
+                    }
+                    return i;
+                }
+                continue;
+            }
+            if opens_block(i) {
+                depth += 1;
+            } else if depth == 0 && is_punctuator(i, ';') {
+                return i;
+            }
+            i += 1;
+        }
+        i
+    }
+}
+
+// 表定义相关的解析，CREATE TABLE 和 ALTER TABLE 共用
+impl Parser {
+    // 解析可选的名称，例如 CONSTRAINT 或 KEY 之后的名字
+    fn parse_optional_name(&mut self) -> Option<String> {
+        if let Some(Token::Identifier(ident)) | Some(Token::QuotedIdentifier(ident)) = self.peek() {
+            let name = ident.to_owned();
+            self.consume_token();
+            Some(name)
+        } else {
+            None
+        }
+    }
+
+    // 解析外键的级联动作
+    fn parse_referential_action(&mut self) -> Result<ReferentialAction, ParseError> {
+        if self.match_keyword(Keyword::RESTRICT) {
+            Ok(ReferentialAction::Restrict)
+        } else if self.match_keyword(Keyword::CASCADE) {
+            Ok(ReferentialAction::Cascade)
+        } else if self.match_keyword(Keyword::SET) {
+            if self.match_keyword(Keyword::NULL) {
+                Ok(ReferentialAction::SetNull)
+            } else if self.match_keyword(Keyword::DEFAULT) {
+                Ok(ReferentialAction::SetDefault)
+            } else {
+                Err(self.expected("NULL or DEFAULT after SET"))
+            }
+        } else if self.match_keyword(Keyword::NO) {
+            self.expect_keyword(Keyword::ACTION, "NO")?;
+            Ok(ReferentialAction::NoAction)
+        } else {
+            Err(self.expected("referential action"))
+        }
+    }
+
+    /// 解析外键引用，REFERENCES 已经解析
+    pub fn parse_foreign_key_reference(&mut self) -> Result<ForeignKeyReference, ParseError> {
+        let table = self.parse_object_name("table")?;
+        let columns = self.parse_column_name_list()?;
+        let mut on_delete = None;
+        let mut on_update = None;
+        // ON DELETE 和 ON UPDATE 的顺序不限
+        while self.is_keyword(Keyword::ON) {
+            if matches!(self.peek_n(1), Some(Token::Keyword { keyword: Keyword::DELETE, .. })) {
+                self.skip(2);
+                on_delete = Some(self.parse_referential_action()?);
+            } else if matches!(self.peek_n(1), Some(Token::Keyword { keyword: Keyword::UPDATE, .. })) {
+                self.skip(2);
+                on_update = Some(self.parse_referential_action()?);
+            } else {
+                break;
+            }
+        }
+        Ok(ForeignKeyReference { table, columns, on_delete, on_update })
+    }
+
+    // 解析 CHECK 后括号内的表达式，CHECK 已经解析
+    fn parse_check_expr(&mut self) -> Result<Expr, ParseError> {
+        if !self.match_punctuator('(') {
+            return Err(self.expected("opening parenthesis after CHECK"));
+        }
+        let expr = self.parse_expr(0)?;
+        if !self.match_punctuator(')') {
+            return Err(self.expected("closing parenthesis after CHECK expression"));
+        }
+        Ok(expr)
+    }
+
+    /// 判断当前位置是否为表级约束的开始
+    pub fn is_table_constraint_start(&self) -> bool {
+        self.is_keyword(Keyword::CONSTRAINT)
+            || self.is_keyword(Keyword::PRIMARY)
+            || self.is_keyword(Keyword::FOREIGN)
+            || self.is_keyword(Keyword::CHECK)
+            || self.is_keyword(Keyword::UNIQUE)
+    }
+
+    /// 解析表级约束：[CONSTRAINT [name]] {PRIMARY KEY | UNIQUE | FOREIGN KEY | CHECK} ...
+    pub fn parse_table_constraint(&mut self) -> Result<TableConstraint, ParseError> {
+        let name = if self.match_keyword(Keyword::CONSTRAINT) {
+            self.parse_optional_name()
+        } else {
+            None
+        };
+        let kind = if self.match_keyword(Keyword::PRIMARY) {
+            if !self.match_keyword(Keyword::KEY) {
+                return Err(self.expected("KEY after PRIMARY"));
+            }
+            ConstraintKind::PrimaryKey { columns: self.parse_column_name_list()? }
+        } else if self.match_keyword(Keyword::UNIQUE) {
+            if !self.match_keyword(Keyword::KEY) {
+                self.match_keyword(Keyword::INDEX);
+            }
+            let index_name = self.parse_optional_name();
+            ConstraintKind::Unique { index_name, columns: self.parse_column_name_list()? }
+        } else if self.match_keyword(Keyword::FOREIGN) {
+            if !self.match_keyword(Keyword::KEY) {
+                return Err(self.expected("KEY after FOREIGN"));
+            }
+            // MySQL 允许在 FOREIGN KEY 后再指定索引名，这里只保留约束名
+            let index_name = self.parse_optional_name();
+            let columns = self.parse_column_name_list()?;
+            if !self.match_keyword(Keyword::REFERENCES) {
+                return Err(self.expected("REFERENCES after FOREIGN KEY columns"));
+            }
+            let reference = self.parse_foreign_key_reference()?;
+            return Ok(TableConstraint {
+                name: name.or(index_name),
+                kind: ConstraintKind::ForeignKey { columns, reference },
+            });
+        } else if self.match_keyword(Keyword::CHECK) {
+            let expr = self.parse_check_expr()?;
+            let enforced = if self.match_keyword(Keyword::ENFORCED) {
+                Some(true)
+            } else if self.is_keyword(Keyword::NOT) && self.is_keyword_at(1, Keyword::ENFORCED) {
+                self.skip(2);
+                Some(false)
+            } else {
+                None
+            };
+            ConstraintKind::Check { expr, enforced }
+        } else {
+            return Err(self.expected("PRIMARY KEY, UNIQUE, FOREIGN KEY or CHECK"));
+        };
+        Ok(TableConstraint { name, kind })
+    }
+
+    /// 解析普通索引定义，INDEX/KEY 已经解析
+    pub fn parse_index_def(&mut self) -> Result<IndexDef, ParseError> {
+        let name = self.parse_optional_name();
+        let columns = self.parse_column_name_list()?;
+        Ok(IndexDef { name, columns })
+    }
+
+    // 解析字符集或排序规则名称
+    fn parse_charset_name(&mut self, after: &str) -> Result<String, ParseError> {
+        match self.peek() {
+            Some(Token::Identifier(name)) | Some(Token::StringLiteral(name)) => {
+                let name = name.to_owned();
+                self.consume_token();
+                Ok(name)
+            }
+            _ => Err(self.expected(&format!("name after {}", after))),
+        }
+    }
+
+    // 解析单个列属性，不是列属性时返回None
+    fn parse_column_option(&mut self) -> Result<Option<ColumnOption>, ParseError> {
+        let option = if self.match_keyword(Keyword::NOT) {
+            if !self.match_keyword(Keyword::NULL) {
+                return Err(self.expected("NULL after NOT"));
+            }
+            ColumnOption::NotNull
+        } else if self.match_keyword(Keyword::NULL) {
+            ColumnOption::Null
+        } else if self.match_keyword(Keyword::DEFAULT) {
+            ColumnOption::Default(self.parse_expr(0)?)
+        } else if self.is_keyword(Keyword::ON)
+            && matches!(self.peek_n(1), Some(Token::Keyword { keyword: Keyword::UPDATE, .. }))
+        {
+            self.skip(2);
+            ColumnOption::OnUpdate(self.parse_expr(0)?)
+        } else if self.match_keyword(Keyword::AUTO_INCREMENT) {
+            ColumnOption::AutoIncrement
+        } else if self.match_keyword(Keyword::PRIMARY) {
+            if !self.match_keyword(Keyword::KEY) {
+                return Err(self.expected("KEY after PRIMARY"));
+            }
+            ColumnOption::PrimaryKey
+        } else if self.match_keyword(Keyword::KEY) {
+            // 列定义中单独的 KEY 等同于 PRIMARY KEY
+            ColumnOption::PrimaryKey
+        } else if self.match_keyword(Keyword::UNIQUE) {
+            self.match_keyword(Keyword::KEY);
+            ColumnOption::Unique
+        } else if self.match_keyword(Keyword::COMMENT) {
+            match self.peek() {
+                Some(Token::StringLiteral(s)) => {
+                    let comment = s.to_owned();
+                    self.consume_token();
+                    ColumnOption::Comment(comment)
+                }
+                _ => {
+                    return Err(self.expected("string after COMMENT"));
+                }
+            }
+        } else if self.match_keyword(Keyword::CHARSET) {
+            ColumnOption::CharacterSet(self.parse_charset_name("CHARSET")?)
+        } else if self.match_keyword(Keyword::CHARACTER) {
+            if !self.match_keyword(Keyword::SET) {
+                return Err(self.expected("SET after CHARACTER"));
+            }
+            ColumnOption::CharacterSet(self.parse_charset_name("CHARACTER SET")?)
+        } else if self.match_keyword(Keyword::COLLATE) {
+            ColumnOption::Collate(self.parse_charset_name("COLLATE")?)
+        } else if self.is_keyword(Keyword::CONSTRAINT) || self.is_keyword(Keyword::CHECK) {
+            let name = if self.match_keyword(Keyword::CONSTRAINT) {
+                self.parse_optional_name()
+            } else {
+                None
+            };
+            if !self.match_keyword(Keyword::CHECK) {
+                return Err(self.expected("CHECK after CONSTRAINT in column definition"));
+            }
+            ColumnOption::Check { name, expr: self.parse_check_expr()? }
+        } else if self.match_keyword(Keyword::REFERENCES) {
+            ColumnOption::References(self.parse_foreign_key_reference()?)
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(option))
+    }
+
+    /// 解析列定义：name data_type [option] ...
+    pub fn parse_column_def(&mut self) -> Result<ColumnDef, ParseError> {
+        let name = self.parse_identifier("column")?;
+        let data_type = self.parse_data_type()?;
+        let mut options = Vec::new();
+        while let Some(option) = self.parse_column_option()? {
+            options.push(option);
+        }
+        Ok(ColumnDef { name, data_type, options })
+    }
+
+    // 解析表选项，例如 ENGINE=InnoDB DEFAULT CHARSET=utf8mb4
+    fn parse_table_options(&mut self) -> Result<Vec<TableOption>, ParseError> {
+        let mut options = Vec::new();
+        while self.has_more() && !self.is_punctuator(';') && !self.is_keyword(Keyword::PARTITION) {
+            // DEFAULT CHARSET 中的 DEFAULT 没有实际意义
+            self.match_keyword(Keyword::DEFAULT);
+            let mut name = match self.peek() {
+                Some(Token::Identifier(word)) | Some(Token::Keyword { text: word, .. }) => word.to_uppercase(),
+                _ => return Err(self.expected("table option")),
+            };
+            self.consume_token();
+            // CHARACTER SET 由两个单词组成
+            if name == "CHARACTER" && self.match_keyword(Keyword::SET) {
+                name.push_str(" SET");
+            }
+            self.match_operator("=");
+            let value = match self.peek() {
+                Some(Token::Identifier(v)) | Some(Token::Keyword { text: v, .. })
+                | Some(Token::NumericLiteral(v)) | Some(Token::StringLiteral(v)) => v.to_owned(),
+                _ => return Err(self.expected(&format!("value of table option {}", name))),
+            };
+            self.consume_token();
+            options.push(TableOption { name, value });
+            self.match_punctuator(',');
+        }
+        Ok(options)
+    }
+
+    // 解析括号内以逗号分隔的表达式列表
+    fn parse_partition_expr_list(&mut self) -> Result<Vec<Expr>, ParseError> {
+        if !self.match_punctuator('(') {
+            return Err(self.expected("opening parenthesis"));
+        }
+        let mut exprs = Vec::new();
+        loop {
+            exprs.push(self.parse_expr(0)?);
+            if !self.match_punctuator(',') {
+                break;
+            }
+        }
+        if !self.match_punctuator(')') {
+            return Err(self.expected("closing parenthesis after value list"));
+        }
+        Ok(exprs)
+    }
+
+    // 解析单个分区定义
+    fn parse_partition_def(&mut self) -> Result<PartitionDef, ParseError> {
+        if !self.match_keyword(Keyword::PARTITION) {
+            return Err(self.expected("PARTITION"));
+        }
+        let name = self.parse_optional_name().ok_or_else(|| {
+            self.expected("partition name")
+        })?;
+        let values = if self.match_keyword(Keyword::VALUES) {
+            if self.match_keyword(Keyword::IN) {
+                Some(PartitionValues::In(self.parse_partition_expr_list()?))
+            } else {
+                self.expect_keyword(Keyword::LESS, "VALUES")?;
+                self.expect_keyword(Keyword::THAN, "LESS")?;
+                if self.is_keyword(Keyword::MAXVALUE) {
+                    let max_value = self.parse_expr(0)?;
+                    Some(PartitionValues::LessThan(vec![max_value]))
+                } else {
+                    Some(PartitionValues::LessThan(self.parse_partition_expr_list()?))
+                }
+            }
+        } else {
+            None
+        };
+        Ok(PartitionDef { name, values })
+    }
+
+    // 解析PARTITION BY子句，PARTITION 已经解析
+    fn parse_partition_by(&mut self) -> Result<PartitionBy, ParseError> {
+        if !self.match_keyword(Keyword::BY) {
+            return Err(self.expected("BY after PARTITION"));
+        }
+        let linear = self.match_keyword(Keyword::LINEAR);
+        let method = if self.match_keyword(Keyword::RANGE) {
+            if self.match_keyword(Keyword::COLUMNS) {
+                PartitionMethod::RangeColumns
+            } else {
+                PartitionMethod::Range
+            }
+        } else if self.match_keyword(Keyword::LIST) {
+            if self.match_keyword(Keyword::COLUMNS) {
+                PartitionMethod::ListColumns
+            } else {
+                PartitionMethod::List
+            }
+        } else if self.match_keyword(Keyword::HASH) {
+            PartitionMethod::Hash
+        } else if self.match_keyword(Keyword::KEY) {
+            PartitionMethod::Key
+        } else {
+            return Err(self.expected("RANGE, LIST, HASH or KEY after PARTITION BY"));
+        };
+        if linear && !matches!(method, PartitionMethod::Hash | PartitionMethod::Key) {
+            return Err(self.get_parse_error("LINEAR is only allowed for HASH or KEY partitioning"));
+        }
+
+        // 按列分区时括号内是列名，否则是一个表达式
+        let (expr, columns) = match method {
+            PartitionMethod::RangeColumns | PartitionMethod::ListColumns | PartitionMethod::Key => {
+                (None, self.parse_column_name_list()?)
+            }
+            _ => {
+                if !self.match_punctuator('(') {
+                    return Err(self.expected("opening parenthesis after partition method"));
+                }
+                let expr = self.parse_expr(0)?;
+                if !self.match_punctuator(')') {
+                    return Err(self.expected("closing parenthesis after partition expression"));
+                }
+                (Some(expr), Vec::new())
+            }
+        };
+
+        let count = if self.match_keyword(Keyword::PARTITIONS) {
+            match self.peek() {
+                Some(Token::NumericLiteral(n)) => {
+                    let count = n.parse::<u64>().map_err(|_| {
+                        self.invalid_value("number of partitions", n)
+                    })?;
+                    self.consume_token();
+                    Some(count)
+                }
+                _ => {
+                    return Err(self.expected("number after PARTITIONS"));
+                }
+            }
+        } else {
+            None
+        };
+
+        let mut partitions = Vec::new();
+        if self.match_punctuator('(') {
+            loop {
+                partitions.push(self.parse_partition_def()?);
+                if !self.match_punctuator(',') {
+                    break;
+                }
+            }
+            if !self.match_punctuator(')') {
+                return Err(self.expected("closing parenthesis after partition definitions"));
+            }
+        }
+
+        Ok(PartitionBy { method, linear, expr, columns, count, partitions })
+    }
+
+    // 解析 CREATE TABLE 之后的部分，TABLE 已经解析
+    // 可选的 ALGORITHM = { UNDEFINED | MERGE | TEMPTABLE }
+    fn parse_view_algorithm(&mut self) -> Result<Option<ViewAlgorithm>, ParseError> {
+        if !self.match_keyword(Keyword::ALGORITHM) {
+            return Ok(None);
+        }
+        if !self.match_operator("=") {
+            return Err(self.expected("= after ALGORITHM"));
+        }
+        let algorithm = if self.match_keyword(Keyword::UNDEFINED) {
+            ViewAlgorithm::Undefined
+        } else if self.match_keyword(Keyword::MERGE) {
+            ViewAlgorithm::Merge
+        } else if self.match_keyword(Keyword::TEMPTABLE) {
+            ViewAlgorithm::TempTable
+        } else {
+            return Err(self.expected("UNDEFINED, MERGE or TEMPTABLE after ALGORITHM ="));
+        };
+        Ok(Some(algorithm))
+    }
+
+    // 解析 VIEW 之后的 name [(columns)] AS query [WITH [CASCADED | LOCAL] CHECK OPTION]
+    fn parse_create_view(
+        &mut self,
+        or_replace: bool,
+        algorithm: Option<ViewAlgorithm>,
+        definer: Option<String>,
+        security: Option<SqlSecurity>,
+    ) -> Result<CreateViewStatement, ParseError> {
+        let name = self.parse_object_name("view")?;
+        let columns = if self.is_punctuator('(') {
+            self.parse_column_name_list()?
+        } else {
+            Vec::new()
+        };
+        self.expect_keyword(Keyword::AS, "view name")?;
+        let query = Box::new(self.parse_select_statement()?);
+        let check_option = if self.match_keyword(Keyword::WITH) {
+            let option = if self.match_keyword(Keyword::LOCAL) {
+                ViewCheckOption::Local
+            } else {
+                self.match_keyword(Keyword::CASCADED);
+                ViewCheckOption::Cascaded
+            };
+            self.expect_keyword(Keyword::CHECK, "WITH")?;
+            self.expect_keyword(Keyword::OPTION, "WITH CHECK")?;
+            Some(option)
+        } else {
+            None
+        };
+        Ok(CreateViewStatement { or_replace, algorithm, definer, security, name, columns, query, check_option })
+    }
+
+    fn parse_create_table(&mut self) -> Result<CreateTableStatement, ParseError> {
+        let if_not_exists = self.parse_if_not_exists()?;
+        let name = self.parse_object_name("table")?;
+        if !self.match_punctuator('(') {
+            return Err(self.expected("opening parenthesis after table name"));
+        }
+        let mut columns = Vec::new();
+        let mut constraints = Vec::new();
+        let mut indexes = Vec::new();
+        loop {
+            if self.is_table_constraint_start() {
+                constraints.push(self.parse_table_constraint()?);
+            } else if self.match_keyword(Keyword::KEY) || self.match_keyword(Keyword::INDEX) {
+                indexes.push(self.parse_index_def()?);
+            } else {
+                columns.push(self.parse_column_def()?);
+            }
+            if !self.match_punctuator(',') {
+                break;
+            }
+        }
+        if !self.match_punctuator(')') {
+            return Err(self.expected("comma or closing parenthesis in table definition"));
+        }
+        let options = self.parse_table_options()?;
+        let partition_by = if self.match_keyword(Keyword::PARTITION) {
+            Some(self.parse_partition_by()?)
+        } else {
+            None
+        };
+        Ok(CreateTableStatement {
+            if_not_exists,
+            name,
+            columns,
+            constraints,
+            indexes,
+            options,
+            partition_by,
+        })
+    }
+}
+
+impl CreateStatementParser for Parser {
+    type Error = ParseError;
+    // 解析CREATE语句
+    fn parse_create_statement(&mut self) -> Result<CreateStatement, Self::Error> {
+        // 期望以CREATE关键字开始
+        if !self.match_keyword(Keyword::CREATE) {
+            return Err(self.expected("CREATE"));
+        }
+        // OR REPLACE、ALGORITHM 和 SQL SECURITY 只用于视图
+        let or_replace = self.match_keyword(Keyword::OR);
+        if or_replace {
+            self.expect_keyword(Keyword::REPLACE, "OR")?;
+        }
+        let algorithm = self.parse_view_algorithm()?;
+        let definer = self.parse_definer()?;
+        let security = self.parse_sql_security()?;
+        if self.match_keyword(Keyword::VIEW) {
+            let view = self.parse_create_view(or_replace, algorithm, definer, security)?;
+            return Ok(CreateStatement::View(view));
+        }
+        if or_replace || algorithm.is_some() || security.is_some() {
+            return Err(self.expected("VIEW"));
+        }
+        if self.is_keyword(Keyword::PROCEDURE) || self.is_keyword(Keyword::FUNCTION) {
+            return Ok(CreateStatement::Routine(self.parse_create_routine(definer)?));
+        }
+        if definer.is_none() && self.match_keyword(Keyword::TABLE) {
+            return Ok(CreateStatement::Table(self.parse_create_table()?));
+        }
+        Err(self.unsupported("CREATE statement"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ast::common::{DataType, Ident, ObjectName};
+    use crate::ast::expr::Value;
+    use crate::ast::SQLStatement;
+    use crate::token::{tokenize, Span};
+
+    #[test]
+    fn test_create_procedure() {
+        let sql = "CREATE DEFINER=`root`@`localhost` PROCEDURE update_stock(IN product_id INT, INOUT amount DECIMAL(10,2), OUT result VARCHAR(20))
+                   COMMENT 'adjust stock' MODIFIES SQL DATA
+                   BEGIN UPDATE products SET stock = stock - amount WHERE id = product_id; END;";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_create_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let CreateStatement::Routine(routine) = result.unwrap() else {
+            panic!("Expected CREATE PROCEDURE/FUNCTION");
+        };
+
+        assert_eq!(routine.kind, RoutineKind::Procedure);
+        assert_eq!(routine.definer, Some("root@localhost".to_string()));
+        assert_eq!(routine.name.to_string(), "update_stock");
+        assert_eq!(routine.params, vec![
+            RoutineParameter {
+                mode: Some(ParameterMode::In),
+                name: "product_id".to_string(),
+                data_type: DataType { name: "INT".to_string(), length: None, values: None, unsigned: false },
+            },
+            RoutineParameter {
+                mode: Some(ParameterMode::InOut),
+                name: "amount".to_string(),
+                data_type: DataType { name: "DECIMAL".to_string(), length: Some("10,2".to_string()), values: None, unsigned: false },
+            },
+            RoutineParameter {
+                mode: Some(ParameterMode::Out),
+                name: "result".to_string(),
+                data_type: DataType { name: "VARCHAR".to_string(), length: Some("20".to_string()), values: None, unsigned: false },
+            },
+        ]);
+        assert_eq!(routine.returns, None);
+        assert_eq!(routine.characteristics, vec![
+            RoutineCharacteristic::Comment("adjust stock".to_string()),
+            RoutineCharacteristic::DataAccess(SqlDataAccess::ModifiesSqlData),
+        ]);
+        // 函数体保留原始SQL，去掉了末尾的分号
+        let body = routine.body_sql.as_deref().unwrap();
+        assert!(body.starts_with("BEGIN"), "{}", body);
+        assert!(body.ends_with("END"), "{}", body);
+        let span = routine.body_span.unwrap();
+        assert_eq!(&sql[span.start..span.end], body);
+        assert_eq!(routine.body, tokenize(body));
+    }
+
+    #[test]
+    fn test_create_function() {
+        let sql = "CREATE FUNCTION IF NOT EXISTS mydb.add_tax(price DECIMAL(10,2)) RETURNS DECIMAL(10,2)
+                   NOT DETERMINISTIC SQL SECURITY INVOKER RETURN price * 1.1";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_create_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let CreateStatement::Routine(routine) = result.unwrap() else {
+            panic!("Expected CREATE PROCEDURE/FUNCTION");
+        };
+
+        assert_eq!(routine.kind, RoutineKind::Function);
+        assert!(routine.if_not_exists);
+        assert_eq!(routine.name.to_string(), "mydb.add_tax");
+        assert_eq!(routine.params.len(), 1);
+        assert_eq!(routine.params[0].mode, None);
+        assert_eq!(routine.returns, Some(DataType { name: "DECIMAL".to_string(), length: Some("10,2".to_string()), values: None, unsigned: false }));
+        assert_eq!(routine.characteristics, vec![
+            RoutineCharacteristic::Deterministic(false),
+            RoutineCharacteristic::SqlSecurity(SqlSecurity::Invoker),
+        ]);
+        assert_eq!(routine.body_sql.as_deref(), Some("RETURN price * 1.1"));
+
+        // 函数体中的注释、空白、大小写和字符串转义保持原样
+        let sql = "CREATE FUNCTION f() RETURNS INT\n  return  /* one */ 'it\\'s';";
+        let mut parser = Parser::new_from_sql(sql);
+        let Ok(CreateStatement::Routine(routine)) = parser.parse_create_statement() else {
+            panic!("Expected CREATE FUNCTION");
+        };
+        assert_eq!(routine.body_sql.as_deref(), Some("return  /* one */ 'it\\'s'"));
+        assert_eq!(routine.body_span, Some(Span::new(34, 59)));
+        // 由token创建的解析器没有原始SQL，只保留函数体的token
+        let mut parser = Parser::new(tokenize("CREATE FUNCTION f() RETURNS INT RETURN 1"));
+        let Ok(CreateStatement::Routine(routine)) = parser.parse_create_statement() else {
+            panic!("Expected CREATE FUNCTION");
+        };
+        assert_eq!(routine.body, tokenize("RETURN 1"));
+        assert_eq!(routine.body_sql, None);
+        assert_eq!(routine.body_span, None);
+
+        // 函数的参数不能指定方向，且必须有RETURNS
+        let mut parser = Parser::new_from_sql("CREATE FUNCTION f(IN a INT) RETURNS INT RETURN a");
+        assert!(parser.parse_create_statement().is_err());
+        let mut parser = Parser::new_from_sql("CREATE FUNCTION f(a INT) RETURN a");
+        assert!(parser.parse_create_statement().is_err());
+    }
+
+    #[test]
+    fn test_routine_body_end() {
+        let body_of = |statement: &SQLStatement| match statement {
+            SQLStatement::Create(CreateStatement::Routine(routine)) => routine.body_sql.clone().unwrap(),
+            _ => panic!("Expected CREATE PROCEDURE/FUNCTION"),
+        };
+
+        // 函数体在语句结束符处结束，之后的语句单独解析
+        let sql = "CREATE FUNCTION f() RETURNS INT RETURN CASE WHEN a THEN 1 END + 1; SELECT 2";
+        let statements = Parser::new_from_sql(sql).parse_statements().unwrap();
+        assert_eq!(statements.len(), 2);
+        assert_eq!(body_of(&statements[0]), "RETURN CASE WHEN a THEN 1 END + 1");
+
+        // BEGIN ... END 中的分号属于函数体，函数体在匹配的 END 之后结束
+        let sql = "CREATE PROCEDURE p() main: BEGIN
+                       IF IF(a, 1, 0) = 1 THEN DROP TABLE IF EXISTS t; END IF;
+                       REPEAT SET a = REPEAT('x', 2); UNTIL a END REPEAT;
+                   END main; SELECT 2";
+        let statements = Parser::new_from_sql(sql).parse_statements().unwrap();
+        assert_eq!(statements.len(), 2);
+        let body = body_of(&statements[0]);
+        assert!(body.starts_with("main: BEGIN") && body.ends_with("END main"), "{}", body);
+        let sql = "CREATE PROCEDURE p() BEGIN SELECT 1; END SELECT 2";
+        assert!(Parser::new_from_sql(sql).parse_statements().is_err());
+
+        let mut parser = Parser::new_from_sql("CREATE PROCEDURE p();");
+        assert!(parser.parse_create_statement().is_err());
+    }
+
+    #[test]
+    fn test_enum_and_set_types() {
+        let sql = "CREATE PROCEDURE set_status(IN status ENUM('new','in progress','it''s done'), IN tags SET ('a', 'b,c'))
+                   BEGIN END";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_create_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let CreateStatement::Routine(routine) = result.unwrap() else {
+            panic!("Expected CREATE PROCEDURE/FUNCTION");
+        };
+
+        assert_eq!(routine.params[0].data_type, DataType {
+            name: "ENUM".to_string(),
+            length: None,
+            values: Some(vec!["new".to_string(), "in progress".to_string(), "it's done".to_string()]),
+            unsigned: false,
+        });
+        assert_eq!(routine.params[1].data_type, DataType {
+            name: "SET".to_string(),
+            length: None,
+            values: Some(vec!["a".to_string(), "b,c".to_string()]),
+            unsigned: false,
+        });
+
+        // ENUM 必须带值列表
+        let mut parser = Parser::new_from_sql("CREATE PROCEDURE p(IN status ENUM) BEGIN END");
+        assert!(parser.parse_create_statement().is_err());
+
+        // 参数之间可以有空格，引号内的括号和逗号属于值
+        let sql = "CREATE TABLE t (state ENUM('a', 'b)c', 'd,e'), price DECIMAL(10, 2) UNSIGNED, code CHAR (3))";
+        let Ok(CreateStatement::Table(table)) = Parser::new_from_sql(sql).parse_create_statement() else {
+            panic!("Expected CREATE TABLE");
+        };
+        let types: Vec<_> = table.columns.iter().map(|column| &column.data_type).collect();
+        assert_eq!(types[0].values.as_deref(), Some(&["a".to_string(), "b)c".to_string(), "d,e".to_string()][..]));
+        assert_eq!((types[1].length.as_deref(), types[1].unsigned), (Some("10,2"), true));
+        assert_eq!(types[2].length.as_deref(), Some("3"));
+        for sql in ["CREATE TABLE t (a ENUM(1, 2))", "CREATE TABLE t (a DECIMAL('10'))", "CREATE TABLE t (a INT(10)"] {
+            assert!(Parser::new_from_sql(sql).parse_create_statement().is_err(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_create_table_constraints() {
+        let sql = "CREATE TABLE IF NOT EXISTS `orders` (
+                `id` bigint unsigned NOT NULL AUTO_INCREMENT,
+                `user_id` int NOT NULL,
+                `amount` DECIMAL(10,2) NOT NULL DEFAULT '0' COMMENT 'order amount',
+                `status` varchar(20) CHARACTER SET utf8mb4 COLLATE utf8mb4_bin DEFAULT NULL CHECK (`status` != ''),
+                PRIMARY KEY (`id`),
+                UNIQUE KEY `uk_user` (`user_id`, `status`),
+                KEY `idx_amount` (`amount`),
+                CONSTRAINT `fk_user` FOREIGN KEY (`user_id`) REFERENCES `users` (`id`) ON DELETE CASCADE ON UPDATE NO ACTION,
+                CONSTRAINT chk_amount CHECK (amount >= 0) NOT ENFORCED
+            ) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_0900_ai_ci;";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_create_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let CreateStatement::Table(table) = result.unwrap() else {
+            panic!("Expected CREATE TABLE");
+        };
+
+        assert!(table.if_not_exists);
+        assert_eq!(table.name.name().value, "orders");
+        assert_eq!(table.columns.len(), 4);
+        assert!(table.columns[0].data_type.unsigned);
+        assert_eq!(table.columns[0].options, vec![ColumnOption::NotNull, ColumnOption::AutoIncrement]);
+        assert_eq!(table.columns[3].options[0], ColumnOption::CharacterSet("utf8mb4".to_string()));
+        assert_eq!(table.columns[3].options[1], ColumnOption::Collate("utf8mb4_bin".to_string()));
+        assert!(matches!(table.columns[3].options[3], ColumnOption::Check { name: None, .. }));
+
+        assert_eq!(table.constraints.len(), 4);
+        assert_eq!(table.constraints[0], TableConstraint {
+            name: None,
+            kind: ConstraintKind::PrimaryKey { columns: vec!["id".to_string()] },
+        });
+        assert_eq!(table.constraints[1], TableConstraint {
+            name: None,
+            kind: ConstraintKind::Unique {
+                index_name: Some("uk_user".to_string()),
+                columns: vec!["user_id".to_string(), "status".to_string()],
+            },
+        });
+        assert_eq!(table.constraints[2], TableConstraint {
+            name: Some("fk_user".to_string()),
+            kind: ConstraintKind::ForeignKey {
+                columns: vec!["user_id".to_string()],
+                reference: ForeignKeyReference {
+                    table: ObjectName(vec![Ident::with_quote('`', "users")]),
+                    columns: vec!["id".to_string()],
+                    on_delete: Some(ReferentialAction::Cascade),
+                    on_update: Some(ReferentialAction::NoAction),
+                },
+            },
+        });
+        assert!(matches!(&table.constraints[3], TableConstraint {
+            name: Some(name),
+            kind: ConstraintKind::Check { enforced: Some(false), .. },
+        } if name == "chk_amount"));
+
+        assert_eq!(table.indexes, vec![IndexDef {
+            name: Some("idx_amount".to_string()),
+            columns: vec!["amount".to_string()],
+        }]);
+        assert_eq!(table.options, vec![
+            TableOption { name: "ENGINE".to_string(), value: "InnoDB".to_string() },
+            TableOption { name: "CHARSET".to_string(), value: "utf8mb4".to_string() },
+            TableOption { name: "COLLATE".to_string(), value: "utf8mb4_0900_ai_ci".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn test_create_table_partition_by() {
+        let sql = "CREATE TABLE logs (id INT, created_year INT) ENGINE=InnoDB
+                   PARTITION BY RANGE (created_year) (
+                       PARTITION p2023 VALUES LESS THAN (2024),
+                       PARTITION pmax VALUES LESS THAN MAXVALUE
+                   )";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_create_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let CreateStatement::Table(table) = result.unwrap() else {
+            panic!("Expected CREATE TABLE");
+        };
+        assert_eq!(table.options.len(), 1);
+        let partition_by = table.partition_by.unwrap();
+        assert_eq!(partition_by.method, PartitionMethod::Range);
+        assert_eq!(partition_by.expr, Some(Expr::Identifier(ObjectName::new(["created_year"]))));
+        assert_eq!(partition_by.partitions, vec![
+            PartitionDef {
+                name: "p2023".to_string(),
+                values: Some(PartitionValues::LessThan(vec![Expr::Literal(Value::Integer(2024))])),
+            },
+            PartitionDef {
+                name: "pmax".to_string(),
+                values: Some(PartitionValues::LessThan(vec![Expr::Identifier(ObjectName::new(["MAXVALUE"]))])),
+            },
+        ]);
+
+        let sql = "CREATE TABLE t (a INT, b INT) PARTITION BY LINEAR KEY (a, b) PARTITIONS 4";
+        let mut parser = Parser::new_from_sql(sql);
+        let CreateStatement::Table(table) = parser.parse_create_statement().unwrap() else {
+            panic!("Expected CREATE TABLE");
+        };
+        let partition_by = table.partition_by.unwrap();
+        assert_eq!(partition_by.method, PartitionMethod::Key);
+        assert!(partition_by.linear);
+        assert_eq!(partition_by.columns, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(partition_by.count, Some(4));
+        assert!(partition_by.partitions.is_empty());
+    }
+
+    #[test]
+    fn test_create_view() {
+        let sql = "CREATE OR REPLACE ALGORITHM=MERGE DEFINER=`root`@`localhost` SQL SECURITY INVOKER
+                   VIEW shop.active_users (id, name) AS SELECT id, name FROM users u WHERE active = 1 WITH LOCAL CHECK OPTION";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_create_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let CreateStatement::View(view) = result.unwrap() else {
+            panic!("Expected CREATE VIEW");
+        };
+        assert!(view.or_replace);
+        assert_eq!(view.algorithm, Some(ViewAlgorithm::Merge));
+        assert_eq!(view.definer.as_deref(), Some("root@localhost"));
+        assert_eq!(view.security, Some(SqlSecurity::Invoker));
+        assert_eq!(view.name.to_string(), "shop.active_users");
+        assert_eq!(view.columns, vec!["id".to_string(), "name".to_string()]);
+        assert_eq!(view.query.from.as_ref().unwrap().alias.as_deref(), Some("u"));
+        assert_eq!(view.check_option, Some(ViewCheckOption::Local));
+
+        let sql = "CREATE VIEW v AS WITH t AS (SELECT 1 AS x) SELECT x FROM t UNION SELECT 2 WITH CHECK OPTION";
+        let CreateStatement::View(view) = Parser::new_from_sql(sql).parse_create_statement().unwrap() else {
+            panic!("Expected CREATE VIEW");
+        };
+        assert!(!view.or_replace && view.algorithm.is_none() && view.definer.is_none() && view.columns.is_empty());
+        assert!(view.query.with.is_some());
+        assert_eq!(view.query.set_operations.len(), 1);
+        assert_eq!(view.check_option, Some(ViewCheckOption::Cascaded));
+
+        for sql in [
+            "CREATE VIEW v SELECT 1",
+            "CREATE VIEW v AS",
+            "CREATE ALGORITHM=FAST VIEW v AS SELECT 1",
+            "CREATE VIEW v AS SELECT 1 WITH CHECK",
+            "CREATE OR REPLACE TABLE t (id INT)",
+            "CREATE SQL SECURITY DEFINER PROCEDURE p() BEGIN END",
+        ] {
+            assert!(Parser::new_from_sql(sql).parse_create_statement().is_err(), "{}", sql);
+        }
+    }
+}
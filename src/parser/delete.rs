@@ -1,8 +1,9 @@
 use super::{ParseError, Parser};
+use crate::kerwords::Keyword;
 
 use crate::ast::{
     common::TableReference,
-    delete::DeleteStatement,
+    delete::{DeleteModifiers, DeleteStatement},
 };
 
 /// delete语句解析器接口
@@ -25,13 +26,20 @@ impl DeleteStatementParser for Parser {
     // 解析DELETE语句
     fn parse_delete_statement(&mut self) -> Result<DeleteStatement, Self::Error> {
         // 期望以DELETE关键字开始
-        if !self.match_keyword("DELETE") {
-            return Err(self.get_parse_error(&format!("Expected DELETE, found{:?}", self.peek())));
+        if !self.match_keyword(Keyword::DELETE) {
+            return Err(self.expected("DELETE"));
         }
 
+        // 修饰符必须按 LOW_PRIORITY、QUICK、IGNORE 的顺序出现
+        let modifiers = DeleteModifiers {
+            low_priority: self.match_keyword(Keyword::LOW_PRIORITY),
+            quick: self.match_keyword(Keyword::QUICK),
+            ignore: self.match_keyword(Keyword::IGNORE),
+        };
+
         // 必须有FROM子句
-        if !self.match_keyword("FROM") {
-            return Err(self.get_parse_error(&format!("Expected FROM, found {:?}", self.peek())));
+        if !self.match_keyword(Keyword::FROM) {
+            return Err(self.expected("FROM"));
         }
 
         // 解析FROM的表引用
@@ -41,7 +49,7 @@ impl DeleteStatementParser for Parser {
         let mut current_idx: u8 = FROM_IDX;
 
         // 可选的WHERE子句
-        let where_clause = if self.match_keyword("WHERE") {
+        let where_clause = if self.match_keyword(Keyword::WHERE) {
             current_idx = self.move_current_idx(current_idx, WHERE_IDX,get_clause_name)?;
             Some(self.parse_expr(0)?)
         } else {
@@ -49,21 +57,18 @@ impl DeleteStatementParser for Parser {
         };
 
         // 可选的ORDER BY子句
-        let order_by = if self.match_keyword("ORDER") {
-            if !self.match_keyword("BY") {
-                return Err(self.get_parse_error(&format!(
-                    "Expected BY after ORDER, found {:?}",
-                    self.peek()
-                )));
+        let order_by = if self.match_keyword(Keyword::ORDER) {
+            if !self.match_keyword(Keyword::BY) {
+                return Err(self.expected("BY after ORDER"));
             }
             current_idx = self.move_current_idx(current_idx, ORDER_BY_IDX,get_clause_name)?;
-            Some(self.parse_order_by()?)
+            Some(self.parse_order_by(false)?)
         } else {
             None
         };
 
         // 可选的LIMIT子句
-        let limit = if self.match_keyword("LIMIT") {
+        let limit = if self.match_keyword(Keyword::LIMIT) {
             // Since this is the last clause, we don't need to store the updated index
             self.move_current_idx(current_idx, LIMIT_IDX,get_clause_name)?;
             Some(self.parse_limit()?)
@@ -73,6 +78,7 @@ impl DeleteStatementParser for Parser {
 
         // 完成DELETE语句解析
         Ok(DeleteStatement {
+            modifiers,
             table,
             where_clause,
             order_by,
@@ -95,7 +101,8 @@ fn get_clause_name(idx: u8) -> &'static str {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::ast::expr::{BinaryOperator, Expr, LimitClause, OrderByExpr, Value,LogicalOperator};
+    use crate::ast::common::ObjectName;
+    use crate::ast::expr::{BinaryOperator, Expr, LimitClause, OrderByExpr, SortKey, Value};
 
     #[test]
     fn test_delete_parser()  {
@@ -104,18 +111,25 @@ mod test {
         let result = parser.parse_delete_statement();
         if let Ok(delete) = result {
             let expect = DeleteStatement {
+                modifiers: DeleteModifiers::default(),
                 table: TableReference {
-                    name: "users".to_string(),
+                    name: ObjectName::new(["users"]),
+                    partitions: None,
                     alias: None,
+                    index_hints: Vec::new(),
+                    sample: None,
+                    subquery: None,
+                    function: None,
+                    lateral: false,
                 },
                 where_clause: Some(Expr::BinaryOp {
-                    left: Box::new(Expr::Identifier("id".to_string())),
+                    left: Box::new(Expr::Identifier(ObjectName::new(["id"]))),
                     op: BinaryOperator::Eq,
                     right: Box::new(Expr::Literal(Value::Integer(1))),
                 }),
                 order_by:Some(vec![
                     OrderByExpr {
-                        expr: Expr::Identifier("name".to_string()),
+                        expr: SortKey::Expr(Expr::Identifier(ObjectName::new(["name"]))),
                         asc:true,
                     }
                 ]),
@@ -139,53 +153,28 @@ mod test {
         let result = parser.parse_delete_statement();
         if let Ok(delete) = result {
             let expect = DeleteStatement {
+                modifiers: DeleteModifiers::default(),
                 table: TableReference {
-                    name: "employees".to_string(),
+                    name: ObjectName::new(["employees"]),
+                    partitions: None,
                     alias: Some("e".to_string()),
+                    index_hints: Vec::new(),
+                    sample: None,
+                    subquery: None,
+                    function: None,
+                    lateral: false,
                 },
-                where_clause: Some(Expr::LogicalOp {
-                    op:LogicalOperator::Or,
-                    expressions:vec![
-                        Expr::LogicalOp {
-                            op:LogicalOperator::And,
-                            expressions:vec![
-                                Expr::BinaryOp {
-                                    left: Box::new(Expr::Identifier("e.department".to_string())),
-                                    op: BinaryOperator::Eq,
-                                    right: Box::new(Expr::Literal(Value::String("IT".to_string()))),
-                                },
-                                Expr::BinaryOp {
-                                    left: Box::new(Expr::Identifier("e.salary".to_string())),
-                                    op: BinaryOperator::Gt,
-                                    right: Box::new(Expr::Literal(Value::Integer(100000))),
-                                }
-                            ]
-                        },
-                        Expr::LogicalOp {
-                            op:LogicalOperator::And,
-                            expressions:vec![
-                                Expr::BinaryOp {
-                                    left: Box::new(Expr::Identifier("e.last_active".to_string())),
-                                    op: BinaryOperator::Lt,
-                                    right: Box::new(Expr::Literal(Value::String("2023-01-01".to_string()))),
-                                },
-                                Expr::BinaryOp {
-                                    left: Box::new(Expr::Identifier("e.status".to_string())),
-                                    op: BinaryOperator::Eq,
-                                    right: Box::new(Expr::Literal(Value::String("inactive".to_string()))),
-                                }
-                            ]
-                        }
-
-                    ]
-                }),
+                where_clause: Some(crate::expr!(
+                    (col("e.department") = "IT" && col("e.salary") > 100000)
+                        || (col("e.last_active") < "2023-01-01" && col("e.status") = "inactive")
+                )),
                 order_by: Some(vec![
                     OrderByExpr {
-                        expr: Expr::Identifier("e.last_active".to_string()),
+                        expr: SortKey::Expr(Expr::Identifier(ObjectName::new(["e", "last_active"]))),
                         asc: false,
                     },
                     OrderByExpr {
-                        expr: Expr::Identifier("e.name".to_string()),
+                        expr: SortKey::Expr(Expr::Identifier(ObjectName::new(["e", "name"]))),
                         asc: true,
                     }
                 ]),
@@ -201,4 +190,27 @@ mod test {
         }
            
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_delete_modifiers() {
+        let sql = "DELETE LOW_PRIORITY QUICK IGNORE FROM logs WHERE id < 100";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_delete_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        assert_eq!(result.unwrap().modifiers, DeleteModifiers {
+            low_priority: true,
+            quick: true,
+            ignore: true,
+        });
+
+        let mut parser = Parser::new_from_sql("DELETE IGNORE FROM logs");
+        assert_eq!(parser.parse_delete_statement().unwrap().modifiers, DeleteModifiers {
+            ignore: true,
+            ..Default::default()
+        });
+
+        // 修饰符顺序错误
+        let mut parser = Parser::new_from_sql("DELETE IGNORE QUICK FROM logs");
+        assert!(parser.parse_delete_statement().is_err());
+    }
+}
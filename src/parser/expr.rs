@@ -1,8 +1,18 @@
-use super::{ParseError, Parser};
-use crate::ast::expr::{BinaryOperator, Expr, LogicalOperator, UnaryOperator, Value};
+use super::{Dialect, ParseError, Parser};
+#[cfg(feature = "arena")]
+use crate::ast::arena::{ExprArena, ExprId};
+use crate::ast::common::{DataType, Ident, ObjectName};
+use crate::ast::expr::{
+    AggregateFunction, BinaryOperator, Expr, FrameBound, FrameUnits, GroupConcat, IntervalUnit, LogicalOperator,
+    SearchMode, UnaryOperator, Value, WindowFrame, WindowSpec,
+};
+use crate::ast::select::SelectStatement;
+use crate::kerwords::{is_reserved_keyword, Keyword};
+use super::select::SelectStatementParser;
 use crate::token::Token;
 
 const MAX_EXPR_DEPTH: usize = 100;
+const MAX_SUBQUERY_DEPTH: usize = 16;
 
 /**
 * 递归下降解析器
@@ -19,44 +29,72 @@ impl Parser {
         if depth > MAX_EXPR_DEPTH {
             Err(self.get_parse_error("Expression nesting too deep"))
         } else {
+            #[cfg(feature = "metrics")]
+            self.collector.enter_expr(depth);
             // 先从最低优先级开始解析
-            self.parse_logical_or(depth)
+            self.parse_assignment(depth)
+        }
+    }
+
+    /// 解析 VALUES 列表中的值或 SET 赋值的右侧，单独的 DEFAULT 表示列的默认值
+    pub fn parse_assigned_value(&mut self) -> Result<Expr, ParseError> {
+        if self.is_keyword(Keyword::DEFAULT) && self.peek_n(1) != Some(&Token::Punctuator('(')) {
+            self.consume_token();
+            return Ok(Expr::Literal(Value::DEFAULT));
+        }
+        self.parse_expr(0)
+    }
+
+    // 解析用户变量赋值 @var := expr，优先级最低且右结合
+    fn parse_assignment(&mut self, depth: usize) -> Result<Expr, ParseError> {
+        let expr = self.parse_logical_or(depth)?;
+        if !self.is_operator(":=") {
+            return Ok(expr);
+        }
+        match expr {
+            Expr::Identifier(name) if name.0.len() == 1 && is_user_variable(&name.name().value) => {
+                self.consume_token();
+                let value = self.parse_expr(depth + 1)?;
+                Ok(Expr::Assignment { variable: name.name().value.to_string(), value: Box::new(value) })
+            }
+            _ => Err(self.get_parse_error("Only a user variable can be assigned with :=")),
         }
     }
 
     // 解析OR表达式（最低优先级）
     fn parse_logical_or(&mut self, depth: usize) -> Result<Expr, ParseError> {
-        let mut expr = self.parse_logical_and(depth)?;
-        // 这里使用while是因为or可以连续使用
-        while self.match_keyword("OR") {
-            let right = self.parse_logical_and(depth)?;
-            expr = Expr::LogicalOp {
-                op: LogicalOperator::Or,
-                expressions: vec![expr, right],
-            };
+        let first = self.parse_logical_and(depth)?;
+        // 连续的 OR 收集到同一个列表中，不会随项数增加嵌套深度
+        let mut expressions = vec![first];
+        while self.match_keyword(Keyword::OR) {
+            expressions.push(self.parse_logical_and(depth)?);
         }
-
-        Ok(expr)
+        Ok(Self::logical_chain(LogicalOperator::Or, expressions))
     }
 
     // 下一优先级：AND
     fn parse_logical_and(&mut self, depth: usize) -> Result<Expr, ParseError> {
-        let mut expr = self.parse_not(depth)?;
-        // 这里使用while是因为and可以连续使用
-        while self.match_keyword("AND") {
-            let right = self.parse_not(depth)?;
-            expr = Expr::LogicalOp {
-                op: LogicalOperator::And,
-                expressions: vec![expr, right],
-            };
+        let first = self.parse_not(depth)?;
+        let mut expressions = vec![first];
+        while self.match_keyword(Keyword::AND) {
+            expressions.push(self.parse_not(depth)?);
+        }
+        Ok(Self::logical_chain(LogicalOperator::And, expressions))
+    }
+
+    // 只有一项时不构造 LogicalOp
+    fn logical_chain(op: LogicalOperator, mut expressions: Vec<Expr>) -> Expr {
+        if expressions.len() == 1 {
+            expressions.pop().unwrap()
+        } else {
+            Expr::LogicalOp { op, expressions }
         }
-        Ok(expr)
     }
 
     // 在parse_logical_and之前添加
     fn parse_not(&mut self, depth: usize) -> Result<Expr, ParseError> {
         // 这里使用if是因为not不能连续使用
-        if self.match_keyword("NOT") {
+        if self.match_keyword(Keyword::NOT) {
             let expr = self.parse_comparison(depth)?;
             return Ok(Expr::LogicalOp {
                 op: LogicalOperator::Not,
@@ -70,8 +108,55 @@ impl Parser {
     fn parse_comparison(&mut self, depth: usize) -> Result<Expr, ParseError> {
         let left = self.parse_additive(depth)?; // 先解析加减法表达式
 
+        // [NOT] IN (...)、[NOT] BETWEEN low AND high、[NOT] LIKE pattern
+        let negated = self.is_keyword(Keyword::NOT)
+            && matches!(
+                self.peek_n(1),
+                Some(Token::Keyword { keyword: Keyword::IN | Keyword::BETWEEN | Keyword::LIKE, .. })
+            );
+        if negated {
+            self.consume_token();
+        }
+        if self.match_keyword(Keyword::IN) {
+            return self.parse_in_list(left, negated, depth);
+        }
+        if self.match_keyword(Keyword::BETWEEN) {
+            // 上下界只解析到加减运算，之后的 AND 属于 BETWEEN
+            let low = self.parse_additive(depth)?;
+            self.expect_keyword(Keyword::AND, "BETWEEN lower bound")?;
+            let high = self.parse_additive(depth)?;
+            return Ok(Expr::Between { expr: Box::new(left), low: Box::new(low), high: Box::new(high), negated });
+        }
+        if self.match_keyword(Keyword::LIKE) {
+            let like = Expr::BinaryOp { left: Box::new(left), op: BinaryOperator::Like, right: Box::new(self.parse_additive(depth)?) };
+            // NOT LIKE 表示为 NOT (a LIKE b)
+            return Ok(if negated { Expr::LogicalOp { op: LogicalOperator::Not, expressions: vec![like] } } else { like });
+        }
+        // IS [NOT] NULL
+        if self.match_keyword(Keyword::IS) {
+            let negated = self.match_keyword(Keyword::NOT);
+            self.expect_keyword(Keyword::NULL, "IS")?;
+            return Ok(Expr::IsNull { expr: Box::new(left), negated });
+        }
+
         // 检查是否有比较运算符，这时不用while是因为不会有连续比较运算符
         if let Some(op) = self.match_comparison_operator() {
+            // PostgreSQL 的 expr op ANY(array)
+            if self.options.dialect == Dialect::Postgres
+                && self.is_keyword(Keyword::ANY)
+                && self.peek_n(1) == Some(&Token::Punctuator('('))
+            {
+                self.skip(2);
+                let array = if self.is_keyword(Keyword::SELECT) || self.is_keyword(Keyword::WITH) {
+                    Expr::Subquery(Box::new(self.parse_subquery()?))
+                } else {
+                    self.parse_expr(depth + 1)?
+                };
+                if !self.match_punctuator(')') {
+                    return Err(self.expected("')' after ANY"));
+                }
+                return Ok(Expr::Any { expr: Box::new(left), op, array: Box::new(array) });
+            }
             let right = self.parse_additive(depth)?;
             return Ok(Expr::BinaryOp {
                 left: Box::new(left),
@@ -83,30 +168,76 @@ impl Parser {
         Ok(left)
     }
 
+    // 解析 IN 之后括号中的列表或子查询
+    // 列表可能有成千上万项，逐项循环解析，并按逗号数量预先分配空间
+    fn parse_in_list(&mut self, expr: Expr, negated: bool, depth: usize) -> Result<Expr, ParseError> {
+        if !self.match_punctuator('(') {
+            return Err(self.expected("'(' after IN"));
+        }
+        let list = if self.is_keyword(Keyword::SELECT) || self.is_keyword(Keyword::WITH) {
+            vec![Expr::Subquery(Box::new(self.parse_subquery()?))]
+        } else {
+            let mut list = Vec::with_capacity(self.count_list_items());
+            loop {
+                list.push(self.parse_expr(depth + 1)?);
+                if !self.match_punctuator(',') {
+                    break;
+                }
+            }
+            list
+        };
+        if !self.match_punctuator(')') {
+            return Err(self.expected("')' after IN list"));
+        }
+        Ok(Expr::In { expr: Box::new(expr), list, negated })
+    }
+
+    // 从当前位置到匹配的右括号之间，最外层用逗号分隔的项数
+    fn count_list_items(&self) -> usize {
+        let mut nesting = 0usize;
+        let mut items = 1;
+        for token in &self.tokens[self.current..] {
+            match token {
+                Token::Punctuator('(') => nesting += 1,
+                Token::Punctuator(')') if nesting == 0 => break,
+                Token::Punctuator(')') => nesting -= 1,
+                Token::Punctuator(',') if nesting == 0 => items += 1,
+                _ => {}
+            }
+        }
+        items
+    }
+
+    // 解析括号中的子查询，左括号已经消费，右括号由调用者处理
+    pub(crate) fn parse_subquery(&mut self) -> Result<SelectStatement, ParseError> {
+        if self.subquery_depth >= MAX_SUBQUERY_DEPTH {
+            return Err(self.get_parse_error("Subquery nesting too deep"));
+        }
+        self.subquery_depth += 1;
+        let result = self.parse_select_statement();
+        self.subquery_depth -= 1;
+        result
+    }
+
     // 下一优先级,解析加法和减法
     fn parse_additive(&mut self, depth: usize) -> Result<Expr, ParseError> {
         let mut expr = self.parse_multiplicative(depth)?;
 
-        while let Some(token) = self.peek() {
-            match token.clone() {
-                Token::Operator(op) if op == "+" || op == "-" => {
-                    self.consume_token(); // 消费token
-
-                    let binary_op = if op == "+" {
-                        BinaryOperator::Plus
-                    } else {
-                        BinaryOperator::Minus
-                    };
-
-                    let right = self.parse_multiplicative(depth)?;
-                    expr = Expr::BinaryOp {
-                        left: Box::new(expr),
-                        op: binary_op,
-                        right: Box::new(right),
-                    };
-                }
-                _ => break,
-            }
+        loop {
+            let binary_op = if self.match_operator("+") {
+                BinaryOperator::Plus
+            } else if self.match_operator("-") {
+                BinaryOperator::Minus
+            } else {
+                break;
+            };
+
+            let right = self.parse_multiplicative(depth)?;
+            expr = Expr::BinaryOp {
+                left: Box::new(expr),
+                op: binary_op,
+                right: Box::new(right),
+            };
         }
 
         Ok(expr)
@@ -116,26 +247,21 @@ impl Parser {
     fn parse_multiplicative(&mut self, depth: usize) -> Result<Expr, ParseError> {
         let mut expr = self.parse_unary(depth)?;
 
-        while let Some(token) = self.peek() {
-            match token.clone() {
-                Token::Operator(op) if op == "*" || op == "/" => {
-                    self.consume_token(); // 消费token
-
-                    let binary_op = if op == "*" {
-                        BinaryOperator::Multiply
-                    } else {
-                        BinaryOperator::Divide
-                    };
-
-                    let right = self.parse_unary(depth)?;
-                    expr = Expr::BinaryOp {
-                        left: Box::new(expr),
-                        op: binary_op,
-                        right: Box::new(right),
-                    };
-                }
-                _ => break,
-            }
+        loop {
+            let binary_op = if self.match_operator("*") {
+                BinaryOperator::Multiply
+            } else if self.match_operator("/") {
+                BinaryOperator::Divide
+            } else {
+                break;
+            };
+
+            let right = self.parse_unary(depth)?;
+            expr = Expr::BinaryOp {
+                left: Box::new(expr),
+                op: binary_op,
+                right: Box::new(right),
+            };
         }
 
         Ok(expr)
@@ -144,35 +270,65 @@ impl Parser {
     // 新增 parse_unary 函数，处理一元操作符
     fn parse_unary(&mut self, depth: usize) -> Result<Expr, ParseError> {
         // 检查是否有一元操作符
-        if let Some(token) = self.peek() {
-            match token.clone() {
-                Token::Operator(op) if op == "+" || op == "-" => {
-                    self.consume_token(); // 消费操作符
-
-                    // 递归解析操作数
-                    let operand = self.parse_unary(depth)?; // 递归处理连续的一元操作符
-
-                    // 正号可以直接返回操作数，负号需要创建一元表达式
-                    if op == "-" {
-                        return Ok(Expr::UnaryOp {
-                            op: UnaryOperator::Minus,
-                            expr: Box::new(operand),
-                        });
-                    } else {
-                        // +号在数值表达式中可以忽略
-                        return Ok(operand);
-                    }
+        let negative = self.is_operator("-");
+        if negative || self.is_operator("+") {
+            self.consume_token(); // 消费操作符
+
+            // 递归解析操作数
+            let operand = self.parse_unary(depth)?; // 递归处理连续的一元操作符
+
+            // +号在数值表达式中可以忽略，负号需要创建一元表达式
+            if !negative {
+                return Ok(operand);
+            }
+            // 负号紧跟数字字面量时直接得到负数，例如 VALUES (-1, -2.5)
+            if let Expr::Literal(value) = &operand {
+                if let Some(negated) = value.negate() {
+                    return Ok(Expr::Literal(negated));
                 }
-                _ => {}
             }
+            return Ok(Expr::UnaryOp {
+                op: UnaryOperator::Minus,
+                expr: Box::new(operand),
+            });
         }
 
         // 没有一元操作符，继续解析基本表达式
-        self.parse_primary(depth)
+        let mut expr = self.parse_primary(depth)?;
+        // PostgreSQL 的数组下标，可以连续使用，例如 matrix[1][2]
+        while self.options.dialect == Dialect::Postgres && self.match_punctuator('[') {
+            let index = self.parse_expr(depth + 1)?;
+            if !self.match_punctuator(']') {
+                return Err(self.expected("']' after array subscript"));
+            }
+            expr = Expr::Subscript { expr: Box::new(expr), index: Box::new(index) };
+        }
+        // 后缀的 COLLATE 优先级高于所有二元操作符
+        while self.match_keyword(Keyword::COLLATE) {
+            let collation = match self.peek() {
+                Some(Token::Identifier(name)) | Some(Token::QuotedIdentifier(name)) | Some(Token::StringLiteral(name)) => {
+                    name.to_owned()
+                }
+                _ => return Err(self.expected("collation name after COLLATE")),
+            };
+            self.consume_token();
+            expr = Expr::Collate {
+                expr: Box::new(expr),
+                collation,
+            };
+        }
+        Ok(expr)
+    }
+
+    /// 解析表达式并放入arena，返回根节点的索引
+    #[cfg(feature = "arena")]
+    pub fn parse_expr_in(&mut self, arena: &mut ExprArena) -> Result<ExprId, ParseError> {
+        let expr = self.parse_expr(0)?;
+        Ok(arena.alloc(&expr))
     }
 
     // 这里左括号已经解析了
-    fn parse_function_args(&mut self) -> Result<Vec<Expr>, ParseError> {
+    pub(crate) fn parse_function_args(&mut self) -> Result<Vec<Expr>, ParseError> {
         let mut args = Vec::new();
 
         // 优化: 先检查是否为空参数列表
@@ -198,75 +354,477 @@ impl Parser {
                 break;
             } else {
                 // 既不是逗号也不是右括号，语法错误
-                return Err(self.get_parse_error(
-                    "Expected comma or closing parenthesis after function argument"
-                ));
+                return Err(self.expected("comma or closing parenthesis after function argument"));
             }
         }
 
         Ok(args)
     }
 
+    // 名称后面紧跟左括号时为函数调用，否则为列名
+    fn parse_name_or_function_call(&mut self, name: Ident) -> Result<Expr, ParseError> {
+        if self.match_punctuator('(') {
+            if name.quote_style.is_none() {
+                if name.value.eq_ignore_ascii_case("GROUP_CONCAT") {
+                    return self.parse_group_concat();
+                }
+                if name.value.eq_ignore_ascii_case("EXTRACT") {
+                    return self.parse_extract();
+                }
+                if name.value.eq_ignore_ascii_case("SUBSTRING") || name.value.eq_ignore_ascii_case("SUBSTR") {
+                    return self.parse_substring(&name.value);
+                }
+                if let Some(func) = AggregateFunction::from_name(&name.value) {
+                    let aggregate = self.parse_aggregate(func)?;
+                    return self.parse_over(aggregate);
+                }
+            }
+            let args = self.parse_function_args()?;
+            self.parse_over(Expr::FunctionCall { name: name.value.into(), args })
+        } else {
+            Ok(Expr::Identifier(ObjectName(vec![self.intern_ident(name)])))
+        }
+    }
+
+    // 函数调用之后可选的 OVER w 或 OVER (...)，有则构造窗口函数
+    fn parse_over(&mut self, func: Expr) -> Result<Expr, ParseError> {
+        if !self.match_keyword(Keyword::OVER) {
+            return Ok(func);
+        }
+        let over = if self.match_punctuator('(') {
+            let spec = self.parse_window_spec()?;
+            if !self.match_punctuator(')') {
+                return Err(self.expected("')' after window specification"));
+            }
+            spec
+        } else {
+            WindowSpec { name: Some(self.parse_identifier("window name")?), ..Default::default() }
+        };
+        Ok(Expr::Window { func: Box::new(func), over: Box::new(over) })
+    }
+
+    /// 解析括号中的窗口定义 [name] [PARTITION BY ...] [ORDER BY ...] [frame]，括号由调用者处理
+    pub(crate) fn parse_window_spec(&mut self) -> Result<WindowSpec, ParseError> {
+        let name = if !self.is_keyword(Keyword::ROWS) && matches!(self.peek(), Some(Token::Identifier(_) | Token::QuotedIdentifier(_))) {
+            Some(self.parse_identifier("window name")?)
+        } else {
+            None
+        };
+        let mut partition_by = Vec::new();
+        if self.match_keyword(Keyword::PARTITION) {
+            if !self.match_keyword(Keyword::BY) {
+                return Err(self.expected("BY after PARTITION"));
+            }
+            loop {
+                partition_by.push(self.parse_expr(0)?);
+                if !self.match_punctuator(',') {
+                    break;
+                }
+            }
+        }
+        let order_by = if self.match_keyword(Keyword::ORDER) {
+            if !self.match_keyword(Keyword::BY) {
+                return Err(self.expected("BY after ORDER"));
+            }
+            self.parse_order_by(false)?
+        } else {
+            Vec::new()
+        };
+        let units = if self.match_keyword(Keyword::ROWS) {
+            Some(FrameUnits::Rows)
+        } else if self.match_keyword(Keyword::RANGE) {
+            Some(FrameUnits::Range)
+        } else {
+            None
+        };
+        let frame = match units {
+            Some(units) if self.match_keyword(Keyword::BETWEEN) => {
+                let start = self.parse_frame_bound()?;
+                if !self.match_keyword(Keyword::AND) {
+                    return Err(self.expected("AND in window frame"));
+                }
+                Some(WindowFrame { units, start, end: Some(self.parse_frame_bound()?) })
+            }
+            Some(units) => Some(WindowFrame { units, start: self.parse_frame_bound()?, end: None }),
+            None => None,
+        };
+        Ok(WindowSpec { name, partition_by, order_by, frame })
+    }
+
+    // UNBOUNDED PRECEDING | n PRECEDING | CURRENT ROW | n FOLLOWING | UNBOUNDED FOLLOWING
+    fn parse_frame_bound(&mut self) -> Result<FrameBound, ParseError> {
+        if self.match_keyword(Keyword::UNBOUNDED) {
+            if self.match_keyword(Keyword::PRECEDING) {
+                return Ok(FrameBound::UnboundedPreceding);
+            }
+            self.expect_keyword(Keyword::FOLLOWING, "UNBOUNDED")?;
+            return Ok(FrameBound::UnboundedFollowing);
+        }
+        if self.match_keyword(Keyword::CURRENT) {
+            self.expect_keyword(Keyword::ROW, "CURRENT")?;
+            return Ok(FrameBound::CurrentRow);
+        }
+        // 偏移量可以是数字或 INTERVAL，例如 INTERVAL 7 DAY PRECEDING
+        let offset = Box::new(self.parse_additive(1)?);
+        if self.match_keyword(Keyword::PRECEDING) {
+            Ok(FrameBound::Preceding(offset))
+        } else if self.match_keyword(Keyword::FOLLOWING) {
+            Ok(FrameBound::Following(offset))
+        } else {
+            Err(self.expected("PRECEDING or FOLLOWING in window frame"))
+        }
+    }
+
+    // COUNT(*)、COUNT([DISTINCT] expr, ...) 等聚合函数，左括号已经解析
+    fn parse_aggregate(&mut self, func: AggregateFunction) -> Result<Expr, ParseError> {
+        let distinct = self.match_keyword(Keyword::DISTINCT);
+        // * 只能单独作为 COUNT 的参数
+        if func == AggregateFunction::Count && !distinct && self.match_operator("*") {
+            if !self.match_punctuator(')') {
+                return Err(self.expected("closing parenthesis after COUNT(*"));
+            }
+            return Ok(Expr::Aggregate { func, distinct, args: vec![Expr::Wildcard] });
+        }
+        if self.is_punctuator(')') {
+            return Err(self.expected(&format!("argument for {}", func.name())));
+        }
+        let args = self.parse_function_args()?;
+        Ok(Expr::Aggregate { func, distinct, args })
+    }
+
+    // GROUP_CONCAT([DISTINCT] expr, ... [ORDER BY ...] [SEPARATOR 'str'])，左括号已经解析
+    fn parse_group_concat(&mut self) -> Result<Expr, ParseError> {
+        let distinct = self.match_keyword(Keyword::DISTINCT);
+        let mut args = vec![self.parse_expr(0)?];
+        while self.match_punctuator(',') {
+            args.push(self.parse_expr(0)?);
+        }
+        let order_by = if self.match_keyword(Keyword::ORDER) {
+            if !self.match_keyword(Keyword::BY) {
+                return Err(self.expected("BY after ORDER"));
+            }
+            self.parse_order_by(true)?
+        } else {
+            Vec::new()
+        };
+        let separator = if self.match_keyword(Keyword::SEPARATOR) {
+            let Some(Token::StringLiteral(separator)) = self.peek() else {
+                return Err(self.expected("string after SEPARATOR"));
+            };
+            let separator = separator.to_owned();
+            self.consume_token();
+            Some(separator)
+        } else {
+            None
+        };
+        if !self.match_punctuator(')') {
+            return Err(self.expected("closing parenthesis after GROUP_CONCAT arguments"));
+        }
+        Ok(Expr::GroupConcat(Box::new(GroupConcat { distinct, args, order_by, separator })))
+    }
+
+    // 解析 INTERVAL 关键字之后的值和单位
+    fn parse_interval(&mut self, depth: usize) -> Result<Expr, ParseError> {
+        let value = self.parse_additive(depth + 1)?;
+        let unit = self.match_interval_unit().ok_or_else(|| self.expected("interval unit such as DAY or HOUR"))?;
+        Ok(Expr::Interval { value: Box::new(value), unit })
+    }
+
+    // 时间单位可能是标识符、关键字或类型名，例如 DAY、YEAR
+    fn match_interval_unit(&mut self) -> Option<IntervalUnit> {
+        let unit = match self.peek() {
+            Some(Token::Identifier(word) | Token::Keyword { text: word, .. } | Token::DataType(word)) => {
+                IntervalUnit::from_name(word)
+            }
+            _ => None,
+        };
+        if unit.is_some() {
+            self.consume_token();
+        }
+        unit
+    }
+
+    // EXTRACT(unit FROM expr)，左括号已经解析
+    fn parse_extract(&mut self) -> Result<Expr, ParseError> {
+        let unit = self.match_interval_unit().ok_or_else(|| self.expected("unit such as YEAR or MONTH in EXTRACT"))?;
+        self.expect_keyword(Keyword::FROM, "EXTRACT unit")?;
+        let expr = self.parse_expr(0)?;
+        if !self.match_punctuator(')') {
+            return Err(self.expected("')' after EXTRACT"));
+        }
+        Ok(Expr::Extract { unit, expr: Box::new(expr) })
+    }
+
+    // SUBSTRING(str FROM pos [FOR len]) 转换为 SUBSTRING(str, pos[, len])，左括号已经解析
+    fn parse_substring(&mut self, name: &str) -> Result<Expr, ParseError> {
+        let start = self.current;
+        let expr = self.parse_expr(0)?;
+        if !self.match_keyword(Keyword::FROM) {
+            // 逗号分隔参数的普通写法
+            self.current = start;
+            let args = self.parse_function_args()?;
+            return Ok(Expr::FunctionCall { name: name.into(), args });
+        }
+        let mut args = vec![expr, self.parse_expr(0)?];
+        if self.match_keyword(Keyword::FOR) {
+            args.push(self.parse_expr(0)?);
+        }
+        if !self.match_punctuator(')') {
+            return Err(self.expected("')' after SUBSTRING"));
+        }
+        Ok(Expr::FunctionCall { name: name.into(), args })
+    }
+
+    // CASE [operand] WHEN ... THEN ... [ELSE ...] END，CASE 已经消费
+    fn parse_case(&mut self, depth: usize) -> Result<Expr, ParseError> {
+        let operand = if self.is_keyword(Keyword::WHEN) { None } else { Some(Box::new(self.parse_expr(depth + 1)?)) };
+        let mut conditions = Vec::new();
+        let mut results = Vec::new();
+        while self.match_keyword(Keyword::WHEN) {
+            conditions.push(self.parse_expr(depth + 1)?);
+            self.expect_keyword(Keyword::THEN, "WHEN condition")?;
+            results.push(self.parse_expr(depth + 1)?);
+        }
+        if conditions.is_empty() {
+            return Err(self.expected("WHEN in CASE"));
+        }
+        let else_result = if self.match_keyword(Keyword::ELSE) { Some(Box::new(self.parse_expr(depth + 1)?)) } else { None };
+        self.expect_keyword(Keyword::END, "CASE")?;
+        Ok(Expr::Case { operand, conditions, results, else_result })
+    }
+
+    // 解析 CAST 关键字之后的 (expr AS type)，当前token为左括号
+    fn parse_cast(&mut self, depth: usize) -> Result<Expr, ParseError> {
+        self.consume_token();
+        let expr = self.parse_expr(depth + 1)?;
+        if !self.match_keyword(Keyword::AS) {
+            return Err(self.expected("AS in CAST"));
+        }
+        // MySQL 转换为整数的 SIGNED [INTEGER]、UNSIGNED [INTEGER]
+        let integer = if self.match_keyword(Keyword::SIGNED) {
+            Some("SIGNED")
+        } else if self.match_keyword(Keyword::UNSIGNED) {
+            Some("UNSIGNED")
+        } else {
+            None
+        };
+        let data_type = match integer {
+            Some(name) => {
+                let int = |name: &str| name.eq_ignore_ascii_case("INTEGER") || name.eq_ignore_ascii_case("INT");
+                if matches!(self.peek(), Some(Token::DataType(name) | Token::Identifier(name)) if int(name)) {
+                    self.consume_token();
+                }
+                DataType { name: name.to_string(), length: None, values: None, unsigned: false }
+            }
+            None => self.parse_data_type()?,
+        };
+        if !self.match_punctuator(')') {
+            return Err(self.expected("')' after CAST"));
+        }
+        Ok(Expr::Cast { expr: Box::new(expr), data_type })
+    }
+
+    // 解析 ARRAY 之后方括号中的元素，当前token为左方括号
+    fn parse_array(&mut self, depth: usize) -> Result<Expr, ParseError> {
+        self.consume_token();
+        let mut items = Vec::new();
+        if !self.match_punctuator(']') {
+            loop {
+                items.push(self.parse_expr(depth + 1)?);
+                if !self.match_punctuator(',') {
+                    break;
+                }
+            }
+            if !self.match_punctuator(']') {
+                return Err(self.expected("']' after array elements"));
+            }
+        }
+        Ok(Expr::Array(items))
+    }
+
+    // 解析 MATCH 关键字之后的全文检索，例如 (title, body) AGAINST ('rust' IN BOOLEAN MODE)
+    fn parse_match(&mut self, depth: usize) -> Result<Expr, ParseError> {
+        self.match_punctuator('(');
+        let mut columns = Vec::new();
+        loop {
+            match self.parse_primary(depth + 1)? {
+                Expr::Identifier(name) => columns.push(name),
+                _ => {
+                    self.back();
+                    return Err(self.expected("column name in MATCH"));
+                }
+            }
+            if !self.match_punctuator(',') {
+                break;
+            }
+        }
+        if !self.match_punctuator(')') {
+            return Err(self.expected("')' after MATCH columns"));
+        }
+        self.expect_keyword(Keyword::AGAINST, "MATCH(...)")?;
+        if !self.match_punctuator('(') {
+            return Err(self.expected("'(' after AGAINST"));
+        }
+        // 检索串之后的 IN 是模式，不是 IN 运算，因此只解析到加减运算
+        let against = self.parse_additive(depth + 1)?;
+        let mode = if self.match_keyword(Keyword::IN) {
+            let mode = if self.match_keyword(Keyword::BOOLEAN) {
+                SearchMode::Boolean
+            } else {
+                self.expect_keyword(Keyword::NATURAL, "IN")?;
+                self.expect_keyword(Keyword::LANGUAGE, "NATURAL")?;
+                SearchMode::NaturalLanguage
+            };
+            self.expect_keyword(Keyword::MODE, "search modifier")?;
+            if mode == SearchMode::NaturalLanguage && self.match_keyword(Keyword::WITH) {
+                self.expect_keyword(Keyword::QUERY, "WITH")?;
+                self.expect_keyword(Keyword::EXPANSION, "QUERY")?;
+                SearchMode::NaturalLanguageWithQueryExpansion
+            } else {
+                mode
+            }
+        } else if self.match_keyword(Keyword::WITH) {
+            self.expect_keyword(Keyword::QUERY, "WITH")?;
+            self.expect_keyword(Keyword::EXPANSION, "QUERY")?;
+            SearchMode::QueryExpansion
+        } else {
+            SearchMode::NaturalLanguage
+        };
+        if !self.match_punctuator(')') {
+            return Err(self.expected("')' after AGAINST expression"));
+        }
+        Ok(Expr::Match { columns, against: Box::new(against), mode })
+    }
+
+    /// 把数字token转换为字面量
+    pub(crate) fn numeric_literal(&self, n: String) -> Result<Expr, ParseError> {
+        // 带指数的为浮点数，只有小数点的默认为定点小数
+        if n.contains(['e', 'E']) || (n.contains('.') && !self.options.exact_decimals) {
+            // 尝试解析为浮点数
+            match n.parse::<f64>() {
+                Ok(f) => Ok(Expr::Literal(Value::Float(f))),
+                Err(_) => Err(self.invalid_value("float", &n)),
+            }
+        } else if n.contains('.') {
+            Ok(Expr::Literal(Value::Decimal(n)))
+        } else {
+            // 尝试解析为整数
+            match n.parse::<i64>() {
+                Ok(i) => Ok(Expr::Literal(Value::Integer(i))),
+                Err(_) => Err(self.invalid_value("integer", &n)),
+            }
+        }
+    }
+
     // 解析无法再分解的表达式
     fn parse_primary(&mut self, depth: usize) -> Result<Expr, ParseError> {
-        let c_token = self.consume_token()
-            .ok_or_else(|| self.get_parse_error("Expected primary expression, but found none"))?
-            .clone();
+        // 构造AST需要token中的字符串，这里是表达式解析中唯一复制token的地方
+        let Some(c_token) = self.consume_token().cloned() else {
+            return Err(self.expected("expression"));
+        };
 
         match c_token {
             // 字面量处理
-            Token::NumericLiteral(n) => {
-                // 检查是否包含小数点
-                if n.contains('.') {
-                    // 尝试解析为浮点数
-                    match n.parse::<f64>() {
-                        Ok(f) => Ok(Expr::Literal(Value::Float(f))),
-                        Err(_) => Err(self.get_parse_error(&format!("Invalid float: {}", n))),
-                    }
+            Token::NumericLiteral(n) => self.numeric_literal(n),
+            // 处理其他可能的情况
+            Token::Keyword { keyword: Keyword::NULL, .. } => Ok(Expr::Literal(Value::Null)),
+            // DEFAULT(col) 是取列默认值的函数，单独的 DEFAULT 只能出现在 parse_assigned_value 处理的位置
+            Token::Keyword { keyword: Keyword::DEFAULT, .. } => {
+                if self.is_punctuator('(') {
+                    self.parse_name_or_function_call(Ident::new("DEFAULT"))
                 } else {
-                    // 尝试解析为整数
-                    match n.parse::<i64>() {
-                        Ok(i) => Ok(Expr::Literal(Value::Integer(i))),
-                        Err(_) => Err(self.get_parse_error(&format!("Invalid integer: {}", n))),
-                    }
+                    self.back();
+                    Err(self.get_parse_error("DEFAULT is only allowed as a value in VALUES or SET"))
                 }
             }
-            // 处理其他可能的情况
-            Token::Keyword(k) if k.to_uppercase() == "NULL" => Ok(Expr::Literal(Value::Null)),
-            Token::Keyword(k) if k.to_uppercase() == "DEFAULT" => Ok(Expr::Literal(Value::DEFAULT)),
             Token::StringLiteral(s) => Ok(Expr::Literal(Value::String(s))),
+            // 时间间隔，例如 INTERVAL 7 DAY；INTERVAL(N, N1, ...) 是比较函数
+            Token::Keyword { keyword: Keyword::INTERVAL, .. } => {
+                let start = self.current;
+                match self.parse_interval(depth) {
+                    Err(_) if self.tokens.get(start) == Some(&Token::Punctuator('(')) => {
+                        self.current = start;
+                        self.parse_name_or_function_call(Ident::new("INTERVAL"))
+                    }
+                    result => result,
+                }
+            }
+            Token::Keyword { keyword: Keyword::MATCH, .. } if self.is_punctuator('(') => self.parse_match(depth),
+            Token::Keyword { keyword: Keyword::CASE, .. } => self.parse_case(depth),
+            // EXISTS (SELECT ...)
+            Token::Keyword { keyword: Keyword::EXISTS, .. } if self.is_punctuator('(') => {
+                self.consume_token();
+                let select = self.parse_subquery()?;
+                if !self.match_punctuator(')') {
+                    return Err(self.expected("')' after subquery"));
+                }
+                Ok(Expr::Exists(Box::new(select)))
+            }
+            // 日期时间字面量，例如 DATE '1998-12-01'，表示为 CAST('1998-12-01' AS DATE)
+            Token::DataType(name)
+                if ["DATE", "TIME", "TIMESTAMP", "DATETIME"].iter().any(|t| name.eq_ignore_ascii_case(t))
+                    && matches!(self.peek(), Some(Token::StringLiteral(_))) =>
+            {
+                let value = self.parse_string_literal("date literal")?;
+                let data_type = DataType { name: name.to_uppercase(), length: None, values: None, unsigned: false };
+                Ok(Expr::Cast { expr: Box::new(Expr::Literal(Value::String(value))), data_type })
+            }
+            Token::Keyword { keyword: Keyword::CAST, .. } if self.is_punctuator('(') => self.parse_cast(depth),
+            // VALUES(col) 在 ON DUPLICATE KEY UPDATE 中表示要插入的值
+            Token::Keyword { keyword: Keyword::VALUES, .. } if self.is_punctuator('(') => {
+                self.parse_name_or_function_call(Ident::new("VALUES"))
+            }
+            // PostgreSQL 的数组字面量，例如 ARRAY[1, 2, 3]
+            Token::Identifier(ident)
+                if self.options.dialect == Dialect::Postgres
+                    && ident.eq_ignore_ascii_case("ARRAY")
+                    && self.is_punctuator('[') =>
+            {
+                self.parse_array(depth)
+            }
             // 标识符处理
             Token::Identifier(ident) => {
-                // 检查是否是函数调用
-                if self.match_punctuator('(') {
-                    let args = self.parse_function_args()?;
-                    Ok(Expr::FunctionCall {
-                        name: ident.clone(),
-                        args,
-                    })
-                } else {
-                    Ok(Expr::Identifier(ident.clone()))
+                // 用户变量或系统变量，例如 @total、@@version
+                if ident == "@" {
+                    self.current -= 1;
+                    return Ok(Expr::Identifier(ObjectName::new([self.parse_variable_name()?])));
+                }
+                // 字符集引导符，例如 _utf8mb4'text'
+                if ident.starts_with('_') && ident.len() > 1 {
+                    if let Some(Token::StringLiteral(s)) = self.peek() {
+                        let literal = Expr::Literal(Value::String(s.to_owned()));
+                        self.consume_token();
+                        return Ok(Expr::Introducer {
+                            charset: ident[1..].to_string(),
+                            expr: Box::new(literal),
+                        });
+                    }
                 }
+                self.parse_name_or_function_call(Ident::new(ident))
             }
+            // 非保留字和不带参数的类型名作为列名或函数名，例如 status、key、timestamp
+            Token::Keyword { keyword, text } if !keyword.is_reserved(self.options.dialect) => self.parse_name_or_function_call(Ident::new(text)),
+            Token::DataType(name) if !is_reserved_keyword(&name, self.options.dialect) => {
+                self.parse_name_or_function_call(Ident::new(name))
+            }
+            // 反引号包裹的标识符，例如 `order`、`VALUES`(stock)
+            Token::QuotedIdentifier(ident) => self.parse_name_or_function_call(Ident::with_quote('`', ident)),
             // 处理带有限定符的标识符
-            Token::QualifiedIdentifier { qualifier, name } => {
-                Ok(Expr::Identifier(format!(
-                    "{}.{}",
-                    qualifier, name
-                )))
+            Token::QualifiedIdentifier(parts) => Ok(Expr::Identifier(self.intern_object_name(ObjectName(parts)))),
+            // 子查询，例如 (SELECT MAX(id) FROM users)
+            Token::Punctuator('(') if self.is_keyword(Keyword::SELECT) || self.is_keyword(Keyword::WITH) => {
+                let select = self.parse_subquery()?;
+                if !self.match_punctuator(')') {
+                    return Err(self.expected("')' after subquery"));
+                }
+                Ok(Expr::Subquery(Box::new(select)))
             }
             // 括号表达式
             Token::Punctuator('(') => {
                 let expr = self.parse_expr(depth + 1)?;
 
                 if !self.match_punctuator(')') {
-                    let next_token = self
-                        .peek()
-                        .map_or("end of input".to_string(), |t| format!("{:?}", t));
-                    return Err(self.get_parse_error(&format!(
-                        "Expect ')' expression, found: {:?}",
-                        next_token
-                    )));
+                    return Err(self.expected("')' after expression"));
                 }
                 // 如果上述检查通过，则右括号本身已经被消费
                 Ok(expr)
@@ -275,32 +833,443 @@ impl Parser {
             // 处理星号
             Token::Punctuator('*')  => Ok(Expr::Wildcard),
             // 如果没有匹配的情况，返回错误
-            _ => Err(self.get_parse_error(&format!(
-                "Unexpected token in primary expression: {:?}",
-                c_token
-            ))),
+            _ => {
+                // 回到出错的token，让错误指向它
+                self.back();
+                Err(self.expected("expression"))
+            }
         }
     }
 
     fn match_comparison_operator(&mut self) -> Option<BinaryOperator> {
-        if let Some(Token::Operator(op)) = self.peek() {
-            let op = op.to_owned();
-            // 仅在匹配到时才能消耗token
-            let r = match op.as_str() {
-                "=" => Some(BinaryOperator::Eq),
-                "!=" | "<>" => Some(BinaryOperator::NotEq),
-                "<" => Some(BinaryOperator::Lt),
-                "<=" => Some(BinaryOperator::LtEq),
-                ">" => Some(BinaryOperator::Gt),
-                ">=" => Some(BinaryOperator::GtEq),
-                _ => None,
+        let Some(Token::Operator(op)) = self.peek() else {
+            return None;
+        };
+        let op = match op.as_str() {
+            "=" => BinaryOperator::Eq,
+            "!=" | "<>" => BinaryOperator::NotEq,
+            "<" => BinaryOperator::Lt,
+            "<=" => BinaryOperator::LtEq,
+            ">" => BinaryOperator::Gt,
+            ">=" => BinaryOperator::GtEq,
+            _ => return None,
+        };
+        // 仅在匹配到时才能消耗token
+        self.consume_token();
+        Some(op)
+    }
+}
+
+// 用户变量以单个 @ 开头，@@ 开头的是系统变量
+fn is_user_variable(name: &str) -> bool {
+    name.starts_with('@') && !name.starts_with("@@")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ast::expr::SortKey;
+    use crate::parser::ParserOptions;
+
+    #[test]
+    fn test_charset_introducer_and_collate() {
+        let mut parser = Parser::new_from_sql("name COLLATE utf8mb4_bin = _utf8mb4'Alice'");
+        let result = parser.parse_expr(0);
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        assert_eq!(result.unwrap(), Expr::BinaryOp {
+            left: Box::new(Expr::Collate {
+                expr: Box::new(Expr::Identifier(ObjectName::new(["name"]))),
+                collation: "utf8mb4_bin".to_string(),
+            }),
+            op: BinaryOperator::Eq,
+            right: Box::new(Expr::Introducer {
+                charset: "utf8mb4".to_string(),
+                expr: Box::new(Expr::Literal(Value::String("Alice".to_string()))),
+            }),
+        });
+
+        // 以下划线开头的普通标识符不受影响
+        let mut parser = Parser::new_from_sql("_tmp + 1");
+        assert!(matches!(parser.parse_expr(0), Ok(Expr::BinaryOp { op: BinaryOperator::Plus, .. })));
+    }
+
+    #[test]
+    fn test_interval() {
+        let interval = |value: Expr, unit| Expr::Interval { value: Box::new(value), unit };
+        let mut parser = Parser::new_from_sql("created_at + INTERVAL 7 DAY > NOW()");
+        let Ok(Expr::BinaryOp { left, op: BinaryOperator::Gt, .. }) = parser.parse_expr(0) else {
+            panic!("Expected comparison");
+        };
+        assert_eq!(*left, Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(ObjectName::new(["created_at"]))),
+            op: BinaryOperator::Plus,
+            right: Box::new(interval(Expr::Literal(Value::Integer(7)), IntervalUnit::Day)),
+        });
+
+        let mut parser = Parser::new_from_sql("DATE_ADD(ts, INTERVAL '1:30' hour_minute)");
+        let Ok(Expr::FunctionCall { args, .. }) = parser.parse_expr(0) else {
+            panic!("Expected function call");
+        };
+        assert_eq!(args[1], interval(Expr::Literal(Value::String("1:30".to_string())), IntervalUnit::HourMinute));
+
+        // YEAR 同时是类型名；值可以是表达式
+        let mut parser = Parser::new_from_sql("INTERVAL n * 2 YEAR");
+        assert!(matches!(parser.parse_expr(0), Ok(Expr::Interval { unit: IntervalUnit::Year, .. })));
+
+        // INTERVAL(N, N1, ...) 仍按函数解析
+        let mut parser = Parser::new_from_sql("INTERVAL(23, 1, 15, 17)");
+        assert!(matches!(parser.parse_expr(0), Ok(Expr::FunctionCall { name, args }) if name == "INTERVAL" && args.len() == 4));
+
+        let mut parser = Parser::new_from_sql("INTERVAL 1 FORTNIGHT");
+        assert!(parser.parse_expr(0).is_err());
+    }
+
+    #[test]
+    fn test_match_against() {
+        let mut parser = Parser::new_from_sql("MATCH(title, p.body) AGAINST ('+rust -java' IN BOOLEAN MODE) > 0.5");
+        let Ok(Expr::BinaryOp { left, op: BinaryOperator::Gt, .. }) = parser.parse_expr(0) else {
+            panic!("Expected comparison");
+        };
+        assert_eq!(*left, Expr::Match {
+            columns: vec![ObjectName::new(["title"]), ObjectName::new(["p", "body"])],
+            against: Box::new(Expr::Literal(Value::String("+rust -java".to_string()))),
+            mode: SearchMode::Boolean,
+        });
+
+        let modes = [
+            ("", SearchMode::NaturalLanguage),
+            (" IN NATURAL LANGUAGE MODE", SearchMode::NaturalLanguage),
+            (" IN NATURAL LANGUAGE MODE WITH QUERY EXPANSION", SearchMode::NaturalLanguageWithQueryExpansion),
+            (" with query expansion", SearchMode::QueryExpansion),
+        ];
+        for (modifier, expected) in modes {
+            let sql = format!("MATCH (doc) AGAINST (@q{})", modifier);
+            let result = Parser::new_from_sql(&sql).parse_expr(0);
+            assert!(matches!(result, Ok(Expr::Match { mode, .. }) if mode == expected), "{}", sql);
+        }
+
+        assert!(Parser::new_from_sql("MATCH(1) AGAINST ('x')").parse_expr(0).is_err());
+        assert!(Parser::new_from_sql("MATCH(a) AGAINST ('x' IN SOME MODE)").parse_expr(0).is_err());
+    }
+
+    #[test]
+    fn test_flat_logical_chain() {
+        // 连续的 OR/AND 是同一层列表，括号中的分组保持为子表达式
+        let terms: Vec<String> = (0..3000).map(|i| format!("id = {}", i)).collect();
+        let sql = format!("{} OR (a AND b AND c) OR d", terms.join(" OR "));
+        let Ok(Expr::LogicalOp { op: LogicalOperator::Or, expressions }) = Parser::new_from_sql(&sql).parse_expr(0) else {
+            panic!("Expected OR");
+        };
+        assert_eq!(expressions.len(), 3002);
+        assert!(matches!(&expressions[3000], Expr::LogicalOp { op: LogicalOperator::And, expressions } if expressions.len() == 3));
+        assert_eq!(expressions.iter().map(|e| e.iter().count()).max(), Some(4));
+
+        let Ok(Expr::LogicalOp { op: LogicalOperator::And, expressions }) =
+            Parser::new_from_sql("a AND b OR c AND NOT d AND e").parse_expr(0).map(|e| e.children()[1].clone())
+        else {
+            panic!("Expected AND");
+        };
+        assert_eq!(expressions.len(), 3);
+    }
+
+    #[test]
+    fn test_in_list_and_subquery() {
+        let mut parser = Parser::new_from_sql("id NOT IN (1, 2 + 3, (4)) AND name IN ('a')");
+        let Ok(Expr::LogicalOp { expressions, .. }) = parser.parse_expr(0) else {
+            panic!("Expected AND");
+        };
+        let Expr::In { list, negated: true, .. } = &expressions[0] else {
+            panic!("Expected NOT IN, found {:?}", expressions[0]);
+        };
+        assert_eq!(list.len(), 3);
+        assert!(matches!(list[1], Expr::BinaryOp { op: BinaryOperator::Plus, .. }));
+        assert!(matches!(&expressions[1], Expr::In { list, negated: false, .. } if list.len() == 1));
+
+        // IN 子查询统一表示为只有一个 Subquery 元素的列表
+        let mut parser = Parser::new_from_sql("owner IN (SELECT id FROM users WHERE age > (SELECT 18))");
+        let Ok(Expr::In { list, .. }) = parser.parse_expr(0) else {
+            panic!("Expected IN");
+        };
+        let [Expr::Subquery(select)] = list.as_slice() else {
+            panic!("Expected subquery, found {:?}", list);
+        };
+        assert!(matches!(&select.where_clause, Some(Expr::BinaryOp { right, .. }) if matches!(**right, Expr::Subquery(_))));
+        assert!(!parser.has_more());
+
+        // 大量的项逐项解析，不会因为递归过深而失败
+        let items = vec!["7"; 20_000].join(", ");
+        let mut parser = Parser::new_from_sql(&format!("id IN ({})", items));
+        assert!(matches!(parser.parse_expr(0), Ok(Expr::In { list, .. }) if list.len() == 20_000));
+
+        assert!(Parser::new_from_sql("id IN ()").parse_expr(0).is_err());
+        assert!(Parser::new_from_sql("id IN 1").parse_expr(0).is_err());
+        let nested = format!("{}1{}", "(SELECT ".repeat(40), ")".repeat(40));
+        assert!(Parser::new_from_sql(&nested).parse_expr(0).is_err());
+    }
+
+    #[test]
+    fn test_structured_identifier() {
+        let mut parser = Parser::new_from_sql("e.`department`");
+        let result = parser.parse_expr(0);
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let Expr::Identifier(name) = result.unwrap() else {
+            panic!("Expected identifier");
+        };
+        assert_eq!(name, ObjectName(vec![Ident::new("e"), Ident::with_quote('`', "department")]));
+        assert_eq!(name.qualifier(), &[Ident::new("e")]);
+        assert_eq!(name.name().value, "department");
+        // Display 保持以前的拼接形式，引号原样保留
+        assert_eq!(name.to_string(), "e.`department`");
+        assert_eq!(ObjectName::new(["e", "department"]).to_string(), "e.department");
+    }
+
+    #[test]
+    fn test_group_concat() {
+        let mut parser = Parser::new_from_sql("GROUP_CONCAT(DISTINCT name, '-', id ORDER BY name DESC, 2 SEPARATOR ', ')");
+        let Ok(Expr::GroupConcat(group_concat)) = parser.parse_expr(0) else {
+            panic!("Expected GROUP_CONCAT");
+        };
+        let GroupConcat { distinct, args, order_by, separator } = *group_concat;
+        assert!(distinct);
+        assert_eq!(args.len(), 3);
+        assert_eq!(order_by.len(), 2);
+        assert!(!order_by[0].asc);
+        assert_eq!(order_by[1].expr, SortKey::Ordinal(2));
+        assert_eq!(separator.as_deref(), Some(", "));
+        assert!(!parser.has_more());
+
+        // 参数中的表达式是普通的子节点
+        let expr = Parser::new_from_sql("group_concat(a + 1 ORDER BY b)").parse_expr(0).unwrap();
+        assert!(matches!(&expr, Expr::GroupConcat(g) if !g.distinct && g.separator.is_none()));
+        assert_eq!(expr.children().len(), 2);
+
+        // 带引号的名称是普通函数
+        let expr = Parser::new_from_sql("`GROUP_CONCAT`(a)").parse_expr(0).unwrap();
+        assert!(matches!(expr, Expr::FunctionCall { .. }));
+
+        for sql in ["GROUP_CONCAT()", "GROUP_CONCAT(a ORDER name)", "GROUP_CONCAT(a SEPARATOR b)", "GROUP_CONCAT(a b)"] {
+            assert!(Parser::new_from_sql(sql).parse_expr(0).is_err(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let expr = Parser::new_from_sql("COUNT(*)").parse_expr(0).unwrap();
+        assert!(expr.is_count_star() && expr.is_aggregate());
+        #[cfg(feature = "formatter")]
+        assert_eq!(expr.to_string(), "COUNT(*)");
+
+        let expr = Parser::new_from_sql("count(id)").parse_expr(0).unwrap();
+        assert!(matches!(&expr, Expr::Aggregate { func: AggregateFunction::Count, distinct: false, args } if args.len() == 1));
+        assert!(!expr.is_count_star());
+        #[cfg(feature = "formatter")]
+        assert_eq!(expr.to_string(), "COUNT(id)");
+
+        let expr = Parser::new_from_sql("COUNT(DISTINCT a, b)").parse_expr(0).unwrap();
+        assert!(matches!(&expr, Expr::Aggregate { distinct: true, args, .. } if args.len() == 2));
+        #[cfg(feature = "formatter")]
+        assert_eq!(expr.to_string(), "COUNT(DISTINCT a, b)");
+
+        let expr = Parser::new_from_sql("SUM(DISTINCT price * qty)").parse_expr(0).unwrap();
+        assert!(matches!(&expr, Expr::Aggregate { func: AggregateFunction::Sum, distinct: true, .. }));
+        assert_eq!(expr.children().len(), 1);
+
+        // 其它函数和带引号的名称仍然是普通的函数调用
+        for sql in ["LOWER(name)", "`COUNT`(id)"] {
+            let expr = Parser::new_from_sql(sql).parse_expr(0).unwrap();
+            assert!(matches!(expr, Expr::FunctionCall { .. }) && !expr.is_aggregate(), "{}", sql);
+        }
+
+        for sql in ["COUNT()", "COUNT(DISTINCT)", "COUNT(DISTINCT *)", "SUM(*)", "COUNT(* , a)", "MAX(a, *)"] {
+            assert!(Parser::new_from_sql(sql).parse_expr(0).is_err(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_assignment() {
+        for sql in ["@row := @row + 1", "@row:=@row+1"] {
+            let expr = Parser::new_from_sql(sql).parse_expr(0).unwrap();
+            let Expr::Assignment { variable, value } = &expr else {
+                panic!("Expected assignment, found {:?}", expr);
             };
-            if r.is_some() {
-                self.consume_token();
-            }
-            r
-        } else {
-            None
+            assert_eq!(variable, "@row");
+            assert!(matches!(**value, Expr::BinaryOp { op: BinaryOperator::Plus, .. }));
+            #[cfg(feature = "formatter")]
+            assert_eq!(expr.to_string(), "@row := @row + 1");
+        }
+
+        // 右结合，优先级低于 OR；作为操作数时输出括号
+        let expr = Parser::new_from_sql("@a := @b := x OR y").parse_expr(0).unwrap();
+        assert!(matches!(&expr, Expr::Assignment { value, .. } if matches!(**value, Expr::Assignment { .. })));
+        #[cfg(feature = "formatter")]
+        {
+            assert_eq!(expr.to_string(), "@a := @b := x OR y");
+            let expr = Parser::new_from_sql("(@n := 1) + 1").parse_expr(0).unwrap();
+            assert_eq!(expr.to_string(), "(@n := 1) + 1");
+        }
+
+        for sql in ["a := 1", "@@sql_mode := ''", "t.a := 1", "@a + 1 := 2", "@a :="] {
+            assert!(Parser::new_from_sql(sql).parse_expr(0).is_err(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_cast() {
+        let parse = |sql: &str| Parser::new_from_sql(sql).parse_expr(0);
+        let expr = parse("CAST(a + 1 AS DECIMAL(10,2))").unwrap();
+        #[cfg_attr(not(feature = "formatter"), allow(unused_variables))]
+        let Expr::Cast { expr: inner, data_type } = &expr else { panic!("expected CAST, got {:?}", expr) };
+        assert_eq!(data_type.name, "DECIMAL");
+        let spaced = parse("CAST(a AS DECIMAL(10, 2))").unwrap();
+        assert!(matches!(&spaced, Expr::Cast { data_type, .. } if data_type.length.as_deref() == Some("10,2")));
+        #[cfg(feature = "formatter")]
+        {
+            assert_eq!(inner.to_string(), "a + 1");
+            assert_eq!(expr.to_string(), "CAST(a + 1 AS DECIMAL(10,2))");
+            assert_eq!(parse("cast(b as unsigned integer)").unwrap().to_string(), "CAST(b AS UNSIGNED)");
+            assert_eq!(parse("CAST(x AS SIGNED) > 1").unwrap().to_string(), "CAST(x AS SIGNED) > 1");
+        }
+        for sql in ["CAST(a)", "CAST(a AS)", "CAST(a AS INT"] {
+            assert!(parse(sql).is_err(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_postgres_arrays() {
+        let options = ParserOptions { dialect: Dialect::Postgres, ..Default::default() };
+        let parse = |sql: &str| Parser::new_from_sql(sql).with_options(options).parse_expr(0);
+
+        let expr = parse("flags[1] = 'beta' AND 'beta' = ANY(flags) AND ARRAY[1, 2 + 3][2] > 0").unwrap();
+        let Expr::LogicalOp { expressions, .. } = &expr else {
+            panic!("Expected AND, found {:?}", expr);
+        };
+        assert_eq!(expressions[0], Expr::BinaryOp {
+            left: Box::new(Expr::Subscript {
+                expr: Box::new(Expr::Identifier(ObjectName::new(["flags"]))),
+                index: Box::new(Expr::Literal(Value::Integer(1))),
+            }),
+            op: BinaryOperator::Eq,
+            right: Box::new(Expr::Literal(Value::String("beta".to_string()))),
+        });
+        assert!(matches!(&expressions[1], Expr::Any { op: BinaryOperator::Eq, array, .. }
+            if **array == Expr::Identifier(ObjectName::new(["flags"]))));
+        let Expr::BinaryOp { left, .. } = &expressions[2] else {
+            panic!("Expected comparison, found {:?}", expressions[2]);
+        };
+        assert!(matches!(&**left, Expr::Subscript { expr, .. } if matches!(**expr, Expr::Array(ref items) if items.len() == 2)));
+        #[cfg(feature = "formatter")]
+        assert_eq!(expr.to_string(), "flags[1] = 'beta' AND 'beta' = ANY(flags) AND ARRAY[1, 2 + 3][2] > 0");
+
+        let expr = parse("id != ANY(SELECT id FROM banned)").unwrap();
+        assert!(matches!(&expr, Expr::Any { op: BinaryOperator::NotEq, array, .. } if matches!(**array, Expr::Subquery(_))));
+        #[cfg(feature = "formatter")]
+        {
+            assert_eq!(expr.to_string(), "id != ANY(SELECT id FROM banned)");
+            assert_eq!(parse("(a + b)[1] = ARRAY[]").unwrap().to_string(), "(a + b)[1] = ARRAY[]");
+        }
+        assert!(parse("(a + b)[1] = ARRAY[]").is_ok());
+
+        for sql in ["flags[1", "ARRAY[1, 2", "x = ANY(flags"] {
+            assert!(parse(sql).is_err(), "{}", sql);
+        }
+        // MySQL 方言中没有数组
+        assert!(Parser::new_from_sql("SELECT flags[1] FROM t").parse_statements().is_err());
+    }
+
+    #[test]
+    fn test_case_exists_extract() {
+        let parse = |sql: &str| Parser::new_from_sql(sql).parse_expr(0);
+        let expr = parse("CASE WHEN a > 0 THEN 'y' WHEN a < 0 THEN 'n' ELSE NULL END").unwrap();
+        let Expr::Case { operand: None, conditions, results, else_result: Some(else_result) } = &expr else {
+            panic!("expected CASE, got {:?}", expr);
+        };
+        assert_eq!((conditions.len(), results.len()), (2, 2));
+        assert_eq!(results[1], Expr::Literal(Value::String("n".to_string())));
+        assert_eq!(**else_result, Expr::Literal(Value::Null));
+        let expr = parse("CASE status WHEN 1 THEN 'on' END").unwrap();
+        assert!(matches!(&expr, Expr::Case { operand: Some(_), else_result: None, .. }));
+
+        let expr = parse("NOT EXISTS (SELECT 1 FROM t WHERE t.id = u.id)").unwrap();
+        let Expr::LogicalOp { op: LogicalOperator::Not, expressions } = &expr else {
+            panic!("expected NOT, got {:?}", expr);
+        };
+        assert!(matches!(&expressions[0], Expr::Exists(select) if select.where_clause.is_some()));
+
+        let expr = parse("EXTRACT(YEAR FROM o_orderdate)").unwrap();
+        assert_eq!(expr, Expr::Extract {
+            unit: IntervalUnit::Year,
+            expr: Box::new(Expr::Identifier(ObjectName::new(["o_orderdate"]))),
+        });
+        #[cfg(feature = "formatter")]
+        {
+            assert_eq!(parse("case x when 1 then 2 else 3 end").unwrap().to_string(), "CASE x WHEN 1 THEN 2 ELSE 3 END");
+            assert_eq!(expr.to_string(), "EXTRACT(YEAR FROM o_orderdate)");
+            assert_eq!(parse("exists (select 1)").unwrap().to_string(), "EXISTS (SELECT 1)");
         }
+        for sql in ["CASE END", "CASE WHEN a THEN 1", "CASE WHEN a 1 END", "EXTRACT(YEAR o_orderdate)", "EXTRACT(FOO FROM d)"] {
+            assert!(parse(sql).is_err(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_predicates() {
+        let parse = |sql: &str| Parser::new_from_sql(sql).parse_expr(0);
+        let column = |name: &str| Box::new(Expr::Identifier(ObjectName::new([name])));
+        let integer = |i: i64| Box::new(Expr::Literal(Value::Integer(i)));
+        assert_eq!(parse("age NOT BETWEEN 18 AND 30 + 1").unwrap(), Expr::Between {
+            expr: column("age"),
+            low: integer(18),
+            high: Box::new(Expr::BinaryOp { left: integer(30), op: BinaryOperator::Plus, right: integer(1) }),
+            negated: true,
+        });
+        // BETWEEN 中的 AND 不是逻辑运算
+        let expr = parse("a BETWEEN 1 AND 2 AND b IS NOT NULL").unwrap();
+        let Expr::LogicalOp { op: LogicalOperator::And, expressions } = &expr else {
+            panic!("expected AND, got {:?}", expr);
+        };
+        assert!(matches!(&expressions[0], Expr::Between { negated: false, .. }));
+        assert_eq!(expressions[1], Expr::IsNull { expr: column("b"), negated: true });
+
+        let like = Expr::BinaryOp {
+            left: column("name"),
+            op: BinaryOperator::Like,
+            right: Box::new(Expr::Literal(Value::String("a%".to_string()))),
+        };
+        assert_eq!(parse("name LIKE 'a%'").unwrap(), like);
+        assert_eq!(parse("name NOT LIKE 'a%'").unwrap(), Expr::LogicalOp {
+            op: LogicalOperator::Not,
+            expressions: vec![like],
+        });
+        assert!(matches!(parse("x <> 1").unwrap(), Expr::BinaryOp { op: BinaryOperator::NotEq, .. }));
+        for sql in ["a BETWEEN 1", "a BETWEEN 1 OR 2", "a NOT BETWEEN", "a IS NOT 1"] {
+            assert!(parse(sql).is_err(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_date_literal_and_substring() {
+        let parse = |sql: &str| Parser::new_from_sql(sql).parse_expr(0);
+        let Expr::Cast { expr, data_type } = parse("date '1998-12-01'").unwrap() else {
+            panic!("expected DATE literal");
+        };
+        assert_eq!(*expr, Expr::Literal(Value::String("1998-12-01".to_string())));
+        assert_eq!(data_type.name, "DATE");
+
+        let expr = parse("SUBSTRING(c_phone FROM 1 FOR 2)").unwrap();
+        let Expr::FunctionCall { name, args } = &expr else {
+            panic!("expected SUBSTRING, got {:?}", expr);
+        };
+        assert_eq!((name.as_str(), args.len()), ("SUBSTRING", 3));
+        assert_eq!(parse("substr(c_phone, 1, 2)").unwrap(), Expr::FunctionCall {
+            name: "substr".to_string(),
+            args: vec![
+                Expr::Identifier(ObjectName::new(["c_phone"])),
+                Expr::Literal(Value::Integer(1)),
+                Expr::Literal(Value::Integer(2)),
+            ],
+        });
+        #[cfg(feature = "formatter")]
+        assert_eq!(parse("d >= DATE '1994-01-01'").unwrap().to_string(), "d >= CAST('1994-01-01' AS DATE)");
+        assert!(parse("SUBSTRING(c_phone FROM 1").is_err());
     }
 }
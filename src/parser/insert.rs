@@ -1,12 +1,13 @@
 use super::{ParseError, Parser};
 use crate::ast::expr::Expr;
 use crate::ast::select::SelectStatement;
-use crate::token::Token;
 use crate::ast::{
-    common::TableReference,
-    insert::{InsertStatement, OnDuplicateClause},
+    common::{ObjectName, TableReference},
+    insert::{InsertModifiers, InsertStatement, OnDuplicateClause, RowAlias},
 };
 use super::select::SelectStatementParser;
+use crate::token::Token;
+use crate::kerwords::Keyword;
 
 /// insert语句解析器接口
 pub trait InsertStatementParser {
@@ -22,22 +23,41 @@ const VALUES_IDX: u8 = 1;
 const ON_DUPLICATE_KEY_UPDATE_IDX: u8 = 2;
 
 impl Parser {
+    // 解析INSERT之后的修饰符，优先级修饰符最多只能有一个
+    fn parse_insert_modifiers(&mut self) -> Result<InsertModifiers, ParseError> {
+        let mut modifiers = InsertModifiers::default();
+        if self.match_keyword(Keyword::LOW_PRIORITY) {
+            modifiers.low_priority = true;
+        } else if self.match_keyword(Keyword::DELAYED) {
+            modifiers.delayed = true;
+        } else if self.match_keyword(Keyword::HIGH_PRIORITY) {
+            modifiers.high_priority = true;
+        }
+        modifiers.ignore = self.match_keyword(Keyword::IGNORE);
+        if self.is_keyword(Keyword::LOW_PRIORITY) || self.is_keyword(Keyword::DELAYED) || self.is_keyword(Keyword::HIGH_PRIORITY) {
+            return Err(self.get_parse_error(
+                "Only one of LOW_PRIORITY, DELAYED and HIGH_PRIORITY is allowed, and it must precede IGNORE",
+            ));
+        }
+        Ok(modifiers)
+    }
+
     fn parse_select_clause(&mut self) -> Result<Option<SelectStatement>, ParseError> {
-        if self.is_keyword("SELECT") {
+        if self.is_keyword(Keyword::SELECT) {
             // 解析SELECT子句
             let select_statement = self.parse_select_statement()?;
-            return Ok(Some(select_statement));
+            Ok(Some(select_statement))
         } else {
-            return Ok(None);
+            Ok(None)
         }
     }
     fn parse_values_clause(&mut self) -> Result<Option<Vec<Vec<Expr>>>,ParseError> {
-        if self.match_keyword("VALUES") {
+        if self.match_keyword(Keyword::VALUES) {
             let mut values = Vec::new();
             loop {
                 // 解析值列表
                 if !self.match_punctuator('(') {
-                    return Err(self.get_parse_error("Expected opening parenthesis"));
+                    return Err(self.expected("opening parenthesis"));
                 }
                 
                 // 新增: 检查是否是空括号对
@@ -47,7 +67,7 @@ impl Parser {
                 } else {
                     let mut value_list = Vec::new();
                     loop {
-                        let value = self.parse_expr(0)?;
+                        let value = self.parse_assigned_value()?;
                         value_list.push(value);
                         
                         if !self.match_punctuator(',') {
@@ -56,7 +76,7 @@ impl Parser {
                     }
                     
                     if !self.match_punctuator(')') {
-                        return Err(self.get_parse_error("Expected closing parenthesis"));
+                        return Err(self.expected("closing parenthesis"));
                     }
                     
                     values.push(value_list);
@@ -68,149 +88,80 @@ impl Parser {
                 }
             }
 
-            return Ok(Some(values));
+            Ok(Some(values))
         } else {
-            return Ok(None);
+            Ok(None)
         }
     }
 
-    fn parse_set_clause(&mut self) -> Result<Option<Vec<(String, Expr)>>, ParseError> {
-        if self.match_keyword("SET") {
-            let mut set_clause = Vec::new();
-            loop {
-                // 解析列名
-                let column = match self.peek() {
-                    Some(Token::Identifier(ident)) => {
-                        let name = ident.to_owned();
-                        self.consume_token();
-                        name
-                    }
-                    _ => return Err(self.get_parse_error("Expected column name"))
-                };
-                
-                // 解析等号
-                if !self.match_operator("=") {
-                    return Err(self.get_parse_error("Expected = after column name"));
-                }
-                
-                // 解析表达式
-                let value = self.parse_expr(0)?;
-                
-                // 添加到SET子句
-                set_clause.push((column, value));
-                
-                // 检查是否有更多的赋值
-                if !self.match_punctuator(',') {
-                    break;
-                }
-            }
-
-            return Ok(Some(set_clause));
+    fn parse_set_clause(&mut self) -> Result<Option<Vec<(ObjectName, Expr)>>, ParseError> {
+        if self.match_keyword(Keyword::SET) {
+            Ok(Some(self.parse_assignments()?))
         } else {
-            return Ok(None);
+            Ok(None)
         }
     }
 
-    // 解析插入的列名
+    // 解析插入的列名，列名可以是反引号包裹的关键字或带表名限定，例如 (`order`, t.id)
     fn parse_insert_columns(&mut self) -> Result<Option<Vec<String>>, ParseError> {
-       // 解析可选的列名列表
-        let columns = if self.match_punctuator('(') {
-            let mut column_list = Vec::new();
-            // 如果没有列名，直接返回
-            if self.match_punctuator(')') {
-                return Ok(Some(column_list));
-            }
-            // 循环解析列名
-            loop {
-                match self.peek() {
-                    Some(Token::Identifier(ident)) => {
-                        let column = ident.to_owned();
-                        self.consume_token();
-                        column_list.push(column);
-                    }
-                    _ => return Err(self.get_parse_error("Expected column name"))
-                };
-                
-                if !self.match_punctuator(',') {
-                    break;
-                }
-            }
-            
-            if !self.match_punctuator(')') {
-                return Err(self.get_parse_error("Expected closing parenthesis"));
-            }
-            
-            Some(column_list)
-        } else {
-            None
-        };
-        Ok(columns)
+        if !self.is_punctuator('(') {
+            return Ok(None);
+        }
+        // 空的列名列表 () 表示所有列取默认值
+        if self.peek_n(1) == Some(&Token::Punctuator(')')) {
+            self.consume_token();
+            self.consume_token();
+            return Ok(Some(Vec::new()));
+        }
+        Ok(Some(self.parse_column_names(true)?))
     }
 
     fn parse_default_values(&mut self) -> Result<bool, ParseError> {
-        if self.match_keyword("DEFAULT") {
-            if self.match_keyword("VALUES") {
-                return Ok(true);
+        if self.match_keyword(Keyword::DEFAULT) {
+            if self.match_keyword(Keyword::VALUES) {
+                Ok(true)
             } else {
-                return Err(self.get_parse_error(&format!(
-                    "Expected VALUES after DEFAULT, found {:?}",
-                    self.peek()
-                )));
+                Err(self.expected("VALUES after DEFAULT"))
             }
         } else {
-            return Ok(false);
+            Ok(false)
         }
     }
 
+    // 解析 VALUES 或 SET 之后可选的行别名：AS new [(a, b, ...)]
+    fn parse_row_alias(&mut self) -> Result<Option<RowAlias>, ParseError> {
+        if !self.match_keyword(Keyword::AS) {
+            return Ok(None);
+        }
+        let name = self.parse_identifier("row alias")?;
+        let columns = if self.is_punctuator('(') {
+            Some(self.parse_column_name_list()?)
+        } else {
+            None
+        };
+        Ok(Some(RowAlias { name, columns }))
+    }
+
     fn parse_on_duplicate_key_update(&mut self) -> Result<Option<OnDuplicateClause>, ParseError>  {
         // 如果没有ON关键字，表示没有这个子句
-        if !self.match_keyword("ON") {
+        if !self.match_keyword(Keyword::ON) {
             return Ok(None);
         }
         // 检查完整的关键字序列
-        if !self.match_keyword("DUPLICATE") {
-            return Err(self.get_parse_error("Expected DUPLICATE after ON"));
+        if !self.match_keyword(Keyword::DUPLICATE) {
+            return Err(self.expected("DUPLICATE after ON"));
         }
 
-        if !self.match_keyword("KEY") {
-            return Err(self.get_parse_error("Expected KEY after ON DUPLICATE"));
+        if !self.match_keyword(Keyword::KEY) {
+            return Err(self.expected("KEY after ON DUPLICATE"));
         }
 
-        if !self.match_keyword("UPDATE") {
-            return Err(self.get_parse_error("Expected UPDATE after ON DUPLICATE KEY"));
+        if !self.match_keyword(Keyword::UPDATE) {
+            return Err(self.expected("UPDATE after ON DUPLICATE KEY"));
         }
 
         // 解析赋值列表
-        let mut updates = Vec::new();
-        
-        loop {
-            // 解析列名
-            let column = match self.peek() {
-                Some(Token::Identifier(ident)) => {
-                    let name = ident.to_owned();
-                    self.consume_token();
-                    name
-                }
-                _ => return Err(self.get_parse_error("Expected column name"))
-            };
-            
-            // 解析等号
-            if !self.match_operator("=") {
-                return Err(self.get_parse_error("Expected = after column name"));
-            }
-            
-            // 解析表达式
-            let value = self.parse_expr(0)?;
-            
-            // 添加到更新列表
-            updates.push((column, value));
-            
-            // 检查是否有更多的赋值
-            if !self.match_punctuator(',') {
-                break;
-            }
-        }
-
+        let updates = self.parse_assignments()?;
         Ok(Some(OnDuplicateClause { updates }))
     }
 }
@@ -220,13 +171,15 @@ impl InsertStatementParser for Parser {
     // 解析INSERT语句
     fn parse_insert_statement(&mut self) -> Result<InsertStatement, Self::Error> {
         // 期望以insert关键字开始
-        if !self.match_keyword("INSERT") {
-            return Err(self.get_parse_error(&format!("Expected INSERT, found{:?}", self.peek())));
+        if !self.match_keyword(Keyword::INSERT) {
+            return Err(self.expected("INSERT"));
         }
 
+        let modifiers = self.parse_insert_modifiers()?;
+
         // 必须有into子句
-        if !self.match_keyword("INTO") {
-            return Err(self.get_parse_error(&format!("Expected INTO, found {:?}", self.peek())));
+        if !self.match_keyword(Keyword::INTO) {
+            return Err(self.expected("INTO"));
         }
 
         // 解析INTO的表引用
@@ -243,6 +196,11 @@ impl InsertStatementParser for Parser {
         let values = self.parse_values_clause()?;
 
         let set_clause = self.parse_set_clause()?;
+        let row_alias = if values.is_some() || set_clause.is_some() {
+            self.parse_row_alias()?
+        } else {
+            None
+        };
         let select_clause = self.parse_select_clause()?;
 
         let data_sources = [
@@ -256,7 +214,7 @@ impl InsertStatementParser for Parser {
             return Err(self.get_parse_error("Cannot specify multiple value sources"));
         }
         if data_sources == 0 {
-            return Err(self.get_parse_error("Expected VALUES, SELECT, DEFAULT VALUES or SET"));
+            return Err(self.expected("VALUES, SELECT, DEFAULT VALUES or SET"));
         }
         // 跟踪当前已处理的最高子句索引
         let current_idx: u8 = VALUES_IDX;
@@ -267,11 +225,13 @@ impl InsertStatementParser for Parser {
         }
 
         Ok(InsertStatement {
+            modifiers,
             table,
             columns,
             values,
             select_clause,
             set_clause,
+            row_alias,
             on_duplicate,
             is_default_values,
             is_return_count: true, // 默认返回行数
@@ -295,7 +255,9 @@ fn get_clause_name(idx: u8) -> &'static str {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::ast::expr::{Expr, Value};
+    use crate::ast::expr::{BinaryOperator, Expr, Value};
+    use crate::ast::common::{Ident, ObjectName};
+    use crate::parser::ParserOptions;
 
     #[test]
     fn test_basic_insert() {
@@ -308,7 +270,7 @@ mod test {
         let stmt = result.unwrap();
         
         // 验证表名
-        assert_eq!(stmt.table.name, "users");
+        assert_eq!(stmt.table.name.to_string(), "users");
         assert_eq!(stmt.table.alias, None);
         
         // 验证列名
@@ -354,7 +316,7 @@ mod test {
         let stmt = result.unwrap();
         
         // 验证表名
-        assert_eq!(stmt.table.name, "logs");
+        assert_eq!(stmt.table.name.to_string(), "logs");
         
         // 验证没有VALUES子句
         assert!(stmt.values.is_none());
@@ -365,7 +327,7 @@ mod test {
         assert_eq!(set_clause.len(), 3); // 三个赋值
         
         // 验证第一个赋值
-        assert_eq!(set_clause[0].0, "message");
+        assert_eq!(set_clause[0].0, ObjectName::new(["message"]));
         if let Expr::Literal(Value::String(s)) = &set_clause[0].1 {
             assert_eq!(*s, "Error occurred".to_string());
         } else {
@@ -373,7 +335,7 @@ mod test {
         }
         
         // 验证第三个赋值是函数调用
-        assert_eq!(set_clause[2].0, "timestamp");
+        assert_eq!(set_clause[2].0, ObjectName(vec![Ident::with_quote('`', "timestamp")]));
         if let Expr::FunctionCall { name, args } = &set_clause[2].1 {
             assert_eq!(*name, "NOW".to_string());
             assert_eq!(args.len(), 0);
@@ -390,7 +352,7 @@ mod test {
                   (101, 'Laptop', 999.99, 50),
                   (102, 'Smartphone', 499.99, 100)
                   ON DUPLICATE KEY UPDATE 
-                  stock = stock + `VALUES`(stock),
+                  stock = stock + VALUES(stock),
                   update_time = NOW()";
         
         let mut parser = Parser::new_from_sql(sql);
@@ -400,7 +362,7 @@ mod test {
         let stmt = result.unwrap();
         
         // 验证表名
-        assert_eq!(stmt.table.name, "products");
+        assert_eq!(stmt.table.name.to_string(), "products");
         
         // 验证列名
         assert_eq!(stmt.columns, Some(vec![
@@ -415,12 +377,8 @@ mod test {
         let values = stmt.values.unwrap();
         assert_eq!(values.len(), 2); // 两行数据
         
-        // 验证第一行的价格是999.99
-        if let Expr::Literal(Value::Float(num)) = &values[0][2] {
-            assert!((num - 999.99).abs() < 0.001); // 浮点数比较
-        } else {
-            panic!("Expected float 999.99, found {:?}", values[0][2]);
-        }
+        // 验证第一行的价格是999.99，小数按原文保存
+        assert_eq!(values[0][2], Expr::Literal(Value::Decimal("999.99".to_string())));
         
         // 验证ON DUPLICATE KEY UPDATE子句
         assert!(stmt.on_duplicate.is_some());
@@ -428,8 +386,174 @@ mod test {
         assert_eq!(on_duplicate.updates.len(), 2); // 两个更新表达式
         
         // 验证第一个更新是stock = stock + VALUES(stock)
-        assert_eq!(on_duplicate.updates[0].0, "stock");
+        assert_eq!(on_duplicate.updates[0].0, ObjectName::new(["stock"]));
+        let Expr::BinaryOp { right, .. } = &on_duplicate.updates[0].1 else {
+            panic!("Expected stock + VALUES(stock), found {:?}", on_duplicate.updates[0].1);
+        };
+        assert_eq!(**right, Expr::FunctionCall {
+            name: "VALUES".to_string(),
+            args: vec![Expr::Identifier(ObjectName::new(["stock"]))],
+        });
+    }
+
+    #[test]
+    fn test_assignment_targets() {
+        let sql = "INSERT INTO shop.products p SET `key` = 'k1', p.`order` = 1 \
+                   ON DUPLICATE KEY UPDATE products.stock = stock + 1, `shop`.`products`.`status` = DEFAULT";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_insert_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let stmt = result.unwrap();
+        assert!(!parser.has_more());
+
+        let targets = |pairs: &[(ObjectName, Expr)]| pairs.iter().map(|(column, _)| column.to_string()).collect::<Vec<_>>();
+        assert_eq!(targets(stmt.set_clause.as_ref().unwrap()), ["`key`", "p.`order`"]);
+        let updates = &stmt.on_duplicate.as_ref().unwrap().updates;
+        assert_eq!(targets(updates), ["products.stock", "`shop`.`products`.`status`"]);
+        assert_eq!(updates[1].0.name().value, "status");
+        assert_eq!(updates[1].1, Expr::Literal(Value::DEFAULT));
+
+        // 未加引号的保留字不能作为列名
+        for sql in ["INSERT INTO t SET order = 1", "INSERT INTO t VALUES (1) ON DUPLICATE KEY UPDATE select = 1"] {
+            assert!(Parser::new_from_sql(sql).parse_insert_statement().is_err(), "{}", sql);
+        }
     }
 
+    #[test]
+    fn test_insert_row_alias() {
+        let sql = "INSERT INTO products (id, stock) VALUES (1, 5) AS new ON DUPLICATE KEY UPDATE stock = stock + new.stock";
+        let stmt = Parser::new_from_sql(sql).parse_insert_statement().unwrap();
+        assert_eq!(stmt.row_alias, Some(RowAlias { name: "new".to_string(), columns: None }));
+        #[cfg(feature = "formatter")]
+        {
+            let updates = &stmt.on_duplicate.as_ref().unwrap().updates;
+            assert_eq!(updates[0].1.to_string(), "stock + new.stock");
+            assert_eq!(stmt.to_string(), sql);
+        }
+
+        // 带列别名，SET 形式同样可以使用行别名
+        let sql = "INSERT INTO products SET id = 1, stock = 5 AS n(i, s) ON DUPLICATE KEY UPDATE stock = s";
+        let stmt = Parser::new_from_sql(sql).parse_insert_statement().unwrap();
+        let alias = stmt.row_alias.unwrap();
+        assert_eq!(alias.name, "n");
+        assert_eq!(alias.columns, Some(vec!["i".to_string(), "s".to_string()]));
+
+        for sql in [
+            "INSERT INTO products VALUES (1) AS ON DUPLICATE KEY UPDATE a = 1",
+            "INSERT INTO products VALUES (1) AS new () ON DUPLICATE KEY UPDATE a = 1",
+            "INSERT INTO products SELECT 1 AS ON DUPLICATE KEY UPDATE a = 1",
+        ] {
+            let mut parser = Parser::new_from_sql(sql);
+            assert!(parser.parse_insert_statement().is_err() || parser.has_more(), "{}", sql);
+        }
+    }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_insert_modifiers_and_partition() {
+        let sql = "INSERT LOW_PRIORITY IGNORE INTO orders PARTITION (p1, p2) (id, amount) VALUES (1, 10)";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_insert_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let stmt = result.unwrap();
+
+        assert_eq!(stmt.modifiers, InsertModifiers {
+            low_priority: true,
+            ignore: true,
+            ..Default::default()
+        });
+        assert_eq!(stmt.table.name.to_string(), "orders");
+        assert_eq!(stmt.table.partitions, Some(vec!["p1".to_string(), "p2".to_string()]));
+        assert_eq!(stmt.columns, Some(vec!["id".to_string(), "amount".to_string()]));
+
+        // 默认没有修饰符
+        let mut parser = Parser::new_from_sql("INSERT INTO orders VALUES (1, 10)");
+        assert_eq!(parser.parse_insert_statement().unwrap().modifiers, InsertModifiers::default());
+
+        // 优先级修饰符不能同时出现，也不能放在IGNORE之后
+        let mut parser = Parser::new_from_sql("INSERT LOW_PRIORITY DELAYED INTO orders VALUES (1, 10)");
+        assert!(parser.parse_insert_statement().is_err());
+        let mut parser = Parser::new_from_sql("INSERT IGNORE HIGH_PRIORITY INTO orders VALUES (1, 10)");
+        assert!(parser.parse_insert_statement().is_err());
+    }
+
+    #[test]
+    fn test_insert_values_cells() {
+        let sql = "INSERT INTO orders (id, status, created_at, total, note) VALUES
+                   (DEFAULT, NULL, NOW(), price * 2 + 1, default),
+                   (7, DEFAULT, CONCAT('a', 'b'), -(3 - 1), DEFAULT(note))";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_insert_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let rows = result.unwrap().values.unwrap();
+        assert_eq!(rows.len(), 2);
+
+        let first = &rows[0];
+        assert_eq!(first[0], Expr::Literal(Value::DEFAULT));
+        assert_eq!(first[1], Expr::Literal(Value::Null));
+        assert!(matches!(&first[2], Expr::FunctionCall { name, args } if name == "NOW" && args.is_empty()));
+        assert!(matches!(&first[3], Expr::BinaryOp { op: BinaryOperator::Plus, .. }));
+        assert_eq!(first[4], Expr::Literal(Value::DEFAULT));
+
+        let second = &rows[1];
+        assert_eq!(second[0], Expr::Literal(Value::Integer(7)));
+        assert_eq!(second[1], Expr::Literal(Value::DEFAULT));
+        assert!(matches!(&second[2], Expr::FunctionCall { name, args } if name == "CONCAT" && args.len() == 2));
+        assert!(matches!(&second[3], Expr::UnaryOp { .. }));
+        // DEFAULT(col) 取列的默认值
+        assert!(matches!(&second[4], Expr::FunctionCall { name, args } if name == "DEFAULT" && args.len() == 1));
+
+        let sql = "INSERT INTO orders SET status = DEFAULT ON DUPLICATE KEY UPDATE note = DEFAULT";
+        let insert = Parser::new_from_sql(sql).parse_insert_statement().unwrap();
+        assert_eq!(insert.set_clause.unwrap()[0].1, Expr::Literal(Value::DEFAULT));
+        assert_eq!(insert.on_duplicate.unwrap().updates[0].1, Expr::Literal(Value::DEFAULT));
+
+        // 单独的 DEFAULT 只能是整个值，不能参与运算
+        for sql in ["INSERT INTO t VALUES (DEFAULT + 1)", "INSERT INTO t VALUES (-DEFAULT)", "INSERT INTO t SET a = (DEFAULT)"] {
+            assert!(Parser::new_from_sql(sql).parse_insert_statement().is_err(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_insert_signed_literals() {
+        let sql = "INSERT INTO points VALUES (-1,-2.5,+3),(1e3,-1.5E-3, 2e+2),( - 7 ,x-1,-(-4))";
+        let mut parser = Parser::new_from_sql(sql);
+        let rows = parser.parse_insert_statement().unwrap().values.unwrap();
+        let literal = |value| Expr::Literal(value);
+        assert_eq!(rows[0], vec![literal(Value::Integer(-1)), literal(Value::Decimal("-2.5".to_string())), literal(Value::Integer(3))]);
+        assert_eq!(rows[1], vec![literal(Value::Float(1000.0)), literal(Value::Float(-0.0015)), literal(Value::Float(200.0))]);
+        assert_eq!(rows[2][0], literal(Value::Integer(-7)));
+        assert!(matches!(&rows[2][1], Expr::BinaryOp { op: BinaryOperator::Minus, .. }));
+        assert_eq!(rows[2][2], literal(Value::Integer(4)));
+    }
+
+    #[test]
+    fn test_insert_column_names() {
+        // 反引号包裹的关键字、非保留关键字和限定列名都可以出现在列名列表中
+        let sql = "INSERT INTO t (`order`,`key`, status, t.id, db.t.`select`) VALUES (1, 2, 3, 4, 5)";
+        let stmt = Parser::new_from_sql(sql).parse_insert_statement().unwrap();
+        assert_eq!(stmt.columns.unwrap(), vec!["order", "key", "status", "id", "select"]);
+
+        let stmt = Parser::new_from_sql("INSERT INTO t () VALUES ()").parse_insert_statement().unwrap();
+        assert_eq!(stmt.columns, Some(vec![]));
+
+        // 未加引号的保留字仍然不能作为列名
+        for sql in ["INSERT INTO t (order) VALUES (1)", "INSERT INTO t (a,) VALUES (1)"] {
+            assert!(Parser::new_from_sql(sql).parse_insert_statement().is_err(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_decimal_option() {
+        let sql = "INSERT INTO prices VALUES (999.99, 0.1, 1e2)";
+        let rows = Parser::new_from_sql(sql).parse_insert_statement().unwrap().values.unwrap();
+        assert_eq!(rows[0][0], Expr::Literal(Value::Decimal("999.99".to_string())));
+        assert_eq!(rows[0][1], Expr::Literal(Value::Decimal("0.1".to_string())));
+        assert_eq!(rows[0][2], Expr::Literal(Value::Float(100.0)));
+
+        // 关闭后小数按以前的方式解析为浮点数
+        let options = ParserOptions { exact_decimals: false, ..Default::default() };
+        let rows = Parser::new_from_sql(sql).with_options(options).parse_insert_statement().unwrap().values.unwrap();
+        assert_eq!(rows[0][0], Expr::Literal(Value::Float(999.99)));
+        assert_eq!(rows[0][1], Expr::Literal(Value::Float(0.1)));
+    }
+}
@@ -0,0 +1,99 @@
+//! 解析统计
+//!
+//! 表数量和表达式深度在解析过程中顺便记录，token 相关的计数只扫描本条语句消费的 token，
+//! 不需要在解析之后再遍历一次AST，适合代理层在每次解析后导出监控指标。
+
+use super::{ParseError, Parser, StatementParser};
+use crate::ast::SQLStatement;
+use crate::token::Token;
+use std::time::{Duration, Instant};
+
+/// 一次解析的统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseMetrics {
+    /// 语句消费的token数，包括结尾的 ';'
+    pub token_count: usize,
+    /// 字符串和数字字面量的个数
+    pub literal_count: usize,
+    /// ? 占位符的个数
+    pub placeholder_count: usize,
+    /// 表达式的最大嵌套层数，与解析器限制表达式深度时的计数方式一致，子查询重新从1开始；没有表达式时为0
+    pub max_expr_depth: usize,
+    /// 表引用的个数，同一张表出现多次时分别计数
+    pub table_count: usize,
+    /// 解析耗时
+    pub parse_duration: Duration,
+}
+
+// 解析过程中记录的计数
+#[derive(Debug, Default)]
+pub(crate) struct Collector {
+    pub(crate) tables: usize,
+    max_expr_depth: usize,
+}
+
+impl Collector {
+    // 进入一层表达式，depth 为解析器传入的嵌套层数，从0开始
+    pub(crate) fn enter_expr(&mut self, depth: usize) {
+        self.max_expr_depth = self.max_expr_depth.max(depth + 1);
+    }
+}
+
+impl Parser {
+    /// 与 [`StatementParser::parse`] 一样解析一条语句，同时返回本次解析的统计
+    pub fn parse_with_metrics(&mut self) -> Result<(SQLStatement, ParseMetrics), ParseError> {
+        let started = Instant::now();
+        let start = self.current;
+        self.collector = Collector::default();
+        let stmt = self.parse()?;
+        let mut metrics = count_tokens(&self.tokens[start..self.current]);
+        metrics.max_expr_depth = self.collector.max_expr_depth;
+        metrics.table_count = self.collector.tables;
+        metrics.parse_duration = started.elapsed();
+        Ok((stmt, metrics))
+    }
+}
+
+// 统计token数、字面量和占位符
+fn count_tokens(tokens: &[Token]) -> ParseMetrics {
+    let mut metrics = ParseMetrics { token_count: tokens.len(), ..Default::default() };
+    for token in tokens {
+        match token {
+            Token::Identifier(ident) if ident == "?" => metrics.placeholder_count += 1,
+            Token::StringLiteral(_) | Token::NumericLiteral(_) => metrics.literal_count += 1,
+            _ => {}
+        }
+    }
+    metrics
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_with_metrics() {
+        let sql = "SELECT a.id, 'x' FROM a JOIN b ON a.id = b.id WHERE a.n IN (1, 2, ?) AND b.m = ? AND (a.x + (1 * 2)) > 0; SELECT 1";
+        let mut parser = Parser::new_from_sql(sql);
+        let (stmt, metrics) = parser.parse_with_metrics().unwrap();
+        assert!(matches!(stmt, SQLStatement::Select(_)));
+        assert_eq!(metrics.table_count, 2);
+        assert_eq!(metrics.literal_count, 6);
+        assert_eq!(metrics.placeholder_count, 2);
+        assert!(metrics.max_expr_depth >= 2);
+        // 只统计第一条语句，结尾的 ';' 计入
+        assert_eq!(metrics.token_count, parser.current);
+
+        // 每次解析重新计数
+        let (_, metrics) = parser.parse_with_metrics().unwrap();
+        assert_eq!(metrics.token_count, 2);
+        assert_eq!(metrics.literal_count, 1);
+        assert_eq!(metrics.table_count, 0);
+        assert_eq!(metrics.max_expr_depth, 1);
+
+        let (_, metrics) = Parser::new_from_sql("DELETE FROM t").parse_with_metrics().unwrap();
+        assert_eq!(metrics.table_count, 1);
+        assert_eq!(metrics.max_expr_depth, 0);
+        assert!(Parser::new_from_sql("SELECT FROM").parse_with_metrics().is_err());
+    }
+}
@@ -1,40 +1,193 @@
 use crate::ast::SQLStatement;
-use crate::token::{Token,self};
+use crate::ast::common::{Ident, ObjectName};
+use crate::ast::symbol::Interner;
+use crate::error::{self, ErrorKind, Language};
+use crate::kerwords::Keyword;
+use crate::token::{LexError, Span, Token,self};
 use std::error::Error;
 use std::fmt;
+use std::sync::Arc;
 
 pub mod expr;
 pub mod common;
 pub mod select;
 pub mod delete;
+pub mod update;
 pub mod insert;
+#[cfg(feature = "ddl")]
+pub mod create;
+#[cfg(feature = "ddl")]
+pub mod alter;
+pub mod show;
+pub mod set;
+pub mod plugin;
+#[cfg(feature = "routines")]
+pub mod routine;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 
 // 解析错误
 #[derive(Debug)]
 pub struct ParseError {
+    /// 英文的错误描述
     pub message: String,
+    /// 机器可读的错误类别，与显示语言无关
+    pub kind: Box<ErrorKind>,
     pub token_position: usize,
+    /// 出错位置在原始SQL中的字节范围，只有通过SQL文本创建的解析器才能定位
+    pub span: Option<Span>,
+    // 原始SQL，用于渲染出错的源码行
+    source: Option<Arc<str>>,
+    // Display 使用的语言
+    language: Language,
 }
 
+impl ParseError {
+    pub fn new(message: impl Into<String>, token_position: usize) -> Self {
+        ParseError {
+            message: message.into(),
+            kind: Box::new(ErrorKind::Syntax),
+            token_position,
+            span: None,
+            source: None,
+            language: Language::default(),
+        }
+    }
+
+    /// 按指定语言生成的错误描述
+    pub fn localized(&self, language: Language) -> String {
+        self.kind.render(&self.message, language)
+    }
+
+    /// 出错位置的行号和列号，均从1开始
+    pub fn line_column(&self) -> Option<(usize, usize)> {
+        let (source, span) = (self.source.as_deref()?, self.span?);
+        let before = &source[..span.start];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        Some((line, column))
+    }
+}
+
+// 默认使用解析选项指定的语言，{:#} 总是使用中文
 impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "Parse error at position {}: {}",
-            self.token_position, self.message
-        )
+        let language = if f.alternate() { Language::Chinese } else { self.language };
+        let message = self.localized(language);
+        let zh = language == Language::Chinese;
+        let (Some(source), Some(span), Some((line, column))) = (self.source.as_deref(), self.span, self.line_column())
+        else {
+            return if zh {
+                write!(f, "解析错误，位置 {}：{}", self.token_position, message)
+            } else {
+                write!(f, "Parse error at position {}: {}", self.token_position, message)
+            };
+        };
+        // 渲染出错的源码行，并在出错的范围下方标记 ^^^
+        let line_start = source[..span.start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[span.start..].find('\n').map_or(source.len(), |i| span.start + i);
+        let text = &source[line_start..line_end];
+        let width = source[span.start..span.end.min(line_end)].chars().count().max(1);
+        let gutter = " ".repeat(line.to_string().len());
+        if zh {
+            writeln!(f, "错误：{}", message)?;
+            writeln!(f, "{}--> 第 {} 行，第 {} 列", gutter, line, column)?;
+        } else {
+            writeln!(f, "error: {}", message)?;
+            writeln!(f, "{}--> line {}, column {}", gutter, line, column)?;
+        }
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", line, text)?;
+        write!(f, "{} | {}{}", gutter, " ".repeat(column - 1), "^".repeat(width))
     }
 }
 
 impl Error for ParseError {}
 
+/// SQL 方言，默认为 MySQL
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Dialect {
+    #[default]
+    MySql,
+    /// PostgreSQL，额外支持 SELECT DISTINCT ON (...) 等语法
+    Postgres,
+    /// SQL Server，额外支持 SELECT TOP n 等语法
+    MsSql,
+    /// BigQuery，额外支持 QUALIFY 子句，# 开头的也是注释
+    BigQuery,
+    /// Snowflake，额外支持 QUALIFY 子句
+    Snowflake,
+}
+
+impl Dialect {
+    /// 是否支持按窗口函数结果过滤的 QUALIFY 子句
+    pub fn supports_qualify(self) -> bool {
+        matches!(self, Dialect::BigQuery | Dialect::Snowflake)
+    }
+}
+
+/// 解析选项
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParserOptions {
+    /// 是否把 SELECT ... FROM DUAL 规范化为没有FROM子句的SELECT，默认开启
+    pub normalize_dual: bool,
+    /// 错误信息的显示语言，默认英文
+    pub language: Language,
+    /// SQL 方言
+    pub dialect: Dialect,
+    /// 是否把不带指数的小数（例如 999.99）解析为 Value::Decimal，默认开启
+    /// 关闭后与以前一样解析为 Value::Float
+    pub exact_decimals: bool,
+    /// 通过 StatementParser 解析单条语句时，是否要求语句之后是输入结尾或 ';'，默认开启
+    /// 关闭后与以前一样忽略语句之后的token
+    pub require_statement_end: bool,
+    /// 是否在解析前按 [`token::normalize_case`] 规范化标识符和关键字的大小写，默认关闭
+    /// 开启后相同语义、不同书写风格的语句得到相同的AST，错误位置仍然指向原始SQL
+    pub normalize_case: bool,
+    /// normalize_case 开启时，是否把加引号的标识符也转为小写，默认关闭
+    /// 只在确定标识符不区分大小写时开启，例如 MySQL 的 lower_case_table_names 不为 0
+    pub fold_quoted_identifiers: bool,
+    /// 是否容忍未闭合的字符串和反引号标识符，默认关闭，此时解析到它们时报告 [`ErrorKind::Lex`]
+    /// 开启后它们延伸到输入结尾，作为普通的字符串或标识符继续解析
+    pub lenient_lexing: bool,
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        ParserOptions {
+            normalize_dual: true,
+            language: Language::default(),
+            dialect: Dialect::default(),
+            exact_decimals: true,
+            require_statement_end: true,
+            normalize_case: false,
+            fold_quoted_identifiers: false,
+            lenient_lexing: false,
+        }
+    }
+}
+
 // 核心解析器结构
 pub struct Parser {
     tokens: Vec<Token>,
     current: usize,
+    options: ParserOptions,
+    // 设置后，解析出的标识符通过它共享字符串
+    interner: Option<Interner>,
+    // 原始SQL，用于在错误中定位出错的源码
+    source: Option<Arc<str>>,
+    // 每个token在原始SQL中的位置，由词法分析记录，不是由SQL创建时为空
+    spans: Vec<Span>,
+    // 词法分析时遇到的错误，此时最后一个token是恢复得到的
+    lex_error: Option<LexError>,
+    // 当前所在子查询的嵌套层数，子查询中的表达式深度重新计算，需要单独限制
+    subquery_depth: usize,
+    // 解析过程中顺便收集的统计，见 parse_with_metrics
+    #[cfg(feature = "metrics")]
+    collector: metrics::Collector,
 }
 
-// 语句解析接口
+// 语句解析接口，是对象安全的，插件见 plugin 模块
 pub trait StatementParser {
     // 将解析的Token流转换为对应的语句类型
     fn parse(&mut self) -> Result<SQLStatement, ParseError>;
@@ -43,11 +196,84 @@ pub trait StatementParser {
 // 添加基本功能
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Self {
-        Parser { tokens, current: 0 }
+        Parser {
+            tokens,
+            current: 0,
+            options: ParserOptions::default(),
+            interner: None,
+            source: None,
+            spans: Vec::new(),
+            lex_error: None,
+            subquery_depth: 0,
+            #[cfg(feature = "metrics")]
+            collector: metrics::Collector::default(),
+        }
     }
     pub fn new_from_sql(sql: &str) -> Self {
-        let tokens = token::tokenize(sql);
-        Parser { tokens, current: 0 }
+        let lexed = token::lex(sql, Dialect::default());
+        Parser {
+            tokens: lexed.tokens,
+            current: 0,
+            options: ParserOptions::default(),
+            interner: None,
+            source: Some(Arc::from(sql)),
+            spans: lexed.spans,
+            lex_error: lexed.error,
+            subquery_depth: 0,
+            #[cfg(feature = "metrics")]
+            collector: metrics::Collector::default(),
+        }
+    }
+
+    // 使用指定的解析选项
+    // 由SQL创建且尚未开始解析时，方言变化会按新方言的词法规则重新拆分token
+    // 尚未开始解析时按 normalize_case 规范化token的大小写
+    pub fn with_options(mut self, options: ParserOptions) -> Self {
+        if options.dialect != self.options.dialect && self.current == 0 {
+            if let Some(source) = self.source.as_deref() {
+                let lexed = token::lex(source, options.dialect);
+                (self.tokens, self.spans, self.lex_error) = (lexed.tokens, lexed.spans, lexed.error);
+            }
+        }
+        if options.normalize_case && self.current == 0 {
+            token::normalize_case(&mut self.tokens, options.fold_quoted_identifiers);
+        }
+        self.options = options;
+        self
+    }
+
+    pub fn options(&self) -> &ParserOptions {
+        &self.options
+    }
+
+    // 使用字符串驻留池，重复出现的标识符共享同一份分配
+    // 同一个驻留池可以通过 take_interner 取回，继续用于后续的解析
+    pub fn with_interner(mut self, interner: Interner) -> Self {
+        self.interner = Some(interner);
+        self
+    }
+
+    pub fn interner(&self) -> Option<&Interner> {
+        self.interner.as_ref()
+    }
+
+    pub fn take_interner(&mut self) -> Option<Interner> {
+        self.interner.take()
+    }
+
+    // 启用驻留池时，把标识符替换为驻留后的Symbol
+    pub(crate) fn intern_ident(&mut self, mut ident: Ident) -> Ident {
+        if let Some(interner) = self.interner.as_mut() {
+            ident.value = interner.intern(&ident.value);
+        }
+        ident
+    }
+
+    pub(crate) fn intern_object_name(&mut self, name: ObjectName) -> ObjectName {
+        if self.interner.is_none() {
+            return name;
+        }
+        ObjectName(name.0.into_iter().map(|ident| self.intern_ident(ident)).collect())
     }
 
     // ===== 迭代器风格方法 =====
@@ -62,15 +288,11 @@ impl Parser {
         self.tokens.get(self.current + n)
     }
 
-    // 消费当前token并返回它
-    pub fn consume_token(&mut self) -> Option<Token> {
-        if self.current < self.tokens.len() {
-            let token = self.tokens[self.current].clone();
-            self.current += 1;
-            Some(token)
-        } else {
-            None
-        }
+    // 消费当前token并返回它的引用，需要所有权时由调用方自行 clone
+    pub fn consume_token(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.current)?;
+        self.current += 1;
+        Some(token)
     }
 
     // 检查序列中是否还有更多token
@@ -111,21 +333,56 @@ impl Parser {
     }
 
     // 尝试匹配一个关键字
-    pub fn match_keyword(&mut self, keyword: &str) -> bool {
-        if let Some(Token::Keyword(k)) = self.peek() {
-            if k.to_uppercase() == keyword.to_uppercase() {
-                self.consume_token(); // 消费匹配的token
-                return true;
-            }
+    pub fn match_keyword(&mut self, keyword: Keyword) -> bool {
+        if self.is_keyword(keyword) {
+            self.consume_token(); // 消费匹配的token
+            return true;
         }
         false
     }
 
-    pub fn is_keyword(&self, keyword: &str) -> bool {
-        if let Some(Token::Keyword(k)) = self.peek() {
-            return k.to_uppercase() == keyword.to_uppercase();
+    pub fn is_keyword(&self, keyword: Keyword) -> bool {
+        matches!(self.peek(), Some(Token::Keyword { keyword: k, .. }) if *k == keyword)
+    }
+
+    // 向前看第 n 个token是否为指定的关键字
+    pub fn is_keyword_at(&self, n: usize, keyword: Keyword) -> bool {
+        matches!(self.peek_n(n), Some(Token::Keyword { keyword: k, .. }) if *k == keyword)
+    }
+
+    // 判断当前token能否作为标识符：普通标识符、非保留字，或者不带参数的类型名（如 timestamp、date）
+    pub fn is_identifier(&self) -> bool {
+        match self.peek() {
+            Some(Token::Identifier(ident)) => ident != "@",
+            Some(Token::QuotedIdentifier(_)) => true,
+            Some(Token::Keyword { keyword, .. }) => !keyword.is_reserved(self.options.dialect),
+            Some(Token::DataType(_)) => true,
+            _ => false,
+        }
+    }
+
+    // 解析必须出现的标识符，非保留字也可以作为标识符
+    pub fn parse_identifier(&mut self, object: &str) -> Result<String, ParseError> {
+        let name = match self.peek() {
+            Some(Token::Identifier(ident)) if ident != "@" => ident.to_owned(),
+            Some(Token::QuotedIdentifier(ident)) => ident.to_owned(),
+            Some(Token::Keyword { keyword, text }) if !keyword.is_reserved(self.options.dialect) => text.to_owned(),
+            Some(Token::DataType(name)) => name.to_owned(),
+            _ => {
+                return Err(self.expected(&format!("{} name", object)));
+            }
+        };
+        self.consume_token();
+        Ok(name)
+    }
+
+    // 期望下一个token为指定的关键字
+    pub fn expect_keyword(&mut self, keyword: Keyword, after: &str) -> Result<(), ParseError> {
+        if self.match_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.expected(&format!("{} after {}", keyword.as_str(), after)))
         }
-        false
     }
 
     // 尝试匹配一个操作符
@@ -148,8 +405,9 @@ impl Parser {
     // 将token格式化为更可读的形式
     pub fn format_token(&self, token: &Token) -> String {
         match token {
-            Token::Keyword(k) => k.clone(),
+            Token::Keyword { text, .. } => text.clone(),
             Token::Identifier(id) => id.clone(),
+            Token::QuotedIdentifier(id) => format!("`{}`", id),
             Token::StringLiteral(s) => format!("'{}'", s),
             Token::NumericLiteral(n) => n.to_string(),
             Token::Punctuator(c) => c.to_string(),
@@ -160,35 +418,286 @@ impl Parser {
         }
     }
 
-    // 辅助方法：生成错误上下文
-    pub fn get_error_context(&self) -> String {
-        // 获取当前位置前后的几个token
-        let start = if self.current > 3 {
-            self.current - 3
-        } else {
-            0
-        };
-        let end = std::cmp::min(self.current + 2, self.tokens.len());
+    // 描述一个token，用于 "found X" 形式的错误信息
+    pub fn describe_token(token: Option<&Token>) -> String {
+        error::describe_token(token, Language::English)
+    }
 
-        // 将tokens转换为可读字符串
-        let context_tokens: Vec<String> = self.tokens[start..end]
-            .iter()
-            .enumerate()
-            .map(|(i, t)| {
-                let pos = start + i;
-                let marker = if pos == self.current { "👉 " } else { "" };
-                format!("{}{}", marker, self.format_token(t))
-            })
-            .collect();
+    // 当前token的描述
+    pub fn found(&self) -> String {
+        Self::describe_token(self.peek())
+    }
 
-        format!("\"{}\"", context_tokens.join(" "))
+    // 当前token在原始SQL中的位置，已到达末尾时指向SQL末尾
+    fn current_span(&self) -> Option<Span> {
+        let source = self.source.as_deref()?;
+        if self.current >= self.tokens.len() {
+            let end = source.trim_end().len();
+            return Some(Span::new(end, end));
+        }
+        self.spans.get(self.current).copied()
+    }
+
+    // tokens[start..end] 在原始SQL中的范围和文本，不是由SQL创建时为None
+    #[cfg(feature = "ddl")]
+    pub(crate) fn source_slice(&self, start: usize, end: usize) -> Option<(Span, &str)> {
+        let source = self.source.as_deref()?;
+        let span = Span::new(self.spans.get(start)?.start, self.spans.get(end - 1)?.end);
+        Some((span, &source[span.start..span.end]))
+    }
+
+    // 严格模式下解析到恢复得到的token（总是最后一个）时，报告词法错误而不是语法错误
+    fn lex_failure(&self) -> Option<ParseError> {
+        let err = self.lex_error.as_ref().filter(|_| !self.options.lenient_lexing)?;
+        if self.current + 1 < self.tokens.len() {
+            return None;
+        }
+        Some(ParseError {
+            message: err.to_string(),
+            kind: Box::new(ErrorKind::Lex(err.clone())),
+            token_position: self.current,
+            span: Some(err.span()),
+            source: self.source.clone(),
+            language: self.options.language,
+        })
     }
 
     pub fn get_parse_error(&self, message: &str) -> ParseError {
-        let context = self.get_error_context();
+        if let Some(err) = self.lex_failure() {
+            return err;
+        }
         ParseError {
-            message: format!("{}. Near: {}", message, context),
+            message: message.to_string(),
+            kind: Box::new(ErrorKind::Syntax),
             token_position: self.current,
+            span: self.current_span(),
+            source: self.source.clone(),
+            language: self.options.language,
+        }
+    }
+
+    // 生成指定类别的错误，英文描述由类别生成
+    pub fn error(&self, kind: ErrorKind) -> ParseError {
+        if let Some(err) = self.lex_failure() {
+            return err;
         }
+        let message = kind.render("", Language::English);
+        ParseError { kind: Box::new(kind), ..self.get_parse_error(&message) }
+    }
+
+    // 生成 "Expected X, found Y" 形式的错误
+    pub fn expected(&self, expected: &str) -> ParseError {
+        self.error(ErrorKind::Expected { expected: expected.to_string(), found: self.peek().cloned() })
+    }
+
+    // 生成不支持的语句或操作的错误
+    pub fn unsupported(&self, construct: &str) -> ParseError {
+        self.error(ErrorKind::Unsupported { construct: construct.to_string(), found: self.peek().cloned() })
+    }
+
+    // 解析完一条语句后，检查其后是否为输入结尾或 ';'，并消费作为结束符的 ';'
+    pub fn expect_statement_end(&mut self) -> Result<(), ParseError> {
+        // 语句包含了恢复得到的token
+        if let Some(err) = self.lex_failure().filter(|_| self.current >= self.tokens.len()) {
+            return Err(err);
+        }
+        if self.match_punctuator(';') {
+            return Ok(());
+        }
+        match self.peek() {
+            Some(token) if self.options.require_statement_end => {
+                Err(self.error(ErrorKind::TrailingInput { found: token.clone() }))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // 跳过空语句（单独的 ';'），返回之后是否还有语句
+    pub(crate) fn skip_empty_statements(&mut self) -> bool {
+        while self.match_punctuator(';') {}
+        self.has_more()
+    }
+
+    // 解析以 ';' 分隔的多条语句，空语句被跳过，最后一条语句的 ';' 可以省略
+    pub fn parse_statements(&mut self) -> Result<Vec<SQLStatement>, ParseError> {
+        let mut statements = Vec::new();
+        while self.skip_empty_statements() {
+            statements.push(self.parse()?);
+        }
+        Ok(statements)
+    }
+
+    // 生成无法解析的值的错误
+    pub fn invalid_value(&self, what: &str, value: &str) -> ParseError {
+        self.error(ErrorKind::InvalidValue { what: what.to_string(), value: value.to_string() })
+    }
+}
+
+// 按第一个关键字选择对应的语句解析器
+impl StatementParser for Parser {
+    fn parse(&mut self) -> Result<SQLStatement, ParseError> {
+        use self::{delete::DeleteStatementParser, insert::InsertStatementParser, select::SelectStatementParser};
+        use self::{set::SetStatementParser, show::ShowStatementParser, update::UpdateStatementParser};
+        #[cfg(feature = "ddl")]
+        use self::{alter::AlterStatementParser, create::CreateStatementParser};
+
+        // 不认识的单词（例如 GRANT）是不支持的语句，其它token说明不是语句的开头
+        let keyword = match self.peek() {
+            Some(Token::Keyword { keyword, .. }) => *keyword,
+            Some(Token::Identifier(_)) => return Err(self.unsupported("statement")),
+            _ => return Err(self.expected("statement")),
+        };
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("parse_statement", kind = %keyword, start = self.current).entered();
+        #[cfg(feature = "tracing")]
+        let start = self.current;
+        let stmt = match keyword {
+            Keyword::SELECT | Keyword::WITH => SQLStatement::Select(Box::new(self.parse_select_statement()?)),
+            Keyword::INSERT => SQLStatement::Insert(Box::new(self.parse_insert_statement()?)),
+            Keyword::UPDATE => SQLStatement::Update(self.parse_update_statement()?),
+            Keyword::DELETE => SQLStatement::Delete(self.parse_delete_statement()?),
+            #[cfg(feature = "ddl")]
+            Keyword::CREATE => SQLStatement::Create(self.parse_create_statement()?),
+            #[cfg(feature = "ddl")]
+            Keyword::ALTER => SQLStatement::Alter(self.parse_alter_statement()?),
+            Keyword::SHOW => SQLStatement::Show(self.parse_show_statement()?),
+            Keyword::SET => SQLStatement::Set(self.parse_set_statement()?),
+            _ => return Err(self.unsupported("statement")),
+        };
+        self.expect_statement_end()?;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(tokens = self.current - start, "statement parsed");
+        Ok(stmt)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_statement_dispatch() {
+        let parse = |sql: &str| Parser::new_from_sql(sql).parse();
+        assert!(matches!(parse("SELECT 1"), Ok(SQLStatement::Select(_))));
+        assert!(matches!(parse("insert INTO t VALUES (1)"), Ok(SQLStatement::Insert(_))));
+        assert!(matches!(parse("UPDATE t SET a = 1"), Ok(SQLStatement::Update(_))));
+        assert!(matches!(parse("DELETE FROM t"), Ok(SQLStatement::Delete(_))));
+        assert!(matches!(parse("SHOW TABLES"), Ok(SQLStatement::Show(_))));
+        assert!(matches!(parse("SET @a := 1"), Ok(SQLStatement::Set(_))));
+        #[cfg(feature = "ddl")]
+        {
+            assert!(matches!(parse("CREATE TABLE t (id INT)"), Ok(SQLStatement::Create(_))));
+            assert!(matches!(parse("ALTER TABLE t DROP COLUMN a"), Ok(SQLStatement::Alter(_))));
+        }
+
+        let err = parse("GRANT ALL ON t TO u").unwrap_err();
+        assert!(matches!(*err.kind, ErrorKind::Unsupported { .. }));
+        let err = parse("(SELECT 1)").unwrap_err();
+        assert!(matches!(*err.kind, ErrorKind::Expected { .. }));
+        let err = parse("").unwrap_err();
+        assert!(matches!(*err.kind, ErrorKind::Expected { .. }));
+        // 语句之后只能是输入结尾或 ';'
+        assert!(parse("SELECT * FROM t;").is_ok());
+        // 作为结束符的 ';' 被消费
+        let mut parser = Parser::new_from_sql("SELECT 1; SELECT 2");
+        assert!(parser.parse().is_ok());
+        assert_eq!(parser.peek(), Some(&Token::Keyword { keyword: Keyword::SELECT, text: "SELECT".to_string() }));
+        // 第一个 garbage 是表的别名
+        let err = parse("SELECT * FROM t garbage garbage").unwrap_err();
+        assert_eq!(*err.kind, ErrorKind::TrailingInput { found: Token::Identifier("garbage".to_string()) });
+        assert_eq!(err.span, Some(Span::new(24, 31)));
+        // 位置由词法分析时记录，不受转义和注释影响
+        let sql = "SELECT 'it\\'s -- x', `a``b` /* garbage */ FROM t x garbage";
+        let span = parse(sql).unwrap_err().span.unwrap();
+        assert_eq!((&sql[span.start..span.end], span.start), ("garbage", sql.len() - 7));
+        assert!(parse("DELETE FROM t WHERE id = 1 LIMIT 1 2").is_err());
+        let options = ParserOptions { require_statement_end: false, ..Default::default() };
+        let mut parser = Parser::new_from_sql("SELECT * FROM t garbage garbage").with_options(options);
+        assert!(parser.parse().is_ok());
+        assert_eq!(parser.peek(), Some(&Token::Identifier("garbage".to_string())));
+
+        // 具体解析器的错误原样返回
+        let err = parse("INSERT t VALUES (1)").unwrap_err();
+        assert_eq!(err.message, "Expected INTO, found identifier t");
+    }
+
+    #[test]
+    fn test_token_cursor() {
+        let mut parser = Parser::new_from_sql("SELECT a");
+        assert_eq!(parser.consume_token(), Some(&Token::Keyword { keyword: Keyword::SELECT, text: "SELECT".to_string() }));
+        assert_eq!(parser.peek(), Some(&Token::Identifier("a".to_string())));
+        assert!(parser.consume_token().is_some());
+        // 到达结尾后不再前进，回退仍然指向最后一个token
+        assert_eq!(parser.consume_token(), None);
+        parser.back();
+        assert_eq!(parser.peek(), Some(&Token::Identifier("a".to_string())));
+    }
+
+    #[test]
+    fn test_parse_statements() {
+        let parse = |sql: &str| Parser::new_from_sql(sql).parse_statements();
+        let statements = parse(";; SELECT 1;;\n; UPDATE t SET a = 1; ;DELETE FROM t").unwrap();
+        assert_eq!(statements.len(), 3);
+        assert!(matches!(statements[0], SQLStatement::Select(_)));
+        assert!(matches!(statements[1], SQLStatement::Update(_)));
+        assert!(matches!(statements[2], SQLStatement::Delete(_)));
+        assert!(parse("").unwrap().is_empty());
+        assert!(parse(" ; ;; ").unwrap().is_empty());
+
+        // 缺少分隔符时第二条语句是多余的输入
+        let err = parse("SELECT 1 FROM t SELECT 2").unwrap_err();
+        assert_eq!(*err.kind, ErrorKind::TrailingInput { found: Token::Keyword { keyword: Keyword::SELECT, text: "SELECT".to_string() } });
+    }
+
+    #[test]
+    fn test_lex_errors() {
+        let sql = "SELECT a FROM t WHERE b = 'x\n  AND c = 1";
+        let err = Parser::new_from_sql(sql).parse().unwrap_err();
+        assert!(matches!(*err.kind, ErrorKind::Lex(LexError::Unterminated { quote: '\'', .. })));
+        assert_eq!(err.message, "Unterminated string literal starting at line 1 col 27");
+        assert_eq!(err.line_column(), Some((1, 27)));
+        assert_eq!(err.localized(Language::Chinese), "未闭合的字符串，开始于第 1 行第 27 列");
+        // 恢复得到的token可以作为别名，解析成功时同样报告
+        let err = Parser::new_from_sql("SELECT a `b").parse().unwrap_err();
+        assert_eq!(err.message, "Unterminated quoted identifier starting at line 1 col 10");
+        // 之前的语句不受影响，之前的语法错误照常报告
+        let mut parser = Parser::new_from_sql("SELECT 1; SELECT 'x");
+        assert!(parser.parse().is_ok());
+        assert!(matches!(*parser.parse().unwrap_err().kind, ErrorKind::Lex(_)));
+        let err = Parser::new_from_sql("SELECT FROM; SELECT 'x").parse().unwrap_err();
+        assert!(matches!(*err.kind, ErrorKind::Expected { .. }));
+
+        // 宽松模式下作为普通的字符串解析
+        let options = ParserOptions { lenient_lexing: true, ..Default::default() };
+        assert!(Parser::new_from_sql(sql).with_options(options).parse().is_ok());
+        // 切换方言重新拆分token时同样检查
+        let options = ParserOptions { dialect: Dialect::Postgres, ..Default::default() };
+        assert!(Parser::new_from_sql("SELECT 'x").with_options(options).parse().is_err());
+        assert!(Parser::new_from_sql("SELECT '''x'''").parse().is_ok());
+    }
+
+    #[test]
+    fn test_normalize_case() {
+        let options = ParserOptions { normalize_case: true, ..Default::default() };
+        let parse = |sql: &str| Parser::new_from_sql(sql).with_options(options).parse();
+        assert_eq!(
+            parse("select ID, `name` from Users u where U.Age > 1").unwrap(),
+            parse("SELECT id, `name` FROM users U WHERE u.age > 1").unwrap()
+        );
+        // 加引号的标识符默认区分大小写，需要显式开启
+        assert_ne!(parse("SELECT `Name` FROM t").unwrap(), parse("SELECT `name` FROM t").unwrap());
+        let folded = ParserOptions { fold_quoted_identifiers: true, ..options };
+        assert_eq!(
+            Parser::new_from_sql("SELECT `Name` FROM t").with_options(folded).parse().unwrap(),
+            parse("SELECT `name` FROM t").unwrap()
+        );
+        assert_ne!(
+            Parser::new_from_sql("SELECT ID FROM t").parse().unwrap(),
+            Parser::new_from_sql("SELECT id FROM t").parse().unwrap()
+        );
+        // 错误位置指向原始SQL
+        let err = parse("SELECT * FROM Users Garbage Garbage").unwrap_err();
+        assert_eq!(*err.kind, ErrorKind::TrailingInput { found: Token::Identifier("garbage".to_string()) });
+        assert_eq!(err.span, Some(Span::new(28, 35)));
     }
 }
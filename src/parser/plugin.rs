@@ -0,0 +1,156 @@
+//! 语句解析插件
+//!
+//! [`StatementParser`] 是对象安全的，可以通过 `Box<dyn StatementParser>` 或 `&mut dyn StatementParser` 使用。
+//! 需要支持内置解析器不认识的语句时，实现 [`StatementPlugin`] 并用 [`PluginParser`] 包装 [`Parser`]：
+//! 每条语句先依次交给插件，都不处理时再按第一个关键字使用内置的解析器。
+
+use super::{ParseError, Parser, StatementParser};
+use crate::ast::SQLStatement;
+
+impl<P: StatementParser + ?Sized> StatementParser for Box<P> {
+    fn parse(&mut self) -> Result<SQLStatement, ParseError> {
+        (**self).parse()
+    }
+}
+
+impl<P: StatementParser + ?Sized> StatementParser for &mut P {
+    fn parse(&mut self) -> Result<SQLStatement, ParseError> {
+        (**self).parse()
+    }
+}
+
+/// 语句解析插件
+pub trait StatementPlugin {
+    /// 尝试从当前位置解析一条语句，不处理时返回 None
+    ///
+    /// 返回 None 时插件消费的token会被退回，下一个插件或内置解析器从同一位置开始。
+    fn parse(&self, parser: &mut Parser) -> Option<Result<SQLStatement, ParseError>>;
+}
+
+/// 带插件的语句解析器
+pub struct PluginParser {
+    parser: Parser,
+    plugins: Vec<Box<dyn StatementPlugin>>,
+}
+
+impl PluginParser {
+    pub fn new(parser: Parser) -> Self {
+        PluginParser { parser, plugins: Vec::new() }
+    }
+
+    // 添加插件，按添加的顺序尝试
+    pub fn with_plugin(mut self, plugin: impl StatementPlugin + 'static) -> Self {
+        self.plugins.push(Box::new(plugin));
+        self
+    }
+
+    pub fn parser(&self) -> &Parser {
+        &self.parser
+    }
+
+    pub fn into_inner(self) -> Parser {
+        self.parser
+    }
+
+    // 与 Parser::parse_statements 相同，每条语句都先交给插件
+    pub fn parse_statements(&mut self) -> Result<Vec<SQLStatement>, ParseError> {
+        let mut statements = Vec::new();
+        while self.parser.skip_empty_statements() {
+            statements.push(self.parse()?);
+        }
+        Ok(statements)
+    }
+}
+
+impl StatementParser for PluginParser {
+    fn parse(&mut self) -> Result<SQLStatement, ParseError> {
+        let start = self.parser.current;
+        for plugin in &self.plugins {
+            match plugin.parse(&mut self.parser) {
+                Some(result) => {
+                    let stmt = result?;
+                    self.parser.expect_statement_end()?;
+                    return Ok(stmt);
+                }
+                None => self.parser.current = start,
+            }
+        }
+        self.parser.parse()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ast::select::{SelectColumn, SelectModifiers, SelectStatement};
+    use crate::kerwords::Keyword;
+
+    // MySQL 8 的 TABLE t，等价于 SELECT * FROM t
+    struct TableStatement;
+
+    impl StatementPlugin for TableStatement {
+        fn parse(&self, parser: &mut Parser) -> Option<Result<SQLStatement, ParseError>> {
+            if !parser.match_keyword(Keyword::TABLE) {
+                return None;
+            }
+            Some(parser.parse_table_reference(false).map(|table| {
+                SQLStatement::Select(Box::new(SelectStatement {
+                    with: None,
+                    columns: vec![SelectColumn::Wildcard],
+                    distinct: false,
+                    distinct_on: None,
+                    modifiers: SelectModifiers::default(),
+                    top: None,
+                    from: Some(table),
+                    joins: Vec::new(),
+                    where_clause: None,
+                    group_by: None,
+                    with_rollup: false,
+                    having: None,
+                    qualify: None,
+                    windows: Vec::new(),
+                    set_operations: Vec::new(),
+                    order_by: None,
+                    limit: None,
+                    into: None,
+                    lock: None,
+                }))
+            }))
+        }
+    }
+
+    // 消费了token但不处理的插件
+    struct Greedy;
+
+    impl StatementPlugin for Greedy {
+        fn parse(&self, parser: &mut Parser) -> Option<Result<SQLStatement, ParseError>> {
+            parser.skip(2);
+            None
+        }
+    }
+
+    #[test]
+    fn test_plugin_parser() {
+        let parse = |sql: &str| {
+            let mut parser: Box<dyn StatementParser> =
+                Box::new(PluginParser::new(Parser::new_from_sql(sql)).with_plugin(Greedy).with_plugin(TableStatement));
+            parser.parse()
+        };
+        match parse("TABLE users") {
+            Ok(SQLStatement::Select(select)) => {
+                assert_eq!(select.columns, vec![SelectColumn::Wildcard]);
+                assert_eq!(&*select.from.unwrap().name.name().value, "users");
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        // 插件不处理的语句交给内置解析器
+        assert!(matches!(parse("DELETE FROM users"), Ok(SQLStatement::Delete(_))));
+        assert!(parse("TABLE").is_err());
+        assert!(parse("TABLE users u garbage").is_err());
+
+        let mut parser = PluginParser::new(Parser::new_from_sql("TABLE a; SELECT 1;; TABLE b;")).with_plugin(TableStatement);
+        let statements = parser.parse_statements().unwrap();
+        assert_eq!(statements.len(), 3);
+        assert!(statements.iter().all(|stmt| matches!(stmt, SQLStatement::Select(_))));
+    }
+}
@@ -0,0 +1,436 @@
+use super::{ParseError, Parser};
+use super::delete::DeleteStatementParser;
+use super::insert::InsertStatementParser;
+use super::select::SelectStatementParser;
+use super::update::UpdateStatementParser;
+use crate::ast::expr::Expr;
+use crate::ast::routine::{HandlerAction, HandlerCondition, RoutineStatement, SignalCondition};
+use crate::ast::SQLStatement;
+use crate::token::Token;
+use crate::kerwords::Keyword;
+
+/// 存储过程/函数体解析器接口
+pub trait RoutineStatementParser {
+    type Error;
+    // 解析存储过程/函数体中的一条语句，不包含结尾的分号
+    fn parse_routine_statement(&mut self) -> Result<RoutineStatement, Self::Error>;
+}
+
+impl Parser {
+    // 解析语句前的标签，例如 "read_loop:"
+    fn parse_statement_label(&mut self) -> Option<String> {
+        if let (Some(Token::Identifier(label)), Some(Token::Punctuator(':'))) = (self.peek(), self.peek_n(1)) {
+            let label = label.to_owned();
+            self.skip(2);
+            Some(label)
+        } else {
+            None
+        }
+    }
+
+    // 解析 END 之后可选的结束标签，必须与开始标签一致
+    fn parse_end_label(&mut self, label: &Option<String>) -> Result<(), ParseError> {
+        if let (Some(label), Some(Token::Identifier(end_label))) = (label, self.peek()) {
+            if label != end_label {
+                return Err(self.get_parse_error(&format!(
+                    "End label {} does not match begin label {}",
+                    end_label, label
+                )));
+            }
+            self.consume_token();
+        }
+        Ok(())
+    }
+
+    // 解析一个标识符名称，例如变量名、游标名或标签
+    fn parse_routine_name(&mut self, object: &str) -> Result<String, ParseError> {
+        self.parse_identifier(object)
+    }
+
+    // 解析以逗号分隔的名称列表
+    fn parse_routine_name_list(&mut self, object: &str) -> Result<Vec<String>, ParseError> {
+        let mut names = Vec::new();
+        loop {
+            names.push(self.parse_routine_name(object)?);
+            if !self.match_punctuator(',') {
+                break;
+            }
+        }
+        Ok(names)
+    }
+
+    // 解析语句列表，每条语句以分号结束，遇到任一结束关键字时停止（不消费）
+    fn parse_routine_statement_list(&mut self, terminators: &[Keyword]) -> Result<Vec<RoutineStatement>, ParseError> {
+        let mut statements = Vec::new();
+        while !terminators.iter().any(|t| self.is_keyword(*t)) {
+            if !self.has_more() {
+                let expected: Vec<&str> = terminators.iter().map(|t| t.as_str()).collect();
+                return Err(self.expected(&expected.join(" or ")));
+            }
+            statements.push(self.parse_routine_statement()?);
+            if !self.match_punctuator(';') {
+                return Err(self.expected("';' after statement"));
+            }
+        }
+        Ok(statements)
+    }
+
+    // 期望 END 后紧跟指定的关键字，例如 END IF、END WHILE
+    fn expect_end_of(&mut self, keyword: Keyword) -> Result<(), ParseError> {
+        if !self.match_keyword(Keyword::END) || !self.match_keyword(keyword) {
+            return Err(self.expected(&format!("END {}", keyword)));
+        }
+        Ok(())
+    }
+
+    // 解析 name = expr [, name = expr] ... 形式的赋值列表
+    fn parse_routine_assignments(&mut self) -> Result<Vec<(String, Expr)>, ParseError> {
+        let mut assignments = Vec::new();
+        loop {
+            let name = self.parse_variable_name()?;
+            if !self.match_operator("=") && !self.match_operator(":=") {
+                return Err(self.expected("= or := after variable name"));
+            }
+            assignments.push((name, self.parse_assigned_value()?));
+            if !self.match_punctuator(',') {
+                break;
+            }
+        }
+        Ok(assignments)
+    }
+
+    // 解析 SQLSTATE [VALUE] 'sqlstate_value' 中的状态值，SQLSTATE 已经解析
+    fn parse_sqlstate_value(&mut self) -> Result<String, ParseError> {
+        self.match_keyword(Keyword::VALUE);
+        match self.peek() {
+            Some(Token::StringLiteral(s)) => {
+                let state = s.to_owned();
+                self.consume_token();
+                Ok(state)
+            }
+            _ => Err(self.expected("SQLSTATE value")),
+        }
+    }
+
+    // 解析异常处理器的单个条件
+    fn parse_handler_condition(&mut self) -> Result<HandlerCondition, ParseError> {
+        if self.match_keyword(Keyword::SQLSTATE) {
+            return Ok(HandlerCondition::SqlState(self.parse_sqlstate_value()?));
+        }
+        if self.match_keyword(Keyword::SQLWARNING) {
+            return Ok(HandlerCondition::SqlWarning);
+        }
+        if self.match_keyword(Keyword::SQLEXCEPTION) {
+            return Ok(HandlerCondition::SqlException);
+        }
+        if self.match_keyword(Keyword::NOT) {
+            self.expect_keyword(Keyword::FOUND, "NOT")?;
+            return Ok(HandlerCondition::NotFound);
+        }
+        if let Some(Token::NumericLiteral(n)) = self.peek() {
+            let code = n.parse::<u32>().map_err(|_| {
+                self.invalid_value("error code", n)
+            })?;
+            self.consume_token();
+            return Ok(HandlerCondition::ErrorCode(code));
+        }
+        Ok(HandlerCondition::Name(self.parse_routine_name("condition")?))
+    }
+
+    // 解析DECLARE语句，DECLARE 已经解析
+    fn parse_declare(&mut self) -> Result<RoutineStatement, ParseError> {
+        // DECLARE {CONTINUE | EXIT | UNDO} HANDLER FOR ...
+        let is_handler = self.is_keyword_at(1, Keyword::HANDLER);
+        if is_handler {
+            let action = if self.match_keyword(Keyword::CONTINUE) {
+                HandlerAction::Continue
+            } else if self.match_keyword(Keyword::EXIT) {
+                HandlerAction::Exit
+            } else if self.match_keyword(Keyword::UNDO) {
+                HandlerAction::Undo
+            } else {
+                return Err(self.expected("CONTINUE, EXIT or UNDO"));
+            };
+            self.skip(1); // 消费HANDLER
+            if !self.match_keyword(Keyword::FOR) {
+                return Err(self.expected("FOR after HANDLER"));
+            }
+            let mut conditions = Vec::new();
+            loop {
+                conditions.push(self.parse_handler_condition()?);
+                if !self.match_punctuator(',') {
+                    break;
+                }
+            }
+            let statement = Box::new(self.parse_routine_statement()?);
+            return Ok(RoutineStatement::DeclareHandler { action, conditions, statement });
+        }
+
+        // DECLARE cursor_name CURSOR FOR select_statement
+        if matches!(self.peek_n(1), Some(Token::Keyword { keyword: Keyword::CURSOR, .. })) {
+            let name = self.parse_routine_name("cursor")?;
+            self.skip(1); // 消费CURSOR
+            if !self.match_keyword(Keyword::FOR) {
+                return Err(self.expected("FOR after CURSOR"));
+            }
+            let query = Box::new(self.parse_select_statement()?);
+            return Ok(RoutineStatement::DeclareCursor { name, query });
+        }
+
+        // DECLARE var [, var] ... type [DEFAULT expr]
+        let names = self.parse_routine_name_list("variable")?;
+        let data_type = self.parse_data_type()?;
+        let default = if self.match_keyword(Keyword::DEFAULT) {
+            Some(self.parse_expr(0)?)
+        } else {
+            None
+        };
+        Ok(RoutineStatement::DeclareVariable { names, data_type, default })
+    }
+
+    // 解析IF语句，IF 已经解析
+    fn parse_if(&mut self) -> Result<RoutineStatement, ParseError> {
+        let mut branches = Vec::new();
+        loop {
+            let condition = self.parse_expr(0)?;
+            if !self.match_keyword(Keyword::THEN) {
+                return Err(self.expected("THEN after IF condition"));
+            }
+            let statements = self.parse_routine_statement_list(&[Keyword::ELSEIF, Keyword::ELSE, Keyword::END])?;
+            branches.push((condition, statements));
+            if !self.match_keyword(Keyword::ELSEIF) {
+                break;
+            }
+        }
+        let else_branch = if self.match_keyword(Keyword::ELSE) {
+            Some(self.parse_routine_statement_list(&[Keyword::END])?)
+        } else {
+            None
+        };
+        self.expect_end_of(Keyword::IF)?;
+        Ok(RoutineStatement::If { branches, else_branch })
+    }
+
+    // 解析SIGNAL语句，SIGNAL 已经解析
+    fn parse_signal(&mut self) -> Result<RoutineStatement, ParseError> {
+        let condition = if self.match_keyword(Keyword::SQLSTATE) {
+            SignalCondition::SqlState(self.parse_sqlstate_value()?)
+        } else {
+            SignalCondition::Name(self.parse_routine_name("condition")?)
+        };
+        let set = if self.match_keyword(Keyword::SET) {
+            self.parse_routine_assignments()?
+        } else {
+            Vec::new()
+        };
+        Ok(RoutineStatement::Signal { condition, set })
+    }
+
+    // 解析FETCH语句，FETCH 已经解析
+    fn parse_fetch(&mut self) -> Result<RoutineStatement, ParseError> {
+        if self.match_keyword(Keyword::NEXT) && !self.is_keyword(Keyword::FROM) {
+            return Err(self.expected("FROM after FETCH NEXT"));
+        }
+        self.match_keyword(Keyword::FROM);
+        let cursor = self.parse_routine_name("cursor")?;
+        if !self.match_keyword(Keyword::INTO) {
+            return Err(self.expected("INTO after cursor name"));
+        }
+        let into = self.parse_routine_name_list("variable")?;
+        Ok(RoutineStatement::Fetch { cursor, into })
+    }
+
+    // 暂不支持的语句，保留到分号之前的原始token
+    fn parse_raw_statement(&mut self) -> Result<RoutineStatement, ParseError> {
+        let mut tokens = Vec::new();
+        while self.has_more() && !self.is_punctuator(';') {
+            if let Some(token) = self.consume_token() {
+                tokens.push(token.clone());
+            }
+        }
+        if tokens.is_empty() {
+            return Err(self.expected("statement"));
+        }
+        Ok(RoutineStatement::Raw(tokens))
+    }
+}
+
+impl RoutineStatementParser for Parser {
+    type Error = ParseError;
+    fn parse_routine_statement(&mut self) -> Result<RoutineStatement, Self::Error> {
+        let label = self.parse_statement_label();
+
+        // 可以带标签的语句
+        if self.match_keyword(Keyword::BEGIN) {
+            let statements = self.parse_routine_statement_list(&[Keyword::END])?;
+            self.match_keyword(Keyword::END);
+            self.parse_end_label(&label)?;
+            return Ok(RoutineStatement::Compound { label, statements });
+        }
+        if self.match_keyword(Keyword::WHILE) {
+            let condition = self.parse_expr(0)?;
+            if !self.match_keyword(Keyword::DO) {
+                return Err(self.expected("DO after WHILE condition"));
+            }
+            let body = self.parse_routine_statement_list(&[Keyword::END])?;
+            self.expect_end_of(Keyword::WHILE)?;
+            self.parse_end_label(&label)?;
+            return Ok(RoutineStatement::While { label, condition, body });
+        }
+        if self.match_keyword(Keyword::LOOP) {
+            let body = self.parse_routine_statement_list(&[Keyword::END])?;
+            self.expect_end_of(Keyword::LOOP)?;
+            self.parse_end_label(&label)?;
+            return Ok(RoutineStatement::Loop { label, body });
+        }
+        if self.match_keyword(Keyword::REPEAT) {
+            let body = self.parse_routine_statement_list(&[Keyword::UNTIL])?;
+            self.match_keyword(Keyword::UNTIL);
+            let condition = self.parse_expr(0)?;
+            self.expect_end_of(Keyword::REPEAT)?;
+            self.parse_end_label(&label)?;
+            return Ok(RoutineStatement::Repeat { label, body, condition });
+        }
+        if let Some(label) = label {
+            return Err(self.get_parse_error(&format!(
+                "Label {} must be followed by BEGIN, LOOP, REPEAT or WHILE",
+                label
+            )));
+        }
+
+        if self.match_keyword(Keyword::DECLARE) {
+            self.parse_declare()
+        } else if self.match_keyword(Keyword::IF) {
+            self.parse_if()
+        } else if self.match_keyword(Keyword::LEAVE) {
+            Ok(RoutineStatement::Leave(self.parse_routine_name("label")?))
+        } else if self.match_keyword(Keyword::ITERATE) {
+            Ok(RoutineStatement::Iterate(self.parse_routine_name("label")?))
+        } else if self.is_keyword(Keyword::OPEN) && matches!(self.peek_n(1), Some(Token::Identifier(_))) {
+            self.skip(1);
+            Ok(RoutineStatement::Open(self.parse_routine_name("cursor")?))
+        } else if self.is_keyword(Keyword::CLOSE) && matches!(self.peek_n(1), Some(Token::Identifier(_))) {
+            self.skip(1);
+            Ok(RoutineStatement::Close(self.parse_routine_name("cursor")?))
+        } else if self.match_keyword(Keyword::FETCH) {
+            self.parse_fetch()
+        } else if self.match_keyword(Keyword::SIGNAL) {
+            self.parse_signal()
+        } else if self.match_keyword(Keyword::RETURN) {
+            Ok(RoutineStatement::Return(self.parse_expr(0)?))
+        } else if self.match_keyword(Keyword::SET) {
+            Ok(RoutineStatement::Set(self.parse_routine_assignments()?))
+        } else if self.is_keyword(Keyword::SELECT) {
+            Ok(RoutineStatement::Sql(Box::new(SQLStatement::Select(Box::new(self.parse_select_statement()?)))))
+        } else if self.is_keyword(Keyword::INSERT) {
+            Ok(RoutineStatement::Sql(Box::new(SQLStatement::Insert(Box::new(self.parse_insert_statement()?)))))
+        } else if self.is_keyword(Keyword::DELETE) {
+            Ok(RoutineStatement::Sql(Box::new(SQLStatement::Delete(self.parse_delete_statement()?))))
+        } else if self.is_keyword(Keyword::UPDATE) {
+            Ok(RoutineStatement::Sql(Box::new(SQLStatement::Update(self.parse_update_statement()?))))
+        } else {
+            self.parse_raw_statement()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ast::create::CreateStatement;
+    use crate::ast::expr::{BinaryOperator, Value};
+    use crate::parser::create::CreateStatementParser;
+
+    #[test]
+    fn test_routine_body() {
+        let sql = "CREATE PROCEDURE archive_orders(IN max_id INT)
+                   BEGIN
+                       DECLARE done INT DEFAULT 0;
+                       DECLARE order_id INT;
+                       DECLARE cur CURSOR FOR SELECT id FROM orders WHERE id < max_id;
+                       DECLARE CONTINUE HANDLER FOR NOT FOUND SET done = 1;
+                       OPEN cur;
+                       read_loop: LOOP
+                           FETCH cur INTO order_id;
+                           IF done = 1 THEN
+                               LEAVE read_loop;
+                           ELSEIF order_id < 0 THEN
+                               SIGNAL SQLSTATE '45000' SET MESSAGE_TEXT = 'bad id';
+                           ELSE
+                               UPDATE orders SET archived = 1 WHERE id = order_id;
+                           END IF;
+                       END LOOP read_loop;
+                       CLOSE cur;
+                   END";
+        let mut parser = Parser::new_from_sql(sql);
+        let CreateStatement::Routine(routine) = parser.parse_create_statement().unwrap() else {
+            panic!("Expected CREATE PROCEDURE");
+        };
+
+        let mut parser = Parser::new(routine.body);
+        let result = parser.parse_routine_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        assert!(!parser.has_more());
+
+        let RoutineStatement::Compound { label, statements } = result.unwrap() else {
+            panic!("Expected compound statement");
+        };
+        assert_eq!(label, None);
+        assert_eq!(statements.len(), 7);
+        assert!(matches!(&statements[0], RoutineStatement::DeclareVariable { names, default: Some(Expr::Literal(Value::Integer(0))), .. } if names == &vec!["done".to_string()]));
+        assert!(matches!(&statements[2], RoutineStatement::DeclareCursor { name, .. } if name == "cur"));
+        assert!(matches!(&statements[3], RoutineStatement::DeclareHandler {
+            action: HandlerAction::Continue, conditions, ..
+        } if conditions == &vec![HandlerCondition::NotFound]));
+        assert!(matches!(&statements[4], RoutineStatement::Open(name) if name == "cur"));
+        assert!(matches!(&statements[6], RoutineStatement::Close(name) if name == "cur"));
+
+        let RoutineStatement::Loop { label, body } = &statements[5] else {
+            panic!("Expected LOOP, found {:?}", statements[5]);
+        };
+        assert_eq!(label.as_deref(), Some("read_loop"));
+        assert!(matches!(&body[0], RoutineStatement::Fetch { cursor, into } if cursor == "cur" && into == &vec!["order_id".to_string()]));
+        let RoutineStatement::If { branches, else_branch } = &body[1] else {
+            panic!("Expected IF, found {:?}", body[1]);
+        };
+        assert_eq!(branches.len(), 2);
+        assert!(matches!(&branches[0].0, Expr::BinaryOp { op: BinaryOperator::Eq, .. }));
+        assert!(matches!(&branches[0].1[0], RoutineStatement::Leave(label) if label == "read_loop"));
+        assert!(matches!(&branches[1].1[0], RoutineStatement::Signal {
+            condition: SignalCondition::SqlState(state), set
+        } if state == "45000" && set.len() == 1));
+        assert!(matches!(&else_branch.as_ref().unwrap()[0], RoutineStatement::Sql(stmt) if matches!(**stmt, SQLStatement::Update(_))));
+    }
+
+    #[test]
+    fn test_routine_loop_errors() {
+        let sql = "l1: WHILE i < 10 DO SET i = i + 1; END WHILE l2";
+        let mut parser = Parser::new_from_sql(sql);
+        assert!(parser.parse_routine_statement().is_err());
+
+        let sql = "REPEAT SET i = i + 1; UNTIL i >= 10 END REPEAT";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_routine_statement();
+        assert!(matches!(result, Ok(RoutineStatement::Repeat { label: None, .. })), "{:?}", result);
+
+        // SET 中 := 与 = 相同，也可以给用户变量赋值
+        let sql = "SET @row := 0, total := total + 1";
+        let mut parser = Parser::new_from_sql(sql);
+        let Ok(RoutineStatement::Set(assignments)) = parser.parse_routine_statement() else {
+            panic!("Expected SET");
+        };
+        assert_eq!(assignments[0], ("@row".to_string(), Expr::Literal(Value::Integer(0))));
+        assert_eq!(assignments[1].0, "total");
+
+        let sql = "BEGIN RETURN 1";
+        let mut parser = Parser::new_from_sql(sql);
+        assert!(parser.parse_routine_statement().is_err());
+
+        // 暂不支持的语句保留为原始token
+        let sql = "BEGIN TRUNCATE TABLE logs; END";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_routine_statement();
+        assert!(matches!(result, Ok(RoutineStatement::Compound { statements, .. })
+            if matches!(&statements[0], RoutineStatement::Raw(tokens) if tokens.len() == 3)));
+    }
+}
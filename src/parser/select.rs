@@ -1,9 +1,14 @@
-use super::{ParseError, Parser};
+use super::{Dialect, ParseError, Parser};
 use crate::ast::{
-    expr::Expr,
-    select::{SelectColumn, SelectStatement},
+    common::{JsonTableColumn, JsonTableFallback, ObjectName, Quantity, QuantityUnit, TableFunction, TableReference},
+    expr::{Expr, SortKey, WindowSpec},
+    select::{
+        Cte, FieldsOptions, IntoClause, Join, JoinKind, LinesOptions, LockClause, LockMode, LockWait, SelectColumn,
+        SelectModifiers, SelectStatement, SetOperation, SetOperator, Top, With,
+    },
 };
 use crate::token::Token;
+use crate::kerwords::Keyword;
 
 // SELECT语句解析器接口
 pub trait SelectStatementParser {
@@ -16,59 +21,338 @@ pub trait SelectStatementParser {
 const WHERE_IDX: u8 = 1;
 const GROUP_BY_IDX: u8 = 2;
 const HAVING_IDX: u8 = 3;
-const ORDER_BY_IDX: u8 = 4;
-const LIMIT_IDX: u8 = 5;
+const QUALIFY_IDX: u8 = 4;
+const WINDOW_IDX: u8 = 5;
+const ORDER_BY_IDX: u8 = 6;
+const LIMIT_IDX: u8 = 7;
 const FROM_IDX: u8 = 0;
 
 // 实现其它解析功能
 impl Parser {
     // 解析单个选择列
     fn parse_select_column(&mut self) -> Result<SelectColumn, ParseError> {
-        // 获取列名
-        let name = match self.peek() {
-            Some(Token::Identifier(ident)) => {
-                let column_name = ident.to_owned();
-                self.consume_token();
-                column_name
+        // t.* 被拆分为表名、'.' 和 '*' 三个token
+        let is_name = matches!(
+            self.peek(),
+            Some(Token::Identifier(_) | Token::QuotedIdentifier(_) | Token::QualifiedIdentifier(_))
+        );
+        if is_name
+            && matches!(self.peek_n(1), Some(Token::Punctuator('.')))
+            && matches!(self.peek_n(2), Some(Token::Operator(op)) if op == "*")
+        {
+            let table = self.parse_object_name("table")?;
+            self.skip(2);
+            return Ok(SelectColumn::QualifiedWildcard(table));
+        }
+        // 先按表达式解析，单独的列名保持为Column，其它情况（字面量、函数调用、变量等）为Expression
+        let expr = self.parse_expr(0)?;
+        // 检查是否有别名，省略 AS 时别名是标识符或非保留字，WITH 可能开始 WITH CHECK OPTION 之类的子句
+        let implicit_alias = match self.peek() {
+            Some(Token::Identifier(ident)) => ident != "@",
+            Some(Token::QuotedIdentifier(_)) => true,
+            Some(Token::Keyword { keyword, .. }) => *keyword != Keyword::WITH && !keyword.is_reserved(self.options.dialect),
+            _ => false,
+        };
+        let alias = if self.match_keyword(Keyword::AS) || implicit_alias {
+            Some(self.parse_identifier("alias")?)
+        } else {
+            None
+        };
+
+        match expr {
+            Expr::Identifier(name) if !name.name().value.starts_with('@') => Ok(SelectColumn::Column { name, alias }),
+            expr => Ok(SelectColumn::Expression { expr, alias }),
+        }
+    }
+
+    // 解析 DISTINCT/ALL，PostgreSQL 方言下还支持 DISTINCT ON (expr, ...)
+    fn parse_distinct(&mut self) -> Result<(bool, Option<Vec<Expr>>), ParseError> {
+        if !self.match_keyword(Keyword::DISTINCT) {
+            self.match_keyword(Keyword::ALL);
+            return Ok((false, None));
+        }
+        if self.options.dialect != Dialect::Postgres || !self.match_keyword(Keyword::ON) {
+            return Ok((true, None));
+        }
+        if !self.match_punctuator('(') {
+            return Err(self.expected("opening parenthesis after DISTINCT ON"));
+        }
+        let mut exprs = Vec::new();
+        loop {
+            exprs.push(self.parse_expr(0)?);
+            if !self.match_punctuator(',') {
+                break;
             }
-            _ => {
-                return Err(
-                    self.get_parse_error(&format!("Expected column name, found {:?}", self.peek()))
-                );
+        }
+        if !self.match_punctuator(')') {
+            return Err(self.expected("closing parenthesis after DISTINCT ON expressions"));
+        }
+        Ok((true, Some(exprs)))
+    }
+
+    // 解析 SQL Server 的 TOP n [PERCENT] [WITH TIES]，TOP 已经消费
+    // 不带括号时只接受数字，避免把 TOP 10 * 中的星号当作乘法
+    fn parse_top(&mut self) -> Result<Top, ParseError> {
+        let value = match self.peek() {
+            Some(Token::NumericLiteral(n)) => {
+                let n = n.clone();
+                self.consume_token();
+                self.numeric_literal(n)?
             }
+            Some(Token::Punctuator('(')) => self.parse_parenthesized_expr("TOP")?,
+            _ => return Err(self.expected("number after TOP")),
         };
-        // 检查是否有AS别名
-        let alias = if self.match_keyword("AS") {
-            if let Some(Token::Identifier(ident)) = self.peek() {
-                let alias_name = ident.to_owned();
-                self.consume_token();
-                Some(alias_name)
+        let unit = if self.match_keyword(Keyword::PERCENT) { QuantityUnit::Percent } else { QuantityUnit::Rows };
+        let with_ties = self.is_keyword(Keyword::WITH) && self.is_keyword_at(1, Keyword::TIES);
+        if with_ties {
+            self.skip(2);
+        }
+        Ok(Top { quantity: Quantity { value, unit }, with_ties })
+    }
+
+    // 解析 DISTINCT 之后的修饰符，MySQL 不限制它们的顺序，但同一个修饰符只能出现一次
+    fn parse_select_modifiers(&mut self) -> Result<SelectModifiers, ParseError> {
+        let mut modifiers = SelectModifiers::default();
+        while let Some(Token::Keyword { keyword, .. }) = self.peek() {
+            let keyword = *keyword;
+            let flag = match keyword {
+                Keyword::HIGH_PRIORITY => &mut modifiers.high_priority,
+                Keyword::STRAIGHT_JOIN => &mut modifiers.straight_join,
+                Keyword::SQL_SMALL_RESULT => &mut modifiers.sql_small_result,
+                Keyword::SQL_BIG_RESULT => &mut modifiers.sql_big_result,
+                Keyword::SQL_BUFFER_RESULT => &mut modifiers.sql_buffer_result,
+                Keyword::SQL_NO_CACHE => &mut modifiers.sql_no_cache,
+                Keyword::SQL_CALC_FOUND_ROWS => &mut modifiers.sql_calc_found_rows,
+                _ => break,
+            };
+            if *flag {
+                return Err(self.get_parse_error(&format!("Duplicate {} modifier", keyword)));
+            }
+            *flag = true;
+            self.consume_token();
+        }
+        Ok(modifiers)
+    }
+
+    // 解析 FROM 第一个表之后的连接和逗号分隔的表
+    fn parse_joins(&mut self) -> Result<Vec<Join>, ParseError> {
+        let mut joins = Vec::new();
+        loop {
+            if self.match_punctuator(',') {
+                let table = self.parse_table_factor()?;
+                joins.push(Join { kind: JoinKind::Comma, table, on: None, using: None, natural: false });
+                continue;
+            }
+            let natural = self.match_keyword(Keyword::NATURAL);
+            let kind = if self.match_keyword(Keyword::JOIN) {
+                JoinKind::Inner
+            } else if self.match_keyword(Keyword::STRAIGHT_JOIN) {
+                JoinKind::Straight
+            } else {
+                let kind = if self.match_keyword(Keyword::INNER) {
+                    JoinKind::Inner
+                } else if self.match_keyword(Keyword::CROSS) {
+                    JoinKind::Cross
+                } else if self.match_keyword(Keyword::LEFT) {
+                    JoinKind::Left
+                } else if self.match_keyword(Keyword::RIGHT) {
+                    JoinKind::Right
+                } else if natural {
+                    return Err(self.expected("JOIN after NATURAL"));
+                } else {
+                    return Ok(joins);
+                };
+                if matches!(kind, JoinKind::Left | JoinKind::Right) {
+                    self.match_keyword(Keyword::OUTER);
+                }
+                if !self.match_keyword(Keyword::JOIN) {
+                    return Err(self.expected("JOIN"));
+                }
+                kind
+            };
+            if natural && matches!(kind, JoinKind::Cross | JoinKind::Straight) {
+                return Err(self.get_parse_error("NATURAL can only be used with [INNER], LEFT or RIGHT JOIN"));
+            }
+            let table = self.parse_table_factor()?;
+            let (on, using) = if natural {
+                if self.is_keyword(Keyword::ON) || self.is_keyword(Keyword::USING) {
+                    return Err(self.get_parse_error("NATURAL JOIN cannot have ON or USING"));
+                }
+                (None, None)
+            } else if self.match_keyword(Keyword::ON) {
+                (Some(self.parse_expr(0)?), None)
+            } else if kind != JoinKind::Straight && self.match_keyword(Keyword::USING) {
+                (None, Some(self.parse_column_name_list()?))
+            } else if matches!(kind, JoinKind::Left | JoinKind::Right) {
+                return Err(self.expected("ON or USING for outer join"));
             } else {
-                return Err(self.get_parse_error(&format!(
-                    "Expected alias after AS, found {:?}",
-                    self.peek()
-                )));
+                (None, None)
+            };
+            joins.push(Join { kind, table, on, using, natural });
+        }
+    }
+
+    // FROM 和 JOIN 中的表，或者 [LATERAL] (SELECT ...) [AS] alias 派生表、JSON_TABLE(...) [AS] alias 表函数，
+    // 派生表和表函数必须有别名
+    fn parse_table_factor(&mut self) -> Result<TableReference, ParseError> {
+        let lateral = self.match_keyword(Keyword::LATERAL);
+        let (subquery, mut function) = if self.is_punctuator('(') && self.is_query_at(1) {
+            self.consume_token();
+            let subquery = self.parse_subquery()?;
+            if !self.match_punctuator(')') {
+                return Err(self.expected("closing parenthesis after derived table"));
             }
+            (Some(Box::new(subquery)), None)
+        } else if !lateral && self.is_keyword(Keyword::JSON_TABLE) && self.peek_n(1) == Some(&Token::Punctuator('(')) {
+            (None, Some(Box::new(self.parse_json_table()?)))
+        } else if self.options.dialect == Dialect::Postgres
+            && matches!(self.peek(), Some(Token::Identifier(_)))
+            && self.peek_n(1) == Some(&Token::Punctuator('('))
+        {
+            // PostgreSQL 中函数前的 LATERAL 可以省略
+            (None, Some(Box::new(self.parse_table_function_call()?)))
+        } else if lateral {
+            return Err(self.expected("subquery after LATERAL"));
+        } else {
+            return self.parse_table_reference(true);
+        };
+        let alias = if self.match_keyword(Keyword::AS) {
+            Some(self.parse_identifier("alias")?)
+        } else if let Some(Token::Identifier(ident)) | Some(Token::QuotedIdentifier(ident)) = self.peek() {
+            let alias = ident.clone();
+            self.consume_token();
+            Some(alias)
         } else {
             None
         };
+        let alias = match (alias, function.as_deref_mut()) {
+            (Some(alias), Some(TableFunction::Call { column_aliases, .. })) => {
+                if self.is_punctuator('(') {
+                    *column_aliases = self.parse_column_name_list()?;
+                }
+                alias
+            }
+            (Some(alias), _) => alias,
+            // 没有别名时 PostgreSQL 以函数名作为表名
+            (None, Some(TableFunction::Call { name, .. })) => name.clone(),
+            (None, _) if subquery.is_some() => return Err(self.expected("alias for derived table")),
+            (None, _) => return Err(self.expected("alias for JSON_TABLE")),
+        };
+        #[cfg(feature = "metrics")]
+        {
+            self.collector.tables += 1;
+        }
+        Ok(TableReference {
+            name: ObjectName::new([alias]),
+            partitions: None,
+            alias: None,
+            index_hints: Vec::new(),
+            sample: None,
+            subquery,
+            function,
+            lateral,
+        })
+    }
 
-        Ok(SelectColumn::Column { name, alias })
+    // JSON_TABLE(expr, path COLUMNS (...))，当前token为 JSON_TABLE
+    fn parse_json_table(&mut self) -> Result<TableFunction, ParseError> {
+        self.skip(2);
+        let expr = self.parse_expr(0)?;
+        if !self.match_punctuator(',') {
+            return Err(self.expected("',' after JSON_TABLE document"));
+        }
+        let path = self.parse_string_literal("JSON path")?;
+        let columns = self.parse_json_table_columns()?;
+        if !self.match_punctuator(')') {
+            return Err(self.expected("closing parenthesis after JSON_TABLE"));
+        }
+        Ok(TableFunction::JsonTable { expr, path, columns })
     }
 
-    fn parse_select_columns(&mut self) -> Result<(Vec<SelectColumn>, bool), ParseError> {
+    // name(args) [WITH ORDINALITY]，当前token为函数名
+    fn parse_table_function_call(&mut self) -> Result<TableFunction, ParseError> {
+        let Some(Token::Identifier(name)) = self.peek() else {
+            return Err(self.expected("table function name"));
+        };
+        let name = name.clone();
+        self.skip(2);
+        let args = self.parse_function_args()?;
+        let with_ordinality = self.is_keyword(Keyword::WITH) && self.is_keyword_at(1, Keyword::ORDINALITY);
+        if with_ordinality {
+            self.skip(2);
+        }
+        Ok(TableFunction::Call { name, args, with_ordinality, column_aliases: Vec::new() })
+    }
+
+    // COLUMNS (column, ...)，NESTED PATH 中可以再嵌套
+    fn parse_json_table_columns(&mut self) -> Result<Vec<JsonTableColumn>, ParseError> {
+        self.expect_keyword(Keyword::COLUMNS, "JSON path")?;
+        if !self.match_punctuator('(') {
+            return Err(self.expected("'(' after COLUMNS"));
+        }
         let mut columns = Vec::new();
-        // 更清晰的写法
-        let distinct = if self.match_keyword("DISTINCT") {
-            true
+        loop {
+            let column = if self.match_keyword(Keyword::NESTED) {
+                self.match_keyword(Keyword::PATH);
+                let path = self.parse_string_literal("JSON path")?;
+                JsonTableColumn::Nested { path, columns: self.parse_json_table_columns()? }
+            } else {
+                let name = self.parse_identifier("column")?;
+                if self.match_keyword(Keyword::FOR) {
+                    self.expect_keyword(Keyword::ORDINALITY, "FOR")?;
+                    JsonTableColumn::Ordinality(name)
+                } else {
+                    let data_type = self.parse_data_type()?;
+                    let exists = self.match_keyword(Keyword::EXISTS);
+                    self.expect_keyword(Keyword::PATH, "column type")?;
+                    let path = self.parse_string_literal("JSON path")?;
+                    let mut on_empty = None;
+                    let mut on_error = None;
+                    while let Some(fallback) = self.parse_json_table_fallback()? {
+                        if !self.match_keyword(Keyword::ON) {
+                            return Err(self.expected("ON EMPTY or ON ERROR"));
+                        }
+                        // ON EMPTY 必须在 ON ERROR 之前
+                        if on_error.is_none() && on_empty.is_none() && self.match_keyword(Keyword::EMPTY) {
+                            on_empty = Some(fallback);
+                        } else if on_error.is_none() && self.match_keyword(Keyword::ERROR) {
+                            on_error = Some(fallback);
+                        } else {
+                            return Err(self.expected("ON EMPTY or ON ERROR"));
+                        }
+                    }
+                    JsonTableColumn::Path { name, data_type, exists, path, on_empty, on_error }
+                }
+            };
+            columns.push(column);
+            if self.match_punctuator(')') {
+                return Ok(columns);
+            }
+            if !self.match_punctuator(',') {
+                return Err(self.expected("',' or ')' in JSON_TABLE columns"));
+            }
+        }
+    }
+
+    // NULL、ERROR 或 DEFAULT 'json'，之后是 ON EMPTY 或 ON ERROR
+    fn parse_json_table_fallback(&mut self) -> Result<Option<JsonTableFallback>, ParseError> {
+        if self.match_keyword(Keyword::NULL) {
+            Ok(Some(JsonTableFallback::Null))
+        } else if self.match_keyword(Keyword::ERROR) {
+            Ok(Some(JsonTableFallback::Error))
+        } else if self.match_keyword(Keyword::DEFAULT) {
+            Ok(Some(JsonTableFallback::Default(self.parse_string_literal("DEFAULT value")?)))
         } else {
-            self.match_keyword("ALL");
-            false
-        };
+            Ok(None)
+        }
+    }
+
+    fn parse_select_columns(&mut self) -> Result<Vec<SelectColumn>, ParseError> {
+        let mut columns = Vec::new();
         // 判断是否为*
         if self.match_operator("*") {
             columns.push(SelectColumn::Wildcard);
-            return Ok((columns, distinct));
+            return Ok(columns);
         }
         // 解析列列表
         loop {
@@ -81,15 +365,213 @@ impl Parser {
             }
         }
 
-        Ok((columns, distinct))
+        Ok(columns)
+    }
+
+    // 解析导出选项中 BY 之后的字符串
+    fn parse_export_string(&mut self, option: &str) -> Result<String, ParseError> {
+        if !self.match_keyword(Keyword::BY) {
+            return Err(self.expected(&format!("BY after {}", option)));
+        }
+        match self.peek() {
+            Some(Token::StringLiteral(s)) => {
+                let value = s.to_owned();
+                self.consume_token();
+                Ok(value)
+            }
+            _ => Err(self.expected(&format!("string after {} BY", option))),
+        }
+    }
+
+    // 解析INTO子句，INTO 已经解析
+    fn parse_into_clause(&mut self) -> Result<IntoClause, ParseError> {
+        let is_outfile = self.match_keyword(Keyword::OUTFILE);
+        if is_outfile || self.match_keyword(Keyword::DUMPFILE) {
+            let path = match self.peek() {
+                Some(Token::StringLiteral(s)) => {
+                    let path = s.to_owned();
+                    self.consume_token();
+                    path
+                }
+                _ => {
+                    return Err(self.expected("file path"));
+                }
+            };
+            if !is_outfile {
+                return Ok(IntoClause::Dumpfile(path));
+            }
+
+            let charset = if self.match_keyword(Keyword::CHARACTER) {
+                if !self.match_keyword(Keyword::SET) {
+                    return Err(self.expected("SET after CHARACTER"));
+                }
+                Some(self.parse_variable_name()?)
+            } else {
+                None
+            };
+
+            let fields = if self.match_keyword(Keyword::FIELDS) || self.match_keyword(Keyword::COLUMNS) {
+                let mut fields = FieldsOptions::default();
+                loop {
+                    if self.match_keyword(Keyword::TERMINATED) {
+                        fields.terminated_by = Some(self.parse_export_string("TERMINATED")?);
+                    } else if self.match_keyword(Keyword::OPTIONALLY) {
+                        self.expect_keyword(Keyword::ENCLOSED, "OPTIONALLY")?;
+                        fields.optionally_enclosed = true;
+                        fields.enclosed_by = Some(self.parse_export_string("ENCLOSED")?);
+                    } else if self.match_keyword(Keyword::ENCLOSED) {
+                        fields.enclosed_by = Some(self.parse_export_string("ENCLOSED")?);
+                    } else if self.match_keyword(Keyword::ESCAPED) {
+                        fields.escaped_by = Some(self.parse_export_string("ESCAPED")?);
+                    } else {
+                        break;
+                    }
+                }
+                Some(fields)
+            } else {
+                None
+            };
+
+            let lines = if self.match_keyword(Keyword::LINES) {
+                let mut lines = LinesOptions::default();
+                loop {
+                    if self.match_keyword(Keyword::STARTING) {
+                        lines.starting_by = Some(self.parse_export_string("STARTING")?);
+                    } else if self.match_keyword(Keyword::TERMINATED) {
+                        lines.terminated_by = Some(self.parse_export_string("TERMINATED")?);
+                    } else {
+                        break;
+                    }
+                }
+                Some(lines)
+            } else {
+                None
+            };
+
+            return Ok(IntoClause::Outfile { path, charset, fields, lines });
+        }
+
+        // INTO var [, var] ...
+        let mut variables = Vec::new();
+        loop {
+            variables.push(self.parse_variable_name()?);
+            if !self.match_punctuator(',') {
+                break;
+            }
+        }
+        Ok(IntoClause::Variables(variables))
+    }
+
+    // 解析 FOR UPDATE | FOR SHARE | LOCK IN SHARE MODE，以及可选的 NOWAIT | SKIP LOCKED
+    fn parse_lock_clause(&mut self) -> Result<Option<LockClause>, ParseError> {
+        let mode = if self.match_keyword(Keyword::FOR) {
+            if self.match_keyword(Keyword::UPDATE) {
+                LockMode::Update
+            } else if self.match_keyword(Keyword::SHARE) {
+                LockMode::Share
+            } else {
+                return Err(self.expected("UPDATE or SHARE after FOR"));
+            }
+        } else if self.match_keyword(Keyword::LOCK) {
+            self.expect_keyword(Keyword::IN, "LOCK")?;
+            self.expect_keyword(Keyword::SHARE, "LOCK IN")?;
+            self.expect_keyword(Keyword::MODE, "LOCK IN SHARE")?;
+            // 旧语法不支持 NOWAIT、SKIP LOCKED
+            return Ok(Some(LockClause { mode: LockMode::Share, wait: LockWait::Wait }));
+        } else {
+            return Ok(None);
+        };
+        let wait = if self.match_keyword(Keyword::NOWAIT) {
+            LockWait::Nowait
+        } else if self.match_keyword(Keyword::SKIP) {
+            self.expect_keyword(Keyword::LOCKED, "SKIP")?;
+            LockWait::SkipLocked
+        } else {
+            LockWait::Wait
+        };
+        Ok(Some(LockClause { mode, wait }))
+    }
+
+    // WINDOW w AS (...) [, w2 AS (...)] ...
+    fn parse_named_windows(&mut self) -> Result<Vec<(String, WindowSpec)>, ParseError> {
+        let mut windows = Vec::new();
+        loop {
+            let name = self.parse_identifier("window name")?;
+            if !self.match_keyword(Keyword::AS) {
+                return Err(self.expected("AS after window name"));
+            }
+            if !self.match_punctuator('(') {
+                return Err(self.expected("'(' after AS"));
+            }
+            let spec = self.parse_window_spec()?;
+            if !self.match_punctuator(')') {
+                return Err(self.expected("')' after window specification"));
+            }
+            windows.push((name, spec));
+            if !self.match_punctuator(',') {
+                break;
+            }
+        }
+        Ok(windows)
+    }
+
+    // 解析完整个语句后检查窗口引用：
+    // 命名窗口不能重名，只能引用在它之前定义的窗口；OVER 中引用的窗口必须在 WINDOW 子句中定义。
+    // 引用其它窗口时不能再指定 PARTITION BY，被引用的窗口已有 ORDER BY 或框架时不能再指定
+    fn check_window_references(&self, select: &SelectStatement) -> Result<(), ParseError> {
+        let check = |defined: &[(String, WindowSpec)], spec: &WindowSpec| -> Result<(), ParseError> {
+            let Some(name) = &spec.name else {
+                return Ok(());
+            };
+            let Some((_, base)) = defined.iter().find(|(window, _)| window.eq_ignore_ascii_case(name)) else {
+                return Err(self.get_parse_error(&format!("Window {} is not defined", name)));
+            };
+            if !spec.partition_by.is_empty() {
+                return Err(self.get_parse_error(&format!("Cannot override PARTITION BY of window {}", name)));
+            }
+            if !spec.order_by.is_empty() && !base.order_by.is_empty() {
+                return Err(self.get_parse_error(&format!("Cannot override ORDER BY of window {}", name)));
+            }
+            if base.frame.is_some() {
+                return Err(self.get_parse_error(&format!("Cannot reference window {} which has a frame", name)));
+            }
+            Ok(())
+        };
+        for (i, (name, spec)) in select.windows.iter().enumerate() {
+            if select.windows[..i].iter().any(|(window, _)| window.eq_ignore_ascii_case(name)) {
+                return Err(self.get_parse_error(&format!("Window {} is defined more than once", name)));
+            }
+            check(&select.windows[..i], spec)?;
+        }
+        let exprs = select
+            .columns
+            .iter()
+            .filter_map(|column| match column {
+                SelectColumn::Expression { expr, .. } => Some(expr),
+                _ => None,
+            })
+            .chain(select.having.as_ref())
+            .chain(select.qualify.as_ref())
+            .chain(select.order_by.iter().flatten().filter_map(|item| match &item.expr {
+                SortKey::Expr(expr) => Some(expr),
+                SortKey::Ordinal(_) => None,
+            }));
+        for expr in exprs {
+            for node in expr.iter() {
+                if let Expr::Window { over, .. } = node {
+                    check(&select.windows, over)?;
+                }
+            }
+        }
+        Ok(())
     }
 
-    fn parse_group_exr(&mut self) -> Result<Vec<Expr>, ParseError> {
+    fn parse_group_exr(&mut self) -> Result<Vec<SortKey>, ParseError> {
         let mut group_by = Vec::new();
         // 解析列列表
         loop {
             // 解析单个列
-            group_by.push(self.parse_expr(0)?);
+            group_by.push(self.parse_sort_key(true)?);
 
             // 如果后面是逗号，继续解析下一个列
             if !self.match_punctuator(',') {
@@ -98,84 +580,249 @@ impl Parser {
         }
         Ok(group_by)
     }
-}
 
-impl SelectStatementParser for Parser {
-    type Error = ParseError;
-    fn parse_select_statement(&mut self) -> Result<SelectStatement, Self::Error> {
+    // WITH 之后的 [RECURSIVE] name [(column, ...)] AS (query) [, ...]
+    fn parse_with(&mut self) -> Result<With, ParseError> {
+        let recursive = self.match_keyword(Keyword::RECURSIVE);
+        let mut ctes = Vec::new();
+        loop {
+            let name = self.parse_identifier("CTE")?;
+            let columns = if self.is_punctuator('(') {
+                self.parse_column_name_list()?
+            } else {
+                Vec::new()
+            };
+            self.expect_keyword(Keyword::AS, "CTE name")?;
+            if !self.match_punctuator('(') {
+                return Err(self.expected("'(' after AS"));
+            }
+            let query = self.parse_subquery()?;
+            if !self.match_punctuator(')') {
+                return Err(self.expected("')' after CTE query"));
+            }
+            ctes.push(Cte { name, columns, query });
+            if !self.match_punctuator(',') {
+                break;
+            }
+        }
+        Ok(With { recursive, ctes })
+    }
+
+    fn match_set_operator(&mut self) -> Option<SetOperator> {
+        let op = match self.peek() {
+            Some(Token::Keyword { keyword: Keyword::UNION, .. }) => SetOperator::Union,
+            Some(Token::Keyword { keyword: Keyword::EXCEPT, .. }) => SetOperator::Except,
+            Some(Token::Keyword { keyword: Keyword::INTERSECT, .. }) => SetOperator::Intersect,
+            _ => return None,
+        };
+        self.consume_token();
+        Some(op)
+    }
+
+    /// 从第 n 个token开始是否为查询：SELECT、WITH，或者括号中的查询，例如 ((SELECT 1) UNION (SELECT 2))
+    pub(crate) fn is_query_at(&self, n: usize) -> bool {
+        let mut n = n;
+        while self.peek_n(n) == Some(&Token::Punctuator('(')) {
+            n += 1;
+        }
+        self.is_keyword_at(n, Keyword::SELECT) || self.is_keyword_at(n, Keyword::WITH)
+    }
+
+    // 集合运算的一个操作数：不带 ORDER BY、LIMIT 的 SELECT，或者括号中的完整查询
+    fn parse_query_term(&mut self) -> Result<SelectStatement, ParseError> {
+        if !self.match_punctuator('(') {
+            return self.parse_select_core();
+        }
+        let select = self.parse_subquery()?;
+        if !self.match_punctuator(')') {
+            return Err(self.expected("closing parenthesis after query"));
+        }
+        Ok(select)
+    }
+
+    // SELECT 到 WINDOW 子句为止的部分
+    fn parse_select_core(&mut self) -> Result<SelectStatement, ParseError> {
         // 期望以SELECT关键字开始
-        if !self.match_keyword("SELECT") {
-            return Err(self.get_parse_error(&format!("Expected SELECT, found{:?}", self.peek())));
+        if !self.match_keyword(Keyword::SELECT) {
+            return Err(self.expected("SELECT"));
         }
         // 解析列
-        let (columns, distinct) = self.parse_select_columns()?;
-        // 必须有FROM子句
-        if !self.match_keyword("FROM") {
-            return Err(self.get_parse_error(&format!("Expected FROM, found {:?}", self.peek())));
-        }
-        // 解析FROM的表引用
-        let from = self.parse_table_reference(true)?;
+        let (distinct, distinct_on) = self.parse_distinct()?;
+        let top = if self.options.dialect == Dialect::MsSql && self.match_keyword(Keyword::TOP) {
+            Some(self.parse_top()?)
+        } else {
+            None
+        };
+        let modifiers = self.parse_select_modifiers()?;
+        let columns = self.parse_select_columns()?;
+        // INTO 可以出现在FROM之前
+        let into = if self.match_keyword(Keyword::INTO) {
+            Some(self.parse_into_clause()?)
+        } else {
+            None
+        };
+        // 可选的FROM子句，例如 SELECT 1、SELECT NOW() 没有FROM
+        let (from, joins) = if self.match_keyword(Keyword::FROM) {
+            let table = self.parse_table_factor()?;
+            let joins = self.parse_joins()?;
+            // FROM DUAL 等价于没有FROM子句
+            let is_dual = table.name.0.len() == 1
+                && table.name.name().value.eq_ignore_ascii_case("DUAL")
+                && table.partitions.is_none()
+                && table.alias.is_none()
+                && table.index_hints.is_empty()
+                && joins.is_empty();
+            if is_dual && self.options.normalize_dual {
+                (None, joins)
+            } else {
+                (Some(table), joins)
+            }
+        } else {
+            (None, Vec::new())
+        };
         // 跟踪当前已处理的最高子句索引
         let mut current_idx: u8 = FROM_IDX;
         // 可选的WHERE子句
-        let where_clause = if self.match_keyword("WHERE") {
+        let where_clause = if self.match_keyword(Keyword::WHERE) {
             current_idx = self.move_current_idx(current_idx, WHERE_IDX,get_clause_name)?;
             Some(self.parse_expr(0)?)
         } else {
             None
         };
         // 可选的GROUP BY子句
-        let group_by = if self.match_keyword("GROUP") {
-            if !self.match_keyword("BY") {
-                return Err(self.get_parse_error(&format!(
-                    "Expected BY after GROUP, found {:?}",
-                    self.peek()
-                )));
+        let group_by = if self.match_keyword(Keyword::GROUP) {
+            if !self.match_keyword(Keyword::BY) {
+                return Err(self.expected("BY after GROUP"));
             }
             current_idx = self.move_current_idx(current_idx, GROUP_BY_IDX,get_clause_name)?;
             Some(self.parse_group_exr()?)
         } else {
             None
         };
+        // GROUP BY ... WITH ROLLUP
+        let with_rollup = group_by.is_some()
+            && self.is_keyword(Keyword::WITH)
+            && self.is_keyword_at(1, Keyword::ROLLUP);
+        if with_rollup {
+            self.skip(2);
+        }
         // 可选的HAVING子句
-        let having = if self.match_keyword("HAVING") {
+        let having = if self.match_keyword(Keyword::HAVING) {
             current_idx = self.move_current_idx(current_idx, HAVING_IDX,get_clause_name)?;
             Some(self.parse_expr(0)?)
         } else {
             None
         };
-        // 可选的ORDER BY子句
-        let order_by = if self.match_keyword("ORDER") {
-            if !self.match_keyword("BY") {
-                return Err(self.get_parse_error(&format!(
-                    "Expected BY after ORDER, found {:?}",
-                    self.peek()
-                )));
-            }
-            current_idx = self.move_current_idx(current_idx, ORDER_BY_IDX,get_clause_name)?;
-            Some(self.parse_order_by()?)
+        // 可选的QUALIFY子句
+        let qualify = if self.options.dialect.supports_qualify() && self.match_keyword(Keyword::QUALIFY) {
+            current_idx = self.move_current_idx(current_idx, QUALIFY_IDX,get_clause_name)?;
+            Some(self.parse_expr(0)?)
         } else {
             None
         };
-        // 可选的LIMIT子句
-        let limit = if self.match_keyword("LIMIT") {
-            // Since this is the last clause, we don't need to store the updated index
-            self.move_current_idx(current_idx, LIMIT_IDX,get_clause_name)?;
-            Some(self.parse_limit()?)
+        // 可选的WINDOW子句
+        let windows = if self.match_keyword(Keyword::WINDOW) {
+            // ORDER BY 和 LIMIT 由 parse_select_statement 解析，这里不需要保存更新后的索引
+            self.move_current_idx(current_idx, WINDOW_IDX,get_clause_name)?;
+            self.parse_named_windows()?
         } else {
-            None
+            Vec::new()
         };
-
-        Ok(SelectStatement {
+        let select = SelectStatement {
+            with: None,
             columns,
             distinct,
+            distinct_on,
+            modifiers,
+            top,
             from,
+            joins,
             where_clause,
             group_by,
+            with_rollup,
             having,
-            order_by,
-            limit,
-        })
+            qualify,
+            windows,
+            set_operations: Vec::new(),
+            order_by: None,
+            limit: None,
+            into,
+            lock: None,
+        };
+        self.check_window_references(&select)?;
+        Ok(select)
+    }
+
+}
+
+impl SelectStatementParser for Parser {
+    type Error = ParseError;
+    fn parse_select_statement(&mut self) -> Result<SelectStatement, Self::Error> {
+        let with = if self.match_keyword(Keyword::WITH) {
+            Some(self.parse_with()?)
+        } else {
+            None
+        };
+        // 第一个查询带括号时，它就是整个查询，之后的集合运算和子句都加在它上面
+        let parenthesized = self.is_punctuator('(');
+        let mut select = self.parse_query_term()?;
+        if with.is_some() {
+            if select.with.is_some() {
+                return Err(self.unsupported("WITH on both a parenthesized query and the whole query"));
+            }
+            select.with = with;
+        }
+        // 括号中第一个查询自带的集合运算
+        let inner_operations = select.set_operations.len();
+        while let Some(op) = self.match_set_operator() {
+            if parenthesized && (select.order_by.is_some() || select.limit.is_some()) {
+                return Err(self.unsupported("ORDER BY or LIMIT in the first query of a set operation"));
+            }
+            // 按 SQL 的优先级，INTERSECT 先于它前面的 UNION、EXCEPT 计算，不能与括号中的查询合并
+            if op == SetOperator::Intersect && select.set_operations[..inner_operations].iter().any(|s| s.op != op) {
+                return Err(self.unsupported("INTERSECT after a parenthesized UNION or EXCEPT"));
+            }
+            let all = self.match_keyword(Keyword::ALL);
+            if !all {
+                self.match_keyword(Keyword::DISTINCT);
+            }
+            let operand = self.parse_query_term()?;
+            select.set_operations.push(SetOperation { op, all, select: operand });
+        }
+        // 之后的子句作用于整个查询，带括号的第一个查询不能再有同样的子句
+        let mut current_idx: u8 = WINDOW_IDX;
+        // 可选的ORDER BY子句
+        if self.match_keyword(Keyword::ORDER) {
+            if !self.match_keyword(Keyword::BY) {
+                return Err(self.expected("BY after ORDER"));
+            }
+            current_idx = self.move_current_idx(current_idx, ORDER_BY_IDX,get_clause_name)?;
+            if select.order_by.is_some() {
+                return Err(self.unsupported("ORDER BY on both a parenthesized query and the whole query"));
+            }
+            select.order_by = Some(self.parse_order_by(true)?);
+        }
+        // 可选的LIMIT子句
+        if self.match_keyword(Keyword::LIMIT) {
+            // Since this is the last clause, we don't need to store the updated index
+            self.move_current_idx(current_idx, LIMIT_IDX,get_clause_name)?;
+            if select.limit.is_some() {
+                return Err(self.unsupported("LIMIT on both a parenthesized query and the whole query"));
+            }
+            select.limit = Some(self.parse_limit()?);
+        }
+        if let Some(lock) = self.parse_lock_clause()? {
+            select.lock = Some(lock);
+        }
+        // INTO 也可以出现在语句末尾，但只能出现一次
+        if self.match_keyword(Keyword::INTO) {
+            if select.into.is_some() {
+                return Err(self.get_parse_error("Multiple INTO clauses are not allowed"));
+            }
+            select.into = Some(self.parse_into_clause()?);
+        }
+        self.check_window_references(&select)?;
+        Ok(select)
     }
 }
 
@@ -185,6 +832,8 @@ fn get_clause_name(idx: u8) -> &'static str {
         WHERE_IDX => "WHERE",
         GROUP_BY_IDX => "GROUP BY",
         HAVING_IDX => "HAVING",
+        QUALIFY_IDX => "QUALIFY",
+        WINDOW_IDX => "WINDOW",
         ORDER_BY_IDX => "ORDER BY",
         LIMIT_IDX => "LIMIT",
         _ => "FROM",
@@ -196,9 +845,14 @@ fn get_clause_name(idx: u8) -> &'static str {
 mod test {
     use super::*;
     use crate::token::tokenize;
-    use crate::ast::common::TableReference;
-    use crate::ast::select::{SelectStatement, SelectColumn};
-    use crate::ast::expr::{BinaryOperator, Expr, LimitClause, OrderByExpr, Value};
+    use crate::ast::common::{
+        Ident, IndexHint, IndexHintKind, IndexHintScope, ObjectName, SampleMethod, TableReference,
+    };
+    use crate::parser::{Dialect, ParserOptions};
+    use crate::error::{ErrorKind, Language};
+    use crate::token::Token;
+    use crate::ast::select::{SelectStatement, SelectColumn, SelectModifiers};
+    use crate::ast::expr::{BinaryOperator, Expr, LimitClause, OrderByExpr, SortKey, Value};
 
     #[test]
     fn test_select_parser() {
@@ -210,35 +864,50 @@ mod test {
         let result = parser.parse_select_statement();
         if let Ok(select) = result {
             let expect = SelectStatement {
+                with: None,
                 columns: vec![
                     SelectColumn::Column {
-                        name: "id".to_string(),
+                        name: ObjectName::new(["id"]),
                         alias: None,
                     },
                     SelectColumn::Column {
-                        name: "name".to_string(),
+                        name: ObjectName::new(["name"]),
                         alias: Some("user_name".to_string()),
                     },
                 ],
                 distinct: false,
-                from: TableReference {
-                    name: "users".to_string(),
+                distinct_on: None,
+                modifiers: SelectModifiers::default(),
+                top: None,
+                joins: Vec::new(),
+                from: Some(TableReference {
+                    name: ObjectName::new(["users"]),
+                    partitions: None,
                     alias: None,
-                },
+                    index_hints: Vec::new(),
+                    sample: None,
+                    subquery: None,
+                    function: None,
+                    lateral: false,
+                }),
                 where_clause: Some(Expr::BinaryOp {
-                    left: Box::new(Expr::Identifier("age".to_string())),
+                    left: Box::new(Expr::Identifier(ObjectName::new(["age"]))),
                     op: BinaryOperator::GtEq,
                     right: Box::new(Expr::Literal(Value::Integer(18))),
                 }),
                 group_by: None,
+                with_rollup: false,
                 having: None,
+                qualify: None,
+                windows: Vec::new(),
+                set_operations: Vec::new(),
                 order_by:Some(vec![
                     OrderByExpr {
-                        expr: Expr::Identifier("name".to_string()),
+                        expr: SortKey::Expr(Expr::Identifier(ObjectName::new(["name"]))),
                         asc:false,
                     },
                     OrderByExpr {
-                        expr: Expr::Identifier("age".to_string()),
+                        expr: SortKey::Expr(Expr::Identifier(ObjectName::new(["age"]))),
                         asc:true,
                     },
                 ]),
@@ -246,10 +915,770 @@ mod test {
                     limit: 10,
                     offset: None,
                 }),
+                into: None,
+                lock: None,
             };
             assert_eq!(select, expect);
         } else {
             println!("Error: {:?}", result.unwrap_err());
         }
     }
+
+    #[test]
+    fn test_select_partition_selection() {
+        let sql = "SELECT id FROM orders PARTITION (p0, p1) AS o WHERE id > 10";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_select_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let select = result.unwrap();
+        assert_eq!(select.from, Some(TableReference {
+            name: ObjectName::new(["orders"]),
+            partitions: Some(vec!["p0".to_string(), "p1".to_string()]),
+            alias: Some("o".to_string()),
+            index_hints: Vec::new(),
+            sample: None,
+            subquery: None,
+            function: None,
+            lateral: false,
+        }));
+    }
+
+    #[test]
+    fn test_select_without_from() {
+        let sql = "SELECT 1, NOW() AS now, @@version";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_select_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let select = result.unwrap();
+        assert_eq!(select.from, None);
+        assert_eq!(select.columns, vec![
+            SelectColumn::Expression {
+                expr: Expr::Literal(Value::Integer(1)),
+                alias: None,
+            },
+            SelectColumn::Expression {
+                expr: Expr::FunctionCall { name: "NOW".to_string(), args: vec![] },
+                alias: Some("now".to_string()),
+            },
+            SelectColumn::Expression {
+                expr: Expr::Identifier(ObjectName::new(["@@version"])),
+                alias: None,
+            },
+        ]);
+
+        // 没有FROM时仍然可以带WHERE、LIMIT
+        let mut parser = Parser::new_from_sql("SELECT 1 WHERE 1 = 1 LIMIT 1");
+        assert!(parser.parse_select_statement().is_ok());
+    }
+
+    #[test]
+    fn test_select_from_dual() {
+        let sql = "SELECT 1 + 1 FROM DUAL";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_select_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        assert_eq!(result.unwrap().from, None);
+
+        // 关闭规范化时保留DUAL表
+        let options = ParserOptions { normalize_dual: false, ..Default::default() };
+        let mut parser = Parser::new_from_sql(sql).with_options(options);
+        let result = parser.parse_select_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        assert_eq!(result.unwrap().from, Some(TableReference {
+            name: ObjectName::new(["DUAL"]),
+            partitions: None,
+            alias: None,
+            index_hints: Vec::new(),
+            sample: None,
+            subquery: None,
+            function: None,
+            lateral: false,
+        }));
+    }
+
+    #[test]
+    fn test_non_reserved_keyword_as_identifier() {
+        let sql = "SELECT status, key, timestamp AS offset FROM values WHERE end = 1";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_select_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let select = result.unwrap();
+        assert_eq!(select.columns, vec![
+            SelectColumn::Column { name: ObjectName::new(["status"]), alias: None },
+            SelectColumn::Column { name: ObjectName::new(["key"]), alias: None },
+            SelectColumn::Column { name: ObjectName::new(["timestamp"]), alias: Some("offset".to_string()) },
+        ]);
+        assert_eq!(select.from.unwrap().name.to_string(), "values");
+        assert_eq!(select.where_clause, Some(Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(ObjectName::new(["end"]))),
+            op: BinaryOperator::Eq,
+            right: Box::new(Expr::Literal(Value::Integer(1))),
+        }));
+
+        // 保留字仍然需要反引号
+        let mut parser = Parser::new_from_sql("SELECT id FROM order");
+        assert!(parser.parse_select_statement().is_err());
+        let mut parser = Parser::new_from_sql("SELECT id FROM `order`");
+        assert!(parser.parse_select_statement().is_ok());
+    }
+
+    #[test]
+    fn test_dialect_reserved_keyword() {
+        // qualify 在 MySQL 中可以作为列名，在 BigQuery 中是保留字
+        let sql = "SELECT qualify FROM t";
+        assert!(Parser::new_from_sql(sql).parse_select_statement().is_ok());
+        let options = ParserOptions { dialect: Dialect::BigQuery, ..Default::default() };
+        assert!(Parser::new_from_sql(sql).with_options(options).parse_select_statement().is_err());
+
+        // signal 只在 MySQL 中是保留字
+        let sql = "SELECT id FROM signal";
+        assert!(Parser::new_from_sql(sql).parse_select_statement().is_err());
+        let options = ParserOptions { dialect: Dialect::Postgres, ..Default::default() };
+        assert!(Parser::new_from_sql(sql).with_options(options).parse_select_statement().is_ok());
+    }
+
+    #[test]
+    fn test_select_qualified_names() {
+        let sql = "SELECT mydb.users.id FROM `mydb`.`users` WHERE catalog.mydb.users.age > 18";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_select_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let select = result.unwrap();
+        assert_eq!(select.columns, vec![SelectColumn::Column {
+            name: ObjectName::new(["mydb", "users", "id"]),
+            alias: None,
+        }]);
+        let from = select.from.unwrap();
+        assert_eq!(from.name, ObjectName(vec![Ident::with_quote('`', "mydb"), Ident::with_quote('`', "users")]));
+        assert_eq!(from.name.qualifier(), &[Ident::with_quote('`', "mydb")]);
+        assert_eq!(from.name.name().value, "users");
+        assert_eq!(from.name.to_string(), "`mydb`.`users`");
+        assert_eq!(select.where_clause, Some(Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(ObjectName::new(["catalog", "mydb", "users", "age"]))),
+            op: BinaryOperator::Gt,
+            right: Box::new(Expr::Literal(Value::Integer(18))),
+        }));
+    }
+
+    #[test]
+    fn test_select_into() {
+        let sql = "SELECT id, name FROM users WHERE age > 18 INTO OUTFILE '/tmp/users.csv'
+                   FIELDS TERMINATED BY ',' OPTIONALLY ENCLOSED BY '\"' LINES TERMINATED BY '\\n'";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_select_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        assert_eq!(result.unwrap().into, Some(IntoClause::Outfile {
+            path: "/tmp/users.csv".to_string(),
+            charset: None,
+            fields: Some(FieldsOptions {
+                terminated_by: Some(",".to_string()),
+                enclosed_by: Some("\"".to_string()),
+                optionally_enclosed: true,
+                escaped_by: None,
+            }),
+            lines: Some(LinesOptions {
+                starting_by: None,
+                terminated_by: Some("\n".to_string()),
+            }),
+        }));
+
+        let sql = "SELECT id, name INTO @uid, @@name FROM users LIMIT 1";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_select_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        assert_eq!(
+            result.unwrap().into,
+            Some(IntoClause::Variables(vec!["@uid".to_string(), "@@name".to_string()]))
+        );
+
+        // INTO 只能出现一次
+        let sql = "SELECT id INTO @a FROM users LIMIT 1 INTO @b";
+        let mut parser = Parser::new_from_sql(sql);
+        assert!(parser.parse_select_statement().is_err());
+    }
+
+    #[test]
+    fn test_error_excerpt() {
+        let sql = "SELECT id,\n  name\nFROM users WHERE (age > 1";
+        let err = Parser::new_from_sql(sql).parse_select_statement().unwrap_err();
+        assert_eq!(err.message, "Expected ')' after expression, found end of input");
+        assert_eq!(err.line_column(), Some((3, 26)));
+        assert_eq!(
+            err.to_string(),
+            "error: Expected ')' after expression, found end of input\n \
+             --> line 3, column 26\n  \
+             |\n\
+             3 | FROM users WHERE (age > 1\n  \
+             |                          ^"
+        );
+
+        // 标记覆盖整个出错的token
+        let sql = "SELECT id FROM users WHERE name = 'it''s' AND `key` = )";
+        let err = Parser::new_from_sql(sql).parse_select_statement().unwrap_err();
+        assert_eq!(err.span.map(|span| &sql[span.start..span.end]), Some(")"));
+        assert!(err.to_string().ends_with(&format!("  | {}^", " ".repeat(sql.find(')').unwrap()))));
+        let err = Parser::new_from_sql("SELECT FROM users").parse_select_statement().unwrap_err();
+        assert_eq!(err.message, "Expected expression, found keyword FROM");
+        assert!(err.to_string().ends_with("  |        ^^^^"));
+
+        // 直接由token创建的解析器没有源码可以展示
+        let err = Parser::new(tokenize("SELECT FROM users")).parse_select_statement().unwrap_err();
+        assert_eq!(err.span, None);
+        assert_eq!(err.to_string(), "Parse error at position 1: Expected expression, found keyword FROM");
+    }
+
+    #[test]
+    fn test_localized_error() {
+        let sql = "SELECT id FROM users WHERE (age > 1";
+        let err = Parser::new_from_sql(sql).parse_select_statement().unwrap_err();
+        // 错误类别与语言无关
+        assert_eq!(*err.kind, ErrorKind::Expected { expected: "')' after expression".to_string(), found: None });
+        assert!(err.to_string().starts_with("error: Expected ')' after expression, found end of input\n"));
+        // {:#} 使用中文
+        assert!(format!("{:#}", err).starts_with("错误：期望表达式之后的 ')'，但遇到输入结尾\n --> 第 1 行，第 36 列"));
+
+        let options = ParserOptions { language: Language::Chinese, ..Default::default() };
+        let err = Parser::new_from_sql("SELECT id FROM users LIMIT abc")
+            .with_options(options)
+            .parse_select_statement()
+            .unwrap_err();
+        assert!(matches!(*err.kind, ErrorKind::Expected { found: Some(Token::Identifier(_)), .. }));
+        assert!(err.to_string().starts_with("错误：期望 LIMIT 之后的整数，但遇到标识符 abc"));
+        assert_eq!(err.localized(Language::English), err.message);
+    }
+
+    #[test]
+    fn test_order_by_ordinal() {
+        let sql = "SELECT dept, COUNT(id) FROM users GROUP BY 1 ORDER BY 2 DESC, 1 + 0, dept";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_select_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let select = result.unwrap();
+        assert_eq!(select.group_by, Some(vec![SortKey::Ordinal(1)]));
+        let order_by = select.order_by.as_ref().unwrap();
+        assert_eq!(order_by[0], OrderByExpr { expr: SortKey::Ordinal(2), asc: false });
+        // 序号参与运算时仍是普通表达式
+        assert!(matches!(order_by[1].expr, SortKey::Expr(Expr::BinaryOp { .. })));
+        assert_eq!(order_by[2].expr, SortKey::Expr(Expr::Identifier(ObjectName::new(["dept"]))));
+
+        // 按select列表解析序号
+        assert!(matches!(select.resolve_ordinal(1), Some(SelectColumn::Column { name, .. }) if name.name().value == "dept"));
+        assert!(matches!(select.resolve_ordinal(2), Some(SelectColumn::Expression { .. })));
+        assert_eq!(select.resolve_ordinal(0), None);
+        assert_eq!(select.resolve_ordinal(3), None);
+        let select = Parser::new_from_sql("SELECT *, id FROM users ORDER BY 2").parse_select_statement().unwrap();
+        assert_eq!(select.resolve_ordinal(2), None);
+
+        // DELETE 中的 ORDER BY 1 只是常量
+        let mut parser = Parser::new_from_sql("DELETE FROM users ORDER BY 1 LIMIT 1");
+        let delete = crate::parser::delete::DeleteStatementParser::parse_delete_statement(&mut parser).unwrap();
+        assert_eq!(delete.order_by.unwrap()[0].expr, SortKey::Expr(Expr::Literal(Value::Integer(1))));
+    }
+
+    #[test]
+    fn test_distinct_on() {
+        let sql = "SELECT DISTINCT ON (user_id, LOWER(email)) user_id, amount FROM orders ORDER BY user_id, created_at DESC";
+        let options = ParserOptions { dialect: Dialect::Postgres, ..Default::default() };
+        let mut parser = Parser::new_from_sql(sql).with_options(options);
+        let result = parser.parse_select_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let select = result.unwrap();
+        assert!(select.distinct);
+        let distinct_on = select.distinct_on.unwrap();
+        assert_eq!(distinct_on.len(), 2);
+        assert_eq!(distinct_on[0], Expr::Identifier(ObjectName::new(["user_id"])));
+        assert!(matches!(&distinct_on[1], Expr::FunctionCall { name, .. } if name == "LOWER"));
+        assert_eq!(select.columns.len(), 2);
+
+        // 普通的 DISTINCT 不受影响
+        let mut parser = Parser::new_from_sql("SELECT DISTINCT user_id FROM orders").with_options(options);
+        let select = parser.parse_select_statement().unwrap();
+        assert!(select.distinct);
+        assert_eq!(select.distinct_on, None);
+
+        // MySQL 方言不支持 DISTINCT ON
+        let mut parser = Parser::new_from_sql(sql);
+        assert!(parser.parse_select_statement().is_err());
+    }
+
+    #[test]
+    fn test_select_modifiers() {
+        let sql = "SELECT DISTINCT SQL_CALC_FOUND_ROWS sql_no_cache HIGH_PRIORITY id FROM t LIMIT 10";
+        let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+        assert!(select.distinct);
+        assert_eq!(
+            select.modifiers,
+            SelectModifiers { sql_calc_found_rows: true, sql_no_cache: true, high_priority: true, ..Default::default() }
+        );
+        assert_eq!(select.columns.len(), 1);
+
+        let select = Parser::new_from_sql("SELECT STRAIGHT_JOIN SQL_BIG_RESULT a FROM t").parse_select_statement().unwrap();
+        assert!(select.modifiers.straight_join && select.modifiers.sql_big_result);
+
+        let err = Parser::new_from_sql("SELECT SQL_NO_CACHE SQL_NO_CACHE id FROM t").parse_select_statement().unwrap_err();
+        assert_eq!(err.message, "Duplicate SQL_NO_CACHE modifier");
+    }
+
+    #[test]
+    fn test_joins_and_index_hints() {
+        let sql = "SELECT o.id FROM orders o FORCE INDEX (idx_created) IGNORE KEY FOR ORDER BY (PRIMARY, idx_user) \
+                   STRAIGHT_JOIN users u USE INDEX () ON u.id = o.user_id \
+                   LEFT OUTER JOIN items i ON i.order_id = o.id CROSS JOIN regions WHERE o.id > 10";
+        let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+        let from = select.from.as_ref().unwrap();
+        assert_eq!(from.alias.as_deref(), Some("o"));
+        assert_eq!(
+            from.index_hints,
+            vec![
+                IndexHint { kind: IndexHintKind::Force, scope: None, indexes: vec!["idx_created".to_string()] },
+                IndexHint {
+                    kind: IndexHintKind::Ignore,
+                    scope: Some(IndexHintScope::OrderBy),
+                    indexes: vec!["PRIMARY".to_string(), "idx_user".to_string()],
+                },
+            ]
+        );
+        let kinds: Vec<JoinKind> = select.joins.iter().map(|join| join.kind).collect();
+        assert_eq!(kinds, [JoinKind::Straight, JoinKind::Left, JoinKind::Cross]);
+        assert_eq!(select.joins[0].table.index_hints[0].indexes, Vec::<String>::new());
+        assert!(select.joins[2].on.is_none());
+        assert!(select.where_clause.is_some());
+
+        for sql in [
+            "SELECT * FROM a LEFT JOIN b",
+            "SELECT * FROM a FORCE INDEX ()",
+            "SELECT * FROM a USE INDEX FOR SORT (i)",
+            "SELECT * FROM a INNER b",
+        ] {
+            assert!(Parser::new_from_sql(sql).parse_select_statement().is_err(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_lateral_and_json_table() {
+        let sql = "SELECT u.id, x.total FROM users u JOIN LATERAL (SELECT SUM(total) AS total FROM orders o \
+                   WHERE o.user_id = u.id) x ON TRUE";
+        let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+        let table = &select.joins[0].table;
+        assert!(table.lateral && table.subquery.is_some());
+        assert_eq!(table.name, ObjectName::new(["x"]));
+        #[cfg(feature = "formatter")]
+        assert_eq!(
+            select.to_string(),
+            "SELECT u.id, x.total FROM users AS u JOIN LATERAL (SELECT SUM(total) AS total FROM orders AS o \
+             WHERE o.user_id = u.id) AS x ON TRUE"
+        );
+
+        let sql = "SELECT jt.* FROM orders o CROSS JOIN JSON_TABLE(o.doc, '$.items[*]' COLUMNS (\
+                   rn FOR ORDINALITY, sku VARCHAR(20) PATH '$.sku' DEFAULT '\"none\"' ON EMPTY ERROR ON ERROR, \
+                   has_qty INT EXISTS PATH '$.qty', NESTED PATH '$.tags[*]' COLUMNS (tag TEXT PATH '$'))) AS jt";
+        let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+        let table = &select.joins[0].table;
+        assert_eq!(table.name, ObjectName::new(["jt"]));
+        let Some(TableFunction::JsonTable { path, columns, .. }) = table.function.as_deref() else {
+            panic!("Expected JSON_TABLE");
+        };
+        assert_eq!(path, "$.items[*]");
+        assert_eq!(columns[0], JsonTableColumn::Ordinality("rn".to_string()));
+        assert!(matches!(
+            &columns[1],
+            JsonTableColumn::Path {
+                on_empty: Some(JsonTableFallback::Default(value)),
+                on_error: Some(JsonTableFallback::Error),
+                exists: false,
+                ..
+            } if value == "\"none\""
+        ));
+        assert!(matches!(&columns[2], JsonTableColumn::Path { exists: true, .. }));
+        assert!(matches!(&columns[3], JsonTableColumn::Nested { columns, .. } if columns.len() == 1));
+        #[cfg(feature = "formatter")]
+        {
+            assert_eq!(
+                table.to_string(),
+                "JSON_TABLE(o.doc, '$.items[*]' COLUMNS (rn FOR ORDINALITY, sku VARCHAR(20) PATH '$.sku' \
+                 DEFAULT '\"none\"' ON EMPTY ERROR ON ERROR, has_qty INT EXISTS PATH '$.qty', \
+                 NESTED PATH '$.tags[*]' COLUMNS (tag TEXT PATH '$'))) AS jt"
+            );
+            // 重新输出的 SQL 解析后不变
+            let reparsed = Parser::new_from_sql(&select.to_string()).parse_select_statement().unwrap();
+            assert_eq!(reparsed, select);
+        }
+
+        for sql in [
+            "SELECT * FROM LATERAL users",
+            "SELECT * FROM JSON_TABLE(doc, '$' COLUMNS (a INT PATH '$'))",
+            "SELECT * FROM JSON_TABLE(doc, '$' COLUMNS (a INT)) t",
+            "SELECT * FROM JSON_TABLE(doc, '$' COLUMNS (a INT PATH '$' ERROR ON ERROR NULL ON EMPTY)) t",
+        ] {
+            assert!(Parser::new_from_sql(sql).parse_statements().is_err(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_comma_separated_tables() {
+        let sql = "SELECT o.id, jt.sku FROM orders o, JSON_TABLE(o.doc, '$.items[*]' COLUMNS (sku VARCHAR(20) PATH '$.sku')) jt, \
+                   users AS u JOIN carts c ON c.user_id = u.id WHERE o.user_id = u.id";
+        let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+        assert_eq!(select.from.as_ref().unwrap().name, ObjectName::new(["orders"]));
+        let kinds: Vec<JoinKind> = select.joins.iter().map(|join| join.kind).collect();
+        assert_eq!(kinds, [JoinKind::Comma, JoinKind::Comma, JoinKind::Inner]);
+        assert_eq!(select.joins[0].table.name, ObjectName::new(["jt"]));
+        assert!(matches!(select.joins[0].table.function.as_deref(), Some(TableFunction::JsonTable { .. })));
+        assert_eq!(select.joins[1].table.alias.as_deref(), Some("u"));
+        assert!(select.joins[1].on.is_none());
+        #[cfg(feature = "formatter")]
+        {
+            let sql = "SELECT * FROM a, b AS x, (SELECT 1) AS d WHERE a.id = x.id";
+            let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+            assert_eq!(select.to_string(), sql);
+        }
+
+        for sql in ["SELECT * FROM a,", "SELECT * FROM a, b ON a.id = b.id", "SELECT * FROM a, NATURAL JOIN b"] {
+            assert!(Parser::new_from_sql(sql).parse_statements().is_err(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_limit_offset_comma() {
+        // MySQL 的 LIMIT offset, row_count 与 LIMIT row_count OFFSET offset 等价
+        let select = Parser::new_from_sql("SELECT * FROM t LIMIT 5, 10").parse_select_statement().unwrap();
+        assert_eq!(select.limit, Some(LimitClause { limit: 10, offset: Some(5) }));
+        let other = Parser::new_from_sql("SELECT * FROM t LIMIT 10 OFFSET 5").parse_select_statement().unwrap();
+        assert_eq!(select, other);
+
+        for sql in ["SELECT * FROM t LIMIT 5,", "SELECT * FROM t LIMIT 5, 'a'", "SELECT * FROM t LIMIT 5, 10 OFFSET 1"] {
+            assert!(Parser::new_from_sql(sql).parse_statements().is_err(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_postgres_table_functions() {
+        let sql = "SELECT t.elem, t.n, g FROM orders o CROSS JOIN UNNEST(o.tags) WITH ORDINALITY AS t(elem, n) \
+                   JOIN generate_series(1, 3) g ON g <= t.n";
+        assert!(Parser::new_from_sql(sql).parse_statements().is_err(), "MySQL 方言不支持表函数");
+        let options = ParserOptions { dialect: Dialect::Postgres, ..Default::default() };
+        let select = Parser::new_from_sql(sql).with_options(options).parse_select_statement().unwrap();
+        let table = &select.joins[0].table;
+        assert_eq!(table.name, ObjectName::new(["t"]));
+        let Some(TableFunction::Call { name, args, with_ordinality, column_aliases }) = table.function.as_deref() else {
+            panic!("Expected table function");
+        };
+        assert_eq!(name, "UNNEST");
+        assert_eq!(args.len(), 1);
+        assert!(*with_ordinality);
+        assert_eq!(column_aliases, &["elem".to_string(), "n".to_string()]);
+        let table = &select.joins[1].table;
+        assert_eq!(table.name, ObjectName::new(["g"]));
+        assert!(matches!(table.function.as_deref(), Some(TableFunction::Call { with_ordinality: false, .. })));
+        #[cfg(feature = "formatter")]
+        assert_eq!(
+            select.to_string(),
+            "SELECT t.elem, t.n, g FROM orders AS o CROSS JOIN UNNEST(o.tags) WITH ORDINALITY AS t(elem, n) \
+             JOIN generate_series(1, 3) AS g ON g <= t.n"
+        );
+
+        // 没有别名时以函数名作为表名
+        let sql = "SELECT generate_series FROM generate_series(1, 10)";
+        let select = Parser::new_from_sql(sql).with_options(options).parse_select_statement().unwrap();
+        let table = select.from.unwrap();
+        assert_eq!(table.name, ObjectName::new(["generate_series"]));
+        assert!(table.function.is_some());
+    }
+
+    #[test]
+    fn test_named_windows() {
+        let sql = "SELECT user_id, ROW_NUMBER() OVER w AS rn, \
+                   SUM(total) OVER (w ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) AS running, \
+                   AVG(total) OVER (PARTITION BY region ORDER BY created_at DESC RANGE INTERVAL 7 DAY PRECEDING) \
+                   FROM orders WINDOW base AS (PARTITION BY user_id), w AS (base ORDER BY created_at) ORDER BY user_id";
+        let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+        assert_eq!(select.windows.len(), 2);
+        assert_eq!(select.windows[1].0, "w");
+        assert_eq!(select.windows[1].1.name.as_deref(), Some("base"));
+        let SelectColumn::Expression { expr: Expr::Window { func, over }, .. } = &select.columns[2] else {
+            panic!("Expected window function");
+        };
+        assert!(matches!(**func, Expr::Aggregate { .. }));
+        let resolved = select.resolve_window(over).unwrap();
+        assert_eq!(resolved.name, None);
+        assert_eq!(resolved.partition_by, vec![Expr::Identifier(ObjectName::new(["user_id"]))]);
+        assert_eq!(resolved.order_by.len(), 1);
+        assert_eq!(resolved.frame, over.frame);
+        #[cfg(feature = "formatter")]
+        assert_eq!(
+            select.to_string(),
+            "SELECT user_id, ROW_NUMBER() OVER w AS rn, \
+             SUM(total) OVER (w ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) AS running, \
+             AVG(total) OVER (PARTITION BY region ORDER BY created_at DESC RANGE INTERVAL 7 DAY PRECEDING) \
+             FROM orders WINDOW base AS (PARTITION BY user_id), w AS (base ORDER BY created_at) ORDER BY user_id"
+        );
+
+        for sql in [
+            "SELECT ROW_NUMBER() OVER w FROM t",
+            "SELECT 1 FROM t WINDOW w AS (), W AS ()",
+            "SELECT 1 FROM t WINDOW a AS (b), b AS ()",
+            "SELECT SUM(x) OVER (w PARTITION BY y) FROM t WINDOW w AS ()",
+            "SELECT SUM(x) OVER (w ORDER BY y) FROM t WINDOW w AS (ORDER BY z)",
+            "SELECT SUM(x) OVER w2 FROM t WINDOW w AS (ROWS 2 PRECEDING), w2 AS (w)",
+            "SELECT 1 FROM t ORDER BY 1 WINDOW w AS ()",
+            "SELECT SUM(x) OVER (ROWS BETWEEN 1 PRECEDING) FROM t",
+        ] {
+            assert!(Parser::new_from_sql(sql).parse_statements().is_err(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_qualify() {
+        let sql = "SELECT user_id, amount FROM orders WHERE amount > 0 \
+                   QUALIFY ROW_NUMBER() OVER (PARTITION BY user_id ORDER BY amount DESC) = 1 ORDER BY user_id";
+        for dialect in [Dialect::BigQuery, Dialect::Snowflake] {
+            let options = ParserOptions { dialect, ..Default::default() };
+            let select = Parser::new_from_sql(sql).with_options(options).parse_select_statement().unwrap();
+            let Some(Expr::BinaryOp { left, .. }) = &select.qualify else {
+                panic!("Expected QUALIFY condition");
+            };
+            assert!(matches!(**left, Expr::Window { .. }));
+            assert!(select.order_by.is_some());
+            #[cfg(feature = "formatter")]
+            assert_eq!(select.to_string(), sql);
+        }
+
+        // QUALIFY 在 HAVING 之后、WINDOW 之前，引用的窗口同样检查
+        let options = ParserOptions { dialect: Dialect::BigQuery, ..Default::default() };
+        let sql = "SELECT a FROM t GROUP BY a HAVING COUNT(*) > 1 QUALIFY RANK() OVER w = 1 WINDOW w AS (ORDER BY a)";
+        assert!(Parser::new_from_sql(sql).with_options(options).parse_statements().is_ok());
+        for sql in [
+            "SELECT a FROM t QUALIFY RANK() OVER w = 1",
+            "SELECT a FROM t ORDER BY a QUALIFY RANK() OVER (ORDER BY a) = 1",
+        ] {
+            assert!(Parser::new_from_sql(sql).with_options(options).parse_statements().is_err(), "{}", sql);
+        }
+        // 其它方言中 QUALIFY 不是子句
+        let sql = "SELECT a FROM t QUALIFY RANK() OVER (ORDER BY a) = 1";
+        assert!(Parser::new_from_sql(sql).parse_statements().is_err());
+    }
+
+    #[test]
+    fn test_join_using_and_natural() {
+        let sql = "SELECT * FROM orders o JOIN users u USING (user_id, `region`) \
+                   NATURAL LEFT OUTER JOIN items NATURAL JOIN tags LEFT JOIN notes n USING (id)";
+        let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+        let joins = &select.joins;
+        assert_eq!(joins[0].using, Some(vec!["user_id".to_string(), "region".to_string()]));
+        assert!(joins[0].on.is_none() && !joins[0].natural);
+        assert!(joins[1].natural && joins[1].kind == JoinKind::Left && joins[1].using.is_none());
+        assert!(joins[2].natural && joins[2].kind == JoinKind::Inner);
+        #[cfg(feature = "formatter")]
+        assert_eq!(
+            select.to_string(),
+            "SELECT * FROM orders AS o JOIN users AS u USING (user_id, region) NATURAL LEFT JOIN items \
+             NATURAL JOIN tags LEFT JOIN notes AS n USING (id)"
+        );
+
+        // USING 隐含的等值条件
+        let condition = joins[0].using_condition(&ObjectName::new(["o"])).unwrap();
+        assert!(matches!(&condition, Expr::LogicalOp { expressions, .. } if expressions.len() == 2));
+        #[cfg(feature = "formatter")]
+        assert_eq!(condition.to_string(), "o.user_id = u.user_id AND o.region = u.region");
+        assert_eq!(joins[2].using_condition(&ObjectName::new(["o"])), None);
+
+        for sql in [
+            "SELECT * FROM a NATURAL JOIN b ON a.id = b.id",
+            "SELECT * FROM a NATURAL JOIN b USING (id)",
+            "SELECT * FROM a NATURAL CROSS JOIN b",
+            "SELECT * FROM a NATURAL b",
+            "SELECT * FROM a STRAIGHT_JOIN b USING (id)",
+            "SELECT * FROM a JOIN b USING id",
+        ] {
+            assert!(Parser::new_from_sql(sql).parse_statements().is_err(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_qualified_wildcard() {
+        let sql = "SELECT u.*, o.id, `order`.*, shop.items.* FROM users u";
+        let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+        assert_eq!(
+            select.columns,
+            vec![
+                SelectColumn::QualifiedWildcard(ObjectName::new(["u"])),
+                SelectColumn::Column { name: ObjectName::new(["o", "id"]), alias: None },
+                SelectColumn::QualifiedWildcard(ObjectName(vec![Ident::with_quote('`', "order")])),
+                SelectColumn::QualifiedWildcard(ObjectName::new(["shop", "items"])),
+            ]
+        );
+        // t.* 之后不能有别名
+        assert!(Parser::new_from_sql("SELECT u.* AS x FROM users u").parse_statements().is_err());
+    }
+
+    #[test]
+    fn test_derived_table() {
+        let sql = "SELECT t.n FROM (SELECT COUNT(*) AS n FROM orders GROUP BY user_id) t \
+                   JOIN (SELECT id FROM users) AS u ON u.id = t.n";
+        let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+        let from = select.from.as_ref().unwrap();
+        assert_eq!(from.name, ObjectName::new(["t"]));
+        assert_eq!(from.alias, None);
+        assert!(from.subquery.as_ref().unwrap().group_by.is_some());
+        assert_eq!(select.joins[0].table.name, ObjectName::new(["u"]));
+        #[cfg(feature = "formatter")]
+        assert_eq!(from.subquery.as_ref().unwrap().to_string(), "SELECT COUNT(*) AS n FROM orders GROUP BY user_id");
+        #[cfg(feature = "formatter")]
+        assert_eq!(
+            select.to_string(),
+            "SELECT t.n FROM (SELECT COUNT(*) AS n FROM orders GROUP BY user_id) AS t \
+             JOIN (SELECT id FROM users) AS u ON u.id = t.n"
+        );
+        // 派生表必须有别名
+        assert!(Parser::new_from_sql("SELECT * FROM (SELECT 1)").parse_select_statement().is_err());
+        assert!(Parser::new_from_sql("SELECT * FROM (SELECT 1) WHERE 1 = 1").parse_select_statement().is_err());
+    }
+
+    #[test]
+    fn test_top_and_table_sample() {
+        let options = ParserOptions { dialect: Dialect::MsSql, ..Default::default() };
+        let sql = "SELECT TOP 10 * FROM orders ORDER BY created_at DESC";
+        let select = Parser::new_from_sql(sql).with_options(options).parse_select_statement().unwrap();
+        assert_eq!(select.columns, vec![SelectColumn::Wildcard]);
+        assert_eq!(select.row_limit(), Some(10));
+
+        let sql = "SELECT DISTINCT TOP (5 * 2) PERCENT WITH TIES id FROM orders";
+        let select = Parser::new_from_sql(sql).with_options(options).parse_select_statement().unwrap();
+        let top = select.top.unwrap();
+        assert!(matches!(top.quantity.value, Expr::BinaryOp { .. }));
+        assert_eq!(top.quantity.unit, QuantityUnit::Percent);
+        assert!(top.with_ties);
+        assert_eq!(select.columns.len(), 1);
+
+        // MySQL 中 top 是普通的列名
+        let select = Parser::new_from_sql("SELECT top FROM t LIMIT 3").parse_select_statement().unwrap();
+        assert!(select.top.is_none());
+        assert_eq!(select.row_limit(), Some(3));
+
+        let options = ParserOptions { dialect: Dialect::Postgres, ..Default::default() };
+        let sql = "SELECT * FROM events e TABLESAMPLE BERNOULLI (2.5) REPEATABLE (42) WHERE e.kind = 'click'";
+        let select = Parser::new_from_sql(sql).with_options(options).parse_select_statement().unwrap();
+        let from = select.from.unwrap();
+        assert_eq!(from.alias.as_deref(), Some("e"));
+        let sample = from.sample.unwrap();
+        assert_eq!(sample.method, SampleMethod::Bernoulli);
+        assert_eq!(sample.size, Quantity { value: Expr::Literal(Value::Decimal("2.5".to_string())), unit: QuantityUnit::Percent });
+        assert_eq!(sample.seed, Some(Expr::Literal(Value::Integer(42))));
+        assert!(select.where_clause.is_some());
+
+        let sql = "SELECT * FROM events TABLESAMPLE RANDOM (1)";
+        assert!(Parser::new_from_sql(sql).with_options(options).parse_select_statement().is_err());
+    }
+
+    #[test]
+    fn test_hash_comment() {
+        let sql = "# 查询用户\nSELECT id, # 主键\n name FROM users # 结尾";
+        let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+        assert_eq!(select.columns.len(), 2);
+        assert!(select.from.is_some());
+
+        // PostgreSQL 中 # 不是注释
+        let options = ParserOptions { dialect: Dialect::Postgres, ..Default::default() };
+        let mut parser = Parser::new_from_sql(sql).with_options(options);
+        assert!(parser.parse_select_statement().is_err());
+    }
+
+    #[test]
+    fn test_lock_clause() {
+        let cases = [
+            ("SELECT id FROM t WHERE id = 1 FOR UPDATE", LockMode::Update, LockWait::Wait),
+            ("SELECT id FROM t LIMIT 1 FOR SHARE SKIP LOCKED", LockMode::Share, LockWait::SkipLocked),
+            ("SELECT id FROM t FOR UPDATE NOWAIT INTO @id", LockMode::Update, LockWait::Nowait),
+            ("SELECT id FROM t LOCK IN SHARE MODE", LockMode::Share, LockWait::Wait),
+        ];
+        for (sql, mode, wait) in cases {
+            let mut parser = Parser::new_from_sql(sql);
+            let result = parser.parse_select_statement();
+            assert!(result.is_ok(), "解析失败: {:?}", result.err());
+            assert_eq!(result.unwrap().lock, Some(LockClause { mode, wait }), "{}", sql);
+        }
+
+        let mut parser = Parser::new_from_sql("SELECT id FROM t FOR DELETE");
+        let err = parser.parse_select_statement().unwrap_err();
+        assert_eq!(err.message, "Expected UPDATE or SHARE after FOR, found keyword DELETE");
+    }
+
+    #[test]
+    fn test_with_clause() {
+        let sql = "WITH RECURSIVE r (n) AS (SELECT 1), s AS (SELECT n FROM r) SELECT n FROM s";
+        let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+        let with = select.with.as_ref().unwrap();
+        assert!(with.recursive);
+        assert_eq!(with.ctes.len(), 2);
+        assert_eq!((with.ctes[0].name.as_str(), &with.ctes[0].columns[..]), ("r", &["n".to_string()][..]));
+        assert_eq!(with.ctes[1].query.from.as_ref().unwrap().name, ObjectName::new(["r"]));
+        #[cfg(feature = "formatter")]
+        assert_eq!(select.to_string(), "WITH RECURSIVE r (n) AS (SELECT 1), s AS (SELECT n FROM r) SELECT n FROM s");
+
+        // 子查询中同样可以使用 WITH
+        let sql = "SELECT * FROM (WITH t AS (SELECT 1 AS x) SELECT x FROM t) AS d WHERE x IN (WITH u AS (SELECT 1) SELECT * FROM u)";
+        assert!(Parser::new_from_sql(sql).parse_select_statement().is_ok());
+
+        for sql in ["WITH t AS SELECT 1 SELECT 1", "WITH t (SELECT 1) SELECT 1", "WITH t AS (SELECT 1 SELECT 1", "WITH SELECT 1"] {
+            assert!(Parser::new_from_sql(sql).parse_select_statement().is_err(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let sql = "SELECT a FROM t UNION ALL SELECT b FROM u EXCEPT (SELECT c FROM v ORDER BY c LIMIT 1) ORDER BY 1 LIMIT 10";
+        let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+        let ops: Vec<_> = select.set_operations.iter().map(|operation| (operation.op, operation.all)).collect();
+        assert_eq!(ops, [(SetOperator::Union, true), (SetOperator::Except, false)]);
+        // 末尾的 ORDER BY、LIMIT 作用于整个查询，括号中的属于操作数
+        assert_eq!(select.order_by, Some(vec![OrderByExpr { expr: SortKey::Ordinal(1), asc: true }]));
+        assert!(select.limit.is_some());
+        assert!(select.set_operations[0].select.order_by.is_none());
+        assert!(select.set_operations[1].select.limit.is_some());
+        #[cfg(feature = "formatter")]
+        assert_eq!(
+            select.to_string(),
+            "SELECT a FROM t UNION ALL SELECT b FROM u EXCEPT (SELECT c FROM v ORDER BY c LIMIT 1) ORDER BY 1 LIMIT 10"
+        );
+
+        let select = Parser::new_from_sql("(SELECT a FROM t) UNION DISTINCT (SELECT a FROM u) INTERSECT SELECT 1")
+            .parse_select_statement()
+            .unwrap();
+        assert_eq!(select.set_operations.len(), 2);
+        assert!(!select.set_operations[0].all);
+
+        for sql in [
+            "SELECT 1 UNION",
+            "SELECT 1 UNION ALL",
+            "(SELECT 1 LIMIT 1) UNION SELECT 2",
+            "(SELECT 1 UNION SELECT 2) INTERSECT SELECT 3",
+            "(SELECT 1 ORDER BY 1) ORDER BY 1",
+        ] {
+            assert!(Parser::new_from_sql(sql).parse_select_statement().is_err(), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_implicit_alias_and_rollup() {
+        let sql = "SELECT sum(x) total, y `the y`, count(*) returns FROM t GROUP BY y WITH ROLLUP";
+        let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+        let aliases: Vec<_> = select
+            .columns
+            .iter()
+            .map(|column| match column {
+                SelectColumn::Column { alias, .. } | SelectColumn::Expression { alias, .. } => alias.as_deref(),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(aliases, [Some("total"), Some("the y"), Some("returns")]);
+        assert!(select.with_rollup);
+        #[cfg(feature = "formatter")]
+        assert!(select.to_string().ends_with("GROUP BY y WITH ROLLUP"));
+
+        // 保留字不能作为省略 AS 的别名
+        assert!(Parser::new_from_sql("SELECT a from FROM t").parse_select_statement().is_err());
+        // 没有 GROUP BY 时 WITH ROLLUP 不合法
+        assert!(Parser::new_from_sql("SELECT a FROM t WITH ROLLUP").parse_statements().is_err());
+    }
+
 }
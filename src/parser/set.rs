@@ -0,0 +1,90 @@
+use super::{ParseError, Parser};
+use crate::ast::set::{SetAssignment, SetStatement};
+use crate::ast::show::VariableScope;
+use crate::kerwords::Keyword;
+
+/// set语句解析器接口
+pub trait SetStatementParser {
+    type Error;
+    // 解析set语句
+    fn parse_set_statement(&mut self) -> Result<SetStatement, Self::Error>;
+}
+
+impl SetStatementParser for Parser {
+    type Error = ParseError;
+    // 解析 SET [GLOBAL | SESSION | LOCAL] var {= | :=} expr [, ...]
+    fn parse_set_statement(&mut self) -> Result<SetStatement, Self::Error> {
+        if !self.match_keyword(Keyword::SET) {
+            return Err(self.expected("SET"));
+        }
+
+        let mut assignments = Vec::new();
+        loop {
+            let start = self.current;
+            let scope = if self.match_keyword(Keyword::GLOBAL) {
+                Some(VariableScope::Global)
+            } else if self.match_keyword(Keyword::SESSION) || self.match_keyword(Keyword::LOCAL) {
+                Some(VariableScope::Session)
+            } else {
+                None
+            };
+            let variable = self.parse_variable_name()?;
+            if !self.match_operator("=") && !self.match_operator(":=") {
+                // SET NAMES、SET TRANSACTION 等不是变量赋值的 SET 语句
+                if assignments.is_empty() && scope.is_none() && !variable.starts_with('@') {
+                    self.current = start;
+                    return Err(self.unsupported("SET statement"));
+                }
+                return Err(self.expected("= or := after variable name"));
+            }
+            let value = self.parse_assigned_value()?;
+            assignments.push(SetAssignment { scope, variable, value });
+            if !self.match_punctuator(',') {
+                break;
+            }
+        }
+
+        Ok(SetStatement { assignments })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ast::common::ObjectName;
+    use crate::ast::expr::{BinaryOperator, Expr, Value};
+    use crate::error::ErrorKind;
+
+    #[test]
+    fn test_set_statement() {
+        let sql = "SET @a := 1, @b = @a + 1, GLOBAL max_connections = 100, @@session.sql_mode = DEFAULT";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_set_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        assert!(!parser.has_more());
+        assert_eq!(result.unwrap().assignments, vec![
+            SetAssignment { scope: None, variable: "@a".to_string(), value: Expr::Literal(Value::Integer(1)) },
+            SetAssignment {
+                scope: None,
+                variable: "@b".to_string(),
+                value: Expr::BinaryOp {
+                    left: Box::new(Expr::Identifier(ObjectName::new(["@a"]))),
+                    op: BinaryOperator::Plus,
+                    right: Box::new(Expr::Literal(Value::Integer(1))),
+                },
+            },
+            SetAssignment {
+                scope: Some(VariableScope::Global),
+                variable: "max_connections".to_string(),
+                value: Expr::Literal(Value::Integer(100)),
+            },
+            SetAssignment { scope: None, variable: "@@session.sql_mode".to_string(), value: Expr::Literal(Value::DEFAULT) },
+        ]);
+
+        for sql in ["SET", "SET @a", "SET @a 1", "SET @a = 1,", "SET GLOBAL = 1"] {
+            assert!(Parser::new_from_sql(sql).parse_set_statement().is_err(), "{}", sql);
+        }
+        let err = Parser::new_from_sql("SET NAMES utf8mb4").parse_set_statement().unwrap_err();
+        assert!(matches!(*err.kind, ErrorKind::Unsupported { .. }), "{:?}", err);
+    }
+}
@@ -0,0 +1,142 @@
+use super::{ParseError, Parser};
+use crate::ast::show::{ShowFilter, ShowStatement, ShowTarget, VariableScope};
+use crate::token::Token;
+use crate::kerwords::Keyword;
+
+/// show语句解析器接口
+pub trait ShowStatementParser {
+    type Error;
+    // 解析show语句
+    fn parse_show_statement(&mut self) -> Result<ShowStatement, Self::Error>;
+}
+
+impl Parser {
+    // 解析可选的 LIKE 'pattern' 或 WHERE expr
+    fn parse_show_filter(&mut self) -> Result<Option<ShowFilter>, ParseError> {
+        if self.match_keyword(Keyword::LIKE) {
+            return match self.peek() {
+                Some(Token::StringLiteral(s)) => {
+                    let pattern = s.to_owned();
+                    self.consume_token();
+                    Ok(Some(ShowFilter::Like(pattern)))
+                }
+                _ => Err(self.expected("pattern after LIKE")),
+            };
+        }
+        if self.match_keyword(Keyword::WHERE) {
+            return Ok(Some(ShowFilter::Where(self.parse_expr(0)?)));
+        }
+        Ok(None)
+    }
+
+    // 解析可选的 {FROM | IN} db
+    fn parse_show_database(&mut self) -> Result<Option<String>, ParseError> {
+        if self.match_keyword(Keyword::FROM) || self.match_keyword(Keyword::IN) {
+            Ok(Some(self.parse_identifier("database")?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl ShowStatementParser for Parser {
+    type Error = ParseError;
+    // 解析SHOW语句
+    fn parse_show_statement(&mut self) -> Result<ShowStatement, Self::Error> {
+        // 期望以SHOW关键字开始
+        if !self.match_keyword(Keyword::SHOW) {
+            return Err(self.expected("SHOW"));
+        }
+
+        let target = if self.match_keyword(Keyword::CREATE) {
+            if !self.match_keyword(Keyword::TABLE) {
+                return Err(self.expected("TABLE after SHOW CREATE"));
+            }
+            ShowTarget::CreateTable(self.parse_object_name("table")?)
+        } else if self.match_keyword(Keyword::INDEX) || self.match_keyword(Keyword::INDEXES) || self.match_keyword(Keyword::KEYS) {
+            if !self.match_keyword(Keyword::FROM) && !self.match_keyword(Keyword::IN) {
+                return Err(self.expected("FROM or IN after SHOW INDEX"));
+            }
+            let table = self.parse_object_name("table")?;
+            let database = self.parse_show_database()?;
+            // SHOW INDEX 只支持 WHERE 过滤
+            let filter = if self.match_keyword(Keyword::WHERE) {
+                Some(ShowFilter::Where(self.parse_expr(0)?))
+            } else {
+                None
+            };
+            ShowTarget::Index { table, database, filter }
+        } else if self.match_keyword(Keyword::DATABASES) {
+            ShowTarget::Databases { filter: self.parse_show_filter()? }
+        } else {
+            let scope = if self.match_keyword(Keyword::GLOBAL) {
+                Some(VariableScope::Global)
+            } else if self.match_keyword(Keyword::SESSION) || self.match_keyword(Keyword::LOCAL) {
+                Some(VariableScope::Session)
+            } else {
+                None
+            };
+            let full = scope.is_none() && self.match_keyword(Keyword::FULL);
+
+            if !full && self.match_keyword(Keyword::VARIABLES) {
+                ShowTarget::Variables { scope, filter: self.parse_show_filter()? }
+            } else if !full && self.match_keyword(Keyword::STATUS) {
+                ShowTarget::Status { scope, filter: self.parse_show_filter()? }
+            } else if scope.is_none() && self.match_keyword(Keyword::PROCESSLIST) {
+                ShowTarget::ProcessList { full }
+            } else if scope.is_none() && self.match_keyword(Keyword::TABLES) {
+                let database = self.parse_show_database()?;
+                ShowTarget::Tables { full, database, filter: self.parse_show_filter()? }
+            } else {
+                return Err(self.unsupported("SHOW statement"));
+            }
+        };
+
+        Ok(ShowStatement { target })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ast::common::ObjectName;
+    use crate::ast::expr::{BinaryOperator, Expr, Value};
+
+    fn parse_target(sql: &str) -> ShowTarget {
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_show_statement();
+        assert!(result.is_ok(), "解析失败: {} {:?}", sql, result.err());
+        result.unwrap().target
+    }
+
+    #[test]
+    fn test_show_statements() {
+        assert_eq!(parse_target("SHOW CREATE TABLE mydb.users"), ShowTarget::CreateTable(ObjectName::new(["mydb", "users"])));
+        assert_eq!(parse_target("SHOW GLOBAL VARIABLES LIKE 'max_%'"), ShowTarget::Variables {
+            scope: Some(VariableScope::Global),
+            filter: Some(ShowFilter::Like("max_%".to_string())),
+        });
+        assert_eq!(parse_target("SHOW STATUS"), ShowTarget::Status { scope: None, filter: None });
+        assert_eq!(parse_target("SHOW FULL PROCESSLIST"), ShowTarget::ProcessList { full: true });
+        assert_eq!(parse_target("SHOW INDEX FROM users FROM mydb WHERE Non_unique = 0"), ShowTarget::Index {
+            table: ObjectName::new(["users"]),
+            database: Some("mydb".to_string()),
+            filter: Some(ShowFilter::Where(Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(ObjectName::new(["Non_unique"]))),
+                op: BinaryOperator::Eq,
+                right: Box::new(Expr::Literal(Value::Integer(0))),
+            })),
+        });
+        assert_eq!(parse_target("SHOW FULL TABLES IN mydb"), ShowTarget::Tables {
+            full: true,
+            database: Some("mydb".to_string()),
+            filter: None,
+        });
+
+        // FULL 不能用于 VARIABLES，作用域不能用于 PROCESSLIST
+        let mut parser = Parser::new_from_sql("SHOW FULL VARIABLES");
+        assert!(parser.parse_show_statement().is_err());
+        let mut parser = Parser::new_from_sql("SHOW GLOBAL PROCESSLIST");
+        assert!(parser.parse_show_statement().is_err());
+    }
+}
@@ -0,0 +1,213 @@
+use super::{ParseError, Parser};
+use crate::kerwords::Keyword;
+
+use crate::ast::{
+    common::TableReference,
+    update::{UpdateModifiers, UpdateStatement},
+};
+
+/// update语句解析器接口
+pub trait UpdateStatementParser {
+    type Error;
+    // 解析update语句
+    fn parse_update_statement(&mut self) -> Result<UpdateStatement, Self::Error>;
+}
+
+
+// 子句优先级/索引
+const SET_IDX: u8 = 0;
+const WHERE_IDX: u8 = 1;
+const ORDER_BY_IDX: u8 = 2;
+const LIMIT_IDX: u8 = 3;
+
+
+impl UpdateStatementParser for Parser {
+    type Error = ParseError;
+    // 解析UPDATE语句
+    fn parse_update_statement(&mut self) -> Result<UpdateStatement, Self::Error> {
+        // 期望以UPDATE关键字开始
+        if !self.match_keyword(Keyword::UPDATE) {
+            return Err(self.expected("UPDATE"));
+        }
+
+        // 修饰符必须按 LOW_PRIORITY、IGNORE 的顺序出现
+        let modifiers = UpdateModifiers {
+            low_priority: self.match_keyword(Keyword::LOW_PRIORITY),
+            ignore: self.match_keyword(Keyword::IGNORE),
+        };
+
+        // 解析要更新的表
+        let table: TableReference = self.parse_table_reference(true)?;
+
+        // 必须有SET子句
+        if !self.match_keyword(Keyword::SET) {
+            return Err(self.expected("SET"));
+        }
+        // 列名可以带表名或别名限定，例如 o.status
+        let assignments = self.parse_assignments()?;
+
+        // 跟踪当前已处理的最高子句索引
+        let mut current_idx: u8 = SET_IDX;
+
+        // 可选的WHERE子句
+        let where_clause = if self.match_keyword(Keyword::WHERE) {
+            current_idx = self.move_current_idx(current_idx, WHERE_IDX,get_clause_name)?;
+            Some(self.parse_expr(0)?)
+        } else {
+            None
+        };
+
+        // 可选的ORDER BY子句
+        let order_by = if self.match_keyword(Keyword::ORDER) {
+            if !self.match_keyword(Keyword::BY) {
+                return Err(self.expected("BY after ORDER"));
+            }
+            current_idx = self.move_current_idx(current_idx, ORDER_BY_IDX,get_clause_name)?;
+            Some(self.parse_order_by(false)?)
+        } else {
+            None
+        };
+
+        // 可选的LIMIT子句
+        let limit = if self.match_keyword(Keyword::LIMIT) {
+            // Since this is the last clause, we don't need to store the updated index
+            self.move_current_idx(current_idx, LIMIT_IDX,get_clause_name)?;
+            Some(self.parse_limit()?)
+        } else {
+            None
+        };
+
+        // 完成UPDATE语句解析
+        Ok(UpdateStatement {
+            modifiers,
+            table,
+            assignments,
+            where_clause,
+            order_by,
+            limit,
+            is_return_count: true, // 默认行为
+        })
+    }
+}
+
+// 可选的辅助函数，将索引转换为子句名称
+fn get_clause_name(idx: u8) -> &'static str {
+    match idx {
+        WHERE_IDX => "WHERE",
+        ORDER_BY_IDX => "ORDER BY",
+        LIMIT_IDX => "LIMIT",
+        _ => "SET",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ast::common::{Ident, ObjectName};
+    use crate::ast::expr::{BinaryOperator, Expr, Value};
+
+    #[test]
+    fn test_update_modifiers() {
+        let sql = "UPDATE LOW_PRIORITY IGNORE users SET status = 'inactive', score = score - 1 WHERE id = 1";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_update_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let update = result.unwrap();
+
+        assert_eq!(update.modifiers, UpdateModifiers {
+            low_priority: true,
+            ignore: true,
+        });
+        assert_eq!(update.table.name.to_string(), "users");
+        assert_eq!(update.assignments.len(), 2);
+        assert_eq!(update.assignments[0], (ObjectName::new(["status"]), Expr::Literal(Value::String("inactive".to_string()))));
+        assert_eq!(update.where_clause, Some(Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(ObjectName::new(["id"]))),
+            op: BinaryOperator::Eq,
+            right: Box::new(Expr::Literal(Value::Integer(1))),
+        }));
+
+        // 修饰符顺序错误
+        let mut parser = Parser::new_from_sql("UPDATE IGNORE LOW_PRIORITY users SET a = 1");
+        assert!(parser.parse_update_statement().is_err());
+    }
+
+    #[test]
+    fn test_update_default_value() {
+        let sql = "UPDATE users SET status = DEFAULT, score = DEFAULT(score) + 1 WHERE id = 1";
+        let update = Parser::new_from_sql(sql).parse_update_statement().unwrap();
+        assert_eq!(update.assignments[0].1, Expr::Literal(Value::DEFAULT));
+        assert!(matches!(&update.assignments[1].1, Expr::BinaryOp { op: BinaryOperator::Plus, .. }));
+
+        // 赋值以外的位置不能使用单独的 DEFAULT
+        for sql in ["UPDATE users SET a = 1 WHERE b = DEFAULT", "UPDATE users SET a = DEFAULT + 1"] {
+            let mut parser = Parser::new_from_sql(sql);
+            assert!(parser.parse_update_statement().is_err() || parser.has_more(), "{}", sql);
+        }
+        let err = Parser::new_from_sql("a = DEFAULT").parse_expr(0).unwrap_err();
+        assert!(err.message.contains("DEFAULT"), "{:?}", err);
+    }
+
+    #[test]
+    fn test_update_colon_equals() {
+        // SET 中的 := 与 = 相同，值中的 := 仍然是用户变量赋值
+        let update = Parser::new_from_sql("UPDATE t SET a := 1, b := @n := @n + 1").parse_update_statement().unwrap();
+        let expected = Parser::new_from_sql("UPDATE t SET a = 1, b = @n := @n + 1").parse_update_statement().unwrap();
+        assert_eq!(update, expected);
+        assert!(matches!(&update.assignments[1].1, Expr::Assignment { variable, .. } if variable == "@n"));
+    }
+
+    #[test]
+    fn test_update_qualified_table() {
+        let sql = "UPDATE mydb.`order` AS o SET `status` = 'paid' WHERE o.id = 1";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_update_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let update = result.unwrap();
+
+        assert_eq!(update.table, TableReference {
+            name: ObjectName(vec![Ident::new("mydb"), Ident::with_quote('`', "order")]),
+            partitions: None,
+            alias: Some("o".to_string()),
+            index_hints: Vec::new(),
+            sample: None,
+            subquery: None,
+            function: None,
+            lateral: false,
+        });
+        assert_eq!(update.table.name.to_string(), "mydb.`order`");
+        assert_eq!(update.assignments[0].0, ObjectName(vec![Ident::with_quote('`', "status")]));
+    }
+
+    #[test]
+    fn test_update_qualified_targets_order_by_limit() {
+        let sql = "UPDATE orders o SET o.status = 'late', o.`total` = o.total * 2 + shipping, retries = retries + 1 \
+                   WHERE o.due < NOW() ORDER BY o.due DESC, id LIMIT 10";
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_update_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        let update = result.unwrap();
+        assert!(!parser.has_more());
+
+        let targets: Vec<String> = update.assignments.iter().map(|(column, _)| column.to_string()).collect();
+        assert_eq!(targets, ["o.status", "o.`total`", "retries"]);
+        assert_eq!(update.assignments[1].0.name().value, "total");
+        #[cfg(feature = "formatter")]
+        assert_eq!(update.assignments[1].1.to_string(), "o.total * 2 + shipping");
+        assert_eq!(update.order_by.as_ref().map(Vec::len), Some(2));
+        assert!(!update.order_by.unwrap()[0].asc);
+        assert!(update.limit.is_some());
+
+        // 子句顺序与 DELETE 相同：WHERE、ORDER BY、LIMIT
+        for sql in [
+            "UPDATE t SET a = 1 LIMIT 1 ORDER BY id",
+            "UPDATE t SET a = 1 ORDER BY id WHERE b = 2",
+            "UPDATE t SET a = 1 LIMIT 1 WHERE b = 2",
+            "UPDATE t SET a = 1 ORDER id",
+            "UPDATE t SET t. = 1",
+        ] {
+            let mut parser = Parser::new_from_sql(sql);
+            assert!(parser.parse_update_statement().is_err() || parser.has_more(), "{}", sql);
+        }
+    }
+}
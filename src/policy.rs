@@ -0,0 +1,429 @@
+//! 表、库和函数的访问策略
+//!
+//! 嵌入方通过 [`Policy`] 声明允许或禁止访问的表、库（schema）和函数，
+//! [`Policy::check`] 遍历语句的AST（包括 INSERT ... SELECT 中的查询），返回所有违反策略的引用。
+//! 名称比较不区分大小写，也不区分是否加了引号，`mydb`.`users` 与 mydb.users 是同一张表。
+
+use std::collections::HashSet;
+
+use crate::ast::common::{Ident, ObjectName, TableFunction, TableReference};
+#[cfg(feature = "ddl")]
+use crate::ast::{alter::AlterStatement, create::CreateStatement};
+use crate::ast::delete::DeleteStatement;
+use crate::ast::expr::{Expr, OrderByExpr, SortKey};
+use crate::ast::insert::InsertStatement;
+use crate::ast::select::{SelectColumn, SelectStatement};
+use crate::ast::show::ShowTarget;
+use crate::ast::update::UpdateStatement;
+use crate::ast::SQLStatement;
+use crate::parser::Dialect;
+use crate::token::{tokenize_with_spans, Span, Token};
+
+// 允许列表和禁止列表，允许列表为None表示不限制
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Rule {
+    allow: Option<HashSet<String>>,
+    deny: HashSet<String>,
+}
+
+impl Rule {
+    fn permits(&self, names: &[String]) -> bool {
+        let allowed = self.allow.as_ref().is_none_or(|allow| names.iter().any(|name| allow.contains(name)));
+        allowed && !names.iter().any(|name| self.deny.contains(name))
+    }
+}
+
+// 去掉引号后以点号连接的全名，例如 `mydb`.`users` 为 mydb.users；
+// 名称本身含点号时保留引号，`mydb.users` 是当前库中的另一张表
+fn full_name(parts: &[Ident]) -> String {
+    let parts: Vec<String> = parts
+        .iter()
+        .map(|ident| if ident.value.contains('.') { ident.to_string() } else { ident.value.to_string() })
+        .collect();
+    parts.join(".").to_lowercase()
+}
+
+fn lowercase<I: IntoIterator<Item = S>, S: AsRef<str>>(names: I) -> impl Iterator<Item = String> {
+    names.into_iter().map(|name| name.as_ref().to_lowercase())
+}
+
+/// 访问策略
+///
+/// 同一类对象同时设置了允许列表和禁止列表时，必须在允许列表中且不在禁止列表中。
+/// 表名可以写成 users 或 mydb.users，前者匹配任意库中的 users 表。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Policy {
+    tables: Rule,
+    schemas: Rule,
+    functions: Rule,
+}
+
+/// 违反策略的对象
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyViolationKind {
+    Table(ObjectName),
+    Schema(String),
+    Function(String),
+}
+
+/// 违反策略的引用
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyViolation {
+    pub kind: PolicyViolationKind,
+    /// 在原始SQL中的位置，只有 [`Policy::check_sql`] 会设置
+    pub span: Option<Span>,
+}
+
+/// 可以进行策略检查的语句
+pub trait PolicyTarget {
+    // 按出现顺序收集引用的表和函数
+    fn collect(&self, refs: &mut References);
+}
+
+/// 语句中引用的表和函数
+#[derive(Debug, Default)]
+pub struct References {
+    tables: Vec<ObjectName>,
+    functions: Vec<String>,
+}
+
+impl Policy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 只允许访问这些表，可以多次调用
+    pub fn allow_tables<I: IntoIterator<Item = S>, S: AsRef<str>>(mut self, tables: I) -> Self {
+        self.tables.allow.get_or_insert_with(HashSet::new).extend(lowercase(tables));
+        self
+    }
+
+    /// 禁止访问这些表
+    pub fn deny_tables<I: IntoIterator<Item = S>, S: AsRef<str>>(mut self, tables: I) -> Self {
+        self.tables.deny.extend(lowercase(tables));
+        self
+    }
+
+    /// 只允许访问这些库中的表，未限定库名的表不受限制
+    pub fn allow_schemas<I: IntoIterator<Item = S>, S: AsRef<str>>(mut self, schemas: I) -> Self {
+        self.schemas.allow.get_or_insert_with(HashSet::new).extend(lowercase(schemas));
+        self
+    }
+
+    /// 禁止访问这些库中的表
+    pub fn deny_schemas<I: IntoIterator<Item = S>, S: AsRef<str>>(mut self, schemas: I) -> Self {
+        self.schemas.deny.extend(lowercase(schemas));
+        self
+    }
+
+    /// 只允许调用这些函数
+    pub fn allow_functions<I: IntoIterator<Item = S>, S: AsRef<str>>(mut self, functions: I) -> Self {
+        self.functions.allow.get_or_insert_with(HashSet::new).extend(lowercase(functions));
+        self
+    }
+
+    /// 禁止调用这些函数
+    pub fn deny_functions<I: IntoIterator<Item = S>, S: AsRef<str>>(mut self, functions: I) -> Self {
+        self.functions.deny.extend(lowercase(functions));
+        self
+    }
+
+    /// 检查语句，按出现顺序返回所有违反策略的引用
+    pub fn check<S: PolicyTarget + ?Sized>(&self, stmt: &S) -> Vec<PolicyViolation> {
+        let mut refs = References::default();
+        stmt.collect(&mut refs);
+        let mut violations = Vec::new();
+        for table in refs.tables {
+            let full = full_name(&table.0);
+            let name = full_name(std::slice::from_ref(table.name()));
+            if !self.tables.permits(&[full, name]) {
+                violations.push(PolicyViolationKind::Table(table.clone()));
+            }
+            if let Some(schema) = table.qualifier().last() {
+                let schema = schema.value.to_lowercase();
+                if !self.schemas.permits(std::slice::from_ref(&schema)) {
+                    violations.push(PolicyViolationKind::Schema(schema));
+                }
+            }
+        }
+        for function in refs.functions {
+            if !self.functions.permits(&[function.to_lowercase()]) {
+                violations.push(PolicyViolationKind::Function(function));
+            }
+        }
+        violations.into_iter().map(|kind| PolicyViolation { kind, span: None }).collect()
+    }
+
+    /// 与 [`check`](Self::check) 相同，并根据原始SQL为每个违反策略的引用计算位置
+    pub fn check_sql<S: PolicyTarget + ?Sized>(&self, sql: &str, stmt: &S) -> Vec<PolicyViolation> {
+        let mut violations = self.check(stmt);
+        let (tokens, spans) = tokenize_with_spans(sql, Dialect::MySql);
+        let mut used = HashSet::new();
+        for violation in &mut violations {
+            // 同名的引用依次对应到源码中的各次出现
+            let position = (0..tokens.len()).find(|i| !used.contains(i) && refers_to(&tokens, *i, &violation.kind));
+            if let Some(i) = position {
+                used.insert(i);
+                violation.span = Some(spans[i]);
+            }
+        }
+        violations
+    }
+}
+
+// 第 i 个token是否为违反策略的引用
+fn refers_to(tokens: &[Token], i: usize, kind: &PolicyViolationKind) -> bool {
+    match (kind, &tokens[i]) {
+        (PolicyViolationKind::Table(name), Token::QualifiedIdentifier(parts)) => {
+            full_name(parts) == full_name(&name.0)
+        }
+        (PolicyViolationKind::Table(name), Token::Identifier(ident) | Token::QuotedIdentifier(ident)) => {
+            name.0.len() == 1 && ident.eq_ignore_ascii_case(&name.name().value)
+        }
+        (PolicyViolationKind::Schema(schema), Token::QualifiedIdentifier(parts)) => {
+            parts.len() > 1 && parts[parts.len() - 2].value.eq_ignore_ascii_case(schema)
+        }
+        (PolicyViolationKind::Function(function), Token::Identifier(name) | Token::Keyword { text: name, .. }) => {
+            name.eq_ignore_ascii_case(function) && tokens.get(i + 1) == Some(&Token::Punctuator('('))
+        }
+        _ => false,
+    }
+}
+
+impl References {
+    /// 按出现顺序引用的表
+    pub fn tables(&self) -> &[ObjectName] {
+        &self.tables
+    }
+
+    // 派生表和表函数的名称只是别名，只收集子查询和函数参数中的引用
+    fn table(&mut self, table: &TableReference) {
+        if let Some(subquery) = &table.subquery {
+            subquery.collect(self);
+        } else if let Some(function) = &table.function {
+            match &**function {
+                TableFunction::JsonTable { expr, .. } => {
+                    self.functions.push("JSON_TABLE".to_string());
+                    self.expr(expr);
+                }
+                TableFunction::Call { name, args, .. } => {
+                    self.functions.push(name.clone());
+                    args.iter().for_each(|arg| self.expr(arg));
+                }
+            }
+        } else {
+            self.tables.push(table.name.clone());
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        expr.walk(|node| match node {
+            Expr::FunctionCall { name, .. } => self.functions.push(name.clone()),
+            Expr::Aggregate { func, .. } => self.functions.push(func.name().to_string()),
+            Expr::GroupConcat(_) => self.functions.push("GROUP_CONCAT".to_string()),
+            Expr::Subquery(select) | Expr::Exists(select) => select.collect(self),
+            _ => {}
+        });
+    }
+
+    fn order_by(&mut self, order_by: &Option<Vec<OrderByExpr>>) {
+        for item in order_by.iter().flatten() {
+            if let SortKey::Expr(expr) = &item.expr {
+                self.expr(expr);
+            }
+        }
+    }
+}
+
+impl PolicyTarget for SelectStatement {
+    fn collect(&self, refs: &mut References) {
+        let start = refs.tables.len();
+        for cte in self.with.iter().flat_map(|with| &with.ctes) {
+            cte.query.collect(refs);
+        }
+        for column in &self.columns {
+            if let SelectColumn::Expression { expr, .. } = column {
+                refs.expr(expr);
+            }
+        }
+        self.distinct_on.iter().flatten().for_each(|expr| refs.expr(expr));
+        if let Some(from) = &self.from {
+            refs.table(from);
+        }
+        for join in &self.joins {
+            refs.table(&join.table);
+            join.on.iter().for_each(|expr| refs.expr(expr));
+        }
+        self.where_clause.iter().for_each(|expr| refs.expr(expr));
+        for key in self.group_by.iter().flatten() {
+            if let SortKey::Expr(expr) = key {
+                refs.expr(expr);
+            }
+        }
+        self.having.iter().for_each(|expr| refs.expr(expr));
+        self.qualify.iter().for_each(|expr| refs.expr(expr));
+        self.windows.iter().flat_map(|(_, spec)| spec.exprs()).for_each(|expr| refs.expr(expr));
+        for operation in &self.set_operations {
+            operation.select.collect(refs);
+        }
+        refs.order_by(&self.order_by);
+        // 对 CTE 的引用不是表
+        if let Some(with) = &self.with {
+            let is_cte = |name: &ObjectName| {
+                name.0.len() == 1 && with.ctes.iter().any(|cte| cte.name.eq_ignore_ascii_case(&name.name().value))
+            };
+            let body = refs.tables.split_off(start);
+            refs.tables.extend(body.into_iter().filter(|name| !is_cte(name)));
+        }
+    }
+}
+
+impl PolicyTarget for InsertStatement {
+    fn collect(&self, refs: &mut References) {
+        refs.table(&self.table);
+        self.values.iter().flatten().flatten().for_each(|cell| refs.expr(cell));
+        self.set_clause.iter().flatten().for_each(|(_, value)| refs.expr(value));
+        if let Some(select) = &self.select_clause {
+            select.collect(refs);
+        }
+        for (_, value) in self.on_duplicate.iter().flat_map(|clause| &clause.updates) {
+            refs.expr(value);
+        }
+    }
+}
+
+impl PolicyTarget for UpdateStatement {
+    fn collect(&self, refs: &mut References) {
+        refs.table(&self.table);
+        self.assignments.iter().for_each(|(_, value)| refs.expr(value));
+        self.where_clause.iter().for_each(|expr| refs.expr(expr));
+        refs.order_by(&self.order_by);
+    }
+}
+
+impl PolicyTarget for DeleteStatement {
+    fn collect(&self, refs: &mut References) {
+        refs.table(&self.table);
+        self.where_clause.iter().for_each(|expr| refs.expr(expr));
+        refs.order_by(&self.order_by);
+    }
+}
+
+impl PolicyTarget for SQLStatement {
+    fn collect(&self, refs: &mut References) {
+        match self {
+            SQLStatement::Select(select) => select.collect(refs),
+            SQLStatement::Insert(insert) => insert.collect(refs),
+            SQLStatement::Update(update) => update.collect(refs),
+            SQLStatement::Delete(delete) => delete.collect(refs),
+            #[cfg(feature = "ddl")]
+            SQLStatement::Create(CreateStatement::Table(create)) => refs.tables.push(create.name.clone()),
+            #[cfg(feature = "ddl")]
+            SQLStatement::Create(CreateStatement::Routine(_)) => {}
+            #[cfg(feature = "ddl")]
+            SQLStatement::Create(CreateStatement::View(view)) => {
+                refs.tables.push(view.name.clone());
+                view.query.collect(refs);
+            }
+            #[cfg(feature = "ddl")]
+            SQLStatement::Alter(AlterStatement::Table(alter)) => refs.tables.push(alter.name.clone()),
+            SQLStatement::Show(show) => match &show.target {
+                ShowTarget::CreateTable(table) | ShowTarget::Index { table, .. } => refs.tables.push(table.clone()),
+                _ => {}
+            },
+            SQLStatement::Set(set) => set.assignments.iter().for_each(|assignment| refs.expr(&assignment.value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::insert::InsertStatementParser;
+    use crate::parser::select::SelectStatementParser;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_policy() {
+        let policy = Policy::new()
+            .allow_tables(["orders", "analytics.events", "users"])
+            .deny_schemas(["mysql"])
+            .deny_functions(["SLEEP", "load_file"]);
+
+        let sql = "SELECT id, LOAD_FILE('/etc/passwd'), UPPER(name) FROM mysql.users WHERE SLEEP(1) = 0";
+        let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+        let violations = policy.check_sql(sql, &select);
+        let kinds: Vec<_> = violations.iter().map(|v| v.kind.clone()).collect();
+        assert_eq!(
+            kinds,
+            [
+                PolicyViolationKind::Schema("mysql".to_string()),
+                PolicyViolationKind::Function("LOAD_FILE".to_string()),
+                PolicyViolationKind::Function("SLEEP".to_string()),
+            ]
+        );
+        let texts: Vec<&str> = violations.iter().map(|v| &sql[v.span.unwrap().start..v.span.unwrap().end]).collect();
+        assert_eq!(texts, ["mysql.users", "LOAD_FILE", "SLEEP"]);
+
+        // 表名可以按库限定，INSERT ... SELECT 中的查询也会检查
+        let sql = "INSERT INTO analytics.events (id) SELECT id FROM payments";
+        let insert = Parser::new_from_sql(sql).parse_insert_statement().unwrap();
+        let violations = policy.check(&insert);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].kind, PolicyViolationKind::Table(ObjectName::new(["payments"])));
+        assert_eq!(violations[0].span, None);
+
+        let select = Parser::new_from_sql("SELECT COUNT(id) FROM Orders").parse_select_statement().unwrap();
+        assert!(policy.check(&SQLStatement::Select(Box::new(select))).is_empty());
+
+        // 派生表的别名不是表名，检查的是子查询中的表
+        let sql = "SELECT COUNT(*) FROM (SELECT id FROM payments) AS orders";
+        let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+        let violations = policy.check(&select);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].kind, PolicyViolationKind::Table(ObjectName::new(["payments"])));
+
+        // WINDOW 子句中的表达式同样检查
+        let sql = "SELECT SUM(total) OVER w FROM orders WINDOW w AS (PARTITION BY SLEEP(1))";
+        let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+        let violations = policy.check(&select);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].kind, PolicyViolationKind::Function("SLEEP".to_string()));
+    }
+
+    #[test]
+    fn test_policy_quoted_names() {
+        let deny = Policy::new().deny_tables(["mydb.users", "secrets"]);
+        let allow = Policy::new().allow_tables(["mydb.users", "orders"]);
+        let tables = |policy: &Policy, sql: &str| -> Vec<String> {
+            let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+            policy
+                .check_sql(sql, &select)
+                .iter()
+                .map(|violation| {
+                    assert!(matches!(violation.kind, PolicyViolationKind::Table(_)));
+                    let span = violation.span.expect("violation should have a span");
+                    sql[span.start..span.end].to_string()
+                })
+                .collect()
+        };
+
+        // 加引号不能绕过禁止列表
+        for sql in [
+            "SELECT * FROM mydb.users",
+            "SELECT * FROM `mydb`.`users`",
+            "SELECT * FROM mydb.`users`",
+            "SELECT * FROM `MyDb`.users",
+        ] {
+            let table = sql.trim_start_matches("SELECT * FROM ");
+            assert_eq!(tables(&deny, sql), [table], "{}", sql);
+            assert!(tables(&allow, sql).is_empty(), "{}", sql);
+        }
+        assert_eq!(tables(&deny, "SELECT * FROM `secrets`"), ["`secrets`"]);
+        assert!(tables(&allow, "SELECT * FROM `orders` JOIN shop.`Orders` ON 1 = 1").is_empty());
+        assert_eq!(tables(&allow, "SELECT * FROM `mydb`.`payments`"), ["`mydb`.`payments`"]);
+        // 引号内的点号是名称的一部分
+        assert_eq!(tables(&allow, "SELECT * FROM `mydb.users`"), ["`mydb.users`"]);
+        assert!(tables(&deny, "SELECT * FROM `mydb.users`").is_empty());
+    }
+}
@@ -0,0 +1,700 @@
+//! 从条件中提取列的取值范围
+//!
+//! 把 WHERE 中对某一列的简单比较（=、<、<=、>、>=、BETWEEN、IN）归一化为区间或取值集合，
+//! 供分片层判断需要扫描哪些分区。AND 连接的多个条件取交集；无法分析的条件会报告出来，
+//! 由调用方决定是否退回到扫描全部分区。
+//!
+//! [`time_window`] 在此基础上计算查询在时间列上扫描的时间窗口，供成本检查拒绝扫描范围过大的查询。
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::analysis::split_conjunction;
+use crate::ast::common::ObjectName;
+use crate::ast::expr::{BinaryOperator, Expr, IntervalUnit, UnaryOperator, Value};
+use crate::ast::select::SelectStatement;
+
+/// 区间的一端
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Bound {
+    Unbounded,
+    Included(Value),
+    Excluded(Value),
+}
+
+/// 列的取值范围
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnRange {
+    /// 区间，两端都是 Unbounded 时表示条件没有限制这一列
+    Range { low: Bound, high: Bound },
+    /// 有限的取值集合，来自 = 和 IN，按升序排列且没有重复
+    Values(Vec<Value>),
+    /// 条件不可能成立，例如 a > 5 AND a < 3
+    Empty,
+}
+
+/// 无法提取取值范围的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeError {
+    /// 涉及这一列、但不是简单比较的条件，例如 OR、NOT IN、函数调用或与其它列比较
+    Unsupported(Expr),
+    /// 两个值无法比较大小，例如字符串和数字
+    Incomparable(Value, Value),
+    /// 时间列的取值不是可识别的时间，见 [`time_window`]
+    InvalidTimestamp(Value),
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeError::Unsupported(expr) => write!(f, "Unsupported predicate: {:?}", expr),
+            RangeError::Incomparable(a, b) => write!(f, "Cannot compare {:?} with {:?}", a, b),
+            RangeError::InvalidTimestamp(value) => write!(f, "Invalid timestamp: {:?}", value),
+        }
+    }
+}
+
+impl Error for RangeError {}
+
+impl ColumnRange {
+    /// 不限制取值的范围
+    pub fn unbounded() -> Self {
+        ColumnRange::Range { low: Bound::Unbounded, high: Bound::Unbounded }
+    }
+
+    /// 范围是否包含 value，无法比较时返回None
+    pub fn contains(&self, value: &Value) -> Option<bool> {
+        match self {
+            ColumnRange::Range { low, high } => {
+                let above = match low {
+                    Bound::Unbounded => true,
+                    Bound::Included(low) => compare(value, low)? != Ordering::Less,
+                    Bound::Excluded(low) => compare(value, low)? == Ordering::Greater,
+                };
+                let below = match high {
+                    Bound::Unbounded => true,
+                    Bound::Included(high) => compare(value, high)? != Ordering::Greater,
+                    Bound::Excluded(high) => compare(value, high)? == Ordering::Less,
+                };
+                Some(above && below)
+            }
+            ColumnRange::Values(values) => {
+                for v in values {
+                    if compare(v, value)? == Ordering::Equal {
+                        return Some(true);
+                    }
+                }
+                Some(false)
+            }
+            ColumnRange::Empty => Some(false),
+        }
+    }
+
+    // 与另一个范围取交集
+    fn intersect(self, other: ColumnRange) -> Result<ColumnRange, RangeError> {
+        let range = match (self, other) {
+            (ColumnRange::Empty, _) | (_, ColumnRange::Empty) => ColumnRange::Empty,
+            (ColumnRange::Values(values), other) | (other, ColumnRange::Values(values)) => {
+                let mut kept = Vec::new();
+                for value in values {
+                    match other.contains(&value) {
+                        Some(true) => kept.push(value),
+                        Some(false) => {}
+                        None => return Err(incomparable(&other, value)),
+                    }
+                }
+                if kept.is_empty() {
+                    ColumnRange::Empty
+                } else {
+                    ColumnRange::Values(kept)
+                }
+            }
+            (ColumnRange::Range { low: l1, high: h1 }, ColumnRange::Range { low: l2, high: h2 }) => {
+                let low = tighter(l1, l2, Ordering::Greater)?;
+                let high = tighter(h1, h2, Ordering::Less)?;
+                let empty = match (&low, &high) {
+                    (Bound::Included(l), Bound::Included(h)) => checked_compare(l, h)? == Ordering::Greater,
+                    (Bound::Included(l) | Bound::Excluded(l), Bound::Included(h) | Bound::Excluded(h)) => {
+                        checked_compare(l, h)? != Ordering::Less
+                    }
+                    _ => false,
+                };
+                if empty {
+                    ColumnRange::Empty
+                } else {
+                    ColumnRange::Range { low, high }
+                }
+            }
+        };
+        Ok(range)
+    }
+}
+
+// 取值集合与区间无法比较时，报告集合中的值和区间的一端
+fn incomparable(range: &ColumnRange, value: Value) -> RangeError {
+    let bound = match range {
+        ColumnRange::Range { low: Bound::Included(v) | Bound::Excluded(v), .. }
+        | ColumnRange::Range { high: Bound::Included(v) | Bound::Excluded(v), .. } => v.clone(),
+        ColumnRange::Values(values) => values[0].clone(),
+        _ => Value::Null,
+    };
+    RangeError::Incomparable(value, bound)
+}
+
+// 取两个下界（prefer 为 Greater）或上界（prefer 为 Less）中更严格的一个，值相同时 Excluded 更严格
+fn tighter(a: Bound, b: Bound, prefer: Ordering) -> Result<Bound, RangeError> {
+    let (va, vb) = match (&a, &b) {
+        (Bound::Unbounded, _) => return Ok(b),
+        (_, Bound::Unbounded) => return Ok(a),
+        (Bound::Included(va) | Bound::Excluded(va), Bound::Included(vb) | Bound::Excluded(vb)) => (va, vb),
+    };
+    Ok(match checked_compare(va, vb)? {
+        Ordering::Equal if matches!(b, Bound::Excluded(_)) => b,
+        Ordering::Equal => a,
+        ordering if ordering == prefer => a,
+        _ => b,
+    })
+}
+
+// 数字之间按数值比较，字符串之间按字节比较，其它组合无法比较
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Integer(a), Value::Integer(b)) => Some(a.cmp(b)),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        (Value::Boolean(a), Value::Boolean(b)) => Some(a.cmp(b)),
+        _ => number(a)?.partial_cmp(&number(b)?),
+    }
+}
+
+fn checked_compare(a: &Value, b: &Value) -> Result<Ordering, RangeError> {
+    compare(a, b).ok_or_else(|| RangeError::Incomparable(a.clone(), b.clone()))
+}
+
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(i) => Some(*i as f64),
+        Value::Float(f) => Some(*f),
+        Value::Decimal(d) => d.parse().ok(),
+        _ => None,
+    }
+}
+
+// 字面量，包括带负号的数字和 DATE '2024-01-01' 这样的日期时间字面量；NULL 和 DEFAULT 不能用于确定范围
+fn literal(expr: &Expr) -> Option<Value> {
+    match expr {
+        Expr::Literal(Value::Null | Value::DEFAULT) => None,
+        Expr::Literal(value) => Some(value.clone()),
+        Expr::UnaryOp { op: UnaryOperator::Minus, expr } => match expr.as_ref() {
+            Expr::Literal(value) => value.negate(),
+            _ => None,
+        },
+        Expr::UnaryOp { op: UnaryOperator::Plus, expr } => literal(expr),
+        Expr::Cast { expr, data_type }
+            if ["DATE", "TIME", "TIMESTAMP", "DATETIME"].iter().any(|t| data_type.name.eq_ignore_ascii_case(t)) =>
+        {
+            match expr.as_ref() {
+                Expr::Literal(value @ Value::String(_)) => Some(value.clone()),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// column 可以写成 col 或 t.col，未限定的一方匹配任意表
+fn is_column(expr: &Expr, column: &ObjectName) -> bool {
+    matches!(expr, Expr::Identifier(name) if name.matches(column))
+}
+
+// 把一个简单比较转换为范围，不是简单比较时返回None，value 把比较的另一侧转换为值
+fn predicate_range<F>(expr: &Expr, column: &ObjectName, value: &F) -> Result<Option<ColumnRange>, RangeError>
+where
+    F: Fn(&Expr) -> Result<Option<Value>, RangeError>,
+{
+    let range = |low, high| Ok(Some(ColumnRange::Range { low, high }));
+    match expr {
+        Expr::BinaryOp { left, op, right } => {
+            // 字面量在左边时交换两侧，同时翻转比较方向
+            let (other, op) = if is_column(left, column) {
+                (right, op.clone())
+            } else if is_column(right, column) {
+                let flipped = match op {
+                    BinaryOperator::Lt => BinaryOperator::Gt,
+                    BinaryOperator::LtEq => BinaryOperator::GtEq,
+                    BinaryOperator::Gt => BinaryOperator::Lt,
+                    BinaryOperator::GtEq => BinaryOperator::LtEq,
+                    op => op.clone(),
+                };
+                (left, flipped)
+            } else {
+                return Ok(None);
+            };
+            if !matches!(
+                op,
+                BinaryOperator::Eq | BinaryOperator::Lt | BinaryOperator::LtEq | BinaryOperator::Gt | BinaryOperator::GtEq
+            ) {
+                return Ok(None);
+            }
+            let Some(v) = value(other)? else {
+                return Ok(None);
+            };
+            match op {
+                BinaryOperator::Eq => Ok(Some(ColumnRange::Values(vec![v]))),
+                BinaryOperator::Lt => range(Bound::Unbounded, Bound::Excluded(v)),
+                BinaryOperator::LtEq => range(Bound::Unbounded, Bound::Included(v)),
+                BinaryOperator::Gt => range(Bound::Excluded(v), Bound::Unbounded),
+                _ => range(Bound::Included(v), Bound::Unbounded),
+            }
+        }
+        Expr::Between { expr, low, high, negated: false } if is_column(expr, column) => {
+            match (value(low)?, value(high)?) {
+                (Some(low), Some(high)) => range(Bound::Included(low), Bound::Included(high)),
+                _ => Ok(None),
+            }
+        }
+        Expr::In { expr, list, negated: false } if is_column(expr, column) => {
+            let mut values = Vec::new();
+            for item in list {
+                let Some(v) = value(item)? else {
+                    return Ok(None);
+                };
+                values.push(v);
+            }
+            Ok(Some(ColumnRange::Values(values)))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// 提取条件对 column 的取值范围，column 可以写成 col 或 t.col
+///
+/// 条件按 AND 拆分后，不涉及这一列的项被忽略，涉及这一列的项必须都是与字面量的简单比较，
+/// 否则返回 [`RangeError::Unsupported`]。没有任何项限制这一列时返回 [`ColumnRange::unbounded`]。
+/// 数字之间按数值比较，字符串之间按字节比较，不考虑排序规则；DATE '2024-01-01' 按字符串处理。
+pub fn extract_range(expr: &Expr, column: &str) -> Result<ColumnRange, RangeError> {
+    column_range(expr, &ObjectName::new(column.split('.')), &|expr| Ok(literal(expr)))
+}
+
+fn column_range<F>(expr: &Expr, column: &ObjectName, value: &F) -> Result<ColumnRange, RangeError>
+where
+    F: Fn(&Expr) -> Result<Option<Value>, RangeError>,
+{
+    let mut result = ColumnRange::unbounded();
+    for conjunct in split_conjunction(expr) {
+        if !conjunct.iter().any(|node| is_column(node, column)) {
+            continue;
+        }
+        let range = predicate_range(conjunct, column, value)?.ok_or_else(|| RangeError::Unsupported(conjunct.clone()))?;
+        // 取值集合排序去重
+        let range = match range {
+            ColumnRange::Values(mut values) => {
+                for pair in values.windows(2) {
+                    checked_compare(&pair[0], &pair[1])?;
+                }
+                values.sort_by(|a, b| compare(a, b).unwrap_or(Ordering::Equal));
+                values.dedup_by(|a, b| compare(a, b) == Some(Ordering::Equal));
+                ColumnRange::Values(values)
+            }
+            range => range,
+        };
+        result = result.intersect(range)?;
+    }
+    Ok(result)
+}
+
+/// 查询在时间列上扫描的时间窗口，时间以 UTC 的 Unix 秒表示
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeWindow {
+    /// 条件不可能成立，不扫描任何数据
+    Empty,
+    /// 扫描 start 到 end 之间的数据，None 表示这一端没有限制
+    Window { start: Option<i64>, end: Option<i64> },
+}
+
+impl TimeWindow {
+    /// 两端都有限制
+    pub fn is_bounded(&self) -> bool {
+        matches!(self, TimeWindow::Empty | TimeWindow::Window { start: Some(_), end: Some(_) })
+    }
+
+    /// 窗口的长度，有一端没有限制时返回None
+    pub fn duration(&self) -> Option<Duration> {
+        match *self {
+            TimeWindow::Empty => Some(Duration::ZERO),
+            TimeWindow::Window { start: Some(start), end: Some(end) } => {
+                Some(Duration::from_secs(end.saturating_sub(start).max(0) as u64))
+            }
+            TimeWindow::Window { .. } => None,
+        }
+    }
+
+    /// 扫描范围是否超过 max，没有限制的窗口总是超过
+    pub fn exceeds(&self, max: Duration) -> bool {
+        self.duration().is_none_or(|duration| duration > max)
+    }
+
+    // 与另一个窗口取交集
+    fn intersect(self, other: TimeWindow) -> TimeWindow {
+        match (self, other) {
+            (TimeWindow::Window { start: s1, end: e1 }, TimeWindow::Window { start: s2, end: e2 }) => {
+                let start = s1.max(s2);
+                let end = match (e1, e2) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                };
+                match (start, end) {
+                    (Some(start), Some(end)) if start > end => TimeWindow::Empty,
+                    _ => TimeWindow::Window { start, end },
+                }
+            }
+            _ => TimeWindow::Empty,
+        }
+    }
+}
+
+/// 计算查询在 columns 这些时间列上扫描的时间窗口，列名可以写成 col 或 t.col
+///
+/// 每一列的范围按 [`extract_range`] 的规则从 WHERE 中提取，多个时间列的窗口取交集；没有 WHERE
+/// 或没有条件限制时间列时窗口没有限制。时间可以写成 'YYYY-MM-DD' 或 'YYYY-MM-DD HH:MM:SS'
+/// （月、日、时、分、秒可以只写一位，小数秒被忽略），也可以写成 DATE '...'、TIMESTAMP '...'，
+/// 按 UTC 计算；整数按 Unix 秒处理。比较前先把时间转换为 Unix 秒，'2024-1-5' 早于 '2024-01-10'。
+/// 取值集合按最早到最晚的时间计算窗口，开区间和闭区间不做区分。
+///
+/// NOW()、CURRENT_DATE 等相对时间按当前系统时间计算，可以加减 INTERVAL n unit 或用
+/// DATE_ADD/DATE_SUB 偏移；需要固定参照时间时使用 [`time_window_at`]。
+pub fn time_window(select: &SelectStatement, columns: &[&str]) -> Result<TimeWindow, RangeError> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64);
+    time_window_at(select, columns, now)
+}
+
+/// 与 [`time_window`] 相同，但相对时间以 now（UTC 的 Unix 秒）为参照
+///
+/// 只支持单一单位的 INTERVAL（SECOND 到 YEAR，不含 MICROSECOND），数量必须是整数；
+/// INTERVAL '1:30' HOUR_MINUTE 这样的复合单位以及其它无法计算的时间表达式返回 [`RangeError::Unsupported`]。
+pub fn time_window_at(select: &SelectStatement, columns: &[&str], now: i64) -> Result<TimeWindow, RangeError> {
+    let mut window = TimeWindow::Window { start: None, end: None };
+    let Some(selection) = &select.where_clause else {
+        return Ok(window);
+    };
+    let value = |expr: &Expr| Ok(time_value(expr, now)?.map(Value::Integer));
+    for column in columns {
+        // 所有取值都已经转换为 Unix 秒
+        let seconds = |value: &Value| match value {
+            Value::Integer(seconds) => *seconds,
+            _ => unreachable!("time values are converted to seconds"),
+        };
+        let column_window = match column_range(selection, &ObjectName::new(column.split('.')), &value)? {
+            ColumnRange::Empty => TimeWindow::Empty,
+            ColumnRange::Values(values) => {
+                // 取值集合已经按升序排列
+                let start = seconds(&values[0]);
+                let end = seconds(&values[values.len() - 1]);
+                TimeWindow::Window { start: Some(start), end: Some(end) }
+            }
+            ColumnRange::Range { low, high } => {
+                let bound = |bound: Bound| match bound {
+                    Bound::Unbounded => None,
+                    Bound::Included(value) | Bound::Excluded(value) => Some(seconds(&value)),
+                };
+                TimeWindow::Window { start: bound(low), end: bound(high) }
+            }
+        };
+        window = window.intersect(column_window);
+    }
+    Ok(window)
+}
+
+// 把时间表达式转换为 Unix 秒，不是时间表达式时返回None，字面量不是可识别的时间时报错
+fn time_value(expr: &Expr, now: i64) -> Result<Option<i64>, RangeError> {
+    let name_is = |name: &str, names: &[&str]| names.iter().any(|n| name.eq_ignore_ascii_case(n));
+    let relative = |name: &str| {
+        if name_is(name, &["NOW", "CURRENT_TIMESTAMP", "SYSDATE", "LOCALTIME", "LOCALTIMESTAMP", "UTC_TIMESTAMP"]) {
+            Some(now)
+        } else if name_is(name, &["CURDATE", "CURRENT_DATE", "UTC_DATE"]) {
+            Some(now - now.rem_euclid(86400))
+        } else {
+            None
+        }
+    };
+    let seconds = match expr {
+        Expr::FunctionCall { name, args } if args.is_empty() => relative(name),
+        // CURRENT_DATE、CURRENT_TIMESTAMP 可以不带括号
+        Expr::Identifier(name) if name.qualifier().is_empty() && name.name().quote_style.is_none() => {
+            relative(&name.name().value)
+        }
+        Expr::BinaryOp { left, op: op @ (BinaryOperator::Plus | BinaryOperator::Minus), right } => {
+            let (time, interval, negative) = match (left.as_ref(), right.as_ref()) {
+                (time, Expr::Interval { .. }) => (time, right.as_ref(), *op == BinaryOperator::Minus),
+                (Expr::Interval { .. }, time) if *op == BinaryOperator::Plus => (time, left.as_ref(), false),
+                _ => return Ok(None),
+            };
+            match time_value(time, now)? {
+                Some(time) => shift(time, interval, negative),
+                None => None,
+            }
+        }
+        // DATE_ADD(NOW(), INTERVAL 7 DAY)
+        Expr::FunctionCall { name, args } if args.len() == 2 && name_is(name, &["DATE_ADD", "DATE_SUB", "ADDDATE", "SUBDATE"]) => {
+            match time_value(&args[0], now)? {
+                Some(time) => shift(time, &args[1], name_is(name, &["DATE_SUB", "SUBDATE"])),
+                None => None,
+            }
+        }
+        _ => match literal(expr) {
+            Some(Value::Integer(seconds)) => Some(seconds),
+            Some(Value::String(s)) => match parse_timestamp(&s) {
+                Some(seconds) => Some(seconds),
+                None => return Err(RangeError::InvalidTimestamp(Value::String(s))),
+            },
+            Some(value) => return Err(RangeError::InvalidTimestamp(value)),
+            None => None,
+        },
+    };
+    Ok(seconds)
+}
+
+// 时间加上或减去 INTERVAL n unit，复合单位或数量不是整数时返回None
+fn shift(time: i64, interval: &Expr, negative: bool) -> Option<i64> {
+    let Expr::Interval { value, unit } = interval else {
+        return None;
+    };
+    let amount = match literal(value)? {
+        Value::Integer(n) => n,
+        Value::String(s) => s.trim().parse().ok()?,
+        _ => return None,
+    };
+    let amount = if negative { amount.checked_neg()? } else { amount };
+    let seconds = |unit: i64| time.checked_add(amount.checked_mul(unit)?);
+    match unit {
+        IntervalUnit::Second => seconds(1),
+        IntervalUnit::Minute => seconds(60),
+        IntervalUnit::Hour => seconds(3600),
+        IntervalUnit::Day => seconds(86400),
+        IntervalUnit::Week => seconds(7 * 86400),
+        IntervalUnit::Month => add_months(time, amount),
+        IntervalUnit::Quarter => add_months(time, amount.checked_mul(3)?),
+        IntervalUnit::Year => add_months(time, amount.checked_mul(12)?),
+        _ => None,
+    }
+}
+
+// 按日历加减月份，日期超过目标月份的天数时取该月最后一天，与 MySQL 的 DATE_ADD 一致
+fn add_months(time: i64, months: i64) -> Option<i64> {
+    let (days, seconds) = (time.div_euclid(86400), time.rem_euclid(86400));
+    let (year, month, day) = civil_from_days(days);
+    let total = (year * 12 + month - 1).checked_add(months)?;
+    let (year, month) = (total.div_euclid(12), total.rem_euclid(12) + 1);
+    let day = day.min(days_in_month(year, month));
+    days_from_civil(year, month, day).checked_mul(86400)?.checked_add(seconds)
+}
+
+// 解析 YYYY-MM-DD[( |T)HH:MM[:SS[.fff]]]
+fn parse_timestamp(s: &str) -> Option<i64> {
+    let (date, time) = match s.find([' ', 'T']) {
+        Some(i) => (&s[..i], Some(&s[i + 1..])),
+        None => (s, None),
+    };
+    let mut parts = date.split('-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (parts.next()??, parts.next()??, parts.next()??);
+    if parts.next().is_some() || !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+    let mut seconds = days_from_civil(year, month, day) * 86400;
+    if let Some(time) = time {
+        // 小数秒不影响按秒计算的窗口
+        let time = time.split('.').next()?;
+        let fields = time.split(':').map(|part| part.parse::<i64>().ok()).collect::<Option<Vec<_>>>()?;
+        let (hour, minute, second) = match fields[..] {
+            [hour, minute] => (hour, minute, 0),
+            [hour, minute, second] => (hour, minute, second),
+            _ => return None,
+        };
+        if !(0..24).contains(&hour) || !(0..60).contains(&minute) || !(0..60).contains(&second) {
+            return None;
+        }
+        seconds += hour * 3600 + minute * 60 + second;
+    }
+    Some(seconds)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// 公历日期到 1970-01-01 的天数
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+// 1970-01-01 之后的天数到公历日期，days_from_civil 的逆运算
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::select::SelectStatementParser;
+    use crate::parser::Parser;
+
+    fn range(sql: &str, column: &str) -> Result<ColumnRange, RangeError> {
+        extract_range(&Parser::new_from_sql(sql).parse_expr(0).unwrap(), column)
+    }
+
+    #[test]
+    fn test_extract_range() {
+        let int = Value::Integer;
+        assert_eq!(
+            range("id >= 10 AND id < 20 AND name = 'x'", "id"),
+            Ok(ColumnRange::Range { low: Bound::Included(int(10)), high: Bound::Excluded(int(20)) })
+        );
+        // 字面量在左边和更严格的边界
+        assert_eq!(
+            range("100 > t.id AND t.id >= 1 AND id > 1", "id"),
+            Ok(ColumnRange::Range { low: Bound::Excluded(int(1)), high: Bound::Excluded(int(100)) })
+        );
+        // 表达式解析器还不支持 BETWEEN，直接构造
+        let between = Expr::Between {
+            expr: Box::new(Parser::new_from_sql("t.id").parse_expr(0).unwrap()),
+            low: Box::new(Expr::Literal(int(1))),
+            high: Box::new(Expr::Literal(int(200))),
+            negated: false,
+        };
+        assert_eq!(
+            extract_range(&between, "id"),
+            Ok(ColumnRange::Range { low: Bound::Included(int(1)), high: Bound::Included(int(200)) })
+        );
+        assert_eq!(range("id IN (3, 1, 2, 3) AND id > 1.5", "t.id"), Ok(ColumnRange::Values(vec![int(2), int(3)])));
+        assert_eq!(range("id = 5 AND id IN (1, 2)", "id"), Ok(ColumnRange::Empty));
+        assert_eq!(range("id > 5 AND id <= 5", "id"), Ok(ColumnRange::Empty));
+        assert_eq!(range("id >= -5 AND id <= -5", "id").unwrap().contains(&int(-5)), Some(true));
+        assert_eq!(
+            range("day >= '2024-01-01' AND day < '2024-02-01'", "day").unwrap().contains(&Value::String("2024-01-15".into())),
+            Some(true)
+        );
+        assert_eq!(
+            range("day BETWEEN DATE '2024-01-01' AND '2024-01-31'", "day"),
+            Ok(ColumnRange::Range {
+                low: Bound::Included(Value::String("2024-01-01".into())),
+                high: Bound::Included(Value::String("2024-01-31".into()))
+            })
+        );
+        assert_eq!(range("name = 'x'", "id"), Ok(ColumnRange::unbounded()));
+
+        // 无法分析的条件
+        assert!(matches!(range("id = 1 OR id = 2", "id"), Err(RangeError::Unsupported(_))));
+        assert!(matches!(range("id NOT IN (1, 2)", "id"), Err(RangeError::Unsupported(_))));
+        assert!(matches!(range("id > other_id", "id"), Err(RangeError::Unsupported(_))));
+        assert!(matches!(range("ABS(id) < 3", "id"), Err(RangeError::Unsupported(_))));
+        assert_eq!(
+            range("id > 1 AND id < 'x'", "id"),
+            Err(RangeError::Incomparable(int(1), Value::String("x".into())))
+        );
+    }
+
+    fn window(sql: &str, columns: &[&str]) -> Result<TimeWindow, RangeError> {
+        time_window(&Parser::new_from_sql(sql).parse_select_statement().unwrap(), columns)
+    }
+
+    #[test]
+    fn test_time_window() {
+        assert_eq!(parse_timestamp("1970-01-01"), Some(0));
+        assert_eq!(parse_timestamp("2024-03-01 01:02:03.5"), Some(1709254923));
+        assert_eq!(parse_timestamp("2024-02-29T00:00"), Some(1709164800));
+        assert_eq!(parse_timestamp("2023-02-29"), None);
+        assert_eq!(parse_timestamp("2024-01-01 25:00:00"), None);
+
+        let days = |n: u64| Duration::from_secs(n * 86400);
+        let w = window("SELECT * FROM t WHERE ts >= '2024-01-01' AND ts < '2024-01-08' AND id = 1", &["ts"]).unwrap();
+        assert_eq!(w.duration(), Some(days(7)));
+        assert!(!w.exceeds(days(7)) && w.exceeds(days(6)));
+
+        // 没有条件或只有一端有限制
+        let w = window("SELECT * FROM t", &["ts"]).unwrap();
+        assert!(!w.is_bounded() && w.exceeds(days(365)));
+        let w = window("SELECT * FROM t WHERE t.ts > '2024-01-01'", &["ts"]).unwrap();
+        assert_eq!(w, TimeWindow::Window { start: Some(1704067200), end: None });
+
+        // 多个时间列取交集，取值集合按最早到最晚计算
+        let w = window(
+            "SELECT * FROM t WHERE created >= '2024-01-01' AND updated < '2024-01-03' AND day IN ('2024-01-02', '2023-12-01')",
+            &["created", "updated", "day"],
+        )
+        .unwrap();
+        assert_eq!(w.duration(), Some(days(1)));
+        assert_eq!(window("SELECT * FROM t WHERE a < '2024-01-01' AND b > '2024-02-01'", &["a", "b"]), Ok(TimeWindow::Empty));
+
+        assert_eq!(
+            window("SELECT * FROM t WHERE ts > 'yesterday'", &["ts"]),
+            Err(RangeError::InvalidTimestamp(Value::String("yesterday".into())))
+        );
+
+        // 比较前先转换为时间，不按字符串比较
+        let w = window("SELECT * FROM t WHERE ts >= '2024-1-5' AND ts < '2024-01-10' AND ts > '2024-01-01 0:00'", &["ts"]);
+        assert_eq!(w.unwrap().duration(), Some(days(5)));
+        let w = window("SELECT * FROM t WHERE ts IN ('2024-1-9', '2024-01-10', DATE '2024-01-10')", &["ts"]).unwrap();
+        assert_eq!(w.duration(), Some(days(1)));
+        let w = window("SELECT * FROM t WHERE ts BETWEEN DATE '2024-01-01' AND TIMESTAMP '2024-01-02 12:00:00'", &["ts"]);
+        assert_eq!(w.unwrap().duration(), Some(Duration::from_secs(36 * 3600)));
+    }
+
+    fn window_at(sql: &str, now: i64) -> Result<TimeWindow, RangeError> {
+        time_window_at(&Parser::new_from_sql(sql).parse_select_statement().unwrap(), &["ts"], now)
+    }
+
+    #[test]
+    fn test_relative_time_window() {
+        let now = parse_timestamp("2024-03-31 12:00:00").unwrap();
+        let at = |s: &str| parse_timestamp(s);
+        assert_eq!(
+            window_at("SELECT * FROM t WHERE ts >= NOW() - INTERVAL 7 DAY AND ts <= NOW()", now),
+            Ok(TimeWindow::Window { start: at("2024-03-24 12:00:00"), end: Some(now) })
+        );
+        assert_eq!(
+            window_at("SELECT * FROM t WHERE ts >= CURRENT_DATE - INTERVAL '1' MONTH AND ts < CURDATE() + INTERVAL 1 DAY", now),
+            Ok(TimeWindow::Window { start: at("2024-02-29"), end: at("2024-04-01") })
+        );
+        assert_eq!(
+            window_at("SELECT * FROM t WHERE ts BETWEEN DATE_SUB(NOW(), INTERVAL 1 YEAR) AND INTERVAL 2 HOUR + NOW()", now),
+            Ok(TimeWindow::Window { start: at("2023-03-31 12:00:00"), end: at("2024-03-31 14:00:00") })
+        );
+        assert_eq!(
+            window_at("SELECT * FROM t WHERE ts > DATE '2024-01-31' + INTERVAL 1 QUARTER", now),
+            Ok(TimeWindow::Window { start: at("2024-04-30"), end: None })
+        );
+        assert!(time_window(&Parser::new_from_sql("SELECT * FROM t WHERE ts > NOW()").parse_select_statement().unwrap(), &["ts"])
+            .is_ok());
+
+        // 复合单位和无法计算的表达式
+        assert!(matches!(
+            window_at("SELECT * FROM t WHERE ts > NOW() - INTERVAL '1:30' HOUR_MINUTE", now),
+            Err(RangeError::Unsupported(_))
+        ));
+        assert!(matches!(window_at("SELECT * FROM t WHERE ts > FROM_UNIXTIME(0)", now), Err(RangeError::Unsupported(_))));
+        assert_eq!(
+            window_at("SELECT * FROM t WHERE ts > DATE 'soon' - INTERVAL 1 DAY", now),
+            Err(RangeError::InvalidTimestamp(Value::String("soon".into())))
+        );
+    }
+}
@@ -0,0 +1,1135 @@
+//! 基于AST的语句改写
+//!
+//! 改写结果仍然是AST，通过 Display 重新输出为 SQL。
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::ast::common::{Ident, ObjectName, TableFunction, TableReference};
+use crate::ast::delete::DeleteStatement;
+use crate::ast::expr::{AggregateFunction, BinaryOperator, Expr, LogicalOperator, OrderByExpr, SortKey, Value};
+use crate::ast::insert::InsertStatement;
+use crate::ast::select::{JoinKind, SelectColumn, SelectModifiers, SelectStatement};
+use crate::ast::update::UpdateStatement;
+use crate::ast::SQLStatement;
+use crate::error::ErrorKind;
+use crate::kerwords::{is_keyword, is_type_name, Keyword};
+use crate::parser::select::SelectStatementParser;
+use crate::parser::{Dialect, ParseError, Parser};
+use crate::token::Token;
+#[cfg(feature = "ddl")]
+use crate::kerwords::is_reserved_keyword;
+#[cfg(feature = "ddl")]
+use crate::schema::SchemaProvider;
+
+/// 把多行 INSERT ... VALUES 拆分为若干条语句，每条最多 max_rows 行
+///
+/// 其余部分（修饰符、列名、ON DUPLICATE KEY UPDATE 等）原样复制到每条语句中，
+/// 用于控制单条语句的大小不超过 max_allowed_packet。不是 VALUES 形式、或者行数
+/// 不超过 max_rows 的语句原样返回。
+///
+/// # Panics
+///
+/// max_rows 为 0 时 panic
+pub fn split_insert(stmt: &InsertStatement, max_rows: usize) -> Vec<InsertStatement> {
+    assert!(max_rows > 0, "max_rows must be greater than 0");
+    let Some(rows) = stmt.values.as_ref().filter(|rows| rows.len() > max_rows) else {
+        return vec![stmt.clone()];
+    };
+    rows.chunks(max_rows)
+        .map(|chunk| InsertStatement {
+            values: Some(chunk.to_vec()),
+            ..stmt.clone()
+        })
+        .collect()
+}
+
+/// 把相邻的、可以合并的 INSERT ... VALUES 合并为一条多行 INSERT，与 [`split_insert`] 相反
+///
+/// 表、列名列表、修饰符和 ON DUPLICATE KEY UPDATE 都相同时才能合并。只合并相邻的语句，
+/// 保持原有的执行顺序，适合回放日志时压缩语句数量。其它语句原样保留。
+pub fn merge_inserts(stmts: &[InsertStatement]) -> Vec<InsertStatement> {
+    let mut merged: Vec<InsertStatement> = Vec::new();
+    for stmt in stmts {
+        if let Some(last) = merged.last_mut() {
+            if can_merge(last, stmt) {
+                if let (Some(rows), Some(more)) = (last.values.as_mut(), stmt.values.as_ref()) {
+                    rows.extend(more.iter().cloned());
+                    continue;
+                }
+            }
+        }
+        merged.push(stmt.clone());
+    }
+    merged
+}
+
+// 两条语句除了 VALUES 的行之外完全相同
+fn can_merge(a: &InsertStatement, b: &InsertStatement) -> bool {
+    a.values.is_some()
+        && b.values.is_some()
+        && a.modifiers == b.modifiers
+        && a.table == b.table
+        && a.columns == b.columns
+        && a.row_alias == b.row_alias
+        && a.on_duplicate == b.on_duplicate
+        && a.is_return_count == b.is_return_count
+}
+
+/// 展开 * 时在表结构中找不到的表，或者 t.* 中不属于 FROM 子句的限定符
+#[cfg(feature = "ddl")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownTable(pub ObjectName);
+
+#[cfg(feature = "ddl")]
+impl fmt::Display for UnknownTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Cannot expand wildcard: unknown table '{}'", self.0)
+    }
+}
+
+#[cfg(feature = "ddl")]
+impl Error for UnknownTable {}
+
+/// 按表结构把选择列表中的 * 和 t.* 替换为明确的列，列按表定义的顺序排列
+///
+/// 只有一张表时 * 展开为不带限定符的列名，有连接时每一列都加上表的别名（没有别名时为表名）；
+/// t.* 展开的列使用原来的限定符。保留字列名加反引号。没有 FROM 子句时 * 保持不变，
+/// 子查询中的 * 不会展开。出错时语句不变。
+#[cfg(feature = "ddl")]
+pub fn expand_wildcards<P: SchemaProvider + ?Sized>(select: &mut SelectStatement, schema: &P) -> Result<(), UnknownTable> {
+    let tables: Vec<&TableReference> = select.from.iter().chain(select.joins.iter().map(|join| &join.table)).collect();
+    let columns_of = |table: &TableReference, qualifier: Option<&ObjectName>| -> Result<Vec<SelectColumn>, UnknownTable> {
+        let schema = schema.table(&table.name).ok_or_else(|| UnknownTable(table.name.clone()))?;
+        Ok(schema
+            .columns
+            .iter()
+            .map(|column| {
+                let ident = if is_reserved_keyword(&column.name, Dialect::MySql) {
+                    Ident::with_quote('`', column.name.as_str())
+                } else {
+                    Ident::new(column.name.as_str())
+                };
+                let mut parts = qualifier.map(|name| name.0.clone()).unwrap_or_default();
+                parts.push(ident);
+                SelectColumn::Column { name: ObjectName(parts), alias: None }
+            })
+            .collect())
+    };
+
+    let mut expanded = Vec::with_capacity(select.columns.len());
+    for column in &select.columns {
+        match column {
+            SelectColumn::Wildcard if !tables.is_empty() => {
+                for table in &tables {
+                    let qualifier = match &table.alias {
+                        _ if tables.len() == 1 => None,
+                        Some(alias) => Some(ObjectName::new([alias.as_str()])),
+                        None => Some(table.name.clone()),
+                    };
+                    expanded.extend(columns_of(table, qualifier.as_ref())?);
+                }
+            }
+            SelectColumn::QualifiedWildcard(qualifier) => {
+                // 有别名时只能通过别名引用
+                let table = tables.iter().find(|table| match &table.alias {
+                    Some(alias) => qualifier.0.len() == 1 && qualifier.name().value.eq_ignore_ascii_case(alias),
+                    None => table.name.matches(qualifier),
+                });
+                let table = table.ok_or_else(|| UnknownTable(qualifier.clone()))?;
+                expanded.extend(columns_of(table, Some(qualifier))?);
+            }
+            column => expanded.push(column.clone()),
+        }
+    }
+    select.columns = expanded;
+    Ok(())
+}
+
+/// 无法改写为键集分页的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeysetError {
+    /// 没有 ORDER BY 子句
+    MissingOrderBy,
+    /// 上一页最后一行的值与 ORDER BY 的项数不一致
+    ValueCount { expected: usize, found: usize },
+    /// 不能在 WHERE 中使用的排序项，例如聚合函数或无法解析的列序号
+    UnsupportedSortKey(SortKey),
+    /// 上一页最后一行的值是 NULL，第几项（从0开始）
+    NullValue(usize),
+}
+
+impl fmt::Display for KeysetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeysetError::MissingOrderBy => f.write_str("Keyset pagination requires ORDER BY"),
+            KeysetError::ValueCount { expected, found } => {
+                write!(f, "Expected {} values for ORDER BY, found {}", expected, found)
+            }
+            KeysetError::UnsupportedSortKey(key) => write!(f, "Unsupported sort key for keyset pagination: {:?}", key),
+            KeysetError::NullValue(index) => write!(f, "Value {} of the last row is NULL", index),
+        }
+    }
+}
+
+impl Error for KeysetError {}
+
+/// 把 ORDER BY + LIMIT/OFFSET 的查询改写为键集分页，last_values 为上一页最后一行中各排序项的值
+///
+/// 按排序项的顺序展开为 (a > x) OR (a = x AND b > y) ...，DESC 的项使用 <，与原来的 WHERE 用 AND
+/// 连接，并去掉 OFFSET。列序号和选择列表中的别名替换为对应的表达式。排序项的组合应当唯一，
+/// 否则相同取值的行会被跳过。出错时语句不变。
+pub fn keyset_paginate(select: &mut SelectStatement, last_values: &[Value]) -> Result<(), KeysetError> {
+    let order_by = select.order_by.as_deref().ok_or(KeysetError::MissingOrderBy)?;
+    if order_by.len() != last_values.len() {
+        return Err(KeysetError::ValueCount { expected: order_by.len(), found: last_values.len() });
+    }
+    if let Some(index) = last_values.iter().position(|value| *value == Value::Null) {
+        return Err(KeysetError::NullValue(index));
+    }
+    let keys = order_by
+        .iter()
+        .map(|item| sort_key_expr(select, &item.expr).ok_or_else(|| KeysetError::UnsupportedSortKey(item.expr.clone())))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let compare = |i: usize, op: BinaryOperator| Expr::BinaryOp {
+        left: Box::new(keys[i].clone()),
+        op,
+        right: Box::new(Expr::Literal(last_values[i].clone())),
+    };
+    let branches: Vec<Expr> = (0..keys.len())
+        .map(|i| {
+            let op = if order_by[i].asc { BinaryOperator::Gt } else { BinaryOperator::Lt };
+            let mut terms: Vec<Expr> = (0..i).map(|j| compare(j, BinaryOperator::Eq)).collect();
+            terms.push(compare(i, op));
+            logical(LogicalOperator::And, terms)
+        })
+        .collect();
+    let condition = logical(LogicalOperator::Or, branches);
+
+    select.where_clause = Some(match select.where_clause.take() {
+        Some(Expr::LogicalOp { op: LogicalOperator::And, mut expressions }) => {
+            expressions.push(condition);
+            Expr::LogicalOp { op: LogicalOperator::And, expressions }
+        }
+        Some(existing) => logical(LogicalOperator::And, vec![existing, condition]),
+        None => condition,
+    });
+    if let Some(limit) = &mut select.limit {
+        limit.offset = None;
+    }
+    Ok(())
+}
+
+// 只有一项时不构造 LogicalOp
+fn logical(op: LogicalOperator, mut expressions: Vec<Expr>) -> Expr {
+    if expressions.len() == 1 {
+        expressions.pop().unwrap()
+    } else {
+        Expr::LogicalOp { op, expressions }
+    }
+}
+
+// 排序项在 WHERE 中的等价表达式，含聚合函数时返回None
+fn sort_key_expr(select: &SelectStatement, key: &SortKey) -> Option<Expr> {
+    let column_expr = |column: &SelectColumn| match column {
+        SelectColumn::Column { name, .. } => Some(Expr::Identifier(name.clone())),
+        SelectColumn::Expression { expr, .. } => Some(expr.clone()),
+        _ => None,
+    };
+    let expr = match key {
+        SortKey::Ordinal(ordinal) => column_expr(select.resolve_ordinal(*ordinal)?)?,
+        SortKey::Expr(Expr::Identifier(name)) if name.0.len() == 1 => {
+            // WHERE 中不能引用选择列表的别名
+            let aliased = select.columns.iter().find(|column| match column {
+                SelectColumn::Column { alias: Some(alias), .. } | SelectColumn::Expression { alias: Some(alias), .. } => {
+                    alias.eq_ignore_ascii_case(&name.name().value)
+                }
+                _ => false,
+            });
+            match aliased {
+                Some(column) => column_expr(column)?,
+                None => Expr::Identifier(name.clone()),
+            }
+        }
+        SortKey::Expr(expr) => expr.clone(),
+    };
+    let aggregate = expr.iter().any(|node| matches!(node, Expr::Aggregate { .. } | Expr::GroupConcat(_)));
+    (!aggregate).then_some(expr)
+}
+
+/// 由查询得到统计结果总行数的 COUNT 查询，用于分页时计算总数
+///
+/// 去掉 ORDER BY、LIMIT、TOP、INTO 和加锁子句。没有 GROUP BY、DISTINCT、HAVING、集合运算，选择列表中也没有聚合函数时，
+/// 直接把选择列表替换为 COUNT(*)；否则把查询包装为 SELECT COUNT(*) FROM (...) AS t，
+/// 其中只有 GROUP BY、没有 DISTINCT 和 HAVING 时选择列表替换为 1，避免派生表中出现同名的列；包装时 WITH 子句移到外层查询。
+pub fn to_count(select: &SelectStatement) -> SelectStatement {
+    let count = SelectColumn::Expression {
+        expr: Expr::Aggregate { func: AggregateFunction::Count, distinct: false, args: vec![Expr::Wildcard] },
+        alias: None,
+    };
+    let mut inner = SelectStatement {
+        top: None,
+        order_by: None,
+        limit: None,
+        into: None,
+        lock: None,
+        modifiers: SelectModifiers { straight_join: select.modifiers.straight_join, ..SelectModifiers::default() },
+        ..select.clone()
+    };
+    let aggregate = inner.columns.iter().any(|column| match column {
+        SelectColumn::Expression { expr, .. } => expr.iter().any(Expr::is_aggregate),
+        _ => false,
+    });
+    // 集合运算的各个查询的列数必须相同，不能只替换第一个查询的选择列表
+    let compound = !inner.set_operations.is_empty();
+    if !compound && !inner.distinct && inner.group_by.is_none() && inner.having.is_none() && inner.qualify.is_none() && !aggregate {
+        inner.columns = vec![count];
+        return inner;
+    }
+    if !compound && !inner.distinct && inner.having.is_none() && inner.qualify.is_none() && !aggregate {
+        inner.columns = vec![SelectColumn::Expression { expr: Expr::Literal(Value::Integer(1)), alias: None }];
+    }
+    SelectStatement {
+        with: inner.with.take(),
+        columns: vec![count],
+        distinct: false,
+        distinct_on: None,
+        modifiers: SelectModifiers::default(),
+        top: None,
+        from: Some(TableReference {
+            name: ObjectName::new(["t"]),
+            partitions: None,
+            alias: None,
+            index_hints: Vec::new(),
+            sample: None,
+            subquery: Some(Box::new(inner)),
+            function: None,
+            lateral: false,
+        }),
+        joins: Vec::new(),
+        where_clause: None,
+        group_by: None,
+        with_rollup: false,
+        having: None,
+        qualify: None,
+        windows: Vec::new(),
+        set_operations: Vec::new(),
+        order_by: None,
+        limit: None,
+        into: None,
+        lock: None,
+    }
+}
+
+/// 去掉优化器提示：索引提示、STRAIGHT_JOIN 以及 HIGH_PRIORITY、SQL_NO_CACHE 等 SELECT 修饰符
+///
+/// SQL_CALC_FOUND_ROWS 会改变 FOUND_ROWS() 的结果，予以保留。派生表和表达式中的子查询同样处理。
+/// `/*+ ... */` 形式的提示是注释，解析时已经去掉。
+pub fn strip_hints(select: &mut SelectStatement) {
+    for_each_select(select, &mut |select| {
+        select.modifiers = SelectModifiers {
+            sql_calc_found_rows: select.modifiers.sql_calc_found_rows,
+            ..SelectModifiers::default()
+        };
+        if let Some(from) = &mut select.from {
+            from.index_hints.clear();
+        }
+        for join in &mut select.joins {
+            join.table.index_hints.clear();
+            if join.kind == JoinKind::Straight {
+                join.kind = JoinKind::Inner;
+            }
+        }
+    });
+}
+
+/// 去掉 FOR UPDATE、FOR SHARE、LOCK IN SHARE MODE 加锁子句，包括子查询中的
+pub fn strip_locking(select: &mut SelectStatement) {
+    for_each_select(select, &mut |select| select.lock = None);
+}
+
+/// 得到可以发送到只读副本的查询文本
+///
+/// 去掉 EXPLAIN 包装（EXPLAIN/DESCRIBE/DESC，以及 ANALYZE、FORMAT = JSON、PostgreSQL 的 (ANALYZE, BUFFERS) 等选项），
+/// 注释和 `/*+ ... */` 提示在解析时去掉，再调用 [`strip_hints`] 和 [`strip_locking`]，重新输出为 SQL。
+/// sql 只能包含一条 SELECT 语句。
+pub fn plain_query(sql: &str) -> Result<String, ParseError> {
+    let mut parser = Parser::new_from_sql(sql);
+    skip_explain(&mut parser);
+    if !parser.is_keyword(Keyword::SELECT) {
+        return Err(parser.expected("SELECT"));
+    }
+    let mut select = parser.parse_select_statement()?;
+    parser.expect_statement_end()?;
+    if let Some(token) = parser.skip_empty_statements().then(|| parser.peek().cloned()).flatten() {
+        return Err(parser.error(ErrorKind::TrailingInput { found: token }));
+    }
+    strip_hints(&mut select);
+    strip_locking(&mut select);
+    Ok(select.to_string())
+}
+
+// 跳过 EXPLAIN 及其选项，之后应当是被解释的语句
+fn skip_explain(parser: &mut Parser) {
+    if !parser.match_keyword(Keyword::EXPLAIN) && !parser.match_keyword(Keyword::DESCRIBE) && !parser.match_keyword(Keyword::DESC) {
+        return;
+    }
+    loop {
+        if parser.match_keyword(Keyword::ANALYZE) || parser.match_keyword(Keyword::EXTENDED) || parser.match_keyword(Keyword::PARTITIONS) {
+            continue;
+        }
+        if parser.is_keyword(Keyword::FORMAT) && parser.peek_n(1) == Some(&Token::Operator("=".to_string())) {
+            parser.skip(3);
+        } else if parser.is_punctuator('(') {
+            // PostgreSQL 的选项列表，跳过到匹配的右括号
+            let mut depth = 0;
+            while let Some(token) = parser.consume_token() {
+                match token {
+                    Token::Punctuator('(') => depth += 1,
+                    Token::Punctuator(')') => depth -= 1,
+                    _ => {}
+                }
+                if depth == 0 {
+                    break;
+                }
+            }
+        } else {
+            return;
+        }
+    }
+}
+
+// 对查询本身、CTE、集合运算中的查询、派生表以及表达式中的子查询调用 f
+fn for_each_select(select: &mut SelectStatement, f: &mut dyn FnMut(&mut SelectStatement)) {
+    f(select);
+    for cte in select.with.iter_mut().flat_map(|with| &mut with.ctes) {
+        for_each_select(&mut cte.query, f);
+    }
+    for operation in &mut select.set_operations {
+        for_each_select(&mut operation.select, f);
+    }
+    let tables = select.from.iter_mut().chain(select.joins.iter_mut().map(|join| &mut join.table));
+    for subquery in tables.filter_map(|table| table.subquery.as_mut()) {
+        for_each_select(subquery, f);
+    }
+    for expr in select_exprs(select) {
+        expr.walk_mut(|node| {
+            if let Expr::Subquery(subquery) | Expr::Exists(subquery) = node {
+                for_each_select(subquery, f);
+            }
+        });
+    }
+}
+
+// 查询本身的各个子句中的表达式，不包括派生表和子查询中的
+fn select_exprs(select: &mut SelectStatement) -> impl Iterator<Item = &mut Expr> {
+    let columns = select.columns.iter_mut().filter_map(|column| match column {
+        SelectColumn::Expression { expr, .. } => Some(expr),
+        _ => None,
+    });
+    let keys = select.group_by.iter_mut().flatten().chain(select.order_by.iter_mut().flatten().map(|item| &mut item.expr));
+    let keys = keys.filter_map(|key| match key {
+        SortKey::Expr(expr) => Some(expr),
+        _ => None,
+    });
+    columns
+        .chain(select.distinct_on.iter_mut().flatten())
+        .chain(select.top.iter_mut().map(|top| &mut top.quantity.value))
+        .chain(select.joins.iter_mut().filter_map(|join| join.on.as_mut()))
+        .chain(select.where_clause.iter_mut())
+        .chain(select.having.iter_mut())
+        .chain(select.qualify.iter_mut())
+        .chain(select.windows.iter_mut().flat_map(|(_, spec)| spec.exprs_mut()))
+        .chain(keys)
+}
+
+/// 字面量的脱敏方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum MaskMode {
+    /// 替换为占位符 ?
+    #[default]
+    Placeholder,
+    /// 替换为同类型的哈希值，相同的值得到相同的结果，便于在日志中关联
+    Hash,
+    /// 保留原值
+    Keep,
+}
+
+/// 脱敏策略，可以按列名指定不同的方式
+///
+/// 与字面量比较或赋值的列决定了它使用的方式，例如 `email = 'a@b.c'`、
+/// `INSERT INTO t (email) VALUES ('a@b.c')` 中的字面量都属于 email 列。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MaskPolicy {
+    default: MaskMode,
+    columns: HashMap<String, MaskMode>,
+}
+
+impl MaskPolicy {
+    /// 所有列都使用 default 方式
+    pub fn new(default: MaskMode) -> Self {
+        MaskPolicy { default, columns: HashMap::new() }
+    }
+
+    /// 为某一列指定脱敏方式，列名不区分大小写
+    pub fn with_column(mut self, column: &str, mode: MaskMode) -> Self {
+        self.columns.insert(column.to_lowercase(), mode);
+        self
+    }
+
+    fn mode(&self, column: Option<&str>) -> MaskMode {
+        column
+            .and_then(|column| self.columns.get(&column.to_lowercase()))
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
+/// 可以进行字面量脱敏的语句
+pub trait MaskLiterals {
+    fn mask_literals(&mut self, policy: &MaskPolicy);
+}
+
+/// 按策略替换语句中的字符串和数字字面量，用于记录查询日志时不泄露个人信息
+///
+/// NULL、TRUE/FALSE、DEFAULT 以及 LIMIT 中的数字保持不变。
+pub fn mask_literals<S: MaskLiterals + ?Sized>(stmt: &mut S, policy: &MaskPolicy) {
+    stmt.mask_literals(policy);
+}
+
+impl MaskLiterals for Expr {
+    fn mask_literals(&mut self, policy: &MaskPolicy) {
+        mask_expr(self, None, policy);
+    }
+}
+
+impl MaskLiterals for SelectStatement {
+    fn mask_literals(&mut self, policy: &MaskPolicy) {
+        for cte in self.with.iter_mut().flat_map(|with| &mut with.ctes) {
+            cte.query.mask_literals(policy);
+        }
+        for column in &mut self.columns {
+            if let SelectColumn::Expression { expr, .. } = column {
+                mask_expr(expr, None, policy);
+            }
+        }
+        for expr in self.distinct_on.iter_mut().flatten() {
+            mask_expr(expr, None, policy);
+        }
+        for table in self.from.iter_mut().chain(self.joins.iter_mut().map(|join| &mut join.table)) {
+            if let Some(subquery) = &mut table.subquery {
+                subquery.mask_literals(policy);
+            }
+            if let Some(function) = &mut table.function {
+                match &mut **function {
+                    TableFunction::JsonTable { expr, .. } => mask_expr(expr, None, policy),
+                    TableFunction::Call { args, .. } => args.iter_mut().for_each(|arg| mask_expr(arg, None, policy)),
+                }
+            }
+        }
+        for expr in self.joins.iter_mut().filter_map(|join| join.on.as_mut()) {
+            mask_expr(expr, None, policy);
+        }
+        for expr in self.where_clause.iter_mut().chain(self.having.iter_mut()).chain(self.qualify.iter_mut()) {
+            mask_expr(expr, None, policy);
+        }
+        for expr in self.windows.iter_mut().flat_map(|(_, spec)| spec.exprs_mut()) {
+            mask_expr(expr, None, policy);
+        }
+        for key in self.group_by.iter_mut().flatten() {
+            mask_sort_key(key, policy);
+        }
+        for operation in &mut self.set_operations {
+            operation.select.mask_literals(policy);
+        }
+        mask_order_by(&mut self.order_by, policy);
+    }
+}
+
+impl MaskLiterals for InsertStatement {
+    fn mask_literals(&mut self, policy: &MaskPolicy) {
+        for row in self.values.iter_mut().flatten() {
+            for (i, cell) in row.iter_mut().enumerate() {
+                // 按位置对应到列名，没有列名列表时使用默认方式
+                let column = self.columns.as_ref().and_then(|columns| columns.get(i));
+                mask_expr(cell, column.map(String::as_str), policy);
+            }
+        }
+        if let Some(select) = &mut self.select_clause {
+            select.mask_literals(policy);
+        }
+        mask_assignments(self.set_clause.iter_mut().flatten(), policy);
+        mask_assignments(self.on_duplicate.iter_mut().flat_map(|clause| &mut clause.updates), policy);
+    }
+}
+
+impl MaskLiterals for UpdateStatement {
+    fn mask_literals(&mut self, policy: &MaskPolicy) {
+        mask_assignments(&mut self.assignments, policy);
+        if let Some(expr) = &mut self.where_clause {
+            mask_expr(expr, None, policy);
+        }
+        mask_order_by(&mut self.order_by, policy);
+    }
+}
+
+impl MaskLiterals for DeleteStatement {
+    fn mask_literals(&mut self, policy: &MaskPolicy) {
+        if let Some(expr) = &mut self.where_clause {
+            mask_expr(expr, None, policy);
+        }
+        mask_order_by(&mut self.order_by, policy);
+    }
+}
+
+impl MaskLiterals for SQLStatement {
+    fn mask_literals(&mut self, policy: &MaskPolicy) {
+        match self {
+            SQLStatement::Select(select) => select.mask_literals(policy),
+            SQLStatement::Insert(insert) => insert.mask_literals(policy),
+            SQLStatement::Update(update) => update.mask_literals(policy),
+            SQLStatement::Delete(delete) => delete.mask_literals(policy),
+            // DDL 和 SHOW 中的字面量不含业务数据
+            _ => {}
+        }
+    }
+}
+
+fn mask_assignments<'a>(assignments: impl IntoIterator<Item = &'a mut (ObjectName, Expr)>, policy: &MaskPolicy) {
+    for (column, value) in assignments {
+        mask_expr(value, Some(&column.name().value), policy);
+    }
+}
+
+fn mask_order_by(order_by: &mut Option<Vec<OrderByExpr>>, policy: &MaskPolicy) {
+    for item in order_by.iter_mut().flatten() {
+        mask_sort_key(&mut item.expr, policy);
+    }
+}
+
+// 列序号不是数据，保持不变
+fn mask_sort_key(key: &mut SortKey, policy: &MaskPolicy) {
+    if let SortKey::Expr(expr) = key {
+        mask_expr(expr, None, policy);
+    }
+}
+
+// 列名作为比较另一侧字面量的上下文
+fn column_of(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Identifier(name) => Some(&name.name().value),
+        _ => None,
+    }
+}
+
+// column 为字面量所属的列，比较运算会用另一侧的列名替换它，其它子表达式沿用
+// 使用显式的栈遍历，很深的表达式也不会耗尽栈空间
+fn mask_expr(expr: &mut Expr, column: Option<&str>, policy: &MaskPolicy) {
+    let mut stack = vec![(expr, column.map(str::to_string))];
+    while let Some((expr, column)) = stack.pop() {
+        match expr {
+            Expr::Literal(value) => {
+                if let Some(masked) = mask_value(value, policy.mode(column.as_deref())) {
+                    *expr = masked;
+                }
+            }
+            Expr::Subquery(select) | Expr::Exists(select) => select.mask_literals(policy),
+            _ => {
+                let columns = child_columns(expr, column);
+                stack.extend(expr.children_mut().into_iter().zip(columns));
+            }
+        }
+    }
+}
+
+// 每个直接子表达式所属的列，顺序与 Expr::children 一致
+pub(crate) fn child_columns(expr: &Expr, column: Option<String>) -> Vec<Option<String>> {
+    let other = |e: &Expr| column_of(e).map(str::to_string).or_else(|| column.clone());
+    match expr {
+        Expr::BinaryOp { left, op, right } => {
+            let comparison = !matches!(
+                op,
+                BinaryOperator::Plus | BinaryOperator::Minus | BinaryOperator::Multiply | BinaryOperator::Divide
+            );
+            if comparison {
+                vec![other(right), other(left)]
+            } else {
+                vec![column.clone(), column]
+            }
+        }
+        Expr::In { expr, list, .. } => {
+            let mut columns = vec![other(expr); list.len() + 1];
+            columns[0] = column;
+            columns
+        }
+        Expr::Between { expr, .. } => vec![column.clone(), other(expr), other(expr)],
+        // 'beta' = ANY(flags)、id = ANY(ARRAY[1, 2]) 两侧互相对应
+        Expr::Any { expr, array, .. } => vec![other(array), other(expr)],
+        _ => vec![column; expr.children().len()],
+    }
+}
+
+// 返回替换后的表达式，不需要替换时返回None
+fn mask_value(value: &Value, mode: MaskMode) -> Option<Expr> {
+    let text = match value {
+        Value::String(s) => s.clone(),
+        Value::Integer(i) => i.to_string(),
+        Value::Float(f) => f.to_string(),
+        Value::Decimal(d) => d.clone(),
+        Value::Boolean(_) | Value::Null | Value::DEFAULT => return None,
+    };
+    match mode {
+        MaskMode::Keep => None,
+        // 与解析 ? 占位符得到的表达式一致
+        MaskMode::Placeholder => Some(Expr::Identifier(ObjectName::new(["?"]))),
+        MaskMode::Hash => {
+            let hash = fnv1a(text.as_bytes());
+            Some(Expr::Literal(match value {
+                Value::String(_) => Value::String(format!("{:016x}", hash)),
+                _ => Value::Integer((hash >> 1) as i64),
+            }))
+        }
+    }
+}
+
+// FNV-1a 64 位哈希，结果不随 Rust 版本变化
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x100000001b3))
+}
+
+/// 可以规范化标识符引号的语句
+pub trait NormalizeQuotes {
+    fn normalize_quotes(&mut self, dialect: Dialect);
+}
+
+/// 按方言统一标识符的引号，得到与书写风格无关的AST，再通过 Display 输出
+///
+/// 加了引号的标识符在去掉引号后含义不变时去掉引号，即只含字母、数字和下划线，不以数字开头，
+/// 也不是关键字或类型名；否则改用方言的引号，MySQL 和 BigQuery 为反引号，其它方言为双引号。
+/// PostgreSQL 会把未加引号的标识符转为小写，Snowflake 转为大写，含有另一种大小写字母时保留引号。
+/// 未加引号的标识符保持不变，别名等以字符串保存的名称不受影响，DDL 和 SHOW 语句保持不变。
+///
+/// 大小写的规范化在解析时进行，见 [`ParserOptions::normalize_case`](crate::ParserOptions::normalize_case)。
+pub fn normalize_quotes<S: NormalizeQuotes + ?Sized>(stmt: &mut S, dialect: Dialect) {
+    stmt.normalize_quotes(dialect);
+}
+
+impl NormalizeQuotes for Expr {
+    fn normalize_quotes(&mut self, dialect: Dialect) {
+        self.walk_mut(|node| match node {
+            Expr::Identifier(name) => normalize_name(name, dialect),
+            Expr::Match { columns, .. } => columns.iter_mut().for_each(|name| normalize_name(name, dialect)),
+            Expr::Subquery(select) | Expr::Exists(select) => select.normalize_quotes(dialect),
+            _ => {}
+        });
+    }
+}
+
+impl NormalizeQuotes for SelectStatement {
+    fn normalize_quotes(&mut self, dialect: Dialect) {
+        for column in &mut self.columns {
+            if let SelectColumn::QualifiedWildcard(name) | SelectColumn::Column { name, .. } = column {
+                normalize_name(name, dialect);
+            }
+        }
+        for table in self.from.iter_mut().chain(self.joins.iter_mut().map(|join| &mut join.table)) {
+            table.normalize_quotes(dialect);
+        }
+        for expr in select_exprs(self) {
+            expr.normalize_quotes(dialect);
+        }
+        for cte in self.with.iter_mut().flat_map(|with| &mut with.ctes) {
+            cte.query.normalize_quotes(dialect);
+        }
+        for operation in &mut self.set_operations {
+            operation.select.normalize_quotes(dialect);
+        }
+    }
+}
+
+impl NormalizeQuotes for TableReference {
+    fn normalize_quotes(&mut self, dialect: Dialect) {
+        normalize_name(&mut self.name, dialect);
+        if let Some(subquery) = &mut self.subquery {
+            subquery.normalize_quotes(dialect);
+        }
+        if let Some(function) = &mut self.function {
+            match &mut **function {
+                TableFunction::JsonTable { expr, .. } => expr.normalize_quotes(dialect),
+                TableFunction::Call { args, .. } => args.iter_mut().for_each(|arg| arg.normalize_quotes(dialect)),
+            }
+        }
+    }
+}
+
+impl NormalizeQuotes for InsertStatement {
+    fn normalize_quotes(&mut self, dialect: Dialect) {
+        self.table.normalize_quotes(dialect);
+        for expr in self.values.iter_mut().flatten().flatten() {
+            expr.normalize_quotes(dialect);
+        }
+        if let Some(select) = &mut self.select_clause {
+            select.normalize_quotes(dialect);
+        }
+        let assignments = self.set_clause.iter_mut().flatten();
+        normalize_assignments(assignments.chain(self.on_duplicate.iter_mut().flat_map(|clause| &mut clause.updates)), dialect);
+    }
+}
+
+impl NormalizeQuotes for UpdateStatement {
+    fn normalize_quotes(&mut self, dialect: Dialect) {
+        self.table.normalize_quotes(dialect);
+        normalize_assignments(&mut self.assignments, dialect);
+        for expr in self.where_clause.iter_mut().chain(sort_exprs(&mut self.order_by)) {
+            expr.normalize_quotes(dialect);
+        }
+    }
+}
+
+impl NormalizeQuotes for DeleteStatement {
+    fn normalize_quotes(&mut self, dialect: Dialect) {
+        self.table.normalize_quotes(dialect);
+        for expr in self.where_clause.iter_mut().chain(sort_exprs(&mut self.order_by)) {
+            expr.normalize_quotes(dialect);
+        }
+    }
+}
+
+impl NormalizeQuotes for SQLStatement {
+    fn normalize_quotes(&mut self, dialect: Dialect) {
+        match self {
+            SQLStatement::Select(select) => select.normalize_quotes(dialect),
+            SQLStatement::Insert(insert) => insert.normalize_quotes(dialect),
+            SQLStatement::Update(update) => update.normalize_quotes(dialect),
+            SQLStatement::Delete(delete) => delete.normalize_quotes(dialect),
+            _ => {}
+        }
+    }
+}
+
+fn normalize_assignments<'a>(assignments: impl IntoIterator<Item = &'a mut (ObjectName, Expr)>, dialect: Dialect) {
+    for (column, value) in assignments {
+        normalize_name(column, dialect);
+        value.normalize_quotes(dialect);
+    }
+}
+
+fn sort_exprs(order_by: &mut Option<Vec<OrderByExpr>>) -> impl Iterator<Item = &mut Expr> {
+    order_by.iter_mut().flatten().filter_map(|item| match &mut item.expr {
+        SortKey::Expr(expr) => Some(expr),
+        _ => None,
+    })
+}
+
+fn normalize_name(name: &mut ObjectName, dialect: Dialect) {
+    for ident in &mut name.0 {
+        normalize_ident(ident, dialect);
+    }
+}
+
+fn normalize_ident(ident: &mut Ident, dialect: Dialect) {
+    if ident.quote_style.is_none() {
+        return;
+    }
+    let value: &str = &ident.value;
+    let plain = value.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !is_keyword(value)
+        && !is_type_name(value)
+        && match dialect {
+            Dialect::Postgres => !value.chars().any(|c| c.is_ascii_uppercase()),
+            Dialect::Snowflake => !value.chars().any(|c| c.is_ascii_lowercase()),
+            _ => true,
+        };
+    ident.quote_style = match dialect {
+        _ if plain => None,
+        Dialect::MySql | Dialect::BigQuery => Some('`'),
+        _ => Some('"'),
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::insert::InsertStatementParser;
+    use crate::parser::select::SelectStatementParser;
+    use crate::parser::{Parser, StatementParser};
+
+    fn parse(sql: &str) -> InsertStatement {
+        let mut parser = Parser::new_from_sql(sql);
+        let result = parser.parse_insert_statement();
+        assert!(result.is_ok(), "解析失败: {:?}", result.err());
+        result.unwrap()
+    }
+
+    #[test]
+    fn test_split_insert() {
+        let stmt = parse(
+            "INSERT IGNORE INTO users (id, name, score) VALUES (1, 'a', -(2 - 1)), (2, 'O''Brien', DEFAULT), \
+             (3, NULL, price * (1 + 0.5)), (4, CONCAT('x', 'y'), 2.0), (5, 'e', 5) ON DUPLICATE KEY UPDATE score = score + 1",
+        );
+        let parts = split_insert(&stmt, 2);
+        let sql: Vec<String> = parts.iter().map(|part| part.to_string()).collect();
+        assert_eq!(
+            sql,
+            [
+                "INSERT IGNORE INTO users (id, name, score) VALUES (1, 'a', -(2 - 1)), (2, 'O''Brien', DEFAULT) \
+                 ON DUPLICATE KEY UPDATE score = score + 1",
+                "INSERT IGNORE INTO users (id, name, score) VALUES (3, NULL, price * (1 + 0.5)), (4, CONCAT('x', 'y'), 2.0) \
+                 ON DUPLICATE KEY UPDATE score = score + 1",
+                "INSERT IGNORE INTO users (id, name, score) VALUES (5, 'e', 5) ON DUPLICATE KEY UPDATE score = score + 1",
+            ]
+        );
+        // 重新输出的 SQL 解析后与拆分结果一致
+        for (part, sql) in parts.iter().zip(&sql) {
+            assert_eq!(&parse(sql), part);
+        }
+
+        // 行数未超过上限或不是 VALUES 形式时原样返回
+        assert_eq!(split_insert(&stmt, 5), vec![stmt.clone()]);
+        let stmt = parse("INSERT INTO users SET id = 1, name = 'a'");
+        assert_eq!(split_insert(&stmt, 1), vec![stmt.clone()]);
+        assert_eq!(stmt.to_string(), "INSERT INTO users SET id = 1, name = 'a'");
+    }
+
+    #[test]
+    fn test_merge_inserts() {
+        let stmts: Vec<InsertStatement> = [
+            "INSERT INTO users (id, name) VALUES (1, 'a')",
+            "INSERT INTO users (id, name) VALUES (2, 'b'), (3, 'c')",
+            "INSERT INTO users (id, name) VALUES (4, NOW())",
+            // 列名不同，不能合并
+            "INSERT INTO users (name, id) VALUES ('e', 5)",
+            // 修饰符不同
+            "INSERT IGNORE INTO users (name, id) VALUES ('f', 6)",
+            "INSERT INTO users SET id = 7",
+            "INSERT INTO users (name, id) VALUES ('g', 8)",
+            "INSERT INTO users (name, id) VALUES ('h', 9)",
+        ]
+        .iter()
+        .map(|sql| parse(sql))
+        .collect();
+
+        let merged: Vec<String> = merge_inserts(&stmts).iter().map(|stmt| stmt.to_string()).collect();
+        assert_eq!(
+            merged,
+            [
+                "INSERT INTO users (id, name) VALUES (1, 'a'), (2, 'b'), (3, 'c'), (4, NOW())",
+                "INSERT INTO users (name, id) VALUES ('e', 5)",
+                "INSERT IGNORE INTO users (name, id) VALUES ('f', 6)",
+                "INSERT INTO users SET id = 7",
+                "INSERT INTO users (name, id) VALUES ('g', 8), ('h', 9)",
+            ]
+        );
+
+        // 合并后再拆分得到原来的行
+        let merged = merge_inserts(&stmts[..3]);
+        let rows: Vec<_> = split_insert(&merged[0], 1).into_iter().flat_map(|stmt| stmt.values.unwrap()).collect();
+        assert_eq!(rows, stmts[..3].iter().flat_map(|stmt| stmt.values.clone().unwrap()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_mask_literals() {
+        let policy = MaskPolicy::new(MaskMode::Placeholder)
+            .with_column("email", MaskMode::Hash)
+            .with_column("status", MaskMode::Keep);
+
+        let mut parser = Parser::new_from_sql(
+            "SELECT id, LENGTH(name) + 1 FROM users WHERE email = 'a@b.c' AND status = 'active' \
+             AND age > 30 OR 'a@b.c' = Email ORDER BY 1",
+        );
+        let mut select = parser.parse_select_statement().unwrap();
+        mask_literals(&mut select, &policy);
+        let hash = format!("{:016x}", fnv1a(b"a@b.c"));
+        assert_eq!(
+            select.to_string(),
+            format!(
+                "SELECT id, LENGTH(name) + ? FROM users WHERE email = '{hash}' AND status = 'active' \
+                 AND age > ? OR '{hash}' = Email ORDER BY 1"
+            )
+        );
+
+        // VALUES 按位置对应列名，ON DUPLICATE KEY UPDATE 按赋值的列
+        let mut insert = parse(
+            "INSERT INTO users (id, email, status) VALUES (1, 'x@y.z', 'new'), (2, NULL, DEFAULT) \
+             ON DUPLICATE KEY UPDATE email = 'x@y.z'",
+        );
+        mask_literals(&mut insert, &policy);
+        let hash = format!("{:016x}", fnv1a(b"x@y.z"));
+        assert_eq!(
+            insert.to_string(),
+            format!(
+                "INSERT INTO users (id, email, status) VALUES (?, '{hash}', 'new'), (?, NULL, DEFAULT) \
+                 ON DUPLICATE KEY UPDATE email = '{hash}'"
+            )
+        );
+
+        // 数字哈希后仍是数字
+        let mut expr = Parser::new_from_sql("42 = email").parse_expr(0).unwrap();
+        mask_literals(&mut expr, &policy);
+        let Expr::BinaryOp { left, .. } = &expr else { panic!("Expected comparison") };
+        assert_eq!(**left, Expr::Literal(Value::Integer((fnv1a(b"42") >> 1) as i64)));
+    }
+
+    #[test]
+    fn test_keyset_paginate() {
+        let paginate = |sql: &str, values: &[Value]| {
+            let mut select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+            keyset_paginate(&mut select, values).map(|_| select.to_string())
+        };
+        let int = Value::Integer;
+        assert_eq!(
+            paginate("SELECT id, name FROM users ORDER BY id LIMIT 20 OFFSET 40", &[int(42)]).unwrap(),
+            "SELECT id, name FROM users WHERE id > 42 ORDER BY id LIMIT 20"
+        );
+        // 多个排序项、DESC、别名、列序号，原来的 WHERE 用 AND 连接
+        assert_eq!(
+            paginate(
+                "SELECT created_at AS c, score + 1, id FROM t WHERE a = 1 OR b = 2 ORDER BY c DESC, 2, t.id LIMIT 10, 10",
+                &[Value::String("2024-01-01".into()), int(-3), int(7)]
+            )
+            .unwrap(),
+            "SELECT created_at AS c, score + 1, id FROM t WHERE (a = 1 OR b = 2) AND (created_at < '2024-01-01' \
+             OR created_at = '2024-01-01' AND score + 1 > -3 OR created_at = '2024-01-01' AND score + 1 = -3 AND t.id > 7) \
+             ORDER BY c DESC, 2, t.id LIMIT 10"
+        );
+        // 改写结果可以重新解析
+        let sql = paginate("SELECT * FROM t WHERE x = 1 AND y = 2 ORDER BY a, b DESC", &[int(1), int(2)]).unwrap();
+        assert_eq!(sql, "SELECT * FROM t WHERE x = 1 AND y = 2 AND (a > 1 OR a = 1 AND b < 2) ORDER BY a, b DESC");
+        assert!(Parser::new_from_sql(&sql).parse_select_statement().is_ok());
+
+        assert_eq!(paginate("SELECT * FROM t LIMIT 10", &[]), Err(KeysetError::MissingOrderBy));
+        assert_eq!(
+            paginate("SELECT * FROM t ORDER BY a", &[int(1), int(2)]),
+            Err(KeysetError::ValueCount { expected: 1, found: 2 })
+        );
+        assert_eq!(paginate("SELECT * FROM t ORDER BY a, b", &[int(1), Value::Null]), Err(KeysetError::NullValue(1)));
+        assert_eq!(paginate("SELECT * FROM t ORDER BY 1", &[int(1)]), Err(KeysetError::UnsupportedSortKey(SortKey::Ordinal(1))));
+        assert!(matches!(
+            paginate("SELECT a, COUNT(*) AS n FROM t GROUP BY a ORDER BY n", &[int(1)]),
+            Err(KeysetError::UnsupportedSortKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_to_count() {
+        let count = |sql: &str| {
+            let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+            let count = to_count(&select).to_string();
+            // 结果可以重新解析
+            assert_eq!(Parser::new_from_sql(&count).parse_select_statement().unwrap().to_string(), count);
+            count
+        };
+        assert_eq!(
+            count("SELECT SQL_CALC_FOUND_ROWS u.id, u.name FROM users u JOIN orders o ON u.id = o.user_id WHERE o.paid = 1 ORDER BY u.id LIMIT 10, 20"),
+            "SELECT COUNT(*) FROM users AS u JOIN orders AS o ON u.id = o.user_id WHERE o.paid = 1"
+        );
+        assert_eq!(
+            count("SELECT status, COUNT(*) FROM orders GROUP BY status ORDER BY 2 DESC LIMIT 5"),
+            "SELECT COUNT(*) FROM (SELECT status, COUNT(*) FROM orders GROUP BY status) AS t"
+        );
+        assert_eq!(
+            count("SELECT user_id FROM orders GROUP BY user_id FOR UPDATE"),
+            "SELECT COUNT(*) FROM (SELECT 1 FROM orders GROUP BY user_id) AS t"
+        );
+        assert_eq!(
+            count("SELECT DISTINCT city FROM users WHERE age > 18"),
+            "SELECT COUNT(*) FROM (SELECT DISTINCT city FROM users WHERE age > 18) AS t"
+        );
+        assert_eq!(
+            count("SELECT user_id, SUM(total) AS s FROM orders GROUP BY user_id HAVING s > 100"),
+            "SELECT COUNT(*) FROM (SELECT user_id, SUM(total) AS s FROM orders GROUP BY user_id HAVING s > 100) AS t"
+        );
+        // 子查询中的聚合函数不影响外层
+        assert_eq!(
+            count("SELECT id, (SELECT MAX(total) FROM orders) FROM users"),
+            "SELECT COUNT(*) FROM users"
+        );
+    }
+
+    #[test]
+    fn test_plain_query() {
+        assert_eq!(
+            plain_query(
+                "EXPLAIN FORMAT=JSON SELECT /*+ MAX_EXECUTION_TIME(1000) */ HIGH_PRIORITY SQL_NO_CACHE id \
+                 FROM users FORCE INDEX (idx_name) STRAIGHT_JOIN orders USE INDEX (PRIMARY) ON users.id = orders.user_id \
+                 WHERE id IN (SELECT user_id FROM vip FOR UPDATE) FOR UPDATE NOWAIT; -- trailing comment"
+            )
+            .unwrap(),
+            "SELECT id FROM users JOIN orders ON users.id = orders.user_id WHERE id IN (SELECT user_id FROM vip)"
+        );
+        assert_eq!(
+            plain_query("EXPLAIN (ANALYZE, FORMAT JSON) SELECT SQL_CALC_FOUND_ROWS * FROM (SELECT a FROM t LOCK IN SHARE MODE) x").unwrap(),
+            "SELECT SQL_CALC_FOUND_ROWS * FROM (SELECT a FROM t) AS x"
+        );
+        assert_eq!(plain_query("DESC SELECT 1").unwrap(), "SELECT 1");
+        assert_eq!(plain_query("EXPLAIN ANALYZE SELECT a FROM t").unwrap(), "SELECT a FROM t");
+
+        // 只接受一条 SELECT 语句
+        assert!(plain_query("EXPLAIN DELETE FROM t").is_err());
+        assert!(plain_query("DESC users").is_err());
+        assert!(plain_query("SELECT 1; SELECT 2").is_err());
+        assert!(plain_query("SELECT 1;").is_ok());
+    }
+
+    #[cfg(feature = "ddl")]
+    #[test]
+    fn test_expand_wildcards() {
+        let catalog = crate::schema::Catalog::from_script(
+            "CREATE TABLE users (id INT, name VARCHAR(20), `order` INT); CREATE TABLE orders (id INT, user_id INT)",
+        )
+        .unwrap();
+        let expand = |sql: &str| {
+            let mut select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+            expand_wildcards(&mut select, &catalog).map(|_| select.to_string())
+        };
+        assert_eq!(expand("SELECT * FROM users WHERE id = 1").unwrap(), "SELECT id, name, `order` FROM users WHERE id = 1");
+        assert_eq!(
+            expand("SELECT * FROM users u JOIN orders ON u.id = orders.user_id").unwrap(),
+            "SELECT u.id, u.name, u.`order`, orders.id, orders.user_id FROM users AS u JOIN orders ON u.id = orders.user_id"
+        );
+        assert_eq!(
+            expand("SELECT o.*, u.name FROM orders o JOIN users u ON u.id = o.user_id").unwrap(),
+            "SELECT o.id, o.user_id, u.name FROM orders AS o JOIN users AS u ON u.id = o.user_id"
+        );
+        assert_eq!(expand("SELECT 1").unwrap(), "SELECT 1");
+
+        assert_eq!(expand("SELECT * FROM logs"), Err(UnknownTable(ObjectName::new(["logs"]))));
+        // 有别名时不能再用表名限定
+        assert_eq!(expand("SELECT users.* FROM users u"), Err(UnknownTable(ObjectName::new(["users"]))));
+    }
+
+    #[test]
+    fn test_normalize_quotes() {
+        let normalize = |sql: &str, dialect| {
+            let mut select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+            normalize_quotes(&mut select, dialect);
+            select.to_string()
+        };
+        let sql = "SELECT `Id`, `order`, `my col`, t.`Name` FROM `mydb`.`Users` AS t \
+                   WHERE `Id` IN (SELECT `user_id` FROM `orders`) ORDER BY `date`";
+        assert_eq!(
+            normalize(sql, Dialect::MySql),
+            "SELECT Id, `order`, `my col`, t.Name FROM mydb.Users AS t WHERE Id IN (SELECT user_id FROM orders) ORDER BY `date`"
+        );
+        // PostgreSQL 中含大写字母的标识符去掉引号后会转为小写，保留引号
+        assert_eq!(
+            normalize(sql, Dialect::Postgres),
+            "SELECT \"Id\", \"order\", \"my col\", t.\"Name\" FROM mydb.\"Users\" AS t \
+             WHERE \"Id\" IN (SELECT user_id FROM orders) ORDER BY \"date\""
+        );
+        // 未加引号的标识符保持不变
+        assert_eq!(normalize("SELECT Id FROM `Users`", Dialect::Postgres), "SELECT Id FROM \"Users\"");
+        // 书写风格不同的语句得到相同的AST
+        let parse = |sql: &str| {
+            let mut stmt = Parser::new_from_sql(sql).parse().unwrap();
+            normalize_quotes(&mut stmt, Dialect::MySql);
+            stmt
+        };
+        assert_eq!(parse("DELETE FROM `logs` WHERE `ts` < 1"), parse("DELETE FROM logs WHERE ts < 1"));
+        assert_eq!(parse("INSERT INTO `t` SET `a` = `b` + 1"), parse("INSERT INTO t SET a = b + 1"));
+        assert_eq!(parse("UPDATE Users SET `Name` = 'a' WHERE `Id` = 1"), parse("UPDATE Users SET Name = 'a' WHERE Id = 1"));
+    }
+}
@@ -0,0 +1,739 @@
+//! 由 CREATE TABLE 语句构建的表结构目录
+//!
+//! [`Catalog::from_script`] 读取 mysqldump 之类的建表脚本，依次应用其中的 CREATE TABLE 和 ALTER TABLE，
+//! 得到每张表的列、类型、主键、唯一键、外键和索引。脚本中的其它语句（DROP、INSERT、SET 等）被跳过。
+//! 表名和列名的比较不区分大小写。
+
+use crate::ast::alter::{AlterStatement, AlterTableOperation, AlterTableStatement};
+use crate::ast::common::{DataType, ObjectName};
+use crate::ast::create::{
+    ColumnDef, ColumnOption, ConstraintKind, CreateStatement, CreateTableStatement, ForeignKeyReference, IndexDef,
+    TableConstraint,
+};
+use crate::ast::expr::Expr;
+use crate::ast::SQLStatement;
+use crate::kerwords::Keyword;
+use crate::parser::{ParseError, Parser, StatementParser};
+use crate::token::Token;
+use std::error::Error;
+use std::fmt;
+
+/// 按表名查找表结构，供 [`crate::validate::check_schema`] 等检查使用
+pub trait SchemaProvider {
+    /// 查找表，users 匹配任意库中的 users 表，mydb.users 还匹配没有指定库名的 users 表
+    fn table(&self, name: &ObjectName) -> Option<&TableSchema>;
+}
+
+/// 表结构目录，按定义顺序保存各表
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Catalog {
+    tables: Vec<TableSchema>,
+}
+
+/// 表结构
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableSchema {
+    pub name: ObjectName,
+    pub columns: Vec<ColumnSchema>,
+    /// 主键列，没有主键时为空
+    pub primary_key: Vec<String>,
+    /// 唯一键，包括列定义中的 UNIQUE
+    pub unique_keys: Vec<IndexDef>,
+    pub foreign_keys: Vec<ForeignKey>,
+    /// 普通索引
+    ///
+    /// 未命名的索引和外键按 MySQL 的规则命名：索引取第一列的列名（重名时加 _2、_3 后缀），
+    /// 外键取 表名_ibfk_序号，因此索引名总是 Some。
+    pub indexes: Vec<IndexDef>,
+}
+
+/// 列结构
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnSchema {
+    pub name: String,
+    pub data_type: DataType,
+    /// 没有 NOT NULL 且不是主键列时可以为 NULL
+    pub nullable: bool,
+    pub default: Option<Expr>,
+    pub auto_increment: bool,
+}
+
+/// 外键，包括列定义中的 REFERENCES
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignKey {
+    /// CONSTRAINT 后指定的约束名，未指定时为生成的名字
+    pub name: String,
+    pub columns: Vec<String>,
+    pub reference: ForeignKeyReference,
+}
+
+impl Catalog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 解析建表脚本并构建目录
+    ///
+    /// 只解析 CREATE TABLE 和 ALTER TABLE 语句，其它语句跳到下一个 ';' 为止；
+    /// 重复创建的表以后一次为准，除非使用了 IF NOT EXISTS。
+    pub fn from_script(sql: &str) -> Result<Self, ParseError> {
+        let mut catalog = Catalog::new();
+        let mut parser = Parser::new_from_sql(sql);
+        while parser.skip_empty_statements() {
+            let is_table = matches!(parser.peek(), Some(Token::Keyword { keyword: Keyword::CREATE | Keyword::ALTER, .. }))
+                && matches!(parser.peek_n(1), Some(Token::Keyword { keyword: Keyword::TABLE, .. }));
+            if is_table {
+                catalog.apply(&parser.parse()?);
+            } else {
+                while parser.has_more() && !parser.match_punctuator(';') {
+                    parser.skip(1);
+                }
+            }
+        }
+        Ok(catalog)
+    }
+
+    /// 应用一条语句，只有 CREATE TABLE 和 ALTER TABLE 会修改目录
+    pub fn apply(&mut self, stmt: &SQLStatement) {
+        match stmt {
+            SQLStatement::Create(CreateStatement::Table(create)) => self.create_table(create),
+            SQLStatement::Alter(AlterStatement::Table(alter)) => self.alter_table(alter),
+            _ => {}
+        }
+    }
+
+    /// 按定义顺序返回所有表
+    pub fn tables(&self) -> &[TableSchema] {
+        &self.tables
+    }
+
+    fn position(&self, name: &ObjectName) -> Option<usize> {
+        self.tables.iter().position(|table| table.name.matches(name))
+    }
+
+    fn create_table(&mut self, create: &CreateTableStatement) {
+        let table = TableSchema::from_create(create);
+        match self.position(&create.name) {
+            Some(_) if create.if_not_exists => {}
+            Some(i) => self.tables[i] = table,
+            None => self.tables.push(table),
+        }
+    }
+
+    // 修改不存在的表时忽略
+    fn alter_table(&mut self, alter: &AlterTableStatement) {
+        let Some(i) = self.position(&alter.name) else {
+            return;
+        };
+        let table = &mut self.tables[i];
+        for operation in &alter.operations {
+            match operation {
+                AlterTableOperation::AddColumn(def) => table.add_column(def),
+                AlterTableOperation::ModifyColumn(def) => {
+                    let column = ColumnSchema::from_def(def);
+                    match table.columns.iter_mut().find(|c| c.name.eq_ignore_ascii_case(&def.name)) {
+                        Some(existing) => *existing = column,
+                        None => table.columns.push(column),
+                    }
+                    table.apply_column_options(def);
+                }
+                AlterTableOperation::AddConstraint(constraint) => table.add_constraint(constraint),
+                AlterTableOperation::AddIndex(index) => table.add_index(index.name.clone(), &index.columns, false),
+                AlterTableOperation::DropColumn(name) => table.columns.retain(|c| !c.name.eq_ignore_ascii_case(name)),
+                AlterTableOperation::DropPrimaryKey => table.primary_key.clear(),
+                AlterTableOperation::DropForeignKey(name) => table.drop_foreign_key(name),
+                AlterTableOperation::DropIndex(name) => table.drop_index(name),
+                AlterTableOperation::DropConstraint(name) => {
+                    table.drop_foreign_key(name);
+                    table.drop_index(name);
+                }
+                // CHECK 约束不记录在目录中
+                AlterTableOperation::DropCheck(_) => {}
+            }
+        }
+    }
+}
+
+impl SchemaProvider for Catalog {
+    fn table(&self, name: &ObjectName) -> Option<&TableSchema> {
+        self.position(name).map(|i| &self.tables[i])
+    }
+}
+
+fn same_name(a: &Option<String>, name: &str) -> bool {
+    a.as_deref().is_some_and(|a| a.eq_ignore_ascii_case(name))
+}
+
+impl TableSchema {
+    fn from_create(create: &CreateTableStatement) -> Self {
+        let mut table = TableSchema {
+            name: create.name.clone(),
+            columns: Vec::new(),
+            primary_key: Vec::new(),
+            unique_keys: Vec::new(),
+            foreign_keys: Vec::new(),
+            indexes: Vec::new(),
+        };
+        create.columns.iter().for_each(|def| table.add_column(def));
+        create.constraints.iter().for_each(|constraint| table.add_constraint(constraint));
+        create.indexes.iter().for_each(|index| table.add_index(index.name.clone(), &index.columns, false));
+        table
+    }
+
+    /// 按列名查找列
+    pub fn column(&self, name: &str) -> Option<&ColumnSchema> {
+        self.columns.iter().find(|column| column.name.eq_ignore_ascii_case(name))
+    }
+
+    fn add_column(&mut self, def: &ColumnDef) {
+        self.columns.push(ColumnSchema::from_def(def));
+        self.apply_column_options(def);
+    }
+
+    // 列定义中的 PRIMARY KEY、UNIQUE 和 REFERENCES
+    fn apply_column_options(&mut self, def: &ColumnDef) {
+        for option in &def.options {
+            match option {
+                ColumnOption::PrimaryKey => self.primary_key = vec![def.name.clone()],
+                ColumnOption::Unique => self.add_index(None, std::slice::from_ref(&def.name), true),
+                ColumnOption::References(reference) => {
+                    self.add_foreign_key(None, std::slice::from_ref(&def.name), reference)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn add_constraint(&mut self, constraint: &TableConstraint) {
+        match &constraint.kind {
+            ConstraintKind::PrimaryKey { columns } => {
+                self.primary_key = columns.clone();
+                // 主键列隐含 NOT NULL
+                for column in &mut self.columns {
+                    if columns.iter().any(|c| c.eq_ignore_ascii_case(&column.name)) {
+                        column.nullable = false;
+                    }
+                }
+            }
+            ConstraintKind::Unique { index_name, columns } => {
+                self.add_index(index_name.clone().or_else(|| constraint.name.clone()), columns, true)
+            }
+            ConstraintKind::ForeignKey { columns, reference } => {
+                self.add_foreign_key(constraint.name.clone(), columns, reference)
+            }
+            ConstraintKind::Check { .. } => {}
+        }
+    }
+
+    fn add_index(&mut self, name: Option<String>, columns: &[String], unique: bool) {
+        let taken = |name: &str| self.unique_keys.iter().chain(&self.indexes).any(|index| same_name(&index.name, name));
+        let name = name.unwrap_or_else(|| {
+            let base = &columns[0];
+            std::iter::once(base.clone())
+                .chain((2..).map(|i| format!("{}_{}", base, i)))
+                .find(|name| !taken(name))
+                .unwrap()
+        });
+        let index = IndexDef { name: Some(name), columns: columns.to_vec() };
+        if unique {
+            self.unique_keys.push(index);
+        } else {
+            self.indexes.push(index);
+        }
+    }
+
+    fn add_foreign_key(&mut self, name: Option<String>, columns: &[String], reference: &ForeignKeyReference) {
+        let name = name.unwrap_or_else(|| {
+            // 在已生成的最大序号上加1
+            let prefix = format!("{}_ibfk_", self.name.name().value);
+            let last = self.foreign_keys.iter().filter_map(|key| key.name.strip_prefix(&prefix)?.parse::<usize>().ok()).max();
+            format!("{}{}", prefix, last.unwrap_or(0) + 1)
+        });
+        self.foreign_keys.push(ForeignKey { name, columns: columns.to_vec(), reference: reference.clone() });
+    }
+
+    fn drop_foreign_key(&mut self, name: &str) {
+        self.foreign_keys.retain(|key| !key.name.eq_ignore_ascii_case(name));
+    }
+
+    fn drop_index(&mut self, name: &str) {
+        self.indexes.retain(|index| !same_name(&index.name, name));
+        self.unique_keys.retain(|index| !same_name(&index.name, name));
+    }
+}
+
+impl ColumnSchema {
+    fn from_def(def: &ColumnDef) -> Self {
+        let mut column = ColumnSchema {
+            name: def.name.clone(),
+            data_type: def.data_type.clone(),
+            nullable: true,
+            default: None,
+            auto_increment: false,
+        };
+        for option in &def.options {
+            match option {
+                ColumnOption::NotNull | ColumnOption::PrimaryKey => column.nullable = false,
+                ColumnOption::Null => column.nullable = true,
+                ColumnOption::Default(expr) => column.default = Some(expr.clone()),
+                ColumnOption::AutoIncrement => column.auto_increment = true,
+                _ => {}
+            }
+        }
+        column
+    }
+}
+
+/// 外键依赖中的环，tables 中每张表引用下一张，最后一张引用第一张
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignKeyCycle {
+    pub tables: Vec<ObjectName>,
+}
+
+impl fmt::Display for ForeignKeyCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<String> = self.tables.iter().chain(self.tables.first()).map(|table| table.to_string()).collect();
+        write!(f, "Foreign key cycle: {}", names.join(" -> "))
+    }
+}
+
+impl Error for ForeignKeyCycle {}
+
+/// 按外键依赖对语句排序，外键关系取自其中的 CREATE TABLE 和 ALTER TABLE
+///
+/// 见 [`Catalog::order_by_foreign_keys`]。
+pub fn order_by_foreign_keys(statements: Vec<SQLStatement>) -> Result<Vec<SQLStatement>, ForeignKeyCycle> {
+    let mut catalog = Catalog::new();
+    statements.iter().for_each(|stmt| catalog.apply(stmt));
+    catalog.order_by_foreign_keys(statements)
+}
+
+impl Catalog {
+    /// 按目录中的外键依赖对语句排序，使被引用的表先于引用它的表创建和插入数据
+    ///
+    /// CREATE TABLE、ALTER TABLE 和 INSERT 按所操作的表分组，组内保持原有顺序；
+    /// 没有依赖关系的表也保持原有的先后顺序。表引用自身以及引用语句之外的表不影响排序。
+    /// 其它语句按原有顺序放在最后。依赖中有环时返回环上的表。
+    pub fn order_by_foreign_keys(&self, statements: Vec<SQLStatement>) -> Result<Vec<SQLStatement>, ForeignKeyCycle> {
+        let mut groups: Vec<(ObjectName, Vec<SQLStatement>)> = Vec::new();
+        let mut rest = Vec::new();
+        for stmt in statements {
+            let table = match &stmt {
+                SQLStatement::Create(CreateStatement::Table(create)) => create.name.clone(),
+                SQLStatement::Alter(AlterStatement::Table(alter)) => alter.name.clone(),
+                SQLStatement::Insert(insert) => insert.table.name.clone(),
+                _ => {
+                    rest.push(stmt);
+                    continue;
+                }
+            };
+            match groups.iter_mut().find(|(name, _)| name.matches(&table)) {
+                Some((_, group)) => group.push(stmt),
+                None => groups.push((table, vec![stmt])),
+            }
+        }
+
+        // 每组引用的其它组
+        let deps: Vec<Vec<usize>> = groups
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| {
+                let keys = self.table(name).map(|table| table.foreign_keys.as_slice()).unwrap_or_default();
+                keys.iter()
+                    .filter_map(|key| groups.iter().position(|(name, _)| name.matches(&key.reference.table)))
+                    .filter(|&j| j != i)
+                    .collect()
+            })
+            .collect();
+
+        // 每次取出依赖都已输出的第一组
+        let mut done = vec![false; groups.len()];
+        let mut order = Vec::with_capacity(groups.len());
+        while order.len() < groups.len() {
+            match (0..groups.len()).find(|&i| !done[i] && deps[i].iter().all(|&j| done[j])) {
+                Some(i) => {
+                    done[i] = true;
+                    order.push(i);
+                }
+                None => {
+                    // 剩下的每一组都有未输出的依赖，沿依赖走下去一定会回到走过的组
+                    let mut path = vec![(0..groups.len()).find(|&i| !done[i]).unwrap()];
+                    loop {
+                        let last = *path.last().unwrap();
+                        let next = deps[last].iter().copied().find(|&j| !done[j]).unwrap();
+                        if let Some(start) = path.iter().position(|&i| i == next) {
+                            let tables = path[start..].iter().map(|&i| groups[i].0.clone()).collect();
+                            return Err(ForeignKeyCycle { tables });
+                        }
+                        path.push(next);
+                    }
+                }
+            }
+        }
+
+        let mut groups: Vec<Option<Vec<SQLStatement>>> = groups.into_iter().map(|(_, group)| Some(group)).collect();
+        let mut ordered: Vec<SQLStatement> = order.into_iter().flat_map(|i| groups[i].take().unwrap()).collect();
+        ordered.extend(rest);
+        Ok(ordered)
+    }
+}
+
+/// 两个目录之间的一处差异，见 [`diff`]
+///
+/// 启用 formatter 时可以输出为对应的 DDL 语句。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaChange {
+    CreateTable(TableSchema),
+    DropTable(ObjectName),
+    AddColumn { table: ObjectName, column: ColumnSchema },
+    DropColumn { table: ObjectName, column: String },
+    /// 类型、可空性、默认值或自增属性发生变化
+    ModifyColumn { table: ObjectName, old: ColumnSchema, new: ColumnSchema },
+    AddPrimaryKey { table: ObjectName, columns: Vec<String> },
+    DropPrimaryKey { table: ObjectName },
+    AddIndex { table: ObjectName, index: IndexDef, unique: bool },
+    DropIndex { table: ObjectName, name: String },
+    AddForeignKey { table: ObjectName, key: ForeignKey },
+    DropForeignKey { table: ObjectName, name: String },
+}
+
+/// 比较两个目录，返回把 old 变为 new 所需的修改
+///
+/// 按 new 中表的顺序输出新建和修改的表，最后是删除的表。同一张表内先删除外键、索引和列，
+/// 再添加和修改列，最后添加主键、索引和外键，按顺序执行生成的语句不会引用已删除的对象。
+/// 修改过定义的索引和外键表示为先删除再添加；列的顺序变化不算差异。
+pub fn diff(old: &Catalog, new: &Catalog) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+    for table in &new.tables {
+        match old.table(&table.name) {
+            Some(before) => diff_table(before, table, &mut changes),
+            None => changes.push(SchemaChange::CreateTable(table.clone())),
+        }
+    }
+    for table in &old.tables {
+        if new.table(&table.name).is_none() {
+            changes.push(SchemaChange::DropTable(table.name.clone()));
+        }
+    }
+    changes
+}
+
+fn diff_table(old: &TableSchema, new: &TableSchema, changes: &mut Vec<SchemaChange>) {
+    let table = || new.name.clone();
+
+    for key in old.foreign_keys.iter().filter(|key| !new.foreign_keys.contains(key)) {
+        changes.push(SchemaChange::DropForeignKey { table: table(), name: key.name.clone() });
+    }
+    for (indexes, new_indexes) in [(&old.unique_keys, &new.unique_keys), (&old.indexes, &new.indexes)] {
+        for index in indexes.iter().filter(|index| !new_indexes.contains(index)) {
+            let name = index.name.clone().expect("catalog indexes are always named");
+            changes.push(SchemaChange::DropIndex { table: table(), name });
+        }
+    }
+    for column in &old.columns {
+        if new.column(&column.name).is_none() {
+            changes.push(SchemaChange::DropColumn { table: table(), column: column.name.clone() });
+        }
+    }
+
+    for column in &new.columns {
+        match old.column(&column.name) {
+            // 只有列名的大小写不同不算修改
+            Some(before) if ColumnSchema { name: column.name.clone(), ..before.clone() } == *column => {}
+            Some(before) => {
+                changes.push(SchemaChange::ModifyColumn { table: table(), old: before.clone(), new: column.clone() })
+            }
+            None => changes.push(SchemaChange::AddColumn { table: table(), column: column.clone() }),
+        }
+    }
+
+    if old.primary_key != new.primary_key {
+        if !old.primary_key.is_empty() {
+            changes.push(SchemaChange::DropPrimaryKey { table: table() });
+        }
+        if !new.primary_key.is_empty() {
+            changes.push(SchemaChange::AddPrimaryKey { table: table(), columns: new.primary_key.clone() });
+        }
+    }
+    for (indexes, old_indexes, unique) in [(&new.unique_keys, &old.unique_keys, true), (&new.indexes, &old.indexes, false)] {
+        for index in indexes.iter().filter(|index| !old_indexes.contains(index)) {
+            changes.push(SchemaChange::AddIndex { table: table(), index: index.clone(), unique });
+        }
+    }
+    for key in new.foreign_keys.iter().filter(|key| !old.foreign_keys.contains(key)) {
+        changes.push(SchemaChange::AddForeignKey { table: table(), key: key.clone() });
+    }
+}
+
+#[cfg(feature = "formatter")]
+mod display {
+    use super::*;
+    use crate::ast::create::ReferentialAction;
+
+    /// 输出为 CREATE TABLE、DROP TABLE 或 ALTER TABLE 语句，结尾不带 ';'
+    impl fmt::Display for SchemaChange {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                SchemaChange::CreateTable(table) => write!(f, "{}", table),
+                SchemaChange::DropTable(table) => write!(f, "DROP TABLE {}", table),
+                SchemaChange::AddColumn { table, column } => write!(f, "ALTER TABLE {} ADD COLUMN {}", table, column),
+                SchemaChange::DropColumn { table, column } => write!(f, "ALTER TABLE {} DROP COLUMN {}", table, column),
+                SchemaChange::ModifyColumn { table, new, .. } => write!(f, "ALTER TABLE {} MODIFY COLUMN {}", table, new),
+                SchemaChange::AddPrimaryKey { table, columns } => {
+                    write!(f, "ALTER TABLE {} ADD PRIMARY KEY ({})", table, columns.join(", "))
+                }
+                SchemaChange::DropPrimaryKey { table } => write!(f, "ALTER TABLE {} DROP PRIMARY KEY", table),
+                SchemaChange::AddIndex { table, index, unique } => {
+                    write!(f, "ALTER TABLE {} ADD ", table)?;
+                    fmt_index(f, index, *unique)
+                }
+                SchemaChange::DropIndex { table, name } => write!(f, "ALTER TABLE {} DROP INDEX {}", table, name),
+                SchemaChange::AddForeignKey { table, key } => write!(f, "ALTER TABLE {} ADD {}", table, key),
+                SchemaChange::DropForeignKey { table, name } => write!(f, "ALTER TABLE {} DROP FOREIGN KEY {}", table, name),
+            }
+        }
+    }
+
+    /// 输出为 CREATE TABLE 语句
+    impl fmt::Display for TableSchema {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let mut items: Vec<String> = self.columns.iter().map(|column| column.to_string()).collect();
+            if !self.primary_key.is_empty() {
+                items.push(format!("PRIMARY KEY ({})", self.primary_key.join(", ")));
+            }
+            for (indexes, unique) in [(&self.unique_keys, true), (&self.indexes, false)] {
+                items.extend(indexes.iter().map(|index| IndexDisplay(index, unique).to_string()));
+            }
+            items.extend(self.foreign_keys.iter().map(|key| key.to_string()));
+            write!(f, "CREATE TABLE {} ({})", self.name, items.join(", "))
+        }
+    }
+
+    /// 输出为列定义
+    impl fmt::Display for ColumnSchema {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{} {}", self.name, self.data_type)?;
+            f.write_str(if self.nullable { " NULL" } else { " NOT NULL" })?;
+            if let Some(default) = &self.default {
+                write!(f, " DEFAULT {}", default)?;
+            }
+            if self.auto_increment {
+                f.write_str(" AUTO_INCREMENT")?;
+            }
+            Ok(())
+        }
+    }
+
+    /// 输出为表级约束 CONSTRAINT name FOREIGN KEY (...) REFERENCES ...
+    impl fmt::Display for ForeignKey {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let reference = &self.reference;
+            write!(
+                f,
+                "CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({})",
+                self.name,
+                self.columns.join(", "),
+                reference.table,
+                reference.columns.join(", ")
+            )?;
+            for (event, action) in [("DELETE", reference.on_delete), ("UPDATE", reference.on_update)] {
+                if let Some(action) = action {
+                    let action = match action {
+                        ReferentialAction::Restrict => "RESTRICT",
+                        ReferentialAction::Cascade => "CASCADE",
+                        ReferentialAction::SetNull => "SET NULL",
+                        ReferentialAction::NoAction => "NO ACTION",
+                        ReferentialAction::SetDefault => "SET DEFAULT",
+                    };
+                    write!(f, " ON {} {}", event, action)?;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    struct IndexDisplay<'a>(&'a IndexDef, bool);
+
+    impl fmt::Display for IndexDisplay<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt_index(f, self.0, self.1)
+        }
+    }
+
+    // UNIQUE KEY [name] (columns) 或 KEY [name] (columns)
+    fn fmt_index(f: &mut fmt::Formatter<'_>, index: &IndexDef, unique: bool) -> fmt::Result {
+        f.write_str(if unique { "UNIQUE KEY" } else { "KEY" })?;
+        if let Some(name) = &index.name {
+            write!(f, " {}", name)?;
+        }
+        write!(f, " ({})", index.columns.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DUMP: &str = "
+        -- MySQL dump
+        SET NAMES utf8mb4;
+        DROP TABLE IF EXISTS `users`;
+        CREATE TABLE `users` (
+            `id` INT NOT NULL AUTO_INCREMENT,
+            `email` VARCHAR(255) NOT NULL UNIQUE,
+            `nickname` VARCHAR(64) DEFAULT 'guest',
+            PRIMARY KEY (`id`),
+            KEY idx_nickname (nickname)
+        ) ENGINE=InnoDB;
+        LOCK TABLES `users` WRITE;
+        INSERT INTO `users` VALUES (1, 'a@example.com', 'a');
+        UNLOCK TABLES;
+        CREATE TABLE orders (
+            id BIGINT PRIMARY KEY,
+            user_id INT,
+            note TEXT,
+            CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE
+        );
+        ALTER TABLE orders ADD COLUMN total DECIMAL(10,2) NOT NULL, DROP COLUMN note, ADD INDEX idx_total (total);
+        ALTER TABLE missing ADD COLUMN x INT;
+    ";
+
+    #[test]
+    fn test_catalog_from_script() {
+        let catalog = Catalog::from_script(DUMP).unwrap();
+        assert_eq!(catalog.tables().len(), 2);
+
+        let users = catalog.table(&ObjectName::new(["USERS"])).unwrap();
+        let names: Vec<&str> = users.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["id", "email", "nickname"]);
+        let id = users.column("ID").unwrap();
+        assert_eq!(id.data_type.name, "INT");
+        assert!(!id.nullable && id.auto_increment);
+        assert!(users.column("nickname").unwrap().nullable);
+        assert!(users.column("nickname").unwrap().default.is_some());
+        assert_eq!(users.primary_key, ["id"]);
+        // 未命名的唯一键取列名作为索引名
+        assert_eq!(users.unique_keys[0], IndexDef { name: Some("email".to_string()), columns: vec!["email".to_string()] });
+        assert_eq!(users.indexes[0].name.as_deref(), Some("idx_nickname"));
+
+        // 带库名的引用也能找到未限定库名的表
+        let orders = catalog.table(&ObjectName::new(["shop", "orders"])).unwrap();
+        let names: Vec<&str> = orders.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["id", "user_id", "total"]);
+        assert_eq!(orders.column("total").unwrap().data_type.length.as_deref(), Some("10,2"));
+        assert_eq!(orders.foreign_keys[0].name, "fk_user");
+        assert_eq!(orders.foreign_keys[0].reference.table, ObjectName::new(["users"]));
+        assert_eq!(orders.indexes.len(), 1);
+        assert!(catalog.table(&ObjectName::new(["missing"])).is_none());
+
+        let mut catalog = catalog;
+        catalog.apply(&Parser::new_from_sql("ALTER TABLE orders DROP FOREIGN KEY fk_user, DROP PRIMARY KEY").parse().unwrap());
+        let orders = catalog.table(&ObjectName::new(["orders"])).unwrap();
+        assert!(orders.foreign_keys.is_empty() && orders.primary_key.is_empty());
+
+        // 建表语句本身的错误照常返回
+        assert!(Catalog::from_script("CREATE TABLE t (id INT,)").is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = Catalog::from_script(
+            "CREATE TABLE users (id INT PRIMARY KEY, email VARCHAR(100) UNIQUE, age INT, KEY idx_age (age));
+             CREATE TABLE orders (id INT PRIMARY KEY, user_id INT REFERENCES users (id), note TEXT);
+             CREATE TABLE legacy (id INT)",
+        )
+        .unwrap();
+        let new = Catalog::from_script(
+            "CREATE TABLE USERS (id BIGINT UNSIGNED NOT NULL AUTO_INCREMENT, email VARCHAR(100), age INT,
+                 name VARCHAR(20) DEFAULT 'x', PRIMARY KEY (id), KEY idx_age (age, name));
+             CREATE TABLE orders (id INT PRIMARY KEY, user_id INT, note TEXT,
+                 CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE SET NULL);
+             CREATE TABLE tags (id INT PRIMARY KEY, price DECIMAL(10,2) NOT NULL)",
+        )
+        .unwrap();
+        let changes = diff(&old, &new);
+        assert!(matches!(&changes[0], SchemaChange::DropIndex { name, .. } if name == "email"));
+        assert!(matches!(&changes[2], SchemaChange::ModifyColumn { old, new, .. }
+            if old.data_type.name == "INT" && new.data_type.name == "BIGINT" && new.auto_increment));
+        assert!(matches!(changes.last(), Some(SchemaChange::DropTable(name)) if *name == ObjectName::new(["legacy"])));
+        assert!(diff(&new, &new).is_empty());
+
+        #[cfg(feature = "formatter")]
+        {
+            let sql: Vec<String> = changes.iter().map(|change| change.to_string()).collect();
+            assert_eq!(
+                sql,
+                [
+                    "ALTER TABLE USERS DROP INDEX email",
+                    "ALTER TABLE USERS DROP INDEX idx_age",
+                    "ALTER TABLE USERS MODIFY COLUMN id BIGINT UNSIGNED NOT NULL AUTO_INCREMENT",
+                    "ALTER TABLE USERS ADD COLUMN name VARCHAR(20) NULL DEFAULT 'x'",
+                    "ALTER TABLE USERS ADD KEY idx_age (age, name)",
+                    "ALTER TABLE orders DROP FOREIGN KEY orders_ibfk_1",
+                    "ALTER TABLE orders ADD CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE SET NULL",
+                    "CREATE TABLE tags (id INT NOT NULL, price DECIMAL(10,2) NOT NULL, PRIMARY KEY (id))",
+                    "DROP TABLE legacy",
+                ]
+            );
+
+            // 依次执行生成的语句后与新的目录一致（DROP TABLE 不能解析，单独处理）
+            let mut migrated = old.clone();
+            for sql in &sql[..sql.len() - 1] {
+                migrated.apply(&Parser::new_from_sql(sql).parse().unwrap());
+            }
+            migrated.tables.retain(|table| table.name != ObjectName::new(["legacy"]));
+            assert_eq!(diff(&migrated, &new), []);
+        }
+    }
+
+    #[test]
+    fn test_order_by_foreign_keys() {
+        let parse = |sql: &str| Parser::new_from_sql(sql).parse_statements().unwrap();
+        let tables = |statements: &[SQLStatement]| -> Vec<String> {
+            statements
+                .iter()
+                .map(|stmt| match stmt {
+                    SQLStatement::Create(CreateStatement::Table(create)) => format!("create {}", create.name),
+                    SQLStatement::Insert(insert) => format!("insert {}", insert.table.name),
+                    _ => "other".to_string(),
+                })
+                .collect()
+        };
+        let statements = parse(
+            "CREATE TABLE order_items (id INT, order_id INT REFERENCES orders (id), parent INT REFERENCES order_items (id));
+             INSERT INTO order_items VALUES (1, 1, NULL);
+             SELECT 1;
+             CREATE TABLE orders (id INT, user_id INT, FOREIGN KEY (user_id) REFERENCES users (id));
+             CREATE TABLE audit (id INT);
+             INSERT INTO orders VALUES (1, 1);
+             CREATE TABLE users (id INT);
+             INSERT INTO users VALUES (1)",
+        );
+        let ordered = order_by_foreign_keys(statements).unwrap();
+        assert_eq!(
+            tables(&ordered),
+            [
+                "create audit",
+                "create users",
+                "insert users",
+                "create orders",
+                "insert orders",
+                "create order_items",
+                "insert order_items",
+                "other"
+            ]
+        );
+
+        // 只有 INSERT 时使用已有的目录
+        let catalog = Catalog::from_script("CREATE TABLE a (id INT); CREATE TABLE b (a_id INT REFERENCES a (id))").unwrap();
+        let ordered = catalog.order_by_foreign_keys(parse("INSERT INTO b VALUES (1); INSERT INTO a VALUES (1)")).unwrap();
+        assert_eq!(tables(&ordered), ["insert a", "insert b"]);
+
+        let cycle = order_by_foreign_keys(parse(
+            "CREATE TABLE x (id INT); CREATE TABLE a (b_id INT REFERENCES b (id)); CREATE TABLE b (a_id INT REFERENCES a (id))",
+        ))
+        .unwrap_err();
+        assert_eq!(cycle.tables, [ObjectName::new(["a"]), ObjectName::new(["b"])]);
+        assert_eq!(cycle.to_string(), "Foreign key cycle: a -> b -> a");
+    }
+}
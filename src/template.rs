@@ -0,0 +1,327 @@
+//! 带命名槽位的语句模板
+//!
+//! [`extract`] 把语句中的表名、字面量和 LIMIT/OFFSET 的数值提取为命名的槽位，
+//! [`render`] 为槽位填入新的取值后重新输出为 SQL，例如由同一个模板为每个租户生成查询。
+//!
+//! 槽位的命名规则：
+//! - 表名为 `table`、`table_2`……，同一个表只占一个槽位
+//! - 与列比较或赋值的字面量以列名命名，例如 `tenant_id = 5` 中的 5 为 `tenant_id`，
+//!   其它字面量为 `value`、`value_2`……
+//! - LIMIT 和 OFFSET 的数值为 `limit`、`offset`
+//!
+//! 重名时依次加上 `_2`、`_3` 后缀。NULL、TRUE/FALSE、DEFAULT 不是槽位。
+
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+
+use crate::ast::common::{ObjectName, TableFunction, TableReference};
+use crate::ast::expr::{Expr, LimitClause, OrderByExpr, SortKey, Value};
+use crate::ast::insert::InsertStatement;
+use crate::ast::select::{SelectColumn, SelectStatement};
+use crate::rewrite::child_columns;
+
+/// 槽位的取值
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlotValue {
+    Table(ObjectName),
+    Literal(Value),
+    /// LIMIT 或 OFFSET 的行数
+    Count(u64),
+}
+
+/// 语句中可以作为槽位的位置
+pub enum SlotSite<'a> {
+    Table(&'a mut ObjectName),
+    /// 字面量及其所属的列
+    Literal(&'a mut Value, Option<&'a str>),
+    /// LIMIT 或 OFFSET 的行数，第二项为槽位名
+    Count(&'a mut u64, &'static str),
+}
+
+/// 可以提取为模板的语句
+pub trait Slots {
+    /// 按固定的顺序访问所有槽位，结构相同的语句访问顺序也相同
+    fn visit_slots(&mut self, f: &mut dyn FnMut(SlotSite<'_>));
+}
+
+/// 语句模板，由 [`extract`] 创建
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template<S> {
+    statement: S,
+    // 每个位置对应的槽位名，与 visit_slots 的访问顺序一致
+    slots: Vec<String>,
+}
+
+impl<S> Template<S> {
+    /// 所有槽位名，按首次出现的顺序，不重复
+    pub fn slot_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for slot in &self.slots {
+            if !names.contains(&slot.as_str()) {
+                names.push(slot);
+            }
+        }
+        names
+    }
+}
+
+/// 渲染模板时的错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// 没有为槽位提供取值
+    MissingBinding(String),
+    /// 取值的种类与槽位不符，例如为表名槽位提供了字面量
+    KindMismatch(String),
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::MissingBinding(slot) => write!(f, "No binding for slot '{}'", slot),
+            TemplateError::KindMismatch(slot) => write!(f, "Binding for slot '{}' has the wrong kind", slot),
+        }
+    }
+}
+
+impl Error for TemplateError {}
+
+/// 把语句转换为模板，同时返回各槽位在原语句中的取值
+///
+/// 用返回的取值渲染模板可以得到与原语句等价的 SQL。
+pub fn extract<S: Slots + Clone>(stmt: &S) -> (Template<S>, BTreeMap<String, SlotValue>) {
+    let mut statement = stmt.clone();
+    let mut slots = Vec::new();
+    let mut values = BTreeMap::new();
+    let mut tables: HashMap<ObjectName, String> = HashMap::new();
+    statement.visit_slots(&mut |site| {
+        let (base, value) = match site {
+            SlotSite::Table(name) => {
+                if let Some(slot) = tables.get(name) {
+                    slots.push(slot.clone());
+                    return;
+                }
+                ("table", SlotValue::Table(name.clone()))
+            }
+            SlotSite::Literal(value, column) => (column.unwrap_or("value"), SlotValue::Literal(value.clone())),
+            SlotSite::Count(count, name) => (name, SlotValue::Count(*count)),
+        };
+        let slot = unique_name(base, &values);
+        if let SlotValue::Table(name) = &value {
+            tables.insert(name.clone(), slot.clone());
+        }
+        values.insert(slot.clone(), value);
+        slots.push(slot);
+    });
+    (Template { statement, slots }, values)
+}
+
+// base 未被占用时直接使用，否则依次尝试 base_2、base_3……
+fn unique_name(base: &str, taken: &BTreeMap<String, SlotValue>) -> String {
+    let base = base.to_lowercase();
+    if !taken.contains_key(&base) {
+        return base;
+    }
+    (2..).map(|i| format!("{}_{}", base, i)).find(|name| !taken.contains_key(name)).unwrap()
+}
+
+/// 用 bindings 中的取值填充模板的槽位，输出为 SQL
+///
+/// bindings 中多余的取值会被忽略。
+pub fn render<S: Slots + Clone + fmt::Display>(
+    template: &Template<S>,
+    bindings: &BTreeMap<String, SlotValue>,
+) -> Result<String, TemplateError> {
+    let mut statement = template.statement.clone();
+    let mut slots = template.slots.iter();
+    let mut error = None;
+    statement.visit_slots(&mut |site| {
+        let Some(slot) = slots.next() else {
+            return;
+        };
+        if error.is_some() {
+            return;
+        }
+        match (site, bindings.get(slot)) {
+            (_, None) => error = Some(TemplateError::MissingBinding(slot.clone())),
+            (SlotSite::Table(name), Some(SlotValue::Table(table))) => *name = table.clone(),
+            (SlotSite::Literal(value, _), Some(SlotValue::Literal(literal))) => *value = literal.clone(),
+            (SlotSite::Count(count, _), Some(SlotValue::Count(n))) => *count = *n,
+            _ => error = Some(TemplateError::KindMismatch(slot.clone())),
+        }
+    });
+    match error {
+        Some(error) => Err(error),
+        None => Ok(statement.to_string()),
+    }
+}
+
+impl Slots for SelectStatement {
+    fn visit_slots(&mut self, f: &mut dyn FnMut(SlotSite<'_>)) {
+        for cte in self.with.iter_mut().flat_map(|with| &mut with.ctes) {
+            cte.query.visit_slots(f);
+        }
+        for column in &mut self.columns {
+            if let SelectColumn::Expression { expr, .. } = column {
+                visit_expr(expr, None, f);
+            }
+        }
+        if let Some(from) = &mut self.from {
+            visit_table(from, f);
+        }
+        for join in &mut self.joins {
+            visit_table(&mut join.table, f);
+            if let Some(on) = &mut join.on {
+                visit_expr(on, None, f);
+            }
+        }
+        if let Some(expr) = &mut self.where_clause {
+            visit_expr(expr, None, f);
+        }
+        for key in self.group_by.iter_mut().flatten() {
+            visit_sort_key(key, f);
+        }
+        if let Some(expr) = &mut self.having {
+            visit_expr(expr, None, f);
+        }
+        if let Some(expr) = &mut self.qualify {
+            visit_expr(expr, None, f);
+        }
+        for expr in self.windows.iter_mut().flat_map(|(_, spec)| spec.exprs_mut()) {
+            visit_expr(expr, None, f);
+        }
+        for operation in &mut self.set_operations {
+            operation.select.visit_slots(f);
+        }
+        visit_order_by(&mut self.order_by, f);
+        visit_limit(&mut self.limit, f);
+    }
+}
+
+impl Slots for InsertStatement {
+    fn visit_slots(&mut self, f: &mut dyn FnMut(SlotSite<'_>)) {
+        visit_table(&mut self.table, f);
+        for row in self.values.iter_mut().flatten() {
+            for (i, cell) in row.iter_mut().enumerate() {
+                let column = self.columns.as_ref().and_then(|columns| columns.get(i));
+                visit_expr(cell, column.map(String::as_str), f);
+            }
+        }
+        if let Some(select) = &mut self.select_clause {
+            select.visit_slots(f);
+        }
+        let on_duplicate = self.on_duplicate.iter_mut().flat_map(|clause| &mut clause.updates);
+        for (column, value) in self.set_clause.iter_mut().flatten().chain(on_duplicate) {
+            visit_expr(value, Some(&column.name().value), f);
+        }
+    }
+}
+
+fn visit_table(table: &mut TableReference, f: &mut dyn FnMut(SlotSite<'_>)) {
+    if let Some(subquery) = &mut table.subquery {
+        subquery.visit_slots(f);
+    } else if let Some(function) = &mut table.function {
+        match &mut **function {
+            TableFunction::JsonTable { expr, .. } => visit_expr(expr, None, f),
+            TableFunction::Call { args, .. } => args.iter_mut().for_each(|arg| visit_expr(arg, None, f)),
+        }
+    } else {
+        f(SlotSite::Table(&mut table.name));
+    }
+}
+
+fn visit_order_by(order_by: &mut Option<Vec<OrderByExpr>>, f: &mut dyn FnMut(SlotSite<'_>)) {
+    for item in order_by.iter_mut().flatten() {
+        visit_sort_key(&mut item.expr, f);
+    }
+}
+
+// 列序号决定语句的结构，不作为槽位
+fn visit_sort_key(key: &mut SortKey, f: &mut dyn FnMut(SlotSite<'_>)) {
+    if let SortKey::Expr(expr) = key {
+        visit_expr(expr, None, f);
+    }
+}
+
+fn visit_limit(limit: &mut Option<LimitClause>, f: &mut dyn FnMut(SlotSite<'_>)) {
+    if let Some(limit) = limit {
+        f(SlotSite::Count(&mut limit.limit, "limit"));
+        if let Some(offset) = &mut limit.offset {
+            f(SlotSite::Count(offset, "offset"));
+        }
+    }
+}
+
+// 按在SQL中出现的顺序访问字面量，所属列的推断与字面量脱敏相同
+fn visit_expr(expr: &mut Expr, column: Option<&str>, f: &mut dyn FnMut(SlotSite<'_>)) {
+    let mut stack = vec![(expr, column.map(str::to_string))];
+    while let Some((expr, column)) = stack.pop() {
+        match expr {
+            Expr::Literal(Value::Boolean(_) | Value::Null | Value::DEFAULT) => {}
+            Expr::Literal(value) => f(SlotSite::Literal(value, column.as_deref())),
+            Expr::Subquery(select) | Expr::Exists(select) => select.visit_slots(f),
+            _ => {
+                let columns = child_columns(expr, column);
+                stack.extend(expr.children_mut().into_iter().zip(columns).rev());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::insert::InsertStatementParser;
+    use crate::parser::select::SelectStatementParser;
+    use crate::parser::Parser;
+
+    #[test]
+    fn test_extract_and_render() {
+        let sql = "SELECT id, name FROM orders o JOIN users u ON u.id = o.user_id \
+                   WHERE o.tenant_id = 7 AND o.status IN ('paid', 'sent') AND o.amount > 10 + 5 \
+                   AND o.user_id IN (SELECT id FROM users WHERE deleted = FALSE) LIMIT 20 OFFSET 40";
+        let select = Parser::new_from_sql(sql).parse_select_statement().unwrap();
+        let (template, values) = extract(&select);
+        assert_eq!(
+            template.slot_names(),
+            ["table", "table_2", "tenant_id", "status", "status_2", "amount", "amount_2", "limit", "offset"]
+        );
+        assert_eq!(values["table_2"], SlotValue::Table(ObjectName::new(["users"])));
+        assert_eq!(values["status_2"], SlotValue::Literal(Value::String("sent".to_string())));
+        assert_eq!(values["offset"], SlotValue::Count(40));
+        // 原来的取值渲染出等价的语句
+        assert_eq!(render(&template, &values).unwrap(), select.to_string());
+
+        let mut bindings = values.clone();
+        bindings.insert("table".to_string(), SlotValue::Table(ObjectName::new(["tenant_42", "orders"])));
+        bindings.insert("tenant_id".to_string(), SlotValue::Literal(Value::Integer(42)));
+        bindings.insert("limit".to_string(), SlotValue::Count(5));
+        let sql = render(&template, &bindings).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT id, name FROM tenant_42.orders AS o JOIN users AS u ON u.id = o.user_id \
+             WHERE o.tenant_id = 42 AND o.status IN ('paid', 'sent') AND o.amount > 10 + 5 \
+             AND o.user_id IN (SELECT id FROM users WHERE deleted = FALSE) LIMIT 5 OFFSET 40"
+        );
+
+        bindings.remove("amount");
+        assert_eq!(render(&template, &bindings), Err(TemplateError::MissingBinding("amount".to_string())));
+        bindings.insert("amount".to_string(), SlotValue::Count(1));
+        assert_eq!(render(&template, &bindings), Err(TemplateError::KindMismatch("amount".to_string())));
+    }
+
+    #[test]
+    fn test_insert_template() {
+        let sql = "INSERT INTO events (tenant_id, kind) VALUES (3, 'login') ON DUPLICATE KEY UPDATE kind = 'again'";
+        let insert = Parser::new_from_sql(sql).parse_insert_statement().unwrap();
+        let (template, values) = extract(&insert);
+        assert_eq!(template.slot_names(), ["table", "tenant_id", "kind", "kind_2"]);
+
+        let mut bindings = values;
+        bindings.insert("tenant_id".to_string(), SlotValue::Literal(Value::Integer(9)));
+        assert_eq!(
+            render(&template, &bindings).unwrap(),
+            "INSERT INTO events (tenant_id, kind) VALUES (9, 'login') ON DUPLICATE KEY UPDATE kind = 'again'"
+        );
+    }
+}
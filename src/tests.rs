@@ -0,0 +1,540 @@
+//! 跨模块的测试
+//!
+//! - 往返性质测试：随机生成AST，输出为SQL后重新解析，结果应与原AST语义相同。
+//!   没有使用 proptest，生成器是手写的，使用固定种子的 xorshift 伪随机数，
+//!   失败时报告种子和SQL，便于复现，但不会像 proptest 一样自动缩小失败的用例。
+//! - 快照测试：解析 `corpus/samples` 中的每个 SQL 文件，与同名的 `.snap` 文件比较。
+//!   修改语法后运行 `UPDATE_SNAPSHOTS=1 cargo test` 重新生成快照，通过 git diff 检查AST的变化。
+//!   启用 `corpus` 特性时，`corpus/tpch` 与 `corpus/tpcds` 中能完整解析的查询也保存 AST 快照。
+//! - JSON 兼容性测试：样例的 JSON 输出必须与 `corpus/json` 中已发布的输出兼容。
+
+use crate::ast::common::{Ident, IndexHint, IndexHintKind, IndexHintScope, ObjectName, TableReference};
+use crate::ast::expr::{
+    AggregateFunction, BinaryOperator, Expr, GroupConcat, IntervalUnit, LimitClause, LogicalOperator, OrderByExpr, SearchMode, SortKey, UnaryOperator,
+    Value,
+};
+use crate::ast::insert::{InsertModifiers, InsertStatement, OnDuplicateClause, RowAlias};
+use crate::ast::select::{Join, JoinKind, SelectColumn, SelectModifiers, SelectStatement, SetOperation, SetOperator};
+use crate::ast::semantic::SemanticEq;
+use crate::parser::insert::InsertStatementParser;
+use crate::parser::select::SelectStatementParser;
+use crate::parser::{Parser, StatementParser};
+use crate::token::Token;
+use std::path::Path;
+
+const CASES: u64 = 500;
+
+// xorshift64 伪随机数
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Rng(seed.wrapping_mul(0x9E3779B97F4A7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn chance(&mut self, percent: u64) -> bool {
+        self.next() % 100 < percent
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len())]
+    }
+}
+
+const COLUMNS: &[&str] = &["id", "name", "price", "qty", "created_at", "note", "a__b", "_x"];
+const TABLES: &[&str] = &["users", "orders", "items"];
+const FUNCTIONS: &[&str] = &["LOWER", "UPPER", "CONCAT", "COALESCE", "ABS", "NOW"];
+
+// 需要加反引号的列名，包括空白、连续的下划线和反引号本身
+const QUOTED_COLUMNS: &[&str] = &["my col", "a  b", "tab\tcol", "__x__", "x`y", "``", "select", "1st"];
+
+fn gen_column(rng: &mut Rng) -> ObjectName {
+    let column = if rng.chance(20) {
+        Ident::with_quote('`', rng.pick(QUOTED_COLUMNS))
+    } else {
+        Ident::new(rng.pick(COLUMNS))
+    };
+    if rng.chance(20) {
+        ObjectName(vec![Ident::new(rng.pick(TABLES)), column])
+    } else {
+        ObjectName(vec![column])
+    }
+}
+
+fn gen_value(rng: &mut Rng) -> Value {
+    match rng.below(4) {
+        0 => Value::Integer(rng.below(200_000) as i64 - 100_000),
+        // 不带指数的小数解析为 Decimal，浮点数取较大的值，输出为科学计数法
+        1 if rng.chance(50) => Value::Decimal(format!("{}.{:02}", rng.below(2000) as i64 - 1000, rng.below(100))),
+        1 => Value::Float((rng.below(4000) as f64 + 1.0) / 4.0 * 1e20),
+        2 => {
+            let len = rng.below(8);
+            let chars = ["a", "B", "z", "0", "9", "%", "é", "'", "''", "_", "__", " ", "  ", "\t", "\n", "\\", "`", ";", "--"];
+            let text: String = (0..len).map(|_| rng.pick(&chars)).collect();
+            Value::String(text)
+        }
+        _ => Value::Null,
+    }
+}
+
+fn gen_expr(rng: &mut Rng, depth: usize) -> Expr {
+    if depth == 0 || rng.chance(30) {
+        return if rng.chance(50) {
+            Expr::Identifier(gen_column(rng))
+        } else {
+            Expr::Literal(gen_value(rng))
+        };
+    }
+    let child = |rng: &mut Rng| Box::new(gen_expr(rng, depth - 1));
+    match rng.below(13) {
+        0 | 1 => {
+            let ops = [
+                BinaryOperator::Eq,
+                BinaryOperator::NotEq,
+                BinaryOperator::Lt,
+                BinaryOperator::LtEq,
+                BinaryOperator::Gt,
+                BinaryOperator::GtEq,
+                BinaryOperator::Plus,
+                BinaryOperator::Minus,
+                BinaryOperator::Multiply,
+                BinaryOperator::Divide,
+            ];
+            let op = ops[rng.below(ops.len())].clone();
+            Expr::BinaryOp { left: child(rng), op, right: child(rng) }
+        }
+        2 => {
+            let op = if rng.chance(50) { LogicalOperator::And } else { LogicalOperator::Or };
+            Expr::LogicalOp { op, expressions: vec![gen_expr(rng, depth - 1), gen_expr(rng, depth - 1)] }
+        }
+        3 => Expr::LogicalOp { op: LogicalOperator::Not, expressions: vec![gen_expr(rng, depth - 1)] },
+        4 => Expr::UnaryOp { op: UnaryOperator::Minus, expr: child(rng) },
+        5 => {
+            let name = rng.pick(FUNCTIONS).to_string();
+            let args = (0..rng.below(3)).map(|_| gen_expr(rng, depth - 1)).collect();
+            Expr::FunctionCall { name, args }
+        }
+        6 => {
+            let unit = IntervalUnit::from_name(rng.pick(&["DAY", "YEAR", "HOUR_MINUTE"])).unwrap();
+            Expr::Interval { value: child(rng), unit }
+        }
+        7 => {
+            let modes = [
+                SearchMode::NaturalLanguage,
+                SearchMode::NaturalLanguageWithQueryExpansion,
+                SearchMode::Boolean,
+                SearchMode::QueryExpansion,
+            ];
+            let columns = (0..rng.below(2) + 1).map(|_| gen_column(rng)).collect();
+            Expr::Match { columns, against: child(rng), mode: modes[rng.below(modes.len())] }
+        }
+        8 => {
+            let list = (0..rng.below(3) + 1).map(|_| gen_expr(rng, depth - 1)).collect();
+            Expr::In { expr: child(rng), list, negated: rng.chance(50) }
+        }
+        9 => {
+            let args = (0..rng.below(2) + 1).map(|_| gen_expr(rng, depth - 1)).collect();
+            let order_by = (0..rng.below(3))
+                .map(|_| OrderByExpr { expr: SortKey::Expr(Expr::Identifier(gen_column(rng))), asc: rng.chance(50) })
+                .collect();
+            let separator = rng.chance(50).then(|| rng.pick(&[",", " | ", "-", "_", "'", "\\n"]).to_string());
+            Expr::GroupConcat(Box::new(GroupConcat { distinct: rng.chance(50), args, order_by, separator }))
+        }
+        10 => {
+            let funcs = [
+                AggregateFunction::Count,
+                AggregateFunction::Sum,
+                AggregateFunction::Avg,
+                AggregateFunction::Min,
+                AggregateFunction::Max,
+            ];
+            let func = funcs[rng.below(funcs.len())];
+            if func == AggregateFunction::Count && rng.chance(30) {
+                Expr::Aggregate { func, distinct: false, args: vec![Expr::Wildcard] }
+            } else {
+                Expr::Aggregate { func, distinct: rng.chance(50), args: vec![gen_expr(rng, depth - 1)] }
+            }
+        }
+        11 => Expr::Assignment { variable: format!("@v{}", rng.below(3)), value: child(rng) },
+        _ => Expr::Collate { expr: child(rng), collation: "utf8mb4_bin".to_string() },
+    }
+}
+
+fn gen_select(rng: &mut Rng) -> SelectStatement {
+    let alias = |rng: &mut Rng| rng.chance(30).then(|| format!("c{}", rng.below(10)));
+    let columns = if rng.chance(10) {
+        vec![SelectColumn::Wildcard]
+    } else {
+        (0..rng.below(3) + 1)
+            .map(|_| match gen_expr(rng, 2) {
+                _ if rng.chance(10) => SelectColumn::QualifiedWildcard(ObjectName::new([rng.pick(TABLES)])),
+                // 单独的列名解析为 Column
+                Expr::Identifier(name) => SelectColumn::Column { name, alias: alias(rng) },
+                expr => SelectColumn::Expression { expr, alias: alias(rng) },
+            })
+            .collect()
+    };
+    let index_hints = |rng: &mut Rng| {
+        let kinds = [IndexHintKind::Use, IndexHintKind::Force, IndexHintKind::Ignore];
+        let scopes = [None, Some(IndexHintScope::Join), Some(IndexHintScope::OrderBy), Some(IndexHintScope::GroupBy)];
+        (0..rng.below(3))
+            .map(|_| IndexHint {
+                kind: kinds[rng.below(kinds.len())],
+                scope: scopes[rng.below(scopes.len())],
+                indexes: (0..rng.below(2) + 1).map(|i| format!("idx_{}", i)).collect(),
+            })
+            .collect::<Vec<_>>()
+    };
+    let from = rng.chance(90).then(|| TableReference {
+        name: ObjectName::new([rng.pick(TABLES)]),
+        partitions: None,
+        alias: rng.chance(30).then(|| "t".to_string()),
+        index_hints: index_hints(rng),
+        sample: None,
+        subquery: None,
+        function: None,
+        lateral: false,
+    });
+    let joins = match from {
+        Some(_) => (0..rng.below(3))
+            .map(|i| {
+                let kinds =
+                    [JoinKind::Inner, JoinKind::Cross, JoinKind::Left, JoinKind::Right, JoinKind::Straight, JoinKind::Comma];
+                let kind = kinds[rng.below(kinds.len())];
+                let natural = !matches!(kind, JoinKind::Cross | JoinKind::Straight | JoinKind::Comma) && rng.chance(10);
+                let using = (!natural && !matches!(kind, JoinKind::Straight | JoinKind::Comma) && rng.chance(15))
+                    .then(|| (0..rng.below(2) + 1).map(|_| rng.pick(COLUMNS).to_string()).collect::<Vec<_>>());
+                let on = (!natural && using.is_none() && kind != JoinKind::Comma
+                    && (matches!(kind, JoinKind::Left | JoinKind::Right) || rng.chance(70)))
+                .then(|| gen_expr(rng, 2));
+                let table = TableReference {
+                    name: ObjectName::new([rng.pick(TABLES)]),
+                    partitions: None,
+                    alias: Some(format!("j{}", i)),
+                    index_hints: index_hints(rng),
+                    sample: None,
+                    subquery: None,
+                    function: None,
+                    lateral: false,
+                };
+                Join { kind, table, on, using, natural }
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    // GROUP BY、ORDER BY 中的整数会被解析为列序号，这里只使用列名和序号
+    let sort_key = |rng: &mut Rng| {
+        if rng.chance(20) {
+            SortKey::Ordinal(rng.below(3) as u32 + 1)
+        } else {
+            SortKey::Expr(Expr::Identifier(gen_column(rng)))
+        }
+    };
+    let group_by = rng.chance(30).then(|| (0..rng.below(2) + 1).map(|_| sort_key(rng)).collect::<Vec<_>>());
+    // 集合运算的操作数本身不再带集合运算
+    let set_operations = (0..if rng.chance(10) { rng.below(2) + 1 } else { 0 })
+        .map(|_| {
+            let ops = [SetOperator::Union, SetOperator::Except, SetOperator::Intersect];
+            let mut select = gen_select(rng);
+            select.set_operations.clear();
+            SetOperation { op: ops[rng.below(ops.len())], all: rng.chance(50), select }
+        })
+        .collect();
+    SelectStatement {
+        with: None,
+        columns,
+        distinct: rng.chance(20),
+        distinct_on: None,
+        modifiers: SelectModifiers {
+            high_priority: rng.chance(10),
+            straight_join: rng.chance(10),
+            sql_no_cache: rng.chance(10),
+            sql_calc_found_rows: rng.chance(10),
+            ..Default::default()
+        },
+        top: None,
+        from,
+        joins,
+        where_clause: rng.chance(70).then(|| gen_expr(rng, 4)),
+        having: group_by.as_ref().and_then(|_| rng.chance(50).then(|| gen_expr(rng, 2))),
+        with_rollup: group_by.is_some() && rng.chance(20),
+        group_by,
+        qualify: None,
+        windows: Vec::new(),
+        set_operations,
+        order_by: rng.chance(40).then(|| {
+            (0..rng.below(2) + 1).map(|_| OrderByExpr { expr: sort_key(rng), asc: rng.chance(50) }).collect()
+        }),
+        limit: rng.chance(40).then(|| LimitClause {
+            limit: rng.below(1000) as u64,
+            offset: rng.chance(50).then(|| rng.below(1000) as u64),
+        }),
+        into: None,
+        lock: None,
+    }
+}
+
+fn gen_insert(rng: &mut Rng) -> InsertStatement {
+    let width = rng.below(3) + 1;
+    let columns: Vec<String> = COLUMNS[..width].iter().map(|c| c.to_string()).collect();
+    let assignments = |rng: &mut Rng| -> Vec<(ObjectName, Expr)> {
+        (0..rng.below(2) + 1)
+            .map(|_| {
+                let value = if rng.chance(10) { Expr::Literal(Value::DEFAULT) } else { gen_expr(rng, 3) };
+                (ObjectName::new([rng.pick(COLUMNS)]), value)
+            })
+            .collect()
+    };
+    let (values, set_clause) = if rng.chance(80) {
+        let rows = (0..rng.below(3) + 1)
+            .map(|_| {
+                (0..width)
+                    .map(|_| if rng.chance(10) { Expr::Literal(Value::DEFAULT) } else { gen_expr(rng, 3) })
+                    .collect()
+            })
+            .collect();
+        (Some(rows), None)
+    } else {
+        (None, Some(assignments(rng)))
+    };
+    InsertStatement {
+        modifiers: InsertModifiers { ignore: rng.chance(30), ..Default::default() },
+        table: TableReference {
+            name: ObjectName::new([rng.pick(TABLES)]),
+            partitions: None,
+            alias: None,
+            index_hints: Vec::new(),
+            sample: None,
+            subquery: None,
+            function: None,
+            lateral: false,
+        },
+        columns: values.as_ref().map(|_| columns),
+        values,
+        select_clause: None,
+        set_clause,
+        row_alias: rng.chance(20).then(|| RowAlias {
+            name: "new".to_string(),
+            columns: rng.chance(50).then(|| (0..width).map(|i| format!("n{}", i)).collect()),
+        }),
+        on_duplicate: rng.chance(30).then(|| OnDuplicateClause { updates: assignments(rng) }),
+        is_default_values: false,
+        is_return_count: true,
+    }
+}
+
+#[test]
+fn test_expr_round_trip() {
+    for seed in 0..CASES {
+        let expr = gen_expr(&mut Rng::new(seed), 5);
+        let sql = expr.to_string();
+        let parsed = Parser::new_from_sql(&sql).parse_expr(0);
+        assert!(
+            parsed.as_ref().is_ok_and(|parsed| parsed.semantic_eq(&expr)),
+            "seed {}: {}\n原始: {:?}\n解析: {:?}",
+            seed,
+            sql,
+            expr,
+            parsed
+        );
+    }
+}
+
+#[test]
+fn test_select_round_trip() {
+    for seed in 0..CASES {
+        let select = gen_select(&mut Rng::new(seed));
+        let sql = select.to_string();
+        let parsed = Parser::new_from_sql(&sql).parse_select_statement();
+        assert!(
+            parsed.as_ref().is_ok_and(|parsed| parsed.semantic_eq(&select)),
+            "seed {}: {}\n原始: {:?}\n解析: {:?}",
+            seed,
+            sql,
+            select,
+            parsed
+        );
+    }
+}
+
+#[test]
+fn test_insert_round_trip() {
+    for seed in 0..CASES {
+        let insert = gen_insert(&mut Rng::new(seed));
+        let sql = insert.to_string();
+        let parsed = Parser::new_from_sql(&sql).parse_insert_statement();
+        assert!(
+            parsed.as_ref().is_ok_and(|parsed| parsed.semantic_eq(&insert)),
+            "seed {}: {}\n原始: {:?}\n解析: {:?}",
+            seed,
+            sql,
+            insert,
+            parsed
+        );
+    }
+}
+
+// 按完整语句解析，返回AST或错误的文本形式
+fn snapshot_of(sql: &str) -> String {
+    let mut parser = Parser::new_from_sql(sql);
+    let mut text = match parser.parse() {
+        Ok(ast) => format!("{:#?}", ast),
+        Err(err) => format!("error: {:?}", err),
+    };
+    // 出错时记录没有被解析的部分，语法支持增加后会在快照中体现出来
+    let rest: Vec<String> = (0..)
+        .map_while(|n| parser.peek_n(n))
+        .filter(|token| **token != Token::Punctuator(';'))
+        .map(|token| format!("{:?}", token))
+        .collect();
+    if !rest.is_empty() {
+        text.push_str(&format!("\nunparsed: {}", rest.join(" ")));
+    }
+    text
+}
+
+// 与快照文件比较，设置 UPDATE_SNAPSHOTS 时改为写入
+fn assert_snapshot(path: &Path, actual: &str) -> Result<(), String> {
+    let actual = format!("{}\n", actual);
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(path, &actual).map_err(|err| format!("{}: {}", path.display(), err))?;
+        return Ok(());
+    }
+    let expected = std::fs::read_to_string(path)
+        .map_err(|_| format!("{}: 快照不存在，使用 UPDATE_SNAPSHOTS=1 生成", path.display()))?;
+    if expected == actual {
+        return Ok(());
+    }
+    // 逐行比较，只列出不同的行
+    let (old, new): (Vec<&str>, Vec<&str>) = (expected.lines().collect(), actual.lines().collect());
+    let mut diff = format!("{}: 快照不一致\n", path.display());
+    for i in 0..old.len().max(new.len()) {
+        match (old.get(i), new.get(i)) {
+            (Some(a), Some(b)) if a == b => {}
+            (a, b) => {
+                if let Some(a) = a {
+                    diff.push_str(&format!("{:>4} - {}\n", i + 1, a));
+                }
+                if let Some(b) = b {
+                    diff.push_str(&format!("{:>4} + {}\n", i + 1, b));
+                }
+            }
+        }
+    }
+    Err(diff)
+}
+
+#[test]
+fn test_sample_snapshots() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("corpus/samples");
+    let mut paths: Vec<_> = std::fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "sql"))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty());
+
+    let failures: Vec<String> = paths
+        .iter()
+        .filter_map(|path| {
+            let sql = std::fs::read_to_string(path).unwrap();
+            assert_snapshot(&path.with_extension("snap"), &snapshot_of(&sql)).err()
+        })
+        .collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+// 能完整解析的语料查询保存 AST 快照，不能解析的查询不应留有快照
+#[cfg(feature = "corpus")]
+#[test]
+fn test_corpus_snapshots() {
+    use crate::corpus::{parse_query, TPCDS, TPCH};
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("corpus");
+    let mut failures = Vec::new();
+    for (dir, queries) in [("tpch", TPCH), ("tpcds", TPCDS)] {
+        for query in queries {
+            let path = root.join(dir).join(query.name).with_extension("snap");
+            match parse_query(query.sql) {
+                Ok(ast) => failures.extend(assert_snapshot(&path, &format!("{:#?}", ast)).err()),
+                Err(_) if path.exists() => failures.push(format!("{}: 查询不再能解析，但快照仍然存在", path.display())),
+                Err(_) => {}
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+// 按完整语句解析，解析成功时返回带版本号的 JSON 输出
+#[cfg(feature = "json")]
+fn json_of(sql: &str) -> Option<serde_json::Value> {
+    Parser::new_from_sql(sql).parse().ok().map(|ast| crate::json::to_value(&ast))
+}
+
+// 已发布的输出中的每个字段在新的输出中都存在且相同，新的输出可以多出字段
+#[cfg(feature = "json")]
+fn json_compatible(published: &serde_json::Value, current: &serde_json::Value) -> bool {
+    use serde_json::Value as Json;
+    match (published, current) {
+        (Json::Object(old), Json::Object(new)) => {
+            old.iter().all(|(key, value)| new.get(key).is_some_and(|other| json_compatible(value, other)))
+        }
+        (Json::Array(old), Json::Array(new)) => {
+            old.len() == new.len() && old.iter().zip(new).all(|(a, b)| json_compatible(a, b))
+        }
+        _ => published == current,
+    }
+}
+
+// JSON 输出的兼容性测试：`corpus/json/v{SCHEMA_VERSION}` 中保存了每个样例已发布的输出，
+// 当前的输出必须与之兼容，只允许增加字段。与快照不同，已有的文件不会被 UPDATE_SNAPSHOTS 覆盖，只会补充新样例的文件；
+// 需要修改已有文件时，说明输出格式发生了不兼容的变化，应增加 SCHEMA_VERSION 并新建目录。
+#[cfg(feature = "json")]
+#[test]
+fn test_json_schema_compatibility() {
+    let samples = Path::new(env!("CARGO_MANIFEST_DIR")).join("corpus/samples");
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("corpus/json/v{}", crate::json::SCHEMA_VERSION));
+    let mut paths: Vec<_> = std::fs::read_dir(&samples)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "sql"))
+        .collect();
+    paths.sort();
+
+    let mut checked = 0;
+    let mut failures = Vec::new();
+    for path in &paths {
+        let Some(actual) = json_of(&std::fs::read_to_string(path).unwrap()) else {
+            continue;
+        };
+        let fixture = fixtures.join(path.with_extension("json").file_name().unwrap());
+        match std::fs::read_to_string(&fixture) {
+            Ok(expected) => {
+                checked += 1;
+                if !json_compatible(&serde_json::from_str(&expected).unwrap(), &actual) {
+                    failures.push(format!("{}: JSON 输出与已发布的格式不一致", fixture.display()));
+                }
+            }
+            Err(_) if std::env::var_os("UPDATE_SNAPSHOTS").is_some() => {
+                std::fs::create_dir_all(&fixtures).unwrap();
+                let text = serde_json::to_string_pretty(&actual).unwrap();
+                std::fs::write(&fixture, format!("{}\n", text)).unwrap();
+            }
+            Err(_) => failures.push(format!("{}: 不存在，使用 UPDATE_SNAPSHOTS=1 生成", fixture.display())),
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+    assert!(checked > 0 || std::env::var_os("UPDATE_SNAPSHOTS").is_some());
+}
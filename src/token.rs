@@ -1,14 +1,18 @@
-use crate::kerwords::{TYPES, KEYWORDS};
-use regex::Regex;
-use lazy_static::lazy_static;
+use crate::ast::common::Ident;
+use crate::kerwords::{is_type_name, Keyword};
+use crate::parser::Dialect;
+use std::error::Error;
+use std::fmt;
 
 #[non_exhaustive]
-#[derive(Debug, Clone,PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Token {
-    /// MySQL 关键字，如 SELECT, FROM, WHERE 等
-    Keyword(String),
+    /// MySQL 关键字，如 SELECT, FROM, WHERE 等。text 为原始拼写，非保留字作为标识符使用时保持原样
+    Keyword { keyword: Keyword, text: String },
     ///  表示标识符，比如表名、列名
     Identifier(String),
+    /// 用反引号包裹的标识符，例如 `order`，不会被识别为关键字
+    QuotedIdentifier(String),
     /// 字符串字面量，例如 'hello'
     StringLiteral(String),
     /// 数字字面量，例如 123 或 45.67
@@ -17,354 +21,540 @@ pub enum Token {
     Operator(String),
     /// 标点符号，如逗号、分号、括号等
     Punctuator(char),
-    /// 数据类型，例如 VARCHAR(36)。name 为类型名称，length 为可选长度参数
-    DataType { name: String, length: Option<String> },
+    /// 数据类型名称，例如 VARCHAR。括号内的参数是独立的token，由解析器处理
+    DataType(String),
 
-    // 已有的 Token 类型
-    QualifiedIdentifier { qualifier: String, name: String },
+    /// 由点号连接的限定标识符，例如 mydb.`users`.id，按顺序保存每一段及其引号
+    QualifiedIdentifier(Vec<Ident>),
 }
 
-const OPERATOR_SET: &[&str] = &["=", "<", ">", "<=", ">=", "!=", "+", "-", "*", "/", "%"];
-const PUNCTUATORS: &[char] = &[',', ';', '(', ')','.'];
+const OPERATOR_SET: &[&str] = &["=", "<", ">", "<=", ">=", "!=", "<>", ":=", "+", "-", "*", "/", "%"];
+const PUNCTUATORS: &[char] = &[',', ';', '(', ')','.', ':', '[', ']'];
 
-lazy_static! {
-    pub static ref RE_BLOCK: Regex = Regex::new(r"(?s)/\*.*?\*/").unwrap();
-    pub static ref RE_LINE: Regex = Regex::new(r"(?m)--.*$").unwrap();
-    // 用于压缩连续空白字符：\s+ 表示一个或多个空白字符
-    pub static ref RE_SPACES: Regex = Regex::new(r"\s+").unwrap();
+// 判断 pos 处是否为注释的开头，是则返回注释结束的位置
+//
+// 按 MySQL 的规则，`--` 后面必须紧跟空白或控制字符（或者位于末尾）才是注释，
+// 否则是两个减号，例如 `5--1`。`#` 注释到行尾（仅 MySQL，PostgreSQL 中 `#` 是运算符），
+// 块注释可以嵌套，`/* a /* b */ c */` 整体是一个注释。
+fn comment_end(source: &str, pos: usize, dialect: Dialect) -> Option<usize> {
+    let rest = &source[pos..];
+    let line_end = |skip: usize| pos + rest[skip..].find('\n').map_or(rest.len(), |i| skip + i);
+    if let Some(after) = rest.strip_prefix("--") {
+        return match after.chars().next() {
+            None => Some(source.len()),
+            Some(c) if c.is_whitespace() || c.is_control() => Some(line_end(2)),
+            Some(_) => None,
+        };
+    }
+    if matches!(dialect, Dialect::MySql | Dialect::BigQuery) && rest.starts_with('#') {
+        return Some(line_end(1));
+    }
+    if !rest.starts_with("/*") {
+        return None;
+    }
+    let mut depth = 0;
+    let mut end = pos;
+    while end < source.len() {
+        let tail = &source[end..];
+        if tail.starts_with("/*") {
+            depth += 1;
+            end += 2;
+        } else if tail.starts_with("*/") {
+            depth -= 1;
+            end += 2;
+            if depth == 0 {
+                return Some(end);
+            }
+        } else {
+            end += tail.chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    // 未闭合时延伸到末尾
+    Some(source.len())
 }
 
-/// 对输入字符串预处理，去除其中的注释，并将换行符替换为空格，
-/// 然后进一步压缩多个连续空白为一个。
+/// 对输入字符串预处理，去除其中的注释，并将连续空白压缩为一个空格。
+///
+/// 字符串和反引号标识符内的内容原样保留，不会被当作注释，其中的空白也不会被压缩，
+/// 切分单词时整体作为一个单词。MySQL 字符串中的反斜杠转义会被还原，例如 `'a\'b'` 输出为 `'a''b'`。
 pub fn preprocess_input(input: &str) -> String {
-    // 去除多行注释：使用 (?s) 模式使 `.` 匹配换行符
-    let without_block = RE_BLOCK.replace_all(input, "");
-    // 去除行注释
-    let without_line = RE_LINE.replace_all(&without_block, "");
-    // 将换行符替换为空格
-    let mut replaced = without_line.replace('\n', " ");
-    // 压缩多个连续空白为一个空格，然后 trim 去除首尾空白
-    replaced = RE_SPACES.replace_all(&replaced, " ").trim().to_string();
-    // 将三个'替换成一个'
-    replaced = replaced.replace("'''", "'");
-
-    // 将单引号内的空格替换为特殊标记 "___"
+    preprocess_input_with_dialect(input, Dialect::MySql)
+}
+
+/// 按指定方言的注释规则预处理输入
+pub fn preprocess_input_with_dialect(input: &str, dialect: Dialect) -> String {
+    preprocess(input, dialect)
+}
+
+// MySQL 字符串中反斜杠转义的字符，未列出的字符去掉反斜杠后保持原样
+fn unescape(ch: char) -> char {
+    match ch {
+        '0' => '\0',
+        'b' => '\u{8}',
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        'Z' => '\u{1a}',
+        _ => ch,
+    }
+}
+
+// 预处理输入
+//
+// MySQL 字符串中的反斜杠转义在这里还原为对应的字符，转义的引号改写为成对的形式。
+fn preprocess(input: &str, dialect: Dialect) -> String {
     let mut result = String::new();
-    let mut in_quotes = false;
+    // 当前所在的引号，None 表示不在字符串或反引号标识符内
+    let mut quote: Option<char> = None;
+    // 上一个输出的是否为空白，用于压缩连续空白
+    let mut pending_space = false;
+    let mut pos = 0;
 
-    for ch in replaced.chars() {
-        if ch == '\'' {
-            in_quotes = !in_quotes;
+    while let Some(ch) = input[pos..].chars().next() {
+        if let Some(q) = quote {
+            if q == '\'' && ch == '\\' && dialect == Dialect::MySql {
+                if let Some(next) = input[pos + 1..].chars().next() {
+                    match next {
+                        // 成对的形式在后续处理中表示一个引号
+                        '\'' => result.push_str("''"),
+                        // LIKE 模式中的 \% 和 \_ 保留反斜杠
+                        '%' | '_' => {
+                            result.push('\\');
+                            result.push(next);
+                        }
+                        _ => result.push(unescape(next)),
+                    }
+                    pos += 1 + next.len_utf8();
+                    continue;
+                }
+            }
+            if ch == q {
+                quote = None;
+            }
             result.push(ch);
-        } else if ch == ' ' && in_quotes {
-            // 在单引号内，用特殊标记替换空格
-            result.push_str("___");
-        } else if ch == ',' && in_quotes{
-            // 在单引号内，用特殊标记替换逗号
-            result.push_str("---");
+            pos += ch.len_utf8();
+            continue;
+        }
+        if let Some(end) = comment_end(input, pos, dialect) {
+            // 注释相当于一个空白
+            pending_space = true;
+            pos = end;
+            continue;
+        }
+        if ch.is_whitespace() {
+            pending_space = true;
         } else {
-            // 其他情况直接添加字符
+            if pending_space && !result.is_empty() {
+                result.push(' ');
+            }
+            pending_space = false;
+            if ch == '\'' || ch == '`' {
+                // 成对的引号在这里表现为先结束再开始，不需要单独处理
+                quote = Some(ch);
+            }
             result.push(ch);
         }
+        pos += ch.len_utf8();
     }
 
     result
+}
 
+/// 将输入字符串简单拆分为 Token 数组。
+/// 注意：这是一个非常基础的实现，仅供学习使用，后续可扩展处理更多语法细节。
+pub fn tokenize(input: &str) -> Vec<Token> {
+    tokenize_with_dialect(input, Dialect::MySql)
 }
 
-/// 尝试解析数据类型。比如对于 "VARCHAR(36)" 这种形式，将返回 Some(Token::DataType { … })。
-fn try_parse_data_type(word: &str) -> Option<Token> {
-    // 如果是无参数据类型，如 VARCHAR、INT 等
-    if TYPES.contains(&word.to_uppercase()) {
-        return Some(Token::DataType {
-            name: word.to_string(),
-            length: None,
-        });
-    }
-    if let Some(start) = word.find('(') {
-        if word.ends_with(')') {
-            let name = &word[..start];
-            if !TYPES.contains(&name.to_uppercase()) {
-                return None; // 不是有效的数据类型
-            }
-            let inside = &word[start+1..word.len()-1];
-            // 这里可以进一步验证 inside 是否为数字或符合其它要求
-            return Some(Token::DataType {
-                name: name.to_string(),
-                length: if inside.is_empty() { None } else { Some(inside.to_string()) },
-            });
-        }
+/// 按指定方言的词法规则拆分 Token，目前只影响注释的识别
+///
+/// 遇到词法错误时尽量恢复：未闭合的字符串或反引号标识符延伸到输入结尾，
+/// 分别作为一个 StringLiteral 或 QuotedIdentifier。需要报告错误时使用 [`try_tokenize_with_dialect`]。
+pub fn tokenize_with_dialect(input: &str, dialect: Dialect) -> Vec<Token> {
+    lex(input, dialect).tokens
+}
+
+/// 拆分 Token，遇到未闭合的字符串或反引号标识符时返回 [`LexError`]
+pub fn try_tokenize(input: &str) -> Result<Vec<Token>, LexError> {
+    try_tokenize_with_dialect(input, Dialect::MySql)
+}
+
+/// 按指定方言拆分 Token，遇到未闭合的字符串或反引号标识符时返回 [`LexError`]
+pub fn try_tokenize_with_dialect(input: &str, dialect: Dialect) -> Result<Vec<Token>, LexError> {
+    let lexed = lex(input, dialect);
+    match lexed.error {
+        None => Ok(lexed.tokens),
+        Some(err) => Err(err),
     }
-    None
 }
 
-/// 将输入字符串简单拆分为 Token 数组。
-/// 注意：这是一个非常基础的实现，仅供学习使用，后续可扩展处理更多语法细节。
-pub fn tokenize(input: &str) -> Vec<Token> {
-    let mut tokens = Vec::new();
-    // 预处理后，输入变为统一格式
-    let processed = preprocess_input(input);
-    for raw_word in processed.split_whitespace() {
-        // 看最后一个字符是否是标点符号
-        let  mut last_char = None;
-        if !raw_word.is_empty()  {
-            let c = raw_word.chars().last().unwrap();
-            if c == ',' || c == ';' {
-                last_char = Some(Token::Punctuator(c));
+/// 按指定方言拆分 Token，同时返回每个token在原始SQL中的字节范围，两者一一对应
+pub fn tokenize_with_spans(input: &str, dialect: Dialect) -> (Vec<Token>, Vec<Span>) {
+    let lexed = lex(input, dialect);
+    (lexed.tokens, lexed.spans)
+}
+
+// 词法分析的结果，遇到词法错误时最后一个 token 是恢复得到的
+pub(crate) struct Lexed {
+    pub tokens: Vec<Token>,
+    /// 与 tokens 一一对应的位置
+    pub spans: Vec<Span>,
+    pub error: Option<LexError>,
+}
+
+// 拆分 Token，同时记录每个 token 的位置和遇到的词法错误
+pub(crate) fn lex(input: &str, dialect: Dialect) -> Lexed {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("tokenize", bytes = input.len(), ?dialect).entered();
+    let mut lexer = Lexer { source: input, dialect, pos: 0, name_end: None, tokens: Vec::new(), spans: Vec::new(), error: None };
+    lexer.run();
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(tokens = lexer.tokens.len(), "tokenized");
+    Lexed { tokens: lexer.tokens, spans: lexer.spans, error: lexer.error }
+}
+
+// 把单词分类为类型名、关键字、数字或标识符
+fn word_token(word: String) -> Token {
+    if is_type_name(&word) {
+        Token::DataType(word)
+    } else if let Some(keyword) = Keyword::lookup(&word) {
+        Token::Keyword { keyword, text: word }
+    } else if is_number(&word) {
+        Token::NumericLiteral(word)
+    } else {
+        Token::Identifier(word)
+    }
+}
+
+// 是否为整数或科学计数法表示的数字，例如 123、1e3、2E-5
+fn is_number(word: &str) -> bool {
+    let (mantissa, exponent) = match word.find(['e', 'E']) {
+        Some(i) => (&word[..i], Some(word[i + 1..].trim_start_matches(['+', '-']))),
+        None => (word, None),
+    };
+    let digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    digits(mantissa) && exponent.is_none_or(digits)
+}
+
+// 单词由字母、数字和下划线组成
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+// 开头连续的ASCII数字的字节数
+fn ascii_digits(s: &str) -> usize {
+    s.len() - s.trim_start_matches(|c: char| c.is_ascii_digit()).len()
+}
+
+// 直接在原始SQL上逐个字符扫描，每个 token 的位置在生成时记录
+struct Lexer<'a> {
+    source: &'a str,
+    dialect: Dialect,
+    pos: usize,
+    // 上一个反引号标识符或限定标识符的结束位置，紧跟的点号表示继续限定，例如 `mydb`.users
+    name_end: Option<usize>,
+    tokens: Vec<Token>,
+    spans: Vec<Span>,
+    error: Option<LexError>,
+}
+
+impl Lexer<'_> {
+    fn peek(&self) -> Option<char> {
+        self.source[self.pos..].chars().next()
+    }
+
+    // 添加从 start 到当前位置的 token
+    fn push(&mut self, token: Token, start: usize) {
+        self.tokens.push(token);
+        self.spans.push(Span::new(start, self.pos));
+    }
+
+    fn run(&mut self) {
+        loop {
+            // 注释相当于空白
+            self.pos = skip_trivia(self.source, self.pos, self.dialect);
+            let Some(ch) = self.peek() else {
+                break;
+            };
+            let start = self.pos;
+            if ch == '\'' {
+                let content = self.quoted('\'');
+                self.push(Token::StringLiteral(content), start);
+            } else if ch == '`' {
+                let content = self.quoted('`');
+                self.push(Token::QuotedIdentifier(content), start);
+                self.name_end = Some(self.pos);
+            } else if is_word_char(ch) {
+                let word = self.word();
+                if self.peek() == Some('.') {
+                    self.dot(Some(word), start);
+                } else {
+                    self.push(word_token(word), start);
+                }
+            } else if ch == '.' {
+                self.dot(None, start);
+            } else {
+                self.symbol(ch);
             }
         }
-        let word = if last_char.is_some() {
-            &raw_word[..raw_word.len()-1]
-        } else {
-            raw_word
-        };
-        // 如果 word 为空，则跳过
-        if word.is_empty() {
-            if let Some(t) = last_char {
-                tokens.push(t);
+    }
+
+    // 扫描被 quote 包裹的内容，返回还原成对引号和转义后的内容。字符串和反引号标识符内的注释符号、
+    // 标点和空白都原样保留。未闭合时延伸到输入结尾，记录词法错误
+    fn quoted(&mut self, quote: char) -> String {
+        let start = self.pos;
+        // MySQL 字符串中反斜杠转义下一个字符
+        let backslash = quote == '\'' && self.dialect == Dialect::MySql;
+        let mut content = String::new();
+        self.pos += quote.len_utf8();
+        while let Some(ch) = self.peek() {
+            self.pos += ch.len_utf8();
+            if ch == quote {
+                // 两个连续的引号表示一个引号
+                if self.peek() != Some(quote) {
+                    return content;
+                }
+                self.pos += quote.len_utf8();
+                content.push(quote);
+            } else if let Some(next) = self.peek().filter(|_| backslash && ch == '\\') {
+                self.pos += next.len_utf8();
+                // LIKE 模式中的 \% 和 \_ 保留反斜杠
+                if next == '%' || next == '_' {
+                    content.push('\\');
+                }
+                content.push(unescape(next));
+            } else {
+                content.push(ch);
             }
-            continue; // 跳过空单词
-        }
-        // 如果能作为数据类型识别，则直接处理
-        if let Some(t) = try_parse_data_type(word) {
-            tokens.push(t);
-        }
-        // 关键字判断（忽略大小写）
-        else if KEYWORDS.contains(&word.to_uppercase()) {
-            tokens.push(Token::Keyword(word.to_string()));
         }
-        // 数字字面量（仅简单判断所有字符均为数字）
-        else if word.chars().all(|c| c.is_ascii_digit()) {
-            tokens.push(Token::NumericLiteral(word.to_string()));
+        if self.error.is_none() {
+            self.error = Some(LexError::unterminated(self.source, start));
         }
-        // 字符串字面量（简单检查是否以单引号包裹）
-        else if word.starts_with('\'') && word.ends_with('\'') && word.len() >= 2 {
-            let inner = &word[1..word.len()-1];
-            // 将特殊标记 "___" 替换回空格,将 "---" 替换回逗号
-            let restored_inner = inner
-                .replace("___", " ")
-                .replace("''", "'")
-                .replace("---", ",");
-            tokens.push(Token::StringLiteral(restored_inner.to_string()));
-        }
-        // 操作符判断：如果该单词正好匹配预定义操作符之一
-        else if OPERATOR_SET.contains(&word) {
-            tokens.push(Token::Operator(word.to_string()));
+        content
+    }
+
+    // 从当前位置开始连续的单词字符
+    fn word_chars(&mut self) -> &str {
+        let start = self.pos;
+        while let Some(ch) = self.peek().filter(|ch| is_word_char(*ch)) {
+            self.pos += ch.len_utf8();
         }
-        // 标点符号：如果单词是单个字符且在标点符号集合中
-        else if word.len() == 1 && PUNCTUATORS.contains(&word.chars().next().unwrap()) {
-            tokens.push(Token::Punctuator(word.chars().next().unwrap()));
-        } 
-        // 标识符：如果单词是以反引号包裹的标识符
-        // 例如 `table_name` 或 `column_name`
-        else if word.starts_with('`') && word.ends_with('`') && word.len() >= 2 {
-            let inner = &word[1..word.len()-1];
-            tokens.push(Token::Identifier(inner.to_string()));
-        } 
-        // 默认处理为标识符
-        else {
-            let parsed_tokens = parse_identifier(word);
-            for token in parsed_tokens {
-                tokens.push(token);
+        &self.source[start..self.pos]
+    }
+
+    // 扫描一个单词，1e、5E 之后是带符号的指数时，把符号并入单词，例如 2E-5
+    fn word(&mut self) -> String {
+        let start = self.pos;
+        loop {
+            self.word_chars();
+            let word = &self.source[start..self.pos];
+            let rest = &self.source[self.pos..];
+            let signed_exponent = word
+                .strip_suffix(['e', 'E'])
+                .is_some_and(|mantissa| !mantissa.is_empty() && mantissa.chars().all(|c| c.is_ascii_digit()))
+                && rest.starts_with(['+', '-'])
+                && ascii_digits(&rest[1..]) > 0;
+            if !signed_exponent {
+                return word.to_string();
             }
-        }
-        if let Some(t) = last_char {
-            tokens.push(t);
+            self.pos += 1;
         }
     }
 
-    tokens
-}
-
-
-// 
-fn parse_single_identifier(identifier: &str) -> Vec<Token> {
-    let mut tokens = Vec::new();
-    let mut acc = String::new();
-    let mut chars = identifier.chars().peekable();
-    
-    // 添加一个状态变量，用于跟踪是否在反引号内
-    let mut in_backticks = false;
-    // 添加一个状态变量，用于跟踪是否在单引号内
-    let mut in_quotes = false;
-    // 用于存储反引号内的内容
-    let mut backtick_content = String::new();
-    // 用于存储单引号内的内容
-    let mut quote_content = String::new();
-
-    while let Some(ch) = chars.next() {
-        // 
-        if ch == '\'' {
-            if in_quotes {
-                // 结束引号
-                in_quotes = false;
-                tokens.push(Token::StringLiteral(quote_content.clone()));
-                quote_content.clear();
-            } else {
-                // 开始引号
-                if !acc.is_empty() {
-                    // 处理之前的字符
-                    let token = if KEYWORDS.contains(&acc.to_uppercase()) {
-                        Token::Keyword(acc.clone())
-                    } else if acc.chars().all(|c| c.is_ascii_digit()) {
-                        Token::NumericLiteral(acc.clone())
-                    } else {
-                        Token::Identifier(acc.clone())
-                    };
-                    tokens.push(token);
-                    acc.clear();
+    // 当前位置是点号，word 是紧挨在点号之前的单词
+    //
+    // 点号之前是名称时组成限定标识符，例如 mydb.users、`mydb`.users、a.b.c，
+    // 之前是整数或者什么都没有时是小数，例如 1.5、.5、5.
+    fn dot(&mut self, word: Option<String>, start: usize) {
+        let dot = self.pos;
+        let (start, mut parts) = match word {
+            Some(word) if word.chars().all(|c| c.is_ascii_digit()) => return self.decimal(word, start),
+            Some(word) => (start, vec![Ident::new(word)]),
+            None if self.name_end == Some(dot) => {
+                let span = self.spans.pop().unwrap_or(Span::new(dot, dot));
+                match self.tokens.pop() {
+                    Some(Token::QuotedIdentifier(part)) => (span.start, vec![Ident::with_quote('`', part)]),
+                    Some(Token::QualifiedIdentifier(parts)) => (span.start, parts),
+                    _ => unreachable!("name_end only follows quoted or qualified identifiers"),
                 }
-                in_quotes = true;
             }
-        } else if in_quotes {
-            // 如果在引号内，则累积字符
-            quote_content.push(ch);
+            None => return self.decimal(String::new(), start),
+        };
+
+        // 点号后的一段，这一段也可能用反引号包裹
+        self.pos += 1;
+        let part = match self.peek() {
+            Some('`') => Some(Ident::with_quote('`', self.quoted('`'))),
+            Some(ch) if is_word_char(ch) => Some(Ident::new(self.word_chars().to_string())),
+            _ => None,
+        };
+        if let Some(part) = part.filter(|part| !part.value.is_empty()) {
+            parts.push(part);
+            self.push(Token::QualifiedIdentifier(parts), start);
+            self.name_end = Some(self.pos);
+            return;
         }
-        // 检测反引号
-        else if ch == '`' {
-            if in_backticks {
-                // 如果已经在反引号内，则这是结束反引号
-                in_backticks = false;
-                // 将反引号内的内容作为一个标识符添加
-                tokens.push(Token::Identifier(backtick_content.clone()));
-                backtick_content.clear();
-            } else {
-                // 如果不在反引号内，则这是开始反引号
-                // 先处理之前可能累积的字符
-                if !acc.is_empty() {
-                    let token = if KEYWORDS.contains(&acc.to_uppercase()) {
-                        Token::Keyword(acc.clone())
-                    } else if acc.chars().all(|c| c.is_ascii_digit()) {
-                        Token::NumericLiteral(acc.clone())
-                    } else {
-                        Token::Identifier(acc.clone())
-                    };
-                    tokens.push(token);
-                    acc.clear();
-                }
-                in_backticks = true;
-            }
-        } else if in_backticks {
-            // 如果在反引号内，则累积字符
-            backtick_content.push(ch);
-        } else if ch.is_alphanumeric() || ch == '_' {
-            // 正常的标识符字符累积
-            acc.push(ch);
-        } else if ch == '.' {
-            // 保存之前累积的标识符作为限定符
-            let qualifier = acc.clone();
-            acc.clear();
-
-            // 收集点号后的标识符
-            while let Some(&next_ch) = chars.peek() {
-                if next_ch.is_alphanumeric() || next_ch == '_' {
-                    chars.next();
-                    acc.push(next_ch);
-                } else {
-                    break;
-                }
-            }
-            
-            // 如果点号前后内容均为数字，则解析为浮点数
-            if qualifier.is_empty() || qualifier.chars().all(|c| c.is_ascii_digit())  {
-                // 构建完整的浮点数字符串
-                let float_str = format!("{}.{}", qualifier, acc);
-                tokens.push(Token::NumericLiteral(float_str));
-                acc.clear();
-            }
-            // 否则，如果点号后有内容，创建限定标识符
-            else if !acc.is_empty() {
-                tokens.push(Token::QualifiedIdentifier {
-                    qualifier,
-                    name: acc.clone()
-                });
-                acc.clear();
-            } else {
-                // 处理错误情况：点号后没有标识符
-                tokens.push(Token::Identifier(qualifier));
-                tokens.push(Token::Punctuator('.'));
+
+        // 点号后没有标识符，例如 t.*，保留限定符和点号
+        let qualifier = if parts.len() == 1 {
+            let ident = parts.remove(0);
+            match ident.quote_style {
+                Some(_) => Token::QuotedIdentifier(ident.value.into()),
+                None => Token::Identifier(ident.value.into()),
             }
         } else {
-            // 处理积累的普通标识符
-            if !acc.is_empty() {
-                let token = if KEYWORDS.contains(&acc.to_uppercase()) {
-                    Token::Keyword(acc.clone())
-                } else if acc.chars().all(|c| c.is_ascii_digit()) {
-                    Token::NumericLiteral(acc.clone())
-                } else {
-                    Token::Identifier(acc.clone())
-                };
-                tokens.push(token);
-                acc.clear();
-            }
-            
-            // 处理标点符号和操作符
-            if PUNCTUATORS.contains(&ch) {
-                tokens.push(Token::Punctuator(ch));
-            } else {
-                let op_str = ch.to_string();
-                if OPERATOR_SET.contains(&op_str.as_str()) {
-                    tokens.push(Token::Operator(op_str));
-                } else if !ch.is_whitespace() {
-                    tokens.push(Token::Identifier(op_str));
-                }
+            Token::QualifiedIdentifier(parts)
+        };
+        self.tokens.push(qualifier);
+        self.spans.push(Span::new(start, dot));
+        self.push(Token::Punctuator('.'), dot);
+    }
+
+    // 小数，integer 为点号之前的整数部分。小数部分可以带指数，例如 1.5E-3，指数后必须有数字。
+    // 前后都没有数字时是单独的点号，之后紧跟的字母作为新的单词处理
+    fn decimal(&mut self, integer: String, start: usize) {
+        let dot = self.pos;
+        self.pos += 1;
+        self.pos += ascii_digits(&self.source[self.pos..]);
+        if let Some(exponent) = self.source[self.pos..].strip_prefix(['e', 'E']) {
+            let sign = usize::from(exponent.starts_with(['+', '-']));
+            let digits = ascii_digits(&exponent[sign..]);
+            if digits > 0 {
+                self.pos += 1 + sign + digits;
             }
         }
+        let fraction = &self.source[dot + 1..self.pos];
+        if integer.is_empty() && fraction.is_empty() {
+            self.push(Token::Punctuator('.'), dot);
+        } else {
+            let number = format!("{}.{}", integer, fraction);
+            self.push(Token::NumericLiteral(number), start);
+        }
     }
-    
-    // 处理最后可能剩余的字符
-    if !acc.is_empty() {
-        let token = if KEYWORDS.contains(&acc.to_uppercase()) {
-            Token::Keyword(acc)
-        } else if acc.chars().all(|c| c.is_ascii_digit()) {
-            Token::NumericLiteral(acc)
+
+    // 操作符和标点符号，两个字符的操作符优先，例如 @row:=@row+1 中的 :=
+    fn symbol(&mut self, ch: char) {
+        let start = self.pos;
+        let rest = &self.source[start..];
+        if let Some(op) = OPERATOR_SET.iter().find(|op| op.len() == 2 && rest.starts_with(**op)) {
+            self.pos += op.len();
+            self.push(Token::Operator(op.to_string()), start);
+            return;
+        }
+        self.pos += ch.len_utf8();
+        let op = ch.to_string();
+        let token = if PUNCTUATORS.contains(&ch) {
+            Token::Punctuator(ch)
+        } else if OPERATOR_SET.contains(&op.as_str()) {
+            Token::Operator(op)
         } else {
-            Token::Identifier(acc)
+            // 其它字符各自作为一个标识符，例如 @、?、$
+            Token::Identifier(op)
         };
-        tokens.push(token);
+        self.push(token, start);
     }
-    
-    // 确保任何未闭合的反引号内容也被处理
-    if in_backticks && !backtick_content.is_empty() {
-        // 可以选择报错或者将未闭合的反引号内容作为普通标识符处理
-        tokens.push(Token::Identifier(backtick_content));
+}
+
+/// 词法错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LexError {
+    /// 未闭合的字符串（quote 为 '\''）或反引号标识符（quote 为 '`'），
+    /// span 从开始的引号到输入结尾，line 和 column 从1开始
+    Unterminated { quote: char, span: Span, line: usize, column: usize },
+}
+
+impl LexError {
+    fn unterminated(source: &str, start: usize) -> Self {
+        let quote = source[start..].chars().next().unwrap_or('\'');
+        let before = &source[..start];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        LexError::Unterminated { quote, span: Span::new(start, source.len()), line, column }
+    }
+
+    /// 出错位置在原始SQL中的字节范围
+    pub fn span(&self) -> Span {
+        match self {
+            LexError::Unterminated { span, .. } => *span,
+        }
     }
-    
-    tokens
 }
 
-/// 解析标识符，处理可能的关键字、数字和操作符。
-/// 该函数会将输入字符串拆分为多个 Token。
-/**
- * @param identifier: 输入的未处理的标识符字符串,可能包含关键字、数字和操作符
- * @return: 返回一个 Token 向量，包含解析后的标识符、关键字、数字和操作符
- * @note: 该函数会将输入字符串拆分为多个 Token，处理可能的关键字、数字和操作符。
- */
-fn parse_identifier(identifier: &str) -> Vec<Token> {
-    // 对 identifier 进行预处理，给部分符号增加空格
-    let identifier = identifier
-        .replace("(", " ( ")
-        .replace(")", " ) ")
-        .replace(",", " , ")
-        .replace(";", " ; ");
-    let mut tokens = Vec::new();
-    for word in identifier.split_whitespace() {
-        if word.is_empty() {
-            continue; // 跳过空单词
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LexError::Unterminated { quote, line, column, .. } => {
+                let what = if *quote == '`' { "quoted identifier" } else { "string literal" };
+                write!(f, "Unterminated {} starting at line {} col {}", what, line, column)
+            }
         }
-        // 处理可能的标识符、关键字、数字和操作符
-        let parsed_tokens = parse_single_identifier(word);
-        for token in parsed_tokens {
-            tokens.push(token);
+    }
+}
+
+impl Error for LexError {}
+
+/// token 在原始SQL中的字节范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Span { start, end }
+    }
+}
+
+// 跳过空白和注释，返回下一个有效字符的位置
+fn skip_trivia(source: &str, mut pos: usize, dialect: Dialect) -> usize {
+    loop {
+        let rest = &source[pos..];
+        let trimmed = rest.trim_start();
+        pos += rest.len() - trimmed.len();
+        match comment_end(source, pos, dialect) {
+            Some(end) => pos = end,
+            None => return pos,
+        }
+    }
+}
+
+/// 规范化token的大小写，得到与书写风格无关的token序列，用于语句指纹和缓存
+///
+/// 关键字和类型名转为大写，未加引号的标识符（包括限定标识符中未加引号的各段、变量名）转为小写。
+/// 作为标识符使用的非保留字和类型名与关键字一样转为大写。加了引号的标识符默认保持不变：
+/// MySQL 在 lower_case_table_names=0 时表名区分大小写，PostgreSQL 中加引号的标识符总是区分大小写。
+/// fold_quoted 为 true 时引号内同样转为小写，引号保留，适用于确定标识符不区分大小写的场合。
+///
+/// 只改变 ASCII 字母的大小写，token 与原来一一对应，[`tokenize_with_spans`] 得到的位置仍然有效。
+/// 引号风格的统一在AST上进行，见 `rewrite::normalize_quotes`。
+pub fn normalize_case(tokens: &mut [Token], fold_quoted: bool) {
+    for token in tokens {
+        match token {
+            Token::Keyword { text, .. } => text.make_ascii_uppercase(),
+            Token::DataType(name) => name.make_ascii_uppercase(),
+            Token::Identifier(ident) => ident.make_ascii_lowercase(),
+            Token::QuotedIdentifier(ident) if fold_quoted => ident.make_ascii_lowercase(),
+            Token::QualifiedIdentifier(parts) => {
+                for part in parts.iter_mut().filter(|part| part.quote_style.is_none() || fold_quoted) {
+                    part.value = part.value.to_ascii_lowercase().into();
+                }
+            }
+            _ => {}
         }
     }
-    tokens
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
+    // 每个token在SQL中对应的原文
+    fn token_texts(sql: &str) -> Vec<&str> {
+        tokenize_with_spans(sql, Dialect::MySql).1.into_iter().map(|span| &sql[span.start..span.end]).collect()
+    }
+
     #[test]
     fn test_preprocess_input_block_comment() {
         let input = "SELECT * FROM users; /* block comment spanning multiple lines\ncontinued comment */";
@@ -394,20 +584,140 @@ mod test {
         assert_eq!(preprocess_input(input), expected);
     }
 
+    #[test]
+    fn test_preprocess_input_comment_rules() {
+        // -- 后面没有空白时不是注释
+        assert_eq!(preprocess_input("SELECT 5--1 FROM t"), "SELECT 5--1 FROM t");
+        assert_eq!(preprocess_input("SELECT 5 --\nFROM t --"), "SELECT 5 FROM t");
+        assert_eq!(preprocess_input("# header\nSELECT a#tail\n, b FROM t"), "SELECT a , b FROM t");
+        // PostgreSQL 中 # 不是注释
+        assert_eq!(preprocess_input_with_dialect("SELECT a # b", Dialect::Postgres), "SELECT a # b");
+        assert_eq!(preprocess_input_with_dialect("SELECT a # b", Dialect::BigQuery), "SELECT a");
+        // 字符串内的空白不再被压缩
+        assert_eq!(tokenize("SELECT 'a  b'")[1], Token::StringLiteral("a  b".to_string()));
+        assert_eq!(tokenize("SELECT '''x'''")[1], Token::StringLiteral("'x'".to_string()));
+    }
+
+    #[test]
+    fn test_nested_comment_and_string() {
+        assert_eq!(preprocess_input("SELECT /* a /* b */ c */ 1"), "SELECT 1");
+        assert_eq!(preprocess_input("SELECT 1 /* a /* b */"), "SELECT 1");
+
+        // 字符串和反引号标识符中的注释符号保持原样
+        let sql = "SELECT '-- not a comment', '/* x */', `#id` FROM t /* 注释 */ WHERE a = '#1'";
+        let tokens = tokenize(sql);
+        assert_eq!(tokens[1], Token::StringLiteral("-- not a comment".to_string()));
+        assert_eq!(tokens[3], Token::StringLiteral("/* x */".to_string()));
+        assert_eq!(tokens[5], Token::QuotedIdentifier("#id".to_string()));
+        assert_eq!(tokens.last(), Some(&Token::StringLiteral("#1".to_string())));
+
+        assert_eq!(token_texts(sql).last(), Some(&"'#1'"));
+    }
+
+    #[test]
+    fn test_string_literal_opacity() {
+        let contents = [
+            "a = b AND c", "a,b", "(", ")", "x);", ";", "--", "-- x", "/* x */", "# x", "`", "'", "''",
+            "___", "---", "a___b---c", "SELECT * FROM t", " lead", "trail ", "a\tb\nc", "1.5", ".", "t.*",
+            "@x := 1", "it's", "",
+        ];
+        let contexts = [
+            "SELECT {} FROM t",
+            "SELECT CONCAT({},x), y",
+            "SELECT x FROM t WHERE y IN({},{})",
+            "INSERT INTO t VALUES({});",
+            "UPDATE t SET a={}, b=1",
+        ];
+        // MySQL 中反斜杠转义引号和反斜杠本身
+        let escaped = [(r"'a\'b'", "a'b"), (r"'a\\'", r"a\"), (r"'\''", "'"), (r"'\'\\'", r"'\")];
+        let literals = contents
+            .iter()
+            .map(|content| (format!("'{}'", content.replace('\'', "''")), *content))
+            .chain(escaped.iter().map(|(literal, content)| (literal.to_string(), *content)));
+        for (literal, content) in literals {
+            for context in contexts {
+                let sql = context.replace("{}", &literal);
+                let tokens = tokenize(&sql);
+                let strings: Vec<&Token> = tokens.iter().filter(|t| matches!(t, Token::StringLiteral(_))).collect();
+                let expected = Token::StringLiteral(content.to_string());
+                assert_eq!(strings, vec![&expected; context.matches("{}").count()], "{}", sql);
+                // 字符串两侧的 token 保持不变
+                let placeholder = tokenize(&context.replace("{}", "'s'"));
+                assert_eq!(tokens.len(), placeholder.len(), "{}", sql);
+                for (token, text) in tokens.iter().zip(token_texts(&sql)) {
+                    if matches!(token, Token::StringLiteral(_)) {
+                        assert_eq!(text, literal, "{}", sql);
+                    }
+                }
+            }
+        }
+        // 相邻的两个字符串不是一个字符串
+        assert_eq!(tokenize("SELECT 'a'='b'")[1..], [
+            Token::StringLiteral("a".to_string()),
+            Token::Operator("=".to_string()),
+            Token::StringLiteral("b".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_backslash_escapes() {
+        let string = |sql: &str, dialect| tokenize_with_dialect(sql, dialect)[1].clone();
+        assert_eq!(string(r"SELECT 'a\'b'", Dialect::MySql), Token::StringLiteral("a'b".to_string()));
+        assert_eq!(string(r"SELECT 'a\tb\nc\0\Z\q'", Dialect::MySql), Token::StringLiteral("a\tb\nc\0\u{1a}q".to_string()));
+        // LIKE 模式中的 \% 和 \_ 保留反斜杠
+        assert_eq!(string(r"SELECT 'a\%\_'", Dialect::MySql), Token::StringLiteral(r"a\%\_".to_string()));
+        // 其他方言中反斜杠是普通字符
+        assert_eq!(string(r"SELECT 'a\'", Dialect::Postgres), Token::StringLiteral(r"a\".to_string()));
+        // 转义的引号不结束字符串
+        assert!(try_tokenize(r"SELECT 'a\'").is_err());
+        assert!(try_tokenize_with_dialect(r"SELECT 'a\'", Dialect::Postgres).is_ok());
+    }
+
+    #[test]
+    fn test_unterminated_quotes() {
+        // 未闭合的字符串延伸到结尾，其中的注释符号和标点也属于字符串
+        let sql = "SELECT a,\n  'abc, d -- x;";
+        assert_eq!(tokenize(sql).last(), Some(&Token::StringLiteral("abc, d -- x;".to_string())));
+        let err = try_tokenize(sql).unwrap_err();
+        assert_eq!(err, LexError::Unterminated { quote: '\'', span: Span::new(12, sql.len()), line: 2, column: 3 });
+        assert_eq!(err.to_string(), "Unterminated string literal starting at line 2 col 3");
+
+        let sql = "SELECT `my col FROM t";
+        assert_eq!(tokenize(sql)[1], Token::QuotedIdentifier("my col FROM t".to_string()));
+        let err = try_tokenize(sql).unwrap_err();
+        assert_eq!(err.to_string(), "Unterminated quoted identifier starting at line 1 col 8");
+        assert_eq!(tokenize_with_spans(sql, Dialect::MySql).1[1], err.span());
+
+        // 成对的引号和闭合的引号不是错误
+        assert!(try_tokenize("SELECT 'it''s', `a``b`").is_ok());
+        assert!(try_tokenize("SELECT 'x''").is_err());
+        assert!(try_tokenize("SELECT 1 -- it's").is_ok());
+    }
+
+    #[test]
+    fn test_dot_numbers() {
+        let tokens = tokenize("SELECT .5, 5., 1.5abc, .5e-2 FROM t WHERE x=.25");
+        let numbers: Vec<&Token> = tokens.iter().filter(|t| matches!(t, Token::NumericLiteral(_))).collect();
+        let expected = [".5", "5.", "1.5", ".5e-2", ".25"].map(|n| Token::NumericLiteral(n.to_string()));
+        assert_eq!(numbers, expected.iter().collect::<Vec<_>>());
+        // 小数后紧跟的字母是新的单词
+        assert!(tokens.contains(&Token::Identifier("abc".to_string())));
+    }
+
     #[test]
     fn test_parse_identifier() {
         let input = "value=500";
         let expected = vec![
-            Token::Identifier("value".to_string()),
+            Token::Keyword { keyword: Keyword::VALUE, text: "value".to_string() },
             Token::Operator("=".to_string()),
             Token::NumericLiteral("500".to_string()),
         ];
-        let result = parse_identifier(input);
+        let result = tokenize(input);
         assert_eq!(result, expected);
 
         let input = "values(1,2,3)";
         let expected = vec![
-            Token::Keyword("values".to_string()),
+            Token::Keyword { keyword: Keyword::VALUES, text: "values".to_string() },
             Token::Punctuator('('),
             Token::NumericLiteral("1".to_string()),
             Token::Punctuator(','),
@@ -416,10 +726,63 @@ mod test {
             Token::NumericLiteral("3".to_string()),
             Token::Punctuator(')'),
         ];
-        let result = parse_identifier(input);
+        let result = tokenize(input);
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_qualified_identifier() {
+        let tokens = tokenize("SELECT mydb.users.id, `mydb`.`order`.total, t.*, 3.14 FROM catalog.mydb.users t");
+        assert_eq!(tokens, vec![
+            Token::Keyword { keyword: Keyword::SELECT, text: "SELECT".to_string() },
+            Token::QualifiedIdentifier(vec![Ident::new("mydb"), Ident::new("users"), Ident::new("id")]),
+            Token::Punctuator(','),
+            Token::QualifiedIdentifier(vec![
+                Ident::with_quote('`', "mydb"),
+                Ident::with_quote('`', "order"),
+                Ident::new("total"),
+            ]),
+            Token::Punctuator(','),
+            Token::Identifier("t".to_string()),
+            Token::Punctuator('.'),
+            Token::Operator("*".to_string()),
+            Token::Punctuator(','),
+            Token::NumericLiteral("3.14".to_string()),
+            Token::Keyword { keyword: Keyword::FROM, text: "FROM".to_string() },
+            Token::QualifiedIdentifier(vec![Ident::new("catalog"), Ident::new("mydb"), Ident::new("users")]),
+            Token::Identifier("t".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_backquoted_identifier() {
+        let sql = "SELECT `my table`.`weird.column`, `a``b`, db.`t 1`, `c(d), e;` FROM `my  table` WHERE `it's` = 'x'";
+        let tokens = tokenize(sql);
+        assert_eq!(tokens, vec![
+            Token::Keyword { keyword: Keyword::SELECT, text: "SELECT".to_string() },
+            Token::QualifiedIdentifier(vec![Ident::with_quote('`', "my table"), Ident::with_quote('`', "weird.column")]),
+            Token::Punctuator(','),
+            Token::QuotedIdentifier("a`b".to_string()),
+            Token::Punctuator(','),
+            Token::QualifiedIdentifier(vec![Ident::new("db"), Ident::with_quote('`', "t 1")]),
+            Token::Punctuator(','),
+            Token::QuotedIdentifier("c(d), e;".to_string()),
+            Token::Keyword { keyword: Keyword::FROM, text: "FROM".to_string() },
+            Token::QuotedIdentifier("my  table".to_string()),
+            Token::Keyword { keyword: Keyword::WHERE, text: "WHERE".to_string() },
+            Token::QuotedIdentifier("it's".to_string()),
+            Token::Operator("=".to_string()),
+            Token::StringLiteral("x".to_string()),
+        ]);
+        // 成对的反引号也可以出现在限定标识符中
+        assert_eq!(
+            tokenize("`x``y`.`z```"),
+            vec![Token::QualifiedIdentifier(vec![Ident::with_quote('`', "x`y"), Ident::with_quote('`', "z`")])]
+        );
+        let texts = token_texts(sql);
+        assert_eq!((texts[1], texts[3], texts[9]), ("`my table`.`weird.column`", "`a``b`", "`my  table`"));
+    }
+
     #[test]
     fn test_tokenize2() {
         let sql = r#"
@@ -480,4 +843,66 @@ mod test {
         let tokens = tokenize(sql);
         dbg!(tokens);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_data_type_arguments() {
+        // 类型参数总是拆分为独立的token，与是否有空格、引号内是否有括号和逗号无关
+        let p = Token::Punctuator;
+        assert_eq!(
+            tokenize("DECIMAL(10, 2)"),
+            [Token::DataType("DECIMAL".into()), p('('), Token::NumericLiteral("10".into()), p(','),
+                Token::NumericLiteral("2".into()), p(')')]
+        );
+        assert_eq!(tokenize("DECIMAL(10,2)"), tokenize("DECIMAL (10, 2)"));
+        assert_eq!(
+            tokenize("ENUM('a)b', 'c,d'))"),
+            [Token::DataType("ENUM".into()), p('('), Token::StringLiteral("a)b".into()), p(','),
+                Token::StringLiteral("c,d".into()), p(')'), p(')')]
+        );
+    }
+
+    #[test]
+    fn test_token_spans() {
+        let sql = "SELECT `id`, name -- comment\nFROM mydb.users WHERE note = 'a, b' /* x */ AND n <= 10";
+        let (tokens, spans) = tokenize_with_spans(sql, Dialect::MySql);
+        assert_eq!(spans.len(), tokens.len());
+        assert_eq!(
+            token_texts(sql),
+            vec!["SELECT", "`id`", ",", "name", "FROM", "mydb.users", "WHERE", "note", "=", "'a, b'", "AND", "n", "<=", "10"]
+        );
+        // 反斜杠转义、多字节字符、t.* 和小数的位置
+        let sql = "SELECT t.*, '\\'', 名称, .5e-2, `a`.b FROM t";
+        assert_eq!(
+            token_texts(sql),
+            vec!["SELECT", "t", ".", "*", ",", "'\\''", ",", "名称", ",", ".5e-2", ",", "`a`.b", "FROM", "t"]
+        );
+    }
+
+    #[test]
+    fn test_normalize_case() {
+        let sql = "select `Id`, Users.NAME, `MyDb`.Users.Note FROM Users WHERE Note = 'Mixed' AND created < Date('2024-01-01')";
+        let mut tokens = tokenize(sql);
+        normalize_case(&mut tokens, false);
+        assert_eq!(tokens[0], Token::Keyword { keyword: Keyword::SELECT, text: "SELECT".to_string() });
+        // 加引号的标识符默认保持不变
+        assert_eq!(tokens[1], Token::QuotedIdentifier("Id".to_string()));
+        assert_eq!(tokens[3], Token::QualifiedIdentifier(vec![Ident::new("users"), Ident::new("name")]));
+        assert_eq!(
+            tokens[5],
+            Token::QualifiedIdentifier(vec![Ident::with_quote('`', "MyDb"), Ident::new("users"), Ident::new("note")])
+        );
+        assert!(tokens.contains(&Token::StringLiteral("Mixed".to_string())));
+        // token 一一对应，位置不变
+        assert_eq!(tokens.len(), token_texts(sql).len());
+        assert_eq!(token_texts(sql)[5], "`MyDb`.Users.Note");
+
+        // 调用方确定标识符不区分大小写时，引号内同样转为小写
+        let mut tokens = tokenize(sql);
+        normalize_case(&mut tokens, true);
+        assert_eq!(tokens[1], Token::QuotedIdentifier("id".to_string()));
+        assert_eq!(
+            tokens[5],
+            Token::QualifiedIdentifier(vec![Ident::with_quote('`', "mydb"), Ident::new("users"), Ident::new("note")])
+        );
+    }
+}
@@ -0,0 +1,289 @@
+//! 语句的静态检查
+
+#[cfg(feature = "ddl")]
+use crate::ast::common::ObjectName;
+use crate::ast::select::{IntoClause, LockMode, SelectStatement};
+use crate::ast::SQLStatement;
+use crate::kerwords::Keyword;
+use crate::parser::Dialect;
+#[cfg(feature = "ddl")]
+use crate::policy::{PolicyTarget, References};
+#[cfg(feature = "ddl")]
+use crate::schema::SchemaProvider;
+use crate::token::{tokenize_with_spans, Span, Token};
+
+/// 直接写在比较中的字面量，例如 WHERE name = 'alice' 中的 'alice'
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlineLiteral {
+    /// StringLiteral 或 NumericLiteral
+    pub literal: Token,
+    /// 在原始SQL中的位置，带符号的数字包含前面的 - 号
+    pub span: Span,
+}
+
+/// 查找出现在比较位置上的字面量，用于发现手工拼接、没有参数化的SQL
+///
+/// 比较位置包括比较运算符和 LIKE 的两侧、BETWEEN ... AND ... 的边界以及 IN 列表中的元素。
+/// UPDATE ... SET col = 'x' 的赋值同样使用 =，也会被报告。LIMIT、VALUES 等位置的字面量不算在内；?、:name、$1 之类的占位符也不会被报告。
+pub fn find_inline_literals(sql: &str) -> Vec<InlineLiteral> {
+    let (tokens, spans) = tokenize_with_spans(sql, Dialect::MySql);
+    let mut found = Vec::new();
+    // 括号栈，true 表示 IN 列表的括号
+    let mut parens: Vec<bool> = Vec::new();
+
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Punctuator('(') => {
+                parens.push(i > 0 && is_keyword(&tokens[i - 1], Keyword::IN));
+                continue;
+            }
+            Token::Punctuator(')') => {
+                parens.pop();
+                continue;
+            }
+            Token::StringLiteral(_) | Token::NumericLiteral(_) => {}
+            _ => continue,
+        }
+        // $1 形式的占位符
+        if i > 0 && tokens[i - 1] == Token::Identifier("$".to_string()) {
+            continue;
+        }
+        // 带符号的数字从符号开始
+        let start = match i.checked_sub(1).map(|p| &tokens[p]) {
+            Some(Token::Operator(op)) if (op == "-" || op == "+") && is_operand_start(&tokens[..i - 1]) => i - 1,
+            _ => i,
+        };
+        let before = &tokens[..start];
+        let prev = before.last();
+        let in_comparison = prev.is_some_and(is_comparison)
+            || prev.is_some_and(|t| is_keyword(t, Keyword::BETWEEN))
+            // BETWEEN low AND high 中的 high
+            || (prev.is_some_and(|t| is_keyword(t, Keyword::AND))
+                && before.len() >= 3
+                && is_keyword(&before[before.len() - 3], Keyword::BETWEEN))
+            || (parens.last() == Some(&true) && matches!(prev, Some(Token::Punctuator('(' | ','))))
+            || tokens.get(i + 1).is_some_and(is_comparison);
+        if in_comparison {
+            let span = Span::new(spans[start].start, spans[i].end);
+            found.push(InlineLiteral { literal: token.clone(), span });
+        }
+    }
+    found
+}
+
+/// 比较中是否只使用了占位符，没有直接写入的字面量
+pub fn is_parameterized(sql: &str) -> bool {
+    find_inline_literals(sql).is_empty()
+}
+
+/// 只读检查发现的问题
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReadOnlyViolation {
+    /// 修改数据或表结构的语句，statement 为语句类型，例如 "UPDATE"
+    Mutation { statement: &'static str },
+    /// SELECT ... INTO OUTFILE / DUMPFILE 写入服务器上的文件
+    WritesFile { path: String },
+    /// SELECT ... INTO @var 给用户变量赋值
+    VariableAssignment { variables: Vec<String> },
+    /// FOR UPDATE、FOR SHARE 等加锁读取
+    Locking { mode: LockMode },
+}
+
+/// 检查语句是否只读，用于限制临时的分析查询
+///
+/// 返回发现的所有问题，没有问题时返回 Ok。
+pub fn assert_read_only(stmt: &SQLStatement) -> Result<(), Vec<ReadOnlyViolation>> {
+    let mut violations = Vec::new();
+    match stmt {
+        SQLStatement::Select(select) => check_select(select, &mut violations),
+        SQLStatement::Show(_) => {}
+        SQLStatement::Set(set) => {
+            let variables = set.assignments.iter().map(|assignment| assignment.variable.clone()).collect();
+            violations.push(ReadOnlyViolation::VariableAssignment { variables });
+        }
+        SQLStatement::Insert(_) => violations.push(ReadOnlyViolation::Mutation { statement: "INSERT" }),
+        SQLStatement::Update(_) => violations.push(ReadOnlyViolation::Mutation { statement: "UPDATE" }),
+        SQLStatement::Delete(_) => violations.push(ReadOnlyViolation::Mutation { statement: "DELETE" }),
+        #[cfg(feature = "ddl")]
+        SQLStatement::Create(_) => violations.push(ReadOnlyViolation::Mutation { statement: "CREATE" }),
+        #[cfg(feature = "ddl")]
+        SQLStatement::Alter(_) => violations.push(ReadOnlyViolation::Mutation { statement: "ALTER" }),
+    }
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+fn check_select(select: &SelectStatement, violations: &mut Vec<ReadOnlyViolation>) {
+    match &select.into {
+        Some(IntoClause::Outfile { path, .. }) | Some(IntoClause::Dumpfile(path)) => {
+            violations.push(ReadOnlyViolation::WritesFile { path: path.clone() });
+        }
+        Some(IntoClause::Variables(variables)) => {
+            violations.push(ReadOnlyViolation::VariableAssignment { variables: variables.clone() });
+        }
+        None => {}
+    }
+    if let Some(lock) = &select.lock {
+        violations.push(ReadOnlyViolation::Locking { mode: lock.mode });
+    }
+}
+
+/// 表结构检查发现的问题
+#[cfg(feature = "ddl")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaViolation {
+    /// 目录中没有这张表
+    UnknownTable(ObjectName),
+    /// 表中没有这一列
+    UnknownColumn { table: ObjectName, column: String },
+}
+
+/// 按表结构检查语句引用的表，以及 INSERT 列名、UPDATE/SET 赋值目标等写入的列
+///
+/// 每张不存在的表只报告一次；CREATE TABLE 创建的表本身不检查，CREATE VIEW 只检查视图查询引用的表。
+/// SELECT 和 WHERE 中的列需要先解析别名，这里不检查。
+#[cfg(feature = "ddl")]
+pub fn check_schema<P: SchemaProvider + ?Sized>(stmt: &SQLStatement, schema: &P) -> Vec<SchemaViolation> {
+    use crate::ast::create::CreateStatement;
+
+    let mut violations = Vec::new();
+    let mut refs = References::default();
+    match stmt {
+        SQLStatement::Create(CreateStatement::Table(_)) => return violations,
+        SQLStatement::Create(CreateStatement::View(view)) => view.query.collect(&mut refs),
+        _ => stmt.collect(&mut refs),
+    }
+    for table in refs.tables() {
+        let violation = SchemaViolation::UnknownTable(table.clone());
+        if schema.table(table).is_none() && !violations.contains(&violation) {
+            violations.push(violation);
+        }
+    }
+
+    // 写入的目标表和列
+    let (table, columns): (&ObjectName, Vec<&str>) = match stmt {
+        SQLStatement::Insert(insert) => {
+            let mut columns: Vec<&str> = insert.columns.iter().flatten().map(String::as_str).collect();
+            let assigned = insert.set_clause.iter().flatten().chain(insert.on_duplicate.iter().flat_map(|c| &c.updates));
+            columns.extend(assigned.map(|(column, _)| column.name().value.as_str()));
+            (&insert.table.name, columns)
+        }
+        SQLStatement::Update(update) => {
+            (&update.table.name, update.assignments.iter().map(|(column, _)| column.name().value.as_str()).collect())
+        }
+        _ => return violations,
+    };
+    if let Some(schema) = schema.table(table) {
+        for column in columns {
+            if schema.column(column).is_none() {
+                violations.push(SchemaViolation::UnknownColumn { table: table.clone(), column: column.to_string() });
+            }
+        }
+    }
+    violations
+}
+
+fn is_keyword(token: &Token, keyword: Keyword) -> bool {
+    matches!(token, Token::Keyword { keyword: k, .. } if *k == keyword)
+}
+
+// 比较运算符，<=> 会被拆成多个运算符token，最后一个同样是比较运算符
+fn is_comparison(token: &Token) -> bool {
+    match token {
+        Token::Operator(op) => matches!(op.as_str(), "=" | "!=" | "<>" | "<" | ">" | "<=" | ">="),
+        Token::Keyword { keyword: Keyword::LIKE, .. } => true,
+        Token::Identifier(word) => word.eq_ignore_ascii_case("REGEXP"),
+        _ => false,
+    }
+}
+
+// 此处开始一个新的操作数，即后面的 +/- 是一元运算符
+fn is_operand_start(before: &[Token]) -> bool {
+    match before.last() {
+        None => true,
+        Some(Token::Operator(_)) | Some(Token::Keyword { .. }) => true,
+        Some(Token::Punctuator(c)) => *c == '(' || *c == ',',
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::{Parser, StatementParser};
+
+    #[test]
+    fn test_find_inline_literals() {
+        let sql = "SELECT * FROM t WHERE a = ? AND b <> 'x' AND c IN (1, -2, LOWER('y')) AND d LIKE :name \
+                   AND e BETWEEN 1 AND 5 AND 'k' >= f AND g = $1 AND h = price * 2 LIMIT 10";
+        let found = find_inline_literals(sql);
+        let texts: Vec<&str> = found.iter().map(|literal| &sql[literal.span.start..literal.span.end]).collect();
+        assert_eq!(texts, ["'x'", "1", "-2", "1", "5", "'k'"]);
+        assert_eq!(found[0].literal, Token::StringLiteral("x".to_string()));
+        assert!(!is_parameterized(sql));
+
+        // 只使用占位符；LIMIT、VALUES 和函数参数中的字面量不算
+        assert!(is_parameterized("SELECT id FROM users WHERE name = ? AND age > :age LIMIT 10"));
+        assert!(is_parameterized("INSERT INTO logs (level, msg) VALUES (1, 'started')"));
+        assert!(is_parameterized("UPDATE users SET visits = visits + 1 WHERE id = $1"));
+    }
+
+    #[cfg(feature = "ddl")]
+    #[test]
+    fn test_check_schema() {
+        let catalog = crate::schema::Catalog::from_script("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(20))").unwrap();
+        let check = |sql: &str| check_schema(&Parser::new_from_sql(sql).parse().unwrap(), &catalog);
+        let users = ObjectName::new(["users"]);
+        assert_eq!(check("SELECT u.id FROM users u WHERE u.name = 'x'"), []);
+        assert_eq!(
+            check("SELECT * FROM users JOIN orders ON users.id = orders.uid WHERE users.id IN (SELECT uid FROM orders)"),
+            [SchemaViolation::UnknownTable(ObjectName::new(["orders"]))]
+        );
+        assert_eq!(
+            check("INSERT INTO users (id, nmae) VALUES (1, 'x') ON DUPLICATE KEY UPDATE name = 'y', age = 1"),
+            [
+                SchemaViolation::UnknownColumn { table: users.clone(), column: "nmae".to_string() },
+                SchemaViolation::UnknownColumn { table: users.clone(), column: "age".to_string() },
+            ]
+        );
+        assert_eq!(
+            check("UPDATE users SET users.NAME = 'x', email = 'y'"),
+            [SchemaViolation::UnknownColumn { table: users, column: "email".to_string() }]
+        );
+        assert_eq!(check("CREATE TABLE logs (id INT)"), []);
+    }
+
+    #[test]
+    fn test_assert_read_only() {
+        let check = |sql: &str| assert_read_only(&Parser::new_from_sql(sql).parse().unwrap());
+        assert_eq!(check("SELECT id, name FROM users WHERE age > 18 LIMIT 10"), Ok(()));
+        assert_eq!(check("SHOW TABLES"), Ok(()));
+        assert_eq!(
+            check("UPDATE users SET name = 'x'"),
+            Err(vec![ReadOnlyViolation::Mutation { statement: "UPDATE" }])
+        );
+        assert_eq!(
+            check("INSERT INTO users (name) VALUES ('x')"),
+            Err(vec![ReadOnlyViolation::Mutation { statement: "INSERT" }])
+        );
+        assert_eq!(
+            check("DELETE FROM users"),
+            Err(vec![ReadOnlyViolation::Mutation { statement: "DELETE" }])
+        );
+        assert_eq!(
+            check("SELECT * FROM users FOR UPDATE INTO OUTFILE '/tmp/u.csv'"),
+            Err(vec![
+                ReadOnlyViolation::WritesFile { path: "/tmp/u.csv".to_string() },
+                ReadOnlyViolation::Locking { mode: LockMode::Update },
+            ])
+        );
+        assert_eq!(
+            check("SELECT id INTO @id FROM users LIMIT 1"),
+            Err(vec![ReadOnlyViolation::VariableAssignment { variables: vec!["@id".to_string()] }])
+        );
+    }
+
+}